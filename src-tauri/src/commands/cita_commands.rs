@@ -4,10 +4,14 @@
 /// los anfitriones registren a sus invitados antes de que lleguen físicamente a planta,
 /// agilizando así el proceso de recepción.
 use crate::domain::errors::CitaError;
-use crate::models::cita::{CitaResponse, CreateCitaInput};
+use crate::models::cita::{
+    CitaResponse, CitaSerieResponse, CreateCitaInput, CreateCitaSerieInput, HorarioLibre,
+    IcalExportResponse, IcalImportResponse, InasistenciasAnfitrion, InvitacionResponse,
+    UpdateCitaSerieInput,
+};
 use crate::models::visitante::CreateVisitanteInput;
-use crate::services::cita_service;
 use crate::services::session::SessionState;
+use crate::services::{cita_lifecycle_service, cita_service};
 use tauri::{command, State};
 
 // --------------------------------------------------------------------------
@@ -61,6 +65,32 @@ pub async fn get_cita_by_id(
     cita_service::get_cita_by_id(id).await
 }
 
+/// [Comando Tauri] Horarios libres de un día según la capacidad del anfitrión y del área.
+///
+/// # Argumentos
+/// * `session` - Estado de la sesión.
+/// * `fecha` - Día a consultar (YYYY-MM-DD).
+/// * `anfitrion` - Anfitrión opcional a respetar.
+/// * `area_visitada` - Área opcional a respetar.
+/// * `duracion_minutos` - Duración de la cita; por defecto la configurada.
+#[command]
+pub async fn get_horarios_libres(
+    session: State<'_, SessionState>,
+    fecha: String,
+    anfitrion: Option<String>,
+    area_visitada: Option<String>,
+    duracion_minutos: Option<u32>,
+) -> Result<Vec<HorarioLibre>, CitaError> {
+    require_perm!(session, "citas:read")?;
+    cita_service::horarios_libres(
+        &fecha,
+        anfitrion.as_deref(),
+        area_visitada.as_deref(),
+        duracion_minutos,
+    )
+    .await
+}
+
 // --------------------------------------------------------------------------
 // OPERACIONES DE GESTIÓN (MUTACIONES)
 // --------------------------------------------------------------------------
@@ -87,6 +117,7 @@ pub async fn create_cita(
         cita,
         visitante_extra: visitante,
         usuario_id: user.id,
+        uid_ical: None,
     })
     .await
 }
@@ -97,7 +128,7 @@ pub async fn create_cita(
 ///
 /// # Argumentos
 /// * `session` - Estado de la sesión.
-/// * `cita_id` - ID de la cita a completar.
+/// * `cita_id` - ID de la cita a procesar.
 /// * `gafete` - Número de identificación física asignada.
 ///
 /// # Retorno
@@ -134,3 +165,180 @@ pub async fn cancelar_cita(session: State<'_, SessionState>, id: String) -> Resu
     require_perm!(session, "citas:delete", format!("Cancelando cita #{}", id))?;
     cita_service::cancelar_cita(id).await
 }
+
+/// [Comando Tauri] Genera la invitación firmada (QR + PDF) de una cita programada.
+///
+/// # Argumentos
+/// * `session` - Estado de la sesión.
+/// * `cita_id` - ID de la cita.
+///
+/// # Retorno
+/// Token firmado, ventana de validez y PDF de la invitación.
+#[command]
+pub async fn generar_invitacion_cita(
+    session: State<'_, SessionState>,
+    cita_id: String,
+) -> Result<InvitacionResponse, CitaError> {
+    require_perm!(session, "citas:create", format!("Emitiendo invitación para cita #{}", cita_id))?;
+    cita_service::generar_invitacion(cita_id).await
+}
+
+/// [Comando Tauri] Verifica el QR de una invitación y devuelve la cita para pre-llenar la visita.
+///
+/// # Argumentos
+/// * `session` - Estado de la sesión.
+/// * `token` - Contenido escaneado del QR.
+#[command]
+pub async fn verificar_invitacion_cita(
+    session: State<'_, SessionState>,
+    token: String,
+) -> Result<CitaResponse, CitaError> {
+    require_perm!(session, "citas:read")?;
+    cita_service::verificar_invitacion(&token).await
+}
+
+/// [Comando Tauri] Procesa el ingreso de una cita a partir del QR de su invitación.
+///
+/// # Argumentos
+/// * `session` - Estado de la sesión.
+/// * `token` - Contenido escaneado del QR.
+/// * `gafete` - Número de identificación física asignada.
+#[command]
+pub async fn procesar_ingreso_cita_token(
+    session: State<'_, SessionState>,
+    token: String,
+    gafete: Option<String>,
+) -> Result<CitaResponse, CitaError> {
+    let user =
+        require_perm!(session, "citas:update", "Validando llegada física con invitación firmada")?;
+    cita_service::procesar_ingreso_cita_token(&token, gafete, user.id).await
+}
+
+// --------------------------------------------------------------------------
+// SERIES RECURRENTES
+// --------------------------------------------------------------------------
+
+/// [Comando Tauri] Agenda una serie de citas recurrentes (diaria, semanal o mensual).
+///
+/// # Argumentos
+/// * `session` - Estado de la sesión.
+/// * `serie` - Datos de la primera cita y regla de recurrencia.
+/// * `visitante` - Datos opcionales del nuevo visitante.
+///
+/// # Retorno
+/// La serie con sus próximas citas generadas.
+#[command]
+pub async fn create_cita_serie(
+    session: State<'_, SessionState>,
+    serie: CreateCitaSerieInput,
+    visitante: Option<CreateVisitanteInput>,
+) -> Result<CitaSerieResponse, CitaError> {
+    let user = require_perm!(session, "citas:create", "Registrando serie de citas recurrentes")?;
+    cita_service::agendar_serie(cita_service::AgendarSerieParams {
+        serie,
+        visitante_extra: visitante,
+        usuario_id: user.id,
+    })
+    .await
+}
+
+/// [Comando Tauri] Obtiene una serie con sus próximas citas.
+#[command]
+pub async fn get_cita_serie(
+    session: State<'_, SessionState>,
+    id: String,
+) -> Result<CitaSerieResponse, CitaError> {
+    require_perm!(session, "citas:read")?;
+    cita_service::get_serie(id).await
+}
+
+/// [Comando Tauri] Edita una serie; los cambios afectan a las citas pendientes futuras.
+#[command]
+pub async fn update_cita_serie(
+    session: State<'_, SessionState>,
+    id: String,
+    cambios: UpdateCitaSerieInput,
+) -> Result<CitaSerieResponse, CitaError> {
+    require_perm!(session, "citas:update", format!("Editando serie de citas #{}", id))?;
+    cita_service::actualizar_serie(id, cambios).await
+}
+
+/// [Comando Tauri] Cancela una serie y sus citas pendientes futuras.
+///
+/// # Retorno
+/// Cantidad de citas canceladas.
+#[command]
+pub async fn cancelar_cita_serie(
+    session: State<'_, SessionState>,
+    id: String,
+) -> Result<usize, CitaError> {
+    require_perm!(session, "citas:delete", format!("Cancelando serie de citas #{}", id))?;
+    cita_service::cancelar_serie(id).await
+}
+
+// --------------------------------------------------------------------------
+// CICLO DE VIDA E INASISTENCIAS
+// --------------------------------------------------------------------------
+
+/// [Comando Tauri] Resumen de inasistencias (no-show) por anfitrión.
+///
+/// # Argumentos
+/// * `session` - Estado de la sesión.
+/// * `desde` - Fecha inicial (YYYY-MM-DD).
+/// * `hasta` - Fecha final inclusiva (YYYY-MM-DD); por defecto igual a `desde`.
+#[command]
+pub async fn get_reporte_inasistencias(
+    session: State<'_, SessionState>,
+    desde: String,
+    hasta: Option<String>,
+) -> Result<Vec<InasistenciasAnfitrion>, CitaError> {
+    require_perm!(session, "citas:read")?;
+    cita_lifecycle_service::reporte_inasistencias(&desde, hasta.as_deref()).await
+}
+
+/// [Comando Tauri] Ejecuta de inmediato el vencimiento de citas pendientes.
+///
+/// # Retorno
+/// Cantidad de citas marcadas como vencidas.
+#[command]
+pub async fn vencer_citas_ahora(session: State<'_, SessionState>) -> Result<usize, CitaError> {
+    require_perm!(session, "citas:update", "Forzando vencimiento de citas pendientes")?;
+    cita_lifecycle_service::vencer_citas_pendientes().await
+}
+
+// --------------------------------------------------------------------------
+// CALENDARIO (iCalendar)
+// --------------------------------------------------------------------------
+
+/// [Comando Tauri] Exporta la agenda a un archivo iCalendar (`.ics`).
+///
+/// # Argumentos
+/// * `session` - Estado de la sesión.
+/// * `desde` - Fecha inicial (YYYY-MM-DD).
+/// * `hasta` - Fecha final inclusiva (YYYY-MM-DD); por defecto igual a `desde`.
+/// * `anfitrion` - Limita la exportación a las citas de un anfitrión.
+/// * `target_path` - Ruta donde guardar el archivo; sin ella solo se devuelve el contenido.
+#[command]
+pub async fn exportar_citas_ical(
+    session: State<'_, SessionState>,
+    desde: String,
+    hasta: Option<String>,
+    anfitrion: Option<String>,
+    target_path: Option<String>,
+) -> Result<IcalExportResponse, CitaError> {
+    require_perm!(session, "citas:read")?;
+    cita_service::exportar_ical(&desde, hasta.as_deref(), anfitrion.as_deref(), target_path).await
+}
+
+/// [Comando Tauri] Importa citas desde el contenido de un archivo iCalendar.
+///
+/// # Retorno
+/// Citas creadas y eventos rechazados con el motivo de cada uno.
+#[command]
+pub async fn importar_citas_ical(
+    session: State<'_, SessionState>,
+    contenido: String,
+) -> Result<IcalImportResponse, CitaError> {
+    let user = require_perm!(session, "citas:create", "Importando citas desde iCalendar")?;
+    cita_service::importar_ical(&contenido, user.id).await
+}
@@ -4,8 +4,15 @@
 /// incluyendo la identidad de la terminal, preferencias de audio y otros
 /// parámetros operativos almacenados en el archivo local TOML.
 use crate::config::manager::save_config;
-use crate::config::settings::{AppConfig, AppConfigState, TerminalConfig};
+use crate::config::settings::{
+    AppConfig, AppConfigState, CitaConfig, GafeteConfig, ListaNegraConfig, PaseConfig,
+    TerminalConfig,
+};
+use crate::domain::cita as cita_domain;
 use crate::domain::errors::ConfigError;
+use crate::domain::gafete as gafete_domain;
+use crate::domain::lista_negra as lista_negra_domain;
+use crate::services::session::SessionState;
 use log::info;
 use tauri::{command, State};
 
@@ -121,3 +128,208 @@ pub async fn update_backup_config(
 
     Ok(config_guard.backup.clone())
 }
+
+/// Obtiene la configuración de pases imprimibles.
+#[command]
+pub async fn get_pase_config(config: State<'_, AppConfigState>) -> Result<PaseConfig, ConfigError> {
+    let config_guard = config
+        .read()
+        .map_err(|e| ConfigError::Message(format!("Error al leer configuración: {e}")))?;
+
+    Ok(config_guard.pases.clone())
+}
+
+/// Actualiza la configuración de pases (generación automática, plantillas y vigencia).
+#[command]
+pub async fn update_pase_config(
+    config: State<'_, AppConfigState>,
+    pases: PaseConfig,
+) -> Result<PaseConfig, ConfigError> {
+    info!(
+        "Actualizando configuración de pases: generar={}, horas={:?}",
+        pases.generar_al_ingresar, pases.horas_validez
+    );
+
+    let mut config_guard = config
+        .write()
+        .map_err(|e| ConfigError::Message(format!("Error al escribir configuración: {e}")))?;
+
+    config_guard.pases = pases;
+
+    let config_path = if let Some(data_dir) = dirs::data_local_dir() {
+        data_dir.join("Brisas").join("brisas.toml")
+    } else {
+        std::path::PathBuf::from("./config/brisas.toml")
+    };
+
+    save_config(&config_guard, &config_path)
+        .map_err(|e| ConfigError::Message(format!("Error al guardar configuración: {e}")))?;
+
+    info!("Configuración de pases guardada");
+
+    Ok(config_guard.pases.clone())
+}
+
+/// Obtiene la configuración del ciclo de vida de citas.
+#[command]
+pub async fn get_cita_config(config: State<'_, AppConfigState>) -> Result<CitaConfig, ConfigError> {
+    let config_guard = config
+        .read()
+        .map_err(|e| ConfigError::Message(format!("Error al leer configuración: {e}")))?;
+
+    Ok(config_guard.citas.clone())
+}
+
+/// Actualiza el ciclo de vida (gracia de vencimiento) y la agenda (duración, capacidades, jornada).
+#[command]
+pub async fn update_cita_config(
+    config: State<'_, AppConfigState>,
+    citas: CitaConfig,
+) -> Result<CitaConfig, ConfigError> {
+    info!("Actualizando configuración de citas: gracia={} min", citas.gracia_vencimiento_minutos);
+
+    cita_domain::validar_jornada(&citas.jornada_inicio, &citas.jornada_fin)
+        .and_then(|()| cita_domain::validar_duracion(citas.duracion_default_minutos))
+        .map_err(|e| ConfigError::Message(e.to_string()))?;
+    let capacidades = citas.capacidad_por_area.values().copied();
+    if citas
+        .capacidad_por_anfitrion
+        .into_iter()
+        .chain(citas.capacidad_area_default)
+        .chain(capacidades)
+        .any(|c| c == 0)
+    {
+        return Err(ConfigError::Message("Las capacidades deben ser mayores que cero".to_string()));
+    }
+
+    let mut config_guard = config
+        .write()
+        .map_err(|e| ConfigError::Message(format!("Error al escribir configuración: {e}")))?;
+
+    config_guard.citas = citas;
+
+    let config_path = if let Some(data_dir) = dirs::data_local_dir() {
+        data_dir.join("Brisas").join("brisas.toml")
+    } else {
+        std::path::PathBuf::from("./config/brisas.toml")
+    };
+
+    save_config(&config_guard, &config_path)
+        .map_err(|e| ConfigError::Message(format!("Error al guardar configuración: {e}")))?;
+
+    info!("Configuración de citas guardada");
+
+    Ok(config_guard.citas.clone())
+}
+
+/// Recupera la política de asignación automática de gafetes.
+#[command]
+pub async fn get_gafete_config(
+    config: State<'_, AppConfigState>,
+) -> Result<GafeteConfig, ConfigError> {
+    let config_guard = config
+        .read()
+        .map_err(|e| ConfigError::Message(format!("Error al leer configuración: {e}")))?;
+
+    Ok(config_guard.gafetes.clone())
+}
+
+/// Actualiza la política de asignación, los rangos por empresa y la duración de la reserva.
+#[command]
+pub async fn update_gafete_config(
+    config: State<'_, AppConfigState>,
+    gafetes: GafeteConfig,
+) -> Result<GafeteConfig, ConfigError> {
+    info!(
+        "Actualizando configuración de gafetes: política={:?}, rangos={}",
+        gafetes.politica,
+        gafetes.rangos_empresa.len()
+    );
+
+    gafete_domain::validar_rangos(&gafetes.rangos_empresa)
+        .map_err(|e| ConfigError::Message(e.to_string()))?;
+    if gafetes.reserva_minutos == 0 {
+        return Err(ConfigError::Message(
+            "La reserva de gafetes debe durar al menos un minuto".to_string(),
+        ));
+    }
+
+    let mut config_guard = config
+        .write()
+        .map_err(|e| ConfigError::Message(format!("Error al escribir configuración: {e}")))?;
+
+    config_guard.gafetes = gafetes;
+
+    let config_path = if let Some(data_dir) = dirs::data_local_dir() {
+        data_dir.join("Brisas").join("brisas.toml")
+    } else {
+        std::path::PathBuf::from("./config/brisas.toml")
+    };
+
+    save_config(&config_guard, &config_path)
+        .map_err(|e| ConfigError::Message(format!("Error al guardar configuración: {e}")))?;
+
+    info!("Configuración de gafetes guardada");
+
+    Ok(config_guard.gafetes.clone())
+}
+
+/// Recupera la vigencia y revisión de bloqueos por nivel de severidad.
+#[command]
+pub async fn get_lista_negra_config(
+    config: State<'_, AppConfigState>,
+) -> Result<ListaNegraConfig, ConfigError> {
+    let config_guard = config
+        .read()
+        .map_err(|e| ConfigError::Message(format!("Error al leer configuración: {e}")))?;
+
+    Ok(config_guard.lista_negra.clone())
+}
+
+/// Actualiza la vigencia y revisión de bloqueos. Aplica a los bloqueos que se creen,
+/// reactiven o revisen desde este momento.
+///
+/// Incluye la política de doble control, por lo que requiere `config:update`.
+#[command]
+pub async fn update_lista_negra_config(
+    session: State<'_, SessionState>,
+    config: State<'_, AppConfigState>,
+    lista_negra: ListaNegraConfig,
+) -> Result<ListaNegraConfig, ConfigError> {
+    require_perm!(session, "config:update", "Actualizando política de lista negra")?;
+    info!("Actualizando configuración de lista negra");
+
+    for (nivel, politica) in
+        [("BAJO", lista_negra.bajo), ("MEDIO", lista_negra.medio), ("ALTO", lista_negra.alto)]
+    {
+        lista_negra_domain::validar_politica_vigencia(
+            nivel,
+            politica.vigencia_meses,
+            politica.revision_meses,
+        )
+        .map_err(|e| ConfigError::Message(e.to_string()))?;
+    }
+    lista_negra_domain::validar_umbral_coincidencia(lista_negra.umbral_coincidencia_nombre)
+        .map_err(|e| ConfigError::Message(e.to_string()))?;
+    lista_negra_domain::validar_sitios_confiables(&lista_negra.sitios_confiables)
+        .map_err(|e| ConfigError::Message(e.to_string()))?;
+
+    let mut config_guard = config
+        .write()
+        .map_err(|e| ConfigError::Message(format!("Error al escribir configuración: {e}")))?;
+
+    config_guard.lista_negra = lista_negra;
+
+    let config_path = if let Some(data_dir) = dirs::data_local_dir() {
+        data_dir.join("Brisas").join("brisas.toml")
+    } else {
+        std::path::PathBuf::from("./config/brisas.toml")
+    };
+
+    save_config(&config_guard, &config_path)
+        .map_err(|e| ConfigError::Message(format!("Error al guardar configuración: {e}")))?;
+
+    info!("Configuración de lista negra guardada");
+
+    Ok(config_guard.lista_negra.clone())
+}
@@ -34,7 +34,8 @@ const fn create_service(
 > {
     ContratistaService::new(
         SurrealContratistaRepository,
-        SurrealSecurityRepository,
+        // El registro de contratistas solo verifica bloqueos por cédula
+        SurrealSecurityRepository::new(None),
         SurrealEmpresaRepository,
         SurrealVehiculoRepository,
         SurrealAuditRepository,
@@ -274,6 +275,6 @@ pub async fn cambiar_estado_con_historial(
     let user =
         session.get_user().ok_or(ContratistaError::Unauthorized("Sesión no válida".to_string()))?;
     create_service(Some(search_service.inner().clone()))
-        .cambiar_estado_con_historial(input, user.id.clone())
+        .cambiar_estado_con_historial(input, Some(user.id.clone()))
         .await
 }
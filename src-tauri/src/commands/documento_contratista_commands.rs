@@ -0,0 +1,150 @@
+//! # Commands Tauri: Documentos de Contratista
+//!
+//! Documentos adicionales al PRAIND (inducción, póliza de riesgos, certificados) y
+//! reglas de documentos requeridos por empresa o por área.
+//!
+//! ## Seguridad
+//! - Consulta de documentos y pendientes: `contratistas:read`
+//! - Escaneo descifrado: `contratistas:read` (queda en auditoría)
+//! - Registro, actualización y eliminación: `contratistas:update`
+//! - Reglas de documentos requeridos: `config:update` (lectura con `contratistas:read`)
+
+use crate::domain::errors::DocumentoContratistaError;
+use crate::models::documento_contratista::{
+    CreateDocumentoContratistaInput, CreateRequisitoDocumentoInput, DocumentoContratistaResponse,
+    DocumentoPendiente, EscaneoDocumentoResponse, RequisitoDocumentoResponse,
+    UpdateDocumentoContratistaInput,
+};
+use crate::services::documento_contratista_service;
+use crate::services::session::SessionState;
+use tauri::{command, State};
+
+// --------------------------------------------------------------------------
+// DOCUMENTOS
+// --------------------------------------------------------------------------
+
+/// Documentos registrados de un contratista (sin escaneos).
+#[command]
+pub async fn listar_documentos_contratista(
+    session: State<'_, SessionState>,
+    contratista_id: String,
+) -> Result<Vec<DocumentoContratistaResponse>, DocumentoContratistaError> {
+    require_perm!(session, "contratistas:read")?;
+    documento_contratista_service::listar(&contratista_id).await
+}
+
+/// Registra un documento con escaneo opcional (JPEG, PNG, WebP o PDF, máx. 10 MiB).
+///
+/// ## Ejemplo TypeScript
+/// ```typescript
+/// await invoke('create_documento_contratista', {
+///     input: {
+///         contratistaId: 'contratista:abc',
+///         tipo: 'POLIZA_RIESGOS',
+///         numero: 'PR-2026-0042',
+///         fechaEmision: '2026-01-10',
+///         fechaVencimiento: '2027-01-10',
+///         filePath: '/ruta/poliza.pdf'
+///     }
+/// });
+/// ```
+#[command]
+pub async fn create_documento_contratista(
+    session: State<'_, SessionState>,
+    input: CreateDocumentoContratistaInput,
+) -> Result<DocumentoContratistaResponse, DocumentoContratistaError> {
+    let user =
+        require_perm!(session, "contratistas:update", "Registrando documento de contratista")?;
+    documento_contratista_service::registrar(input, &user.id).await
+}
+
+/// Actualiza un documento; `filePath` reemplaza el escaneo.
+#[command]
+pub async fn update_documento_contratista(
+    session: State<'_, SessionState>,
+    id: String,
+    input: UpdateDocumentoContratistaInput,
+) -> Result<DocumentoContratistaResponse, DocumentoContratistaError> {
+    let user = require_perm!(
+        session,
+        "contratistas:update",
+        format!("Actualizando documento de contratista {id}")
+    )?;
+    documento_contratista_service::actualizar(&id, input, &user.id).await
+}
+
+/// Descifra el escaneo de un documento, verifica su SHA-256 y lo entrega en Base64.
+#[command]
+pub async fn get_escaneo_documento_contratista(
+    session: State<'_, SessionState>,
+    id: String,
+) -> Result<EscaneoDocumentoResponse, DocumentoContratistaError> {
+    let user = require_perm!(session, "contratistas:read", "Consultando escaneo de documento")?;
+    documento_contratista_service::obtener_escaneo(&id, &user.id).await
+}
+
+/// Elimina un documento y su escaneo cifrado.
+#[command]
+pub async fn delete_documento_contratista(
+    session: State<'_, SessionState>,
+    id: String,
+) -> Result<(), DocumentoContratistaError> {
+    let user = require_perm!(
+        session,
+        "contratistas:update",
+        format!("Eliminando documento de contratista {id}")
+    )?;
+    documento_contratista_service::eliminar(&id, &user.id).await
+}
+
+/// Documentos exigidos que el contratista no cumple; `area` agrega las reglas de esa área.
+#[command]
+pub async fn get_documentos_pendientes_contratista(
+    session: State<'_, SessionState>,
+    contratista_id: String,
+    area: Option<String>,
+) -> Result<Vec<DocumentoPendiente>, DocumentoContratistaError> {
+    require_perm!(session, "contratistas:read")?;
+    documento_contratista_service::get_pendientes(&contratista_id, area.as_deref()).await
+}
+
+// --------------------------------------------------------------------------
+// REQUISITOS
+// --------------------------------------------------------------------------
+
+/// Reglas de documentos requeridos.
+#[command]
+pub async fn listar_requisitos_documento(
+    session: State<'_, SessionState>,
+) -> Result<Vec<RequisitoDocumentoResponse>, DocumentoContratistaError> {
+    require_perm!(session, "contratistas:read")?;
+    documento_contratista_service::listar_requisitos().await
+}
+
+/// Crea una regla de documento requerido (sin empresa ni área = todos los contratistas).
+///
+/// ## Ejemplo TypeScript
+/// ```typescript
+/// await invoke('create_requisito_documento', {
+///     input: { tipo: 'TRABAJO_ALTURAS', area: 'Calderas' }
+/// });
+/// ```
+#[command]
+pub async fn create_requisito_documento(
+    session: State<'_, SessionState>,
+    input: CreateRequisitoDocumentoInput,
+) -> Result<RequisitoDocumentoResponse, DocumentoContratistaError> {
+    let user = require_perm!(session, "config:update", "Creando requisito de documento")?;
+    documento_contratista_service::crear_requisito(input, &user.id).await
+}
+
+/// Elimina una regla de documento requerido.
+#[command]
+pub async fn delete_requisito_documento(
+    session: State<'_, SessionState>,
+    id: String,
+) -> Result<(), DocumentoContratistaError> {
+    let user =
+        require_perm!(session, "config:update", format!("Eliminando requisito de documento {id}"))?;
+    documento_contratista_service::eliminar_requisito(&id, &user.id).await
+}
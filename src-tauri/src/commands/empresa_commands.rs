@@ -4,10 +4,13 @@
 /// registradas en el sistema.
 use crate::domain::errors::EmpresaError;
 use crate::models::empresa::{
-    CreateEmpresaInput, EmpresaListResponse, EmpresaResponse, UpdateEmpresaInput,
+    BajaEmpresaResponse, CreateEmpresaInput, EmpresaListResponse, EmpresaResponse,
+    UpdateEmpresaInput,
 };
 use crate::services::empresa_service as service;
+use crate::services::search_service::SearchService;
 use crate::services::session::SessionState;
+use std::sync::Arc;
 use tauri::{command, State};
 
 // --------------------------------------------------------------------------
@@ -61,6 +64,23 @@ pub async fn get_empresa_by_id(
     service::get_empresa_by_id(&id).await
 }
 
+/// [Comando Tauri] Lista las subcontratistas directas de una empresa.
+///
+/// # Argumentos
+/// * `session` - Estado de la sesión.
+/// * `id` - Identificador de la contratista principal.
+///
+/// # Retorno
+/// Subcontratistas ordenadas por nombre.
+#[command]
+pub async fn get_subempresas(
+    session: State<'_, SessionState>,
+    id: String,
+) -> Result<Vec<EmpresaResponse>, EmpresaError> {
+    require_perm!(session, "empresas:read")?;
+    service::get_subempresas(&id).await
+}
+
 // --------------------------------------------------------------------------
 // OPERACIONES DE GESTIÓN (MUTACIONES)
 // --------------------------------------------------------------------------
@@ -109,15 +129,30 @@ pub async fn update_empresa(
 ///
 /// # Argumentos
 /// * `session` - Estado de la sesión.
+/// * `search_service` - Buscador donde se refleja el personal desactivado.
 /// * `id` - ID de la empresa a eliminar.
+/// * `desactivar_en_cascada` - Si tiene personal o subcontratistas, desactivarlos
+///   junto con la empresa en lugar de rechazar la baja.
 ///
 /// # Retorno
-/// Ok(()) o error si tiene dependencias activas (contratistas).
+/// Resumen de la baja o error si tiene dependencias y no se pidió la cascada.
 #[command]
 pub async fn delete_empresa(
     session: State<'_, SessionState>,
+    search_service: State<'_, Arc<SearchService>>,
     id: String,
-) -> Result<(), EmpresaError> {
-    require_perm!(session, "empresas:delete", format!("Dando de baja entidad corporativa {}", id))?;
-    service::delete_empresa(&id).await
+    desactivar_en_cascada: Option<bool>,
+) -> Result<BajaEmpresaResponse, EmpresaError> {
+    let user = require_perm!(
+        session,
+        "empresas:delete",
+        format!("Dando de baja entidad corporativa {}", id)
+    )?;
+    service::delete_empresa(
+        &id,
+        desactivar_en_cascada.unwrap_or(false),
+        &user.id,
+        search_service.inner(),
+    )
+    .await
 }
@@ -4,8 +4,10 @@
 /// (PDF, Excel, CSV), permitiendo filtrar y previsualizar la información del sistema
 /// antes de su archivado o auditoría.
 use crate::domain::errors::ExportError;
-use crate::models::export::{ExportRequest, ExportResponse};
-use crate::services::export_service;
+use crate::models::export::{ExportRequest, ExportResponse, ReportDefinition, ReportRequest};
+use crate::services::session::SessionState;
+use crate::services::{export_service, report_service};
+use tauri::State;
 
 /// Comando universal para la generación de archivos de exportación.
 #[tauri::command]
@@ -37,6 +39,34 @@ pub async fn is_export_format_available(format: String) -> Result<bool, ExportEr
     Ok(export::is_format_available(&format))
 }
 
+// --------------------------------------------------------------------------
+// REPORTES GENERADOS EN EL SERVIDOR
+// --------------------------------------------------------------------------
+
+/// Catálogo de reportes predefinidos que el backend puede generar desde la base de datos.
+#[tauri::command]
+pub async fn list_report_definitions(
+    session: State<'_, SessionState>,
+) -> Result<Vec<ReportDefinition>, ExportError> {
+    require_perm!(session, "export:read")?;
+    Ok(report_service::list_report_definitions())
+}
+
+/// Genera un reporte predefinido consultando `SurrealDB` directamente (sin pasar filas por la UI).
+#[tauri::command]
+pub async fn generate_report(
+    session: State<'_, SessionState>,
+    request: ReportRequest,
+) -> Result<ExportResponse, ExportError> {
+    let user = require_perm!(
+        session,
+        "export:export",
+        format!("Generando reporte {}", request.reporte.as_str())
+    )?;
+    let generated_by = format!("{} {}", user.nombre, user.apellido);
+    report_service::generate_report(request, Some(generated_by)).await
+}
+
 // --------------------------------------------------------------------------
 // COMANDOS ESPECIALIZADOS POR FORMATO
 // --------------------------------------------------------------------------
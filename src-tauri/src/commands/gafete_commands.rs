@@ -4,11 +4,12 @@
 /// hasta su asignación y control de estado (Daño, Extravío, Disponibilidad).
 use crate::domain::errors::GafeteError;
 use crate::models::gafete::{
-    CreateGafeteInput, CreateGafeteRangeInput, GafeteListResponse, GafeteResponse, StatsGafetes,
-    StatsPorTipo, UpdateGafeteInput, UpdateGafeteStatusInput,
+    ContextoMovimientoGafete, CreateGafeteInput, CreateGafeteRangeInput, DeudaGafeteResponse,
+    GafeteHistorialResponse, GafeteListResponse, GafeteResponse, PropuestaGafeteResponse,
+    StatsGafetes, StatsPorTipo, UpdateGafeteInput, UpdateGafeteStatusInput,
 };
-use crate::services::gafete_service;
 use crate::services::session::SessionState;
+use crate::services::{gafete_asignacion_service, gafete_service};
 use tauri::{command, State};
 
 /// Registra una nueva unidad de identificación en el sistema.
@@ -73,14 +74,21 @@ pub async fn get_gafetes_disponibles(tipo: String) -> Result<Vec<GafeteResponse>
 }
 
 /// Comprobación de estado rápida para validaciones en caliente.
+///
+/// Las reservas de la propia sesión no cuentan como ocupado.
 /// [Comando Tauri]
 #[command]
-pub async fn is_gafete_disponible(numero: String, tipo: String) -> Result<bool, GafeteError> {
+pub async fn is_gafete_disponible(
+    session: State<'_, SessionState>,
+    numero: String,
+    tipo: String,
+) -> Result<bool, GafeteError> {
     let numero_int = numero.parse::<i32>().map_err(|_| {
         GafeteError::Validation("El número de gafete debe ser numérico".to_string())
     })?;
+    let usuario = session.get_user().map(|u| crate::domain::common::parse_record_id(&u.id, "user"));
 
-    gafete_service::is_gafete_disponible(numero_int, &tipo).await
+    gafete_service::is_gafete_disponible(numero_int, &tipo, usuario.as_ref()).await
 }
 
 /// [Comando Tauri]
@@ -94,15 +102,80 @@ pub async fn update_gafete(
 }
 
 /// Actualización de Estado Operativo: Permite marcar un gafete como 'dañado' o 'extraviado'.
+///
+/// El cambio queda en el historial del gafete con el usuario de la sesión y el motivo.
 /// [Comando Tauri]
 #[command]
 pub async fn update_gafete_status(
+    session: State<'_, SessionState>,
     id: String,
     input: UpdateGafeteStatusInput,
-    _usuario_id: Option<String>,
-    _motivo: Option<String>,
+    motivo: Option<String>,
 ) -> Result<GafeteResponse, GafeteError> {
-    gafete_service::update_gafete_status(&id, input.estado).await
+    let user = require_perm!(
+        session,
+        "gafetes:update",
+        format!("Cambiando estado del gafete {id} a {}", input.estado)
+    )?;
+    let contexto = ContextoMovimientoGafete {
+        usuario: user.id.parse().ok(),
+        notas: motivo,
+        ..ContextoMovimientoGafete::default()
+    };
+    gafete_service::update_gafete_status(&id, input.estado, contexto).await
+}
+
+/// Cadena de Custodia: Historia completa de un gafete (entregas, devoluciones y cambios de estado).
+/// [Comando Tauri]
+#[command]
+pub async fn get_gafete_historial(
+    session: State<'_, SessionState>,
+    id: String,
+) -> Result<GafeteHistorialResponse, GafeteError> {
+    require_perm!(session, "gafetes:read")?;
+    gafete_service::get_historial(&id).await
+}
+
+/// Libro de Deudas: Gafetes no devueltos pendientes, de todos o de una persona.
+/// [Comando Tauri]
+#[command]
+pub async fn get_deudas_gafete(
+    session: State<'_, SessionState>,
+    cedula: Option<String>,
+) -> Result<Vec<DeudaGafeteResponse>, GafeteError> {
+    require_perm!(session, "gafetes:read")?;
+    match cedula.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+        Some(c) => Ok(gafete_service::get_deudas_by_cedula(c)
+            .await?
+            .into_iter()
+            .map(DeudaGafeteResponse::from)
+            .collect()),
+        None => gafete_service::get_deudas_pendientes().await,
+    }
+}
+
+/// Asignación Automática: Propone el siguiente gafete libre y lo aparta para esta terminal.
+/// [Comando Tauri]
+#[command]
+pub async fn proponer_gafete(
+    session: State<'_, SessionState>,
+    tipo: String,
+    empresa_id: Option<String>,
+) -> Result<PropuestaGafeteResponse, GafeteError> {
+    let user = require_perm!(session, "ingresos:create")?;
+    gafete_asignacion_service::proponer_gafete(&tipo, empresa_id.as_deref(), &user.id).await
+}
+
+/// Devuelve al pool un gafete propuesto que finalmente no se entregó.
+/// [Comando Tauri]
+#[command]
+pub async fn cancelar_reserva_gafete(
+    session: State<'_, SessionState>,
+    numero: i32,
+    tipo: String,
+) -> Result<(), GafeteError> {
+    let user = require_perm!(session, "ingresos:create")?;
+    gafete_asignacion_service::cancelar_reserva(numero, &tipo, &user.id).await
 }
 
 /// Baja definitiva de un activo del inventario.
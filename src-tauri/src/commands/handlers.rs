@@ -21,6 +21,7 @@ macro_rules! register_handlers {
             commands::empresa_commands::get_empresas_activas,
             commands::empresa_commands::update_empresa,
             commands::empresa_commands::delete_empresa,
+            commands::empresa_commands::get_subempresas,
             // Comandos de contratista
             commands::contratista_commands::create_contratista,
             commands::contratista_commands::get_contratista_by_id,
@@ -35,6 +36,23 @@ macro_rules! register_handlers {
             // Auditoría de contratista
             commands::contratista_commands::actualizar_praind_con_historial,
             commands::contratista_commands::cambiar_estado_con_historial,
+            // Digest de vencimiento PRAIND
+            commands::praind_digest_commands::get_digests_praind,
+            commands::praind_digest_commands::ejecutar_revision_praind,
+            commands::praind_digest_commands::exportar_digest_praind,
+            // Documentos de contratista y requisitos de ingreso
+            commands::documento_contratista_commands::listar_documentos_contratista,
+            commands::documento_contratista_commands::create_documento_contratista,
+            commands::documento_contratista_commands::update_documento_contratista,
+            commands::documento_contratista_commands::get_escaneo_documento_contratista,
+            commands::documento_contratista_commands::delete_documento_contratista,
+            commands::documento_contratista_commands::get_documentos_pendientes_contratista,
+            commands::documento_contratista_commands::listar_requisitos_documento,
+            commands::documento_contratista_commands::create_requisito_documento,
+            commands::documento_contratista_commands::delete_requisito_documento,
+            // Importación masiva de personas (CSV/Excel)
+            commands::importacion_commands::previsualizar_importacion,
+            commands::importacion_commands::importar_personas,
             // Comandos de lista negra
             commands::lista_negra_commands::add_to_lista_negra,
             commands::lista_negra_commands::get_lista_negra_by_id,
@@ -44,6 +62,21 @@ macro_rules! register_handlers {
             commands::lista_negra_commands::update_lista_negra,
             commands::lista_negra_commands::delete_from_lista_negra,
             commands::lista_negra_commands::restore_lista_negra,
+            commands::lista_negra_commands::get_revisiones_lista_negra,
+            commands::lista_negra_commands::revisar_lista_negra,
+            commands::lista_negra_commands::get_empresas_vetadas,
+            commands::lista_negra_commands::vetar_empresa,
+            commands::lista_negra_commands::update_empresa_vetada,
+            commands::lista_negra_commands::levantar_veto_empresa,
+            commands::lista_negra_evidencia_commands::listar_evidencias_lista_negra,
+            commands::lista_negra_evidencia_commands::get_evidencia_lista_negra,
+            commands::lista_negra_evidencia_commands::adjuntar_evidencia_lista_negra,
+            commands::lista_negra_evidencia_commands::delete_evidencia_lista_negra,
+            commands::lista_negra_intercambio_commands::exportar_lista_negra,
+            commands::lista_negra_intercambio_commands::previsualizar_importacion_lista_negra,
+            commands::lista_negra_intercambio_commands::importar_lista_negra,
+            commands::lista_negra_propuesta_commands::get_propuestas_lista_negra,
+            commands::lista_negra_propuesta_commands::resolver_propuesta_lista_negra,
             // Comandos de vehículo
             commands::vehiculo_commands::create_vehiculo,
             commands::vehiculo_commands::get_vehiculo_by_id,
@@ -62,6 +95,10 @@ macro_rules! register_handlers {
             commands::gafete_commands::is_gafete_disponible,
             commands::gafete_commands::update_gafete,
             commands::gafete_commands::update_gafete_status,
+            commands::gafete_commands::get_gafete_historial,
+            commands::gafete_commands::get_deudas_gafete,
+            commands::gafete_commands::proponer_gafete,
+            commands::gafete_commands::cancelar_reserva_gafete,
             commands::gafete_commands::delete_gafete,
             // ==========================================
             // COMANDOS DE CITAS (Pre-registro)
@@ -70,8 +107,20 @@ macro_rules! register_handlers {
             commands::cita_commands::get_citas_hoy,
             commands::cita_commands::get_citas_pendientes,
             commands::cita_commands::get_cita_by_id,
+            commands::cita_commands::get_horarios_libres,
             commands::cita_commands::procesar_ingreso_cita,
             commands::cita_commands::cancelar_cita,
+            commands::cita_commands::generar_invitacion_cita,
+            commands::cita_commands::verificar_invitacion_cita,
+            commands::cita_commands::procesar_ingreso_cita_token,
+            commands::cita_commands::create_cita_serie,
+            commands::cita_commands::get_cita_serie,
+            commands::cita_commands::update_cita_serie,
+            commands::cita_commands::cancelar_cita_serie,
+            commands::cita_commands::get_reporte_inasistencias,
+            commands::cita_commands::vencer_citas_ahora,
+            commands::cita_commands::exportar_citas_ical,
+            commands::cita_commands::importar_citas_ical,
             // ==========================================
             // COMANDOS DE VISITANTE
             // ==========================================
@@ -140,8 +189,12 @@ macro_rules! register_handlers {
             commands::ingreso_commands::resolver_alerta_gafete,
             // Comandos de búsqueda
             commands::search_commands::search_omnibox,
+            commands::search_commands::search_structured,
             commands::search_commands::search_global,
             commands::search_commands::reindex_global_search,
+            commands::search_commands::check_search_index_consistency,
+            commands::search_commands::search_ingresos_historial,
+            commands::search_commands::reindex_ingresos_historial,
             // ==========================================
             // COMANDOS DE EXPORTACIÓN
             // ==========================================
@@ -153,6 +206,8 @@ macro_rules! register_handlers {
             commands::export_commands::export_to_excel,
             commands::export_commands::export_to_csv,
             commands::export_commands::export_preview,
+            commands::export_commands::list_report_definitions,
+            commands::export_commands::generate_report,
             // ==========================================
             // COMANDOS DE PERFILES DE EXPORTACIÓN
             // ==========================================
@@ -162,6 +217,27 @@ macro_rules! register_handlers {
             commands::export_profiles::set_default_export_profile,
             commands::export_profiles::get_default_export_profile,
             // ==========================================
+            // COMANDOS DE REPORTES PROGRAMADOS
+            // ==========================================
+            commands::report_schedules::get_report_schedules,
+            commands::report_schedules::save_report_schedule,
+            commands::report_schedules::delete_report_schedule,
+            commands::report_schedules::run_report_schedule_now,
+            commands::report_schedules::get_report_run_history,
+            // ==========================================
+            // COMANDOS DE PASES IMPRIMIBLES
+            // ==========================================
+            commands::pase_commands::get_pase_ingreso,
+            commands::pase_commands::generar_pase_ingreso,
+            commands::pase_commands::list_pase_templates,
+            // ==========================================
+            // COMANDOS DE ESCÁNER
+            // ==========================================
+            commands::scanner_commands::procesar_lectura_escaner,
+            commands::scanner_commands::decodificar_lectura_escaner,
+            commands::scanner_commands::get_scanner_config,
+            commands::scanner_commands::update_scanner_config,
+            // ==========================================
             // COMANDOS DE BACKUP/RESTORE
             // ==========================================
             commands::backup::backup_database,
@@ -179,6 +255,14 @@ macro_rules! register_handlers {
             commands::config_commands::update_audio_config,
             commands::config_commands::get_backup_config,
             commands::config_commands::update_backup_config,
+            commands::config_commands::get_pase_config,
+            commands::config_commands::update_pase_config,
+            commands::config_commands::get_cita_config,
+            commands::config_commands::update_cita_config,
+            commands::config_commands::get_gafete_config,
+            commands::config_commands::update_gafete_config,
+            commands::config_commands::get_lista_negra_config,
+            commands::config_commands::update_lista_negra_config,
             // ==========================================
             // COMANDOS DE VENTANA
             // ==========================================
@@ -199,6 +283,8 @@ macro_rules! register_handlers {
             commands::keyring_commands::generate_random_secret,
             commands::keyring_commands::export_master_key_cmd,
             commands::keyring_commands::import_master_key_cmd,
+            commands::keyring_commands::get_lista_negra_exchange_public_key,
+            commands::keyring_commands::set_lista_negra_exchange_key,
             commands::keyring_commands::test_keyring,
             commands::keyring_commands::reset_all_credentials,
             commands::keyring_commands::save_secret,
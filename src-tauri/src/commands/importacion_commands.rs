@@ -0,0 +1,49 @@
+//! # Commands Tauri: Importación Masiva de Personas
+//!
+//! Alta de contratistas, proveedores y visitantes desde CSV o Excel.
+//!
+//! ## Seguridad
+//! Ambos comandos exigen el permiso de alta del tipo importado (`contratistas:create`,
+//! `proveedores:create` o `visitantes:create`); la importación queda en auditoría.
+
+use crate::domain::errors::ImportacionError;
+use crate::models::importacion::{
+    ImportacionInput, ImportacionResponse, PrevisualizacionImportacion,
+};
+use crate::services::importacion_service as service;
+use crate::services::search_service::SearchService;
+use crate::services::session::SessionState;
+use std::sync::Arc;
+use tauri::{command, State};
+
+/// Valida el archivo fila por fila y reporta los errores sin registrar nada.
+///
+/// ## Ejemplo TypeScript
+/// ```typescript
+/// const preview = await invoke('previsualizar_importacion', {
+///   input: { tipo: 'contratista', filePath, empresaId: 'empresa:acme' }
+/// });
+/// ```
+#[command]
+pub async fn previsualizar_importacion(
+    session: State<'_, SessionState>,
+    input: ImportacionInput,
+) -> Result<PrevisualizacionImportacion, ImportacionError> {
+    require_perm!(session, input.tipo.permiso())?;
+    service::previsualizar(&input).await
+}
+
+/// Registra las filas válidas del archivo y devuelve las rechazadas.
+#[command]
+pub async fn importar_personas(
+    session: State<'_, SessionState>,
+    search_service: State<'_, Arc<SearchService>>,
+    input: ImportacionInput,
+) -> Result<ImportacionResponse, ImportacionError> {
+    let user = require_perm!(
+        session,
+        input.tipo.permiso(),
+        format!("Importando {}s desde {}", input.tipo.as_str(), input.file_path)
+    )?;
+    service::importar(&input, &search_service, &user.email).await
+}
@@ -21,7 +21,10 @@ use crate::repositories::{
     ingreso_contratista::SurrealIngresoContratistaRepository,
 };
 use crate::services::ingreso_contratista_service::IngresoContratistaService;
+use crate::services::pase_service;
+use crate::services::search_service::SearchService;
 use crate::services::session::SessionState;
+use std::sync::Arc;
 
 use tauri::{command, AppHandle, Emitter, State};
 
@@ -30,7 +33,11 @@ use tauri::{command, AppHandle, Emitter, State};
 // --------------------------------------------------------------------------
 
 /// Crea una instancia del servicio con las implementaciones concretas de `SurrealDB`.
-const fn create_service() -> IngresoContratistaService<
+///
+/// El índice de búsqueda solo se necesita en la validación de lista negra por nombre.
+const fn create_service(
+    search_service: Option<Arc<SearchService>>,
+) -> IngresoContratistaService<
     SurrealIngresoContratistaRepository,
     SurrealGafeteRepository,
     SurrealContratistaRepository,
@@ -40,7 +47,7 @@ const fn create_service() -> IngresoContratistaService<
         SurrealIngresoContratistaRepository,
         SurrealGafeteRepository,
         SurrealContratistaRepository,
-        SurrealSecurityRepository,
+        SurrealSecurityRepository::new(search_service),
     )
 }
 
@@ -49,14 +56,19 @@ const fn create_service() -> IngresoContratistaService<
 // --------------------------------------------------------------------------
 
 /// [Comando Tauri] Pre-chequeo de identidad y seguridad antes del ingreso.
+/// `area` (opcional) activa los documentos exigidos para esa área.
 #[command]
 pub async fn validate_ingreso_contratista(
     session: State<'_, SessionState>,
+    search_service: State<'_, Arc<SearchService>>,
     contratista_id: String,
+    area: Option<String>,
 ) -> Result<ValidacionIngresoResponse, IngresoContratistaError> {
     require_session!(session);
     require_perm!(session, "ingresos:read")?;
-    create_service().validar_ingreso_contratista(contratista_id).await
+    create_service(Some(search_service.inner().clone()))
+        .validar_ingreso_contratista(contratista_id, area)
+        .await
 }
 
 /// [Comando Tauri] Registra la entrada física de un contratista.
@@ -65,11 +77,25 @@ pub async fn validate_ingreso_contratista(
 pub async fn create_ingreso_contratista(
     app: AppHandle,
     session: State<'_, SessionState>,
+    search_service: State<'_, Arc<SearchService>>,
     input: CreateIngresoContratistaInput,
 ) -> Result<IngresoResponse, IngresoContratistaError> {
     let user = require_session!(session);
     require_perm!(session, "ingresos:create")?;
-    let result = create_service().crear_ingreso_contratista(input, user.id.clone()).await?;
+    // Descartar una posible coincidencia en lista negra es una decisión de supervisión
+    if input.coincidencia_lista_negra_descartada {
+        require_perm!(
+            session,
+            "lista_negra:update",
+            format!("Descartando coincidencia en lista negra para {}", input.contratista_id)
+        )?;
+    }
+    let result = create_service(Some(search_service.inner().clone()))
+        .crear_ingreso_contratista(input, user.id.clone())
+        .await?;
+
+    // Pase imprimible (el servicio usa repositorios inyectados, se genera aquí)
+    pase_service::generar_pase_en_segundo_plano(result.id.clone());
 
     // Emit event to refresh gafete grid
     let _ = app.emit("gafetes:refresh", ());
@@ -91,7 +117,7 @@ pub async fn validate_exit_contratista(
     require_session!(session);
     require_perm!(session, "ingresos:read")?;
 
-    create_service()
+    create_service(None)
         .validar_puede_salir(&ingreso_id, gafete_devuelto.as_deref())
         .map_err(IngresoContratistaError::Validation)
 }
@@ -106,7 +132,7 @@ pub async fn register_exit_contratista(
 ) -> Result<IngresoResponse, IngresoContratistaError> {
     let user = require_session!(session);
     require_perm!(session, "ingresos:update")?;
-    let result = create_service().registrar_salida(input, user.id.clone()).await?;
+    let result = create_service(None).registrar_salida(input, user.id.clone()).await?;
 
     // Emit event to refresh gafete grid
     let _ = app.emit("gafetes:refresh", ());
@@ -125,7 +151,7 @@ pub async fn get_ingresos_contratistas_activos(
 ) -> Result<Vec<IngresoResponse>, IngresoContratistaError> {
     require_session!(session);
     require_perm!(session, "ingresos:read")?;
-    create_service().get_activos().await
+    create_service(None).get_activos().await
 }
 
 /// [Comando Tauri] Obtiene el historial de salidas de contratistas en un rango de fechas.
@@ -137,7 +163,7 @@ pub async fn get_ingresos_contratistas_historial(
 ) -> Result<Vec<IngresoResponse>, IngresoContratistaError> {
     require_session!(session);
     require_perm!(session, "ingresos:read")?;
-    create_service().get_salidas_en_rango(&fecha_inicio, &fecha_fin).await
+    create_service(None).get_salidas_en_rango(&fecha_inicio, &fecha_fin).await
 }
 
 /// [Comando Tauri] Consulta alertas por tiempos de permanencia excedidos.
@@ -147,5 +173,5 @@ pub async fn check_time_alerts(
 ) -> Result<Vec<AlertaTiempoExcedido>, IngresoContratistaError> {
     require_session!(session);
     require_perm!(session, "ingresos:read")?;
-    create_service().verificar_tiempos_excedidos()
+    create_service(None).verificar_tiempos_excedidos()
 }
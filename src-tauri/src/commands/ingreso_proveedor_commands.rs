@@ -6,16 +6,31 @@
 use crate::domain::errors::IngresoProveedorError;
 use crate::domain::ingreso_proveedor::ValidacionIngresoProveedorResponse;
 use crate::models::ingreso::{CreateIngresoProveedorInput, IngresoResponse};
+use crate::models::role::{Action, Module};
 use crate::services::ingreso_proveedor_service as service;
-use tauri::command;
+use crate::services::search_service::SearchService;
+use crate::services::session::SessionState;
+use std::sync::Arc;
+use tauri::{command, State};
 
 /// Registra físicamente la llegada de un proveedor.
+/// Descartar una posible coincidencia en lista negra requiere 'lista_negra:update'.
 #[command]
 pub async fn crear_ingreso_proveedor_v2(
+    session: State<'_, SessionState>,
+    search_service: State<'_, Arc<SearchService>>,
     input: CreateIngresoProveedorInput,
     usuario_id: String,
 ) -> Result<IngresoResponse, String> {
-    service::registrar_ingreso(input, usuario_id).await.map_err(|e| e.to_string())
+    if input.coincidencia_lista_negra_descartada {
+        session
+            .require_permission(Module::ListaNegra, Action::Update)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    service::registrar_ingreso(input, usuario_id, Some(search_service.inner().as_ref()))
+        .await
+        .map_err(|e| e.to_string())
 }
 
 /// Lista los proveedores que están actualmente dentro de las instalaciones.
@@ -48,7 +63,8 @@ pub async fn search_proveedores_by_cedula(
 /// Pre-chequeo de Seguridad: Valida si el proveedor es elegible para ingresar (Lista Negra).
 #[command]
 pub async fn validar_ingreso_proveedor(
+    search_service: State<'_, Arc<SearchService>>,
     proveedor_id: String,
 ) -> Result<ValidacionIngresoProveedorResponse, IngresoProveedorError> {
-    service::validar_ingreso(proveedor_id).await
+    service::validar_ingreso(proveedor_id, Some(search_service.inner().as_ref())).await
 }
@@ -250,6 +250,30 @@ pub async fn import_master_key_cmd(
     ks::import_master_key(std::path::PathBuf::from(file_path), &password)
 }
 
+// ==========================================
+// CLAVE DE INTERCAMBIO DE LISTA NEGRA
+// ==========================================
+
+/// Devuelve (HEX) la clave pública con que las plantas hermanas verifican los archivos de
+/// intercambio de lista negra de este sitio. La clave privada nunca se expone.
+#[command]
+pub async fn get_lista_negra_exchange_public_key(
+    session: State<'_, SessionState>,
+) -> Result<String, KeyringError> {
+    require_perm!(session, "config:read")?;
+    ks::get_lista_negra_public_key()
+}
+
+/// Reemplaza el par de claves de intercambio del sitio (PKCS#8 en HEX). Solo escritura.
+#[command]
+pub async fn set_lista_negra_exchange_key(
+    session: State<'_, SessionState>,
+    key: String,
+) -> Result<(), KeyringError> {
+    require_perm!(session, "config:update", "Cambio de clave de intercambio de lista negra")?;
+    ks::set_lista_negra_signing_key(&key)
+}
+
 // ==========================================
 // COMANDOS DE RECUPERACIÓN (SHAMIR)
 // ==========================================
@@ -7,34 +7,28 @@
 //! - **Escritura**: `add_*`, `update_*`, `delete_*` - Requieren autenticación
 //!
 //! ## Seguridad
-//! - `add_to_lista_negra`: Requiere permiso `lista_negra.crear`
-//! - `update_lista_negra`: Requiere permiso `lista_negra.editar`
-//! - `delete_from_lista_negra`: Requiere permiso `lista_negra.eliminar`
+//! - `add_to_lista_negra`: Requiere permiso `lista_negra:create`
+//! - `update_lista_negra` / `restore_lista_negra`: Requieren permiso `lista_negra:update`
+//! - `delete_from_lista_negra`: Requiere permiso `lista_negra:delete`
+//! - `revisar_lista_negra`: Requiere permiso `lista_negra:update`
+//! - Con `lista_negra.requiere_aprobacion`, alta, edición, eliminación, restauración y
+//!   levantamiento en revisión retornan `aplicado = false` con la propuesta que
+//!   espera a un supervisor
+//! - `vetar_empresa` / `update_empresa_vetada` / `levantar_veto_empresa`: Requieren
+//!   `lista_negra:create`, `lista_negra:update` y `lista_negra:delete`
+//! - Consultas (`get_*`, `search_lista_negra`): Requieren `lista_negra:read`
 //! - `check_is_blocked`: Público (hot-path de validación)
 
 use crate::domain::errors::ListaNegraError;
 use crate::models::lista_negra::{
-    AddToListaNegraInput, BlockCheckResponse, ListaNegraListResponse, ListaNegraResponse,
-    UpdateListaNegraInput,
+    AddToListaNegraInput, BlockCheckResponse, CambioListaNegraResponse, EmpresaVetadaResponse,
+    ListaNegraListResponse, ListaNegraResponse, RevisarListaNegraInput, UpdateEmpresaVetadaInput,
+    UpdateListaNegraInput, VetarEmpresaInput,
 };
 use crate::services::lista_negra_service;
 use crate::services::session::SessionState;
 use tauri::{command, State};
 
-// Macro para verificar permisos
-macro_rules! require_perm {
-    ($session:expr, $modulo:expr, $accion:expr) => {{
-        let _user = $session.get_user().ok_or_else(|| {
-            ListaNegraError::Validation("Sesión no válida o expirada".to_string())
-        })?;
-
-        // TODO: Verificar permisos específicos cuando exista el sistema de permisos
-        // if !user.has_permission($modulo, $accion) {
-        //     return Err(ListaNegraError::Validation("Sin permisos suficientes".into()));
-        // }
-    }};
-}
-
 // --------------------------------------------------------------------------
 // COMANDOS DE CONSULTA (Solo Lectura)
 // --------------------------------------------------------------------------
@@ -67,20 +61,21 @@ pub async fn check_is_blocked(cedula: String) -> Result<BlockCheckResponse, List
 /// Obtiene un registro de lista negra por su ID.
 ///
 /// ## Autenticación
-/// Requiere sesión activa.
+/// Requiere sesión activa con permiso `lista_negra:read`.
 ///
 /// ## Parámetros
 /// * `id` - ID del registro (formato: "`lista_negra:xxx`")
 ///
 /// ## Errores
 /// * `ListaNegraError::NotFound` - Registro no existe
-/// * `ListaNegraError::Validation` - ID inválido o sesión expirada
+/// * `ListaNegraError::Validation` - ID inválido
+/// * `ListaNegraError::Unauthorized` - Sin sesión o sin permiso
 #[command]
 pub async fn get_lista_negra_by_id(
     session: State<'_, SessionState>,
     id: String,
 ) -> Result<ListaNegraResponse, ListaNegraError> {
-    require_perm!(session, "lista_negra", "leer");
+    crate::require_perm!(session, "lista_negra:read")?;
 
     lista_negra_service::get_by_id(id).await?.ok_or(ListaNegraError::NotFound)
 }
@@ -88,7 +83,7 @@ pub async fn get_lista_negra_by_id(
 /// Auditoría de Seguridad: Obtiene la relación completa de personas con restricciones.
 ///
 /// ## Autenticación
-/// Requiere sesión activa.
+/// Requiere sesión activa con permiso `lista_negra:read`.
 ///
 /// ## Retorno
 /// Estructura con:
@@ -107,7 +102,7 @@ pub async fn get_lista_negra_by_id(
 pub async fn get_all_lista_negra(
     session: State<'_, SessionState>,
 ) -> Result<ListaNegraListResponse, ListaNegraError> {
-    require_perm!(session, "lista_negra", "leer");
+    crate::require_perm!(session, "lista_negra:read")?;
 
     lista_negra_service::get_all().await
 }
@@ -115,7 +110,7 @@ pub async fn get_all_lista_negra(
 /// Busca registros de lista negra por término.
 ///
 /// ## Autenticación
-/// Requiere sesión activa.
+/// Requiere sesión activa con permiso `lista_negra:read`.
 ///
 /// ## Parámetros
 /// * `query` - Término de búsqueda (nombre, apellido, cédula)
@@ -127,7 +122,7 @@ pub async fn search_lista_negra(
     session: State<'_, SessionState>,
     query: String,
 ) -> Result<Vec<ListaNegraResponse>, ListaNegraError> {
-    require_perm!(session, "lista_negra", "leer");
+    crate::require_perm!(session, "lista_negra:read")?;
 
     lista_negra_service::search(&query).await
 }
@@ -139,16 +134,23 @@ pub async fn search_lista_negra(
 /// Registra una nueva restricción de acceso para un individuo.
 ///
 /// ## Autenticación
-/// Requiere sesión activa con permiso `lista_negra.crear`.
+/// Requiere sesión activa con permiso `lista_negra:create`.
 ///
 /// ## Parámetros
 /// * `input` - Datos del bloqueo (cédula, nombre, motivo, nivel, etc.)
 ///
+/// ## Retorno
+/// * `aplicado = false` con la propuesta cuando queda en aprobación (doble control)
+///
 /// ## Errores
 /// * `ListaNegraError::AlreadyExists` - Persona ya está bloqueada
 /// * `ListaNegraError::Validation` - Datos inválidos
+/// * `ListaNegraError::Unauthorized` - Sin permiso
 /// * `ListaNegraError::Database` - Error de persistencia
 ///
+/// `bloqueadoPor` se toma siempre de la sesión: es quien propone cuando el bloqueo
+/// requiere aprobación.
+///
 /// ## Ejemplo TypeScript
 /// ```typescript
 /// await invoke('add_to_lista_negra', {
@@ -165,9 +167,10 @@ pub async fn search_lista_negra(
 #[command]
 pub async fn add_to_lista_negra(
     session: State<'_, SessionState>,
-    input: AddToListaNegraInput,
-) -> Result<ListaNegraResponse, ListaNegraError> {
-    require_perm!(session, "lista_negra", "crear");
+    mut input: AddToListaNegraInput,
+) -> Result<CambioListaNegraResponse, ListaNegraError> {
+    let user = crate::require_perm!(session, "lista_negra:create")?;
+    input.bloqueado_por = user.id;
 
     lista_negra_service::add_to_lista_negra(input).await
 }
@@ -175,7 +178,7 @@ pub async fn add_to_lista_negra(
 /// Actualiza un registro de lista negra existente.
 ///
 /// ## Autenticación
-/// Requiere sesión activa con permiso `lista_negra.editar`.
+/// Requiere sesión activa con permiso `lista_negra:update`.
 ///
 /// ## Campos Actualizables
 /// - `nivel_severidad`: Cambiar gravedad
@@ -190,16 +193,16 @@ pub async fn update_lista_negra(
     session: State<'_, SessionState>,
     id: String,
     input: UpdateListaNegraInput,
-) -> Result<ListaNegraResponse, ListaNegraError> {
-    require_perm!(session, "lista_negra", "editar");
+) -> Result<CambioListaNegraResponse, ListaNegraError> {
+    let user = crate::require_perm!(session, "lista_negra:update")?;
 
-    lista_negra_service::update(id, input).await
+    lista_negra_service::update(id, input, &user.id).await
 }
 
 /// Elimina (desactiva) un registro de lista negra.
 ///
 /// ## Autenticación
-/// Requiere sesión activa con permiso `lista_negra.eliminar`.
+/// Requiere sesión activa con permiso `lista_negra:delete`.
 ///
 /// ## Soft Delete
 /// El registro no se elimina físicamente, solo se marca como inactivo.
@@ -210,16 +213,16 @@ pub async fn update_lista_negra(
 pub async fn delete_from_lista_negra(
     session: State<'_, SessionState>,
     id: String,
-) -> Result<(), ListaNegraError> {
-    require_perm!(session, "lista_negra", "eliminar");
+) -> Result<CambioListaNegraResponse, ListaNegraError> {
+    let user = crate::require_perm!(session, "lista_negra:delete")?;
 
-    lista_negra_service::delete(id).await
+    lista_negra_service::delete(id, &user.id).await
 }
 
 /// Restaura un registro de lista negra previamente eliminado.
 ///
 /// ## Autenticación
-/// Requiere sesión activa con permiso `lista_negra.editar`.
+/// Requiere sesión activa con permiso `lista_negra:update`.
 ///
 /// ## Parámetros
 /// * `id` - ID del registro a restaurar
@@ -227,10 +230,115 @@ pub async fn delete_from_lista_negra(
 pub async fn restore_lista_negra(
     session: State<'_, SessionState>,
     id: String,
-) -> Result<ListaNegraResponse, ListaNegraError> {
-    require_perm!(session, "lista_negra", "editar");
+) -> Result<CambioListaNegraResponse, ListaNegraError> {
+    let user = crate::require_perm!(session, "lista_negra:update")?;
+
+    lista_negra_service::restore(id, &user.id).await
+}
+
+/// Bloqueos con la revisión periódica vencida o próxima a vencer.
+///
+/// ## Autenticación
+/// Requiere sesión activa con permiso `lista_negra:read`.
+#[command]
+pub async fn get_revisiones_lista_negra(
+    session: State<'_, SessionState>,
+) -> Result<Vec<ListaNegraResponse>, ListaNegraError> {
+    crate::require_perm!(session, "lista_negra:read")?;
+
+    lista_negra_service::get_revisiones_pendientes().await
+}
+
+/// Registra la revisión periódica de un bloqueo: lo mantiene o lo levanta.
+///
+/// ## Autenticación
+/// Requiere sesión activa con permiso `lista_negra:update`.
+///
+/// ## Retorno
+/// * `registro` - Bloqueo mantenido con la nueva fecha de revisión
+/// * Sin `registro` - Bloqueo levantado, o propuesta de baja si `aplicado = false`
+#[command]
+pub async fn revisar_lista_negra(
+    session: State<'_, SessionState>,
+    id: String,
+    input: RevisarListaNegraInput,
+) -> Result<CambioListaNegraResponse, ListaNegraError> {
+    let revisor = crate::require_perm!(session, "lista_negra:update")?;
+
+    lista_negra_service::revisar(id, input, &revisor.id).await
+}
+
+// --------------------------------------------------------------------------
+// EMPRESAS VETADAS
+// --------------------------------------------------------------------------
+
+/// Empresas con veto activo.
+///
+/// ## Autenticación
+/// Requiere sesión activa con permiso `lista_negra:read`.
+#[command]
+pub async fn get_empresas_vetadas(
+    session: State<'_, SessionState>,
+) -> Result<Vec<EmpresaVetadaResponse>, ListaNegraError> {
+    crate::require_perm!(session, "lista_negra:read")?;
+
+    lista_negra_service::get_empresas_vetadas().await
+}
+
+/// Veta una empresa completa: el motor deniega o advierte (según severidad) el
+/// ingreso de sus contratistas y proveedores.
+///
+/// ## Autenticación
+/// Requiere sesión activa con permiso `lista_negra:create`.
+///
+/// ## Ejemplo TypeScript
+/// ```typescript
+/// await invoke('vetar_empresa', {
+///     input: {
+///         empresaId: 'empresa:acme',
+///         nivelSeveridad: 'ALTO',
+///         motivo: 'Incumplimiento reiterado de normas de seguridad',
+///         bloquearRegistros: true
+///     }
+/// });
+/// ```
+#[command]
+pub async fn vetar_empresa(
+    session: State<'_, SessionState>,
+    input: VetarEmpresaInput,
+) -> Result<EmpresaVetadaResponse, ListaNegraError> {
+    let usuario = crate::require_perm!(session, "lista_negra:create")?;
+
+    lista_negra_service::vetar_empresa(input, &usuario.id).await
+}
+
+/// Modifica un veto de empresa activo.
+///
+/// ## Autenticación
+/// Requiere sesión activa con permiso `lista_negra:update`.
+#[command]
+pub async fn update_empresa_vetada(
+    session: State<'_, SessionState>,
+    id: String,
+    input: UpdateEmpresaVetadaInput,
+) -> Result<EmpresaVetadaResponse, ListaNegraError> {
+    crate::require_perm!(session, "lista_negra:update")?;
+
+    lista_negra_service::update_empresa_vetada(id, input).await
+}
+
+/// Levanta el veto de una empresa.
+///
+/// ## Autenticación
+/// Requiere sesión activa con permiso `lista_negra:delete`.
+#[command]
+pub async fn levantar_veto_empresa(
+    session: State<'_, SessionState>,
+    id: String,
+) -> Result<(), ListaNegraError> {
+    crate::require_perm!(session, "lista_negra:delete")?;
 
-    lista_negra_service::restore(id).await
+    lista_negra_service::levantar_veto_empresa(id).await
 }
 
 // --------------------------------------------------------------------------
@@ -0,0 +1,69 @@
+//! # Commands Tauri: Evidencias de Lista Negra
+//!
+//! Adjuntos cifrados (fotos, informes) que respaldan un bloqueo.
+//!
+//! ## Seguridad
+//! Usan el permiso dedicado `lista_negra_evidencia:*`, separado de `lista_negra:*`:
+//! el rol de guardia puede consultar si alguien está bloqueado, pero no ve la evidencia.
+//! - `listar_evidencias_lista_negra`: `lista_negra_evidencia:view`
+//! - `get_evidencia_lista_negra`: `lista_negra_evidencia:read` (queda en auditoría)
+//! - `adjuntar_evidencia_lista_negra`: `lista_negra_evidencia:create`
+//! - `delete_evidencia_lista_negra`: `lista_negra_evidencia:delete`
+
+use crate::domain::errors::ListaNegraError;
+use crate::models::lista_negra::{
+    AdjuntarEvidenciaInput, EvidenciaContenidoResponse, EvidenciaListaNegraResponse,
+};
+use crate::services::lista_negra_evidencia_service;
+use crate::services::session::SessionState;
+use tauri::{command, State};
+
+/// Metadatos de las evidencias de un registro (sin contenido).
+#[command]
+pub async fn listar_evidencias_lista_negra(
+    session: State<'_, SessionState>,
+    lista_negra_id: String,
+) -> Result<Vec<EvidenciaListaNegraResponse>, ListaNegraError> {
+    require_perm!(session, "lista_negra_evidencia:view")?;
+    lista_negra_evidencia_service::listar(&lista_negra_id).await
+}
+
+/// Descifra una evidencia, verifica su SHA-256 y la entrega en Base64.
+#[command]
+pub async fn get_evidencia_lista_negra(
+    session: State<'_, SessionState>,
+    id: String,
+) -> Result<EvidenciaContenidoResponse, ListaNegraError> {
+    let user =
+        require_perm!(session, "lista_negra_evidencia:read", "Consultando evidencia de bloqueo")?;
+    lista_negra_evidencia_service::obtener(&id, &user.id).await
+}
+
+/// Adjunta una evidencia (JPEG, PNG, WebP o PDF, máx. 10 MiB) a un registro.
+///
+/// ## Ejemplo TypeScript
+/// ```typescript
+/// await invoke('adjuntar_evidencia_lista_negra', {
+///     input: { listaNegraId: 'lista_negra:abc', filePath: '/ruta/informe.pdf' }
+/// });
+/// ```
+#[command]
+pub async fn adjuntar_evidencia_lista_negra(
+    session: State<'_, SessionState>,
+    input: AdjuntarEvidenciaInput,
+) -> Result<EvidenciaListaNegraResponse, ListaNegraError> {
+    let user =
+        require_perm!(session, "lista_negra_evidencia:create", "Adjuntando evidencia de bloqueo")?;
+    lista_negra_evidencia_service::adjuntar(input, &user.id).await
+}
+
+/// Elimina una evidencia y su archivo cifrado.
+#[command]
+pub async fn delete_evidencia_lista_negra(
+    session: State<'_, SessionState>,
+    id: String,
+) -> Result<(), ListaNegraError> {
+    let user =
+        require_perm!(session, "lista_negra_evidencia:delete", "Eliminando evidencia de bloqueo")?;
+    lista_negra_evidencia_service::eliminar(&id, &user.id).await
+}
@@ -0,0 +1,57 @@
+//! # Commands Tauri: Intercambio de Lista Negra entre Sitios
+//!
+//! Exportación e importación firmada de bloqueos entre plantas hermanas.
+//!
+//! ## Seguridad
+//! - `exportar_lista_negra`: `lista_negra:export`
+//! - `previsualizar_importacion_lista_negra`: `lista_negra:read`
+//! - `importar_lista_negra`: `lista_negra:create` (queda en auditoría)
+
+use crate::domain::errors::ListaNegraError;
+use crate::models::lista_negra::{
+    ExportacionListaNegraResponse, ImportacionListaNegraResponse,
+    PrevisualizacionImportacionListaNegra,
+};
+use crate::services::lista_negra_intercambio_service as service;
+use crate::services::session::SessionState;
+use tauri::{command, State};
+
+/// Exporta los bloqueos vigentes a `target_path` (`.csv` o `.json`) junto a su firma `.sig`.
+///
+/// ## Ejemplo TypeScript
+/// ```typescript
+/// const res = await invoke('exportar_lista_negra', { targetPath: 'C:/intercambio/norte.json' });
+/// // res.firmaPath === 'C:/intercambio/norte.json.sig'
+/// ```
+#[command]
+pub async fn exportar_lista_negra(
+    session: State<'_, SessionState>,
+    target_path: String,
+) -> Result<ExportacionListaNegraResponse, ListaNegraError> {
+    require_perm!(session, "lista_negra:export", "Exportando lista negra para intercambio")?;
+    service::exportar(&target_path).await
+}
+
+/// Verifica la firma de un archivo de intercambio y muestra los cambios sin aplicarlos.
+#[command]
+pub async fn previsualizar_importacion_lista_negra(
+    session: State<'_, SessionState>,
+    file_path: String,
+) -> Result<PrevisualizacionImportacionListaNegra, ListaNegraError> {
+    require_perm!(session, "lista_negra:read")?;
+    service::previsualizar(&file_path).await
+}
+
+/// Aplica un archivo de intercambio: bloqueos nuevos y cambios de severidad.
+#[command]
+pub async fn importar_lista_negra(
+    session: State<'_, SessionState>,
+    file_path: String,
+) -> Result<ImportacionListaNegraResponse, ListaNegraError> {
+    let user = require_perm!(
+        session,
+        "lista_negra:create",
+        format!("Importando lista negra desde {file_path}")
+    )?;
+    service::importar(&file_path, &user.id).await
+}
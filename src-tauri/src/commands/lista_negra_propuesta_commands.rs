@@ -0,0 +1,48 @@
+//! # Commands Tauri: Aprobación de Lista Negra (Doble Control)
+//!
+//! Bandeja del supervisor para las propuestas de alta, modificación y baja que se
+//! generan cuando `lista_negra.requiere_aprobacion` está activo.
+//!
+//! ## Seguridad
+//! Usan el permiso dedicado `lista_negra_aprobacion:*`: tener `lista_negra:create`
+//! permite proponer un bloqueo, no aprobarlo.
+//! - `get_propuestas_lista_negra`: `lista_negra_aprobacion:read`
+//! - `resolver_propuesta_lista_negra`: `lista_negra_aprobacion:update` (queda en auditoría)
+
+use crate::domain::errors::ListaNegraError;
+use crate::models::lista_negra::{PropuestaListaNegraResponse, ResolverPropuestaInput};
+use crate::services::lista_negra_propuesta_service;
+use crate::services::session::SessionState;
+use tauri::{command, State};
+
+/// Propuestas pendientes de revisión, las más antiguas primero.
+#[command]
+pub async fn get_propuestas_lista_negra(
+    session: State<'_, SessionState>,
+) -> Result<Vec<PropuestaListaNegraResponse>, ListaNegraError> {
+    require_perm!(session, "lista_negra_aprobacion:read")?;
+    lista_negra_propuesta_service::get_pendientes().await
+}
+
+/// Aprueba o rechaza una propuesta; el comentario es obligatorio.
+///
+/// ## Ejemplo TypeScript
+/// ```typescript
+/// await invoke('resolver_propuesta_lista_negra', {
+///     id: 'lista_negra_propuesta:abc',
+///     input: { aprobar: true, comentario: 'Confirmado con el video del incidente' }
+/// });
+/// ```
+#[command]
+pub async fn resolver_propuesta_lista_negra(
+    session: State<'_, SessionState>,
+    id: String,
+    input: ResolverPropuestaInput,
+) -> Result<PropuestaListaNegraResponse, ListaNegraError> {
+    let user = require_perm!(
+        session,
+        "lista_negra_aprobacion:update",
+        format!("Resolviendo propuesta de lista negra {id}")
+    )?;
+    lista_negra_propuesta_service::resolver(&id, input, &user.id).await
+}
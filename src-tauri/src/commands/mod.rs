@@ -11,6 +11,7 @@ pub mod audio_commands;
 pub mod cita_commands;
 pub mod config_commands;
 pub mod contratista_commands;
+pub mod documento_contratista_commands;
 pub mod module_commands;
 pub mod system_commands;
 
@@ -18,6 +19,7 @@ pub mod empresa_commands;
 pub mod export_commands;
 pub mod export_profiles;
 pub mod gafete_commands;
+pub mod importacion_commands;
 
 // Servicios de Ingreso Unificados:
 pub mod ingreso_commands;
@@ -27,8 +29,15 @@ pub mod ingreso_visita_commands;
 
 pub mod keyring_commands;
 pub mod lista_negra_commands;
+pub mod lista_negra_evidencia_commands;
+pub mod lista_negra_intercambio_commands;
+pub mod lista_negra_propuesta_commands;
+pub mod pase_commands;
+pub mod praind_digest_commands;
 pub mod proveedor_commands;
+pub mod report_schedules;
 pub mod role_commands;
+pub mod scanner_commands;
 
 pub mod search_commands;
 pub mod security_commands;
@@ -0,0 +1,59 @@
+/// Pases Imprimibles: PDF Entregado al Ingresar.
+///
+/// Permite descargar o reimprimir el pase (con QR del ingreso) de una visita,
+/// contratista o proveedor, y regenerarlo con otra plantilla o con foto.
+use crate::domain::errors::ExportError;
+use crate::models::pase::{PaseResponse, PaseTemplate, PaseTemplateInfo};
+use crate::services::pase_service;
+use crate::services::session::SessionState;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use tauri::State;
+
+/// Obtiene el pase de un ingreso (lo genera si todavía no existe).
+#[tauri::command]
+pub async fn get_pase_ingreso(
+    session: State<'_, SessionState>,
+    ingreso_id: String,
+) -> Result<PaseResponse, ExportError> {
+    require_perm!(session, "ingresos:read")?;
+    pase_service::get_pase(&ingreso_id).await
+}
+
+/// Regenera el pase de un ingreso, opcionalmente con otra plantilla o una foto (base64).
+#[tauri::command]
+pub async fn generar_pase_ingreso(
+    session: State<'_, SessionState>,
+    ingreso_id: String,
+    plantilla: Option<PaseTemplate>,
+    foto_base64: Option<String>,
+) -> Result<PaseResponse, ExportError> {
+    require_perm!(
+        session,
+        "ingresos:create",
+        format!("Regenerando pase del ingreso {ingreso_id}")
+    )?;
+
+    let foto = foto_base64
+        .map(|raw| {
+            // Acepta también data URLs (`data:image/png;base64,...`)
+            let datos = raw.split_once(',').map_or(raw.as_str(), |(_, datos)| datos);
+            STANDARD
+                .decode(datos.trim())
+                .map_err(|e| ExportError::Validation(format!("Foto inválida: {e}")))
+        })
+        .transpose()?;
+
+    pase_service::generar_pase(&ingreso_id, plantilla, foto).await
+}
+
+/// Lista las plantillas de pase disponibles.
+#[tauri::command]
+pub async fn list_pase_templates(
+    session: State<'_, SessionState>,
+) -> Result<Vec<PaseTemplateInfo>, ExportError> {
+    require_perm!(session, "ingresos:read")?;
+    Ok(PaseTemplate::all()
+        .into_iter()
+        .map(|id| PaseTemplateInfo { id, nombre: id.display().to_string() })
+        .collect())
+}
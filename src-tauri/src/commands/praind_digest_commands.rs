@@ -0,0 +1,59 @@
+//! # Commands Tauri: Digest de Vencimiento PRAIND
+//!
+//! Consulta, ejecución manual y exportación de los digests que genera la revisión
+//! diaria de PRAIND (`praind_vigencia_service`).
+//!
+//! ## Seguridad
+//! - `get_digests_praind`: `contratistas:read`
+//! - `ejecutar_revision_praind`: `contratistas:update` (suspende contratistas; queda en auditoría)
+//! - `exportar_digest_praind`: `export:export`
+
+use crate::domain::errors::ExportError;
+use crate::models::contratista::{DigestPraind, ExportarDigestPraindInput};
+use crate::models::export::ExportResponse;
+use crate::services::praind_vigencia_service;
+use crate::services::search_service::SearchService;
+use crate::services::session::SessionState;
+use std::sync::Arc;
+use tauri::{command, State};
+
+/// Digests guardados, del más reciente al más antiguo.
+#[command]
+pub async fn get_digests_praind(
+    session: State<'_, SessionState>,
+) -> Result<Vec<DigestPraind>, ExportError> {
+    require_perm!(session, "contratistas:read")?;
+    praind_vigencia_service::get_digests()
+}
+
+/// Ejecuta la revisión de PRAIND sin esperar a la tarea diaria.
+#[command]
+pub async fn ejecutar_revision_praind(
+    session: State<'_, SessionState>,
+    search_service: State<'_, Arc<SearchService>>,
+) -> Result<DigestPraind, ExportError> {
+    require_perm!(session, "contratistas:update", "Ejecutando revisión de vencimiento PRAIND")?;
+    praind_vigencia_service::ejecutar_revision(Some(search_service.inner().clone())).await
+}
+
+/// Exporta un digest guardado a PDF, Excel o CSV.
+///
+/// ## Ejemplo TypeScript
+/// ```typescript
+/// await invoke('exportar_digest_praind', {
+///     input: { digestId: digest.id, format: 'excel', targetPath: ruta }
+/// });
+/// ```
+#[command]
+pub async fn exportar_digest_praind(
+    session: State<'_, SessionState>,
+    input: ExportarDigestPraindInput,
+) -> Result<ExportResponse, ExportError> {
+    let user = require_perm!(
+        session,
+        "export:export",
+        format!("Exportando digest PRAIND {}", input.digest_id)
+    )?;
+    let generated_by = format!("{} {}", user.nombre, user.apellido);
+    praind_vigencia_service::exportar_digest(input, Some(generated_by)).await
+}
@@ -0,0 +1,64 @@
+/// Reportes Programados: Generación Desatendida de Reportes.
+///
+/// Permite definir reportes que se generan solos en horarios tipo cron (p.ej. el
+/// PDF de movimientos del día o el Excel semanal de PRAIND) y consultar el
+/// historial de ejecuciones con su resultado.
+use crate::domain::errors::ExportError;
+use crate::models::export::{ReportRun, ReportSchedule};
+use crate::services::report_schedule_service;
+use crate::services::session::SessionState;
+use tauri::State;
+
+/// Lista las programaciones de reportes configuradas.
+#[tauri::command]
+pub async fn get_report_schedules(
+    session: State<'_, SessionState>,
+) -> Result<Vec<ReportSchedule>, ExportError> {
+    require_perm!(session, "export:read")?;
+    report_schedule_service::get_all_schedules()
+}
+
+/// Crea o actualiza una programación de reporte.
+#[tauri::command]
+pub async fn save_report_schedule(
+    session: State<'_, SessionState>,
+    schedule: ReportSchedule,
+) -> Result<ReportSchedule, ExportError> {
+    require_perm!(
+        session,
+        "export:update",
+        format!("Guardando reporte programado '{}'", schedule.nombre)
+    )?;
+    report_schedule_service::save_schedule(schedule)
+}
+
+/// Elimina una programación de reporte (el historial se conserva).
+#[tauri::command]
+pub async fn delete_report_schedule(
+    session: State<'_, SessionState>,
+    id: String,
+) -> Result<(), ExportError> {
+    require_perm!(session, "export:delete", format!("Eliminando reporte programado {id}"))?;
+    report_schedule_service::delete_schedule(&id)
+}
+
+/// Ejecuta una programación de inmediato y devuelve el resultado registrado.
+#[tauri::command]
+pub async fn run_report_schedule_now(
+    session: State<'_, SessionState>,
+    id: String,
+) -> Result<ReportRun, ExportError> {
+    require_perm!(session, "export:export", format!("Ejecutando reporte programado {id}"))?;
+    report_schedule_service::run_schedule_now(&id).await
+}
+
+/// Historial de ejecuciones (más recientes primero), opcionalmente de una sola programación.
+#[tauri::command]
+pub async fn get_report_run_history(
+    session: State<'_, SessionState>,
+    schedule_id: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<ReportRun>, ExportError> {
+    require_perm!(session, "export:read")?;
+    report_schedule_service::get_run_history(schedule_id.as_deref(), limit.unwrap_or(100))
+}
@@ -0,0 +1,70 @@
+/// Escáner de Códigos: Cédulas, Gafetes, Citas y Pases.
+///
+/// Recibe las lecturas del escáner de la garita y devuelve el contexto listo para
+/// validar, evitando digitar cédulas y números de gafete a mano.
+use crate::config::manager::save_config;
+use crate::config::settings::{AppConfigState, ScannerConfig};
+use crate::domain::errors::ScanError;
+use crate::models::scanner::{CodigoEscaneado, ScanResolucion};
+use crate::services::scanner_service;
+use crate::services::session::SessionState;
+use tauri::State;
+
+/// Decodifica y resuelve una lectura cruda del escáner.
+#[tauri::command]
+pub async fn procesar_lectura_escaner(
+    session: State<'_, SessionState>,
+    raw: String,
+) -> Result<ScanResolucion, ScanError> {
+    require_perm!(session, "ingresos:read")?;
+    scanner_service::resolver_lectura(&raw).await
+}
+
+/// Solo decodifica la lectura (sin consultar la base de datos), útil para probar el escáner.
+#[tauri::command]
+pub async fn decodificar_lectura_escaner(
+    session: State<'_, SessionState>,
+    raw: String,
+) -> Result<CodigoEscaneado, ScanError> {
+    require_perm!(session, "ingresos:read")?;
+    scanner_service::decodificar(&raw)
+}
+
+/// Obtiene la configuración del escáner.
+#[tauri::command]
+pub async fn get_scanner_config(
+    config: State<'_, AppConfigState>,
+) -> Result<ScannerConfig, ScanError> {
+    let config_guard = config
+        .read()
+        .map_err(|e| ScanError::Config(format!("Error al leer configuración: {e}")))?;
+
+    Ok(config_guard.scanner.clone())
+}
+
+/// Actualiza el prefijo/sufijo configurado en el escáner.
+#[tauri::command]
+pub async fn update_scanner_config(
+    session: State<'_, SessionState>,
+    config: State<'_, AppConfigState>,
+    scanner: ScannerConfig,
+) -> Result<ScannerConfig, ScanError> {
+    require_perm!(session, "config:update", "Actualizando configuración del escáner")?;
+
+    let mut config_guard = config
+        .write()
+        .map_err(|e| ScanError::Config(format!("Error al escribir configuración: {e}")))?;
+
+    config_guard.scanner = scanner;
+
+    let config_path = if let Some(data_dir) = dirs::data_local_dir() {
+        data_dir.join("Brisas").join("brisas.toml")
+    } else {
+        std::path::PathBuf::from("./config/brisas.toml")
+    };
+
+    save_config(&config_guard, &config_path)
+        .map_err(|e| ScanError::Config(format!("Error al guardar configuración: {e}")))?;
+
+    Ok(config_guard.scanner.clone())
+}
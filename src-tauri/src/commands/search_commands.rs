@@ -4,8 +4,13 @@
 /// localizar perfiles de manera instantánea entre miles de registros, superando
 /// las limitaciones de velocidad de las consultas directas a base de datos.
 use crate::domain::errors::SearchError;
+use crate::models::role::Action;
+use crate::search::historial::HistorialSearchResult;
 use crate::search::searcher::SearchResultDto;
-use crate::services::search_service::SearchService;
+use crate::services::historial_search_service::{HistorialQuery, HistorialSearchService};
+use crate::services::search_service::{
+    tipos_visibles, IndexConsistencyReport, SearchPage, SearchQuery, SearchService, MODULO_POR_TIPO,
+};
 use crate::services::session::SessionState;
 use std::sync::Arc;
 use tauri::State;
@@ -25,6 +30,52 @@ pub async fn search_omnibox(
     search_service.search(&query, limit.unwrap_or(20)).map_err(SearchError::Engine)
 }
 
+/// Búsqueda Estructurada: Filtros por tipo, empresa, estado, PRAIND y presencia, con facetas.
+///
+/// Solo devuelve los tipos de entidad cuyo módulo puede leer el usuario (`<módulo>:read`).
+#[tauri::command]
+pub async fn search_structured(
+    session: State<'_, SessionState>,
+    search_service: State<'_, Arc<SearchService>>,
+    mut query: SearchQuery,
+) -> Result<SearchPage, SearchError> {
+    let mut permitidos = Vec::new();
+    for (tipo, modulo) in MODULO_POR_TIPO {
+        if session.require_permission(modulo, Action::Read).await.is_ok() {
+            permitidos.push(tipo);
+        }
+    }
+
+    query.tipos = tipos_visibles(&query.tipos, &permitidos);
+    if query.tipos.is_empty() {
+        return Err(SearchError::Unauthorized(
+            "Sin permiso de lectura sobre los tipos consultados".to_string(),
+        ));
+    }
+    search_service.search_structured(&query).await.map_err(SearchError::Engine)
+}
+
+/// Historial de Movimientos: Búsqueda full-text en observaciones, motivo y anfitrión de ingresos.
+#[tauri::command]
+pub async fn search_ingresos_historial(
+    session: State<'_, SessionState>,
+    historial_service: State<'_, Arc<HistorialSearchService>>,
+    query: HistorialQuery,
+) -> Result<HistorialSearchResult, SearchError> {
+    require_perm!(session, "ingresos:read", "Consultando historial de ingresos")?;
+    historial_service.search(&query).await.map_err(SearchError::Engine)
+}
+
+/// Sincronización Manual: Reconstruye el índice del historial de ingresos desde `SurrealDB`.
+#[tauri::command]
+pub async fn reindex_ingresos_historial(
+    session: State<'_, SessionState>,
+    historial_service: State<'_, Arc<HistorialSearchService>>,
+) -> Result<usize, SearchError> {
+    require_perm!(session, "config:update", "Reindexando historial de ingresos")?;
+    historial_service.reindex_all().await.map_err(SearchError::Engine)
+}
+
 /// Sincronización Manual: Forza la reconstrucción del índice de búsqueda global desde `SurrealDB`.
 #[tauri::command]
 pub async fn reindex_global_search(
@@ -35,6 +86,16 @@ pub async fn reindex_global_search(
     search_service.reindex_all().await.map_err(SearchError::Engine)
 }
 
+/// Diagnóstico: Compara los documentos indexados por tipo con los registros de `SurrealDB`.
+#[tauri::command]
+pub async fn check_search_index_consistency(
+    session: State<'_, SessionState>,
+    search_service: State<'_, Arc<SearchService>>,
+) -> Result<IndexConsistencyReport, SearchError> {
+    require_perm!(session, "config:read", "Verificando consistencia del índice de búsqueda")?;
+    search_service.check_consistency().await.map_err(SearchError::Engine)
+}
+
 /// Búsqueda Unificada: Consulta global en múltiples entidades (Usuarios, Contratistas, etc.).
 #[tauri::command]
 pub async fn search_global(
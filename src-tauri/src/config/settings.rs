@@ -1,6 +1,10 @@
 // src-tauri/src/config/settings.rs
 
+use crate::models::gafete::{PoliticaAsignacionGafete, RangoGafeteEmpresa};
+use crate::models::lista_negra::{NivelSeveridad, SitioConfiable};
+use crate::models::pase::PaseTemplate;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Configuración completa de la aplicación
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +18,16 @@ pub struct AppConfig {
     pub setup: SetupState,
     #[serde(default)]
     pub backup: BackupConfig,
+    #[serde(default)]
+    pub pases: PaseConfig,
+    #[serde(default)]
+    pub scanner: ScannerConfig,
+    #[serde(default)]
+    pub citas: CitaConfig,
+    #[serde(default)]
+    pub gafetes: GafeteConfig,
+    #[serde(default)]
+    pub lista_negra: ListaNegraConfig,
 }
 
 /// Configuración de backup automático
@@ -56,6 +70,254 @@ impl Default for BackupConfig {
     }
 }
 
+/// Configuración de pases imprimibles entregados al ingresar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaseConfig {
+    /// Generar el PDF del pase automáticamente al registrar un ingreso
+    #[serde(default = "default_generar_al_ingresar")]
+    pub generar_al_ingresar: bool,
+    /// Plantilla para ingresos de visitas
+    #[serde(default = "default_plantilla_visita")]
+    pub plantilla_visita: PaseTemplate,
+    /// Plantilla para ingresos de contratistas
+    #[serde(default = "default_plantilla_contratista")]
+    pub plantilla_contratista: PaseTemplate,
+    /// Plantilla para ingresos de proveedores
+    #[serde(default = "default_plantilla_proveedor")]
+    pub plantilla_proveedor: PaseTemplate,
+    /// Horas de validez desde el ingreso; sin valor, el pase vence al final del día
+    #[serde(default)]
+    pub horas_validez: Option<u32>,
+}
+
+const fn default_generar_al_ingresar() -> bool {
+    true
+}
+
+const fn default_plantilla_visita() -> PaseTemplate {
+    PaseTemplate::Visitante
+}
+
+const fn default_plantilla_contratista() -> PaseTemplate {
+    PaseTemplate::ContratistaTemporal
+}
+
+const fn default_plantilla_proveedor() -> PaseTemplate {
+    PaseTemplate::Proveedor
+}
+
+impl Default for PaseConfig {
+    fn default() -> Self {
+        Self {
+            generar_al_ingresar: default_generar_al_ingresar(),
+            plantilla_visita: default_plantilla_visita(),
+            plantilla_contratista: default_plantilla_contratista(),
+            plantilla_proveedor: default_plantilla_proveedor(),
+            horas_validez: None,
+        }
+    }
+}
+
+/// Configuración de la asignación automática de gafetes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GafeteConfig {
+    /// Criterio para proponer el siguiente gafete libre
+    #[serde(default)]
+    pub politica: PoliticaAsignacionGafete,
+    /// Rangos de números reservados por empresa (solo se proponen a su personal)
+    #[serde(default)]
+    pub rangos_empresa: Vec<RangoGafeteEmpresa>,
+    /// Minutos que un gafete propuesto queda apartado para la terminal que lo pidió
+    #[serde(default = "default_reserva_minutos")]
+    pub reserva_minutos: u32,
+}
+
+const fn default_reserva_minutos() -> u32 {
+    5
+}
+
+impl Default for GafeteConfig {
+    fn default() -> Self {
+        Self {
+            politica: PoliticaAsignacionGafete::default(),
+            rangos_empresa: Vec::new(),
+            reserva_minutos: default_reserva_minutos(),
+        }
+    }
+}
+
+/// Vigencia y revisión periódica de los bloqueos de un nivel de severidad
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PoliticaVigencia {
+    /// Meses tras los cuales el bloqueo expira solo (`None` = no expira)
+    #[serde(default)]
+    pub vigencia_meses: Option<u32>,
+    /// Cada cuántos meses debe revisarse el bloqueo
+    #[serde(default = "default_revision_meses")]
+    pub revision_meses: u32,
+}
+
+const fn default_revision_meses() -> u32 {
+    12
+}
+
+/// Configuración de vigencia y revisión de la lista negra por nivel de severidad
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListaNegraConfig {
+    #[serde(default = "default_politica_bajo")]
+    pub bajo: PoliticaVigencia,
+    #[serde(default = "default_politica_medio")]
+    pub medio: PoliticaVigencia,
+    #[serde(default = "default_politica_alto")]
+    pub alto: PoliticaVigencia,
+    /// Días de anticipación con que se avisa una revisión próxima
+    #[serde(default = "default_aviso_revision_dias")]
+    pub aviso_revision_dias: u32,
+    /// Puntaje mínimo (0.5–1.0) para reportar una posible coincidencia por nombre
+    #[serde(default = "default_umbral_coincidencia_nombre")]
+    pub umbral_coincidencia_nombre: f64,
+    /// Nombre del sitio (planta) en los archivos de intercambio; por defecto la ubicación
+    /// de la terminal
+    #[serde(default)]
+    pub sitio: Option<String>,
+    /// Plantas (y sus claves públicas) cuyos archivos de intercambio se aceptan
+    #[serde(default)]
+    pub sitios_confiables: Vec<SitioConfiable>,
+    /// Doble control: altas, modificaciones y bajas quedan como propuesta hasta que un
+    /// supervisor las apruebe
+    #[serde(default)]
+    pub requiere_aprobacion: bool,
+    /// Advertir en el ingreso cuando la persona tiene un bloqueo propuesto sin aprobar
+    #[serde(default = "default_advertir_propuestas")]
+    pub advertir_propuestas: bool,
+}
+
+const fn default_politica_bajo() -> PoliticaVigencia {
+    PoliticaVigencia { vigencia_meses: Some(6), revision_meses: 6 }
+}
+
+const fn default_politica_medio() -> PoliticaVigencia {
+    PoliticaVigencia { vigencia_meses: None, revision_meses: 6 }
+}
+
+const fn default_politica_alto() -> PoliticaVigencia {
+    PoliticaVigencia { vigencia_meses: None, revision_meses: 12 }
+}
+
+const fn default_aviso_revision_dias() -> u32 {
+    15
+}
+
+const fn default_umbral_coincidencia_nombre() -> f64 {
+    0.85
+}
+
+const fn default_advertir_propuestas() -> bool {
+    true
+}
+
+impl ListaNegraConfig {
+    /// Política aplicable a un nivel de severidad
+    pub const fn politica(&self, nivel: &NivelSeveridad) -> PoliticaVigencia {
+        match nivel {
+            NivelSeveridad::Bajo => self.bajo,
+            NivelSeveridad::Medio => self.medio,
+            NivelSeveridad::Alto => self.alto,
+        }
+    }
+}
+
+impl Default for ListaNegraConfig {
+    fn default() -> Self {
+        Self {
+            bajo: default_politica_bajo(),
+            medio: default_politica_medio(),
+            alto: default_politica_alto(),
+            aviso_revision_dias: default_aviso_revision_dias(),
+            umbral_coincidencia_nombre: default_umbral_coincidencia_nombre(),
+            sitio: None,
+            sitios_confiables: Vec::new(),
+            requiere_aprobacion: false,
+            advertir_propuestas: default_advertir_propuestas(),
+        }
+    }
+}
+
+/// Configuración del ciclo de vida y la agenda de citas
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CitaConfig {
+    /// Minutos de tolerancia tras `fecha_inicio` antes de marcar una cita pendiente como vencida
+    #[serde(default = "default_gracia_vencimiento_minutos")]
+    pub gracia_vencimiento_minutos: u32,
+    /// Duración asumida cuando la cita no indica una
+    #[serde(default = "default_duracion_minutos")]
+    pub duracion_default_minutos: u32,
+    /// Citas simultáneas permitidas por anfitrión (`None` = sin límite)
+    #[serde(default)]
+    pub capacidad_por_anfitrion: Option<u32>,
+    /// Citas simultáneas permitidas por área (clave: nombre del área, sin distinguir mayúsculas)
+    #[serde(default)]
+    pub capacidad_por_area: HashMap<String, u32>,
+    /// Límite para las áreas que no aparecen en `capacidad_por_area` (`None` = sin límite)
+    #[serde(default)]
+    pub capacidad_area_default: Option<u32>,
+    /// Inicio de la jornada para la búsqueda de horarios libres (HH:MM)
+    #[serde(default = "default_jornada_inicio")]
+    pub jornada_inicio: String,
+    /// Fin de la jornada para la búsqueda de horarios libres (HH:MM)
+    #[serde(default = "default_jornada_fin")]
+    pub jornada_fin: String,
+    /// Separación entre horarios candidatos en la búsqueda de huecos
+    #[serde(default = "default_intervalo_horarios_minutos")]
+    pub intervalo_horarios_minutos: u32,
+}
+
+const fn default_gracia_vencimiento_minutos() -> u32 {
+    120
+}
+
+const fn default_duracion_minutos() -> u32 {
+    60
+}
+
+fn default_jornada_inicio() -> String {
+    "07:00".to_string()
+}
+
+fn default_jornada_fin() -> String {
+    "17:00".to_string()
+}
+
+const fn default_intervalo_horarios_minutos() -> u32 {
+    30
+}
+
+impl Default for CitaConfig {
+    fn default() -> Self {
+        Self {
+            gracia_vencimiento_minutos: default_gracia_vencimiento_minutos(),
+            duracion_default_minutos: default_duracion_minutos(),
+            capacidad_por_anfitrion: None,
+            capacidad_por_area: HashMap::new(),
+            capacidad_area_default: None,
+            jornada_inicio: default_jornada_inicio(),
+            jornada_fin: default_jornada_fin(),
+            intervalo_horarios_minutos: default_intervalo_horarios_minutos(),
+        }
+    }
+}
+
+/// Configuración del escáner de códigos (teclado o serial)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScannerConfig {
+    /// Prefijo que el escáner antepone a cada lectura (vacío = ninguno)
+    #[serde(default)]
+    pub prefijo: String,
+    /// Sufijo que el escáner agrega a cada lectura, además del Enter (vacío = ninguno)
+    #[serde(default)]
+    pub sufijo: String,
+}
+
 /// Configuración de audio y alertas
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioConfig {
@@ -147,6 +409,11 @@ impl Default for AppConfig {
             audio: AudioConfig::default(),
             setup: SetupState::default(),
             backup: BackupConfig::default(),
+            pases: PaseConfig::default(),
+            scanner: ScannerConfig::default(),
+            citas: CitaConfig::default(),
+            gafetes: GafeteConfig::default(),
+            lista_negra: ListaNegraConfig::default(),
         }
     }
 }
@@ -7,7 +7,9 @@ pub mod surrealdb_utils;
 pub mod surrealdb_alerta_queries;
 pub mod surrealdb_audit_queries;
 pub mod surrealdb_cita_queries;
+pub mod surrealdb_cita_serie_queries;
 pub mod surrealdb_contratista_queries;
+pub mod surrealdb_documento_contratista_queries;
 pub mod surrealdb_empresa_queries;
 pub mod surrealdb_gafete_queries;
 pub mod surrealdb_ingreso_contratista_queries;
@@ -15,6 +17,8 @@ pub mod surrealdb_ingreso_general_queries;
 pub mod surrealdb_ingreso_proveedor_queries;
 pub mod surrealdb_ingreso_queries;
 pub mod surrealdb_ingreso_visita_queries;
+pub mod surrealdb_lista_negra_evidencia_queries;
+pub mod surrealdb_lista_negra_propuesta_queries;
 pub mod surrealdb_lista_negra_queries;
 pub mod surrealdb_proveedor_queries;
 pub mod surrealdb_role_queries;
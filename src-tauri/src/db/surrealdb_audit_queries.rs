@@ -2,7 +2,10 @@
 // src/db/surrealdb_audit_queries.rs
 // ==========================================
 
+use crate::domain::common::parse_record_id;
 use crate::services::surrealdb_service::{get_db, SurrealDbError};
+use serde::Deserialize;
+use surrealdb::RecordId;
 
 pub async fn insert_praind_historial(
     contratista_id: &str,
@@ -89,3 +92,64 @@ pub async fn insert_historial_estado(
 
     Ok(())
 }
+
+/// Motivo del cambio de estado más reciente de un contratista.
+pub async fn ultimo_motivo_estado(contratista_id: &str) -> Result<Option<String>, SurrealDbError> {
+    #[derive(Deserialize)]
+    struct MotivoRow {
+        motivo: String,
+    }
+
+    let db = get_db().await?;
+    let c_id = format!(
+        "contratista:{}",
+        contratista_id.strip_prefix("contratista:").unwrap_or(contratista_id)
+    );
+
+    let row: Option<MotivoRow> = db
+        .query(
+            "SELECT motivo, created_at FROM audit_estado WHERE contratista_id = $contratista_id \
+             ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(("contratista_id", c_id))
+        .await?
+        .take(0)?;
+
+    Ok(row.map(|r| r.motivo))
+}
+
+/// Registra en `audit_log` que un usuario descartó una posible coincidencia por nombre con
+/// el bloqueo `bloqueo_id` para dejar ingresar a la persona.
+pub async fn insert_coincidencia_descartada(
+    bloqueo_id: &str,
+    persona: &RecordId,
+    cedula: &str,
+    usuario: &RecordId,
+) -> Result<(), SurrealDbError> {
+    let db = get_db().await?;
+
+    db.query(
+        r"
+        CREATE audit_log CONTENT {
+            action: 'DESCARTAR_COINCIDENCIA',
+            target_id: $bloqueo,
+            target_table: 'lista_negra',
+            actor: $usuario,
+            change_reason: $motivo,
+            new_state: { persona: $persona, cedula: $cedula }
+        }
+    ",
+    )
+    .bind(("bloqueo", parse_record_id(bloqueo_id, "lista_negra")))
+    .bind(("usuario", usuario.clone()))
+    .bind((
+        "motivo",
+        format!("Coincidencia por nombre descartada al registrar el ingreso de {cedula}"),
+    ))
+    .bind(("persona", persona.clone()))
+    .bind(("cedula", cedula.to_string()))
+    .await?
+    .check()?;
+
+    Ok(())
+}
@@ -3,7 +3,7 @@
 // Enterprise Quality SurrealDB Implementation
 // ==========================================
 
-use crate::models::cita::{Cita, CitaCreateDTO, CitaFetched, EstadoCita};
+use crate::models::cita::{Cita, CitaCreateDTO, CitaFetched, CitaUpdateDTO, EstadoCita};
 use crate::services::surrealdb_service::{get_db, SurrealDbError};
 use surrealdb::RecordId;
 
@@ -79,6 +79,28 @@ pub async fn find_pendientes_fetched() -> Result<Vec<CitaFetched>, SurrealDbErro
     Ok(result.take(0)?)
 }
 
+/// Citas (de cualquier estado) con inicio dentro del rango, con relaciones pobladas.
+pub async fn find_en_rango_fetched(
+    desde: &str,
+    hasta: &str,
+) -> Result<Vec<CitaFetched>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            r"
+            SELECT * FROM cita
+            WHERE fecha_inicio >= type::datetime($desde) AND fecha_inicio <= type::datetime($hasta)
+            ORDER BY fecha_inicio ASC
+            FETCH visitante_id, usuario_id
+        ",
+        )
+        .bind(("desde", desde.to_string()))
+        .bind(("hasta", hasta.to_string()))
+        .await?;
+
+    Ok(result.take(0)?)
+}
+
 // ==========================================
 // QUERIES BÁSICAS (sin FETCH)
 // ==========================================
@@ -99,17 +121,82 @@ pub async fn find_by_visitante(visitante_id: &RecordId) -> Result<Vec<Cita>, Sur
     Ok(result.take(0)?)
 }
 
+/// Cita importada desde el evento iCalendar con ese `UID`, si existe.
+pub async fn find_by_uid_ical(uid: &str) -> Result<Option<Cita>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query("SELECT * FROM cita WHERE uid_ical = $uid LIMIT 1")
+        .bind(("uid", uid.to_string()))
+        .await?;
+
+    Ok(result.take(0)?)
+}
+
 // ==========================================
 // MUTACIONES
 // ==========================================
 
-pub async fn insert(dto: CitaCreateDTO) -> Result<Cita, SurrealDbError> {
+/// Prefijo del error lanzado cuando la cita excede la capacidad dentro de la transacción.
+pub const SIN_CAPACIDAD: &str = "Sin capacidad: ";
+
+/// Límites de citas simultáneas que se verifican al crear (`None` = sin límite).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LimitesCapacidad {
+    pub anfitrion: Option<u32>,
+    pub area: Option<u32>,
+}
+
+/// Pico de citas simultáneas de `$solapadas` cuyo `campo` coincide con `$clave_<campo>`,
+/// y `THROW` si alcanza `$cap_<campo>`. El pico se da al inicio de la nueva franja o al
+/// inicio de alguna cita que empiece dentro de ella (intervalos `[inicio, fin)`).
+fn guarda_capacidad(campo: &str, recurso: &str) -> String {
+    format!(
+        r"
+        IF $cap_{campo} != NONE {{
+            LET $del_recurso = (SELECT * FROM $solapadas WHERE {campo} != NONE
+                AND string::lowercase(string::trim({campo})) = $clave_{campo});
+            LET $puntos = array::concat([$fecha_inicio],
+                (SELECT VALUE inicio FROM $del_recurso WHERE inicio > $fecha_inicio));
+            LET $pico = math::max(array::map($puntos, |$p|
+                count(SELECT * FROM $del_recurso WHERE inicio <= $p AND fin > $p)));
+            IF $pico >= $cap_{campo} {{
+                THROW string::concat('{SIN_CAPACIDAD}{recurso} ', ${campo}, ' ya tiene ',
+                    <string>$pico, ' cita(s) en ese horario (máximo ', <string>$cap_{campo}, ')');
+            }};
+        }};
+        "
+    )
+}
+
+/// Crea la cita verificando la capacidad del anfitrión y del área en la misma
+/// transacción, de modo que dos altas simultáneas no puedan superar el límite.
+///
+/// Si no hay cupo, la transacción se cancela con un error que empieza por
+/// [`SIN_CAPACIDAD`].
+pub async fn insert(dto: CitaCreateDTO, limites: LimitesCapacidad) -> Result<Cita, SurrealDbError> {
     let db = get_db().await?;
 
+    let clave = |v: &Option<String>| v.as_deref().map(|s| s.trim().to_lowercase());
+    let clave_anfitrion = clave(&dto.anfitrion);
+    let clave_area = clave(&dto.area_visitada);
+
     let mut result = db
-        .query(
+        .query(format!(
             r"
-            CREATE cita CONTENT {
+            BEGIN TRANSACTION;
+            LET $fin_efectivo = IF $fecha_fin > $fecha_inicio THEN $fecha_fin ELSE $fecha_inicio + 1s END;
+            LET $solapadas = (
+                SELECT * FROM (
+                    SELECT anfitrion, area_visitada, fecha_inicio AS inicio,
+                        IF fecha_fin > fecha_inicio THEN fecha_fin ELSE fecha_inicio + 1s END AS fin
+                    FROM cita
+                    WHERE estado IN $estados AND fecha_inicio < $fin_efectivo
+                        AND fecha_fin >= $fecha_inicio
+                ) WHERE fin > $fecha_inicio
+            );
+            {}
+            {}
+            CREATE cita CONTENT {{
                 visitante_id: $visitante_id,
                 usuario_id: $usuario_id,
                 motivo: $motivo,
@@ -119,13 +206,18 @@ pub async fn insert(dto: CitaCreateDTO) -> Result<Cita, SurrealDbError> {
                 area_visitada: $area_visitada,
                 visitante_nombre: $visitante_nombre,
                 visitante_cedula: $visitante_cedula,
+                serie: $serie,
+                uid_ical: $uid_ical,
                 estado: $estado,
                 activa: true,
                 created_at: time::now(),
                 updated_at: time::now()
-            }
+            }};
+            COMMIT TRANSACTION;
         ",
-        )
+            guarda_capacidad("anfitrion", "El anfitrión"),
+            guarda_capacidad("area_visitada", "El área"),
+        ))
         .bind(("visitante_id", dto.visitante_id))
         .bind(("usuario_id", dto.usuario_id))
         .bind(("motivo", dto.motivo))
@@ -135,10 +227,22 @@ pub async fn insert(dto: CitaCreateDTO) -> Result<Cita, SurrealDbError> {
         .bind(("area_visitada", dto.area_visitada))
         .bind(("visitante_nombre", dto.visitante_nombre))
         .bind(("visitante_cedula", dto.visitante_cedula))
+        .bind(("serie", dto.serie))
+        .bind(("uid_ical", dto.uid_ical))
         .bind(("estado", EstadoCita::Programada))
-        .await?;
+        .bind(("estados", vec![EstadoCita::Programada, EstadoCita::EnCurso]))
+        .bind(("cap_anfitrion", limites.anfitrion.filter(|_| clave_anfitrion.is_some())))
+        .bind(("cap_area_visitada", limites.area.filter(|_| clave_area.is_some())))
+        .bind(("clave_anfitrion", clave_anfitrion))
+        .bind(("clave_area_visitada", clave_area))
+        .await?
+        .check()?;
 
-    result.take::<Option<Cita>>(0)?.ok_or(SurrealDbError::Query("Error creando cita".to_string()))
+    // El CREATE es la última sentencia de la transacción
+    let ultima = result.num_statements().saturating_sub(1);
+    result
+        .take::<Option<Cita>>(ultima)?
+        .ok_or(SurrealDbError::Query("Error creando cita".to_string()))
 }
 
 pub async fn cancel(id: &RecordId) -> Result<Option<Cita>, SurrealDbError> {
@@ -160,23 +264,185 @@ pub async fn cancel(id: &RecordId) -> Result<Option<Cita>, SurrealDbError> {
     Ok(result.take(0)?)
 }
 
-pub async fn completar(id: &RecordId) -> Result<Option<CitaFetched>, SurrealDbError> {
+/// Marca la cita como `en_curso` y la vincula con el ingreso de visita que la abrió.
+pub async fn iniciar(
+    id: &RecordId,
+    ingreso_id: &RecordId,
+) -> Result<Option<CitaFetched>, SurrealDbError> {
     let db = get_db().await?;
     let mut result = db
         .query(
             r"
             UPDATE $id MERGE {
-                activa: false,
                 estado: $estado,
+                ingreso_visita: $ingreso,
                 updated_at: time::now()
             };
             SELECT * FROM $id FETCH visitante_id, usuario_id
         ",
         )
         .bind(("id", id.clone()))
-        .bind(("estado", EstadoCita::Finalizada))
+        .bind(("ingreso", ingreso_id.clone()))
+        .bind(("estado", EstadoCita::EnCurso))
         .await?;
 
-    // Take the second statement result (the SELECT)
     Ok(result.take(1)?)
 }
+
+/// Completa la cita `en_curso` vinculada a un ingreso de visita (al registrar su salida).
+pub async fn completar_por_ingreso(ingreso_id: &RecordId) -> Result<Vec<Cita>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            r"
+            UPDATE cita SET
+                activa = false,
+                estado = $completada,
+                updated_at = time::now()
+            WHERE ingreso_visita = $ingreso AND estado = $en_curso
+        ",
+        )
+        .bind(("ingreso", ingreso_id.clone()))
+        .bind(("completada", EstadoCita::Finalizada))
+        .bind(("en_curso", EstadoCita::EnCurso))
+        .await?;
+
+    Ok(result.take(0)?)
+}
+
+/// Marca como `vencidas` las citas pendientes cuya hora de inicio es anterior al límite.
+pub async fn vencer_pendientes(limite: surrealdb::Datetime) -> Result<Vec<Cita>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            r"
+            UPDATE cita SET
+                activa = false,
+                estado = $vencida,
+                updated_at = time::now()
+            WHERE activa = true AND estado = $pendiente AND fecha_inicio < $limite
+        ",
+        )
+        .bind(("limite", limite))
+        .bind(("vencida", EstadoCita::Vencida))
+        .bind(("pendiente", EstadoCita::Programada))
+        .await?;
+
+    Ok(result.take(0)?)
+}
+
+/// Citas pendientes o en curso que se cruzan con `[desde, hasta)`.
+///
+/// Incluye las citas sin duración (`fecha_fin == fecha_inicio`) que caen en `desde`.
+pub async fn find_ocupadas(
+    desde: surrealdb::Datetime,
+    hasta: surrealdb::Datetime,
+) -> Result<Vec<Cita>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            r"
+            SELECT * FROM cita
+            WHERE estado IN $estados AND fecha_inicio < $hasta AND fecha_fin >= $desde
+            ORDER BY fecha_inicio ASC
+        ",
+        )
+        .bind(("desde", desde))
+        .bind(("hasta", hasta))
+        .bind(("estados", vec![EstadoCita::Programada, EstadoCita::EnCurso]))
+        .await?;
+
+    Ok(result.take(0)?)
+}
+
+/// Citas (de cualquier estado) con inicio dentro del rango.
+pub async fn find_en_rango(desde: &str, hasta: &str) -> Result<Vec<Cita>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            r"
+            SELECT * FROM cita
+            WHERE fecha_inicio >= type::datetime($desde) AND fecha_inicio <= type::datetime($hasta)
+            ORDER BY fecha_inicio ASC
+        ",
+        )
+        .bind(("desde", desde.to_string()))
+        .bind(("hasta", hasta.to_string()))
+        .await?;
+
+    Ok(result.take(0)?)
+}
+
+// ==========================================
+// SERIES RECURRENTES
+// ==========================================
+
+/// Todas las citas generadas por una serie (incluye canceladas, para no regenerarlas).
+pub async fn find_by_serie(serie: &RecordId) -> Result<Vec<Cita>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query("SELECT * FROM cita WHERE serie = $serie ORDER BY fecha_inicio ASC")
+        .bind(("serie", serie.clone()))
+        .await?;
+
+    Ok(result.take(0)?)
+}
+
+/// Aplica cambios de plantilla a las citas pendientes futuras de la serie.
+pub async fn update_pendientes_serie(
+    serie: &RecordId,
+    dto: CitaUpdateDTO,
+) -> Result<Vec<Cita>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            r"
+            UPDATE cita MERGE $datos
+            WHERE serie = $serie AND estado = $pendiente AND fecha_inicio > time::now()
+        ",
+        )
+        .bind(("serie", serie.clone()))
+        .bind(("datos", dto))
+        .bind(("pendiente", EstadoCita::Programada))
+        .await?;
+
+    Ok(result.take(0)?)
+}
+
+/// Elimina las citas pendientes futuras de la serie (antes de regenerarlas con otra regla).
+pub async fn delete_pendientes_serie(serie: &RecordId) -> Result<(), SurrealDbError> {
+    let db = get_db().await?;
+    db.query(
+        r"
+        DELETE cita
+        WHERE serie = $serie AND estado = $pendiente AND fecha_inicio > time::now()
+    ",
+    )
+    .bind(("serie", serie.clone()))
+    .bind(("pendiente", EstadoCita::Programada))
+    .await?
+    .check()?;
+
+    Ok(())
+}
+
+/// Cancela las citas pendientes futuras de la serie.
+pub async fn cancel_pendientes_serie(serie: &RecordId) -> Result<Vec<Cita>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            r"
+            UPDATE cita SET
+                activa = false,
+                estado = $cancelada,
+                updated_at = time::now()
+            WHERE serie = $serie AND estado = $pendiente AND fecha_inicio > time::now()
+        ",
+        )
+        .bind(("serie", serie.clone()))
+        .bind(("cancelada", EstadoCita::Cancelada))
+        .bind(("pendiente", EstadoCita::Programada))
+        .await?;
+
+    Ok(result.take(0)?)
+}
@@ -0,0 +1,129 @@
+// ==========================================
+// src/db/surrealdb_cita_serie_queries.rs
+// Series de citas recurrentes
+// ==========================================
+
+use crate::models::cita::{CitaSerie, CitaSerieCreateDTO, CitaSerieUpdateDTO, ReglaRecurrencia};
+use crate::services::surrealdb_service::{get_db, SurrealDbError};
+use surrealdb::RecordId;
+
+// ==========================================
+// CONSULTAS
+// ==========================================
+
+pub async fn find_by_id(id: &RecordId) -> Result<Option<CitaSerie>, SurrealDbError> {
+    let db = get_db().await?;
+    let result = db.select(id.clone()).await?;
+    Ok(result)
+}
+
+pub async fn find_activas() -> Result<Vec<CitaSerie>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result =
+        db.query("SELECT * FROM cita_serie WHERE activa = true ORDER BY fecha_inicio ASC").await?;
+    Ok(result.take(0)?)
+}
+
+// ==========================================
+// MUTACIONES
+// ==========================================
+
+pub async fn insert(dto: CitaSerieCreateDTO) -> Result<CitaSerie, SurrealDbError> {
+    let db = get_db().await?;
+    let regla = dto.regla;
+
+    let mut result = db
+        .query(
+            r"
+            CREATE cita_serie CONTENT {
+                visitante_id: $visitante_id,
+                usuario_id: $usuario_id,
+                motivo: $motivo,
+                anfitrion: $anfitrion,
+                area_visitada: $area_visitada,
+                visitante_nombre: $visitante_nombre,
+                visitante_cedula: $visitante_cedula,
+                fecha_inicio: $fecha_inicio,
+                duracion_minutos: $duracion_minutos,
+                frecuencia: $frecuencia,
+                intervalo: $intervalo,
+                dias_semana: $dias_semana,
+                hasta: $hasta,
+                repeticiones: $repeticiones,
+                excluir: $excluir,
+                activa: true,
+                created_at: time::now(),
+                updated_at: time::now()
+            }
+        ",
+        )
+        .bind(("visitante_id", dto.visitante_id))
+        .bind(("usuario_id", dto.usuario_id))
+        .bind(("motivo", dto.motivo))
+        .bind(("anfitrion", dto.anfitrion))
+        .bind(("area_visitada", dto.area_visitada))
+        .bind(("visitante_nombre", dto.visitante_nombre))
+        .bind(("visitante_cedula", dto.visitante_cedula))
+        .bind(("fecha_inicio", dto.fecha_inicio))
+        .bind(("duracion_minutos", dto.duracion_minutos))
+        .bind(("frecuencia", regla.frecuencia))
+        .bind(("intervalo", regla.intervalo))
+        .bind(("dias_semana", regla.dias_semana))
+        .bind(("hasta", regla.hasta))
+        .bind(("repeticiones", regla.repeticiones))
+        .bind(("excluir", regla.excluir))
+        .await?;
+
+    let serie: Option<CitaSerie> = result.take(0)?;
+    serie.ok_or(SurrealDbError::Query("Fallo al crear serie de citas".to_string()))
+}
+
+pub async fn update(
+    id: &RecordId,
+    dto: CitaSerieUpdateDTO,
+) -> Result<Option<CitaSerie>, SurrealDbError> {
+    let db = get_db().await?;
+    let result: Option<CitaSerie> = db.update(id.clone()).merge(dto).await?;
+    Ok(result)
+}
+
+/// Reemplaza la regla completa (los campos opcionales pueden quedar en `NONE`).
+pub async fn update_regla(
+    id: &RecordId,
+    regla: ReglaRecurrencia,
+) -> Result<Option<CitaSerie>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            r"
+            UPDATE $id SET
+                frecuencia = $frecuencia,
+                intervalo = $intervalo,
+                dias_semana = $dias_semana,
+                hasta = $hasta,
+                repeticiones = $repeticiones,
+                excluir = $excluir,
+                updated_at = time::now()
+        ",
+        )
+        .bind(("id", id.clone()))
+        .bind(("frecuencia", regla.frecuencia))
+        .bind(("intervalo", regla.intervalo))
+        .bind(("dias_semana", regla.dias_semana))
+        .bind(("hasta", regla.hasta))
+        .bind(("repeticiones", regla.repeticiones))
+        .bind(("excluir", regla.excluir))
+        .await?;
+
+    Ok(result.take(0)?)
+}
+
+pub async fn desactivar(id: &RecordId) -> Result<Option<CitaSerie>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query("UPDATE $id SET activa = false, updated_at = time::now()")
+        .bind(("id", id.clone()))
+        .await?;
+
+    Ok(result.take(0)?)
+}
@@ -40,6 +40,22 @@ pub async fn create(dto: ContratistaCreateDTO) -> Result<ContratistaFetched, Sur
     }
 }
 
+/// Crea varios contratistas en una sola sentencia (todo o nada).
+///
+/// Usado por la importación masiva: si un registro falla, no se crea ninguno.
+pub async fn create_lote(
+    dtos: Vec<ContratistaCreateDTO>,
+) -> Result<Vec<ContratistaFetched>, SurrealDbError> {
+    let db = get_db().await?;
+
+    let creados: Vec<Contratista> =
+        db.query("INSERT INTO contratista $dtos").bind(("dtos", dtos)).await?.take(0)?;
+    let ids: Vec<RecordId> = creados.into_iter().map(|c| c.id).collect();
+
+    let mut result = db.query("SELECT * FROM $ids FETCH empresa").bind(("ids", ids)).await?;
+    Ok(result.take(0)?)
+}
+
 /// Busca un contratista por su ID (sin relaciones).
 ///
 /// ## Query
@@ -167,6 +183,82 @@ pub async fn find_all_fetched() -> Result<Vec<ContratistaFetched>, SurrealDbErro
     Ok(result.take(0)?)
 }
 
+/// Página de contratistas activos con empresa expandida, en orden estable por ID.
+///
+/// Para recorridos completos (reindexación de búsqueda, reportes) sin el tope de
+/// `find_all_fetched`.
+///
+/// ## Query
+/// ```sql
+/// SELECT * FROM contratista
+/// WHERE deleted_at IS NONE
+/// ORDER BY id LIMIT $limit START $start
+/// FETCH empresa
+/// ```
+pub async fn find_page_fetched(
+    start: usize,
+    limit: usize,
+) -> Result<Vec<ContratistaFetched>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            "SELECT * FROM contratista WHERE deleted_at IS NONE \
+             ORDER BY id LIMIT $limit START $start FETCH empresa",
+        )
+        .bind(("limit", limit))
+        .bind(("start", start))
+        .await?;
+    Ok(result.take(0)?)
+}
+
+/// Obtiene los contratistas activos cuyo PRAIND vence antes de `limite` (incluye vencidos).
+///
+/// Usado por la revisión diaria de PRAIND; sin límite de registros para no dejar
+/// contratistas fuera del digest.
+///
+/// ## Query
+/// ```sql
+/// SELECT * FROM contratista
+/// WHERE deleted_at IS NONE AND estado = 'activo' AND fecha_vencimiento_praind < $limite
+/// FETCH empresa
+/// ```
+pub async fn find_activos_praind_antes_de(
+    limite: surrealdb::Datetime,
+) -> Result<Vec<ContratistaFetched>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            r"
+            SELECT * FROM contratista
+            WHERE deleted_at IS NONE AND estado = 'activo' AND fecha_vencimiento_praind < $limite
+            FETCH empresa
+        ",
+        )
+        .bind(("limite", limite))
+        .await?;
+    Ok(result.take(0)?)
+}
+
+/// Cuenta los contratistas no eliminados (los mismos que indexa el motor de búsqueda).
+///
+/// ## Query
+/// ```sql
+/// SELECT count() FROM contratista WHERE deleted_at IS NONE GROUP ALL
+/// ```
+pub async fn count_active() -> Result<u64, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result =
+        db.query("SELECT count() FROM contratista WHERE deleted_at IS NONE GROUP ALL").await?;
+
+    #[derive(serde::Deserialize)]
+    struct CountResult {
+        count: u64,
+    }
+
+    let count: Option<CountResult> = result.take(0)?;
+    Ok(count.map_or(0, |c| c.count))
+}
+
 /// Obtiene contratistas por empresa.
 ///
 /// ## Query
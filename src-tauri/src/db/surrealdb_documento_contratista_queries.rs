@@ -0,0 +1,124 @@
+//! # Queries `SurrealDB`: Documentos de Contratista
+//!
+//! Documentos adicionales al PRAIND y reglas de documentos requeridos. El escaneo
+//! vive cifrado en disco (ver `documento_contratista_service`); aquí solo se guarda
+//! la referencia al archivo y su SHA-256.
+//!
+//! ## Tablas: `documento_contratista`, `requisito_documento`
+
+use crate::models::documento_contratista::{
+    DocumentoContratista, DocumentoContratistaCreateDTO, DocumentoContratistaUpdateDTO,
+    RequisitoDocumento, RequisitoDocumentoCreateDTO,
+};
+use crate::services::surrealdb_service::{get_db, SurrealDbError};
+use log::info;
+use surrealdb::RecordId;
+
+// --------------------------------------------------------------------------
+// DOCUMENTOS
+// --------------------------------------------------------------------------
+
+/// Registra un documento.
+pub async fn create(
+    dto: DocumentoContratistaCreateDTO,
+) -> Result<DocumentoContratista, SurrealDbError> {
+    let db = get_db().await?;
+
+    let created: Option<DocumentoContratista> =
+        db.query("CREATE documento_contratista CONTENT $dto").bind(("dto", dto)).await?.take(0)?;
+
+    let created = created
+        .ok_or_else(|| SurrealDbError::Query("No se pudo registrar el documento".to_string()))?;
+    info!(
+        "📄 Documento registrado: id={}, contratista={}, tipo={}",
+        created.id,
+        created.contratista,
+        created.tipo.as_str()
+    );
+    Ok(created)
+}
+
+/// Busca un documento por su ID.
+pub async fn find_by_id(id: &RecordId) -> Result<Option<DocumentoContratista>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db.query("SELECT * FROM $id").bind(("id", id.clone())).await?;
+    Ok(result.take(0)?)
+}
+
+/// Documentos de un contratista, agrupados por tipo y con el vencimiento más lejano primero.
+///
+/// ## Query
+/// ```sql
+/// SELECT * FROM documento_contratista WHERE contratista = $contratista
+/// ORDER BY tipo ASC, fecha_vencimiento DESC
+/// ```
+pub async fn find_by_contratista(
+    contratista: &RecordId,
+) -> Result<Vec<DocumentoContratista>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            "SELECT * FROM documento_contratista WHERE contratista = $contratista \
+             ORDER BY tipo ASC, fecha_vencimiento DESC",
+        )
+        .bind(("contratista", contratista.clone()))
+        .await?;
+    Ok(result.take(0)?)
+}
+
+/// Actualización parcial de un documento.
+pub async fn update(
+    id: &RecordId,
+    dto: DocumentoContratistaUpdateDTO,
+) -> Result<DocumentoContratista, SurrealDbError> {
+    let db = get_db().await?;
+    let updated: Option<DocumentoContratista> = db.update(id.clone()).merge(dto).await?;
+    updated.ok_or_else(|| SurrealDbError::Query(format!("Documento no encontrado: {id}")))
+}
+
+/// Elimina los metadatos de un documento (el escaneo se borra en el servicio).
+pub async fn delete(id: &RecordId) -> Result<(), SurrealDbError> {
+    let db = get_db().await?;
+    db.query("DELETE $id").bind(("id", id.clone())).await?.check()?;
+    Ok(())
+}
+
+// --------------------------------------------------------------------------
+// REQUISITOS
+// --------------------------------------------------------------------------
+
+/// Registra una regla de documento requerido.
+pub async fn create_requisito(
+    dto: RequisitoDocumentoCreateDTO,
+) -> Result<RequisitoDocumento, SurrealDbError> {
+    let db = get_db().await?;
+
+    let created: Option<RequisitoDocumento> =
+        db.query("CREATE requisito_documento CONTENT $dto").bind(("dto", dto)).await?.take(0)?;
+
+    created.ok_or_else(|| SurrealDbError::Query("No se pudo registrar el requisito".to_string()))
+}
+
+/// Todas las reglas de documentos requeridos.
+pub async fn find_requisitos() -> Result<Vec<RequisitoDocumento>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result =
+        db.query("SELECT * FROM requisito_documento ORDER BY tipo ASC, created_at ASC").await?;
+    Ok(result.take(0)?)
+}
+
+/// Busca una regla por su ID.
+pub async fn find_requisito_by_id(
+    id: &RecordId,
+) -> Result<Option<RequisitoDocumento>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db.query("SELECT * FROM $id").bind(("id", id.clone())).await?;
+    Ok(result.take(0)?)
+}
+
+/// Elimina una regla.
+pub async fn delete_requisito(id: &RecordId) -> Result<(), SurrealDbError> {
+    let db = get_db().await?;
+    db.query("DELETE $id").bind(("id", id.clone())).await?.check()?;
+    Ok(())
+}
@@ -34,6 +34,27 @@ pub async fn exists_by_name(nombre: &str) -> Result<bool, SurrealDbError> {
     Ok(!empresas.is_empty())
 }
 
+pub async fn exists_by_cedula_juridica(
+    cedula_juridica: &str,
+    excluir: Option<&RecordId>,
+) -> Result<bool, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query("SELECT id FROM empresa WHERE cedula_juridica = $cedula AND id != $excluir")
+        .bind(("cedula", cedula_juridica.to_string()))
+        .bind(("excluir", excluir.cloned()))
+        .await?;
+
+    #[derive(serde::Deserialize)]
+    #[allow(dead_code)]
+    struct IdOnly {
+        id: RecordId,
+    }
+
+    let empresas: Vec<IdOnly> = result.take(0)?;
+    Ok(!empresas.is_empty())
+}
+
 pub async fn find_by_id(id: &RecordId) -> Result<Option<Empresa>, SurrealDbError> {
     let db = get_db().await?;
     let result: Option<Empresa> = db.select(id.clone()).await?;
@@ -53,12 +74,43 @@ pub async fn get_empresas_activas() -> Result<Vec<Empresa>, SurrealDbError> {
     Ok(empresas)
 }
 
-pub async fn update(id: &RecordId, dto: EmpresaUpdateDTO) -> Result<Empresa, SurrealDbError> {
+/// Aplica el DTO y quita los campos opcionales indicados (contratista principal,
+/// extremos del contrato) en una sola transacción.
+pub async fn update(
+    id: &RecordId,
+    dto: EmpresaUpdateDTO,
+    quitar: &[&str],
+) -> Result<Empresa, SurrealDbError> {
     let db = get_db().await?;
 
-    let result: Option<Empresa> = db.update(id.clone()).merge(dto).await?;
+    let unset = if quitar.is_empty() {
+        String::new()
+    } else {
+        format!("UPDATE $id UNSET {} RETURN NONE;", quitar.join(", "))
+    };
+    let mut result = db
+        .query(format!(
+            "BEGIN TRANSACTION; {unset} UPDATE $id MERGE $dto RETURN AFTER; COMMIT TRANSACTION;"
+        ))
+        .bind(("id", id.clone()))
+        .bind(("dto", dto))
+        .await?
+        .check()?;
+
+    // El MERGE es la última sentencia de la transacción
+    let ultima = result.num_statements().saturating_sub(1);
+    let actualizada: Option<Empresa> = result.take(ultima)?;
+    actualizada.ok_or(SurrealDbError::Query("No se pudo actualizar la empresa".to_string()))
+}
 
-    result.ok_or(SurrealDbError::Query("No se pudo actualizar la empresa".to_string()))
+/// Subcontratistas directas de una empresa.
+pub async fn find_subempresas(empresa_id: &RecordId) -> Result<Vec<Empresa>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query("SELECT * FROM empresa WHERE empresa_padre = $empresa_id ORDER BY nombre")
+        .bind(("empresa_id", empresa_id.clone()))
+        .await?;
+    Ok(result.take(0)?)
 }
 
 pub async fn delete(id: &RecordId) -> Result<(), SurrealDbError> {
@@ -67,19 +119,121 @@ pub async fn delete(id: &RecordId) -> Result<(), SurrealDbError> {
     Ok(())
 }
 
-pub async fn count_contratistas_by_empresa(empresa_id: &RecordId) -> Result<usize, SurrealDbError> {
+#[derive(serde::Deserialize)]
+struct CountResult {
+    count: usize,
+}
+
+/// Lee el resultado de un `SELECT count() ... GROUP ALL` (sin filas = 0).
+fn take_count(result: &mut surrealdb::Response, index: usize) -> Result<usize, SurrealDbError> {
+    let count: Option<CountResult> = result.take(index)?;
+    Ok(count.map_or(0, |c| c.count))
+}
+
+/// Registros de `tabla` cuyo `campo` apunta a la empresa.
+async fn count_by(
+    tabla: &str,
+    campo: &str,
+    empresa_id: &RecordId,
+) -> Result<usize, SurrealDbError> {
     let db = get_db().await?;
 
     let mut result = db
-        .query("SELECT count() as count FROM contratista WHERE empresa = $empresa_id GROUP ALL")
+        .query(format!(
+            "SELECT count() AS count FROM type::table($tabla) WHERE {campo} = $empresa_id GROUP ALL"
+        ))
+        .bind(("tabla", tabla.to_string()))
         .bind(("empresa_id", empresa_id.clone()))
         .await?;
 
-    #[derive(serde::Deserialize)]
-    struct CountResult {
-        count: usize,
-    }
+    take_count(&mut result, 0)
+}
 
-    let count: Option<CountResult> = result.take(0)?;
-    Ok(count.map_or(0, |c| c.count))
+pub async fn count_contratistas_by_empresa(empresa_id: &RecordId) -> Result<usize, SurrealDbError> {
+    count_by("contratista", "empresa", empresa_id).await
+}
+
+pub async fn count_proveedores_by_empresa(empresa_id: &RecordId) -> Result<usize, SurrealDbError> {
+    count_by("proveedor", "empresa", empresa_id).await
+}
+
+pub async fn count_visitantes_by_empresa(empresa_id: &RecordId) -> Result<usize, SurrealDbError> {
+    count_by("visitante", "empresa", empresa_id).await
+}
+
+pub async fn count_subempresas(empresa_id: &RecordId) -> Result<usize, SurrealDbError> {
+    count_by("empresa", "empresa_padre", empresa_id).await
+}
+
+/// Personas de las empresas indicadas que siguen dentro (ingreso sin salida, con o sin
+/// gafete asignado).
+pub async fn count_personal_dentro(empresas: Vec<RecordId>) -> Result<usize, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(
+            r"
+            SELECT count() AS count FROM ingreso_contratista WHERE fecha_hora_salida IS NONE
+                AND contratista.empresa INSIDE $empresas GROUP ALL;
+            SELECT count() AS count FROM ingreso_proveedor WHERE fecha_hora_salida IS NONE
+                AND proveedor.empresa INSIDE $empresas GROUP ALL;
+            ",
+        )
+        .bind(("empresas", empresas))
+        .await?;
+
+    Ok(take_count(&mut result, 0)? + take_count(&mut result, 1)?)
+}
+
+/// Desactiva las empresas indicadas y a su personal en una sola transacción.
+///
+/// Contratistas activos o suspendidos pasan a `inactivo` (los bloqueados se conservan),
+/// dejando su fila en el historial de estados (`audit_estado`); proveedores activos o
+/// suspendidos pasan a `INACTIVO`.
+///
+/// ## Retorno
+/// IDs de los contratistas y de los proveedores desactivados.
+pub async fn desactivar_en_cascada(
+    empresas: Vec<RecordId>,
+    usuario_id: &str,
+    motivo: &str,
+) -> Result<(Vec<RecordId>, Vec<RecordId>), SurrealDbError> {
+    let db = get_db().await?;
+    let u_id = format!("user:{}", usuario_id.strip_prefix("user:").unwrap_or(usuario_id));
+
+    let mut result = db
+        .query(
+            r"
+            BEGIN TRANSACTION;
+            LET $afectados = (SELECT id, estado FROM contratista WHERE empresa INSIDE $empresas
+                AND estado INSIDE ['activo', 'suspendido'] AND deleted_at IS NONE);
+            FOR $c IN $afectados {
+                CREATE audit_estado CONTENT {
+                    contratista_id: string::concat('contratista:', record::id($c.id)),
+                    estado_anterior: $c.estado,
+                    estado_nuevo: 'inactivo',
+                    usuario_id: $usuario_id,
+                    motivo: $motivo,
+                    created_at: time::now()
+                };
+            };
+            UPDATE empresa SET is_active = false WHERE id INSIDE $empresas RETURN NONE;
+            UPDATE contratista SET estado = 'inactivo' WHERE id INSIDE $afectados.id
+                RETURN VALUE id;
+            UPDATE proveedor SET estado = 'INACTIVO' WHERE empresa INSIDE $empresas
+                AND estado INSIDE ['ACTIVO', 'SUSPENDIDO'] AND deleted_at IS NONE RETURN VALUE id;
+            COMMIT TRANSACTION;
+            ",
+        )
+        .bind(("empresas", empresas))
+        .bind(("usuario_id", u_id))
+        .bind(("motivo", motivo.to_string()))
+        .await?
+        .check()?;
+
+    // Los dos UPDATE de personal son las últimas sentencias de la transacción
+    let ultima = result.num_statements().saturating_sub(1);
+    let contratistas: Vec<RecordId> = result.take(ultima.saturating_sub(1))?;
+    let proveedores: Vec<RecordId> = result.take(ultima)?;
+    Ok((contratistas, proveedores))
 }
@@ -7,7 +7,10 @@
 // src/db/surrealdb_gafete_queries.rs
 // ==========================================
 
-use crate::models::gafete::{Gafete, GafeteCreateDTO};
+use crate::models::gafete::{
+    DeudaGafete, DeudaGafeteCreateDTO, Gafete, GafeteCreateDTO, GafeteMovimiento,
+    GafeteMovimientoCreateDTO,
+};
 use crate::services::surrealdb_service::{get_db, SurrealDbError};
 use surrealdb::RecordId;
 
@@ -26,13 +29,41 @@ pub async fn get_all_gafetes() -> Result<Vec<Gafete>, SurrealDbError> {
     Ok(db.select("gafete").await?)
 }
 
+/// Marca o libera el gafete. Al entregarlo se registra `ultimo_uso`; en ambos casos se
+/// descarta cualquier reserva pendiente.
 pub async fn set_gafete_uso(id: &RecordId, en_uso: bool) -> Result<(), SurrealDbError> {
     let db = get_db().await?;
-    db.query("UPDATE $id SET en_uso = $uso, updated_at = time::now()")
+    db.query(
+        "UPDATE $id SET en_uso = $uso, ultimo_uso = IF $uso THEN time::now() ELSE ultimo_uso END, \
+         reservado_hasta = NONE, reservado_por = NONE, updated_at = time::now()",
+    )
+    .bind(("id", id.clone()))
+    .bind(("uso", en_uso))
+    .await?;
+    Ok(())
+}
+
+/// Entrega el gafete solo si sigue libre y sin reserva vigente de otro usuario.
+///
+/// La condición va en el mismo `UPDATE` (compare-and-set): si otra terminal lo entregó o
+/// lo reservó entre la validación y la entrega, no se modifica nada y retorna `false`.
+pub async fn marcar_en_uso(
+    id: &RecordId,
+    usuario: Option<&RecordId>,
+) -> Result<bool, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            "UPDATE $id SET en_uso = true, ultimo_uso = time::now(), \
+             reservado_hasta = NONE, reservado_por = NONE, updated_at = time::now() \
+             WHERE en_uso = false \
+             AND (reservado_hasta = NONE OR reservado_hasta < time::now() OR reservado_por = $usuario)",
+        )
         .bind(("id", id.clone()))
-        .bind(("uso", en_uso))
+        .bind(("usuario", usuario.cloned()))
         .await?;
-    Ok(())
+    let actualizados: Vec<Gafete> = result.take(0)?;
+    Ok(!actualizados.is_empty())
 }
 
 pub async fn create_gafete(dto: GafeteCreateDTO) -> Result<Gafete, SurrealDbError> {
@@ -72,3 +103,167 @@ pub async fn get_gafetes_disponibles(tipo: &str) -> Result<Vec<Gafete>, SurrealD
         .await?;
     Ok(result.take(0)?)
 }
+
+// ==========================================
+// ASIGNACIÓN AUTOMÁTICA (reservas)
+// ==========================================
+
+/// Gafetes que pueden proponerse: activos, libres y sin reserva vigente de otro usuario.
+pub async fn find_candidatos_asignacion(
+    tipo: &str,
+    usuario: &RecordId,
+) -> Result<Vec<Gafete>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            "SELECT * FROM gafete WHERE tipo = $tipo AND en_uso = false AND estado = 'activo' \
+             AND (reservado_hasta = NONE OR reservado_hasta < time::now() OR reservado_por = $usuario) \
+             ORDER BY numero",
+        )
+        .bind(("tipo", tipo.to_string()))
+        .bind(("usuario", usuario.clone()))
+        .await?;
+    Ok(result.take(0)?)
+}
+
+/// Aparta el gafete para `usuario` hasta `hasta`.
+///
+/// La condición se evalúa dentro del mismo `UPDATE`, así que dos terminales que compiten
+/// por el mismo número no pueden reservarlo a la vez: la perdedora recibe `None`.
+pub async fn reservar(
+    id: &RecordId,
+    usuario: &RecordId,
+    hasta: surrealdb::Datetime,
+) -> Result<Option<Gafete>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            "UPDATE $id SET reservado_hasta = $hasta, reservado_por = $usuario, updated_at = time::now() \
+             WHERE en_uso = false AND estado = 'activo' \
+             AND (reservado_hasta = NONE OR reservado_hasta < time::now() OR reservado_por = $usuario)",
+        )
+        .bind(("id", id.clone()))
+        .bind(("usuario", usuario.clone()))
+        .bind(("hasta", hasta))
+        .await?;
+    let actualizados: Vec<Gafete> = result.take(0)?;
+    Ok(actualizados.into_iter().next())
+}
+
+/// Libera la reserva del gafete si pertenece a `usuario`.
+pub async fn liberar_reserva(
+    numero: i32,
+    tipo: &str,
+    usuario: &RecordId,
+) -> Result<(), SurrealDbError> {
+    let db = get_db().await?;
+    db.query(
+        "UPDATE gafete SET reservado_hasta = NONE, reservado_por = NONE, updated_at = time::now() \
+         WHERE numero = $n AND tipo = $t AND reservado_por = $usuario",
+    )
+    .bind(("n", numero))
+    .bind(("t", tipo.to_string()))
+    .bind(("usuario", usuario.clone()))
+    .await?;
+    Ok(())
+}
+
+// ==========================================
+// HISTORIAL (gafete_movimiento)
+// ==========================================
+
+pub async fn insert_movimiento(
+    dto: GafeteMovimientoCreateDTO,
+) -> Result<GafeteMovimiento, SurrealDbError> {
+    let db = get_db().await?;
+    let result: Option<GafeteMovimiento> =
+        db.query("CREATE gafete_movimiento CONTENT $dto").bind(("dto", dto)).await?.take(0)?;
+    result.ok_or(SurrealDbError::Query("No se pudo registrar el movimiento del gafete".to_string()))
+}
+
+/// Movimientos de un gafete, del más reciente al más antiguo.
+pub async fn find_movimientos(gafete: &RecordId) -> Result<Vec<GafeteMovimiento>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query("SELECT * FROM gafete_movimiento WHERE gafete = $gafete ORDER BY fecha DESC")
+        .bind(("gafete", gafete.clone()))
+        .await?;
+    Ok(result.take(0)?)
+}
+
+// ==========================================
+// DEUDAS (deuda_gafete)
+// ==========================================
+
+pub async fn insert_deuda(dto: DeudaGafeteCreateDTO) -> Result<DeudaGafete, SurrealDbError> {
+    let db = get_db().await?;
+    let result: Option<DeudaGafete> =
+        db.query("CREATE deuda_gafete CONTENT $dto").bind(("dto", dto)).await?.take(0)?;
+    result.ok_or(SurrealDbError::Query("No se pudo registrar la deuda del gafete".to_string()))
+}
+
+/// Deuda pendiente de un gafete concreto (usa `idx_deuda_gafete_numero_tipo`).
+pub async fn find_deuda_pendiente(
+    numero: i32,
+    tipo: &str,
+) -> Result<Option<DeudaGafete>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            "SELECT * FROM deuda_gafete WHERE numero = $n AND tipo = $t AND saldada = false \
+             ORDER BY desde DESC LIMIT 1",
+        )
+        .bind(("n", numero))
+        .bind(("t", tipo.to_string()))
+        .await?;
+    Ok(result.take(0)?)
+}
+
+/// Números con deuda pendiente para un tipo de gafete.
+pub async fn find_numeros_adeudados(tipo: &str) -> Result<Vec<i32>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query("SELECT VALUE numero FROM deuda_gafete WHERE tipo = $t AND saldada = false")
+        .bind(("t", tipo.to_string()))
+        .await?;
+    Ok(result.take(0)?)
+}
+
+/// Deudas pendientes de una persona, de la más antigua a la más reciente.
+pub async fn find_deudas_pendientes_by_cedula(
+    cedula: &str,
+) -> Result<Vec<DeudaGafete>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query("SELECT * FROM deuda_gafete WHERE cedula = $cedula AND saldada = false ORDER BY desde ASC")
+        .bind(("cedula", cedula.to_string()))
+        .await?;
+    Ok(result.take(0)?)
+}
+
+pub async fn find_deudas_pendientes() -> Result<Vec<DeudaGafete>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result =
+        db.query("SELECT * FROM deuda_gafete WHERE saldada = false ORDER BY desde ASC").await?;
+    Ok(result.take(0)?)
+}
+
+/// Salda las deudas pendientes del gafete. Devuelve cuántas se cerraron.
+pub async fn saldar_deudas(
+    numero: i32,
+    tipo: &str,
+    usuario: Option<RecordId>,
+) -> Result<usize, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(
+            "UPDATE deuda_gafete SET saldada = true, saldada_en = time::now(), saldada_por = $usuario \
+             WHERE numero = $n AND tipo = $t AND saldada = false",
+        )
+        .bind(("n", numero))
+        .bind(("t", tipo.to_string()))
+        .bind(("usuario", usuario))
+        .await?;
+    let saldadas: Vec<DeudaGafete> = result.take(0)?;
+    Ok(saldadas.len())
+}
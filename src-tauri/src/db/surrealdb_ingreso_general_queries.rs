@@ -3,10 +3,10 @@
 // Enterprise Quality SurrealDB Implementation
 // ==========================================
 
-use crate::models::ingreso::UniversalIngresoFetched;
+use crate::models::ingreso::{IngresoEliminadoRow, IngresoHistorialRow, UniversalIngresoFetched};
 use crate::services::surrealdb_service::{get_db, SurrealDbError};
 use log::info;
-use surrealdb::RecordId;
+use surrealdb::{Datetime, RecordId};
 
 // NOTE: Now unifies [ingreso_contratista, ingreso_proveedor, ingreso_visita]
 
@@ -70,6 +70,68 @@ pub async fn find_ingresos_abiertos_fetched() -> Result<Vec<UniversalIngresoFetc
     Ok(all_items)
 }
 
+/// IDs de contratistas y proveedores con un ingreso abierto (actualmente dentro).
+pub async fn find_personas_dentro_ids() -> Result<Vec<RecordId>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query("SELECT VALUE contratista FROM ingreso_contratista WHERE fecha_hora_salida IS NONE")
+        .query("SELECT VALUE proveedor FROM ingreso_proveedor WHERE fecha_hora_salida IS NONE")
+        .await?;
+
+    let mut ids: Vec<RecordId> = result.take(0)?;
+    let proveedores: Vec<RecordId> = result.take(1)?;
+    ids.extend(proveedores);
+    Ok(ids)
+}
+
+/// (tabla, tipo, expresión del nombre de empresa) para la proyección del historial.
+const HISTORIAL_TABLAS: [(&str, &str, &str); 3] = [
+    ("ingreso_contratista", "contratista", "contratista.empresa.nombre"),
+    ("ingreso_proveedor", "proveedor", "proveedor.empresa.nombre"),
+    ("ingreso_visita", "visita", "NONE"),
+];
+
+/// Ingresos de las tres tablas en forma plana para el índice de historial.
+///
+/// Con `desde`, solo los registros modificados después de esa fecha (sincronización
+/// incremental); sin él, el historial completo.
+pub async fn find_historial_rows(
+    desde: Option<Datetime>,
+) -> Result<Vec<IngresoHistorialRow>, SurrealDbError> {
+    let db = get_db().await?;
+    // `>=` para no perder filas escritas en el mismo instante que la marca; el servicio
+    // descarta las que ya indexó.
+    let filtro = if desde.is_some() { "WHERE updated_at >= $desde" } else { "" };
+
+    let mut rows = Vec::new();
+    for (tabla, tipo, empresa) in HISTORIAL_TABLAS {
+        let parcial: Vec<IngresoHistorialRow> = db
+            .query(format!(
+                "SELECT id, '{tipo}' AS tipo, cedula, nombre, apellido, segundo_nombre, segundo_apellido, \
+                 {empresa} AS empresa_nombre, anfitrion, area_visitada, motivo, observaciones, placa_vehiculo, \
+                 fecha_hora_ingreso, fecha_hora_salida, updated_at FROM {tabla} {filtro}"
+            ))
+            .bind(("desde", desde.clone()))
+            .await?
+            .take(0)?;
+        rows.extend(parcial);
+    }
+
+    Ok(rows)
+}
+
+/// Lápidas de ingresos borrados desde `desde` (inclusive).
+pub async fn find_historial_eliminados(
+    desde: Datetime,
+) -> Result<Vec<IngresoEliminadoRow>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query("SELECT ingreso, eliminado_en FROM ingreso_eliminado WHERE eliminado_en >= $desde")
+        .bind(("desde", desde))
+        .await?;
+    Ok(result.take(0)?)
+}
+
 pub async fn find_by_id_fetched(
     id: &RecordId,
 ) -> Result<Option<UniversalIngresoFetched>, SurrealDbError> {
@@ -96,6 +158,24 @@ pub async fn find_ingreso_by_gafete_fetched(
     Ok(result.take(0)?)
 }
 
+/// Ingresos abiertos con el número de gafete en las tablas indicadas.
+///
+/// Los números se repiten entre tipos de gafete, así que sin tipo puede haber más de uno.
+pub async fn find_abiertos_by_gafete_fetched(
+    gafete: i32,
+    tablas: &[&str],
+) -> Result<Vec<UniversalIngresoFetched>, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db
+        .query(format!(
+            "SELECT *, <string>type::table(id) AS tipo_ingreso FROM [{}] WHERE gafete_numero = $gafete AND fecha_hora_salida IS NONE {FETCH_ALL}",
+            tablas.join(", ")
+        ))
+        .bind(("gafete", gafete))
+        .await?;
+    Ok(result.take(0)?)
+}
+
 pub async fn find_salidas_in_range_fetched(
     start: &str,
     end: &str,
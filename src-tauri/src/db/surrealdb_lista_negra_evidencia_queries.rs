@@ -0,0 +1,130 @@
+//! # Queries `SurrealDB`: Evidencias de Lista Negra
+//!
+//! Metadatos de los adjuntos que respaldan un bloqueo. El contenido vive cifrado
+//! en disco (ver `lista_negra_evidencia_service`); aquí solo se guarda la referencia
+//! al archivo y su SHA-256.
+
+use crate::models::lista_negra::EvidenciaListaNegra;
+use crate::services::surrealdb_service::{get_db, SurrealDbError};
+use log::{debug, info};
+use surrealdb::RecordId;
+
+/// Datos de una evidencia ya cifrada y escrita en disco.
+pub struct NuevaEvidencia<'a> {
+    pub lista_negra: &'a RecordId,
+    pub archivo_uuid: &'a str,
+    pub nombre_original: &'a str,
+    pub tipo_mime: &'a str,
+    pub tamano_bytes: i64,
+    pub sha256: &'a str,
+    pub descripcion: Option<&'a str>,
+    pub subido_por: &'a str,
+}
+
+/// Registra los metadatos de una evidencia.
+pub async fn create(nueva: NuevaEvidencia<'_>) -> Result<EvidenciaListaNegra, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(
+            "CREATE lista_negra_evidencia CONTENT {
+                lista_negra: $lista_negra,
+                archivo_uuid: $archivo_uuid,
+                nombre_original: $nombre_original,
+                tipo_mime: $tipo_mime,
+                tamano_bytes: $tamano_bytes,
+                sha256: $sha256,
+                descripcion: $descripcion,
+                subido_por: $subido_por,
+                created_at: time::now()
+            }",
+        )
+        .bind(("lista_negra", nueva.lista_negra.clone()))
+        .bind(("archivo_uuid", nueva.archivo_uuid.to_string()))
+        .bind(("nombre_original", nueva.nombre_original.to_string()))
+        .bind(("tipo_mime", nueva.tipo_mime.to_string()))
+        .bind(("tamano_bytes", nueva.tamano_bytes))
+        .bind(("sha256", nueva.sha256.to_string()))
+        .bind(("descripcion", nueva.descripcion.map(str::to_string)))
+        .bind(("subido_por", nueva.subido_por.to_string()))
+        .await
+        .map_err(|e| {
+            SurrealDbError::Query(format!(
+                "Error al registrar evidencia de '{}': {e}",
+                nueva.lista_negra
+            ))
+        })?;
+
+    let created: Option<EvidenciaListaNegra> = result.take(0)?;
+    let created = created.ok_or_else(|| {
+        SurrealDbError::Query("CREATE lista_negra_evidencia no retornó registro".to_string())
+    })?;
+
+    info!("📎 Evidencia registrada: id={}, registro={}", created.id, created.lista_negra);
+    Ok(created)
+}
+
+/// Evidencias de un registro de lista negra, las más recientes primero.
+pub async fn find_by_lista_negra(
+    lista_negra: &RecordId,
+) -> Result<Vec<EvidenciaListaNegra>, SurrealDbError> {
+    debug!("📎 Obteniendo evidencias de {lista_negra}");
+
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(
+            "SELECT * FROM lista_negra_evidencia WHERE lista_negra = $lista_negra \
+             ORDER BY created_at DESC",
+        )
+        .bind(("lista_negra", lista_negra.clone()))
+        .await
+        .map_err(|e| {
+            SurrealDbError::Query(format!("Error al obtener evidencias de '{lista_negra}': {e}"))
+        })?;
+
+    Ok(result.take(0)?)
+}
+
+/// Cuenta las evidencias de un registro.
+pub async fn count_by_lista_negra(lista_negra: &RecordId) -> Result<usize, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(
+            "SELECT VALUE count() FROM lista_negra_evidencia WHERE lista_negra = $lista_negra \
+             GROUP ALL",
+        )
+        .bind(("lista_negra", lista_negra.clone()))
+        .await
+        .map_err(|e| SurrealDbError::Query(format!("Error al contar evidencias: {e}")))?;
+
+    let total: Option<usize> = result.take(0)?;
+    Ok(total.unwrap_or(0))
+}
+
+/// Busca una evidencia por su ID.
+pub async fn find_by_id(id: &RecordId) -> Result<Option<EvidenciaListaNegra>, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query("SELECT * FROM $id")
+        .bind(("id", id.clone()))
+        .await
+        .map_err(|e| SurrealDbError::Query(format!("Error al buscar evidencia '{id}': {e}")))?;
+
+    Ok(result.take(0)?)
+}
+
+/// Elimina los metadatos de una evidencia (el archivo se borra en el servicio).
+pub async fn delete(id: &RecordId) -> Result<(), SurrealDbError> {
+    let db = get_db().await?;
+
+    db.query("DELETE $id")
+        .bind(("id", id.clone()))
+        .await
+        .map_err(|e| SurrealDbError::Query(format!("Error al eliminar evidencia '{id}': {e}")))?
+        .check()?;
+
+    Ok(())
+}
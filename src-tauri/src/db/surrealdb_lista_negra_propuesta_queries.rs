@@ -0,0 +1,173 @@
+//! # Queries `SurrealDB`: Propuestas de Lista Negra
+//!
+//! Cambios a la lista negra que esperan la aprobación de un supervisor (doble control).
+//! La propuesta guarda el input original serializado; se aplica al aprobarse.
+//!
+//! ## Concurrencia
+//! `resolver` solo actualiza propuestas en estado `PENDIENTE`: si dos supervisores
+//! resuelven a la vez, solo uno obtiene el registro.
+
+use crate::models::lista_negra::{
+    EstadoPropuestaListaNegra, PropuestaListaNegra, TipoPropuestaListaNegra,
+};
+use crate::services::surrealdb_service::{get_db, SurrealDbError};
+use log::{debug, info};
+use surrealdb::RecordId;
+
+/// Datos de una propuesta nueva.
+pub struct NuevaPropuesta<'a> {
+    pub tipo: TipoPropuestaListaNegra,
+    pub lista_negra: Option<&'a RecordId>,
+    pub cedula: &'a str,
+    pub nombre_completo: &'a str,
+    pub nivel_severidad: Option<&'a str>,
+    pub motivo: Option<&'a str>,
+    /// Input original en JSON.
+    pub datos: &'a str,
+    pub propuesto_por: &'a str,
+}
+
+/// Registra una propuesta pendiente.
+pub async fn create(nueva: NuevaPropuesta<'_>) -> Result<PropuestaListaNegra, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(
+            "CREATE lista_negra_propuesta CONTENT {
+                tipo: $tipo,
+                lista_negra: $lista_negra,
+                cedula: $cedula,
+                nombre_completo: $nombre_completo,
+                nivel_severidad: $nivel_severidad,
+                motivo: $motivo,
+                datos: $datos,
+                estado: 'PENDIENTE',
+                propuesto_por: $propuesto_por,
+                created_at: time::now()
+            }",
+        )
+        .bind(("tipo", nueva.tipo.as_str().to_string()))
+        .bind(("lista_negra", nueva.lista_negra.cloned()))
+        .bind(("cedula", nueva.cedula.to_string()))
+        .bind(("nombre_completo", nueva.nombre_completo.to_string()))
+        .bind(("nivel_severidad", nueva.nivel_severidad.map(str::to_string)))
+        .bind(("motivo", nueva.motivo.map(str::to_string)))
+        .bind(("datos", nueva.datos.to_string()))
+        .bind(("propuesto_por", nueva.propuesto_por.to_string()))
+        .await
+        .map_err(|e| {
+            SurrealDbError::Query(format!(
+                "Error al registrar propuesta para '{}': {e}",
+                nueva.cedula
+            ))
+        })?;
+
+    let created: Option<PropuestaListaNegra> = result.take(0)?;
+    let created = created.ok_or_else(|| {
+        SurrealDbError::Query("CREATE lista_negra_propuesta no retornó registro".to_string())
+    })?;
+
+    info!(
+        "📝 Propuesta registrada: id={}, tipo={}, cédula={}",
+        created.id,
+        created.tipo.as_str(),
+        created.cedula
+    );
+    Ok(created)
+}
+
+/// Busca una propuesta por su ID.
+pub async fn find_by_id(id: &RecordId) -> Result<Option<PropuestaListaNegra>, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query("SELECT * FROM $id")
+        .bind(("id", id.clone()))
+        .await
+        .map_err(|e| SurrealDbError::Query(format!("Error al buscar propuesta '{id}': {e}")))?;
+
+    Ok(result.take(0)?)
+}
+
+/// Propuestas pendientes, las más antiguas primero (orden de atención).
+pub async fn find_pendientes() -> Result<Vec<PropuestaListaNegra>, SurrealDbError> {
+    debug!("📝 Obteniendo propuestas pendientes");
+
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(
+            "SELECT * FROM lista_negra_propuesta WHERE estado = 'PENDIENTE' \
+             ORDER BY created_at ASC",
+        )
+        .await
+        .map_err(|e| {
+            SurrealDbError::Query(format!("Error al obtener propuestas pendientes: {e}"))
+        })?;
+
+    Ok(result.take(0)?)
+}
+
+/// Propuesta pendiente (de cualquier tipo) para una cédula.
+pub async fn find_pendiente_by_cedula(
+    cedula: &str,
+) -> Result<Option<PropuestaListaNegra>, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(
+            "SELECT * FROM lista_negra_propuesta WHERE cedula = $cedula \
+             AND estado = 'PENDIENTE' ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(("cedula", cedula.trim().to_string()))
+        .await
+        .map_err(|e| {
+            SurrealDbError::Query(format!("Error al buscar propuesta de '{cedula}': {e}"))
+        })?;
+
+    Ok(result.take(0)?)
+}
+
+/// Marca una propuesta pendiente como aprobada o rechazada.
+///
+/// ## Retorno
+/// * `Ok(None)` - La propuesta ya no estaba pendiente (otro supervisor la resolvió)
+pub async fn resolver(
+    id: &RecordId,
+    estado: EstadoPropuestaListaNegra,
+    revisado_por: &str,
+    comentario: &str,
+) -> Result<Option<PropuestaListaNegra>, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(
+            "UPDATE $id SET estado = $estado, revisado_por = $revisado_por, \
+             comentario = $comentario, resuelto_at = time::now() \
+             WHERE estado = 'PENDIENTE' RETURN AFTER",
+        )
+        .bind(("id", id.clone()))
+        .bind(("estado", estado.as_str().to_string()))
+        .bind(("revisado_por", revisado_por.to_string()))
+        .bind(("comentario", comentario.to_string()))
+        .await
+        .map_err(|e| SurrealDbError::Query(format!("Error al resolver propuesta '{id}': {e}")))?;
+
+    Ok(result.take(0)?)
+}
+
+/// Devuelve una propuesta a pendiente cuando su aplicación falló tras aprobarla.
+pub async fn reabrir(id: &RecordId) -> Result<(), SurrealDbError> {
+    let db = get_db().await?;
+
+    db.query(
+        "UPDATE $id SET estado = 'PENDIENTE', revisado_por = NONE, comentario = NONE, \
+         resuelto_at = NONE",
+    )
+    .bind(("id", id.clone()))
+    .await
+    .map_err(|e| SurrealDbError::Query(format!("Error al reabrir propuesta '{id}': {e}")))?
+    .check()?;
+
+    Ok(())
+}
@@ -15,8 +15,8 @@
 //! Queries de lectura filtran por `is_active = true` por defecto.
 
 use crate::models::lista_negra::{
-    AddToListaNegraInput, BlockCheckResponse, ListaNegra, PersonaSearchResult,
-    UpdateListaNegraInput,
+    AddToListaNegraInput, BlockCheckResponse, EmpresaVetada, ListaNegra, PersonaSearchResult,
+    UpdateEmpresaVetadaInput, UpdateListaNegraInput,
 };
 use crate::services::surrealdb_service::{get_db, SurrealDbError};
 use log::{debug, info, warn};
@@ -44,8 +44,12 @@ const MAX_QUERY_RESULTS: usize = 1000;
 /// SELECT nivel_severidad, created_at
 /// FROM lista_negra
 /// WHERE cedula = $cedula AND is_active = true
+///   AND (vigente_hasta = NONE OR vigente_hasta > time::now())
 /// ```
 ///
+/// El vencimiento se evalúa aquí mismo: un bloqueo expirado deja de aplicar
+/// aunque la tarea de vigencia todavía no lo haya desactivado.
+///
 /// ## Optimizaciones
 /// - Selecciona solo campos necesarios (no `SELECT *`)
 /// - Usa índice en `cedula` para búsqueda O(log n)
@@ -73,7 +77,10 @@ pub async fn check_if_blocked_by_cedula(
     }
 
     let mut result = db
-        .query("SELECT nivel_severidad, created_at FROM lista_negra WHERE cedula = $cedula AND is_active = true")
+        .query(
+            "SELECT nivel_severidad, created_at FROM lista_negra WHERE cedula = $cedula AND is_active = true \
+             AND (vigente_hasta = NONE OR vigente_hasta > time::now())",
+        )
         .bind(("cedula", cedula.to_string()))
         .await
         .map_err(|e| SurrealDbError::Query(format!(
@@ -140,6 +147,52 @@ pub async fn find_all() -> Result<Vec<ListaNegra>, SurrealDbError> {
     Ok(registros)
 }
 
+/// Página de registros activos en orden estable por ID (reindexación de búsqueda, reportes).
+///
+/// A diferencia de `find_all`, permite recorrer la lista completa sin el tope de
+/// `MAX_QUERY_RESULTS`.
+pub async fn find_page(start: usize, limit: usize) -> Result<Vec<ListaNegra>, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(
+            "SELECT *, bloqueado_por.nombre as bloqueado_por_nombre
+             FROM lista_negra
+             WHERE is_active = true
+             ORDER BY id
+             LIMIT $limit START $start",
+        )
+        .bind(("limit", limit))
+        .bind(("start", start))
+        .await
+        .map_err(|e| SurrealDbError::Query(format!("Error al paginar lista negra: {e}")))?;
+
+    Ok(result.take(0)?)
+}
+
+/// Cuenta los registros activos de lista negra.
+///
+/// ## Query Ejecutado
+/// ```sql
+/// SELECT count() FROM lista_negra WHERE is_active = true GROUP ALL
+/// ```
+pub async fn count_active() -> Result<u64, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query("SELECT count() FROM lista_negra WHERE is_active = true GROUP ALL")
+        .await
+        .map_err(|e| SurrealDbError::Query(format!("Error al contar lista negra: {e}")))?;
+
+    #[derive(Deserialize)]
+    struct CountResult {
+        count: u64,
+    }
+
+    let rows: Vec<CountResult> = result.take(0)?;
+    Ok(rows.first().map_or(0, |c| c.count))
+}
+
 /// Busca un registro de lista negra por su ID.
 ///
 /// ## Query Ejecutado
@@ -180,6 +233,7 @@ pub async fn find_by_id(id: &RecordId) -> Result<Option<ListaNegra>, SurrealDbEr
 /// ```sql
 /// SELECT * FROM lista_negra
 /// WHERE cedula = $cedula AND is_active = true
+///   AND (vigente_hasta = NONE OR vigente_hasta > time::now())
 /// ```
 ///
 /// ## Uso
@@ -198,7 +252,10 @@ pub async fn find_by_cedula(cedula: &str) -> Result<Option<ListaNegra>, SurrealD
     let db = get_db().await?;
 
     let mut result = db
-        .query("SELECT * FROM lista_negra WHERE cedula = $cedula AND is_active = true")
+        .query(
+            "SELECT * FROM lista_negra WHERE cedula = $cedula AND is_active = true \
+             AND (vigente_hasta = NONE OR vigente_hasta > time::now())",
+        )
         .bind(("cedula", cedula.to_string()))
         .await
         .map_err(|e| {
@@ -239,6 +296,66 @@ pub async fn find_by_cedula_any_status(cedula: &str) -> Result<Option<ListaNegra
     Ok(registro)
 }
 
+/// Obtiene los bloqueos vigentes (activos y no vencidos).
+///
+/// ## Query Ejecutado
+/// ```sql
+/// SELECT * FROM lista_negra
+/// WHERE is_active = true AND (vigente_hasta = NONE OR vigente_hasta > time::now())
+/// LIMIT 1000
+/// ```
+///
+/// ## Uso
+/// Universo de comparación para la coincidencia por nombre cuando el índice de
+/// búsqueda no está disponible.
+pub async fn find_vigentes() -> Result<Vec<ListaNegra>, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(
+            "SELECT * FROM lista_negra WHERE is_active = true \
+             AND (vigente_hasta = NONE OR vigente_hasta > time::now()) LIMIT $limit",
+        )
+        .bind(("limit", MAX_QUERY_RESULTS))
+        .await
+        .map_err(|e| SurrealDbError::Query(format!("Error al obtener bloqueos vigentes: {e}")))?;
+
+    Ok(result.take(0)?)
+}
+
+/// Filtra una lista de IDs dejando solo los bloqueos vigentes.
+///
+/// ## Query Ejecutado
+/// ```sql
+/// SELECT * FROM lista_negra
+/// WHERE id IN $ids AND is_active = true
+///   AND (vigente_hasta = NONE OR vigente_hasta > time::now())
+/// ```
+///
+/// ## Uso
+/// Confirma los candidatos devueltos por el índice de búsqueda, que puede conservar
+/// bloqueos ya vencidos hasta la siguiente reindexación.
+pub async fn find_vigentes_by_ids(ids: Vec<RecordId>) -> Result<Vec<ListaNegra>, SurrealDbError> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(
+            "SELECT * FROM lista_negra WHERE id IN $ids AND is_active = true \
+             AND (vigente_hasta = NONE OR vigente_hasta > time::now())",
+        )
+        .bind(("ids", ids))
+        .await
+        .map_err(|e| {
+            SurrealDbError::Query(format!("Error al confirmar candidatos de lista negra: {e}"))
+        })?;
+
+    Ok(result.take(0)?)
+}
+
 // --------------------------------------------------------------------------
 // OPERACIONES DE ESCRITURA
 // --------------------------------------------------------------------------
@@ -270,7 +387,11 @@ pub async fn find_by_cedula_any_status(cedula: &str) -> Result<Option<ListaNegra
 /// ## Errores
 /// * `SurrealDbError::Query` - Error al crear (ej: constraint violation)
 /// * `SurrealDbError::NotFound` - CREATE no retornó registro (muy raro)
-pub async fn create(input: &AddToListaNegraInput) -> Result<ListaNegra, SurrealDbError> {
+pub async fn create(
+    input: &AddToListaNegraInput,
+    vigente_hasta: Option<Datetime>,
+    proxima_revision: Datetime,
+) -> Result<ListaNegra, SurrealDbError> {
     debug!("➕ Creando registro de lista negra para cédula: {}", input.cedula);
 
     let db = get_db().await?;
@@ -289,6 +410,9 @@ pub async fn create(input: &AddToListaNegraInput) -> Result<ListaNegra, SurrealD
                 motivo_bloqueo: $motivo_bloqueo,
                 bloqueado_por: $bloqueado_por,
                 is_active: true,
+                vigente_hasta: $vigente_hasta,
+                proxima_revision: $proxima_revision,
+                sitio_origen: $sitio_origen,
                 created_at: time::now(),
                 updated_at: time::now()
             }",
@@ -303,6 +427,9 @@ pub async fn create(input: &AddToListaNegraInput) -> Result<ListaNegra, SurrealD
         .bind(("nivel_severidad", input.nivel_severidad.clone()))
         .bind(("motivo_bloqueo", input.motivo_bloqueo.clone()))
         .bind(("bloqueado_por", input.bloqueado_por.clone()))
+        .bind(("vigente_hasta", vigente_hasta))
+        .bind(("proxima_revision", proxima_revision))
+        .bind(("sitio_origen", input.sitio_origen.clone()))
         .await
         .map_err(|e| {
             SurrealDbError::Query(format!(
@@ -489,6 +616,331 @@ pub async fn restore(id: &RecordId) -> Result<ListaNegra, SurrealDbError> {
     }
 }
 
+/// Registra el sitio del que proviene un bloqueo importado.
+pub async fn set_sitio_origen(id: &RecordId, sitio_origen: &str) -> Result<(), SurrealDbError> {
+    let db = get_db().await?;
+
+    db.query("UPDATE $id SET sitio_origen = $sitio_origen, updated_at = time::now()")
+        .bind(("id", id.clone()))
+        .bind(("sitio_origen", sitio_origen.to_string()))
+        .await
+        .map_err(|e| {
+            SurrealDbError::Query(format!("Error al fijar sitio de origen de '{id}': {e}"))
+        })?
+        .check()?;
+
+    Ok(())
+}
+
+// --------------------------------------------------------------------------
+// VIGENCIA Y REVISIÓN
+// --------------------------------------------------------------------------
+
+/// Fija el vencimiento y la próxima revisión de un registro.
+pub async fn set_vigencia(
+    id: &RecordId,
+    vigente_hasta: Option<Datetime>,
+    proxima_revision: Datetime,
+) -> Result<ListaNegra, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(
+            "UPDATE $id SET vigente_hasta = $vigente_hasta, proxima_revision = $proxima_revision, \
+             updated_at = time::now()",
+        )
+        .bind(("id", id.clone()))
+        .bind(("vigente_hasta", vigente_hasta))
+        .bind(("proxima_revision", proxima_revision))
+        .await
+        .map_err(|e| {
+            SurrealDbError::Query(format!("Error al fijar vigencia de lista negra '{id}': {e}"))
+        })?;
+
+    let updated: Option<ListaNegra> = result.take(0)?;
+    updated.ok_or_else(|| {
+        SurrealDbError::Query(format!("Registro de lista negra no encontrado: {id}"))
+    })
+}
+
+/// Registra la revisión de un bloqueo que se mantiene.
+pub async fn marcar_revisado(
+    id: &RecordId,
+    revisado_por: &str,
+    vigente_hasta: Option<Datetime>,
+    proxima_revision: Datetime,
+) -> Result<ListaNegra, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(
+            "UPDATE $id SET ultima_revision = time::now(), revisado_por = $revisado_por, \
+             vigente_hasta = $vigente_hasta, proxima_revision = $proxima_revision, \
+             updated_at = time::now() WHERE is_active = true",
+        )
+        .bind(("id", id.clone()))
+        .bind(("revisado_por", revisado_por.to_string()))
+        .bind(("vigente_hasta", vigente_hasta))
+        .bind(("proxima_revision", proxima_revision))
+        .await
+        .map_err(|e| {
+            SurrealDbError::Query(format!("Error al registrar revisión de lista negra '{id}': {e}"))
+        })?;
+
+    let updated: Option<ListaNegra> = result.take(0)?;
+    updated.ok_or_else(|| {
+        SurrealDbError::Query(format!("Registro de lista negra no encontrado o eliminado: {id}"))
+    })
+}
+
+/// Desactiva (soft delete) los bloqueos cuyo vencimiento ya pasó.
+///
+/// ## Retorno
+/// Registros desactivados en esta pasada.
+pub async fn desactivar_vencidos() -> Result<Vec<ListaNegra>, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(
+            "UPDATE lista_negra SET is_active = false, updated_at = time::now() \
+             WHERE is_active = true AND vigente_hasta != NONE AND vigente_hasta <= time::now()",
+        )
+        .await
+        .map_err(|e| {
+            SurrealDbError::Query(format!("Error al desactivar bloqueos vencidos: {e}"))
+        })?;
+
+    Ok(result.take(0)?)
+}
+
+/// Bloqueos vigentes cuya revisión vence antes de `hasta` (o que nunca se programaron).
+pub async fn find_revision_pendiente(hasta: Datetime) -> Result<Vec<ListaNegra>, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(
+            "SELECT * FROM lista_negra WHERE is_active = true \
+             AND (vigente_hasta = NONE OR vigente_hasta > time::now()) \
+             AND (proxima_revision = NONE OR proxima_revision <= $hasta) \
+             ORDER BY proxima_revision ASC LIMIT $limit",
+        )
+        .bind(("hasta", hasta))
+        .bind(("limit", MAX_QUERY_RESULTS))
+        .await
+        .map_err(|e| {
+            SurrealDbError::Query(format!("Error al consultar revisiones pendientes: {e}"))
+        })?;
+
+    Ok(result.take(0)?)
+}
+
+// --------------------------------------------------------------------------
+// EMPRESAS VETADAS
+// --------------------------------------------------------------------------
+
+/// Proyección común: el veto junto al nombre de la empresa.
+const SELECT_EMPRESA_VETADA: &str =
+    "SELECT *, empresa.nombre AS empresa_nombre FROM empresa_vetada";
+
+/// Veto activo de una empresa (hot-path de registro e ingreso).
+///
+/// ## Query Ejecutado
+/// ```sql
+/// SELECT *, empresa.nombre AS empresa_nombre FROM empresa_vetada
+/// WHERE empresa = $empresa AND is_active = true
+/// ```
+pub async fn check_empresa_vetada(
+    empresa: &RecordId,
+) -> Result<Option<EmpresaVetada>, SurrealDbError> {
+    debug!("🔍 Verificando veto para empresa: {empresa}");
+
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(format!("{SELECT_EMPRESA_VETADA} WHERE empresa = $empresa AND is_active = true"))
+        .bind(("empresa", empresa.clone()))
+        .await
+        .map_err(|e| {
+            SurrealDbError::Query(format!("Error al verificar veto de empresa '{empresa}': {e}"))
+        })?;
+
+    Ok(result.take(0)?)
+}
+
+/// Veto de una empresa sin importar su estado (para reactivar uno levantado).
+pub async fn find_empresa_vetada_any_status(
+    empresa: &RecordId,
+) -> Result<Option<EmpresaVetada>, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(format!("{SELECT_EMPRESA_VETADA} WHERE empresa = $empresa"))
+        .bind(("empresa", empresa.clone()))
+        .await
+        .map_err(|e| {
+            SurrealDbError::Query(format!("Error al buscar veto de empresa '{empresa}': {e}"))
+        })?;
+
+    Ok(result.take(0)?)
+}
+
+/// Busca un veto de empresa por su ID.
+pub async fn find_empresa_vetada_by_id(
+    id: &RecordId,
+) -> Result<Option<EmpresaVetada>, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query("SELECT *, empresa.nombre AS empresa_nombre FROM $id")
+        .bind(("id", id.clone()))
+        .await
+        .map_err(|e| SurrealDbError::Query(format!("Error al buscar veto '{id}': {e}")))?;
+
+    Ok(result.take(0)?)
+}
+
+/// Vetos de empresa activos, los más recientes primero.
+pub async fn find_empresas_vetadas() -> Result<Vec<EmpresaVetada>, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(format!(
+            "{SELECT_EMPRESA_VETADA} WHERE is_active = true ORDER BY created_at DESC LIMIT $limit"
+        ))
+        .bind(("limit", MAX_QUERY_RESULTS))
+        .await
+        .map_err(|e| SurrealDbError::Query(format!("Error al obtener empresas vetadas: {e}")))?;
+
+    Ok(result.take(0)?)
+}
+
+/// Crea el veto de una empresa.
+///
+/// ## Errores
+/// * `SurrealDbError::Query` - La empresa ya tiene un veto (índice único) o fallo de escritura
+pub async fn create_empresa_vetada(
+    empresa: &RecordId,
+    nivel_severidad: &str,
+    motivo: &str,
+    bloquear_registros: bool,
+    bloqueado_por: &str,
+) -> Result<EmpresaVetada, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut result = db
+        .query(
+            "CREATE empresa_vetada CONTENT {
+                empresa: $empresa,
+                nivel_severidad: $nivel_severidad,
+                motivo: $motivo,
+                bloquear_registros: $bloquear_registros,
+                bloqueado_por: $bloqueado_por,
+                is_active: true,
+                created_at: time::now(),
+                updated_at: time::now()
+            }",
+        )
+        .bind(("empresa", empresa.clone()))
+        .bind(("nivel_severidad", nivel_severidad.to_string()))
+        .bind(("motivo", motivo.to_string()))
+        .bind(("bloquear_registros", bloquear_registros))
+        .bind(("bloqueado_por", bloqueado_por.to_string()))
+        .await
+        .map_err(|e| SurrealDbError::Query(format!("Error al vetar empresa '{empresa}': {e}")))?;
+
+    let created: Option<EmpresaVetada> = result.take(0)?;
+    let created = created.ok_or_else(|| {
+        SurrealDbError::Query("CREATE empresa_vetada no retornó registro".to_string())
+    })?;
+
+    info!(
+        "🚫 Empresa vetada: id={}, empresa={empresa}, nivel={}",
+        created.id, created.nivel_severidad
+    );
+    let id = created.id;
+    find_empresa_vetada_by_id(&id)
+        .await?
+        .ok_or_else(|| SurrealDbError::Query(format!("Veto de empresa no encontrado: {id}")))
+}
+
+/// Actualiza los campos presentes de un veto de empresa.
+///
+/// `revetado_por` vuelve a activar un veto levantado (re-veto de la misma empresa): el
+/// veto queda a nombre de quien lo reactiva y con la fecha de hoy.
+pub async fn update_empresa_vetada(
+    id: &RecordId,
+    input: &UpdateEmpresaVetadaInput,
+    revetado_por: Option<&str>,
+) -> Result<EmpresaVetada, SurrealDbError> {
+    let db = get_db().await?;
+
+    let mut set_clauses = vec!["updated_at = time::now()".to_string()];
+    if input.nivel_severidad.is_some() {
+        set_clauses.push("nivel_severidad = $nivel_severidad".to_string());
+    }
+    if input.motivo.is_some() {
+        set_clauses.push("motivo = $motivo".to_string());
+    }
+    if input.bloquear_registros.is_some() {
+        set_clauses.push("bloquear_registros = $bloquear_registros".to_string());
+    }
+    let reactivar = revetado_por.is_some();
+    if reactivar {
+        set_clauses.push("is_active = true".to_string());
+        set_clauses.push("bloqueado_por = $bloqueado_por".to_string());
+        set_clauses.push("created_at = time::now()".to_string());
+    }
+
+    let query = format!(
+        "UPDATE $id SET {}{}",
+        set_clauses.join(", "),
+        if reactivar { "" } else { " WHERE is_active = true" }
+    );
+
+    let mut result = db
+        .query(query)
+        .bind(("id", id.clone()))
+        .bind(("nivel_severidad", input.nivel_severidad.clone()))
+        .bind(("motivo", input.motivo.clone()))
+        .bind(("bloquear_registros", input.bloquear_registros))
+        .bind(("bloqueado_por", revetado_por.map(str::to_string)))
+        .await
+        .map_err(|e| SurrealDbError::Query(format!("Error al actualizar veto '{id}': {e}")))?;
+
+    let updated: Option<EmpresaVetada> = result.take(0)?;
+    if updated.is_none() {
+        return Err(SurrealDbError::Query(format!(
+            "Veto de empresa no encontrado o levantado: {id}"
+        )));
+    }
+
+    info!("✏️ Veto de empresa actualizado: id={id}");
+    find_empresa_vetada_by_id(id)
+        .await?
+        .ok_or_else(|| SurrealDbError::Query(format!("Veto de empresa no encontrado: {id}")))
+}
+
+/// Levanta (soft delete) el veto de una empresa.
+pub async fn levantar_empresa_vetada(id: &RecordId) -> Result<(), SurrealDbError> {
+    warn!("🗑️ Levantando veto de empresa: {id}");
+
+    let db = get_db().await?;
+
+    let mut result = db
+        .query("UPDATE $id SET is_active = false, updated_at = time::now() WHERE is_active = true")
+        .bind(("id", id.clone()))
+        .await
+        .map_err(|e| SurrealDbError::Query(format!("Error al levantar veto '{id}': {e}")))?;
+
+    let updated: Option<EmpresaVetada> = result.take(0)?;
+    match updated {
+        Some(_) => Ok(()),
+        None => Err(SurrealDbError::Query(format!(
+            "Veto de empresa no encontrado o ya levantado: {id}"
+        ))),
+    }
+}
+
 // --------------------------------------------------------------------------
 // BÚSQUEDA AVANZADA
 // --------------------------------------------------------------------------
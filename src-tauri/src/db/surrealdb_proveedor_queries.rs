@@ -34,6 +34,21 @@ pub async fn create(dto: ProveedorCreateDTO) -> Result<ProveedorFetched, Surreal
     })
 }
 
+/// Crea varios proveedores en una sola sentencia (todo o nada).
+pub async fn create_lote(
+    dtos: Vec<ProveedorCreateDTO>,
+) -> Result<Vec<ProveedorFetched>, SurrealDbError> {
+    let db = get_db().await?;
+    debug!("💾 Creando {} proveedores en lote", dtos.len());
+
+    let creados: Vec<Proveedor> =
+        db.query("INSERT INTO proveedor $dtos").bind(("dtos", dtos)).await?.take(0)?;
+    let ids: Vec<RecordId> = creados.into_iter().map(|p| p.id).collect();
+
+    let mut result = db.query("SELECT * FROM $ids FETCH empresa").bind(("ids", ids)).await?;
+    Ok(result.take(0)?)
+}
+
 pub async fn find_by_id(id: &RecordId) -> Result<Option<Proveedor>, SurrealDbError> {
     let db = get_db().await?;
     let result: Option<Proveedor> = db.select(id.clone()).await?;
@@ -72,6 +87,20 @@ pub async fn find_all_fetched() -> Result<Vec<ProveedorFetched>, SurrealDbError>
     Ok(result.take(0)?)
 }
 
+pub async fn count_active() -> Result<u64, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result =
+        db.query("SELECT count() FROM proveedor WHERE deleted_at IS NONE GROUP ALL").await?;
+
+    #[derive(serde::Deserialize)]
+    struct CountResult {
+        count: u64,
+    }
+
+    let count: Option<CountResult> = result.take(0)?;
+    Ok(count.map_or(0, |c| c.count))
+}
+
 pub async fn search(query: &str, limit: usize) -> Result<Vec<ProveedorFetched>, SurrealDbError> {
     let db = get_db().await?;
     let query_upper = query.to_uppercase();
@@ -298,6 +298,19 @@ pub async fn count_by_email_excluding_id(
     Ok(rows.first().map_or(0, |c| c.count))
 }
 
+pub async fn count_all() -> Result<u64, SurrealDbError> {
+    let db = get_db().await?;
+    let mut result = db.query("SELECT count() FROM user GROUP ALL").await?;
+
+    #[derive(Deserialize)]
+    struct CountResult {
+        count: u64,
+    }
+
+    let rows: Vec<CountResult> = result.take(0)?;
+    Ok(rows.first().map_or(0, |c| c.count))
+}
+
 pub async fn get_role_name(role: &RecordId) -> Result<String, SurrealDbError> {
     let db = get_db().await?;
     let mut result = db.query("SELECT name FROM $role").bind(("role", role.clone())).await?;
@@ -325,16 +338,21 @@ pub async fn update_avatar_path(user_id: &str, avatar_path: &str) -> Result<(),
 }
 
 pub async fn get_avatar_path(user_id: &str) -> Result<String, SurrealDbError> {
-    let db = get_db().await?;
     let clean_id = user_id.trim_start_matches("user:").replace(['⟨', '⟩', '<', '>'], "");
-    let user_record = RecordId::from_table_key("user", &clean_id);
+    get_avatar_path_of(&RecordId::from_table_key("user", &clean_id)).await
+}
+
+/// Lee `avatar_path` de cualquier registro con foto (usuario, contratista, proveedor o
+/// visitante). Retorna cadena vacía si el registro no tiene avatar.
+pub async fn get_avatar_path_of(record: &RecordId) -> Result<String, SurrealDbError> {
+    let db = get_db().await?;
 
     #[derive(Deserialize)]
     struct AvatarPath {
         avatar_path: Option<String>,
     }
 
-    let mut result = db.query("SELECT avatar_path FROM $id").bind(("id", user_record)).await?;
+    let mut result = db.query("SELECT avatar_path FROM $id").bind(("id", record.clone())).await?;
 
     let row: Option<AvatarPath> = result.take(0)?;
     Ok(row.and_then(|r| r.avatar_path).unwrap_or_default())
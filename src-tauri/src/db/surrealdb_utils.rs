@@ -3,6 +3,9 @@
 // Utility functions for SurrealDB operations
 // Reduces boilerplate across query modules
 
+use crate::services::surrealdb_service::SurrealDbError;
+use std::future::Future;
+
 /// Strips table prefix from ID if present
 /// Example: "contratista:abc123" -> "abc123"
 pub fn strip_table_prefix<'a>(id: &'a str, table: &str) -> &'a str {
@@ -22,6 +25,29 @@ pub fn normalize_id(id: &str, table: &str) -> String {
     strip_table_prefix(id, table).to_string()
 }
 
+/// Registros por página al recorrer una tabla completa.
+pub const PAGE_SIZE: usize = 500;
+
+/// Lee un feed paginado completo (`pagina(start, limit)`), hasta una página incompleta.
+///
+/// Las consultas `find_all*` tienen tope de seguridad; la reindexación de búsqueda y los
+/// reportes necesitan todos los registros.
+pub async fn leer_paginado<T, F, Fut>(mut pagina: F) -> Result<Vec<T>, SurrealDbError>
+where
+    F: FnMut(usize, usize) -> Fut,
+    Fut: Future<Output = Result<Vec<T>, SurrealDbError>>,
+{
+    let mut todos = Vec::new();
+    loop {
+        let lote = pagina(todos.len(), PAGE_SIZE).await?;
+        let completa = lote.len() == PAGE_SIZE;
+        todos.extend(lote);
+        if !completa {
+            return Ok(todos);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -31,6 +31,16 @@ pub async fn create_visitante(dto: VisitanteCreateDTO) -> Result<Visitante, Surr
     })
 }
 
+/// Crea varios visitantes en una sola sentencia (todo o nada).
+pub async fn create_lote(dtos: Vec<VisitanteCreateDTO>) -> Result<Vec<Visitante>, SurrealDbError> {
+    let db = get_db().await?;
+
+    debug!("DB: Creando {} visitantes en lote", dtos.len());
+    let res: Vec<Visitante> =
+        db.query("INSERT INTO visitante $dtos").bind(("dtos", dtos)).await?.take(0)?;
+    Ok(res)
+}
+
 /// Busca un visitante por su ID interno.
 pub async fn find_by_id(id: &RecordId) -> Result<Option<Visitante>, SurrealDbError> {
     let db = get_db().await?;
@@ -0,0 +1,100 @@
+/// Capa de Dominio: Adjuntos Cifrados.
+///
+/// Reglas puras comunes a los archivos que se guardan cifrados en disco (evidencias de
+/// lista negra y escaneos de documentos de contratista): límite de tamaño y tipo admitido,
+/// identificado por su firma (magic bytes) y no por la extensión.
+use crate::domain::errors::AdjuntoError;
+
+/// Tipos admitidos, identificados por su firma.
+const FIRMAS_ADJUNTO: [(&[u8], &str); 4] = [
+    (b"\xFF\xD8\xFF", "image/jpeg"),
+    (b"\x89PNG\r\n\x1A\n", "image/png"),
+    (b"%PDF-", "application/pdf"),
+    (b"RIFF", "image/webp"),
+];
+
+/// Detecta el tipo MIME de un adjunto a partir de su contenido.
+pub fn detectar_tipo_mime(contenido: &[u8]) -> Option<&'static str> {
+    FIRMAS_ADJUNTO
+        .iter()
+        .find(|(firma, mime)| {
+            contenido.starts_with(firma)
+                && (*mime != "image/webp" || contenido.get(8..12) == Some(b"WEBP".as_slice()))
+        })
+        .map(|(_, mime)| *mime)
+}
+
+/// Valida que un tamaño en bytes no exceda el máximo (se usa antes de leer el archivo).
+pub fn validar_tamano(tamano: u64, max_bytes: usize, etiqueta: &str) -> Result<(), AdjuntoError> {
+    if tamano > u64::try_from(max_bytes).unwrap_or(u64::MAX) {
+        return Err(AdjuntoError::Validation(format!(
+            "El archivo de {etiqueta} excede el máximo de {} MiB",
+            max_bytes / (1024 * 1024)
+        )));
+    }
+    Ok(())
+}
+
+/// Valida tamaño y tipo de un adjunto; retorna el tipo MIME detectado.
+///
+/// # Argumentos
+/// * `etiqueta` - Nombre del adjunto en los mensajes (`evidencia`, `escaneo`).
+pub fn validar_adjunto(
+    contenido: &[u8],
+    max_bytes: usize,
+    etiqueta: &str,
+) -> Result<&'static str, AdjuntoError> {
+    if contenido.is_empty() {
+        return Err(AdjuntoError::Validation(format!("El archivo de {etiqueta} está vacío")));
+    }
+
+    validar_tamano(u64::try_from(contenido.len()).unwrap_or(u64::MAX), max_bytes, etiqueta)?;
+
+    detectar_tipo_mime(contenido).ok_or_else(|| {
+        AdjuntoError::Validation(format!(
+            "Tipo de {etiqueta} no admitido. Solo JPEG, PNG, WebP o PDF"
+        ))
+    })
+}
+
+// --------------------------------------------------------------------------
+// PRUEBAS UNITARIAS
+// --------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX: usize = 10 * 1024 * 1024;
+
+    #[test]
+    fn test_validar_adjunto() {
+        assert_eq!(
+            validar_adjunto(b"\xFF\xD8\xFF\xE0datos", MAX, "evidencia").unwrap(),
+            "image/jpeg"
+        );
+        assert_eq!(validar_adjunto(b"%PDF-1.7\n", MAX, "evidencia").unwrap(), "application/pdf");
+        assert_eq!(
+            validar_adjunto(b"RIFF\0\0\0\0WEBPVP8 ", MAX, "evidencia").unwrap(),
+            "image/webp"
+        );
+
+        // Un RIFF que no es WebP (ej. WAV) o un ejecutable se rechazan
+        assert!(validar_adjunto(b"RIFF\0\0\0\0WAVEfmt ", MAX, "evidencia").is_err());
+        assert!(validar_adjunto(b"MZ\x90\0", MAX, "evidencia").is_err());
+        assert!(validar_adjunto(b"", MAX, "evidencia").is_err());
+
+        let mut grande = b"%PDF-".to_vec();
+        grande.resize(MAX + 1, 0);
+        assert!(validar_adjunto(&grande, MAX, "evidencia").is_err());
+    }
+
+    #[test]
+    fn test_mensajes_usan_la_etiqueta() {
+        let err = validar_adjunto(b"MZ\x90\0", MAX, "escaneo").unwrap_err();
+        assert!(err.to_string().contains("Tipo de escaneo no admitido"));
+
+        let err = validar_tamano(MAX as u64 + 1, MAX, "escaneo").unwrap_err();
+        assert!(err.to_string().contains("El archivo de escaneo excede el máximo de 10 MiB"));
+    }
+}
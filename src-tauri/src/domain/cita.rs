@@ -9,7 +9,13 @@ use crate::domain::common::{
     validar_nombre_estandar,
 };
 use crate::domain::errors::CitaError;
-use crate::models::cita::CreateCitaInput;
+use crate::models::cita::{CreateCitaInput, EstadoCita, InasistenciasAnfitrion, InvitacionCita};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::NaiveTime;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
 
 // --------------------------------------------------------------------------
 // CONSTANTES DE VALIDACIÓN
@@ -24,6 +30,17 @@ pub const AREA_MAX_LEN: usize = 100;
 /// Longitud máxima para el motivo de la visita.
 pub const MOTIVO_MAX_LEN: usize = 255;
 
+/// Prefijo del QR de invitación (`BRISAS:INV:<payload>.<firma>`).
+pub const PREFIJO_INVITACION: &str = "BRISAS:INV:";
+
+/// Minutos antes de la cita desde los que la invitación ya es válida.
+pub const INVITACION_MARGEN_ANTES_MIN: i64 = 120;
+
+/// Minutos después de la cita durante los que la invitación sigue siendo válida.
+pub const INVITACION_MARGEN_DESPUES_MIN: i64 = 240;
+
+const INVITACION_VERSION: &str = "v1";
+
 // --------------------------------------------------------------------------
 // VALIDACIONES DE NEGOCIO
 // --------------------------------------------------------------------------
@@ -74,13 +91,292 @@ pub fn validar_create_input(input: &CreateCitaInput) -> Result<(), CitaError> {
     // 3. Validación de Fecha (Estándar RFC 3339 / ISO 8601)
     validar_fecha_rfc3339(&input.fecha_cita).map_err(|e| CitaError::Validation(e.to_string()))?;
 
+    if let Some(minutos) = input.duracion_minutos {
+        validar_duracion(minutos)?;
+    }
+
     Ok(())
 }
 
 // --------------------------------------------------------------------------
-// PRUEBAS UNITARIAS
+// INVITACIONES FIRMADAS
 // --------------------------------------------------------------------------
 
+/// Ventana de validez (segundos Unix) de la invitación para una cita.
+pub const fn ventana_invitacion(inicio_unix: i64, fin_unix: i64) -> (i64, i64) {
+    (inicio_unix - INVITACION_MARGEN_ANTES_MIN * 60, fin_unix + INVITACION_MARGEN_DESPUES_MIN * 60)
+}
+
+fn firma(payload: &str, clave: &[u8]) -> Result<Hmac<Sha256>, CitaError> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(clave)
+        .map_err(|e| CitaError::Validation(format!("Clave de firma inválida: {e}")))?;
+    mac.update(payload.as_bytes());
+    Ok(mac)
+}
+
+/// Genera el token firmado (HMAC-SHA256) de una invitación.
+pub fn firmar_invitacion(inv: &InvitacionCita, clave: &[u8]) -> Result<String, CitaError> {
+    if inv.cita_id.contains('|') || inv.cedula.contains('|') {
+        return Err(CitaError::Validation("Datos de invitación inválidos".to_string()));
+    }
+
+    let payload = format!(
+        "{INVITACION_VERSION}|{}|{}|{}|{}",
+        inv.cita_id, inv.cedula, inv.valido_desde, inv.valido_hasta
+    );
+    let sig = firma(&payload, clave)?.finalize().into_bytes();
+
+    Ok(format!(
+        "{PREFIJO_INVITACION}{}.{}",
+        URL_SAFE_NO_PAD.encode(payload.as_bytes()),
+        URL_SAFE_NO_PAD.encode(sig)
+    ))
+}
+
+/// Verifica la firma y la ventana de validez de un token de invitación.
+///
+/// `ahora_unix` se recibe como parámetro para mantener la función pura.
+pub fn verificar_invitacion(
+    token: &str,
+    clave: &[u8],
+    ahora_unix: i64,
+) -> Result<InvitacionCita, CitaError> {
+    let invalida = || CitaError::Validation("Invitación inválida o alterada".to_string());
+
+    let cuerpo = token
+        .trim()
+        .get(..PREFIJO_INVITACION.len())
+        .filter(|p| p.eq_ignore_ascii_case(PREFIJO_INVITACION))
+        .map(|_| &token.trim()[PREFIJO_INVITACION.len()..])
+        .ok_or_else(invalida)?;
+
+    let (payload_b64, sig_b64) = cuerpo.split_once('.').ok_or_else(invalida)?;
+    let payload = URL_SAFE_NO_PAD.decode(payload_b64).map_err(|_| invalida())?;
+    let sig = URL_SAFE_NO_PAD.decode(sig_b64).map_err(|_| invalida())?;
+
+    // Comparación en tiempo constante
+    firma(std::str::from_utf8(&payload).map_err(|_| invalida())?, clave)?
+        .verify_slice(&sig)
+        .map_err(|_| invalida())?;
+
+    let payload = String::from_utf8(payload).map_err(|_| invalida())?;
+    let [version, cita_id, cedula, desde, hasta] = payload.split('|').collect::<Vec<_>>()[..]
+    else {
+        return Err(invalida());
+    };
+    if version != INVITACION_VERSION {
+        return Err(invalida());
+    }
+
+    let inv = InvitacionCita {
+        cita_id: cita_id.to_string(),
+        cedula: cedula.to_string(),
+        valido_desde: desde.parse().map_err(|_| invalida())?,
+        valido_hasta: hasta.parse().map_err(|_| invalida())?,
+    };
+
+    if ahora_unix < inv.valido_desde {
+        return Err(CitaError::Validation(
+            "La invitación todavía no es válida (llegada demasiado anticipada)".to_string(),
+        ));
+    }
+    if ahora_unix > inv.valido_hasta {
+        return Err(CitaError::Validation("La invitación ya venció".to_string()));
+    }
+
+    Ok(inv)
+}
+
+// --------------------------------------------------------------------------
+// CICLO DE VIDA
+// --------------------------------------------------------------------------
+
+/// Etiqueta usada en el reporte para citas sin anfitrión registrado.
+pub const ANFITRION_SIN_ASIGNAR: &str = "(Sin anfitrión)";
+
+/// Agrupa citas por anfitrión y calcula su tasa de inasistencia.
+///
+/// Recibe pares `(anfitrion, estado)`; el resultado se ordena de mayor a menor
+/// porcentaje de inasistencia y, a igualdad, por nombre de anfitrión.
+pub fn resumir_inasistencias<'a, I>(citas: I) -> Vec<InasistenciasAnfitrion>
+where
+    I: IntoIterator<Item = (Option<&'a str>, &'a EstadoCita)>,
+{
+    let mut grupos: std::collections::BTreeMap<String, InasistenciasAnfitrion> =
+        std::collections::BTreeMap::new();
+
+    for (anfitrion, estado) in citas {
+        let nombre = anfitrion
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .unwrap_or(ANFITRION_SIN_ASIGNAR)
+            .to_string();
+        let fila = grupos.entry(nombre.clone()).or_insert_with(|| InasistenciasAnfitrion {
+            anfitrion: nombre,
+            total: 0,
+            asistidas: 0,
+            vencidas: 0,
+            canceladas: 0,
+            pendientes: 0,
+            porcentaje_inasistencia: 0.0,
+        });
+
+        fila.total += 1;
+        match estado {
+            EstadoCita::EnCurso | EstadoCita::Finalizada => fila.asistidas += 1,
+            EstadoCita::Vencida => fila.vencidas += 1,
+            EstadoCita::Cancelada => fila.canceladas += 1,
+            EstadoCita::Programada | EstadoCita::Desconocido(_) => fila.pendientes += 1,
+        }
+    }
+
+    let mut filas: Vec<_> = grupos
+        .into_values()
+        .map(|mut f| {
+            let resueltas = f.asistidas + f.vencidas;
+            if resueltas > 0 {
+                f.porcentaje_inasistencia =
+                    (f64::from(f.vencidas) * 10000.0 / f64::from(resueltas)).round() / 100.0;
+            }
+            f
+        })
+        .collect();
+
+    filas.sort_by(|a, b| {
+        b.porcentaje_inasistencia
+            .total_cmp(&a.porcentaje_inasistencia)
+            .then_with(|| a.anfitrion.cmp(&b.anfitrion))
+    });
+    filas
+}
+
+// --------------------------------------------------------------------------
+// AGENDA: CAPACIDAD Y CONFLICTOS
+// --------------------------------------------------------------------------
+
+/// Duración mínima de una cita.
+pub const DURACION_MIN_MINUTOS: u32 = 5;
+
+/// Duración máxima de una cita (una jornada).
+pub const DURACION_MAX_MINUTOS: u32 = 720;
+
+/// Verifica que la duración de una cita esté dentro de los límites operativos.
+pub fn validar_duracion(minutos: u32) -> Result<(), CitaError> {
+    if (DURACION_MIN_MINUTOS..=DURACION_MAX_MINUTOS).contains(&minutos) {
+        Ok(())
+    } else {
+        Err(CitaError::Validation(format!(
+            "La duración debe estar entre {DURACION_MIN_MINUTOS} y {DURACION_MAX_MINUTOS} minutos"
+        )))
+    }
+}
+
+/// Parsea una hora de jornada en formato HH:MM.
+pub fn parsear_hora(hora: &str) -> Result<NaiveTime, CitaError> {
+    NaiveTime::parse_from_str(hora.trim(), "%H:%M")
+        .map_err(|_| CitaError::Validation(format!("Hora inválida ({hora}); use HH:MM")))
+}
+
+/// Verifica que la jornada de búsqueda de horarios sea válida (HH:MM, inicio < fin).
+pub fn validar_jornada(inicio: &str, fin: &str) -> Result<(), CitaError> {
+    if parsear_hora(inicio)? >= parsear_hora(fin)? {
+        return Err(CitaError::Validation(
+            "El inicio de la jornada debe ser anterior a su fin".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Compara nombres de anfitrión o área sin distinguir mayúsculas ni espacios extremos.
+pub fn mismo_recurso(a: &str, b: &str) -> bool {
+    a.trim().to_lowercase() == b.trim().to_lowercase()
+}
+
+/// Límite de citas simultáneas para un área (`None` = sin límite).
+pub fn capacidad_area<S: std::hash::BuildHasher>(
+    por_area: &HashMap<String, u32, S>,
+    por_defecto: Option<u32>,
+    area: &str,
+) -> Option<u32> {
+    por_area.iter().find(|(nombre, _)| mismo_recurso(nombre, area)).map(|(_, c)| *c).or(por_defecto)
+}
+
+/// Las citas sin duración (registradas antes de existir `fecha_fin`) ocupan un instante.
+const fn normalizar(franja: (i64, i64)) -> (i64, i64) {
+    let (inicio, fin) = franja;
+    (inicio, if fin > inicio { fin } else { inicio + 1 })
+}
+
+/// Máximo de franjas ocupadas que coinciden en algún momento dentro de `franja`.
+///
+/// Las franjas son intervalos semiabiertos `[inicio, fin)` en segundos Unix: una cita
+/// que termina a las 10:00 no choca con otra que empieza a las 10:00.
+pub fn max_simultaneas(ocupadas: &[(i64, i64)], franja: (i64, i64)) -> u32 {
+    let (inicio, fin) = normalizar(franja);
+
+    let mut eventos: Vec<(i64, i32)> = ocupadas
+        .iter()
+        .map(|&o| normalizar(o))
+        .filter(|&(a, b)| a < fin && b > inicio)
+        .flat_map(|(a, b)| [(a.max(inicio), 1), (b.min(fin), -1)])
+        .collect();
+    // A igual instante, las salidas se procesan antes que las entradas
+    eventos.sort_unstable();
+
+    let mut actuales: i32 = 0;
+    let mut maximo: i32 = 0;
+    for (_, delta) in eventos {
+        actuales += delta;
+        maximo = maximo.max(actuales);
+    }
+    u32::try_from(maximo).unwrap_or(0)
+}
+
+/// Rechaza la franja si el recurso ya alcanzó su capacidad en ese horario.
+pub fn verificar_capacidad(
+    ocupadas: &[(i64, i64)],
+    franja: (i64, i64),
+    capacidad: u32,
+    recurso: &str,
+) -> Result<(), CitaError> {
+    let simultaneas = max_simultaneas(ocupadas, franja);
+    if simultaneas >= capacidad {
+        return Err(CitaError::Conflicto(format!(
+            "{recurso} ya tiene {simultaneas} cita(s) en ese horario (máximo {capacidad})"
+        )));
+    }
+    Ok(())
+}
+
+/// Franjas candidatas de `duracion` segundos cada `paso` segundos dentro de la jornada.
+pub fn franjas_candidatas(
+    jornada_inicio: i64,
+    jornada_fin: i64,
+    duracion: i64,
+    paso: i64,
+) -> Vec<(i64, i64)> {
+    if duracion <= 0 || paso <= 0 {
+        return Vec::new();
+    }
+    std::iter::successors(Some(jornada_inicio), |t| Some(t + paso))
+        .take_while(|t| t + duracion <= jornada_fin)
+        .map(|t| (t, t + duracion))
+        .collect()
+}
+
+/// Filtra las candidatas que respetan todas las restricciones `(ocupadas, capacidad)`.
+pub fn horarios_libres(
+    candidatas: Vec<(i64, i64)>,
+    restricciones: &[(Vec<(i64, i64)>, u32)],
+) -> Vec<(i64, i64)> {
+    candidatas
+        .into_iter()
+        .filter(|&franja| {
+            restricciones.iter().all(|(ocupadas, cap)| max_simultaneas(ocupadas, franja) < *cap)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -96,6 +392,7 @@ mod tests {
             visitante_cedula: None,
             visitante_nombre: None,
             visitante_apellido: None,
+            duracion_minutos: None,
         }
     }
 
@@ -138,4 +435,126 @@ mod tests {
             CreateCitaInput { visitante_id: None, visitante_cedula: None, ..input_valido() };
         assert!(validar_create_input(&input).is_err());
     }
+
+    fn invitacion() -> InvitacionCita {
+        let (valido_desde, valido_hasta) = ventana_invitacion(1_000_000, 1_000_000);
+        InvitacionCita {
+            cita_id: "cita:abc123".to_string(),
+            cedula: "1-1234-0567".to_string(),
+            valido_desde,
+            valido_hasta,
+        }
+    }
+
+    #[test]
+    fn test_invitacion_firma_y_verifica() {
+        let clave = b"clave-de-prueba";
+        let token = firmar_invitacion(&invitacion(), clave).unwrap();
+        assert!(token.starts_with(PREFIJO_INVITACION));
+
+        let inv = verificar_invitacion(&token, clave, 1_000_000).unwrap();
+        assert_eq!(inv, invitacion());
+    }
+
+    #[test]
+    fn test_invitacion_alterada_o_clave_distinta() {
+        let token = firmar_invitacion(&invitacion(), b"clave-a").unwrap();
+        assert!(verificar_invitacion(&token, b"clave-b", 1_000_000).is_err());
+
+        // Cambiar la cédula en el payload invalida la firma
+        let (payload, sig) = token[PREFIJO_INVITACION.len()..].split_once('.').unwrap();
+        let original = String::from_utf8(URL_SAFE_NO_PAD.decode(payload).unwrap()).unwrap();
+        let alterado = URL_SAFE_NO_PAD.encode(original.replace("1-1234-0567", "9-9999-9999"));
+        let falso = format!("{PREFIJO_INVITACION}{alterado}.{sig}");
+        assert!(verificar_invitacion(&falso, b"clave-a", 1_000_000).is_err());
+    }
+
+    #[test]
+    fn test_invitacion_fuera_de_ventana() {
+        let clave = b"clave";
+        let token = firmar_invitacion(&invitacion(), clave).unwrap();
+        let (desde, hasta) = ventana_invitacion(1_000_000, 1_000_000);
+        assert!(verificar_invitacion(&token, clave, desde - 1).is_err());
+        assert!(verificar_invitacion(&token, clave, hasta + 1).is_err());
+        assert!(verificar_invitacion(&token, clave, hasta).is_ok());
+    }
+
+    #[test]
+    fn test_resumir_inasistencias() {
+        let citas = [
+            (Some("Ana"), EstadoCita::Finalizada),
+            (Some("Ana"), EstadoCita::Vencida),
+            (Some("Ana"), EstadoCita::Vencida),
+            (Some("Ana"), EstadoCita::Cancelada),
+            (Some("Luis"), EstadoCita::EnCurso),
+            (Some("Luis"), EstadoCita::Programada),
+            (None, EstadoCita::Vencida),
+        ];
+        let filas = resumir_inasistencias(citas.iter().map(|(a, e)| (*a, e)));
+
+        assert_eq!(filas.len(), 3);
+        assert_eq!(filas[0].anfitrion, ANFITRION_SIN_ASIGNAR);
+        assert!((filas[0].porcentaje_inasistencia - 100.0).abs() < f64::EPSILON);
+
+        let ana = &filas[1];
+        assert_eq!((ana.total, ana.asistidas, ana.vencidas, ana.canceladas), (4, 1, 2, 1));
+        assert!((ana.porcentaje_inasistencia - 66.67).abs() < 1e-9);
+
+        let luis = &filas[2];
+        assert_eq!((luis.asistidas, luis.pendientes), (1, 1));
+        assert!(luis.porcentaje_inasistencia.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_max_simultaneas_intervalos_semiabiertos() {
+        let ocupadas = [(100, 200), (150, 250), (200, 300), (400, 400)];
+
+        assert_eq!(max_simultaneas(&ocupadas, (0, 100)), 0);
+        assert_eq!(max_simultaneas(&ocupadas, (100, 160)), 2);
+        // (100,200) termina justo cuando empieza (200,300)
+        assert_eq!(max_simultaneas(&ocupadas, (200, 210)), 2);
+        assert_eq!(max_simultaneas(&ocupadas, (260, 300)), 1);
+        // Cita heredada sin duración ocupa su instante
+        assert_eq!(max_simultaneas(&ocupadas, (390, 410)), 1);
+    }
+
+    #[test]
+    fn test_verificar_capacidad() {
+        let ocupadas = [(100, 200)];
+        assert!(verificar_capacidad(&ocupadas, (150, 250), 2, "El anfitrión").is_ok());
+        assert!(matches!(
+            verificar_capacidad(&ocupadas, (150, 250), 1, "El anfitrión"),
+            Err(CitaError::Conflicto(_))
+        ));
+        assert!(verificar_capacidad(&ocupadas, (200, 300), 1, "El anfitrión").is_ok());
+    }
+
+    #[test]
+    fn test_capacidad_area() {
+        let mapa = HashMap::from([("Sala de Juntas".to_string(), 1)]);
+        assert_eq!(capacidad_area(&mapa, None, " sala de juntas "), Some(1));
+        assert_eq!(capacidad_area(&mapa, Some(5), "Bodega"), Some(5));
+        assert_eq!(capacidad_area(&mapa, None, "Bodega"), None);
+    }
+
+    #[test]
+    fn test_horarios_libres() {
+        let candidatas = franjas_candidatas(0, 240, 60, 60);
+        assert_eq!(candidatas, vec![(0, 60), (60, 120), (120, 180), (180, 240)]);
+
+        let anfitrion = (vec![(60, 120)], 1);
+        let area = (vec![(120, 180), (130, 200)], 2);
+        assert_eq!(horarios_libres(candidatas, &[anfitrion, area]), vec![(0, 60), (180, 240)]);
+    }
+
+    #[test]
+    fn test_validar_duracion_y_hora() {
+        assert!(validar_duracion(60).is_ok());
+        assert!(validar_duracion(0).is_err());
+        assert!(validar_duracion(DURACION_MAX_MINUTOS + 1).is_err());
+        assert_eq!(parsear_hora("07:30").unwrap(), NaiveTime::from_hms_opt(7, 30, 0).unwrap());
+        assert!(parsear_hora("25:00").is_err());
+        assert!(validar_jornada("07:00", "17:00").is_ok());
+        assert!(validar_jornada("17:00", "07:00").is_err());
+    }
 }
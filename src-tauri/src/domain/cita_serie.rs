@@ -0,0 +1,320 @@
+/// Capa de Dominio: Series de Citas Recurrentes.
+///
+/// Valida reglas de recurrencia (diaria, semanal, mensual) y calcula las fechas de
+/// cada ocurrencia. Trabaja con fechas locales sin zona (`NaiveDateTime`): la hora de
+/// la primera cita se conserva en todas las ocurrencias aunque cambie el horario de verano.
+use crate::domain::common::{validar_nombre_entidad_estandar, validar_nombre_estandar};
+use crate::domain::errors::CitaError;
+use crate::models::cita::{FrecuenciaRecurrencia, ReglaRecurrencia, UpdateCitaSerieInput};
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveDateTime};
+
+// --------------------------------------------------------------------------
+// CONSTANTES
+// --------------------------------------------------------------------------
+
+/// Días hacia adelante en los que se materializan citas individuales.
+pub const VENTANA_MATERIALIZACION_DIAS: u64 = 28;
+
+/// Intervalo máximo (cada N días/semanas/meses).
+pub const INTERVALO_MAX: u32 = 52;
+
+/// Número máximo de ocurrencias por serie.
+pub const REPETICIONES_MAX: u32 = 366;
+
+/// Duración máxima de una serie desde su primera cita.
+pub const DURACION_MAX_DIAS: u64 = 730;
+
+// --------------------------------------------------------------------------
+// VALIDACIONES
+// --------------------------------------------------------------------------
+
+fn parsear_fecha(fecha: &str, campo: &str) -> Result<NaiveDate, CitaError> {
+    NaiveDate::parse_from_str(fecha.trim(), "%Y-%m-%d")
+        .map_err(|_| CitaError::Validation(format!("{campo} inválida ({fecha}); use YYYY-MM-DD")))
+}
+
+/// Verifica que la regla sea coherente y acotada a partir de la primera cita.
+pub fn validar_regla(regla: &ReglaRecurrencia, inicio: NaiveDate) -> Result<(), CitaError> {
+    if regla.intervalo == 0 || regla.intervalo > INTERVALO_MAX {
+        return Err(CitaError::Validation(format!(
+            "El intervalo debe estar entre 1 y {INTERVALO_MAX}"
+        )));
+    }
+
+    if regla.hasta.is_none() && regla.repeticiones.is_none() {
+        return Err(CitaError::Validation(
+            "La serie debe terminar en una fecha (hasta) o tras un número de repeticiones"
+                .to_string(),
+        ));
+    }
+
+    if let Some(n) = regla.repeticiones {
+        if n == 0 || n > REPETICIONES_MAX {
+            return Err(CitaError::Validation(format!(
+                "Las repeticiones deben estar entre 1 y {REPETICIONES_MAX}"
+            )));
+        }
+    }
+
+    if let Some(ref hasta) = regla.hasta {
+        let hasta = parsear_fecha(hasta, "Fecha final de la serie")?;
+        if hasta < inicio {
+            return Err(CitaError::Validation(
+                "La fecha final de la serie no puede ser anterior a la primera cita".to_string(),
+            ));
+        }
+        if inicio.checked_add_days(Days::new(DURACION_MAX_DIAS)).is_some_and(|max| hasta > max) {
+            return Err(CitaError::Validation(format!(
+                "Una serie no puede durar más de {DURACION_MAX_DIAS} días"
+            )));
+        }
+    }
+
+    if !regla.dias_semana.is_empty() {
+        if regla.frecuencia != FrecuenciaRecurrencia::Semanal {
+            return Err(CitaError::Validation(
+                "Los días de la semana solo aplican a series semanales".to_string(),
+            ));
+        }
+        if regla.dias_semana.iter().any(|d| !(1..=7).contains(d)) {
+            return Err(CitaError::Validation(
+                "Los días de la semana van de 1 (lunes) a 7 (domingo)".to_string(),
+            ));
+        }
+    }
+
+    for fecha in &regla.excluir {
+        parsear_fecha(fecha, "Fecha excluida")?;
+    }
+
+    Ok(())
+}
+
+/// Valida los cambios a nivel de serie; la regla se valida contra la primera cita.
+pub fn validar_update_input(
+    input: &UpdateCitaSerieInput,
+    inicio: NaiveDate,
+) -> Result<(), CitaError> {
+    if let Some(ref anfitrion) = input.anfitrion {
+        validar_nombre_estandar(anfitrion, "anfitrión")
+            .map_err(|e| CitaError::Validation(e.to_string()))?;
+    }
+    if let Some(ref area) = input.area_visitada {
+        validar_nombre_entidad_estandar(area, "área visitada")
+            .map_err(|e| CitaError::Validation(e.to_string()))?;
+    }
+    if let Some(ref motivo) = input.motivo {
+        validar_nombre_entidad_estandar(motivo, "motivo")
+            .map_err(|e| CitaError::Validation(e.to_string()))?;
+    }
+    if let Some(ref regla) = input.regla {
+        validar_regla(regla, inicio)?;
+    }
+    Ok(())
+}
+
+// --------------------------------------------------------------------------
+// CÁLCULO DE OCURRENCIAS
+// --------------------------------------------------------------------------
+
+/// Fechas de la serie (sin exclusiones) desde `inicio` hasta `fin` inclusive.
+fn fechas_serie(inicio: NaiveDate, regla: &ReglaRecurrencia, fin: NaiveDate) -> Vec<NaiveDate> {
+    let max = regla.repeticiones.unwrap_or(REPETICIONES_MAX);
+    let paso = regla.intervalo.max(1);
+    let mut fechas = Vec::new();
+
+    match regla.frecuencia {
+        FrecuenciaRecurrencia::Diaria => {
+            let mut actual = Some(inicio);
+            while let Some(d) = actual.filter(|d| *d <= fin && fechas.len() < max as usize) {
+                fechas.push(d);
+                actual = d.checked_add_days(Days::new(u64::from(paso)));
+            }
+        }
+        FrecuenciaRecurrencia::Semanal => {
+            let mut dias = if regla.dias_semana.is_empty() {
+                vec![inicio.weekday().number_from_monday()]
+            } else {
+                regla.dias_semana.iter().map(|&d| u32::from(d)).collect()
+            };
+            dias.sort_unstable();
+            dias.dedup();
+
+            let lunes = inicio - Days::new(u64::from(inicio.weekday().num_days_from_monday()));
+            let mut semana = Some(lunes);
+            'semanas: while let Some(s) = semana.filter(|s| *s <= fin) {
+                for &dia in &dias {
+                    let Some(d) = s.checked_add_days(Days::new(u64::from(dia - 1))) else {
+                        break 'semanas;
+                    };
+                    if d < inicio {
+                        continue;
+                    }
+                    if d > fin || fechas.len() >= max as usize {
+                        break 'semanas;
+                    }
+                    fechas.push(d);
+                }
+                semana = s.checked_add_days(Days::new(7 * u64::from(paso)));
+            }
+        }
+        FrecuenciaRecurrencia::Mensual => {
+            let primero = inicio.with_day(1).unwrap_or(inicio);
+            let mut k: u32 = 0;
+            while let Some(mes) = primero.checked_add_months(Months::new(k * paso)) {
+                if mes > fin || fechas.len() >= max as usize {
+                    break;
+                }
+                // Los meses sin ese día (ej. 31) se saltan, como en RRULE.
+                if let Some(d) = NaiveDate::from_ymd_opt(mes.year(), mes.month(), inicio.day()) {
+                    if d > fin {
+                        break;
+                    }
+                    fechas.push(d);
+                }
+                k += 1;
+            }
+        }
+    }
+
+    fechas
+}
+
+/// Calcula las ocurrencias de una serie hasta `limite` (inclusive), sin las fechas excluidas.
+///
+/// `inicio` es la primera cita (fecha y hora locales); todas las ocurrencias conservan su hora.
+pub fn ocurrencias(
+    inicio: NaiveDateTime,
+    regla: &ReglaRecurrencia,
+    limite: NaiveDate,
+) -> Result<Vec<NaiveDateTime>, CitaError> {
+    let hasta = regla.hasta.as_deref().map(|h| parsear_fecha(h, "Fecha final de la serie"));
+    let fin = hasta.transpose()?.map_or(limite, |h| h.min(limite));
+    let excluir = regla
+        .excluir
+        .iter()
+        .map(|f| parsear_fecha(f, "Fecha excluida"))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(fechas_serie(inicio.date(), regla, fin)
+        .into_iter()
+        .filter(|d| !excluir.contains(d))
+        .map(|d| d.and_time(inicio.time()))
+        .collect())
+}
+
+// --------------------------------------------------------------------------
+// PRUEBAS UNITARIAS
+// --------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fecha(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn regla(frecuencia: FrecuenciaRecurrencia) -> ReglaRecurrencia {
+        ReglaRecurrencia {
+            frecuencia,
+            intervalo: 1,
+            dias_semana: vec![],
+            hasta: None,
+            repeticiones: None,
+            excluir: vec![],
+        }
+    }
+
+    fn fechas(inicio: &str, regla: &ReglaRecurrencia, limite: &str) -> Vec<String> {
+        let inicio = fecha(inicio).and_hms_opt(9, 30, 0).unwrap();
+        ocurrencias(inicio, regla, fecha(limite))
+            .unwrap()
+            .iter()
+            .map(|o| {
+                assert_eq!(o.time(), inicio.time());
+                o.date().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_validar_regla() {
+        let inicio = fecha("2025-03-03");
+        let mut r = regla(FrecuenciaRecurrencia::Semanal);
+        assert!(validar_regla(&r, inicio).is_err(), "sin fin");
+
+        r.repeticiones = Some(10);
+        assert!(validar_regla(&r, inicio).is_ok());
+
+        r.intervalo = 0;
+        assert!(validar_regla(&r, inicio).is_err());
+        r.intervalo = 2;
+
+        r.dias_semana = vec![1, 8];
+        assert!(validar_regla(&r, inicio).is_err());
+        r.dias_semana = vec![1, 3];
+
+        r.hasta = Some("2025-03-01".to_string());
+        assert!(validar_regla(&r, inicio).is_err(), "termina antes de empezar");
+        r.hasta = Some("2028-01-01".to_string());
+        assert!(validar_regla(&r, inicio).is_err(), "demasiado larga");
+        r.hasta = Some("2025-06-30".to_string());
+
+        r.excluir = vec!["10/03/2025".to_string()];
+        assert!(validar_regla(&r, inicio).is_err());
+
+        let mut diaria = regla(FrecuenciaRecurrencia::Diaria);
+        diaria.repeticiones = Some(3);
+        diaria.dias_semana = vec![1];
+        assert!(validar_regla(&diaria, inicio).is_err());
+    }
+
+    #[test]
+    fn test_ocurrencias_diarias_con_repeticiones_y_exclusiones() {
+        let mut r = regla(FrecuenciaRecurrencia::Diaria);
+        r.intervalo = 2;
+        r.repeticiones = Some(4);
+        r.excluir = vec!["2025-03-05".to_string()];
+
+        // La excluida consume una repetición
+        assert_eq!(
+            fechas("2025-03-01", &r, "2025-12-31"),
+            ["2025-03-01", "2025-03-03", "2025-03-07"]
+        );
+        // La ventana corta antes del fin de la serie
+        assert_eq!(fechas("2025-03-01", &r, "2025-03-03"), ["2025-03-01", "2025-03-03"]);
+    }
+
+    #[test]
+    fn test_ocurrencias_semanales() {
+        let mut r = regla(FrecuenciaRecurrencia::Semanal);
+        r.dias_semana = vec![5, 1];
+        r.hasta = Some("2025-03-21".to_string());
+
+        // 2025-03-05 es miércoles: el lunes 3 queda antes del inicio
+        assert_eq!(
+            fechas("2025-03-05", &r, "2025-12-31"),
+            ["2025-03-07", "2025-03-10", "2025-03-14", "2025-03-17", "2025-03-21"]
+        );
+
+        let mut quincenal = regla(FrecuenciaRecurrencia::Semanal);
+        quincenal.intervalo = 2;
+        quincenal.repeticiones = Some(3);
+        assert_eq!(
+            fechas("2025-03-05", &quincenal, "2025-12-31"),
+            ["2025-03-05", "2025-03-19", "2025-04-02"]
+        );
+    }
+
+    #[test]
+    fn test_ocurrencias_mensuales_saltan_meses_cortos() {
+        let mut r = regla(FrecuenciaRecurrencia::Mensual);
+        r.hasta = Some("2025-06-30".to_string());
+
+        assert_eq!(
+            fechas("2025-01-31", &r, "2025-12-31"),
+            ["2025-01-31", "2025-03-31", "2025-05-31"]
+        );
+    }
+}
@@ -287,6 +287,26 @@ pub fn parsear_fecha_simple(fecha_str: &str) -> Result<chrono::NaiveDate, Common
     })
 }
 
+/// Límites RFC 3339 (UTC) de un rango de días en la zona horaria local (inclusivo):
+/// desde las 00:00:00 de `inicio` hasta las 23:59:59 de `fin`.
+pub fn limites_dias_locales(
+    inicio: chrono::NaiveDate,
+    fin: chrono::NaiveDate,
+) -> Result<(String, String), CommonError> {
+    let limite = |fecha: chrono::NaiveDate, hora: chrono::NaiveTime| {
+        fecha
+            .and_time(hora)
+            .and_local_timezone(chrono::Local)
+            .earliest()
+            .map(|f| f.with_timezone(&chrono::Utc).to_rfc3339())
+            .ok_or_else(|| CommonError::Validation(format!("Fecha fuera de rango: {fecha}")))
+    };
+    Ok((
+        limite(inicio, chrono::NaiveTime::MIN)?,
+        limite(fin, chrono::NaiveTime::from_hms_opt(23, 59, 59).unwrap_or_default())?,
+    ))
+}
+
 /// Convierte un `surrealdb::Datetime` a formato ISO8601 compatible con JavaScript.
 /// Elimina el decorador `d'...'` que agrega `SurrealDB` por defecto.
 pub fn datetime_to_iso(dt: &surrealdb::Datetime) -> String {
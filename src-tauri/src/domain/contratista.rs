@@ -28,6 +28,9 @@ use chrono::{DateTime, NaiveDate};
 /// Días previos al vencimiento para considerar que requiere atención (alerta amarilla).
 pub const DIAS_ALERTA_VENCIMIENTO: i64 = 30;
 
+/// Prefijo del motivo que deja en el historial la suspensión automática por PRAIND vencido.
+pub const MOTIVO_SUSPENSION_PRAIND: &str = "Suspensión automática: PRAIND vencido";
+
 // --------------------------------------------------------------------------
 // VALIDACIONES DE CAMPOS INDIVIDUALES
 // --------------------------------------------------------------------------
@@ -303,6 +306,25 @@ pub fn puede_ingresar(
     *estado == crate::models::contratista::EstadoContratista::Activo && !praind_vencido
 }
 
+/// Determina si renovar el PRAIND debe reactivar a un contratista suspendido.
+///
+/// Solo se levanta la suspensión que aplicó la revisión automática: si el último cambio de
+/// estado del historial no es esa suspensión (p.ej. una suspensión manual posterior), se
+/// mantiene.
+///
+/// # Argumentos
+/// * `ultimo_motivo` - Motivo del último cambio de estado registrado
+/// * `praind_vigente` - Si la nueva fecha de vencimiento es futura
+pub fn reactivar_al_renovar_praind(
+    estado: &EstadoContratista,
+    ultimo_motivo: Option<&str>,
+    praind_vigente: bool,
+) -> bool {
+    *estado == EstadoContratista::Suspendido
+        && praind_vigente
+        && ultimo_motivo.is_some_and(|m| m.starts_with(MOTIVO_SUSPENSION_PRAIND))
+}
+
 // --------------------------------------------------------------------------
 // PRUEBAS UNITARIAS
 // --------------------------------------------------------------------------
@@ -312,6 +334,24 @@ mod tests {
     use super::*;
     use crate::domain::common::MAX_LEN_NOMBRE;
 
+    #[test]
+    fn test_reactivar_al_renovar_praind_solo_suspension_automatica() {
+        let automatica = format!("{MOTIVO_SUSPENSION_PRAIND} el 2025-03-01");
+        let suspendido = EstadoContratista::Suspendido;
+
+        assert!(reactivar_al_renovar_praind(&suspendido, Some(&automatica), true));
+
+        // Suspensión manual, sin historial o PRAIND aún vencido: no se reactiva
+        assert!(!reactivar_al_renovar_praind(&suspendido, Some("Falta grave en planta"), true));
+        assert!(!reactivar_al_renovar_praind(&suspendido, None, true));
+        assert!(!reactivar_al_renovar_praind(&suspendido, Some(&automatica), false));
+        assert!(!reactivar_al_renovar_praind(
+            &EstadoContratista::Inactivo,
+            Some(&automatica),
+            true
+        ));
+    }
+
     #[test]
     fn test_validar_cedula_valida() {
         assert!(validar_cedula("12345678").is_ok());
@@ -0,0 +1,380 @@
+/// Capa de Dominio: Documentos de Contratista y Requisitos de Ingreso.
+///
+/// Reglas puras para validar los documentos que el contratista registra (además del
+/// PRAIND) y para decidir, según las reglas vigentes, qué documentos le faltan o se le
+/// vencieron. El resultado se traduce a un `EstadoAutorizacion` que consume el motor.
+use crate::domain::common::{datetime_to_iso, parsear_fecha_simple};
+use crate::domain::errors::DocumentoContratistaError;
+use crate::models::documento_contratista::{
+    DocumentoContratista, DocumentoPendiente, MotivoDocumentoPendiente, RequisitoDocumento,
+    TipoDocumentoContratista,
+};
+use crate::models::validation::EstadoAutorizacion;
+use chrono::{NaiveDate, Utc};
+
+// --------------------------------------------------------------------------
+// CONSTANTES DE VALIDACIÓN
+// --------------------------------------------------------------------------
+
+pub const NUMERO_MAX_LEN: usize = 50;
+pub const DESCRIPCION_MAX_LEN: usize = 500;
+pub const AREA_MAX_LEN: usize = 100;
+
+/// Tamaño máximo de un escaneo (10 MiB).
+pub const ESCANEO_MAX_BYTES: usize = 10 * 1024 * 1024;
+
+// --------------------------------------------------------------------------
+// VALIDACIONES DE CAMPOS
+// --------------------------------------------------------------------------
+
+/// Valida el número del documento (opcional).
+pub fn validar_numero(numero: Option<&str>) -> Result<(), DocumentoContratistaError> {
+    match numero.map(str::trim) {
+        Some(n) if n.len() > NUMERO_MAX_LEN => Err(DocumentoContratistaError::Validation(format!(
+            "El número de documento no puede exceder {NUMERO_MAX_LEN} caracteres"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Valida la descripción; es obligatoria para el tipo `Otro`.
+pub fn validar_descripcion(
+    tipo: TipoDocumentoContratista,
+    descripcion: Option<&str>,
+) -> Result<(), DocumentoContratistaError> {
+    let descripcion = descripcion.map(str::trim).filter(|d| !d.is_empty());
+
+    if tipo == TipoDocumentoContratista::Otro && descripcion.is_none() {
+        return Err(DocumentoContratistaError::Validation(
+            "Describa el documento cuando el tipo es 'Otro'".to_string(),
+        ));
+    }
+    if descripcion.is_some_and(|d| d.len() > DESCRIPCION_MAX_LEN) {
+        return Err(DocumentoContratistaError::Validation(format!(
+            "La descripción no puede exceder {DESCRIPCION_MAX_LEN} caracteres"
+        )));
+    }
+    Ok(())
+}
+
+/// Parsea una fecha opcional (YYYY-MM-DD).
+pub fn parsear_fecha_opcional(
+    fecha: Option<&str>,
+    campo: &str,
+) -> Result<Option<NaiveDate>, DocumentoContratistaError> {
+    fecha
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+        .map(|f| {
+            parsear_fecha_simple(f)
+                .map_err(|e| DocumentoContratistaError::Validation(format!("{campo}: {e}")))
+        })
+        .transpose()
+}
+
+/// Valida la coherencia de las fechas de un documento.
+///
+/// La emisión no puede ser futura ni posterior al vencimiento. Un documento ya vencido
+/// sí se puede registrar (queda en el historial, pero no cumple el requisito).
+pub fn validar_fechas(
+    emision: Option<NaiveDate>,
+    vencimiento: Option<NaiveDate>,
+    hoy: NaiveDate,
+) -> Result<(), DocumentoContratistaError> {
+    if emision.is_some_and(|e| e > hoy) {
+        return Err(DocumentoContratistaError::Validation(
+            "La fecha de emisión no puede ser futura".to_string(),
+        ));
+    }
+    if let (Some(e), Some(v)) = (emision, vencimiento) {
+        if v < e {
+            return Err(DocumentoContratistaError::Validation(
+                "El vencimiento no puede ser anterior a la emisión".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Días restantes hasta el vencimiento (negativo si ya venció).
+pub fn dias_hasta_vencimiento(fecha_vencimiento: &str) -> Result<i64, DocumentoContratistaError> {
+    let fecha = parsear_fecha_opcional(Some(fecha_vencimiento), "Vencimiento")?
+        .ok_or_else(|| DocumentoContratistaError::Validation("Fecha vacía".to_string()))?;
+    Ok((fecha - Utc::now().date_naive()).num_days())
+}
+
+// --------------------------------------------------------------------------
+// REQUISITOS
+// --------------------------------------------------------------------------
+
+/// Normaliza el área de una regla o de un ingreso (sin espacios sobrantes, vacía = `None`).
+pub fn normalizar_area(area: Option<&str>) -> Option<String> {
+    area.map(str::trim).filter(|a| !a.is_empty()).map(str::to_string)
+}
+
+/// Valida una regla de documento requerido.
+pub fn validar_requisito(
+    tipo: TipoDocumentoContratista,
+    area: Option<&str>,
+    descripcion: Option<&str>,
+) -> Result<(), DocumentoContratistaError> {
+    if tipo == TipoDocumentoContratista::Otro {
+        return Err(DocumentoContratistaError::Validation(
+            "El tipo 'Otro' no se puede exigir como requisito".to_string(),
+        ));
+    }
+    if area.is_some_and(|a| a.trim().len() > AREA_MAX_LEN) {
+        return Err(DocumentoContratistaError::Validation(format!(
+            "El área no puede exceder {AREA_MAX_LEN} caracteres"
+        )));
+    }
+    if descripcion.is_some_and(|d| d.trim().len() > DESCRIPCION_MAX_LEN) {
+        return Err(DocumentoContratistaError::Validation(format!(
+            "La descripción no puede exceder {DESCRIPCION_MAX_LEN} caracteres"
+        )));
+    }
+    Ok(())
+}
+
+/// Indica si una regla aplica al contratista para el ingreso evaluado.
+///
+/// # Argumentos
+/// * `regla_empresa` / `regla_area` - Alcance de la regla (`None` = cualquiera).
+/// * `empresa_id` - Empresa del contratista.
+/// * `area` - Área del ingreso, si se conoce. Las reglas por área no aplican sin ella.
+pub fn requisito_aplica(
+    regla_empresa: Option<&str>,
+    regla_area: Option<&str>,
+    empresa_id: &str,
+    area: Option<&str>,
+) -> bool {
+    let empresa_ok = regla_empresa.is_none_or(|e| e == empresa_id);
+    let area_ok =
+        regla_area.is_none_or(|ra| area.is_some_and(|a| a.trim().eq_ignore_ascii_case(ra.trim())));
+    empresa_ok && area_ok
+}
+
+/// Evalúa los documentos del contratista contra los tipos exigidos.
+///
+/// Por cada tipo exigido basta un documento vigente (sin vencimiento o que vence hoy o
+/// después). Si solo hay documentos vencidos se reporta el vencimiento más reciente.
+///
+/// # Argumentos
+/// * `requeridos` - Tipos exigidos por las reglas que aplican (puede tener repetidos).
+/// * `documentos` - Tipo y vencimiento de cada documento registrado.
+/// * `hoy` - Fecha de referencia.
+pub fn evaluar_documentos(
+    requeridos: &[TipoDocumentoContratista],
+    documentos: &[(TipoDocumentoContratista, Option<NaiveDate>)],
+    hoy: NaiveDate,
+) -> Vec<DocumentoPendiente> {
+    let mut pendientes: Vec<DocumentoPendiente> = Vec::new();
+
+    for &tipo in requeridos {
+        if pendientes.iter().any(|p| p.tipo == tipo) {
+            continue;
+        }
+
+        let del_tipo: Vec<Option<NaiveDate>> =
+            documentos.iter().filter(|(t, _)| *t == tipo).map(|(_, v)| *v).collect();
+
+        if del_tipo.is_empty() {
+            pendientes.push(DocumentoPendiente {
+                tipo,
+                motivo: MotivoDocumentoPendiente::Faltante,
+                fecha_vencimiento: None,
+            });
+            continue;
+        }
+
+        let vigente = del_tipo.iter().any(|v| v.is_none_or(|fecha| fecha >= hoy));
+        if !vigente {
+            pendientes.push(DocumentoPendiente {
+                tipo,
+                motivo: MotivoDocumentoPendiente::Vencido,
+                fecha_vencimiento: del_tipo.iter().flatten().max().map(ToString::to_string),
+            });
+        }
+    }
+
+    pendientes
+}
+
+/// Documentos pendientes de un contratista según las reglas que le aplican.
+///
+/// # Argumentos
+/// * `requisitos` - Todas las reglas registradas.
+/// * `documentos` - Documentos del contratista.
+/// * `empresa_id` - Empresa del contratista.
+/// * `area` - Área del ingreso, si se conoce.
+/// * `hoy` - Fecha de referencia.
+pub fn documentos_pendientes(
+    requisitos: &[RequisitoDocumento],
+    documentos: &[DocumentoContratista],
+    empresa_id: &str,
+    area: Option<&str>,
+    hoy: NaiveDate,
+) -> Vec<DocumentoPendiente> {
+    let requeridos: Vec<TipoDocumentoContratista> = requisitos
+        .iter()
+        .filter(|r| {
+            let regla_empresa = r.empresa.as_ref().map(ToString::to_string);
+            requisito_aplica(regla_empresa.as_deref(), r.area.as_deref(), empresa_id, area)
+        })
+        .map(|r| r.tipo)
+        .collect();
+
+    if requeridos.is_empty() {
+        return Vec::new();
+    }
+
+    let documentos: Vec<(TipoDocumentoContratista, Option<NaiveDate>)> = documentos
+        .iter()
+        .map(|d| {
+            let vencimiento = d.fecha_vencimiento.as_ref().and_then(|v| {
+                let iso = datetime_to_iso(v);
+                NaiveDate::parse_from_str(iso.get(..10).unwrap_or(&iso), "%Y-%m-%d").ok()
+            });
+            (d.tipo, vencimiento)
+        })
+        .collect();
+
+    evaluar_documentos(&requeridos, &documentos, hoy)
+}
+
+/// Combina el estado base del contratista con el resultado de los documentos.
+///
+/// Un documento exigido faltante o vencido equivale a documentación vencida; los
+/// estados que ya deniegan (inactivo, suspendido) se conservan.
+pub const fn estado_autorizacion_con_documentos(
+    base: EstadoAutorizacion,
+    pendientes: &[DocumentoPendiente],
+) -> EstadoAutorizacion {
+    match base {
+        EstadoAutorizacion::Activo | EstadoAutorizacion::PorDefinir if !pendientes.is_empty() => {
+            EstadoAutorizacion::Vencido
+        }
+        otro => otro,
+    }
+}
+
+// --------------------------------------------------------------------------
+// PRUEBAS UNITARIAS
+// --------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fecha(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_validar_descripcion() {
+        assert!(validar_descripcion(TipoDocumentoContratista::PolizaRiesgos, None).is_ok());
+        assert!(validar_descripcion(TipoDocumentoContratista::Otro, Some("  ")).is_err());
+        assert!(validar_descripcion(TipoDocumentoContratista::Otro, Some("Licencia")).is_ok());
+        let larga = "x".repeat(DESCRIPCION_MAX_LEN + 1);
+        assert!(validar_descripcion(TipoDocumentoContratista::Otro, Some(&larga)).is_err());
+    }
+
+    #[test]
+    fn test_validar_fechas() {
+        let hoy = fecha("2026-06-15");
+        assert!(validar_fechas(Some(fecha("2026-01-01")), Some(fecha("2027-01-01")), hoy).is_ok());
+        // Ya vencido: se admite
+        assert!(validar_fechas(Some(fecha("2025-01-01")), Some(fecha("2025-12-31")), hoy).is_ok());
+        // Emisión futura
+        assert!(validar_fechas(Some(fecha("2026-07-01")), None, hoy).is_err());
+        // Vence antes de emitirse
+        assert!(validar_fechas(Some(fecha("2026-01-01")), Some(fecha("2025-12-31")), hoy).is_err());
+    }
+
+    #[test]
+    fn test_validar_requisito() {
+        assert!(validar_requisito(TipoDocumentoContratista::TrabajoAlturas, None, None).is_ok());
+        assert!(validar_requisito(TipoDocumentoContratista::Otro, None, None).is_err());
+        let area = "a".repeat(AREA_MAX_LEN + 1);
+        assert!(
+            validar_requisito(TipoDocumentoContratista::PolizaRiesgos, Some(&area), None).is_err()
+        );
+    }
+
+    #[test]
+    fn test_requisito_aplica() {
+        // Global
+        assert!(requisito_aplica(None, None, "empresa:a", None));
+        // Por empresa
+        assert!(requisito_aplica(Some("empresa:a"), None, "empresa:a", None));
+        assert!(!requisito_aplica(Some("empresa:b"), None, "empresa:a", None));
+        // Por área: sin área conocida no aplica
+        assert!(requisito_aplica(None, Some("Calderas"), "empresa:a", Some(" calderas ")));
+        assert!(!requisito_aplica(None, Some("Calderas"), "empresa:a", None));
+        assert!(!requisito_aplica(None, Some("Calderas"), "empresa:a", Some("Bodega")));
+    }
+
+    #[test]
+    fn test_evaluar_documentos() {
+        let hoy = fecha("2026-06-15");
+        let requeridos = [
+            TipoDocumentoContratista::PolizaRiesgos,
+            TipoDocumentoContratista::CertificadoMedico,
+            TipoDocumentoContratista::TrabajoAlturas,
+            TipoDocumentoContratista::InduccionSeguridad,
+            TipoDocumentoContratista::PolizaRiesgos,
+        ];
+        let documentos = [
+            // Póliza: una vencida y una vigente -> cumple
+            (TipoDocumentoContratista::PolizaRiesgos, Some(fecha("2026-01-01"))),
+            (TipoDocumentoContratista::PolizaRiesgos, Some(fecha("2026-06-15"))),
+            // Certificado médico: solo vencidos
+            (TipoDocumentoContratista::CertificadoMedico, Some(fecha("2025-05-01"))),
+            (TipoDocumentoContratista::CertificadoMedico, Some(fecha("2026-06-14"))),
+            // Inducción sin vencimiento -> cumple
+            (TipoDocumentoContratista::InduccionSeguridad, None),
+        ];
+
+        let pendientes = evaluar_documentos(&requeridos, &documentos, hoy);
+        assert_eq!(
+            pendientes,
+            vec![
+                DocumentoPendiente {
+                    tipo: TipoDocumentoContratista::CertificadoMedico,
+                    motivo: MotivoDocumentoPendiente::Vencido,
+                    fecha_vencimiento: Some("2026-06-14".to_string()),
+                },
+                DocumentoPendiente {
+                    tipo: TipoDocumentoContratista::TrabajoAlturas,
+                    motivo: MotivoDocumentoPendiente::Faltante,
+                    fecha_vencimiento: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_estado_autorizacion_con_documentos() {
+        let pendiente = DocumentoPendiente {
+            tipo: TipoDocumentoContratista::PolizaRiesgos,
+            motivo: MotivoDocumentoPendiente::Faltante,
+            fecha_vencimiento: None,
+        };
+        assert_eq!(
+            estado_autorizacion_con_documentos(EstadoAutorizacion::Activo, &[]),
+            EstadoAutorizacion::Activo
+        );
+        assert_eq!(
+            estado_autorizacion_con_documentos(
+                EstadoAutorizacion::Activo,
+                std::slice::from_ref(&pendiente)
+            ),
+            EstadoAutorizacion::Vencido
+        );
+        // Un estado más grave no se reemplaza
+        assert_eq!(
+            estado_autorizacion_con_documentos(EstadoAutorizacion::Suspendido, &[pendiente]),
+            EstadoAutorizacion::Suspendido
+        );
+    }
+}
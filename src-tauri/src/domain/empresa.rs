@@ -3,9 +3,31 @@
 /// Este módulo centraliza la lógica de validación pura para las empresas (contratistas
 /// o proveedores). Al pertenecer a la capa de dominio, no tiene dependencias de
 /// infraestructura, asegurando reglas de negocio consistentes y testeables.
-use crate::domain::common::validar_nombre_entidad_estandar;
+use crate::domain::common::{
+    datetime_to_iso, parsear_fecha_simple, validar_email_estandar, validar_nombre_entidad_estandar,
+    validar_nombre_estandar, validar_opcional_estandar, TELEFONO_MAX_LEN, TELEFONO_MIN_LEN,
+};
 use crate::domain::errors::EmpresaError;
-use crate::models::empresa::{CreateEmpresaInput, UpdateEmpresaInput};
+use crate::models::empresa::{ContactoEmpresa, CreateEmpresaInput, Empresa, UpdateEmpresaInput};
+use chrono::NaiveDate;
+use std::collections::{HashMap, HashSet};
+
+// --------------------------------------------------------------------------
+// CONSTANTES DE DOMINIO
+// --------------------------------------------------------------------------
+
+/// Máximo de personas de contacto por empresa.
+pub const MAX_CONTACTOS_EMPRESA: usize = 10;
+
+/// Niveles de subcontratación permitidos (principal → sub → sub-sub...).
+pub const MAX_NIVELES_JERARQUIA: usize = 5;
+
+/// Dígitos permitidos en la cédula jurídica (ej: 3-101-123456).
+const MIN_DIGITOS_CEDULA_JURIDICA: usize = 9;
+const MAX_DIGITOS_CEDULA_JURIDICA: usize = 12;
+
+/// Longitud máxima del cargo de un contacto.
+const MAX_LEN_CARGO: usize = 50;
 
 // --------------------------------------------------------------------------
 // VALIDACIONES DE CAMPOS INDIVIDUALES
@@ -35,6 +57,260 @@ pub fn validar_direccion(direccion: &str) -> Result<(), EmpresaError> {
         .map_err(|e| EmpresaError::Validation(e.to_string()))
 }
 
+/// Valida la cédula jurídica: solo dígitos y guiones, entre 9 y 12 dígitos.
+pub fn validar_cedula_juridica(cedula: &str) -> Result<(), EmpresaError> {
+    let limpia = cedula.trim();
+    if !limpia.chars().all(|c| c.is_ascii_digit() || c == '-') {
+        return Err(EmpresaError::Validation(
+            "La cédula jurídica solo puede contener números y guiones".to_string(),
+        ));
+    }
+
+    let digitos = limpia.chars().filter(char::is_ascii_digit).count();
+    if !(MIN_DIGITOS_CEDULA_JURIDICA..=MAX_DIGITOS_CEDULA_JURIDICA).contains(&digitos) {
+        return Err(EmpresaError::Validation(format!(
+            "La cédula jurídica debe tener entre {MIN_DIGITOS_CEDULA_JURIDICA} y \
+             {MAX_DIGITOS_CEDULA_JURIDICA} dígitos"
+        )));
+    }
+    Ok(())
+}
+
+/// Valida una persona de contacto: nombre obligatorio, teléfono y email con formato.
+pub fn validar_contacto(contacto: &ContactoEmpresa) -> Result<(), EmpresaError> {
+    let validacion =
+        |e: crate::domain::errors::CommonError| EmpresaError::Validation(e.to_string());
+
+    validar_nombre_estandar(&contacto.nombre, "nombre del contacto").map_err(validacion)?;
+    validar_opcional_estandar(contacto.cargo.as_ref(), MAX_LEN_CARGO, "cargo del contacto")
+        .map_err(validacion)?;
+
+    if let Some(telefono) = contacto.telefono.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        let formato_valido = telefono
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, ' ' | '-' | '+' | '(' | ')'));
+        if !formato_valido || !(TELEFONO_MIN_LEN..=TELEFONO_MAX_LEN).contains(&telefono.len()) {
+            return Err(EmpresaError::Validation(format!(
+                "Teléfono inválido para el contacto {}",
+                contacto.nombre.trim()
+            )));
+        }
+    }
+
+    if let Some(email) = contacto.email.as_deref().filter(|e| !e.trim().is_empty()) {
+        validar_email_estandar(email).map_err(validacion)?;
+    }
+    Ok(())
+}
+
+/// Valida la lista de contactos: máximo `MAX_CONTACTOS_EMPRESA` y un solo principal.
+pub fn validar_contactos(contactos: &[ContactoEmpresa]) -> Result<(), EmpresaError> {
+    if contactos.len() > MAX_CONTACTOS_EMPRESA {
+        return Err(EmpresaError::Validation(format!(
+            "Una empresa puede tener como máximo {MAX_CONTACTOS_EMPRESA} contactos"
+        )));
+    }
+    if contactos.iter().filter(|c| c.principal).count() > 1 {
+        return Err(EmpresaError::Validation(
+            "Solo un contacto puede ser el principal".to_string(),
+        ));
+    }
+    contactos.iter().try_for_each(validar_contacto)
+}
+
+/// Interpreta el periodo de contrato (YYYY-MM-DD); el fin no puede ser anterior al inicio.
+///
+/// # Retorno
+/// Las fechas parseadas (`None` si el extremo no se indicó o viene vacío).
+pub fn validar_periodo_contrato(
+    inicio: Option<&str>,
+    fin: Option<&str>,
+) -> Result<(Option<NaiveDate>, Option<NaiveDate>), EmpresaError> {
+    let parsear = |valor: Option<&str>, campo: &str| {
+        valor
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(|v| {
+                parsear_fecha_simple(v)
+                    .map_err(|e| EmpresaError::Validation(format!("{campo}: {e}")))
+            })
+            .transpose()
+    };
+    let inicio = parsear(inicio, "Inicio del contrato")?;
+    let fin = parsear(fin, "Fin del contrato")?;
+
+    if let (Some(i), Some(f)) = (inicio, fin) {
+        if f < i {
+            return Err(EmpresaError::Validation(
+                "El fin del contrato no puede ser anterior a su inicio".to_string(),
+            ));
+        }
+    }
+    Ok((inicio, fin))
+}
+
+// --------------------------------------------------------------------------
+// JERARQUÍA (CONTRATISTA PRINCIPAL / SUBCONTRATISTAS)
+// --------------------------------------------------------------------------
+
+/// Verifica que `padre_id` pueda ser la contratista principal de `empresa_id`.
+///
+/// # Argumentos
+/// * `empresa_id` - Empresa que se edita (`None` si aún no existe).
+/// * `padre_id` - Contratista principal propuesta.
+/// * `padres` - Principal actual de cada empresa (`id` → `empresa_padre`).
+///
+/// # Retorno
+/// Error si la empresa sería su propia principal (directa o indirectamente) o si la
+/// cadena supera `MAX_NIVELES_JERARQUIA`.
+pub fn validar_empresa_padre(
+    empresa_id: Option<&str>,
+    padre_id: &str,
+    padres: &HashMap<String, Option<String>>,
+) -> Result<(), EmpresaError> {
+    let error_niveles = || {
+        EmpresaError::Validation(format!(
+            "La cadena de subcontratación no puede superar {MAX_NIVELES_JERARQUIA} niveles"
+        ))
+    };
+
+    // Niveles por encima: la nueva principal y sus ancestros
+    let mut actual = Some(padre_id);
+    let mut ancestros = 0;
+    while let Some(id) = actual {
+        if Some(id) == empresa_id {
+            return Err(EmpresaError::Validation(
+                "Una empresa no puede ser contratista principal de sí misma".to_string(),
+            ));
+        }
+        ancestros += 1;
+        if ancestros >= MAX_NIVELES_JERARQUIA {
+            return Err(error_niveles());
+        }
+        actual = padres.get(id).and_then(Option::as_deref);
+    }
+
+    // Niveles por debajo: la empresa se mueve junto con sus subcontratistas
+    let subarbol = empresa_id.map_or(1, |id| altura_subarbol(id, padres));
+    if ancestros + subarbol > MAX_NIVELES_JERARQUIA {
+        return Err(error_niveles());
+    }
+    Ok(())
+}
+
+/// Niveles desde `raiz` hacia abajo, contándola a ella (1 = sin subcontratistas).
+///
+/// Se corta pasado `MAX_NIVELES_JERARQUIA` para no iterar sin fin con datos cíclicos.
+fn altura_subarbol(raiz: &str, padres: &HashMap<String, Option<String>>) -> usize {
+    let mut nivel = vec![raiz];
+    let mut altura = 0;
+    while !nivel.is_empty() && altura <= MAX_NIVELES_JERARQUIA {
+        altura += 1;
+        nivel = padres
+            .iter()
+            .filter(|(_, padre)| padre.as_deref().is_some_and(|p| nivel.contains(&p)))
+            .map(|(id, _)| id.as_str())
+            .collect();
+    }
+    altura
+}
+
+/// Contratista principal directa de cada empresa (`id` → `empresa_padre`).
+pub fn mapa_padres(empresas: &[Empresa]) -> HashMap<String, Option<String>> {
+    empresas
+        .iter()
+        .map(|e| (e.id.to_string(), e.empresa_padre.as_ref().map(ToString::to_string)))
+        .collect()
+}
+
+/// Contratista principal (raíz de la jerarquía) de una empresa.
+pub fn empresa_principal<'a>(id: &'a str, padres: &'a HashMap<String, Option<String>>) -> &'a str {
+    let mut actual = id;
+    for _ in 0..MAX_NIVELES_JERARQUIA {
+        match padres.get(actual).and_then(Option::as_deref) {
+            Some(padre) => actual = padre,
+            None => break,
+        }
+    }
+    actual
+}
+
+/// La empresa indicada y todas sus subcontratistas (directas e indirectas).
+pub fn empresa_y_subempresas(
+    raiz: &str,
+    padres: &HashMap<String, Option<String>>,
+) -> HashSet<String> {
+    padres
+        .keys()
+        .filter(|id| {
+            let mut actual = Some(id.as_str());
+            for _ in 0..=MAX_NIVELES_JERARQUIA {
+                match actual {
+                    Some(a) if a == raiz => return true,
+                    Some(a) => actual = padres.get(a).and_then(Option::as_deref),
+                    None => break,
+                }
+            }
+            false
+        })
+        .cloned()
+        .chain(std::iter::once(raiz.to_string()))
+        .collect()
+}
+
+// --------------------------------------------------------------------------
+// PERIODO DE CONTRATO
+// --------------------------------------------------------------------------
+
+/// Periodo de contrato de una empresa de la cadena de subcontratación.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeriodoContrato {
+    pub empresa_nombre: String,
+    pub inicio: Option<NaiveDate>,
+    pub fin: Option<NaiveDate>,
+}
+
+impl PeriodoContrato {
+    pub fn desde_empresa(empresa: &Empresa) -> Self {
+        let fecha = |dt: &surrealdb::Datetime| {
+            let iso = datetime_to_iso(dt);
+            NaiveDate::parse_from_str(iso.get(..10).unwrap_or(&iso), "%Y-%m-%d").ok()
+        };
+        Self {
+            empresa_nombre: empresa.nombre.clone(),
+            inicio: empresa.contrato_inicio.as_ref().and_then(fecha),
+            fin: empresa.contrato_fin.as_ref().and_then(fecha),
+        }
+    }
+}
+
+/// Evalúa si `hoy` cae dentro del contrato de la empresa y de todas sus principales.
+///
+/// # Argumentos
+/// * `cadena` - Periodo de la empresa seguido del de cada principal, en orden ascendente.
+/// * `hoy` - Fecha de evaluación.
+///
+/// # Retorno
+/// `None` si todos los contratos están vigentes; si no, el motivo del primero que no lo está.
+pub fn evaluar_contrato(cadena: &[PeriodoContrato], hoy: NaiveDate) -> Option<String> {
+    cadena.iter().enumerate().find_map(|(nivel, periodo)| {
+        let empresa = if nivel == 0 {
+            periodo.empresa_nombre.clone()
+        } else {
+            format!("{} (contratista principal)", periodo.empresa_nombre)
+        };
+        match (periodo.inicio, periodo.fin) {
+            (Some(inicio), _) if hoy < inicio => {
+                Some(format!("el contrato de {empresa} inicia el {inicio}"))
+            }
+            (_, Some(fin)) if hoy > fin => {
+                Some(format!("el contrato de {empresa} venció el {fin}"))
+            }
+            _ => None,
+        }
+    })
+}
+
 // --------------------------------------------------------------------------
 // VALIDACIONES DE INPUTS (DTOs)
 // --------------------------------------------------------------------------
@@ -51,6 +327,11 @@ pub fn validar_create_input(input: &CreateEmpresaInput) -> Result<(), EmpresaErr
     if let Some(ref direccion) = input.direccion {
         validar_direccion(direccion)?;
     }
+    if let Some(ref cedula) = input.cedula_juridica {
+        validar_cedula_juridica(cedula)?;
+    }
+    validar_contactos(&input.contactos)?;
+    validar_periodo_contrato(input.contrato_inicio.as_deref(), input.contrato_fin.as_deref())?;
     Ok(())
 }
 
@@ -68,6 +349,15 @@ pub fn validar_update_input(input: &UpdateEmpresaInput) -> Result<(), EmpresaErr
     if let Some(ref direccion) = input.direccion {
         validar_direccion(direccion)?;
     }
+    if let Some(ref cedula) = input.cedula_juridica {
+        validar_cedula_juridica(cedula)?;
+    }
+    if let Some(ref contactos) = input.contactos {
+        validar_contactos(contactos)?;
+    }
+    // Cada extremo se valida por separado; el orden se verifica contra el valor guardado.
+    validar_periodo_contrato(input.contrato_inicio.as_deref(), None)?;
+    validar_periodo_contrato(None, input.contrato_fin.as_deref())?;
     Ok(())
 }
 
@@ -86,6 +376,21 @@ pub fn normalizar_nombre(nombre: &str) -> String {
     nombre.trim().to_string()
 }
 
+/// Normaliza los contactos: recorta espacios y descarta campos opcionales vacíos.
+pub fn normalizar_contactos(contactos: Vec<ContactoEmpresa>) -> Vec<ContactoEmpresa> {
+    let opcional = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    contactos
+        .into_iter()
+        .map(|c| ContactoEmpresa {
+            nombre: c.nombre.trim().to_string(),
+            cargo: opcional(c.cargo),
+            telefono: opcional(c.telefono),
+            email: opcional(c.email).map(|e| e.to_lowercase()),
+            principal: c.principal,
+        })
+        .collect()
+}
+
 // --------------------------------------------------------------------------
 // PRUEBAS UNITARIAS
 // --------------------------------------------------------------------------
@@ -156,36 +461,179 @@ mod tests {
         let input = CreateEmpresaInput {
             nombre: "Empresa Test".to_string(),
             direccion: Some("Calle Principal 123".to_string()),
+            ..Default::default()
         };
         assert!(validar_create_input(&input).is_ok());
     }
 
     #[test]
     fn test_validar_create_input_invalido() {
-        let input = CreateEmpresaInput { nombre: String::new(), direccion: None };
+        let input = CreateEmpresaInput { nombre: String::new(), ..Default::default() };
         assert!(validar_create_input(&input).is_err());
     }
 
     #[test]
     fn test_validar_update_input_valido() {
-        let input = UpdateEmpresaInput {
-            nombre: Some("Nuevo Nombre".to_string()),
-            direccion: None,
-            is_active: None,
-        };
+        let input =
+            UpdateEmpresaInput { nombre: Some("Nuevo Nombre".to_string()), ..Default::default() };
         assert!(validar_update_input(&input).is_ok());
     }
 
     #[test]
     fn test_validar_update_input_sin_cambios() {
-        let input = UpdateEmpresaInput { nombre: None, direccion: None, is_active: None };
+        let input = UpdateEmpresaInput::default();
         assert!(validar_update_input(&input).is_ok());
     }
 
     #[test]
     fn test_validar_update_input_invalido() {
-        let input =
-            UpdateEmpresaInput { nombre: Some(String::new()), direccion: None, is_active: None };
+        let input = UpdateEmpresaInput { nombre: Some(String::new()), ..Default::default() };
         assert!(validar_update_input(&input).is_err());
     }
+
+    fn contacto(nombre: &str) -> ContactoEmpresa {
+        ContactoEmpresa {
+            nombre: nombre.to_string(),
+            cargo: Some("Encargado de contrato".to_string()),
+            telefono: Some("+506 8888-1234".to_string()),
+            email: Some("ana@acme.cr".to_string()),
+            principal: false,
+        }
+    }
+
+    fn fecha(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_validar_cedula_juridica() {
+        assert!(validar_cedula_juridica("3-101-123456").is_ok());
+        assert!(validar_cedula_juridica("3101123456").is_ok());
+        assert!(validar_cedula_juridica("3-101").is_err());
+        assert!(validar_cedula_juridica("J-12345678-9").is_err());
+    }
+
+    #[test]
+    fn test_validar_contactos() {
+        assert!(validar_contactos(&[contacto("Ana Mora"), contacto("Luis Vega")]).is_ok());
+
+        let sin_nombre = ContactoEmpresa { nombre: " ".to_string(), ..contacto("x") };
+        assert!(validar_contactos(&[sin_nombre]).is_err());
+
+        let telefono_malo = ContactoEmpresa { telefono: Some("llamar".into()), ..contacto("Ana") };
+        assert!(validar_contactos(&[telefono_malo]).is_err());
+
+        let email_malo = ContactoEmpresa { email: Some("ana.acme.cr".into()), ..contacto("Ana") };
+        assert!(validar_contactos(&[email_malo]).is_err());
+
+        let principal = ContactoEmpresa { principal: true, ..contacto("Ana") };
+        assert!(validar_contactos(&[principal.clone(), principal]).is_err());
+
+        let muchos = vec![contacto("Ana"); MAX_CONTACTOS_EMPRESA + 1];
+        assert!(validar_contactos(&muchos).is_err());
+    }
+
+    #[test]
+    fn test_validar_periodo_contrato() {
+        let (inicio, fin) =
+            validar_periodo_contrato(Some("2026-01-01"), Some("2026-12-31")).unwrap();
+        assert_eq!(inicio, Some(fecha("2026-01-01")));
+        assert_eq!(fin, Some(fecha("2026-12-31")));
+
+        assert_eq!(validar_periodo_contrato(Some(""), None).unwrap(), (None, None));
+        assert!(validar_periodo_contrato(Some("2026-12-31"), Some("2026-01-01")).is_err());
+        assert!(validar_periodo_contrato(None, Some("31/12/2026")).is_err());
+    }
+
+    fn jerarquia(pares: &[(&str, Option<&str>)]) -> HashMap<String, Option<String>> {
+        pares
+            .iter()
+            .map(|(id, padre)| ((*id).to_string(), padre.map(ToString::to_string)))
+            .collect()
+    }
+
+    #[test]
+    fn test_jerarquia_empresas() {
+        // acme ← beta ← gamma; delta independiente
+        let padres = jerarquia(&[
+            ("empresa:acme", None),
+            ("empresa:beta", Some("empresa:acme")),
+            ("empresa:gamma", Some("empresa:beta")),
+            ("empresa:delta", None),
+        ]);
+
+        assert_eq!(empresa_principal("empresa:gamma", &padres), "empresa:acme");
+        assert_eq!(empresa_principal("empresa:delta", &padres), "empresa:delta");
+
+        let grupo = empresa_y_subempresas("empresa:acme", &padres);
+        assert_eq!(grupo.len(), 3);
+        assert!(grupo.contains("empresa:gamma") && !grupo.contains("empresa:delta"));
+        assert_eq!(empresa_y_subempresas("empresa:gamma", &padres).len(), 1);
+
+        // Ciclos: acme no puede colgar de gamma ni de sí misma
+        assert!(validar_empresa_padre(Some("empresa:acme"), "empresa:gamma", &padres).is_err());
+        assert!(validar_empresa_padre(Some("empresa:acme"), "empresa:acme", &padres).is_err());
+        assert!(validar_empresa_padre(Some("empresa:delta"), "empresa:gamma", &padres).is_ok());
+        assert!(validar_empresa_padre(None, "empresa:beta", &padres).is_ok());
+    }
+
+    #[test]
+    fn test_validar_empresa_padre_profundidad() {
+        let padres = jerarquia(&[
+            ("e1", None),
+            ("e2", Some("e1")),
+            ("e3", Some("e2")),
+            ("e4", Some("e3")),
+            ("e5", Some("e4")),
+        ]);
+        assert!(validar_empresa_padre(None, "e4", &padres).is_ok());
+        assert!(validar_empresa_padre(None, "e5", &padres).is_err());
+    }
+
+    #[test]
+    fn test_validar_empresa_padre_cuenta_subcontratistas() {
+        // a1 → a2 → a3 (tres niveles) y b1 → b2 → b3 (tres niveles)
+        let padres = jerarquia(&[
+            ("a1", None),
+            ("a2", Some("a1")),
+            ("a3", Some("a2")),
+            ("b1", None),
+            ("b2", Some("b1")),
+            ("b3", Some("b2")),
+        ]);
+
+        // b1 bajo a2: 2 ancestros + 3 niveles propios = 5
+        assert!(validar_empresa_padre(Some("b1"), "a2", &padres).is_ok());
+        // b1 bajo a3: 3 + 3 = 6
+        assert!(validar_empresa_padre(Some("b1"), "a3", &padres).is_err());
+        // b2 (dos niveles) sí cabe bajo a3
+        assert!(validar_empresa_padre(Some("b2"), "a3", &padres).is_ok());
+    }
+
+    #[test]
+    fn test_evaluar_contrato() {
+        let periodo = |nombre: &str, inicio: Option<&str>, fin: Option<&str>| PeriodoContrato {
+            empresa_nombre: nombre.to_string(),
+            inicio: inicio.map(fecha),
+            fin: fin.map(fecha),
+        };
+        let hoy = fecha("2026-06-15");
+
+        let vigente = [periodo("Beta", Some("2026-01-01"), Some("2026-12-31"))];
+        assert!(evaluar_contrato(&vigente, hoy).is_none());
+        assert!(evaluar_contrato(&[periodo("Beta", None, None)], hoy).is_none());
+        // El último día del contrato todavía es válido
+        assert!(evaluar_contrato(&[periodo("Beta", None, Some("2026-06-15"))], hoy).is_none());
+
+        let vencido = evaluar_contrato(&[periodo("Beta", None, Some("2026-05-31"))], hoy).unwrap();
+        assert!(vencido.contains("venció el 2026-05-31"));
+
+        let futuro = evaluar_contrato(&[periodo("Beta", Some("2026-07-01"), None)], hoy).unwrap();
+        assert!(futuro.contains("inicia el 2026-07-01"));
+
+        // El contrato vencido de la principal limita a la subcontratista
+        let cadena = [periodo("Beta", None, None), periodo("Acme", None, Some("2026-03-31"))];
+        let motivo = evaluar_contrato(&cadena, hoy).unwrap();
+        assert!(motivo.contains("Acme (contratista principal)"));
+    }
 }
@@ -104,6 +104,74 @@ impl From<crate::services::surrealdb_authorization::AuthError> for ContratistaEr
     }
 }
 
+// ==========================================================================
+// ERRORES DE DOCUMENTOS DE CONTRATISTA
+// ==========================================================================
+
+/// Errores de los documentos (inducción, póliza, certificados) y sus requisitos.
+#[derive(Error, Debug, Serialize)]
+#[serde(tag = "type", content = "message")]
+pub enum DocumentoContratistaError {
+    #[error("Documento no encontrado")]
+    NotFound,
+    #[error("Contratista no encontrado")]
+    ContratistaNotFound,
+    #[error("Empresa no encontrada")]
+    EmpresaNotFound,
+    #[error("Requisito de documento no encontrado")]
+    RequisitoNotFound,
+    #[error("Error de base de datos: {0}")]
+    Database(String),
+    #[error("Error de validación: {0}")]
+    Validation(String),
+    #[error("No autorizado: {0}")]
+    Unauthorized(String),
+    #[error("Error de archivo: {0}")]
+    IO(String),
+    #[error("Error de cifrado: {0}")]
+    Internal(String),
+    #[error("Escaneo alterado o corrupto: {0}")]
+    Integridad(String),
+}
+
+impl From<crate::services::surrealdb_authorization::AuthError> for DocumentoContratistaError {
+    fn from(err: crate::services::surrealdb_authorization::AuthError) -> Self {
+        Self::Unauthorized(err.to_string())
+    }
+}
+
+// ==========================================================================
+// ERRORES DE IMPORTACIÓN MASIVA
+// ==========================================================================
+
+/// Errores de la importación masiva de personas (CSV/Excel).
+///
+/// Los problemas de una fila concreta no son errores: se reportan en la vista previa.
+#[derive(Error, Debug, Serialize)]
+#[serde(tag = "type", content = "message")]
+pub enum ImportacionError {
+    #[error("Empresa no encontrada")]
+    EmpresaNotFound,
+    #[error("Formato de archivo no soportado: {0}")]
+    FormatoNoSoportado(String),
+    #[error("Archivo inválido: {0}")]
+    ArchivoInvalido(String),
+    #[error("No hay filas válidas para importar")]
+    SinFilasValidas,
+    #[error("Error de base de datos: {0}")]
+    Database(String),
+    #[error("Error de archivo: {0}")]
+    IO(String),
+    #[error("No autorizado: {0}")]
+    Unauthorized(String),
+}
+
+impl From<crate::services::surrealdb_authorization::AuthError> for ImportacionError {
+    fn from(err: crate::services::surrealdb_authorization::AuthError) -> Self {
+        Self::Unauthorized(err.to_string())
+    }
+}
+
 // ==========================================================================
 // ERRORES DE EMPRESA
 // ==========================================================================
@@ -118,12 +186,20 @@ pub enum EmpresaError {
     NotFound,
     #[error("Ya existe una empresa con este nombre")]
     NameExists,
-    #[error("Ya existe una empresa con este RIF/NIT")]
+    #[error("Ya existe una empresa con esta cédula jurídica")]
     RifExists,
     #[error("No se puede eliminar: tiene {0} contratista(s) asociado(s)")]
     HasContratistas(i64),
     #[error("No se puede eliminar: tiene {0} proveedor(es) asociado(s)")]
     HasProveedores(i64),
+    #[error("No se puede eliminar: tiene {0} visitante(s) asociado(s)")]
+    HasVisitantes(i64),
+    #[error("No se puede eliminar: tiene {0} subcontratista(s)")]
+    HasSubempresas(i64),
+    #[error(
+        "No se puede desactivar: hay {0} persona(s) de la empresa dentro de las instalaciones"
+    )]
+    PersonalDentro(i64),
     #[error("Error de base de datos: {0}")]
     Database(String),
     #[error("Error de validación: {0}")]
@@ -215,6 +291,8 @@ pub enum GafeteError {
     InvalidState(String),
     #[error("El gafete está deshabilitado")]
     Disabled,
+    #[error("No hay gafetes disponibles: {0}")]
+    SinDisponibles(String),
     #[error("Error de base de datos: {0}")]
     Database(String),
     #[error("Error de validación: {0}")]
@@ -257,6 +335,48 @@ impl From<crate::services::surrealdb_authorization::AuthError> for AlertaError {
     }
 }
 
+// --------------------------------------------------------------------------
+// ERRORES DE ADJUNTOS CIFRADOS
+// --------------------------------------------------------------------------
+
+/// Errores del almacenamiento cifrado de adjuntos, común a evidencias y escaneos.
+///
+/// No llega al frontend: cada servicio lo convierte a su propio enum.
+#[derive(Error, Debug, Serialize)]
+#[serde(tag = "type", content = "message")]
+pub enum AdjuntoError {
+    #[error("Error de validación: {0}")]
+    Validation(String),
+    #[error("Error de archivo: {0}")]
+    IO(String),
+    #[error("Error de cifrado: {0}")]
+    Internal(String),
+    #[error("Adjunto alterado o corrupto: {0}")]
+    Integridad(String),
+}
+
+impl From<AdjuntoError> for DocumentoContratistaError {
+    fn from(err: AdjuntoError) -> Self {
+        match err {
+            AdjuntoError::Validation(msg) => Self::Validation(msg),
+            AdjuntoError::IO(msg) => Self::IO(msg),
+            AdjuntoError::Internal(msg) => Self::Internal(msg),
+            AdjuntoError::Integridad(id) => Self::Integridad(id),
+        }
+    }
+}
+
+impl From<AdjuntoError> for ListaNegraError {
+    fn from(err: AdjuntoError) -> Self {
+        match err {
+            AdjuntoError::Validation(msg) => Self::Validation(msg),
+            AdjuntoError::IO(msg) => Self::IO(msg),
+            AdjuntoError::Internal(msg) => Self::Internal(msg),
+            AdjuntoError::Integridad(id) => Self::Integridad(id),
+        }
+    }
+}
+
 // --------------------------------------------------------------------------
 // ERRORES DE LISTA NEGRA
 // --------------------------------------------------------------------------
@@ -278,6 +398,22 @@ pub enum ListaNegraError {
     Validation(String),
     #[error("Error al parsear fecha: {0}")]
     DateParse(String),
+    #[error("No autorizado: {0}")]
+    Unauthorized(String),
+    #[error("Error de archivo: {0}")]
+    IO(String),
+    #[error("Error de cifrado: {0}")]
+    Internal(String),
+    #[error("Evidencia alterada o corrupta: {0}")]
+    Integridad(String),
+    #[error("Firma del archivo de intercambio inválida: {0}")]
+    FirmaInvalida(String),
+}
+
+impl From<crate::services::surrealdb_authorization::AuthError> for ListaNegraError {
+    fn from(err: crate::services::surrealdb_authorization::AuthError) -> Self {
+        Self::Unauthorized(err.to_string())
+    }
 }
 
 // --------------------------------------------------------------------------
@@ -353,6 +489,8 @@ pub enum IngresoProveedorError {
     NoActiveIngreso,
     #[error("Gafete no disponible")]
     GafeteNotAvailable,
+    #[error("Posible coincidencia en lista negra, confirme la identidad: {0}")]
+    PosibleListaNegra(String),
     #[error("Error de gafete: {0}")]
     Gafete(String),
     #[error("Error de base de datos: {0}")]
@@ -380,8 +518,14 @@ pub enum IngresoContratistaError {
     ContratistaNotFound,
     #[error("El contratista está en lista negra: {0}")]
     Blacklisted(String),
+    #[error("Posible coincidencia en lista negra, confirme la identidad: {0}")]
+    PosibleListaNegra(String),
     #[error("PRAIND vencido: {0}")]
     PraindExpired(String),
+    #[error("Documentos pendientes: {0}")]
+    DocumentosPendientes(String),
+    #[error("Contrato fuera de vigencia: {0}")]
+    ContratoEmpresaVencido(String),
     #[error("El contratista no está activo")]
     ContratistaInactive,
     #[error("El gafete ya se encuentra en uso o tiene una alerta pendiente")]
@@ -460,6 +604,12 @@ pub enum CitaError {
     IngresoVisita(#[from] IngresoVisitaError),
     #[error("Error de validación: {0}")]
     Validation(String),
+    #[error("Error de invitación: {0}")]
+    Invitacion(String),
+    #[error("Conflicto de agenda: {0}")]
+    Conflicto(String),
+    #[error("Error de calendario: {0}")]
+    Calendario(String),
     #[error("No autorizado: {0}")]
     Unauthorized(String),
 }
@@ -508,10 +658,45 @@ pub enum ExportError {
     Io(String),
     #[error("Error de base de datos: {0}")]
     Database(String),
+    #[error("Error de validación: {0}")]
+    Validation(String),
+    #[error("No autorizado: {0}")]
+    Unauthorized(String),
     #[error("Error del motor de exportación: {0}")]
     Engine(#[from] crate::export::errors::ExportError),
 }
 
+impl From<crate::services::surrealdb_authorization::AuthError> for ExportError {
+    fn from(err: crate::services::surrealdb_authorization::AuthError) -> Self {
+        Self::Unauthorized(err.to_string())
+    }
+}
+
+// --------------------------------------------------------------------------
+// ERRORES DE ESCÁNER
+// --------------------------------------------------------------------------
+
+#[derive(Error, Debug, Serialize)]
+#[serde(tag = "type", content = "message")]
+pub enum ScanError {
+    #[error("Lectura inválida: {0}")]
+    Lectura(String),
+    #[error("No encontrado: {0}")]
+    NotFound(String),
+    #[error("Error de base de datos: {0}")]
+    Database(String),
+    #[error("Error de configuración: {0}")]
+    Config(String),
+    #[error("No autorizado: {0}")]
+    Unauthorized(String),
+}
+
+impl From<crate::services::surrealdb_authorization::AuthError> for ScanError {
+    fn from(err: crate::services::surrealdb_authorization::AuthError) -> Self {
+        Self::Unauthorized(err.to_string())
+    }
+}
+
 // --------------------------------------------------------------------------
 // ERRORES DE CONFIGURACIÓN Y SISTEMA
 // --------------------------------------------------------------------------
@@ -525,6 +710,14 @@ pub enum ConfigError {
     Io(String),
     #[error("Error de base de datos: {0}")]
     Database(String),
+    #[error("No autorizado: {0}")]
+    Unauthorized(String),
+}
+
+impl From<crate::services::surrealdb_authorization::AuthError> for ConfigError {
+    fn from(err: crate::services::surrealdb_authorization::AuthError) -> Self {
+        Self::Unauthorized(err.to_string())
+    }
 }
 
 #[derive(Error, Debug, Serialize)]
@@ -3,7 +3,12 @@
 /// Este módulo gestiona la validación de gafetes. A partir de ahora, el número
 /// es un entero (i32), donde 0 representa "Sin Gafete" (S/G).
 use crate::domain::errors::GafeteError;
-use crate::models::gafete::{CreateGafeteInput, TipoGafete, UpdateGafeteInput};
+use crate::models::gafete::{
+    AccionGafete, CreateGafeteInput, PoliticaAsignacionGafete, RangoGafeteEmpresa, TipoGafete,
+    UpdateGafeteInput,
+};
+use crate::models::ingreso::AlertaGafete;
+use chrono::{DateTime, Utc};
 
 // --------------------------------------------------------------------------
 // VALIDACIONES DE CAMPOS INDIVIDUALES
@@ -47,6 +52,175 @@ pub fn validar_update_input(input: &UpdateGafeteInput) -> Result<(), GafeteError
     Ok(())
 }
 
+// --------------------------------------------------------------------------
+// CADENA DE CUSTODIA
+// --------------------------------------------------------------------------
+
+/// Ubica la última entrega del gafete en una historia ordenada del más reciente al más antiguo.
+///
+/// # Retorno
+/// Índice del último movimiento `Asignado` y si sigue pendiente de devolución
+/// (no hay un `Devuelto` posterior a esa entrega).
+pub fn ultimo_portador<'a, I>(acciones_recientes_primero: I) -> Option<(usize, bool)>
+where
+    I: IntoIterator<Item = &'a AccionGafete>,
+{
+    let mut devuelto_despues = false;
+    for (i, accion) in acciones_recientes_primero.into_iter().enumerate() {
+        match accion {
+            AccionGafete::Asignado => return Some((i, !devuelto_despues)),
+            AccionGafete::Devuelto => devuelto_despues = true,
+            AccionGafete::NoDevuelto | AccionGafete::CambioEstado => {}
+        }
+    }
+    None
+}
+
+// --------------------------------------------------------------------------
+// ASIGNACIÓN AUTOMÁTICA
+// --------------------------------------------------------------------------
+
+/// Gafete libre considerado para una propuesta.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CandidatoGafete {
+    pub numero: i32,
+    /// Última entrega en milisegundos Unix; `None` si nunca se ha usado.
+    pub ultimo_uso: Option<i64>,
+}
+
+/// Quita el prefijo de tabla para comparar `empresa:abc` con `abc`.
+fn empresa_key(id: &str) -> &str {
+    id.strip_prefix("empresa:").unwrap_or(id)
+}
+
+/// Valida los rangos reservados por empresa.
+///
+/// Reglas:
+/// - `desde` debe ser mayor que 0 (0 es S/G) y no mayor que `hasta`.
+/// - Dos empresas distintas no pueden compartir números del mismo tipo.
+pub fn validar_rangos(rangos: &[RangoGafeteEmpresa]) -> Result<(), GafeteError> {
+    for (i, r) in rangos.iter().enumerate() {
+        if empresa_key(r.empresa_id.trim()).is_empty() {
+            return Err(GafeteError::Validation("Cada rango debe indicar una empresa".to_string()));
+        }
+        if r.desde <= 0 || r.desde > r.hasta {
+            return Err(GafeteError::Validation(format!(
+                "Rango de gafetes inválido: {}-{}",
+                r.desde, r.hasta
+            )));
+        }
+        let choque = rangos[..i].iter().find(|o| {
+            o.tipo == r.tipo
+                && empresa_key(&o.empresa_id) != empresa_key(&r.empresa_id)
+                && o.desde <= r.hasta
+                && r.desde <= o.hasta
+        });
+        if let Some(o) = choque {
+            return Err(GafeteError::Validation(format!(
+                "Los rangos {}-{} y {}-{} de {} se solapan entre empresas distintas",
+                o.desde, o.hasta, r.desde, r.hasta, r.tipo
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Ordena los gafetes libres según la política configurada.
+///
+/// Reglas:
+/// - Se descartan el 0 (S/G) y los números en `bloqueados` (p. ej. con alerta pendiente).
+/// - Los números dentro de un rango de otra empresa nunca se proponen.
+/// - Los rangos de la propia empresa van primero; después, el resto del pool.
+/// - `MenosUsado` prioriza los nunca usados y luego el uso más antiguo; el número desempata.
+pub fn ordenar_candidatos(
+    candidatos: &[CandidatoGafete],
+    tipo: &TipoGafete,
+    politica: PoliticaAsignacionGafete,
+    rangos: &[RangoGafeteEmpresa],
+    empresa_id: Option<&str>,
+    bloqueados: &[i32],
+) -> Vec<(i32, bool)> {
+    let empresa = empresa_id.map(empresa_key);
+    let mut elegibles: Vec<(&CandidatoGafete, bool)> = candidatos
+        .iter()
+        .filter(|c| c.numero > 0 && !bloqueados.contains(&c.numero))
+        .filter_map(|c| {
+            let rango = rangos
+                .iter()
+                .find(|r| &r.tipo == tipo && r.desde <= c.numero && c.numero <= r.hasta);
+            match rango {
+                None => Some((c, false)),
+                Some(r) if Some(empresa_key(&r.empresa_id)) == empresa => Some((c, true)),
+                Some(_) => None,
+            }
+        })
+        .collect();
+
+    elegibles.sort_by(|(a, propio_a), (b, propio_b)| {
+        let por_rango = propio_b.cmp(propio_a);
+        let por_politica = match politica {
+            PoliticaAsignacionGafete::MenorNumero => std::cmp::Ordering::Equal,
+            // `None` < `Some` en Option: los nunca usados quedan primero
+            PoliticaAsignacionGafete::MenosUsado => a.ultimo_uso.cmp(&b.ultimo_uso),
+        };
+        por_rango.then(por_politica).then(a.numero.cmp(&b.numero))
+    });
+
+    elegibles.into_iter().map(|(c, propio)| (c.numero, propio)).collect()
+}
+
+/// Indica si una reserva vigente de otro usuario impide entregar el gafete.
+///
+/// Una reserva vencida o del propio `usuario` no bloquea; sin `usuario` cualquier
+/// reserva vigente bloquea.
+pub fn reservado_por_otro(
+    reservado_hasta: Option<DateTime<Utc>>,
+    reservado_por: Option<&str>,
+    usuario: Option<&str>,
+    ahora: DateTime<Utc>,
+) -> bool {
+    match reservado_hasta {
+        Some(hasta) if hasta >= ahora => reservado_por.is_none() || reservado_por != usuario,
+        _ => false,
+    }
+}
+
+/// Tipo del gafete de una alerta, deducido del ingreso al que pertenece.
+pub const fn tipo_de_alerta(alerta: &AlertaGafete) -> Option<TipoGafete> {
+    if alerta.ingreso_contratista.is_some() {
+        Some(TipoGafete::Contratista)
+    } else if alerta.ingreso_proveedor.is_some() {
+        Some(TipoGafete::Proveedor)
+    } else if alerta.ingreso_visita.is_some() {
+        Some(TipoGafete::Visita)
+    } else {
+        None
+    }
+}
+
+/// Alertas de gafete pendientes que todavía no tienen su deuda en el libro.
+///
+/// Se omiten las resueltas, las de S/G (0), las de tipo desconocido y los gafetes que
+/// ya tienen una deuda pendiente; de varias alertas del mismo gafete cuenta la primera.
+pub fn alertas_sin_deuda<'a>(
+    alertas: &'a [AlertaGafete],
+    adeudados: &[(i32, TipoGafete)],
+) -> Vec<(&'a AlertaGafete, TipoGafete)> {
+    let mut vistos: Vec<(i32, TipoGafete)> = adeudados.to_vec();
+    let mut pendientes = Vec::new();
+    for alerta in alertas.iter().filter(|a| !a.resuelto && a.gafete_numero > 0) {
+        let Some(tipo) = tipo_de_alerta(alerta) else {
+            continue;
+        };
+        let clave = (alerta.gafete_numero, tipo);
+        if !vistos.contains(&clave) {
+            pendientes.push((alerta, clave.1.clone()));
+            vistos.push(clave);
+        }
+    }
+    pendientes
+}
+
 // --------------------------------------------------------------------------
 // PRUEBAS UNITARIAS
 // --------------------------------------------------------------------------
@@ -95,4 +269,140 @@ mod tests {
         let invalid_input = CreateGafeteInput { numero: -5, tipo: "visita".to_string() };
         assert!(validar_create_input(&invalid_input).is_err());
     }
+
+    fn rango(empresa: &str, desde: i32, hasta: i32) -> RangoGafeteEmpresa {
+        RangoGafeteEmpresa {
+            empresa_id: empresa.to_string(),
+            tipo: TipoGafete::Contratista,
+            desde,
+            hasta,
+        }
+    }
+
+    fn candidatos(datos: &[(i32, Option<i64>)]) -> Vec<CandidatoGafete> {
+        datos.iter().map(|&(numero, ultimo_uso)| CandidatoGafete { numero, ultimo_uso }).collect()
+    }
+
+    #[test]
+    fn test_validar_rangos() {
+        assert!(validar_rangos(&[rango("a", 1, 10), rango("empresa:a", 5, 20)]).is_ok());
+        assert!(validar_rangos(&[rango("a", 1, 10), rango("b", 11, 20)]).is_ok());
+        assert!(validar_rangos(&[rango("a", 1, 10), rango("b", 10, 20)]).is_err());
+        assert!(validar_rangos(&[rango("a", 0, 10)]).is_err());
+        assert!(validar_rangos(&[rango("a", 10, 5)]).is_err());
+
+        let mut otro_tipo = rango("b", 1, 10);
+        otro_tipo.tipo = TipoGafete::Visita;
+        assert!(validar_rangos(&[rango("a", 1, 10), otro_tipo]).is_ok());
+    }
+
+    #[test]
+    fn test_ordenar_candidatos_menor_numero_y_rangos() {
+        let libres = candidatos(&[(0, None), (3, None), (1, None), (12, None), (25, None)]);
+        let rangos = [rango("empresa:a", 10, 19), rango("b", 20, 29)];
+        let tipo = TipoGafete::Contratista;
+        let politica = PoliticaAsignacionGafete::MenorNumero;
+
+        // Empresa con rango: primero lo suyo, nunca lo de otra empresa
+        let orden = ordenar_candidatos(&libres, &tipo, politica, &rangos, Some("a"), &[]);
+        assert_eq!(orden, vec![(12, true), (1, false), (3, false)]);
+
+        // Sin empresa: solo el pool libre, sin los bloqueados
+        let orden = ordenar_candidatos(&libres, &tipo, politica, &rangos, None, &[1]);
+        assert_eq!(orden, vec![(3, false)]);
+    }
+
+    #[test]
+    fn test_ordenar_candidatos_menos_usado() {
+        let libres = candidatos(&[(1, Some(300)), (2, Some(100)), (3, None), (4, Some(100))]);
+        let orden = ordenar_candidatos(
+            &libres,
+            &TipoGafete::Contratista,
+            PoliticaAsignacionGafete::MenosUsado,
+            &[],
+            None,
+            &[],
+        );
+        let numeros: Vec<i32> = orden.into_iter().map(|(n, _)| n).collect();
+        assert_eq!(numeros, vec![3, 2, 4, 1]);
+    }
+
+    #[test]
+    fn test_ultimo_portador() {
+        use AccionGafete::{Asignado, CambioEstado, Devuelto, NoDevuelto};
+
+        assert_eq!(ultimo_portador(&[]), None);
+        assert_eq!(ultimo_portador(&[CambioEstado]), None);
+        // Salió sin devolverlo y luego se marcó extraviado: lo sigue teniendo
+        assert_eq!(
+            ultimo_portador(&[CambioEstado, NoDevuelto, Asignado, Devuelto]),
+            Some((2, true))
+        );
+        // Devuelto tras la última entrega
+        assert_eq!(ultimo_portador(&[Devuelto, Asignado, Devuelto, Asignado]), Some((1, false)));
+    }
+
+    #[test]
+    fn test_reservado_por_otro() {
+        let ahora = Utc::now();
+        let vigente = Some(ahora + chrono::Duration::minutes(5));
+        let vencida = Some(ahora - chrono::Duration::minutes(5));
+
+        assert!(!reservado_por_otro(None, None, Some("user:a"), ahora));
+        assert!(!reservado_por_otro(vencida, Some("user:b"), Some("user:a"), ahora));
+        assert!(!reservado_por_otro(vigente, Some("user:a"), Some("user:a"), ahora));
+        assert!(reservado_por_otro(vigente, Some("user:b"), Some("user:a"), ahora));
+        assert!(reservado_por_otro(vigente, Some("user:b"), None, ahora));
+        assert!(reservado_por_otro(vigente, None, Some("user:a"), ahora));
+    }
+
+    fn alerta(numero: i32, ingreso: &str, resuelto: bool) -> AlertaGafete {
+        let ingreso_id = Some(surrealdb::RecordId::from_table_key(ingreso, "x"));
+        let ahora = surrealdb::Datetime::from(Utc::now());
+        AlertaGafete {
+            id: surrealdb::RecordId::from_table_key("alerta_gafete", numero.to_string()),
+            persona: None,
+            cedula: "1-1111-1111".to_string(),
+            nombre_completo: "Ana Mora".to_string(),
+            gafete_numero: numero,
+            ingreso_contratista: ingreso_id.clone().filter(|_| ingreso == "ingreso_contratista"),
+            ingreso_proveedor: ingreso_id.clone().filter(|_| ingreso == "ingreso_proveedor"),
+            ingreso_visita: ingreso_id.filter(|_| ingreso == "ingreso_visita"),
+            fecha_reporte: ahora.clone(),
+            resuelto,
+            fecha_resolucion: None,
+            resuelto_por: None,
+            notas: None,
+            reportado_por: surrealdb::RecordId::from_table_key("user", "guardia"),
+            created_at: ahora.clone(),
+            updated_at: ahora,
+        }
+    }
+
+    #[test]
+    fn test_alertas_sin_deuda() {
+        let alertas = vec![
+            alerta(5, "ingreso_contratista", false),
+            alerta(5, "ingreso_contratista", false), // duplicada
+            alerta(5, "ingreso_visita", false),      // mismo número, otro tipo
+            alerta(7, "ingreso_proveedor", false),   // ya adeudado
+            alerta(8, "ingreso_proveedor", true),    // resuelta
+            alerta(0, "ingreso_contratista", false), // S/G
+            alerta(9, "otra_tabla", false),          // tipo desconocido
+        ];
+        let adeudados = [(7, TipoGafete::Proveedor)];
+
+        let pendientes: Vec<(i32, TipoGafete)> = alertas_sin_deuda(&alertas, &adeudados)
+            .into_iter()
+            .map(|(a, tipo)| (a.gafete_numero, tipo))
+            .collect();
+        assert_eq!(pendientes, vec![(5, TipoGafete::Contratista), (5, TipoGafete::Visita)]);
+
+        // Idempotente: con las deudas ya abiertas no queda nada por migrar
+        assert!(alertas_sin_deuda(
+            &alertas,
+            &[(5, TipoGafete::Contratista), (5, TipoGafete::Visita), (7, TipoGafete::Proveedor)]
+        )
+        .is_empty());
+    }
 }
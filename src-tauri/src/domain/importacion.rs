@@ -0,0 +1,444 @@
+/// Capa de Dominio: Importación Masiva de Personas (CSV/Excel).
+///
+/// Reglas puras para ubicar las columnas del archivo, leer cada fila y validarla con
+/// los mismos validadores del alta manual (`validar_create_input` de cada módulo).
+/// Las consultas a la base de datos (lista negra, cédula existente, empresa) viven en
+/// `importacion_service`.
+use crate::domain::errors::ImportacionError;
+use crate::domain::{contratista, proveedor, visitante};
+use crate::models::contratista::CreateContratistaInput;
+use crate::models::importacion::TipoImportacion;
+use crate::models::proveedor::CreateProveedorInput;
+use crate::models::visitante::CreateVisitanteInput;
+use std::collections::HashMap;
+
+// --------------------------------------------------------------------------
+// CONSTANTES
+// --------------------------------------------------------------------------
+
+/// Máximo de filas por archivo (sin contar el encabezado).
+pub const MAX_FILAS_IMPORTACION: usize = 2000;
+
+/// Marcador para validar los demás campos de una fila cuya empresa no se resolvió
+/// (el error de empresa se reporta aparte).
+const EMPRESA_PENDIENTE: &str = "empresa:pendiente";
+
+// --------------------------------------------------------------------------
+// COLUMNAS
+// --------------------------------------------------------------------------
+
+/// Campo al que corresponde una columna del archivo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Columna {
+    Cedula,
+    Nombre,
+    SegundoNombre,
+    Apellido,
+    SegundoApellido,
+    Empresa,
+    FechaVencimientoPraind,
+}
+
+impl Columna {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Cedula => "cedula",
+            Self::Nombre => "nombre",
+            Self::SegundoNombre => "segundo_nombre",
+            Self::Apellido => "apellido",
+            Self::SegundoApellido => "segundo_apellido",
+            Self::Empresa => "empresa",
+            Self::FechaVencimientoPraind => "fecha_vencimiento_praind",
+        }
+    }
+}
+
+/// Normaliza un encabezado: minúsculas, sin tildes y con `_` en lugar de espacios.
+pub fn normalizar_encabezado(encabezado: &str) -> String {
+    encabezado
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'ä' => 'a',
+            'é' | 'è' | 'ë' => 'e',
+            'í' | 'ì' | 'ï' => 'i',
+            'ó' | 'ò' | 'ö' => 'o',
+            'ú' | 'ù' | 'ü' => 'u',
+            ' ' | '-' | '.' => '_',
+            otro => otro,
+        })
+        .collect()
+}
+
+/// Ubica el campo de un encabezado, aceptando los nombres habituales en planillas.
+pub fn columna_desde_encabezado(encabezado: &str) -> Option<Columna> {
+    match normalizar_encabezado(encabezado).as_str() {
+        "cedula" | "identificacion" | "documento" | "cedula_identidad" => Some(Columna::Cedula),
+        "nombre" | "primer_nombre" | "nombres" => Some(Columna::Nombre),
+        "segundo_nombre" => Some(Columna::SegundoNombre),
+        "apellido" | "primer_apellido" | "apellidos" => Some(Columna::Apellido),
+        "segundo_apellido" => Some(Columna::SegundoApellido),
+        "empresa" | "empresa_id" | "compania" => Some(Columna::Empresa),
+        "fecha_vencimiento_praind" | "vencimiento_praind" | "praind" => {
+            Some(Columna::FechaVencimientoPraind)
+        }
+        _ => None,
+    }
+}
+
+/// Posición de cada campo dentro de las filas del archivo.
+#[derive(Debug, Clone, Default)]
+pub struct MapaColumnas {
+    indices: HashMap<Columna, usize>,
+    /// Encabezados que no corresponden a ningún campo.
+    pub ignoradas: Vec<String>,
+}
+
+impl MapaColumnas {
+    fn celda<'a>(&self, columna: Columna, fila: &'a [String]) -> Option<&'a str> {
+        self.indices
+            .get(&columna)
+            .and_then(|&i| fila.get(i))
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+    }
+}
+
+/// Ubica las columnas del encabezado y verifica que estén las obligatorias.
+///
+/// # Argumentos
+/// * `tipo` - Tipo de persona (los contratistas exigen el vencimiento del PRAIND).
+/// * `encabezados` - Primera fila del archivo.
+/// * `con_empresa_por_defecto` - Si se eligió una empresa para todo el archivo, la
+///   columna `empresa` es opcional.
+pub fn mapear_columnas(
+    tipo: TipoImportacion,
+    encabezados: &[String],
+    con_empresa_por_defecto: bool,
+) -> Result<MapaColumnas, ImportacionError> {
+    let mut mapa = MapaColumnas::default();
+
+    for (i, encabezado) in encabezados.iter().enumerate() {
+        match columna_desde_encabezado(encabezado) {
+            Some(columna) if mapa.indices.contains_key(&columna) => {
+                return Err(ImportacionError::ArchivoInvalido(format!(
+                    "La columna '{}' aparece más de una vez",
+                    columna.as_str()
+                )));
+            }
+            Some(columna) => {
+                mapa.indices.insert(columna, i);
+            }
+            None if encabezado.trim().is_empty() => {}
+            None => mapa.ignoradas.push(encabezado.trim().to_string()),
+        }
+    }
+
+    let mut requeridas = vec![Columna::Cedula, Columna::Nombre, Columna::Apellido];
+    if !con_empresa_por_defecto {
+        requeridas.push(Columna::Empresa);
+    }
+    if tipo == TipoImportacion::Contratista {
+        requeridas.push(Columna::FechaVencimientoPraind);
+    }
+
+    let faltantes: Vec<&str> = requeridas
+        .into_iter()
+        .filter(|c| !mapa.indices.contains_key(c))
+        .map(Columna::as_str)
+        .collect();
+    if !faltantes.is_empty() {
+        return Err(ImportacionError::ArchivoInvalido(format!(
+            "Faltan columnas obligatorias: {}",
+            faltantes.join(", ")
+        )));
+    }
+
+    Ok(mapa)
+}
+
+// --------------------------------------------------------------------------
+// FILAS
+// --------------------------------------------------------------------------
+
+/// Fila del archivo con sus campos ya ubicados (sin validar).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilaImportacion {
+    /// Número de fila en el archivo (la fila 1 es el encabezado).
+    pub numero: usize,
+    pub cedula: String,
+    pub nombre: String,
+    pub segundo_nombre: Option<String>,
+    pub apellido: String,
+    pub segundo_apellido: Option<String>,
+    /// Nombre o ID de la empresa tal como viene en el archivo.
+    pub empresa: Option<String>,
+    pub fecha_vencimiento_praind: Option<String>,
+}
+
+impl FilaImportacion {
+    pub fn nombre_completo(&self) -> String {
+        [
+            Some(self.nombre.as_str()),
+            self.segundo_nombre.as_deref(),
+            Some(self.apellido.as_str()),
+            self.segundo_apellido.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+}
+
+/// Lee una fila según el mapa de columnas; la cédula se normaliza como en el alta manual.
+pub fn leer_fila(
+    tipo: TipoImportacion,
+    mapa: &MapaColumnas,
+    fila: &[String],
+    numero: usize,
+) -> FilaImportacion {
+    let texto = |columna| mapa.celda(columna, fila).map(str::to_string);
+    let cedula = texto(Columna::Cedula).unwrap_or_default();
+
+    FilaImportacion {
+        numero,
+        cedula: match tipo {
+            TipoImportacion::Contratista => contratista::normalizar_cedula(&cedula),
+            TipoImportacion::Proveedor => proveedor::normalizar_cedula(&cedula),
+            TipoImportacion::Visitante => visitante::normalizar_cedula(&cedula),
+        },
+        nombre: texto(Columna::Nombre).unwrap_or_default(),
+        segundo_nombre: texto(Columna::SegundoNombre),
+        apellido: texto(Columna::Apellido).unwrap_or_default(),
+        segundo_apellido: texto(Columna::SegundoApellido),
+        empresa: texto(Columna::Empresa),
+        fecha_vencimiento_praind: texto(Columna::FechaVencimientoPraind),
+    }
+}
+
+/// Para cada fila, el número de la primera fila anterior con la misma cédula.
+pub fn cedulas_repetidas(filas: &[FilaImportacion]) -> Vec<Option<usize>> {
+    let mut vistas: HashMap<&str, usize> = HashMap::new();
+    filas
+        .iter()
+        .map(|f| {
+            if f.cedula.is_empty() {
+                return None;
+            }
+            let primera = *vistas.entry(&f.cedula).or_insert(f.numero);
+            (primera != f.numero).then_some(primera)
+        })
+        .collect()
+}
+
+/// Resuelve la empresa de una fila por ID (`empresa:xyz` o solo la clave) o por nombre
+/// exacto sin distinguir mayúsculas.
+///
+/// # Argumentos
+/// * `valor` - Celda `empresa` del archivo.
+/// * `empresas` - Pares `(id, nombre)` de las empresas activas.
+pub fn resolver_empresa<'a>(
+    valor: &str,
+    empresas: &'a [(String, String)],
+) -> Option<&'a (String, String)> {
+    let valor = valor.trim();
+    empresas.iter().find(|(id, nombre)| {
+        id == valor
+            || id.strip_prefix("empresa:") == Some(valor)
+            || nombre.trim().to_lowercase() == valor.to_lowercase()
+    })
+}
+
+/// Delimitador más probable de un CSV según su primera línea (`,`, `;` o tabulador).
+pub fn detectar_delimitador(contenido: &str) -> char {
+    let primera = contenido.lines().next().unwrap_or_default();
+    [';', '\t'].into_iter().fold(',', |elegido, d| {
+        if primera.matches(d).count() > primera.matches(elegido).count() {
+            d
+        } else {
+            elegido
+        }
+    })
+}
+
+// --------------------------------------------------------------------------
+// CONVERSIÓN Y VALIDACIÓN
+// --------------------------------------------------------------------------
+
+pub fn a_contratista_input(fila: &FilaImportacion, empresa_id: &str) -> CreateContratistaInput {
+    CreateContratistaInput {
+        cedula: fila.cedula.clone(),
+        nombre: fila.nombre.clone(),
+        segundo_nombre: fila.segundo_nombre.clone(),
+        apellido: fila.apellido.clone(),
+        segundo_apellido: fila.segundo_apellido.clone(),
+        empresa_id: empresa_id.to_string(),
+        fecha_vencimiento_praind: fila.fecha_vencimiento_praind.clone().unwrap_or_default(),
+        tiene_vehiculo: None,
+        tipo_vehiculo: None,
+        placa: None,
+        marca: None,
+        modelo: None,
+        color: None,
+    }
+}
+
+pub fn a_proveedor_input(fila: &FilaImportacion, empresa_id: &str) -> CreateProveedorInput {
+    CreateProveedorInput {
+        cedula: fila.cedula.clone(),
+        nombre: fila.nombre.clone(),
+        segundo_nombre: fila.segundo_nombre.clone(),
+        apellido: fila.apellido.clone(),
+        segundo_apellido: fila.segundo_apellido.clone(),
+        empresa_id: empresa_id.to_string(),
+        tiene_vehiculo: None,
+        tipo_vehiculo: None,
+        placa: None,
+        marca: None,
+        modelo: None,
+        color: None,
+    }
+}
+
+pub fn a_visitante_input(fila: &FilaImportacion, empresa_id: &str) -> CreateVisitanteInput {
+    CreateVisitanteInput {
+        cedula: fila.cedula.clone(),
+        nombre: fila.nombre.clone(),
+        apellido: fila.apellido.clone(),
+        segundo_nombre: fila.segundo_nombre.clone(),
+        segundo_apellido: fila.segundo_apellido.clone(),
+        empresa_id: empresa_id.to_string(),
+        has_vehicle: false,
+        tipo_vehiculo: None,
+        placa: None,
+        marca: None,
+        modelo: None,
+        color: None,
+    }
+}
+
+/// Valida una fila con el `validar_create_input` del tipo correspondiente.
+///
+/// # Argumentos
+/// * `empresa_id` - Empresa ya resuelta; `None` si no se encontró (se valida el resto).
+pub fn validar_fila(
+    tipo: TipoImportacion,
+    fila: &FilaImportacion,
+    empresa_id: Option<&str>,
+) -> Result<(), String> {
+    let empresa_id = empresa_id.unwrap_or(EMPRESA_PENDIENTE);
+    match tipo {
+        TipoImportacion::Contratista => {
+            contratista::validar_create_input(&a_contratista_input(fila, empresa_id))
+                .map_err(|e| e.to_string())
+        }
+        TipoImportacion::Proveedor => {
+            proveedor::validar_create_input(&a_proveedor_input(fila, empresa_id))
+                .map_err(|e| e.to_string())
+        }
+        TipoImportacion::Visitante => {
+            visitante::validar_create_input(&a_visitante_input(fila, empresa_id))
+                .map_err(|e| e.to_string())
+        }
+    }
+}
+
+// --------------------------------------------------------------------------
+// PRUEBAS UNITARIAS
+// --------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fila(valores: &[&str]) -> Vec<String> {
+        valores.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_mapear_columnas() {
+        let encabezados = fila(&["Cédula", "Primer Nombre", "Apellidos", "Teléfono", "PRAIND"]);
+
+        let mapa = mapear_columnas(TipoImportacion::Contratista, &encabezados, true).unwrap();
+        assert_eq!(mapa.ignoradas, vec!["Teléfono"]);
+
+        // Sin empresa por defecto, la columna empresa es obligatoria
+        let err = mapear_columnas(TipoImportacion::Contratista, &encabezados, false).unwrap_err();
+        assert!(err.to_string().contains("empresa"));
+
+        // Los visitantes no necesitan PRAIND
+        let encabezados = fila(&["cedula", "nombre", "apellido"]);
+        assert!(mapear_columnas(TipoImportacion::Visitante, &encabezados, true).is_ok());
+        assert!(mapear_columnas(TipoImportacion::Contratista, &encabezados, true).is_err());
+
+        // Columna repetida
+        let encabezados = fila(&["cedula", "identificación", "nombre", "apellido"]);
+        assert!(mapear_columnas(TipoImportacion::Visitante, &encabezados, true).is_err());
+    }
+
+    #[test]
+    fn test_leer_y_validar_fila() {
+        let encabezados = fila(&["cedula", "nombre", "apellido", "empresa", "praind"]);
+        let mapa = mapear_columnas(TipoImportacion::Contratista, &encabezados, false).unwrap();
+
+        let leida = leer_fila(
+            TipoImportacion::Contratista,
+            &mapa,
+            &fila(&[" 1-1234-0567 ", "Ana", "Mora", "Acme", "2027-03-01"]),
+            2,
+        );
+        assert_eq!(leida.cedula, "1-1234-0567");
+        assert_eq!(leida.empresa.as_deref(), Some("Acme"));
+        assert_eq!(leida.nombre_completo(), "Ana Mora");
+        assert!(validar_fila(TipoImportacion::Contratista, &leida, Some("empresa:acme")).is_ok());
+
+        // Fecha inválida y fila corta (faltan celdas)
+        let fecha_invalida = FilaImportacion {
+            fecha_vencimiento_praind: Some("01/03/2027".into()),
+            ..leida.clone()
+        };
+        assert!(validar_fila(TipoImportacion::Contratista, &fecha_invalida, Some("empresa:acme"))
+            .is_err());
+        let corta = leer_fila(TipoImportacion::Contratista, &mapa, &fila(&["1-1234-0567"]), 3);
+        assert!(corta.nombre.is_empty());
+        assert!(validar_fila(TipoImportacion::Contratista, &corta, None).is_err());
+
+        // Empresa sin resolver: se validan los demás campos
+        assert!(validar_fila(TipoImportacion::Proveedor, &leida, None).is_ok());
+    }
+
+    #[test]
+    fn test_cedulas_repetidas() {
+        let con_cedula = |numero: usize, cedula: &str| FilaImportacion {
+            numero,
+            cedula: cedula.into(),
+            ..FilaImportacion::default()
+        };
+        let filas =
+            [con_cedula(2, "111"), con_cedula(3, "222"), con_cedula(4, "111"), con_cedula(5, "")];
+        assert_eq!(cedulas_repetidas(&filas), vec![None, None, Some(2), None]);
+    }
+
+    #[test]
+    fn test_resolver_empresa() {
+        let empresas = vec![
+            ("empresa:acme".to_string(), "Acme S.A.".to_string()),
+            ("empresa:beta".to_string(), "Constructora Beta".to_string()),
+        ];
+        assert_eq!(resolver_empresa("empresa:acme", &empresas).unwrap().1, "Acme S.A.");
+        assert_eq!(resolver_empresa("beta", &empresas).unwrap().1, "Constructora Beta");
+        assert_eq!(resolver_empresa(" acme s.a. ", &empresas).unwrap().0, "empresa:acme");
+        assert!(resolver_empresa("Gamma", &empresas).is_none());
+    }
+
+    #[test]
+    fn test_detectar_delimitador() {
+        assert_eq!(detectar_delimitador("cedula;nombre;apellido\n1;a;b"), ';');
+        assert_eq!(detectar_delimitador("cedula,nombre\n"), ',');
+        assert_eq!(detectar_delimitador("cedula\tnombre\tapellido"), '\t');
+        assert_eq!(detectar_delimitador(""), ',');
+    }
+}
@@ -4,6 +4,9 @@
 /// contratistas, incluyendo el cálculo de tiempos de permanencia, gestión de
 /// alertas por vencimiento de documentos (PRAIND) y cierres manuales.
 use crate::domain::errors::IngresoContratistaError;
+use crate::models::contratista::{
+    AccionPraind, DigestEmpresaPraind, EntradaDigestPraind, EstadoContratista,
+};
 use chrono::Utc;
 
 // Re-exportaciones de estructuras desde models
@@ -106,6 +109,61 @@ pub fn debe_suspender_por_praind(
     Ok(dias < 0)
 }
 
+/// Clasifica un contratista para el digest diario de PRAIND.
+///
+/// Solo se consideran contratistas activos: los suspendidos ya recibieron la
+/// acción y los inactivos/bloqueados no pueden ingresar de todos modos.
+///
+/// # Retorno
+/// * `Some(AccionPraind::Suspendido)` - PRAIND vencido, debe suspenderse.
+/// * `Some(AccionPraind::PorVencer)` - Vence dentro de `DIAS_ALERTA_PRAIND`.
+/// * `None` - No entra en el digest.
+pub fn clasificar_para_digest(
+    estado: &EstadoContratista,
+    dias_restantes: i64,
+) -> Option<AccionPraind> {
+    if *estado != EstadoContratista::Activo {
+        return None;
+    }
+    if dias_restantes < 0 {
+        Some(AccionPraind::Suspendido)
+    } else if dias_restantes <= DIAS_ALERTA_PRAIND {
+        Some(AccionPraind::PorVencer)
+    } else {
+        None
+    }
+}
+
+/// Agrupa las entradas del digest por empresa.
+///
+/// Las empresas se ordenan por nombre y, dentro de cada una, los contratistas
+/// por días restantes (los más urgentes primero).
+///
+/// # Argumentos
+/// * `entradas` - Tuplas `(empresa_id, empresa_nombre, entrada)`.
+pub fn agrupar_digest_por_empresa(
+    entradas: Vec<(String, String, EntradaDigestPraind)>,
+) -> Vec<DigestEmpresaPraind> {
+    let mut grupos: Vec<DigestEmpresaPraind> = Vec::new();
+
+    for (empresa_id, empresa_nombre, entrada) in entradas {
+        match grupos.iter_mut().find(|g| g.empresa_id == empresa_id) {
+            Some(grupo) => grupo.entradas.push(entrada),
+            None => grupos.push(DigestEmpresaPraind {
+                empresa_id,
+                empresa_nombre,
+                entradas: vec![entrada],
+            }),
+        }
+    }
+
+    grupos.sort_by(|a, b| a.empresa_nombre.to_lowercase().cmp(&b.empresa_nombre.to_lowercase()));
+    for grupo in &mut grupos {
+        grupo.entradas.sort_by_key(|e| e.dias_restantes);
+    }
+    grupos
+}
+
 // --------------------------------------------------------------------------
 // LÓGICA DE CONTROL: CIERRE MANUAL
 // --------------------------------------------------------------------------
@@ -257,6 +315,46 @@ mod tests {
         assert!(!debe_suspender_por_praind(&hoy).unwrap());
     }
 
+    #[test]
+    fn test_clasificar_para_digest() {
+        let activo = EstadoContratista::Activo;
+        assert_eq!(clasificar_para_digest(&activo, -1), Some(AccionPraind::Suspendido));
+        assert_eq!(clasificar_para_digest(&activo, 0), Some(AccionPraind::PorVencer));
+        assert_eq!(
+            clasificar_para_digest(&activo, DIAS_ALERTA_PRAIND),
+            Some(AccionPraind::PorVencer)
+        );
+        assert_eq!(clasificar_para_digest(&activo, DIAS_ALERTA_PRAIND + 1), None);
+
+        // Ya suspendido o fuera de operación: no se vuelve a reportar
+        assert_eq!(clasificar_para_digest(&EstadoContratista::Suspendido, -5), None);
+        assert_eq!(clasificar_para_digest(&EstadoContratista::Bloqueado, 3), None);
+    }
+
+    #[test]
+    fn test_agrupar_digest_por_empresa() {
+        let entrada = |cedula: &str, dias: i64| EntradaDigestPraind {
+            contratista_id: format!("contratista:{cedula}"),
+            cedula: cedula.to_string(),
+            nombre_completo: format!("Persona {cedula}"),
+            fecha_vencimiento: "2026-01-01".to_string(),
+            dias_restantes: dias,
+            accion: if dias < 0 { AccionPraind::Suspendido } else { AccionPraind::PorVencer },
+        };
+
+        let grupos = agrupar_digest_por_empresa(vec![
+            ("empresa:z".into(), "Zeta".into(), entrada("1", 20)),
+            ("empresa:a".into(), "alfa".into(), entrada("2", 10)),
+            ("empresa:z".into(), "Zeta".into(), entrada("3", -2)),
+        ]);
+
+        assert_eq!(grupos.len(), 2);
+        assert_eq!(grupos[0].empresa_nombre, "alfa");
+        assert_eq!(grupos[1].empresa_id, "empresa:z");
+        let cedulas: Vec<&str> = grupos[1].entradas.iter().map(|e| e.cedula.as_str()).collect();
+        assert_eq!(cedulas, vec!["3", "1"]);
+    }
+
     // ==========================================
     // TESTS NUEVOS: CIERRE MANUAL
     // ==========================================
@@ -3,12 +3,18 @@
 /// Este módulo define la lógica pura para la validación de personas con acceso
 /// denegado a las instalaciones por motivos de seguridad o administrativos.
 use crate::domain::common::{
-    normalizar_nombre_propio, validar_cedula_estandar, validar_nombre_estandar,
-    ENTIDAD_NOMBRE_MAX_LEN, MOTIVO_MAX_LEN,
+    normalizar_nombre_propio, parsear_fecha_simple, validar_cedula_estandar,
+    validar_nombre_estandar, ENTIDAD_NOMBRE_MAX_LEN, MOTIVO_MAX_LEN,
 };
 
 use crate::domain::errors::ListaNegraError;
-use crate::models::lista_negra::{AddToListaNegraInput, NivelSeveridad, UpdateListaNegraInput};
+use crate::models::lista_negra::{
+    AddToListaNegraInput, EstadoPropuestaListaNegra, NivelSeveridad, RegistroIntercambioListaNegra,
+    SitioConfiable, TipoCambioImportacion, UpdateEmpresaVetadaInput, UpdateListaNegraInput,
+    VetarEmpresaInput,
+};
+use chrono::{DateTime, Local, Months, Utc};
+use ring::signature::{Ed25519KeyPair, UnparsedPublicKey, ED25519, ED25519_PUBLIC_KEY_LEN};
 
 // --------------------------------------------------------------------------
 // VALIDACIONES DE CAMPOS INDIVIDUALES
@@ -44,6 +50,23 @@ pub fn validar_motivo(motivo: &str) -> Result<(), ListaNegraError> {
     Ok(())
 }
 
+/// Valida el nombre de un sitio (planta) de origen o destino de un intercambio.
+pub fn validar_sitio(sitio: &str) -> Result<(), ListaNegraError> {
+    let limpio = sitio.trim();
+
+    if limpio.is_empty() {
+        return Err(ListaNegraError::Validation("Debe indicar el sitio de origen".to_string()));
+    }
+
+    if limpio.len() > ENTIDAD_NOMBRE_MAX_LEN {
+        return Err(ListaNegraError::Validation(format!(
+            "El sitio no puede exceder {ENTIDAD_NOMBRE_MAX_LEN} caracteres"
+        )));
+    }
+
+    Ok(())
+}
+
 /// Valida la identidad de la entidad que solicita el bloqueo.
 pub fn validar_bloqueado_por(bloqueado_por: &str) -> Result<(), ListaNegraError> {
     let limpio = bloqueado_por.trim();
@@ -87,6 +110,9 @@ pub fn validar_add_input(input: &AddToListaNegraInput) -> Result<(), ListaNegraE
     }
     validar_bloqueado_por(&input.bloqueado_por)?;
     validar_nivel_severidad(&input.nivel_severidad)?;
+    if let Some(ref sitio) = input.sitio_origen {
+        validar_sitio(sitio)?;
+    }
 
     Ok(())
 }
@@ -104,6 +130,538 @@ pub fn validar_update_input(input: &UpdateListaNegraInput) -> Result<(), ListaNe
     Ok(())
 }
 
+/// Valida el veto de una empresa: el motivo es obligatorio.
+pub fn validar_vetar_empresa_input(input: &VetarEmpresaInput) -> Result<(), ListaNegraError> {
+    if input.empresa_id.trim().is_empty() {
+        return Err(ListaNegraError::Validation("Debe especificar la empresa a vetar".to_string()));
+    }
+    validar_motivo(&input.motivo)?;
+    validar_nivel_severidad(&input.nivel_severidad)?;
+
+    Ok(())
+}
+
+/// Valida la actualización parcial de un veto de empresa.
+pub fn validar_update_empresa_vetada_input(
+    input: &UpdateEmpresaVetadaInput,
+) -> Result<(), ListaNegraError> {
+    if let Some(ref motivo) = input.motivo {
+        validar_motivo(motivo)?;
+    }
+
+    if let Some(ref nivel) = input.nivel_severidad {
+        validar_nivel_severidad(nivel)?;
+    }
+
+    Ok(())
+}
+
+// --------------------------------------------------------------------------
+// VIGENCIA Y REVISIÓN
+// --------------------------------------------------------------------------
+
+/// Fechas de vencimiento y próxima revisión de un bloqueo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VigenciaBloqueo {
+    /// `None` si el bloqueo no expira solo.
+    pub vigente_hasta: Option<DateTime<Utc>>,
+    pub proxima_revision: DateTime<Utc>,
+}
+
+/// Valida una política de vigencia (meses de vigencia y de revisión).
+pub fn validar_politica_vigencia(
+    nivel: &str,
+    vigencia_meses: Option<u32>,
+    revision_meses: u32,
+) -> Result<(), ListaNegraError> {
+    if revision_meses == 0 {
+        return Err(ListaNegraError::Validation(format!(
+            "La revisión de bloqueos {nivel} debe ser de al menos un mes"
+        )));
+    }
+    if vigencia_meses == Some(0) {
+        return Err(ListaNegraError::Validation(format!(
+            "La vigencia de bloqueos {nivel} debe ser de al menos un mes"
+        )));
+    }
+    Ok(())
+}
+
+/// Calcula la vigencia de un bloqueo a partir de `desde`.
+///
+/// Reglas:
+/// - `vigente_hasta` explícito tiene prioridad sobre la vigencia del nivel.
+/// - La próxima revisión nunca queda después del vencimiento.
+pub fn calcular_vigencia(
+    desde: DateTime<Utc>,
+    vigencia_meses: Option<u32>,
+    revision_meses: u32,
+    vigente_hasta: Option<DateTime<Utc>>,
+) -> VigenciaBloqueo {
+    let sumar = |meses: u32| desde.checked_add_months(Months::new(meses)).unwrap_or(desde);
+
+    let vigente_hasta = vigente_hasta.or_else(|| vigencia_meses.map(sumar));
+    let revision = sumar(revision_meses);
+    let proxima_revision = vigente_hasta.map_or(revision, |v| revision.min(v));
+
+    VigenciaBloqueo { vigente_hasta, proxima_revision }
+}
+
+/// Indica si un bloqueo sigue vigente en `ahora`.
+pub fn esta_vigente(vigente_hasta: Option<DateTime<Utc>>, ahora: DateTime<Utc>) -> bool {
+    vigente_hasta.is_none_or(|v| v > ahora)
+}
+
+/// Interpreta la fecha de vencimiento indicada por el usuario.
+///
+/// Acepta RFC 3339 o `YYYY-MM-DD` (en ese caso, hasta el final del día local).
+/// Debe ser posterior a `ahora`.
+pub fn parsear_vigente_hasta(
+    valor: &str,
+    ahora: DateTime<Utc>,
+) -> Result<DateTime<Utc>, ListaNegraError> {
+    let valor = valor.trim();
+    let fecha = if let Ok(f) = DateTime::parse_from_rfc3339(valor) {
+        f.with_timezone(&Utc)
+    } else {
+        let dia =
+            parsear_fecha_simple(valor).map_err(|e| ListaNegraError::DateParse(e.to_string()))?;
+        dia.and_hms_opt(23, 59, 59)
+            .and_then(|dt| dt.and_local_timezone(Local).earliest())
+            .map(|dt| dt.with_timezone(&Utc))
+            .ok_or_else(|| ListaNegraError::DateParse(format!("Fecha inexistente: {valor}")))?
+    };
+
+    if fecha <= ahora {
+        return Err(ListaNegraError::Validation(
+            "La fecha de vencimiento del bloqueo debe ser futura".to_string(),
+        ));
+    }
+    Ok(fecha)
+}
+
+// --------------------------------------------------------------------------
+// COINCIDENCIA APROXIMADA POR NOMBRE
+// --------------------------------------------------------------------------
+
+/// Partículas de apellidos compuestos que no aportan a la comparación ("de la Cruz").
+const PARTICULAS_NOMBRE: [&str; 6] = ["de", "del", "la", "las", "los", "y"];
+
+/// Peso del grupo de apellidos frente al de nombres en el puntaje final.
+const PESO_APELLIDOS: f64 = 0.6;
+
+/// Partes del nombre de una persona, tal como se registran en el sistema.
+#[derive(Debug, Clone, Copy)]
+pub struct NombrePersona<'a> {
+    pub nombre: &'a str,
+    pub segundo_nombre: Option<&'a str>,
+    pub apellido: &'a str,
+    pub segundo_apellido: Option<&'a str>,
+}
+
+impl NombrePersona<'_> {
+    /// Tokens plegados de los nombres de pila.
+    pub fn tokens_nombres(&self) -> Vec<String> {
+        tokens_de(&[Some(self.nombre), self.segundo_nombre])
+    }
+
+    /// Tokens plegados de los apellidos.
+    pub fn tokens_apellidos(&self) -> Vec<String> {
+        tokens_de(&[Some(self.apellido), self.segundo_apellido])
+    }
+}
+
+/// Pliega un texto para comparar nombres: minúsculas, sin tildes ni diéresis, `ñ` → `n`.
+///
+/// Equivale al tokenizer `es_folding` del índice, de modo que los tokens generados
+/// aquí pueden consultarse directamente contra él.
+pub fn plegar_nombre(texto: &str) -> Vec<String> {
+    texto
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.chars().flat_map(char::to_lowercase).map(plegar_caracter).collect::<String>())
+        .filter(|t| !PARTICULAS_NOMBRE.contains(&t.as_str()))
+        .collect()
+}
+
+const fn plegar_caracter(c: char) -> char {
+    match c {
+        'á' | 'à' | 'ä' | 'â' | 'ã' => 'a',
+        'é' | 'è' | 'ë' | 'ê' => 'e',
+        'í' | 'ì' | 'ï' | 'î' => 'i',
+        'ó' | 'ò' | 'ö' | 'ô' | 'õ' => 'o',
+        'ú' | 'ù' | 'ü' | 'û' => 'u',
+        'ñ' => 'n',
+        'ç' => 'c',
+        _ => c,
+    }
+}
+
+fn tokens_de(partes: &[Option<&str>]) -> Vec<String> {
+    partes.iter().flatten().flat_map(|p| plegar_nombre(p)).collect()
+}
+
+/// Distancia de edición (Damerau restringida: una transposición cuenta como un cambio).
+pub fn distancia_edicion(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut tabla = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, fila) in tabla.iter_mut().enumerate() {
+        fila[0] = i;
+    }
+    for (j, celda) in tabla[0].iter_mut().enumerate() {
+        *celda = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let costo = usize::from(a[i - 1] != b[j - 1]);
+            let mut valor =
+                (tabla[i - 1][j] + 1).min(tabla[i][j - 1] + 1).min(tabla[i - 1][j - 1] + costo);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                valor = valor.min(tabla[i - 2][j - 2] + 1);
+            }
+            tabla[i][j] = valor;
+        }
+    }
+    tabla[a.len()][b.len()]
+}
+
+/// Errores de tipeo tolerados en un token según su largo (para consultas difusas).
+pub const fn distancia_maxima(largo: usize) -> u8 {
+    match largo {
+        0..=3 => 0,
+        4..=6 => 1,
+        _ => 2,
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn similitud_token(a: &str, b: &str) -> f64 {
+    let largo = a.chars().count().max(b.chars().count());
+    if largo == 0 {
+        return 0.0;
+    }
+    1.0 - distancia_edicion(a, b) as f64 / largo as f64
+}
+
+/// Similitud entre dos grupos de tokens (nombres o apellidos).
+///
+/// Cada token del grupo más corto se empareja con su mejor par del otro grupo, así un
+/// segundo nombre o apellido omitido no anula la coincidencia; solo la rebaja un poco.
+#[allow(clippy::cast_precision_loss)]
+fn similitud_grupo(a: &[String], b: &[String]) -> f64 {
+    let (corto, largo) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if corto.is_empty() {
+        return 0.0;
+    }
+
+    let suma: f64 =
+        corto.iter().map(|t| largo.iter().map(|o| similitud_token(t, o)).fold(0.0, f64::max)).sum();
+    let promedio = suma / corto.len() as f64;
+    let cobertura = corto.len() as f64 / largo.len() as f64;
+
+    promedio * 0.1f64.mul_add(cobertura, 0.9)
+}
+
+/// Puntaje de similitud (0–1) entre dos nombres de persona.
+///
+/// Ignora tildes, mayúsculas y partículas, tolera errores de tipeo y considera segundo
+/// nombre y segundo apellido cuando ambos lados los tienen. Los apellidos pesan más que
+/// los nombres de pila.
+pub fn similitud_nombres(a: &NombrePersona<'_>, b: &NombrePersona<'_>) -> f64 {
+    let nombres = similitud_grupo(&a.tokens_nombres(), &b.tokens_nombres());
+    let apellidos = similitud_grupo(&a.tokens_apellidos(), &b.tokens_apellidos());
+    PESO_APELLIDOS.mul_add(apellidos, (1.0 - PESO_APELLIDOS) * nombres)
+}
+
+/// Valida el umbral de coincidencia por nombre configurado.
+pub fn validar_umbral_coincidencia(umbral: f64) -> Result<(), ListaNegraError> {
+    if !(0.5..=1.0).contains(&umbral) {
+        return Err(ListaNegraError::Validation(
+            "El umbral de coincidencia por nombre debe estar entre 0.5 y 1.0".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// --------------------------------------------------------------------------
+// EVIDENCIAS ADJUNTAS
+// --------------------------------------------------------------------------
+
+/// Tamaño máximo de una evidencia (10 MiB).
+pub const EVIDENCIA_MAX_BYTES: usize = 10 * 1024 * 1024;
+
+/// Máximo de evidencias por registro de lista negra.
+pub const EVIDENCIAS_MAX_POR_REGISTRO: usize = 20;
+
+// --------------------------------------------------------------------------
+// INTERCAMBIO ENTRE SITIOS
+// --------------------------------------------------------------------------
+
+/// Versión del formato de intercambio (JSON y CSV).
+pub const INTERCAMBIO_VERSION: u32 = 1;
+
+/// Prefijo de la firma separada (`.sig`) de un archivo de intercambio.
+pub const PREFIJO_FIRMA_INTERCAMBIO: &str = "ed25519:";
+
+/// Columnas del formato CSV, en orden.
+pub const COLUMNAS_INTERCAMBIO_CSV: [&str; 10] = [
+    "cedula",
+    "nombre",
+    "segundo_nombre",
+    "apellido",
+    "segundo_apellido",
+    "empresa_nombre",
+    "nivel_severidad",
+    "motivo_bloqueo",
+    "vigente_hasta",
+    "sitio_origen",
+];
+
+/// Similitud mínima para considerar que una cédula ya registrada es la misma persona.
+pub const UMBRAL_MISMA_PERSONA_IMPORTACION: f64 = 0.9;
+
+/// Firma (Ed25519) el contenido exacto de un archivo de intercambio con la clave privada
+/// del sitio (PKCS#8).
+pub fn firmar_intercambio(contenido: &[u8], clave_pkcs8: &[u8]) -> Result<String, ListaNegraError> {
+    let par = Ed25519KeyPair::from_pkcs8(clave_pkcs8)
+        .map_err(|e| ListaNegraError::FirmaInvalida(format!("clave de firma inválida: {e}")))?;
+    Ok(format!("{PREFIJO_FIRMA_INTERCAMBIO}{}", hex::encode(par.sign(contenido))))
+}
+
+/// Decodifica y valida la clave pública (HEX) de un sitio confiable.
+pub fn validar_clave_publica(clave_hex: &str) -> Result<Vec<u8>, ListaNegraError> {
+    hex::decode(clave_hex.trim())
+        .ok()
+        .filter(|clave| clave.len() == ED25519_PUBLIC_KEY_LEN)
+        .ok_or_else(|| {
+            ListaNegraError::Validation(format!(
+                "La clave pública debe ser Ed25519 ({ED25519_PUBLIC_KEY_LEN} bytes en HEX)"
+            ))
+        })
+}
+
+/// Valida la lista de sitios confiables: nombre válido, clave válida y sin repetidos.
+pub fn validar_sitios_confiables(sitios: &[SitioConfiable]) -> Result<(), ListaNegraError> {
+    for (i, sitio) in sitios.iter().enumerate() {
+        validar_sitio(&sitio.nombre)?;
+        validar_clave_publica(&sitio.clave_publica)?;
+        if sitios[..i].iter().any(|s| mismo_sitio(&s.nombre, &sitio.nombre)) {
+            return Err(ListaNegraError::Validation(format!(
+                "El sitio '{}' está repetido",
+                sitio.nombre.trim()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Verifica la firma separada de un archivo de intercambio contra las claves públicas de
+/// los sitios confiables y retorna el sitio que la firmó.
+pub fn verificar_firma_intercambio<'a>(
+    contenido: &[u8],
+    firma: &str,
+    confiables: &'a [SitioConfiable],
+) -> Result<&'a SitioConfiable, ListaNegraError> {
+    let sig = firma
+        .trim()
+        .strip_prefix(PREFIJO_FIRMA_INTERCAMBIO)
+        .and_then(|h| hex::decode(h).ok())
+        .ok_or_else(|| ListaNegraError::FirmaInvalida("formato no reconocido".to_string()))?;
+
+    confiables
+        .iter()
+        .find(|sitio| {
+            validar_clave_publica(&sitio.clave_publica).is_ok_and(|clave| {
+                UnparsedPublicKey::new(&ED25519, clave).verify(contenido, &sig).is_ok()
+            })
+        })
+        .ok_or_else(|| {
+            ListaNegraError::FirmaInvalida(
+                "el archivo fue alterado o no lo firmó ningún sitio confiable".to_string(),
+            )
+        })
+}
+
+/// El sitio declarado en el encabezado del archivo debe ser el dueño de la clave que lo
+/// firmó: un sitio confiable no puede hacerse pasar por otro.
+pub fn validar_sitio_firmante(
+    sitio_archivo: Option<&str>,
+    firmante: &str,
+) -> Result<(), ListaNegraError> {
+    match sitio_archivo {
+        Some(sitio) if !mismo_sitio(sitio, firmante) => {
+            Err(ListaNegraError::FirmaInvalida(format!(
+                "el archivo dice venir de '{}' pero lo firmó '{}'",
+                sitio.trim(),
+                firmante.trim()
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn mismo_sitio(a: &str, b: &str) -> bool {
+    a.trim().to_lowercase() == b.trim().to_lowercase()
+}
+
+/// Convierte un registro en una fila CSV (orden de `COLUMNAS_INTERCAMBIO_CSV`).
+pub fn registro_a_fila_csv(r: &RegistroIntercambioListaNegra) -> Vec<String> {
+    let opcional = |v: &Option<String>| v.clone().unwrap_or_default();
+    vec![
+        r.cedula.clone(),
+        r.nombre.clone(),
+        opcional(&r.segundo_nombre),
+        r.apellido.clone(),
+        opcional(&r.segundo_apellido),
+        opcional(&r.empresa_nombre),
+        r.nivel_severidad.clone(),
+        opcional(&r.motivo_bloqueo),
+        opcional(&r.vigente_hasta),
+        opcional(&r.sitio_origen),
+    ]
+}
+
+/// Lee un registro desde una fila CSV, ubicando cada columna por su encabezado.
+///
+/// Los encabezados no distinguen mayúsculas; las columnas desconocidas se ignoran y
+/// los campos vacíos se tratan como ausentes.
+pub fn registro_desde_fila_csv(
+    encabezados: &[String],
+    fila: &[String],
+) -> Result<RegistroIntercambioListaNegra, String> {
+    let campo = |nombre: &str| {
+        encabezados
+            .iter()
+            .position(|e| e.trim().eq_ignore_ascii_case(nombre))
+            .and_then(|i| fila.get(i))
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let requerido =
+        |nombre: &str| campo(nombre).ok_or_else(|| format!("Falta la columna '{nombre}'"));
+
+    Ok(RegistroIntercambioListaNegra {
+        cedula: requerido("cedula")?,
+        nombre: requerido("nombre")?,
+        segundo_nombre: campo("segundo_nombre"),
+        apellido: requerido("apellido")?,
+        segundo_apellido: campo("segundo_apellido"),
+        empresa_nombre: campo("empresa_nombre"),
+        nivel_severidad: requerido("nivel_severidad")?,
+        motivo_bloqueo: campo("motivo_bloqueo"),
+        vigente_hasta: campo("vigente_hasta"),
+        sitio_origen: campo("sitio_origen"),
+    })
+}
+
+/// Valida un registro importado; retorna su nivel de severidad.
+pub fn validar_registro_intercambio(
+    r: &RegistroIntercambioListaNegra,
+) -> Result<NivelSeveridad, ListaNegraError> {
+    validar_cedula(&r.cedula)?;
+    validar_nombre(&r.nombre)?;
+    validar_nombre(&r.apellido)?;
+    if let Some(ref motivo) = r.motivo_bloqueo {
+        validar_motivo(motivo)?;
+    }
+    match r.sitio_origen {
+        Some(ref sitio) => validar_sitio(sitio)?,
+        None => {
+            return Err(ListaNegraError::Validation(
+                "El registro no indica su sitio de origen".to_string(),
+            ))
+        }
+    }
+    validar_nivel_severidad(&r.nivel_severidad)
+}
+
+/// Bloqueo existente en este sitio para la misma cédula de un registro importado.
+#[derive(Debug, Clone, Copy)]
+pub struct BloqueoLocal<'a> {
+    pub nombre: NombrePersona<'a>,
+    pub nivel_severidad: &'a str,
+    /// Activo y no vencido.
+    pub vigente: bool,
+}
+
+/// Determina qué cambio produciría un registro importado frente al bloqueo local.
+///
+/// Una cédula ya registrada con un nombre distinto es un conflicto aunque el bloqueo
+/// local no esté vigente: probablemente se trata de un error de digitación en un sitio.
+pub fn clasificar_importacion(
+    entrante: &RegistroIntercambioListaNegra,
+    local: Option<&BloqueoLocal<'_>>,
+) -> TipoCambioImportacion {
+    let Some(local) = local else {
+        return TipoCambioImportacion::Nuevo;
+    };
+
+    let nombre_entrante = NombrePersona {
+        nombre: &entrante.nombre,
+        segundo_nombre: entrante.segundo_nombre.as_deref(),
+        apellido: &entrante.apellido,
+        segundo_apellido: entrante.segundo_apellido.as_deref(),
+    };
+    if similitud_nombres(&nombre_entrante, &local.nombre) < UMBRAL_MISMA_PERSONA_IMPORTACION {
+        return TipoCambioImportacion::Conflicto;
+    }
+
+    if !local.vigente {
+        TipoCambioImportacion::Nuevo
+    } else if local.nivel_severidad.eq_ignore_ascii_case(entrante.nivel_severidad.trim()) {
+        TipoCambioImportacion::SinCambios
+    } else {
+        TipoCambioImportacion::SeveridadActualizada
+    }
+}
+
+// --------------------------------------------------------------------------
+// APROBACIÓN DE CAMBIOS (DOBLE CONTROL)
+// --------------------------------------------------------------------------
+
+/// Valida que un supervisor pueda resolver una propuesta.
+///
+/// Reglas del doble control:
+/// - Solo se resuelven propuestas pendientes.
+/// - Quien propone no puede aprobar ni rechazar su propia propuesta.
+/// - La decisión siempre lleva un comentario (queda en la auditoría).
+pub fn validar_resolucion(
+    estado: EstadoPropuestaListaNegra,
+    propuesto_por: &str,
+    revisor: &str,
+    comentario: &str,
+) -> Result<(), ListaNegraError> {
+    if estado != EstadoPropuestaListaNegra::Pendiente {
+        return Err(ListaNegraError::Validation(format!(
+            "La propuesta ya fue resuelta ({})",
+            estado.as_str()
+        )));
+    }
+
+    if propuesto_por.trim() == revisor.trim() {
+        return Err(ListaNegraError::Unauthorized(
+            "Quien propone un cambio no puede resolverlo".to_string(),
+        ));
+    }
+
+    let limpio = comentario.trim();
+    if limpio.is_empty() {
+        return Err(ListaNegraError::Validation(
+            "Debe justificar la aprobación o el rechazo".to_string(),
+        ));
+    }
+    if limpio.len() > MOTIVO_MAX_LEN {
+        return Err(ListaNegraError::Validation(format!(
+            "El comentario no puede exceder {MOTIVO_MAX_LEN} caracteres"
+        )));
+    }
+
+    Ok(())
+}
+
 // --------------------------------------------------------------------------
 // COMPORTAMIENTOS DE DOMINIO ESTRUCTURAL
 // --------------------------------------------------------------------------
@@ -150,6 +708,257 @@ mod tests {
         assert!(validar_motivo(&"a".repeat(501)).is_err());
     }
 
+    #[test]
+    fn test_validar_vetar_empresa_input() {
+        let input = VetarEmpresaInput {
+            empresa_id: "empresa:acme".to_string(),
+            nivel_severidad: "alto".to_string(),
+            motivo: "Incumplimiento de normas de seguridad".to_string(),
+            bloquear_registros: true,
+        };
+        assert!(validar_vetar_empresa_input(&input).is_ok());
+
+        let sin_motivo = VetarEmpresaInput { motivo: "  ".to_string(), ..input.clone() };
+        assert!(validar_vetar_empresa_input(&sin_motivo).is_err());
+
+        let sin_empresa = VetarEmpresaInput { empresa_id: String::new(), ..input.clone() };
+        assert!(validar_vetar_empresa_input(&sin_empresa).is_err());
+
+        let nivel_invalido = VetarEmpresaInput { nivel_severidad: "CRITICO".to_string(), ..input };
+        assert!(validar_vetar_empresa_input(&nivel_invalido).is_err());
+    }
+
+    fn registro_intercambio() -> RegistroIntercambioListaNegra {
+        RegistroIntercambioListaNegra {
+            cedula: "1-1234-0567".to_string(),
+            nombre: "José".to_string(),
+            segundo_nombre: None,
+            apellido: "Rodríguez".to_string(),
+            segundo_apellido: Some("Peña".to_string()),
+            empresa_nombre: Some("Acme, S.A.".to_string()),
+            nivel_severidad: "ALTO".to_string(),
+            motivo_bloqueo: Some("Hurto de \"material\"".to_string()),
+            vigente_hasta: None,
+            sitio_origen: Some("Planta Norte".to_string()),
+        }
+    }
+
+    fn par_de_claves() -> (Vec<u8>, String) {
+        use ring::signature::KeyPair;
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+        let publica =
+            Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap().public_key().as_ref().to_vec();
+        (pkcs8.as_ref().to_vec(), hex::encode(publica))
+    }
+
+    #[test]
+    fn test_firma_intercambio() {
+        let (privada_norte, publica_norte) = par_de_claves();
+        let (privada_sur, publica_sur) = par_de_claves();
+        let confiables = vec![
+            SitioConfiable { nombre: "Planta Sur".to_string(), clave_publica: publica_sur },
+            SitioConfiable { nombre: "Planta Norte".to_string(), clave_publica: publica_norte },
+        ];
+
+        let contenido = b"cedula,nombre\n1-1234-0567,Jose\n";
+        let firma = firmar_intercambio(contenido, &privada_norte).unwrap();
+        assert!(firma.starts_with(PREFIJO_FIRMA_INTERCAMBIO));
+        let firmante =
+            verificar_firma_intercambio(contenido, &format!("{firma}\n"), &confiables).unwrap();
+        assert_eq!(firmante.nombre, "Planta Norte");
+        assert_eq!(
+            verificar_firma_intercambio(
+                contenido,
+                &firmar_intercambio(contenido, &privada_sur).unwrap(),
+                &confiables
+            )
+            .unwrap()
+            .nombre,
+            "Planta Sur"
+        );
+
+        assert!(verificar_firma_intercambio(contenido, &firma, &confiables[..1]).is_err());
+        assert!(verificar_firma_intercambio(b"cedula,nombre\n", &firma, &confiables).is_err());
+        assert!(verificar_firma_intercambio(contenido, "firma-cualquiera", &confiables).is_err());
+        assert!(firmar_intercambio(contenido, b"no-es-pkcs8").is_err());
+    }
+
+    #[test]
+    fn test_sitio_firmante_y_confiables() {
+        assert!(validar_sitio_firmante(Some(" planta norte "), "Planta Norte").is_ok());
+        assert!(validar_sitio_firmante(None, "Planta Norte").is_ok());
+        assert!(validar_sitio_firmante(Some("Planta Sur"), "Planta Norte").is_err());
+
+        let (_, publica) = par_de_claves();
+        let sitio = |nombre: &str, clave: &str| SitioConfiable {
+            nombre: nombre.to_string(),
+            clave_publica: clave.to_string(),
+        };
+        assert!(validar_sitios_confiables(&[sitio("Planta Norte", &publica)]).is_ok());
+        assert!(validar_sitios_confiables(&[sitio("Planta Norte", "abcd")]).is_err());
+        assert!(validar_sitios_confiables(&[sitio("", &publica)]).is_err());
+        assert!(validar_sitios_confiables(&[
+            sitio("Planta Norte", &publica),
+            sitio("planta norte", &publica)
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_registro_csv_ida_y_vuelta() {
+        let encabezados: Vec<String> =
+            COLUMNAS_INTERCAMBIO_CSV.iter().map(|c| c.to_uppercase()).collect();
+        let registro = registro_intercambio();
+
+        let leido = registro_desde_fila_csv(&encabezados, &registro_a_fila_csv(&registro)).unwrap();
+        assert_eq!(leido, registro);
+        assert!(validar_registro_intercambio(&leido).is_ok());
+
+        // Columnas reordenadas o faltantes
+        let encabezados = vec!["nombre".to_string(), "cedula".to_string()];
+        let fila = vec!["Ana".to_string(), "1-1111-1111".to_string()];
+        let error = registro_desde_fila_csv(&encabezados, &fila).unwrap_err();
+        assert!(error.contains("apellido"));
+
+        let sin_sitio = RegistroIntercambioListaNegra { sitio_origen: None, ..registro };
+        assert!(validar_registro_intercambio(&sin_sitio).is_err());
+    }
+
+    #[test]
+    fn test_clasificar_importacion() {
+        let entrante = registro_intercambio();
+        let local = |nombre: &'static str, nivel: &'static str, vigente: bool| BloqueoLocal {
+            nombre: NombrePersona {
+                nombre,
+                segundo_nombre: None,
+                apellido: "Rodriguez",
+                segundo_apellido: Some("Pena"),
+            },
+            nivel_severidad: nivel,
+            vigente,
+        };
+
+        assert_eq!(clasificar_importacion(&entrante, None), TipoCambioImportacion::Nuevo);
+        assert_eq!(
+            clasificar_importacion(&entrante, Some(&local("Jose", "ALTO", true))),
+            TipoCambioImportacion::SinCambios
+        );
+        assert_eq!(
+            clasificar_importacion(&entrante, Some(&local("Jose", "bajo", true))),
+            TipoCambioImportacion::SeveridadActualizada
+        );
+        assert_eq!(
+            clasificar_importacion(&entrante, Some(&local("Jose", "BAJO", false))),
+            TipoCambioImportacion::Nuevo
+        );
+        assert_eq!(
+            clasificar_importacion(&entrante, Some(&local("Marta", "ALTO", true))),
+            TipoCambioImportacion::Conflicto
+        );
+    }
+
+    fn utc(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        use chrono::TimeZone;
+        Utc.with_ymd_and_hms(y, m, d, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_calcular_vigencia() {
+        let desde = utc(2025, 8, 31);
+
+        // BAJO: expira a los 6 meses; la revisión no puede quedar después
+        let v = calcular_vigencia(desde, Some(6), 12, None);
+        assert_eq!(v.vigente_hasta, Some(utc(2026, 2, 28)));
+        assert_eq!(v.proxima_revision, utc(2026, 2, 28));
+
+        // ALTO: sin vencimiento, revisión anual
+        let v = calcular_vigencia(desde, None, 12, None);
+        assert_eq!(v.vigente_hasta, None);
+        assert_eq!(v.proxima_revision, utc(2026, 8, 31));
+
+        // Vencimiento explícito gana sobre la política
+        let v = calcular_vigencia(desde, Some(6), 3, Some(utc(2025, 10, 1)));
+        assert_eq!(v.vigente_hasta, Some(utc(2025, 10, 1)));
+        assert_eq!(v.proxima_revision, utc(2025, 10, 1));
+    }
+
+    #[test]
+    fn test_esta_vigente_y_parseo() {
+        let ahora = utc(2025, 3, 1);
+        assert!(esta_vigente(None, ahora));
+        assert!(esta_vigente(Some(utc(2025, 3, 2)), ahora));
+        assert!(!esta_vigente(Some(ahora), ahora));
+
+        assert!(parsear_vigente_hasta("2025-06-30", ahora).is_ok());
+        assert_eq!(
+            parsear_vigente_hasta("2025-04-01T00:00:00Z", ahora).unwrap(),
+            utc(2025, 4, 1) - chrono::Duration::hours(12)
+        );
+        assert!(parsear_vigente_hasta("2025-02-01", ahora).is_err());
+        assert!(parsear_vigente_hasta("30/06/2025", ahora).is_err());
+    }
+
+    #[test]
+    fn test_validar_politica_vigencia() {
+        assert!(validar_politica_vigencia("BAJO", Some(6), 6).is_ok());
+        assert!(validar_politica_vigencia("ALTO", None, 12).is_ok());
+        assert!(validar_politica_vigencia("ALTO", None, 0).is_err());
+        assert!(validar_politica_vigencia("BAJO", Some(0), 6).is_err());
+    }
+
+    fn persona<'a>(
+        nombre: &'a str,
+        segundo_nombre: Option<&'a str>,
+        apellido: &'a str,
+        segundo_apellido: Option<&'a str>,
+    ) -> NombrePersona<'a> {
+        NombrePersona { nombre, segundo_nombre, apellido, segundo_apellido }
+    }
+
+    #[test]
+    fn test_plegar_nombre_y_distancia() {
+        assert_eq!(plegar_nombre("José  Ñúñez"), vec!["jose", "nunez"]);
+        assert_eq!(plegar_nombre("De la Cruz"), vec!["cruz"]);
+        assert_eq!(distancia_edicion("rodriguez", "rodrigez"), 1);
+        assert_eq!(distancia_edicion("perez", "preez"), 1);
+        assert_eq!(distancia_edicion("", "ana"), 3);
+        assert_eq!(distancia_maxima(3), 0);
+        assert_eq!(distancia_maxima(5), 1);
+        assert_eq!(distancia_maxima(9), 2);
+    }
+
+    #[test]
+    fn test_similitud_nombres() {
+        let bloqueado = persona("José", Some("Luis"), "Rodríguez", Some("Peña"));
+
+        // Mismo nombre sin tildes y con un error de tipeo
+        let s =
+            similitud_nombres(&persona("JOSE", Some("LUIS"), "RODRIGEZ", Some("PENA")), &bloqueado);
+        assert!(s > 0.95, "{s}");
+
+        // Sin segundo nombre ni segundo apellido: sigue siendo coincidencia fuerte
+        let s = similitud_nombres(&persona("Jose", None, "Rodriguez", None), &bloqueado);
+        assert!(s > 0.9 && s < 1.0, "{s}");
+
+        // Apellido distinto: no coincide
+        let s = similitud_nombres(&persona("José", Some("Luis"), "Pérez", None), &bloqueado);
+        assert!(s < 0.85, "{s}");
+
+        // Segundo apellido distinto rebaja el puntaje
+        let completo =
+            similitud_nombres(&persona("José", None, "Rodríguez", Some("Peña")), &bloqueado);
+        let distinto =
+            similitud_nombres(&persona("José", None, "Rodríguez", Some("Mora")), &bloqueado);
+        assert!(distinto < completo);
+    }
+
+    #[test]
+    fn test_validar_umbral_coincidencia() {
+        assert!(validar_umbral_coincidencia(0.85).is_ok());
+        assert!(validar_umbral_coincidencia(0.3).is_err());
+        assert!(validar_umbral_coincidencia(1.2).is_err());
+    }
+
     #[test]
     fn test_validar_nivel_severidad() {
         assert!(validar_nivel_severidad("ALTO").is_ok());
@@ -157,4 +966,30 @@ mod tests {
         assert!(validar_nivel_severidad("Bajo").is_ok());
         assert!(validar_nivel_severidad("INVALIDO").is_err());
     }
+
+    #[test]
+    fn test_validar_resolucion() {
+        let pendiente = EstadoPropuestaListaNegra::Pendiente;
+        assert!(
+            validar_resolucion(pendiente, "user:guardia", "user:supervisor", "Verificado").is_ok()
+        );
+
+        // Quien propone no puede resolver su propia propuesta
+        assert!(matches!(
+            validar_resolucion(pendiente, "user:guardia", "user:guardia", "Ok"),
+            Err(ListaNegraError::Unauthorized(_))
+        ));
+
+        // Comentario obligatorio
+        assert!(validar_resolucion(pendiente, "user:guardia", "user:supervisor", "  ").is_err());
+
+        // Una propuesta ya resuelta no se vuelve a resolver
+        assert!(validar_resolucion(
+            EstadoPropuestaListaNegra::Aprobada,
+            "user:guardia",
+            "user:supervisor",
+            "Ok"
+        )
+        .is_err());
+    }
 }
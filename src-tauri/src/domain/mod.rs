@@ -1,5 +1,9 @@
+pub mod adjunto;
 pub mod cita;
+pub mod cita_serie;
 pub mod common;
+pub mod documento_contratista;
+pub mod importacion;
 pub mod ingreso_contratista;
 pub mod ingreso_proveedor;
 pub mod ingreso_visita;
@@ -16,5 +20,7 @@ pub mod empresa;
 pub mod export;
 pub mod gafete;
 pub mod proveedor;
+pub mod report_schedule;
+pub mod scanner;
 
 pub mod errors;
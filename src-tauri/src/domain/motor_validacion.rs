@@ -2,15 +2,31 @@
 ///
 /// Este módulo implementa la lógica central para la validación de cualquier
 /// tipo de acceso (Visitante, Contratista, Proveedor). Orquestra múltiples
-/// reglas de negocio: listas negras, vigencia de documentos y alertas de seguridad.
+/// reglas de negocio: listas negras (persona y empresa), vigencia de documentos y
+/// contratos de empresa y alertas de seguridad.
 use crate::models::validation::{
-    EstadoAutorizacion, MotorContexto, ValidationReason, ValidationResult, ValidationStatus,
+    EstadoAutorizacion, InfoEmpresaVetada, MotorContexto, NivelSeveridad, ValidationReason,
+    ValidationResult, ValidationStatus,
 };
 
 // --------------------------------------------------------------------------
 // LÓGICA PRINCIPAL DEL MOTOR
 // --------------------------------------------------------------------------
 
+/// Denegación por veto de empresa; `None` si la severidad es baja (solo advierte).
+///
+/// Se expone aparte para que el registro de ingreso la aplique sin armar todo el contexto.
+pub fn denegar_por_empresa_vetada(ev: &InfoEmpresaVetada) -> Option<ValidationResult> {
+    (ev.severidad != NivelSeveridad::Bajo).then(|| ValidationResult {
+        status: ValidationStatus::Denied,
+        reason: ValidationReason::CompanyBlacklisted,
+        message: format!(
+            "Empresa {} VETADA. Severidad: {:?}. Motivo: {}",
+            ev.empresa_nombre, ev.severidad, ev.motivo
+        ),
+    })
+}
+
 /// Ejecuta todas las reglas de negocio sobre un contexto y retorna una decisión de acceso.
 pub fn ejecutar_validacion_motor(ctx: &MotorContexto) -> ValidationResult {
     // 1. REGLA: LISTA NEGRA (Prioridad Crítica)
@@ -22,7 +38,12 @@ pub fn ejecutar_validacion_motor(ctx: &MotorContexto) -> ValidationResult {
         };
     }
 
-    // 2. REGLA: INGRESO DUPLICADO
+    // 2. REGLA: EMPRESA VETADA (severidad MEDIO/ALTO deniega; BAJO solo advierte, ver regla 8)
+    if let Some(denegado) = ctx.empresa_vetada.as_ref().and_then(denegar_por_empresa_vetada) {
+        return denegado;
+    }
+
+    // 3. REGLA: INGRESO DUPLICADO
     if let Some(ref activo) = ctx.ingreso_activo {
         let info_gafete = if activo.gafete_numero == 0 {
             "Sin Gafete Asignado".to_string()
@@ -40,8 +61,27 @@ pub fn ejecutar_validacion_motor(ctx: &MotorContexto) -> ValidationResult {
         };
     }
 
-    // 3. REGLA: ESTADO DE AUTORIZACIÓN (Vigencia de Documentos)
+    // 4. REGLA: CONTRATO DE LA EMPRESA
+    // Fuera del periodo de contrato de la empresa (o de su contratista principal) no hay
+    // acceso, aunque los documentos personales estén vigentes.
+    if let Some(ref motivo) = ctx.contrato_empresa {
+        return ValidationResult {
+            status: ValidationStatus::Denied,
+            reason: ValidationReason::CompanyContractExpired,
+            message: format!("Contrato fuera de vigencia: {motivo}."),
+        };
+    }
+
+    // 5. REGLA: ESTADO DE AUTORIZACIÓN (Vigencia de Documentos)
     match ctx.estado_autorizacion {
+        EstadoAutorizacion::Vencido if !ctx.documentos_pendientes.is_empty() => ValidationResult {
+            status: ValidationStatus::Denied,
+            reason: ValidationReason::ExpiredDocuments,
+            message: format!(
+                "Estado de autorización: VENCIDO. Documentos pendientes: {}.",
+                ctx.documentos_pendientes.join(", ")
+            ),
+        },
         EstadoAutorizacion::Vencido => ValidationResult {
             status: ValidationStatus::Denied,
             reason: ValidationReason::ExpiredDocuments,
@@ -58,7 +98,49 @@ pub fn ejecutar_validacion_motor(ctx: &MotorContexto) -> ValidationResult {
             message: "Estado de autorización: SUSPENDIDO. Contacte administración.".to_string(),
         },
         _ => {
-            // 4. REGLA: ALERTAS DE GAFETE (Hardware/Pérdida)
+            // 6. REGLA: POSIBLE COINCIDENCIA POR NOMBRE EN LISTA NEGRA
+            // La cédula no está bloqueada, pero el nombre se parece al de un bloqueo vigente:
+            // el guardia debe confirmar la identidad antes de permitir el ingreso.
+            if let Some(c) = ctx.posibles_coincidencias.first() {
+                return ValidationResult {
+                    status: ValidationStatus::Warning,
+                    reason: ValidationReason::PossibleBlacklistMatch,
+                    message: format!(
+                        "Posible coincidencia con LISTA NEGRA: {} (cédula {}, severidad {:?}, similitud {:.0}%). Confirme la identidad antes de permitir el ingreso.",
+                        c.nombre_completo,
+                        c.cedula,
+                        c.severidad,
+                        c.score * 100.0
+                    ),
+                };
+            }
+
+            // 7. REGLA: BLOQUEO PROPUESTO PENDIENTE DE APROBACIÓN
+            // Aún no es un bloqueo: solo advierte mientras un supervisor lo revisa.
+            if let Some(ref bp) = ctx.bloqueo_propuesto {
+                return ValidationResult {
+                    status: ValidationStatus::Warning,
+                    reason: ValidationReason::PendingBlacklistApproval,
+                    message: format!(
+                        "Bloqueo PROPUESTO pendiente de aprobación. Severidad: {:?}. Motivo: {}",
+                        bp.severidad, bp.motivo
+                    ),
+                };
+            }
+
+            // 8. REGLA: EMPRESA VETADA CON SEVERIDAD BAJA
+            if let Some(ref ev) = ctx.empresa_vetada {
+                return ValidationResult {
+                    status: ValidationStatus::Warning,
+                    reason: ValidationReason::CompanyBlacklisted,
+                    message: format!(
+                        "Empresa {} con veto de severidad baja: {}",
+                        ev.empresa_nombre, ev.motivo
+                    ),
+                };
+            }
+
+            // 9. REGLA: ALERTAS DE GAFETE (Hardware/Pérdida)
             // Se ejecuta solo si la autorización base es válida (Activo o PorDefinir)
             if let Some(ref alerta) = ctx.alerta_gafete {
                 return ValidationResult {
@@ -86,7 +168,8 @@ pub fn ejecutar_validacion_motor(ctx: &MotorContexto) -> ValidationResult {
 mod tests {
     use super::*;
     use crate::models::validation::{
-        InfoIngresoActivoInt, InfoListaNegra, NivelSeveridad, TipoAcceso,
+        InfoBloqueoPropuesto, InfoCoincidenciaListaNegra, InfoEmpresaVetada, InfoIngresoActivoInt,
+        InfoListaNegra, TipoAcceso,
     };
 
     fn create_base_context() -> MotorContexto {
@@ -95,8 +178,13 @@ mod tests {
             ident_nombre: "Test".to_string(),
             tipo_acceso: TipoAcceso::Visitante,
             lista_negra: None,
+            posibles_coincidencias: Vec::new(),
+            bloqueo_propuesto: None,
+            empresa_vetada: None,
             ingreso_activo: None,
+            contrato_empresa: None,
             estado_autorizacion: EstadoAutorizacion::Activo,
+            documentos_pendientes: Vec::new(),
             alerta_gafete: None,
         }
     }
@@ -140,6 +228,21 @@ mod tests {
         assert_eq!(res.reason, ValidationReason::ExpiredDocuments);
     }
 
+    #[test]
+    fn test_motor_deny_documentos_pendientes() {
+        let mut ctx = create_base_context();
+        ctx.estado_autorizacion = EstadoAutorizacion::Vencido;
+        ctx.documentos_pendientes = vec![
+            "Póliza de riesgos (no registrado)".to_string(),
+            "Certificado médico (vencido el 2026-01-31)".to_string(),
+        ];
+        let res = ejecutar_validacion_motor(&ctx);
+        assert_eq!(res.status, ValidationStatus::Denied);
+        assert_eq!(res.reason, ValidationReason::ExpiredDocuments);
+        assert!(res.message.contains("Póliza de riesgos"));
+        assert!(res.message.contains("vencido el 2026-01-31"));
+    }
+
     #[test]
     fn test_motor_warning_gafete() {
         let mut ctx = create_base_context();
@@ -149,6 +252,93 @@ mod tests {
         assert_eq!(res.reason, ValidationReason::GafeteAlert);
     }
 
+    #[test]
+    fn test_motor_warning_posible_coincidencia() {
+        let mut ctx = create_base_context();
+        ctx.posibles_coincidencias = vec![InfoCoincidenciaListaNegra {
+            cedula: "999".to_string(),
+            nombre_completo: "Test Bloqueado".to_string(),
+            severidad: NivelSeveridad::Alto,
+            score: 0.92,
+        }];
+        ctx.alerta_gafete = Some("Debe gafete #5".to_string());
+        let res = ejecutar_validacion_motor(&ctx);
+        assert_eq!(res.status, ValidationStatus::Warning);
+        assert_eq!(res.reason, ValidationReason::PossibleBlacklistMatch);
+
+        // Un bloqueo exacto por cédula sigue teniendo prioridad
+        ctx.lista_negra =
+            Some(InfoListaNegra { motivo: "Robo".to_string(), severidad: NivelSeveridad::Alto });
+        assert_eq!(ejecutar_validacion_motor(&ctx).reason, ValidationReason::Blacklisted);
+    }
+
+    #[test]
+    fn test_motor_warning_bloqueo_propuesto() {
+        let mut ctx = create_base_context();
+        ctx.bloqueo_propuesto = Some(InfoBloqueoPropuesto {
+            motivo: "Agresión al personal".to_string(),
+            severidad: NivelSeveridad::Alto,
+        });
+        ctx.alerta_gafete = Some("Debe gafete #5".to_string());
+        let res = ejecutar_validacion_motor(&ctx);
+        assert_eq!(res.status, ValidationStatus::Warning);
+        assert_eq!(res.reason, ValidationReason::PendingBlacklistApproval);
+
+        // Sin aprobar no deniega: un documento vencido sigue siendo el motivo de rechazo
+        ctx.estado_autorizacion = EstadoAutorizacion::Vencido;
+        assert_eq!(ejecutar_validacion_motor(&ctx).reason, ValidationReason::ExpiredDocuments);
+    }
+
+    #[test]
+    fn test_motor_empresa_vetada() {
+        let mut ctx = create_base_context();
+        ctx.tipo_acceso = TipoAcceso::Contratista;
+        ctx.empresa_vetada = Some(InfoEmpresaVetada {
+            empresa_nombre: "Acme".to_string(),
+            motivo: "Incumplimiento".to_string(),
+            severidad: NivelSeveridad::Alto,
+        });
+        ctx.ingreso_activo = Some(InfoIngresoActivoInt {
+            id: "id".to_string(),
+            fecha_ingreso: "2023-01-01".to_string(),
+            gafete_numero: 0,
+        });
+        let res = ejecutar_validacion_motor(&ctx);
+        assert_eq!(res.status, ValidationStatus::Denied);
+        assert_eq!(res.reason, ValidationReason::CompanyBlacklisted);
+
+        // Severidad baja: solo advertencia, y un documento vencido sigue denegando
+        ctx.ingreso_activo = None;
+        ctx.empresa_vetada.as_mut().unwrap().severidad = NivelSeveridad::Bajo;
+        let res = ejecutar_validacion_motor(&ctx);
+        assert_eq!(res.status, ValidationStatus::Warning);
+        assert_eq!(res.reason, ValidationReason::CompanyBlacklisted);
+
+        ctx.estado_autorizacion = EstadoAutorizacion::Vencido;
+        assert_eq!(ejecutar_validacion_motor(&ctx).reason, ValidationReason::ExpiredDocuments);
+    }
+
+    #[test]
+    fn test_motor_contrato_empresa_vencido() {
+        let mut ctx = create_base_context();
+        ctx.tipo_acceso = TipoAcceso::Contratista;
+        ctx.contrato_empresa =
+            Some("el contrato de Acme (contratista principal) venció el 2026-06-30".to_string());
+        ctx.alerta_gafete = Some("Debe gafete #5".to_string());
+        let res = ejecutar_validacion_motor(&ctx);
+        assert_eq!(res.status, ValidationStatus::Denied);
+        assert_eq!(res.reason, ValidationReason::CompanyContractExpired);
+        assert!(res.message.contains("venció el 2026-06-30"));
+
+        // Un ingreso abierto sigue reportándose primero
+        ctx.ingreso_activo = Some(InfoIngresoActivoInt {
+            id: "id".to_string(),
+            fecha_ingreso: "2026-07-01".to_string(),
+            gafete_numero: 5,
+        });
+        assert_eq!(ejecutar_validacion_motor(&ctx).reason, ValidationReason::AlreadyInside);
+    }
+
     #[test]
     fn test_estado_mapping() {
         assert_eq!(EstadoAutorizacion::from_str_lossy("vencido"), EstadoAutorizacion::Vencido);
@@ -0,0 +1,243 @@
+/// Capa de Dominio: Reglas para Reportes Programados.
+///
+/// Define el subconjunto de expresiones cron soportado, la validación de una
+/// programación y el cálculo del periodo de datos relativo a la ejecución.
+/// No tiene dependencias de infraestructura ni de base de datos.
+use crate::models::export::{ReportPeriod, ReportSchedule};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
+
+/// Cantidad máxima de ejecuciones que se conservan en el historial.
+pub const MAX_HISTORIAL_EJECUCIONES: usize = 500;
+
+// --------------------------------------------------------------------------
+// EXPRESIONES CRON
+// --------------------------------------------------------------------------
+
+/// Expresión cron de 5 campos: `minuto hora día-mes mes día-semana`.
+///
+/// Cada campo acepta `*`, valores (`5`), listas (`1,15`), rangos (`1-5`) y pasos
+/// (`*/15`, `8-18/2`). El día de la semana va de 0 (domingo) a 6; 7 también es domingo.
+/// Como en cron clásico, si día-mes y día-semana están restringidos basta con que
+/// coincida uno de los dos.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutos: u64,
+    horas: u32,
+    dias_mes: u32,
+    meses: u16,
+    dias_semana: u8,
+    dia_mes_libre: bool,
+    dia_semana_libre: bool,
+}
+
+impl CronSchedule {
+    /// Indica si la expresión coincide con el minuto dado (hora local).
+    pub fn matches(&self, momento: &NaiveDateTime) -> bool {
+        let dia_mes = bit_activo(u64::from(self.dias_mes), momento.day());
+        let dia_semana =
+            bit_activo(u64::from(self.dias_semana), momento.weekday().num_days_from_sunday());
+
+        let dia = match (self.dia_mes_libre, self.dia_semana_libre) {
+            (true, true) => true,
+            (false, true) => dia_mes,
+            (true, false) => dia_semana,
+            (false, false) => dia_mes || dia_semana,
+        };
+
+        dia && bit_activo(self.minutos, momento.minute())
+            && bit_activo(u64::from(self.horas), momento.hour())
+            && bit_activo(u64::from(self.meses), momento.month())
+    }
+}
+
+impl std::str::FromStr for CronSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let campos: Vec<&str> = s.split_whitespace().collect();
+        let [minuto, hora, dia_mes, mes, dia_semana] = campos.as_slice() else {
+            return Err(format!(
+                "La expresión cron debe tener 5 campos (min hora día mes día-semana): {s}"
+            ));
+        };
+
+        // 7 = domingo, se pliega sobre el 0
+        let semana = parsear_campo(dia_semana, 0, 7, "día de la semana")?;
+        let semana = (semana | (semana >> 7)) & 0x7F;
+
+        Ok(Self {
+            minutos: parsear_campo(minuto, 0, 59, "minuto")?,
+            horas: u32::try_from(parsear_campo(hora, 0, 23, "hora")?).unwrap_or(u32::MAX),
+            dias_mes: u32::try_from(parsear_campo(dia_mes, 1, 31, "día del mes")?)
+                .unwrap_or(u32::MAX),
+            meses: u16::try_from(parsear_campo(mes, 1, 12, "mes")?).unwrap_or(u16::MAX),
+            dias_semana: u8::try_from(semana).unwrap_or(u8::MAX),
+            dia_mes_libre: *dia_mes == "*",
+            dia_semana_libre: *dia_semana == "*",
+        })
+    }
+}
+
+const fn bit_activo(mascara: u64, valor: u32) -> bool {
+    valor < 64 && mascara & (1 << valor) != 0
+}
+
+/// Convierte un campo cron en una máscara de bits con los valores permitidos.
+fn parsear_campo(campo: &str, min: u32, max: u32, nombre: &str) -> Result<u64, String> {
+    let invalido = || format!("Valor inválido para {nombre} en cron: {campo}");
+    let numero = |v: &str| -> Result<u32, String> {
+        let n: u32 = v.parse().map_err(|_| invalido())?;
+        if (min..=max).contains(&n) {
+            Ok(n)
+        } else {
+            Err(format!("{nombre} fuera de rango ({min}-{max}): {n}"))
+        }
+    };
+
+    let mut mascara = 0u64;
+    for parte in campo.split(',') {
+        let (rango, paso) = match parte.split_once('/') {
+            Some((rango, paso)) => {
+                let paso: u32 = paso.parse().map_err(|_| invalido())?;
+                if paso == 0 {
+                    return Err(invalido());
+                }
+                (rango, paso)
+            }
+            None => (parte, 1),
+        };
+
+        let (desde, hasta) = match rango {
+            "*" => (min, max),
+            _ => match rango.split_once('-') {
+                Some((a, b)) => (numero(a)?, numero(b)?),
+                // `5/10` equivale a `5-max/10`
+                None if paso > 1 => (numero(rango)?, max),
+                None => {
+                    let n = numero(rango)?;
+                    (n, n)
+                }
+            },
+        };
+
+        if desde > hasta {
+            return Err(invalido());
+        }
+        for valor in (desde..=hasta).step_by(paso as usize) {
+            mascara |= 1 << valor;
+        }
+    }
+
+    Ok(mascara)
+}
+
+// --------------------------------------------------------------------------
+// VALIDACIÓN Y PERIODOS
+// --------------------------------------------------------------------------
+
+/// Valida una programación y devuelve su expresión cron ya parseada.
+pub fn validar_schedule(schedule: &ReportSchedule) -> Result<CronSchedule, String> {
+    if schedule.nombre.trim().is_empty() {
+        return Err("El nombre de la programación no puede estar vacío".to_string());
+    }
+    if schedule.output_dir.trim().is_empty() {
+        return Err("Debe indicar el directorio de destino".to_string());
+    }
+    schedule.cron.parse()
+}
+
+/// Rango de fechas (inclusivo) que cubre el periodo respecto al día de ejecución.
+pub fn rango_periodo(periodo: ReportPeriod, hoy: NaiveDate) -> (NaiveDate, NaiveDate) {
+    match periodo {
+        ReportPeriod::Hoy => (hoy, hoy),
+        ReportPeriod::Ayer => {
+            let ayer = hoy - Duration::days(1);
+            (ayer, ayer)
+        }
+        ReportPeriod::UltimaSemana => (hoy - Duration::days(6), hoy),
+        ReportPeriod::UltimoMes => (hoy - Duration::days(29), hoy),
+    }
+}
+
+/// Indica si una programación ya corrió en el minuto dado (ejecución duplicada).
+pub fn ya_ejecutado_en(last_run_at: Option<&str>, momento: &NaiveDateTime) -> bool {
+    last_run_at.and_then(|raw| chrono::DateTime::parse_from_rfc3339(raw).ok()).is_some_and(
+        |ultima| {
+            let ultima = ultima.with_timezone(&chrono::Local).naive_local();
+            ultima.date() == momento.date()
+                && ultima.hour() == momento.hour()
+                && ultima.minute() == momento.minute()
+        },
+    )
+}
+
+// --------------------------------------------------------------------------
+// PRUEBAS UNITARIAS
+// --------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn momento(fecha: &str, hora: u32, minuto: u32) -> NaiveDateTime {
+        NaiveDate::parse_from_str(fecha, "%Y-%m-%d").unwrap().and_hms_opt(hora, minuto, 0).unwrap()
+    }
+
+    #[test]
+    fn test_cron_diario() {
+        let cron: CronSchedule = "0 18 * * *".parse().unwrap();
+        assert!(cron.matches(&momento("2025-03-05", 18, 0)));
+        assert!(!cron.matches(&momento("2025-03-05", 18, 1)));
+        assert!(!cron.matches(&momento("2025-03-05", 6, 0)));
+    }
+
+    #[test]
+    fn test_cron_semanal_y_pasos() {
+        // Lunes a las 07:30 (2025-03-03 es lunes)
+        let cron: CronSchedule = "30 7 * * 1".parse().unwrap();
+        assert!(cron.matches(&momento("2025-03-03", 7, 30)));
+        assert!(!cron.matches(&momento("2025-03-04", 7, 30)));
+
+        let cron: CronSchedule = "*/15 8-10 * * 1-5".parse().unwrap();
+        assert!(cron.matches(&momento("2025-03-05", 9, 45)));
+        assert!(!cron.matches(&momento("2025-03-05", 9, 50)));
+        assert!(!cron.matches(&momento("2025-03-08", 9, 45)), "Sábado fuera de rango");
+
+        // Domingo como 7
+        let cron: CronSchedule = "0 0 * * 7".parse().unwrap();
+        assert!(cron.matches(&momento("2025-03-09", 0, 0)));
+    }
+
+    #[test]
+    fn test_cron_dia_mes_o_semana() {
+        // Día 1 de cada mes o cualquier viernes
+        let cron: CronSchedule = "0 6 1 * 5".parse().unwrap();
+        assert!(cron.matches(&momento("2025-04-01", 6, 0)));
+        assert!(cron.matches(&momento("2025-03-07", 6, 0)));
+        assert!(!cron.matches(&momento("2025-03-06", 6, 0)));
+    }
+
+    #[test]
+    fn test_cron_invalido() {
+        assert!("0 18 * *".parse::<CronSchedule>().is_err());
+        assert!("60 18 * * *".parse::<CronSchedule>().is_err());
+        assert!("0 18 * * 8".parse::<CronSchedule>().is_err());
+        assert!("*/0 * * * *".parse::<CronSchedule>().is_err());
+        assert!("0 20-8 * * *".parse::<CronSchedule>().is_err());
+        assert!("a * * * *".parse::<CronSchedule>().is_err());
+    }
+
+    #[test]
+    fn test_rango_periodo() {
+        let hoy = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        assert_eq!(rango_periodo(ReportPeriod::Hoy, hoy), (hoy, hoy));
+        assert_eq!(
+            rango_periodo(ReportPeriod::Ayer, hoy).0,
+            NaiveDate::from_ymd_opt(2025, 3, 9).unwrap()
+        );
+        assert_eq!(
+            rango_periodo(ReportPeriod::UltimaSemana, hoy).0,
+            NaiveDate::from_ymd_opt(2025, 3, 4).unwrap()
+        );
+    }
+}
@@ -0,0 +1,285 @@
+/// Capa de Dominio: Decodificación de Lecturas de Escáner.
+///
+/// Interpreta las cadenas crudas que entregan los escáneres (teclado o serial):
+/// cédulas digitadas, el código de barras de la cédula física costarricense,
+/// números de gafete y los QR propios del sistema (citas, gafetes y pases).
+/// No tiene dependencias de infraestructura ni de base de datos.
+use crate::domain::common::{
+    normalizar_gafete_a_int, normalizar_nombre_propio, MAX_DIGITOS_CEDULA, MIN_DIGITOS_CEDULA,
+};
+use crate::models::scanner::CodigoEscaneado;
+use chrono::NaiveDate;
+
+/// Prefijo de los QR generados por el sistema (`BRISAS:CITA:<id>`, `BRISAS:GAFETE:<tipo>:<n>`,
+/// `BRISAS:INV:<token>`).
+pub const PREFIJO_QR: &str = "BRISAS:";
+
+/// Tablas de ingreso cuyo ID viaja en el QR del pase impreso.
+const TABLAS_INGRESO: [&str; 3] = ["ingreso_contratista", "ingreso_proveedor", "ingreso_visita"];
+
+// Layout de ancho fijo del código de barras de la cédula física (ya descifrado por el lector):
+// cédula(9) apellido1(26) apellido2(26) nombre(30) género(1) nacimiento(8) vencimiento(8)
+const CEDULA_FISICA_CAMPOS: [usize; 7] = [9, 26, 26, 30, 1, 8, 8];
+const CEDULA_FISICA_MIN_LEN: usize = 9 + 26 + 26 + 30;
+
+// --------------------------------------------------------------------------
+// LIMPIEZA
+// --------------------------------------------------------------------------
+
+/// Quita caracteres de control (CR/LF, STX/ETX, tabuladores) y el prefijo/sufijo
+/// configurado en el escáner.
+pub fn limpiar_lectura(raw: &str, prefijo: &str, sufijo: &str) -> String {
+    let sin_control: String = raw.chars().filter(|c| !c.is_control()).collect();
+    let mut limpio = sin_control.trim();
+
+    if !prefijo.is_empty() {
+        limpio = limpio.strip_prefix(prefijo).unwrap_or(limpio);
+    }
+    if !sufijo.is_empty() {
+        limpio = limpio.strip_suffix(sufijo).unwrap_or(limpio);
+    }
+
+    limpio.trim().to_string()
+}
+
+// --------------------------------------------------------------------------
+// DECODIFICACIÓN
+// --------------------------------------------------------------------------
+
+/// Decodifica una lectura cruda del escáner.
+pub fn decodificar_lectura(
+    raw: &str,
+    prefijo: &str,
+    sufijo: &str,
+) -> Result<CodigoEscaneado, String> {
+    let lectura = limpiar_lectura(raw, prefijo, sufijo);
+    if lectura.is_empty() {
+        return Err("Lectura vacía".to_string());
+    }
+
+    if let Some(payload) = strip_prefix_ignore_case(&lectura, PREFIJO_QR) {
+        return decodificar_qr_propio(payload);
+    }
+
+    if let Some((tabla, clave)) = lectura.split_once(':') {
+        if TABLAS_INGRESO.contains(&tabla) && es_clave_valida(clave) {
+            return Ok(CodigoEscaneado::Ingreso { id: lectura.clone() });
+        }
+        if tabla == "cita" && es_clave_valida(clave) {
+            return Ok(CodigoEscaneado::Cita { id: lectura.clone() });
+        }
+    }
+
+    if lectura.chars().count() >= CEDULA_FISICA_MIN_LEN
+        && lectura.chars().take(9).all(|c| c.is_ascii_digit())
+    {
+        return decodificar_cedula_fisica(&lectura);
+    }
+
+    if lectura.chars().all(|c| c.is_ascii_digit() || c == '-') {
+        let digitos = lectura.chars().filter(char::is_ascii_digit).count();
+        if (MIN_DIGITOS_CEDULA..=MAX_DIGITOS_CEDULA).contains(&digitos) {
+            return Ok(cedula_simple(lectura));
+        }
+        if !lectura.contains('-') {
+            let numero = normalizar_gafete_a_int(&lectura)?;
+            return Ok(CodigoEscaneado::Gafete { numero, tipo: None });
+        }
+    }
+
+    Err(format!("Lectura no reconocida: {lectura}"))
+}
+
+fn decodificar_qr_propio(payload: &str) -> Result<CodigoEscaneado, String> {
+    let (tipo, resto) =
+        payload.split_once(':').ok_or_else(|| format!("QR inválido: {PREFIJO_QR}{payload}"))?;
+
+    match tipo.to_uppercase().as_str() {
+        // El token se conserva completo: la firma se verifica en `domain::cita`
+        "INV" if es_clave_valida(resto) => {
+            Ok(CodigoEscaneado::Invitacion { token: format!("{PREFIJO_QR}{payload}") })
+        }
+        "CITA" if es_clave_valida(resto) => {
+            let id = if resto.contains(':') { resto.to_string() } else { format!("cita:{resto}") };
+            Ok(CodigoEscaneado::Cita { id })
+        }
+        "GAFETE" => {
+            let (tipo_gafete, numero) = match resto.split_once(':') {
+                Some((t, n)) => (Some(t.to_lowercase()), n),
+                None => (None, resto),
+            };
+            let numero = normalizar_gafete_a_int(numero)?;
+            if numero == 0 {
+                return Err("El QR de gafete no puede ser S/G".to_string());
+            }
+            Ok(CodigoEscaneado::Gafete { numero, tipo: tipo_gafete })
+        }
+        _ => Err(format!("QR del sistema no soportado: {PREFIJO_QR}{payload}")),
+    }
+}
+
+fn decodificar_cedula_fisica(lectura: &str) -> Result<CodigoEscaneado, String> {
+    let chars: Vec<char> = lectura.chars().collect();
+    let mut campos = Vec::with_capacity(CEDULA_FISICA_CAMPOS.len());
+    let mut inicio = 0;
+    for ancho in CEDULA_FISICA_CAMPOS {
+        let fin = (inicio + ancho).min(chars.len());
+        campos.push(chars[inicio.min(fin)..fin].iter().collect::<String>().trim().to_string());
+        inicio += ancho;
+    }
+
+    let texto = |i: usize| -> Option<String> {
+        campos.get(i).filter(|c| !c.is_empty()).map(|c| normalizar_nombre_propio(c))
+    };
+    let fecha = |i: usize| -> Option<String> {
+        campos
+            .get(i)
+            .and_then(|c| NaiveDate::parse_from_str(c, "%Y%m%d").ok())
+            .map(|f| f.format("%Y-%m-%d").to_string())
+    };
+
+    let nombre = texto(3);
+    if nombre.is_none() {
+        return Err("Código de cédula incompleto: falta el nombre".to_string());
+    }
+
+    Ok(CodigoEscaneado::Cedula {
+        cedula: campos[0].clone(),
+        nombre,
+        apellido: texto(1),
+        segundo_apellido: texto(2),
+        fecha_nacimiento: fecha(5),
+        fecha_vencimiento: fecha(6),
+    })
+}
+
+const fn cedula_simple(cedula: String) -> CodigoEscaneado {
+    CodigoEscaneado::Cedula {
+        cedula,
+        nombre: None,
+        apellido: None,
+        segundo_apellido: None,
+        fecha_nacimiento: None,
+        fecha_vencimiento: None,
+    }
+}
+
+/// Formas equivalentes de una cédula para buscarla en la base de datos.
+///
+/// Las cédulas nacionales de 9 dígitos se registran con o sin guiones
+/// (`112340567` / `1-1234-0567`).
+pub fn variantes_cedula(cedula: &str) -> Vec<String> {
+    let limpio = cedula.trim().to_uppercase();
+    let digitos: String = limpio.chars().filter(char::is_ascii_digit).collect();
+
+    let mut variantes = vec![limpio.clone()];
+    if digitos.len() == 9 && limpio.chars().all(|c| c.is_ascii_digit() || c == '-') {
+        for variante in
+            [digitos.clone(), format!("{}-{}-{}", &digitos[0..1], &digitos[1..5], &digitos[5..9])]
+        {
+            if !variantes.contains(&variante) {
+                variantes.push(variante);
+            }
+        }
+    }
+    variantes
+}
+
+// --------------------------------------------------------------------------
+// UTILIDADES
+// --------------------------------------------------------------------------
+
+fn strip_prefix_ignore_case<'a>(texto: &'a str, prefijo: &str) -> Option<&'a str> {
+    texto
+        .get(..prefijo.len())
+        .filter(|inicio| inicio.eq_ignore_ascii_case(prefijo))
+        .map(|_| &texto[prefijo.len()..])
+}
+
+fn es_clave_valida(clave: &str) -> bool {
+    !clave.is_empty() && !clave.chars().any(char::is_whitespace)
+}
+
+// --------------------------------------------------------------------------
+// PRUEBAS UNITARIAS
+// --------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Lectura grabada de una cédula física (datos ficticios), con CR/LF del escáner.
+    fn lectura_cedula_fisica() -> String {
+        format!(
+            "{}{:<26}{:<26}{:<30}{}{}{}\r\n",
+            "112340567", "PÉREZ", "MORA", "ANA LUCÍA", "F", "19900315", "20300315"
+        )
+    }
+
+    #[test]
+    fn test_cedula_fisica() {
+        let codigo = decodificar_lectura(&lectura_cedula_fisica(), "", "").unwrap();
+        assert_eq!(
+            codigo,
+            CodigoEscaneado::Cedula {
+                cedula: "112340567".to_string(),
+                nombre: Some("Ana Lucía".to_string()),
+                apellido: Some("Pérez".to_string()),
+                segundo_apellido: Some("Mora".to_string()),
+                fecha_nacimiento: Some("1990-03-15".to_string()),
+                fecha_vencimiento: Some("2030-03-15".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_prefijo_y_sufijo_configurados() {
+        let codigo = decodificar_lectura("]C1 1-1234-0567 #", "]C1", "#").unwrap();
+        assert!(
+            matches!(codigo, CodigoEscaneado::Cedula { ref cedula, .. } if cedula == "1-1234-0567")
+        );
+    }
+
+    #[test]
+    fn test_gafete_numero_y_qr() {
+        assert_eq!(
+            decodificar_lectura("0042\r", "", "").unwrap(),
+            CodigoEscaneado::Gafete { numero: 42, tipo: None }
+        );
+        assert_eq!(
+            decodificar_lectura("BRISAS:GAFETE:VISITA:7", "", "").unwrap(),
+            CodigoEscaneado::Gafete { numero: 7, tipo: Some("visita".to_string()) }
+        );
+        assert!(decodificar_lectura("BRISAS:GAFETE:S/G", "", "").is_err());
+    }
+
+    #[test]
+    fn test_qr_cita_e_ingreso() {
+        assert_eq!(
+            decodificar_lectura("brisas:cita:abc123", "", "").unwrap(),
+            CodigoEscaneado::Cita { id: "cita:abc123".to_string() }
+        );
+        assert_eq!(
+            decodificar_lectura("BRISAS:INV:djF8Y2l0YQ.c2ln\r\n", "", "").unwrap(),
+            CodigoEscaneado::Invitacion { token: "BRISAS:INV:djF8Y2l0YQ.c2ln".to_string() }
+        );
+        assert_eq!(
+            decodificar_lectura("ingreso_visita:01HZX", "", "").unwrap(),
+            CodigoEscaneado::Ingreso { id: "ingreso_visita:01HZX".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_lecturas_invalidas() {
+        assert!(decodificar_lectura("\r\n", "", "").is_err());
+        assert!(decodificar_lectura("hola mundo", "", "").is_err());
+        assert!(decodificar_lectura("BRISAS:OTRO:1", "", "").is_err());
+    }
+
+    #[test]
+    fn test_variantes_cedula() {
+        assert_eq!(variantes_cedula("112340567"), vec!["112340567", "1-1234-0567"]);
+        assert_eq!(variantes_cedula("1-1234-0567"), vec!["1-1234-0567", "112340567"]);
+        assert_eq!(variantes_cedula("155812345678"), vec!["155812345678"]);
+    }
+}
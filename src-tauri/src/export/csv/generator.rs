@@ -79,7 +79,7 @@ fn build_csv_content(
 }
 
 /// Codifica una línea CSV con escape de caracteres especiales
-fn encode_csv_line(values: &[String], delimiter: char) -> String {
+pub fn encode_csv_line(values: &[String], delimiter: char) -> String {
     values
         .iter()
         .map(|value| escape_csv_field(value, delimiter))
@@ -1,8 +1,10 @@
 // ==========================================
 // src/export/csv/mod.rs
 // ==========================================
-// Re-export del generador CSV
+// Lectura y escritura de archivos CSV
 
 mod generator;
+mod parser;
 
-pub use generator::generate_csv;
+pub use generator::{encode_csv_line, generate_csv};
+pub use parser::parse_csv;
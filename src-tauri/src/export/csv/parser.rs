@@ -0,0 +1,107 @@
+// ==========================================
+// src/export/csv/parser.rs
+// ==========================================
+// Lector CSV (RFC 4180) sin dependencias externas
+
+use crate::export::errors::{ExportError, ExportResult};
+
+// ==========================================
+// FUNCIÓN PRINCIPAL
+// ==========================================
+
+/// Lee un CSV y devuelve sus filas como listas de campos.
+///
+/// Acepta comillas dobles con `""` escapado, saltos de línea dentro de campos entre
+/// comillas, fin de línea `\n` o `\r\n` y el BOM que agrega el generador para Excel.
+/// Las filas completamente vacías se omiten.
+pub fn parse_csv(contenido: &str, delimiter: char) -> ExportResult<Vec<Vec<String>>> {
+    let mut filas = Vec::new();
+    let mut fila: Vec<String> = Vec::new();
+    let mut campo = String::new();
+    let mut entre_comillas = false;
+    let mut linea = 1usize;
+
+    let mut chars = contenido.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(c) = chars.next() {
+        if entre_comillas {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    campo.push('"');
+                }
+                '"' => entre_comillas = false,
+                _ => {
+                    if c == '\n' {
+                        linea += 1;
+                    }
+                    campo.push(c);
+                }
+            }
+            continue;
+        }
+
+        match c {
+            '"' if campo.is_empty() => entre_comillas = true,
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                fila.push(std::mem::take(&mut campo));
+                terminar_fila(&mut filas, std::mem::take(&mut fila));
+                linea += 1;
+            }
+            _ if c == delimiter => fila.push(std::mem::take(&mut campo)),
+            _ => campo.push(c),
+        }
+    }
+
+    if entre_comillas {
+        return Err(ExportError::CsvParseError(format!("comillas sin cerrar (línea {linea})")));
+    }
+
+    fila.push(campo);
+    terminar_fila(&mut filas, fila);
+    Ok(filas)
+}
+
+fn terminar_fila(filas: &mut Vec<Vec<String>>, fila: Vec<String>) {
+    if fila.iter().any(|c| !c.is_empty()) {
+        filas.push(fila);
+    }
+}
+
+// ==========================================
+// TESTS
+// ==========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::csv::encode_csv_line;
+
+    #[test]
+    fn test_roundtrip_con_generador() {
+        let fila = vec![
+            "1-1234-0567".to_string(),
+            "Pérez, Juan".to_string(),
+            "dijo \"alto\"".to_string(),
+            "línea 1\nlínea 2".to_string(),
+            String::new(),
+        ];
+        let contenido = format!("\u{feff}{}\r\n{}\n", encode_csv_line(&fila, ','), "a,b");
+
+        let filas = parse_csv(&contenido, ',').unwrap();
+        assert_eq!(filas.len(), 2);
+        assert_eq!(filas[0], fila);
+        assert_eq!(filas[1], vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_filas_vacias_y_delimitador() {
+        let filas = parse_csv("a;b\n\n;\nc;\n", ';').unwrap();
+        assert_eq!(filas, vec![vec!["a", "b"], vec!["c", ""]]);
+    }
+
+    #[test]
+    fn test_comillas_sin_cerrar() {
+        assert!(parse_csv("a,\"b\nc", ',').is_err());
+    }
+}
@@ -57,12 +57,24 @@ pub enum ExportError {
     #[error("Error de formato Excel: {0}")]
     XlsxFormatError(String),
 
+    #[error("Archivo Excel inválido: {0}")]
+    XlsxReadError(String),
+
     // ==========================================
     // Errores de generación (CSV)
     // ==========================================
     #[error("Error escribiendo CSV: {0}")]
     CsvWriteError(String),
 
+    #[error("Archivo CSV inválido: {0}")]
+    CsvParseError(String),
+
+    // ==========================================
+    // Errores de calendario (iCalendar)
+    // ==========================================
+    #[error("Archivo iCalendar inválido: {0}")]
+    IcalParseError(String),
+
     // ==========================================
     // Errores de sistema
     // ==========================================
@@ -1,8 +1,10 @@
 // ==========================================
 // src/export/excel/mod.rs
 // ==========================================
-// Re-export del builder Excel
+// Lectura y escritura de archivos Excel
 
 mod builder;
+mod parser;
 
 pub use builder::generate_excel;
+pub use parser::parse_xlsx;
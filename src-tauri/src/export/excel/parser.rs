@@ -0,0 +1,90 @@
+// ==========================================
+// src/export/excel/parser.rs
+// ==========================================
+// Lector Excel (.xlsx) usando calamine
+
+use crate::export::errors::{ExportError, ExportResult};
+use calamine::{Data, Reader, Xlsx};
+use std::io::Cursor;
+
+// ==========================================
+// FUNCIÓN PRINCIPAL
+// ==========================================
+
+/// Lee la primera hoja de un `.xlsx` y devuelve sus filas como listas de campos.
+///
+/// Mismo contrato que `parse_csv`: todo se entrega como texto y las filas
+/// completamente vacías se omiten. Los números enteros pierden el `.0` y las
+/// fechas se entregan como YYYY-MM-DD.
+pub fn parse_xlsx(contenido: &[u8]) -> ExportResult<Vec<Vec<String>>> {
+    let mut libro: Xlsx<_> = Xlsx::new(Cursor::new(contenido))
+        .map_err(|e| ExportError::XlsxReadError(format!("archivo no legible: {e}")))?;
+
+    let hoja = libro
+        .worksheet_range_at(0)
+        .ok_or_else(|| ExportError::XlsxReadError("el libro no tiene hojas".to_string()))?
+        .map_err(|e| ExportError::XlsxReadError(e.to_string()))?;
+
+    Ok(hoja
+        .rows()
+        .map(|fila| fila.iter().map(celda_a_texto).collect::<Vec<_>>())
+        .filter(|fila| fila.iter().any(|c| !c.is_empty()))
+        .collect())
+}
+
+// ==========================================
+// HELPERS
+// ==========================================
+
+#[allow(clippy::cast_possible_truncation)]
+fn celda_a_texto(celda: &Data) -> String {
+    match celda {
+        Data::Empty | Data::Error(_) => String::new(),
+        Data::String(s) | Data::DateTimeIso(s) | Data::DurationIso(s) => s.trim().to_string(),
+        // Cédulas y teléfonos guardados como número
+        Data::Float(f) if f.fract() == 0.0 && f.abs() < 1e15 => format!("{}", *f as i64),
+        Data::Float(f) => f.to_string(),
+        Data::Int(i) => i.to_string(),
+        Data::Bool(b) => b.to_string(),
+        Data::DateTime(fecha) => fecha
+            .as_datetime()
+            .map_or_else(|| fecha.as_f64().to_string(), |d| d.format("%Y-%m-%d").to_string()),
+    }
+}
+
+// ==========================================
+// TESTS
+// ==========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_xlsxwriter::{ExcelDateTime, Format, Workbook};
+
+    #[test]
+    fn test_lee_primera_hoja() {
+        let mut libro = Workbook::new();
+        let hoja = libro.add_worksheet();
+        hoja.write(0, 0, "Cédula").unwrap();
+        hoja.write(0, 1, "Nombre").unwrap();
+        hoja.write(0, 2, "Vence").unwrap();
+        hoja.write(1, 0, 112_340_567).unwrap();
+        hoja.write(1, 1, " Ana ").unwrap();
+        let fecha = ExcelDateTime::parse_from_str("2026-12-31").unwrap();
+        hoja.write_with_format(1, 2, &fecha, &Format::new().set_num_format("yyyy-mm-dd")).unwrap();
+        // Fila 3 vacía, fila 4 con datos
+        hoja.write(3, 1, "Luis").unwrap();
+        let contenido = libro.save_to_buffer().unwrap();
+
+        let filas = parse_xlsx(&contenido).unwrap();
+        assert_eq!(filas.len(), 3);
+        assert_eq!(filas[0], vec!["Cédula", "Nombre", "Vence"]);
+        assert_eq!(filas[1], vec!["112340567", "Ana", "2026-12-31"]);
+        assert_eq!(filas[2], vec!["", "Luis", ""]);
+    }
+
+    #[test]
+    fn test_archivo_invalido() {
+        assert!(parse_xlsx(b"cedula,nombre").is_err());
+    }
+}
@@ -0,0 +1,167 @@
+// ==========================================
+// src/export/ical/generator.rs
+// ==========================================
+// Generador iCalendar sin dependencias externas
+
+use crate::models::cita::EventoIcal;
+use chrono::{DateTime, Utc};
+
+const PRODID: &str = "-//Brisas APP//Citas//ES";
+
+/// Longitud máxima de línea en octetos antes de plegar (RFC 5545 §3.1).
+const MAX_OCTETOS_LINEA: usize = 75;
+
+// ==========================================
+// FUNCIÓN PRINCIPAL
+// ==========================================
+
+/// Genera el contenido `.ics` (VCALENDAR con un VEVENT por evento).
+///
+/// Todas las fechas se escriben en UTC. `ahora` se usa como `DTSTAMP`.
+pub fn generate_ics(eventos: &[EventoIcal], nombre: &str, ahora: DateTime<Utc>) -> String {
+    let mut lineas = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{PRODID}"),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escapar(nombre)),
+    ];
+
+    for evento in eventos {
+        lineas.extend(lineas_evento(evento, ahora));
+    }
+    lineas.push("END:VCALENDAR".to_string());
+
+    let mut salida = String::new();
+    for linea in &lineas {
+        salida.push_str(&plegar(linea));
+        salida.push_str("\r\n");
+    }
+    salida
+}
+
+// ==========================================
+// CONSTRUCCIÓN DEL CONTENIDO
+// ==========================================
+
+fn lineas_evento(e: &EventoIcal, ahora: DateTime<Utc>) -> Vec<String> {
+    let mut lineas = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}", escapar(&e.uid)),
+        format!("DTSTAMP:{}", fecha_utc(ahora)),
+    ];
+
+    if let Some(inicio) = e.inicio {
+        lineas.push(format!("DTSTART:{}", fecha_utc(inicio)));
+    }
+    if let Some(fin) = e.fin {
+        lineas.push(format!("DTEND:{}", fecha_utc(fin)));
+    }
+
+    let texto = |nombre: &str, valor: &Option<String>| {
+        valor.as_deref().filter(|v| !v.is_empty()).map(|v| format!("{nombre}:{}", escapar(v)))
+    };
+    lineas.extend(
+        [
+            texto("SUMMARY", &e.resumen),
+            texto("DESCRIPTION", &e.descripcion),
+            texto("LOCATION", &e.ubicacion),
+            texto("X-BRISAS-ANFITRION", &e.anfitrion),
+            texto("X-BRISAS-AREA", &e.ubicacion),
+            texto("X-BRISAS-NOMBRE", &e.visitante_nombre),
+            texto("X-BRISAS-APELLIDO", &e.visitante_apellido),
+            texto("X-BRISAS-CEDULA", &e.visitante_cedula),
+        ]
+        .into_iter()
+        .flatten(),
+    );
+
+    lineas.push(format!("STATUS:{}", if e.cancelado { "CANCELLED" } else { "CONFIRMED" }));
+    lineas.push("END:VEVENT".to_string());
+    lineas
+}
+
+fn fecha_utc(dt: DateTime<Utc>) -> String {
+    dt.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escapa un valor TEXT (RFC 5545 §3.3.11).
+fn escapar(texto: &str) -> String {
+    let mut salida = String::with_capacity(texto.len());
+    for c in texto.chars() {
+        match c {
+            '\\' => salida.push_str("\\\\"),
+            ';' => salida.push_str("\\;"),
+            ',' => salida.push_str("\\,"),
+            '\n' => salida.push_str("\\n"),
+            '\r' => {}
+            _ => salida.push(c),
+        }
+    }
+    salida
+}
+
+/// Pliega una línea larga en varias de 75 octetos como máximo, sin partir caracteres UTF-8.
+fn plegar(linea: &str) -> String {
+    let mut salida = String::with_capacity(linea.len() + linea.len() / MAX_OCTETOS_LINEA * 3);
+    let mut octetos = 0;
+    for c in linea.chars() {
+        if octetos + c.len_utf8() > MAX_OCTETOS_LINEA {
+            // La continuación empieza con un espacio, que también cuenta
+            salida.push_str("\r\n ");
+            octetos = 1;
+        }
+        salida.push(c);
+        octetos += c.len_utf8();
+    }
+    salida
+}
+
+// ==========================================
+// TESTS
+// ==========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_escapar_y_plegar() {
+        assert_eq!(
+            escapar("Sala 1, piso 2; ala\\norte\nfin"),
+            "Sala 1\\, piso 2\\; ala\\\\norte\\nfin"
+        );
+
+        let larga = format!("DESCRIPTION:{}", "á".repeat(60));
+        let plegada = plegar(&larga);
+        for linea in plegada.split("\r\n") {
+            assert!(linea.len() <= MAX_OCTETOS_LINEA);
+        }
+        assert_eq!(plegada.replace("\r\n ", ""), larga);
+    }
+
+    #[test]
+    fn test_generate_ics_estructura() {
+        let ahora = Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap();
+        let evento = EventoIcal {
+            uid: "cita:abc@brisas".to_string(),
+            inicio: Some(Utc.with_ymd_and_hms(2025, 3, 3, 15, 0, 0).unwrap()),
+            fin: Some(Utc.with_ymd_and_hms(2025, 3, 3, 16, 0, 0).unwrap()),
+            resumen: Some("Auditoría".to_string()),
+            visitante_cedula: Some("1-2345-6789".to_string()),
+            cancelado: true,
+            ..EventoIcal::default()
+        };
+
+        let ics = generate_ics(&[evento], "Citas", ahora);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("DTSTART:20250303T150000Z\r\n"));
+        assert!(ics.contains("DTSTAMP:20250301T120000Z\r\n"));
+        assert!(ics.contains("X-BRISAS-CEDULA:1-2345-6789\r\n"));
+        assert!(ics.contains("STATUS:CANCELLED\r\n"));
+        assert!(!ics.contains("LOCATION"));
+    }
+}
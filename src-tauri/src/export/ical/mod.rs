@@ -0,0 +1,10 @@
+// ==========================================
+// src/export/ical/mod.rs
+// ==========================================
+// Lectura y escritura de calendarios iCalendar (RFC 5545)
+
+mod generator;
+mod parser;
+
+pub use generator::generate_ics;
+pub use parser::parse_ics;
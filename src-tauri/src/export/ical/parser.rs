@@ -0,0 +1,553 @@
+// ==========================================
+// src/export/ical/parser.rs
+// ==========================================
+// Lector iCalendar tolerante (solo VEVENT)
+
+use crate::export::errors::{ExportError, ExportResult};
+use crate::models::cita::EventoIcal;
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDateTime, TimeZone, Utc};
+use std::collections::HashMap;
+
+// ==========================================
+// FUNCIÓN PRINCIPAL
+// ==========================================
+
+/// Lee los VEVENT de un archivo `.ics`.
+///
+/// Devuelve cada evento junto con el primer problema encontrado al leerlo (si lo hubo),
+/// para que quien importa pueda reportarlo sin descartar el resto del archivo.
+///
+/// Las horas flotantes se interpretan en la zona local del equipo; las que traen `TZID` se
+/// convierten con el desfase de su `VTIMEZONE` (solo zonas sin horario de verano).
+pub fn parse_ics(contenido: &str) -> ExportResult<Vec<(EventoIcal, Option<String>)>> {
+    let lineas = desplegar(contenido.trim_start_matches('\u{feff}'));
+
+    if !lineas.iter().any(|l| l.eq_ignore_ascii_case("BEGIN:VCALENDAR")) {
+        return Err(ExportError::IcalParseError("falta BEGIN:VCALENDAR".to_string()));
+    }
+
+    let zonas = leer_zonas(&lineas);
+    let mut eventos = Vec::new();
+    let mut actual: Option<EventoEnLectura> = None;
+    // Componentes anidados dentro del VEVENT (ej. VALARM) se ignoran
+    let mut anidados = 0usize;
+
+    for linea in &lineas {
+        let Some(prop) = Propiedad::leer(linea) else {
+            continue;
+        };
+
+        match (prop.nombre.as_str(), prop.valor.to_ascii_uppercase().as_str()) {
+            ("BEGIN", "VEVENT") if actual.is_none() => actual = Some(EventoEnLectura::default()),
+            ("BEGIN", _) if actual.is_some() => anidados += 1,
+            ("END", "VEVENT") if anidados == 0 => {
+                if let Some(evento) = actual.take() {
+                    eventos.push(evento.terminar());
+                }
+            }
+            ("END", _) if actual.is_some() => anidados = anidados.saturating_sub(1),
+            _ => {
+                if let Some(ref mut evento) = actual {
+                    if anidados == 0 {
+                        evento.aplicar(&prop, &zonas);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(eventos)
+}
+
+// ==========================================
+// LECTURA DE LÍNEAS Y PROPIEDADES
+// ==========================================
+
+/// Une las líneas plegadas (las que empiezan con espacio o tabulación).
+fn desplegar(contenido: &str) -> Vec<String> {
+    let mut lineas: Vec<String> = Vec::new();
+    for linea in contenido.split('\n') {
+        let linea = linea.strip_suffix('\r').unwrap_or(linea);
+        if let Some(resto) = linea.strip_prefix([' ', '\t']) {
+            if let Some(anterior) = lineas.last_mut() {
+                anterior.push_str(resto);
+                continue;
+            }
+        }
+        if !linea.trim().is_empty() {
+            lineas.push(linea.to_string());
+        }
+    }
+    lineas
+}
+
+struct Propiedad {
+    nombre: String,
+    parametros: Vec<(String, String)>,
+    valor: String,
+}
+
+impl Propiedad {
+    /// `NOMBRE;PARAM=valor;PARAM="con:dos puntos":VALOR`
+    fn leer(linea: &str) -> Option<Self> {
+        let mut en_comillas = false;
+        let mut separador = None;
+        for (i, c) in linea.char_indices() {
+            match c {
+                '"' => en_comillas = !en_comillas,
+                ':' if !en_comillas => {
+                    separador = Some(i);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let separador = separador?;
+        let (cabecera, valor) = (&linea[..separador], &linea[separador + 1..]);
+
+        let mut partes = dividir_fuera_de_comillas(cabecera, ';').into_iter();
+        let nombre = partes.next()?.trim().to_ascii_uppercase();
+        let parametros = partes
+            .filter_map(|p| {
+                let (k, v) = p.split_once('=')?;
+                Some((k.trim().to_ascii_uppercase(), v.trim().trim_matches('"').to_string()))
+            })
+            .collect();
+
+        Some(Self { nombre, parametros, valor: valor.to_string() })
+    }
+
+    fn parametro(&self, nombre: &str) -> Option<&str> {
+        self.parametros.iter().find(|(k, _)| k == nombre).map(|(_, v)| v.as_str())
+    }
+
+    fn texto(&self) -> Option<String> {
+        let texto = desescapar(&self.valor);
+        let texto = texto.trim();
+        (!texto.is_empty()).then(|| texto.to_string())
+    }
+}
+
+fn dividir_fuera_de_comillas(texto: &str, separador: char) -> Vec<&str> {
+    let mut partes = Vec::new();
+    let mut en_comillas = false;
+    let mut inicio = 0;
+    for (i, c) in texto.char_indices() {
+        if c == '"' {
+            en_comillas = !en_comillas;
+        } else if c == separador && !en_comillas {
+            partes.push(&texto[inicio..i]);
+            inicio = i + c.len_utf8();
+        }
+    }
+    partes.push(&texto[inicio..]);
+    partes
+}
+
+/// Revierte el escape de valores TEXT (RFC 5545 §3.3.11).
+fn desescapar(texto: &str) -> String {
+    let mut salida = String::with_capacity(texto.len());
+    let mut chars = texto.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n' | 'N') => salida.push('\n'),
+                Some(otro) => salida.push(otro),
+                None => salida.push('\\'),
+            }
+        } else {
+            salida.push(c);
+        }
+    }
+    salida
+}
+
+// ==========================================
+// CONSTRUCCIÓN DEL EVENTO
+// ==========================================
+
+#[derive(Default)]
+struct EventoEnLectura {
+    evento: EventoIcal,
+    duracion: Option<Duration>,
+    area: Option<String>,
+    organizador: Option<String>,
+    asistente: Option<String>,
+    error: Option<String>,
+}
+
+impl EventoEnLectura {
+    fn aplicar(&mut self, prop: &Propiedad, zonas: &Zonas) {
+        let e = &mut self.evento;
+        let resultado = match prop.nombre.as_str() {
+            "UID" => {
+                e.uid = prop.valor.trim().to_string();
+                Ok(())
+            }
+            "DTSTART" => leer_fecha(prop, zonas).map(|f| e.inicio = Some(f)),
+            "DTEND" => leer_fecha(prop, zonas).map(|f| e.fin = Some(f)),
+            "DURATION" => leer_duracion(&prop.valor).map(|d| self.duracion = Some(d)),
+            "SUMMARY" => {
+                e.resumen = prop.texto();
+                Ok(())
+            }
+            "DESCRIPTION" => {
+                e.descripcion = prop.texto();
+                Ok(())
+            }
+            "LOCATION" => {
+                e.ubicacion = prop.texto();
+                Ok(())
+            }
+            "STATUS" => {
+                e.cancelado = prop.valor.trim().eq_ignore_ascii_case("CANCELLED");
+                Ok(())
+            }
+            "X-BRISAS-ANFITRION" => {
+                e.anfitrion = prop.texto();
+                Ok(())
+            }
+            "X-BRISAS-AREA" => {
+                self.area = prop.texto();
+                Ok(())
+            }
+            "X-BRISAS-NOMBRE" => {
+                e.visitante_nombre = prop.texto();
+                Ok(())
+            }
+            "X-BRISAS-APELLIDO" => {
+                e.visitante_apellido = prop.texto();
+                Ok(())
+            }
+            "X-BRISAS-CEDULA" => {
+                e.visitante_cedula = prop.texto();
+                Ok(())
+            }
+            "ORGANIZER" => {
+                self.organizador = prop.parametro("CN").map(str::to_string);
+                Ok(())
+            }
+            // Solo el primer asistente se toma como visitante
+            "ATTENDEE" if self.asistente.is_none() => {
+                self.asistente = prop.parametro("CN").map(str::to_string);
+                Ok(())
+            }
+            _ => Ok(()),
+        };
+
+        if let Err(mensaje) = resultado {
+            self.error.get_or_insert(mensaje);
+        }
+    }
+
+    fn terminar(self) -> (EventoIcal, Option<String>) {
+        let Self { mut evento, duracion, area, organizador, asistente, mut error } = self;
+
+        if evento.fin.is_none() {
+            if let Some((inicio, d)) = evento.inicio.zip(duracion) {
+                evento.fin = inicio.checked_add_signed(d);
+                if evento.fin.is_none() {
+                    error.get_or_insert_with(|| "DURATION fuera de rango".to_string());
+                }
+            }
+        }
+        if area.is_some() {
+            evento.ubicacion = area;
+        }
+        if evento.anfitrion.is_none() {
+            evento.anfitrion = organizador;
+        }
+        if evento.visitante_nombre.is_none() {
+            if let Some(cn) = asistente {
+                let mut partes = cn.trim().splitn(2, ' ');
+                evento.visitante_nombre = partes.next().map(str::to_string);
+                evento.visitante_apellido = evento
+                    .visitante_apellido
+                    .or_else(|| partes.next().map(|a| a.trim().to_string()));
+            }
+        }
+
+        if error.is_none() && evento.inicio.is_none() {
+            error = Some("El evento no tiene fecha de inicio (DTSTART)".to_string());
+        }
+        (evento, error)
+    }
+}
+
+fn leer_fecha(prop: &Propiedad, zonas: &Zonas) -> Result<DateTime<Utc>, String> {
+    let valor = prop.valor.trim();
+    if prop.parametro("VALUE").is_some_and(|v| v.eq_ignore_ascii_case("DATE")) || valor.len() == 8 {
+        return Err(format!("{}: evento de día completo sin hora ({valor})", prop.nombre));
+    }
+
+    if let Some(utc) = valor.strip_suffix(['Z', 'z']) {
+        return NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S")
+            .map(|f| f.and_utc())
+            .map_err(|_| format!("{}: fecha inválida ({valor})", prop.nombre));
+    }
+
+    let local = NaiveDateTime::parse_from_str(valor, "%Y%m%dT%H%M%S")
+        .map_err(|_| format!("{}: fecha inválida ({valor})", prop.nombre))?;
+
+    let Some(tzid) = prop.parametro("TZID").map(normalizar_tzid) else {
+        // Hora flotante: se asume la zona local del equipo
+        return Local
+            .from_local_datetime(&local)
+            .earliest()
+            .map(|f| f.with_timezone(&Utc))
+            .ok_or_else(|| format!("{}: la hora {valor} no existe en la zona local", prop.nombre));
+    };
+
+    if ALIAS_UTC.iter().any(|a| a.eq_ignore_ascii_case(tzid)) {
+        return Ok(local.and_utc());
+    }
+    match zonas.get(tzid) {
+        Some(Some(desfase)) => desfase
+            .from_local_datetime(&local)
+            .single()
+            .map(|f| f.with_timezone(&Utc))
+            .ok_or_else(|| format!("{}: fecha fuera de rango ({valor})", prop.nombre)),
+        Some(None) => Err(format!(
+            "{}: la zona horaria {tzid} tiene horario de verano y no se puede convertir",
+            prop.nombre
+        )),
+        None => Err(format!("{}: zona horaria {tzid} sin definición VTIMEZONE", prop.nombre)),
+    }
+}
+
+/// Duración RFC 5545 (`P1W`, `PT1H30M`, `P1DT2H`); no admite valores negativos.
+fn leer_duracion(valor: &str) -> Result<Duration, String> {
+    let invalida = || format!("DURATION inválida ({valor})");
+    let cuerpo = valor.trim().strip_prefix(['P', 'p']).ok_or_else(invalida)?;
+
+    let mut total = Duration::zero();
+    let mut numero = String::new();
+    let mut en_tiempo = false;
+    for c in cuerpo.chars() {
+        match c.to_ascii_uppercase() {
+            'T' => en_tiempo = true,
+            d if d.is_ascii_digit() => numero.push(d),
+            unidad => {
+                let n: i64 = numero.parse().map_err(|_| invalida())?;
+                numero.clear();
+                let parte = match (unidad, en_tiempo) {
+                    ('W', false) => Duration::try_weeks(n),
+                    ('D', false) => Duration::try_days(n),
+                    ('H', true) => Duration::try_hours(n),
+                    ('M', true) => Duration::try_minutes(n),
+                    ('S', true) => Duration::try_seconds(n),
+                    _ => return Err(invalida()),
+                };
+                total = parte
+                    .and_then(|p| total.checked_add(&p))
+                    .ok_or_else(|| format!("DURATION fuera de rango ({valor})"))?;
+            }
+        }
+    }
+
+    if !numero.is_empty() || total.is_zero() {
+        return Err(invalida());
+    }
+    Ok(total)
+}
+
+// ==========================================
+// ZONAS HORARIAS (VTIMEZONE)
+// ==========================================
+
+/// Desfase fijo de cada `TZID` definido en el archivo; `None` si la zona cambia de
+/// desfase durante el año (horario de verano), que no se puede resolver sin reglas.
+type Zonas = HashMap<String, Option<FixedOffset>>;
+
+/// Identificadores que equivalen a UTC aunque el archivo no los defina.
+const ALIAS_UTC: [&str; 5] = ["UTC", "Etc/UTC", "GMT", "Etc/GMT", "Z"];
+
+/// Algunos clientes anteponen `/` a los TZID globales (RFC 5545 §3.2.19).
+fn normalizar_tzid(tzid: &str) -> &str {
+    tzid.trim().trim_start_matches('/')
+}
+
+fn leer_zonas(lineas: &[String]) -> Zonas {
+    let mut zonas = Zonas::new();
+    // (TZID, desfases encontrados en STANDARD/DAYLIGHT, alguno ilegible)
+    let mut actual: Option<(String, Vec<FixedOffset>, bool)> = None;
+
+    for prop in lineas.iter().filter_map(|l| Propiedad::leer(l)) {
+        let valor = prop.valor.trim();
+        match prop.nombre.as_str() {
+            "BEGIN" if valor.eq_ignore_ascii_case("VTIMEZONE") => {
+                actual = Some((String::new(), Vec::new(), false));
+            }
+            "END" if valor.eq_ignore_ascii_case("VTIMEZONE") => {
+                if let Some((tzid, desfases, ilegible)) = actual.take() {
+                    let fijo = desfases
+                        .first()
+                        .copied()
+                        .filter(|d| !ilegible && desfases.iter().all(|o| o == d));
+                    zonas.insert(tzid, fijo);
+                }
+            }
+            "TZID" => {
+                if let Some((ref mut tzid, _, _)) = actual {
+                    *tzid = normalizar_tzid(valor).to_string();
+                }
+            }
+            "TZOFFSETTO" => {
+                if let Some((_, ref mut desfases, ref mut ilegible)) = actual {
+                    match leer_desfase(valor) {
+                        Some(d) => desfases.push(d),
+                        None => *ilegible = true,
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    zonas
+}
+
+/// Desfase UTC `+HHMM` o `-HHMMSS`.
+fn leer_desfase(valor: &str) -> Option<FixedOffset> {
+    let (signo, cifras) = match valor.split_at_checked(1)? {
+        ("+", resto) => (1, resto),
+        ("-", resto) => (-1, resto),
+        _ => return None,
+    };
+    if !(cifras.len() == 4 || cifras.len() == 6) || !cifras.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let campo = |i: usize| cifras.get(i..i + 2).map_or(Some(0), |c| c.parse::<i32>().ok());
+    let segundos = campo(0)? * 3600 + campo(2)? * 60 + campo(4)?;
+    FixedOffset::east_opt(signo * segundos)
+}
+
+// ==========================================
+// TESTS
+// ==========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::ical::generate_ics;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_roundtrip_con_generador() {
+        let original = EventoIcal {
+            uid: "cita:1@brisas".to_string(),
+            inicio: Some(Utc.with_ymd_and_hms(2025, 3, 3, 15, 0, 0).unwrap()),
+            fin: Some(Utc.with_ymd_and_hms(2025, 3, 3, 16, 30, 0).unwrap()),
+            resumen: Some("Revisión, planta; \"norte\"".to_string()),
+            descripcion: Some("Línea 1\nLínea 2 con acentos: áéíóú ".repeat(4).trim().to_string()),
+            ubicacion: Some("Sala de Juntas".to_string()),
+            anfitrion: Some("Ana Solís".to_string()),
+            visitante_nombre: Some("Juan".to_string()),
+            visitante_apellido: Some("Pérez Mora".to_string()),
+            visitante_cedula: Some("1-2345-6789".to_string()),
+            cancelado: false,
+        };
+
+        let ics = generate_ics(std::slice::from_ref(&original), "Citas", Utc::now());
+        let leidos = parse_ics(&ics).unwrap();
+
+        assert_eq!(leidos.len(), 1);
+        assert_eq!(leidos[0].1, None);
+        assert_eq!(leidos[0].0, original);
+    }
+
+    #[test]
+    fn test_respaldos_y_duracion() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:ext-1\r\n\
+            DTSTART:20250303T150000Z\r\n\
+            DURATION:PT1H30M\r\n\
+            SUMMARY:Mantenimiento\r\n\
+            LOCATION:Bodega\r\n\
+            ORGANIZER;CN=\"Solís, Ana\":mailto:ana@example.com\r\n\
+            ATTENDEE;CN=Juan Pérez Mora;ROLE=REQ-PARTICIPANT:mailto:j@example.com\r\n\
+            BEGIN:VALARM\r\n\
+            DESCRIPTION:No debe sobrescribir\r\n\
+            END:VALARM\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let (evento, error) = parse_ics(ics).unwrap().remove(0);
+        assert_eq!(error, None);
+        assert_eq!(evento.fin, Some(Utc.with_ymd_and_hms(2025, 3, 3, 16, 30, 0).unwrap()));
+        assert_eq!(evento.anfitrion.as_deref(), Some("Solís, Ana"));
+        assert_eq!(evento.visitante_nombre.as_deref(), Some("Juan"));
+        assert_eq!(evento.visitante_apellido.as_deref(), Some("Pérez Mora"));
+        assert_eq!(evento.ubicacion.as_deref(), Some("Bodega"));
+        assert_eq!(evento.descripcion, None);
+    }
+
+    #[test]
+    fn test_errores_por_evento() {
+        let ics = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nUID:a\nDTSTART;VALUE=DATE:20250303\nEND:VEVENT\n\
+            BEGIN:VEVENT\nUID:b\nSUMMARY:Sin fecha\nEND:VEVENT\nEND:VCALENDAR\n";
+
+        let leidos = parse_ics(ics).unwrap();
+        assert_eq!(leidos.len(), 2);
+        assert!(leidos[0].1.as_deref().unwrap().contains("día completo"));
+        assert!(leidos[1].1.as_deref().unwrap().contains("DTSTART"));
+
+        assert!(parse_ics("no es un calendario").is_err());
+    }
+
+    #[test]
+    fn test_leer_duracion() {
+        assert_eq!(leer_duracion("PT45M").unwrap(), Duration::minutes(45));
+        assert_eq!(leer_duracion("P1DT2H").unwrap(), Duration::hours(26));
+        assert_eq!(leer_duracion("P1W").unwrap(), Duration::weeks(1));
+        assert!(leer_duracion("PT").is_err());
+        assert!(leer_duracion("1H").is_err());
+        assert!(leer_duracion("P1H").is_err());
+        assert!(leer_duracion("P9999999999999W").is_err());
+        assert!(leer_duracion("P106751991167DT8H").is_err());
+    }
+
+    #[test]
+    fn test_duracion_fuera_de_rango_no_entra_en_panico() {
+        let ics = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nUID:a\nDTSTART:20250303T150000Z\n\
+            DURATION:P15250284W\nEND:VEVENT\nEND:VCALENDAR\n";
+
+        let (evento, error) = parse_ics(ics).unwrap().remove(0);
+        assert_eq!(evento.fin, None);
+        assert!(error.unwrap().contains("fuera de rango"));
+    }
+
+    #[test]
+    fn test_fechas_con_tzid() {
+        let ics = "BEGIN:VCALENDAR\n\
+            BEGIN:VTIMEZONE\nTZID:America/Costa_Rica\n\
+            BEGIN:STANDARD\nTZOFFSETFROM:-0600\nTZOFFSETTO:-0600\nEND:STANDARD\n\
+            END:VTIMEZONE\n\
+            BEGIN:VTIMEZONE\nTZID:America/New_York\n\
+            BEGIN:STANDARD\nTZOFFSETTO:-0500\nEND:STANDARD\n\
+            BEGIN:DAYLIGHT\nTZOFFSETTO:-0400\nEND:DAYLIGHT\n\
+            END:VTIMEZONE\n\
+            BEGIN:VEVENT\nUID:cr\nDTSTART;TZID=America/Costa_Rica:20250303T090000\n\
+            DTEND;TZID=\"America/Costa_Rica\":20250303T100000\nEND:VEVENT\n\
+            BEGIN:VEVENT\nUID:utc\nDTSTART;TZID=Etc/UTC:20250303T090000\nEND:VEVENT\n\
+            BEGIN:VEVENT\nUID:ny\nDTSTART;TZID=America/New_York:20250303T090000\nEND:VEVENT\n\
+            BEGIN:VEVENT\nUID:x\nDTSTART;TZID=Europe/Madrid:20250303T090000\nEND:VEVENT\n\
+            END:VCALENDAR\n";
+
+        let leidos = parse_ics(ics).unwrap();
+        assert_eq!(leidos[0].1, None);
+        assert_eq!(leidos[0].0.inicio, Some(Utc.with_ymd_and_hms(2025, 3, 3, 15, 0, 0).unwrap()));
+        assert_eq!(leidos[0].0.fin, Some(Utc.with_ymd_and_hms(2025, 3, 3, 16, 0, 0).unwrap()));
+        assert_eq!(leidos[1].0.inicio, Some(Utc.with_ymd_and_hms(2025, 3, 3, 9, 0, 0).unwrap()));
+        assert!(leidos[2].1.as_deref().unwrap().contains("horario de verano"));
+        assert!(leidos[3].1.as_deref().unwrap().contains("VTIMEZONE"));
+    }
+
+    #[test]
+    fn test_leer_desfase() {
+        assert_eq!(leer_desfase("-0600"), FixedOffset::west_opt(6 * 3600));
+        assert_eq!(leer_desfase("+053000"), FixedOffset::east_opt(5 * 3600 + 30 * 60));
+        assert_eq!(leer_desfase("0600"), None);
+        assert_eq!(leer_desfase("+6"), None);
+    }
+}
@@ -11,6 +11,9 @@ pub mod errors;
 // CSV siempre disponible (sin deps externas)
 pub mod csv;
 
+// iCalendar siempre disponible (sin deps externas)
+pub mod ical;
+
 // ==========================================
 // SUBMÓDULOS CONDICIONALES
 // ==========================================
@@ -28,7 +31,10 @@ pub mod excel;
 pub use errors::{ExportError, ExportResult};
 
 // CSV (siempre disponible)
-pub use csv::generate_csv;
+pub use csv::{generate_csv, parse_csv};
+
+// iCalendar (siempre disponible)
+pub use ical::{generate_ics, parse_ics};
 
 // PDF (condicional)
 #[cfg(feature = "export-pdf")]
@@ -36,7 +42,7 @@ pub use pdf::generate_pdf;
 
 // Excel (condicional)
 #[cfg(feature = "export-excel")]
-pub use excel::generate_excel;
+pub use excel::{generate_excel, parse_xlsx};
 
 // ==========================================
 // FUNCIÓN DE DISPONIBILIDAD
@@ -48,7 +48,17 @@ pub fn generate_pdf(
 // ==========================================
 
 fn compile_typst_to_pdf(markup: &str) -> ExportResult<Vec<u8>> {
-    let world = TypstWorld::new(markup)?;
+    compile_typst_to_pdf_with_assets(markup, HashMap::new())
+}
+
+/// Compila markup que referencia archivos virtuales (imágenes generadas en memoria).
+///
+/// Las claves de `assets` son rutas relativas a `main.typ` (p.ej. `"qr.svg"`).
+pub(super) fn compile_typst_to_pdf_with_assets(
+    markup: &str,
+    assets: HashMap<String, Vec<u8>>,
+) -> ExportResult<Vec<u8>> {
+    let world = TypstWorld::new(markup, assets)?;
 
     // ✅ FIX: compile() ahora retorna Warned<T>, usar .output
     let result = typst::compile(&world);
@@ -80,11 +90,12 @@ struct TypstWorld {
     fonts: Vec<Font>,
     source: Source,
     main_id: FileId,
-    packages_root: PathBuf, // Carpeta de packages locales
+    packages_root: PathBuf,         // Carpeta de packages locales
+    assets: HashMap<FileId, Bytes>, // Archivos virtuales (QR, fotos)
 }
 
 impl TypstWorld {
-    fn new(markup: &str) -> ExportResult<Self> {
+    fn new(markup: &str, assets: HashMap<String, Vec<u8>>) -> ExportResult<Self> {
         let library = LazyHash::new(Library::default());
 
         let fonts = Self::load_system_fonts();
@@ -96,7 +107,12 @@ impl TypstWorld {
         // Buscar carpeta de packages relativa al ejecutable
         let packages_root = Self::find_packages_root();
 
-        Ok(Self { library, book, fonts, source, main_id, packages_root })
+        let assets = assets
+            .into_iter()
+            .map(|(path, data)| (FileId::new(None, VirtualPath::new(&path)), Bytes::new(data)))
+            .collect();
+
+        Ok(Self { library, book, fonts, source, main_id, packages_root, assets })
     }
 
     /// Busca la carpeta de packages locales
@@ -282,7 +298,9 @@ impl World for TypstWorld {
     }
 
     fn file(&self, id: FileId) -> FileResult<Bytes> {
-        if let Some(path) = self.resolve_package_file(id) {
+        if let Some(bytes) = self.assets.get(&id) {
+            Ok(bytes.clone())
+        } else if let Some(path) = self.resolve_package_file(id) {
             // Cargar archivo binario de package local
             let data = std::fs::read(&path).map_err(|_| FileError::NotFound(path))?;
             Ok(Bytes::new(data))
@@ -0,0 +1,115 @@
+// ==========================================
+// src/export/pdf/invitacion.rs
+// ==========================================
+// Invitación de cita con QR firmado para presentar en garita
+
+use crate::export::errors::{ExportError, ExportResult};
+use crate::models::cita::InvitacionPdfData;
+use std::collections::HashMap;
+
+use super::engine::compile_typst_to_pdf_with_assets;
+use super::pase::generate_qr_svg;
+use super::templates::{escape_typst_string, validate_markup};
+
+// ==========================================
+// FUNCIÓN PRINCIPAL
+// ==========================================
+
+/// Genera el PDF de invitación (media carta) con el token firmado en QR
+pub fn generate_invitacion_pdf(data: &InvitacionPdfData) -> ExportResult<Vec<u8>> {
+    let mut assets = HashMap::new();
+    assets.insert("qr.svg".to_string(), generate_qr_svg(&data.token)?);
+
+    let markup = generate_invitacion_markup(data);
+    validate_markup(&markup)?;
+
+    let pdf_bytes = compile_typst_to_pdf_with_assets(&markup, assets)?;
+    if pdf_bytes.is_empty() {
+        return Err(ExportError::TypstCompilationError("PDF resultante está vacío".to_string()));
+    }
+
+    Ok(pdf_bytes)
+}
+
+// ==========================================
+// MARKUP
+// ==========================================
+
+fn generate_invitacion_markup(data: &InvitacionPdfData) -> String {
+    let mut markup = String::new();
+
+    markup.push_str(
+        "#set page(paper: \"a5\", margin: 12mm)\n\
+#set text(font: (\"Inter\", \"Segoe UI\", \"Arial\"), size: 10pt, fill: rgb(\"#111827\"))\n\n\
+#block(width: 100%, fill: rgb(\"#2563eb\"), inset: 8pt, radius: 3pt)[\n\
+  #align(center)[#text(fill: white, weight: \"bold\", size: 16pt)[INVITACIÓN DE VISITA]]\n\
+]\n\n",
+    );
+
+    markup.push_str(&format!(
+        "#v(4mm)\n#text(size: 14pt, weight: \"bold\")[{}] \\ #text(fill: rgb(\"#4b5563\"))[Cédula: {}]\n\n",
+        escape_typst_string(&data.visitante_nombre),
+        escape_typst_string(&data.cedula),
+    ));
+
+    let mut campos = vec![("Fecha de la cita", data.fecha_cita.as_str())];
+    if let Some(anfitrion) = data.anfitrion.as_deref().filter(|a| !a.trim().is_empty()) {
+        campos.push(("Anfitrión", anfitrion));
+    }
+    if let Some(area) = data.area.as_deref().filter(|a| !a.trim().is_empty()) {
+        campos.push(("Área", area));
+    }
+    campos.push(("Motivo", data.motivo.as_str()));
+    campos.push(("Válida desde", data.valido_desde.as_str()));
+    campos.push(("Válida hasta", data.valido_hasta.as_str()));
+
+    let filas: Vec<String> = campos
+        .iter()
+        .map(|(label, valor)| {
+            format!(
+                "  [#text(fill: rgb(\"#6b7280\"))[{}]], [#text(weight: \"bold\")[{}]],",
+                escape_typst_string(label),
+                escape_typst_string(valor)
+            )
+        })
+        .collect();
+
+    markup.push_str(&format!(
+        "#v(3mm)\n#grid(columns: (auto, 1fr), column-gutter: 4mm, row-gutter: 3mm,\n{}\n)\n\n",
+        filas.join("\n")
+    ));
+
+    markup.push_str(
+        "#v(1fr)\n#align(center)[#image(\"qr.svg\", width: 55mm)]\n\
+#align(center)[#text(size: 8pt, fill: rgb(\"#6b7280\"))[Presente este código en la garita junto con su cédula.]]\n",
+    );
+
+    markup
+}
+
+// ==========================================
+// TESTS
+// ==========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markup_invitacion() {
+        let data = InvitacionPdfData {
+            token: "BRISAS:INV:abc.def".to_string(),
+            visitante_nombre: "Ana Pérez".to_string(),
+            cedula: "1-1234-0567".to_string(),
+            fecha_cita: "05/03/2025 09:00".to_string(),
+            anfitrion: Some("Luis Mora".to_string()),
+            motivo: "Reunión <técnica>".to_string(),
+            ..InvitacionPdfData::default()
+        };
+        let markup = generate_invitacion_markup(&data);
+        assert!(markup.contains("Ana Pérez"));
+        assert!(markup.contains(r"Reunión \<técnica\>"));
+        assert!(!markup.contains("Área"));
+        validate_markup(&markup).unwrap();
+    }
+}
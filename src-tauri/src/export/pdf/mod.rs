@@ -4,6 +4,10 @@
 // Re-export del generador PDF
 
 mod engine;
+mod invitacion;
+mod pase;
 mod templates;
 
 pub use engine::generate_pdf;
+pub use invitacion::generate_invitacion_pdf;
+pub use pase::generate_pase_pdf;
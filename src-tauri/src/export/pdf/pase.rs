@@ -0,0 +1,272 @@
+// ==========================================
+// src/export/pdf/pase.rs
+// ==========================================
+// Pase imprimible para visitas, contratistas y proveedores
+// Incluye QR con el ID del ingreso y foto opcional
+
+use crate::export::errors::{ExportError, ExportResult};
+use crate::models::pase::{PaseData, PaseTemplate};
+use std::collections::HashMap;
+
+use super::engine::compile_typst_to_pdf_with_assets;
+use super::templates::{escape_typst_string, validate_markup};
+
+use qrcode::render::svg;
+use qrcode::{EcLevel, QrCode};
+
+// ==========================================
+// FUNCIÓN PRINCIPAL
+// ==========================================
+
+/// Genera el PDF del pase con la plantilla indicada
+pub fn generate_pase_pdf(data: &PaseData, template: PaseTemplate) -> ExportResult<Vec<u8>> {
+    let mut assets = HashMap::new();
+    assets.insert("qr.svg".to_string(), generate_qr_svg(&data.ingreso_id)?);
+
+    let foto = data.foto.as_ref().and_then(|bytes| {
+        let ext = detect_image_ext(bytes)?;
+        let path = format!("foto.{ext}");
+        assets.insert(path.clone(), bytes.clone());
+        Some(path)
+    });
+
+    let markup = generate_pase_markup(data, template, foto.as_deref());
+    validate_markup(&markup)?;
+
+    let pdf_bytes = compile_typst_to_pdf_with_assets(&markup, assets)?;
+    if pdf_bytes.is_empty() {
+        return Err(ExportError::TypstCompilationError("PDF resultante está vacío".to_string()));
+    }
+
+    Ok(pdf_bytes)
+}
+
+// ==========================================
+// QR Y FOTO
+// ==========================================
+
+pub(super) fn generate_qr_svg(contenido: &str) -> ExportResult<Vec<u8>> {
+    let code = QrCode::with_error_correction_level(contenido.as_bytes(), EcLevel::M)
+        .map_err(|e| ExportError::InvalidData(format!("No se pudo generar el QR: {e}")))?;
+
+    let svg = code
+        .render::<svg::Color>()
+        .quiet_zone(true)
+        .dark_color(svg::Color("#000000"))
+        .light_color(svg::Color("#ffffff"))
+        .build();
+
+    Ok(svg.into_bytes())
+}
+
+/// Detecta el formato de imagen por sus bytes mágicos
+fn detect_image_ext(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0x89, b'P', b'N', b'G']) {
+        Some("png")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("jpg")
+    } else if bytes.starts_with(b"GIF8") {
+        Some("gif")
+    } else if bytes.len() > 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("webp")
+    } else {
+        None
+    }
+}
+
+// ==========================================
+// MARKUP
+// ==========================================
+
+struct Layout {
+    width: &'static str,
+    height: &'static str,
+    color: &'static str,
+    qr_size: &'static str,
+    foto_size: &'static str,
+}
+
+const fn layout_para(template: PaseTemplate) -> Layout {
+    match template {
+        PaseTemplate::Visitante => Layout {
+            width: "105mm",
+            height: "148mm",
+            color: "#2563eb",
+            qr_size: "32mm",
+            foto_size: "28mm",
+        },
+        PaseTemplate::ContratistaTemporal => Layout {
+            width: "105mm",
+            height: "148mm",
+            color: "#d97706",
+            qr_size: "32mm",
+            foto_size: "28mm",
+        },
+        PaseTemplate::Proveedor => Layout {
+            width: "105mm",
+            height: "148mm",
+            color: "#059669",
+            qr_size: "32mm",
+            foto_size: "28mm",
+        },
+        PaseTemplate::Compacto => Layout {
+            width: "85.6mm",
+            height: "54mm",
+            color: "#334155",
+            qr_size: "20mm",
+            foto_size: "18mm",
+        },
+    }
+}
+
+fn generate_pase_markup(data: &PaseData, template: PaseTemplate, foto: Option<&str>) -> String {
+    let layout = layout_para(template);
+    let compacto = template == PaseTemplate::Compacto;
+    let mut markup = String::new();
+
+    markup.push_str(&format!(
+        "#set page(width: {}, height: {}, margin: {})\n\
+#set text(font: (\"Inter\", \"Segoe UI\", \"Arial\"), size: {}, fill: rgb(\"#111827\"))\n\n",
+        layout.width,
+        layout.height,
+        if compacto { "3mm" } else { "6mm" },
+        if compacto { "7pt" } else { "9pt" },
+    ));
+
+    // Banda superior con el tipo de pase
+    markup.push_str(&format!(
+        "#block(width: 100%, fill: rgb(\"{}\"), inset: {}, radius: 2pt)[\n\
+  #align(center)[#text(fill: white, weight: \"bold\", size: {})[{}]]\n\
+]\n\n",
+        layout.color,
+        if compacto { "3pt" } else { "6pt" },
+        if compacto { "9pt" } else { "16pt" },
+        escape_typst_string(&data.titulo.to_uppercase()),
+    ));
+
+    let foto_markup = foto.map_or_else(
+        || {
+            format!(
+                "#box(width: {0}, height: {0}, stroke: 0.5pt + rgb(\"#9ca3af\"))[#align(center + horizon)[#text(fill: rgb(\"#9ca3af\"))[Sin foto]]]",
+                layout.foto_size
+            )
+        },
+        |path| format!("#image(\"{path}\", width: {0}, height: {0}, fit: \"cover\")", layout.foto_size),
+    );
+
+    // Identidad: foto + nombre + cédula
+    markup.push_str(&format!(
+        "#grid(columns: (auto, 1fr), column-gutter: {}, align: horizon,\n\
+  [{}],\n\
+  [#text(weight: \"bold\", size: {})[{}] \\ #text(fill: rgb(\"#4b5563\"))[Cédula: {}]],\n\
+)\n\n",
+        if compacto { "2mm" } else { "4mm" },
+        foto_markup,
+        if compacto { "9pt" } else { "13pt" },
+        escape_typst_string(&data.nombre_completo),
+        escape_typst_string(&data.cedula),
+    ));
+
+    // Campos según plantilla
+    let mut campos: Vec<(&str, &str)> = Vec::new();
+    match template {
+        PaseTemplate::Visitante => {
+            push_campo(&mut campos, "Anfitrión", data.anfitrion.as_deref());
+            push_campo(&mut campos, "Área", data.area.as_deref());
+            push_campo(&mut campos, "Empresa", data.empresa.as_deref());
+        }
+        PaseTemplate::ContratistaTemporal | PaseTemplate::Proveedor => {
+            push_campo(&mut campos, "Empresa", data.empresa.as_deref());
+            push_campo(&mut campos, "Área", data.area.as_deref());
+            push_campo(&mut campos, "Anfitrión", data.anfitrion.as_deref());
+        }
+        PaseTemplate::Compacto => {
+            push_campo(&mut campos, "Empresa", data.empresa.as_deref());
+        }
+    }
+    push_campo(&mut campos, "Gafete", data.gafete.as_deref());
+    if !compacto {
+        push_campo(&mut campos, "Placa", data.placa.as_deref());
+        push_campo(&mut campos, "Ingreso", Some(&data.fecha_ingreso));
+    }
+    push_campo(&mut campos, "Válido hasta", Some(&data.valido_hasta));
+
+    let filas: Vec<String> = campos
+        .iter()
+        .map(|(label, valor)| {
+            format!(
+                "  [#text(fill: rgb(\"#6b7280\"))[{}]], [#text(weight: \"bold\")[{}]],",
+                escape_typst_string(label),
+                escape_typst_string(valor)
+            )
+        })
+        .collect();
+
+    markup.push_str(&format!(
+        "#v({})\n#grid(columns: (auto, 1fr), column-gutter: 3mm, row-gutter: {},\n{}\n)\n\n",
+        if compacto { "1mm" } else { "4mm" },
+        if compacto { "1mm" } else { "2.5mm" },
+        filas.join("\n"),
+    ));
+
+    // QR con el ID del ingreso
+    markup.push_str(&format!(
+        "#v(1fr)\n#align(center)[#image(\"qr.svg\", width: {})]\n#align(center)[#text(size: 6pt, fill: rgb(\"#6b7280\"))[{}]]\n",
+        layout.qr_size,
+        escape_typst_string(&data.ingreso_id),
+    ));
+
+    markup
+}
+
+fn push_campo<'a>(campos: &mut Vec<(&'a str, &'a str)>, label: &'a str, valor: Option<&'a str>) {
+    if let Some(valor) = valor.map(str::trim).filter(|v| !v.is_empty()) {
+        campos.push((label, valor));
+    }
+}
+
+// ==========================================
+// TESTS
+// ==========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datos() -> PaseData {
+        PaseData {
+            ingreso_id: "ingreso_visita:abc123".to_string(),
+            titulo: "Visitante".to_string(),
+            nombre_completo: "Ana [Pérez]".to_string(),
+            cedula: "1-2345-6789".to_string(),
+            anfitrion: Some("Luis #Mora".to_string()),
+            gafete: Some("V-12".to_string()),
+            fecha_ingreso: "05/03/2025 08:00".to_string(),
+            valido_hasta: "05/03/2025 23:59".to_string(),
+            ..PaseData::default()
+        }
+    }
+
+    #[test]
+    fn test_markup_escapa_y_omite_vacios() {
+        let markup = generate_pase_markup(&datos(), PaseTemplate::Visitante, None);
+        assert!(markup.contains(r"Ana \[Pérez\]"));
+        assert!(markup.contains(r"Luis \#Mora"));
+        assert!(!markup.contains("Placa"), "Campos vacíos no se imprimen");
+        assert!(markup.contains("Sin foto"));
+        validate_markup(&markup).unwrap();
+    }
+
+    #[test]
+    fn test_detect_image_ext() {
+        assert_eq!(detect_image_ext(&[0x89, b'P', b'N', b'G', 0, 0]), Some("png"));
+        assert_eq!(detect_image_ext(&[0xFF, 0xD8, 0xFF, 0xE0]), Some("jpg"));
+        assert_eq!(detect_image_ext(b"texto"), None);
+    }
+
+    #[test]
+    fn test_qr_svg() {
+        let svg = generate_qr_svg("ingreso_visita:abc123").unwrap();
+        assert!(String::from_utf8(svg).unwrap().contains("<svg"));
+    }
+}
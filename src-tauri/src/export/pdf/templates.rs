@@ -295,7 +295,7 @@ fn force_break_string(input: &str) -> String {
 // ==========================================
 
 /// Escapa caracteres especiales de Typst
-fn escape_typst_string(input: &str) -> String {
+pub(super) fn escape_typst_string(input: &str) -> String {
     input
         .replace('\\', r"\\")
         .replace('#', r"\#")
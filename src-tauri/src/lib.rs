@@ -15,6 +15,7 @@ pub mod tests;
 use crate::config::manager as config_manager;
 use crate::config::seed;
 use crate::config::settings::{AppConfig, AppConfigState};
+use crate::services::historial_search_service::HistorialSearchService;
 use crate::services::search_service::SearchService;
 use crate::services::session::SessionState;
 use crate::services::surrealdb_service::{setup_embedded_surrealdb, SurrealDbConfig};
@@ -117,6 +118,10 @@ pub fn run() {
                     if let Err(e) = seed::seed_db(config_state.clone()).await {
                         error!("❌ Error durante la verificación de datos iniciales: {e}");
                     }
+                    // Alertas de gafete anteriores al libro de deudas (idempotente)
+                    if let Err(e) = crate::services::gafete_service::migrar_alertas_a_deudas().await {
+                        error!("❌ Error migrando alertas de gafete al libro de deudas: {e}");
+                    }
                 } else {
                     info!("⚠️ El sistema está en modo de espera hasta que el asistente de configuración se complete.");
                 }
@@ -136,9 +141,23 @@ pub fn run() {
                     let search_service = Arc::new(s);
                     app.manage(search_service.clone());
 
-                    // Si el índice de búsqueda está vacío, programamos una reconstrucción en segundo plano.
-                    // Se hace en un hilo separado (spawn) para no retrasar la carga de la interfaz de usuario.
-                    if search_service.is_empty() {
+                    // Si el schema del índice cambió, se reconstruye en una generación nueva
+                    // mientras se sigue sirviendo la anterior; al terminar se intercambian.
+                    if search_service.needs_rebuild() {
+                        let search_service_clone = search_service;
+
+                        tauri::async_runtime::spawn(async move {
+                            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+                            info!("🔄 Schema del índice desactualizado. Reconstruyendo en segundo plano...");
+                            match search_service_clone.rebuild_in_new_generation().await {
+                                Ok(count) => info!("✅ Índice migrado al nuevo schema con {count} registros."),
+                                Err(e) => error!("❌ Fallo en la reconstrucción del índice: {e}"),
+                            }
+                        });
+                    } else if search_service.is_empty() {
+                        // Si el índice de búsqueda está vacío, programamos una reconstrucción en segundo plano.
+                        // Se hace en un hilo separado (spawn) para no retrasar la carga de la interfaz de usuario.
                         let search_service_clone = search_service;
 
                         tauri::async_runtime::spawn(async move {
@@ -163,6 +182,41 @@ pub fn run() {
                 }
             }
 
+            // Índice separado del historial de ingresos. Se sincroniza de forma incremental,
+            // así que en cada arranque solo procesa los movimientos nuevos.
+            let historial_path = app_data_dir.join("search_history_index");
+            match HistorialSearchService::new(&historial_path.to_string_lossy()) {
+                Ok(s) => {
+                    let historial_service = Arc::new(s);
+                    app.manage(historial_service.clone());
+
+                    tauri::async_runtime::spawn(async move {
+                        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+
+                        match historial_service.sync().await {
+                            Ok(count) => info!("✅ Historial de ingresos sincronizado ({count} movimientos)."),
+                            Err(e) => error!("❌ Fallo al sincronizar el historial de ingresos: {e}"),
+                        }
+                    });
+                }
+                Err(e) => {
+                    error!("❌ Fallo crítico al inicializar el índice de historial: {e}");
+                    return Err(Box::new(e));
+                }
+            }
+
+            // Planificador de reportes programados (revisa los horarios cada 30 segundos).
+            crate::services::report_schedule_service::start_scheduler();
+
+            // Vencimiento de citas sin presentarse y series recurrentes (cada 5 minutos).
+            crate::services::cita_lifecycle_service::start_lifecycle_job();
+
+            // Vencimiento y recordatorios de revisión de la lista negra (cada hora).
+            crate::services::lista_negra_vigencia_service::start_vigencia_job(app.handle().clone());
+
+            // Revisión diaria de PRAIND: suspensiones automáticas y digest por empresa.
+            crate::services::praind_vigencia_service::start_praind_job(app.handle().clone());
+
             // =========================================================================
             // SYSTEM TRAY SETUP
             // =========================================================================
@@ -100,6 +100,15 @@ pub struct Cita {
     pub visitante_cedula: Option<String>,
     pub anfitrion: Option<String>,
     pub area_visitada: Option<String>,
+    /// Ingreso de visita que abrió la cita (al pasar a `en_curso`).
+    #[serde(default)]
+    pub ingreso_visita: Option<RecordId>,
+    /// Serie recurrente que generó la cita, si aplica.
+    #[serde(default)]
+    pub serie: Option<RecordId>,
+    /// `UID` del evento iCalendar del que se importó, para no duplicarla al reimportar.
+    #[serde(default)]
+    pub uid_ical: Option<String>,
 
     pub created_at: Datetime,
     pub updated_at: Datetime,
@@ -126,6 +135,10 @@ pub struct CitaFetched {
     pub visitante_cedula: Option<String>,
     pub anfitrion: Option<String>,
     pub area_visitada: Option<String>,
+    #[serde(default)]
+    pub ingreso_visita: Option<RecordId>,
+    #[serde(default)]
+    pub serie: Option<RecordId>,
     pub created_at: Datetime,
     pub updated_at: Datetime,
 }
@@ -147,6 +160,8 @@ pub struct CreateCitaInput {
     pub visitante_cedula: Option<String>,
     pub visitante_nombre: Option<String>,
     pub visitante_apellido: Option<String>,
+    /// Duración prevista; por defecto `citas.duracion_default_minutos`.
+    pub duracion_minutos: Option<u32>,
 }
 
 /// Datos para actualizar una cita existente.
@@ -176,6 +191,8 @@ pub struct CitaCreateDTO {
     pub area_visitada: Option<String>,
     pub visitante_nombre: Option<String>,
     pub visitante_cedula: Option<String>,
+    pub serie: Option<RecordId>,
+    pub uid_ical: Option<String>,
     // Estado y activa se manejan por defecto en creación o en lógica de servicio
 }
 
@@ -218,10 +235,12 @@ pub struct CitaResponse {
     pub usuario_nombre: Option<String>,
     pub motivo: String,
     pub fecha_cita: String,
+    pub fecha_fin: String,
     pub anfitrion: Option<String>,
     pub area_visitada: Option<String>,
     pub estado: String, // Se serializa como string para el Frontend
     pub activa: bool,
+    pub serie_id: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -270,10 +289,12 @@ impl CitaResponse {
             usuario_nombre,
             motivo: c.motivo,
             fecha_cita: c.fecha_inicio.to_string(),
+            fecha_fin: c.fecha_fin.to_string(),
             anfitrion: c.anfitrion,
             area_visitada: c.area_visitada,
             estado: c.estado.to_string(), // Conversión Display -> String
             activa: c.activa,
+            serie_id: c.serie.map(|s| s.to_string()),
             created_at: c.created_at.to_string(),
             updated_at: c.updated_at.to_string(),
         }
@@ -293,16 +314,276 @@ impl From<Cita> for CitaResponse {
             usuario_nombre: None,
             motivo: c.motivo,
             fecha_cita: c.fecha_inicio.to_string(),
+            fecha_fin: c.fecha_fin.to_string(),
             anfitrion: c.anfitrion,
             area_visitada: c.area_visitada,
             estado: c.estado.to_string(),
             activa: c.activa,
+            serie_id: c.serie.map(|s| s.to_string()),
             created_at: c.created_at.to_string(),
             updated_at: c.updated_at.to_string(),
         }
     }
 }
 
+// --------------------------------------------------------------------------
+// SERIES RECURRENTES
+// --------------------------------------------------------------------------
+
+/// Frecuencia base de una serie de citas.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FrecuenciaRecurrencia {
+    Diaria,
+    Semanal,
+    Mensual,
+}
+
+impl FrecuenciaRecurrencia {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Diaria => "diaria",
+            Self::Semanal => "semanal",
+            Self::Mensual => "mensual",
+        }
+    }
+}
+
+const fn default_intervalo() -> u32 {
+    1
+}
+
+/// Regla de recurrencia de una serie (subconjunto práctico de RRULE).
+///
+/// La serie termina en `hasta` o tras `repeticiones` ocurrencias, lo que ocurra primero.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReglaRecurrencia {
+    pub frecuencia: FrecuenciaRecurrencia,
+    /// Cada cuántos días/semanas/meses se repite.
+    #[serde(default = "default_intervalo")]
+    pub intervalo: u32,
+    /// Días de la semana (1 = lunes … 7 = domingo); solo para `semanal`.
+    /// Vacío = el mismo día de la primera cita.
+    #[serde(default)]
+    pub dias_semana: Vec<u8>,
+    /// Última fecha (YYYY-MM-DD, inclusiva).
+    pub hasta: Option<String>,
+    /// Número máximo de ocurrencias (las excluidas también cuentan).
+    pub repeticiones: Option<u32>,
+    /// Fechas (YYYY-MM-DD) que se saltan.
+    #[serde(default)]
+    pub excluir: Vec<String>,
+}
+
+/// Modelo de Serie de Citas (`SurrealDB`).
+///
+/// Guarda la plantilla y la regla; las citas individuales se materializan en
+/// una ventana móvil y apuntan a la serie mediante `cita.serie`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CitaSerie {
+    pub id: RecordId,
+    pub visitante_id: Option<RecordId>,
+    pub usuario_id: RecordId,
+    pub motivo: String,
+    pub anfitrion: Option<String>,
+    pub area_visitada: Option<String>,
+    pub visitante_nombre: Option<String>,
+    pub visitante_cedula: Option<String>,
+    /// Primera ocurrencia: ancla la hora del día y el patrón.
+    pub fecha_inicio: Datetime,
+    /// Duración de cada cita (`None` = la configurada por defecto).
+    #[serde(default)]
+    pub duracion_minutos: Option<u32>,
+    pub frecuencia: FrecuenciaRecurrencia,
+    pub intervalo: u32,
+    #[serde(default)]
+    pub dias_semana: Vec<u8>,
+    pub hasta: Option<String>,
+    pub repeticiones: Option<u32>,
+    #[serde(default)]
+    pub excluir: Vec<String>,
+    pub activa: bool,
+    pub created_at: Datetime,
+    pub updated_at: Datetime,
+}
+
+impl CitaSerie {
+    pub fn regla(&self) -> ReglaRecurrencia {
+        ReglaRecurrencia {
+            frecuencia: self.frecuencia,
+            intervalo: self.intervalo,
+            dias_semana: self.dias_semana.clone(),
+            hasta: self.hasta.clone(),
+            repeticiones: self.repeticiones,
+            excluir: self.excluir.clone(),
+        }
+    }
+}
+
+/// Datos para agendar una serie: la primera cita más la regla de recurrencia.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCitaSerieInput {
+    #[serde(flatten)]
+    pub cita: CreateCitaInput,
+    pub regla: ReglaRecurrencia,
+}
+
+/// Cambios a nivel de serie; se aplican a las citas pendientes futuras.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCitaSerieInput {
+    pub anfitrion: Option<String>,
+    pub area_visitada: Option<String>,
+    pub motivo: Option<String>,
+    /// Nueva regla: regenera las citas pendientes futuras.
+    pub regla: Option<ReglaRecurrencia>,
+}
+
+/// Estructura interna para insertar una serie en DB.
+#[derive(Debug, Serialize)]
+pub struct CitaSerieCreateDTO {
+    pub visitante_id: Option<RecordId>,
+    pub usuario_id: RecordId,
+    pub motivo: String,
+    pub anfitrion: Option<String>,
+    pub area_visitada: Option<String>,
+    pub visitante_nombre: Option<String>,
+    pub visitante_cedula: Option<String>,
+    pub fecha_inicio: Datetime,
+    pub duracion_minutos: Option<u32>,
+    pub regla: ReglaRecurrencia,
+}
+
+/// Parche de los datos de plantilla de una serie.
+#[derive(Debug, Serialize, Default)]
+pub struct CitaSerieUpdateDTO {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub motivo: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anfitrion: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub area_visitada: Option<String>,
+}
+
+/// Serie con sus próximas citas materializadas.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CitaSerieResponse {
+    pub id: String,
+    pub visitante_id: Option<String>,
+    pub visitante_nombre: Option<String>,
+    pub visitante_cedula: Option<String>,
+    pub usuario_id: String,
+    pub motivo: String,
+    pub anfitrion: Option<String>,
+    pub area_visitada: Option<String>,
+    pub fecha_inicio: String,
+    pub regla: ReglaRecurrencia,
+    pub activa: bool,
+    pub duracion_minutos: Option<u32>,
+    /// Citas pendientes desde hoy, ordenadas por fecha.
+    pub proximas: Vec<CitaResponse>,
+    /// Ocurrencias omitidas en esta operación por falta de capacidad (RFC 3339).
+    pub omitidas_por_conflicto: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl CitaSerieResponse {
+    pub fn new(s: CitaSerie, proximas: Vec<CitaResponse>, omitidas: Vec<String>) -> Self {
+        let regla = s.regla();
+        Self {
+            id: s.id.to_string(),
+            visitante_id: s.visitante_id.map(|v| v.to_string()),
+            visitante_nombre: s.visitante_nombre,
+            visitante_cedula: s.visitante_cedula,
+            usuario_id: s.usuario_id.to_string(),
+            motivo: s.motivo,
+            anfitrion: s.anfitrion,
+            area_visitada: s.area_visitada,
+            fecha_inicio: s.fecha_inicio.to_string(),
+            regla,
+            duracion_minutos: s.duracion_minutos,
+            activa: s.activa,
+            proximas,
+            omitidas_por_conflicto: omitidas,
+            created_at: s.created_at.to_string(),
+            updated_at: s.updated_at.to_string(),
+        }
+    }
+}
+
+// --------------------------------------------------------------------------
+// DISPONIBILIDAD
+// --------------------------------------------------------------------------
+
+/// Horario sin conflictos de capacidad para agendar una cita.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HorarioLibre {
+    /// RFC 3339 en hora local.
+    pub inicio: String,
+    pub fin: String,
+}
+
+// --------------------------------------------------------------------------
+// CALENDARIO (iCalendar)
+// --------------------------------------------------------------------------
+
+/// Evento de calendario intercambiado en archivos `.ics`.
+///
+/// Los datos del visitante viajan en propiedades `X-BRISAS-*`; al importar también
+/// se aceptan `ATTENDEE;CN=`, `ORGANIZER;CN=` y `LOCATION` como respaldo.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventoIcal {
+    pub uid: String,
+    pub inicio: Option<chrono::DateTime<chrono::Utc>>,
+    pub fin: Option<chrono::DateTime<chrono::Utc>>,
+    /// `SUMMARY` (motivo de la cita).
+    pub resumen: Option<String>,
+    pub descripcion: Option<String>,
+    /// `LOCATION` (área visitada).
+    pub ubicacion: Option<String>,
+    pub anfitrion: Option<String>,
+    pub visitante_nombre: Option<String>,
+    pub visitante_apellido: Option<String>,
+    pub visitante_cedula: Option<String>,
+    /// `STATUS:CANCELLED`.
+    pub cancelado: bool,
+}
+
+/// Resultado de exportar citas a iCalendar.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IcalExportResponse {
+    pub total: usize,
+    /// Ruta escrita si se indicó `targetPath`.
+    pub file_path: Option<String>,
+    pub contenido: String,
+}
+
+/// Evento del `.ics` que no se pudo convertir en cita.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorImportacionIcal {
+    /// Posición del evento en el archivo (desde 1).
+    pub evento: usize,
+    pub uid: Option<String>,
+    pub resumen: Option<String>,
+    pub mensaje: String,
+}
+
+/// Resultado de importar un archivo `.ics`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IcalImportResponse {
+    pub total: usize,
+    pub creadas: Vec<CitaResponse>,
+    pub errores: Vec<ErrorImportacionIcal>,
+}
+
 // --------------------------------------------------------------------------
 // LIST RESPONSE
 // --------------------------------------------------------------------------
@@ -317,3 +598,65 @@ pub struct CitaListResponse {
     pub completadas: usize,
     pub canceladas: usize,
 }
+
+// --------------------------------------------------------------------------
+// INVITACIONES FIRMADAS
+// --------------------------------------------------------------------------
+
+/// Contenido firmado de una invitación (viaja dentro del QR).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvitacionCita {
+    pub cita_id: String,
+    pub cedula: String,
+    /// Inicio de la ventana de validez (segundos Unix).
+    pub valido_desde: i64,
+    /// Fin de la ventana de validez (segundos Unix).
+    pub valido_hasta: i64,
+}
+
+/// Datos ya formateados que se imprimen en la invitación PDF.
+#[derive(Debug, Clone, Default)]
+pub struct InvitacionPdfData {
+    pub token: String,
+    pub visitante_nombre: String,
+    pub cedula: String,
+    pub fecha_cita: String,
+    pub anfitrion: Option<String>,
+    pub area: Option<String>,
+    pub motivo: String,
+    pub valido_desde: String,
+    pub valido_hasta: String,
+}
+
+/// Invitación generada para una cita.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvitacionResponse {
+    pub cita_id: String,
+    /// Token firmado (contenido del QR).
+    pub token: String,
+    pub valido_desde: String,
+    pub valido_hasta: String,
+    /// PDF listo para enviar o imprimir (`None` si la build no incluye PDF).
+    pub pdf: Option<Vec<u8>>,
+}
+
+// --------------------------------------------------------------------------
+// REPORTE DE INASISTENCIAS
+// --------------------------------------------------------------------------
+
+/// Resumen de asistencia de las citas de un anfitrión en un período.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InasistenciasAnfitrion {
+    pub anfitrion: String,
+    pub total: u32,
+    /// Citas en curso o completadas (el visitante se presentó).
+    pub asistidas: u32,
+    /// Citas vencidas sin presentarse (no-show).
+    pub vencidas: u32,
+    pub canceladas: u32,
+    pub pendientes: u32,
+    /// `vencidas / (asistidas + vencidas) * 100`; 0 si no hay citas resueltas.
+    pub porcentaje_inasistencia: f64,
+}
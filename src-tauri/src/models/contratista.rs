@@ -32,8 +32,10 @@ use surrealdb::{Datetime, RecordId};
 ///
 /// ## Ciclo de Vida
 /// 1. Creado con estado [`EstadoContratista::Activo`] y PRAIND válido
-/// 2. Cambia a [`EstadoContratista::Inactivo`] si PRAIND vence
-/// 3. Puede ser [`EstadoContratista::Bloqueado`] por decisión administrativa
+/// 2. Pasa a [`EstadoContratista::Suspendido`] cuando vence el PRAIND (tarea diaria de
+///    `praind_vigencia_service`) y vuelve a `Activo` al renovarlo
+/// 3. Puede quedar [`EstadoContratista::Inactivo`] o [`EstadoContratista::Bloqueado`]
+///    por decisión administrativa
 ///
 /// ## Relaciones
 /// - Pertenece a una [`Empresa`] (campo `empresa`)
@@ -128,11 +130,13 @@ impl From<ContratistaFetched> for Contratista {
 
 /// Estados posibles de un contratista en el sistema.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")] // "activo", "inactivo", "bloqueado"
+#[serde(rename_all = "lowercase")] // "activo", "inactivo", "suspendido", "bloqueado"
 #[serde(try_from = "String")]
 pub enum EstadoContratista {
     Activo,
     Inactivo,
+    /// Suspensión automática por PRAIND vencido.
+    Suspendido,
     Bloqueado,
 }
 
@@ -141,6 +145,7 @@ impl EstadoContratista {
         match self {
             Self::Activo => "activo",
             Self::Inactivo => "inactivo",
+            Self::Suspendido => "suspendido",
             Self::Bloqueado => "bloqueado",
         }
     }
@@ -153,6 +158,7 @@ impl std::str::FromStr for EstadoContratista {
         match s.to_lowercase().as_str() {
             "activo" => Ok(Self::Activo),
             "inactivo" => Ok(Self::Inactivo),
+            "suspendido" => Ok(Self::Suspendido),
             "bloqueado" => Ok(Self::Bloqueado),
             _ => Err(format!("Estado desconocido: {s}")),
         }
@@ -251,6 +257,98 @@ pub struct EstadoPraind {
     pub requiere_atencion: bool,
 }
 
+// ==========================================
+// DIGEST DIARIO DE VENCIMIENTO PRAIND
+// ==========================================
+
+/// Acción que la revisión diaria tomó sobre un contratista.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AccionPraind {
+    /// PRAIND vigente pero dentro de la ventana de alerta.
+    PorVencer,
+    /// PRAIND vencido: el contratista fue suspendido.
+    Suspendido,
+    /// PRAIND vencido, pero no se pudo suspender (sigue activo).
+    SuspensionFallida,
+}
+
+impl AccionPraind {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::PorVencer => "POR_VENCER",
+            Self::Suspendido => "SUSPENDIDO",
+            Self::SuspensionFallida => "SUSPENSION_FALLIDA",
+        }
+    }
+
+    pub const fn display(self) -> &'static str {
+        match self {
+            Self::PorVencer => "Por vencer",
+            Self::Suspendido => "Suspendido",
+            Self::SuspensionFallida => "Suspensión fallida",
+        }
+    }
+}
+
+/// Contratista incluido en el digest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntradaDigestPraind {
+    pub contratista_id: String,
+    pub cedula: String,
+    pub nombre_completo: String,
+    /// Fecha de vencimiento (YYYY-MM-DD)
+    pub fecha_vencimiento: String,
+    /// Días restantes (negativo si ya venció)
+    pub dias_restantes: i64,
+    pub accion: AccionPraind,
+}
+
+/// Contratistas del digest agrupados por empresa.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestEmpresaPraind {
+    pub empresa_id: String,
+    pub empresa_nombre: String,
+    pub entradas: Vec<EntradaDigestPraind>,
+}
+
+/// Resultado de una revisión diaria de PRAIND (se guarda en `praind_digests.json`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestPraind {
+    pub id: String,
+    /// Timestamp ISO 8601 de la revisión
+    pub generado_en: String,
+    /// Día local de la revisión (YYYY-MM-DD); una revisión automática por día
+    pub fecha: String,
+    /// Ventana de alerta aplicada (`DIAS_ALERTA_PRAIND`)
+    pub dias_alerta: i64,
+    pub empresas: Vec<DigestEmpresaPraind>,
+    pub total_por_vencer: usize,
+    pub total_suspendidos: usize,
+    /// Vencidos que no se pudieron suspender (requieren atención manual)
+    #[serde(default)]
+    pub total_suspensiones_fallidas: usize,
+}
+
+/// Opciones para exportar un digest guardado (las filas salen del digest).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportarDigestPraindInput {
+    pub digest_id: String,
+    /// `pdf`, `excel` o `csv`
+    pub format: String,
+    pub title: Option<String>,
+    pub orientation: Option<String>,
+    pub show_preview: Option<bool>,
+    pub template_id: Option<String>,
+    pub delimiter: Option<String>,
+    pub include_bom: Option<bool>,
+    pub target_path: Option<String>,
+}
+
 // ==========================================
 // DTOs PARA PERSISTENCIA (Service -> DB)
 // ==========================================
@@ -460,6 +558,11 @@ mod tests {
                 nombre: "Empresa Test".to_string(),
                 direccion: Some("Calle Falsa 123".to_string()),
                 is_active: true,
+                cedula_juridica: None,
+                contactos: Vec::new(),
+                empresa_padre: None,
+                contrato_inicio: None,
+                contrato_fin: None,
                 created_at: Some(created.into()),
                 updated_at: Some(created.into()),
             },
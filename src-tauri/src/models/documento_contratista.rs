@@ -0,0 +1,352 @@
+//! # Models: Documentos de Contratista
+//!
+//! Credenciales adicionales al PRAIND que el contratista debe mantener vigentes según
+//! el trabajo que realiza (inducción de seguridad, póliza de riesgos, certificado
+//! médico, trabajo en alturas).
+//!
+//! ## Entidades Principales
+//! - [`DocumentoContratista`]: Documento registrado, con escaneo cifrado opcional
+//! - [`RequisitoDocumento`]: Regla que exige un tipo de documento (global, por empresa o por área)
+//!
+//! ## Convenciones de Fechas
+//! - `fecha_emision` / `fecha_vencimiento`: Fecha en formato YYYY-MM-DD en los inputs
+//! - La validación ocurre en [`crate::domain::documento_contratista`]
+
+use crate::domain::common::datetime_to_iso;
+use serde::{Deserialize, Serialize};
+use surrealdb::{Datetime, RecordId};
+
+// --------------------------------------------------------------------------
+// ENUMS DE DOMINIO
+// --------------------------------------------------------------------------
+
+/// Tipos de documento que se pueden registrar y exigir.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TipoDocumentoContratista {
+    InduccionSeguridad,
+    PolizaRiesgos,
+    CertificadoMedico,
+    TrabajoAlturas,
+    /// Documento libre (se describe en `descripcion`); no se puede exigir por regla.
+    Otro,
+}
+
+impl TipoDocumentoContratista {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::InduccionSeguridad => "INDUCCION_SEGURIDAD",
+            Self::PolizaRiesgos => "POLIZA_RIESGOS",
+            Self::CertificadoMedico => "CERTIFICADO_MEDICO",
+            Self::TrabajoAlturas => "TRABAJO_ALTURAS",
+            Self::Otro => "OTRO",
+        }
+    }
+
+    pub const fn display(self) -> &'static str {
+        match self {
+            Self::InduccionSeguridad => "Inducción de seguridad",
+            Self::PolizaRiesgos => "Póliza de riesgos",
+            Self::CertificadoMedico => "Certificado médico",
+            Self::TrabajoAlturas => "Certificación de trabajo en alturas",
+            Self::Otro => "Otro",
+        }
+    }
+}
+
+impl std::str::FromStr for TipoDocumentoContratista {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_uppercase().as_str() {
+            "INDUCCION_SEGURIDAD" => Ok(Self::InduccionSeguridad),
+            "POLIZA_RIESGOS" => Ok(Self::PolizaRiesgos),
+            "CERTIFICADO_MEDICO" => Ok(Self::CertificadoMedico),
+            "TRABAJO_ALTURAS" => Ok(Self::TrabajoAlturas),
+            "OTRO" => Ok(Self::Otro),
+            _ => Err(format!("Tipo de documento desconocido: {s}")),
+        }
+    }
+}
+
+/// Por qué un documento exigido no cumple.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MotivoDocumentoPendiente {
+    /// No hay ningún documento registrado de ese tipo.
+    Faltante,
+    /// Todos los documentos de ese tipo están vencidos.
+    Vencido,
+}
+
+// --------------------------------------------------------------------------
+// MODELOS DE BASE DE DATOS
+// --------------------------------------------------------------------------
+
+/// Documento registrado para un contratista.
+///
+/// El escaneo (si existe) se guarda cifrado en disco igual que las evidencias de lista
+/// negra; aquí solo viven los metadatos y el SHA-256 del original.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentoContratista {
+    pub id: RecordId,
+    pub contratista: RecordId,
+    pub tipo: TipoDocumentoContratista,
+    pub numero: Option<String>,
+    #[serde(alias = "fecha_emision")]
+    pub fecha_emision: Option<Datetime>,
+    /// `None` si el documento no vence.
+    #[serde(alias = "fecha_vencimiento")]
+    pub fecha_vencimiento: Option<Datetime>,
+    pub descripcion: Option<String>,
+    /// Nombre ofuscado del escaneo cifrado (`{uuid}.enc`).
+    #[serde(alias = "archivo_uuid")]
+    pub archivo_uuid: Option<String>,
+    #[serde(alias = "nombre_original")]
+    pub nombre_original: Option<String>,
+    #[serde(alias = "tipo_mime")]
+    pub tipo_mime: Option<String>,
+    #[serde(alias = "tamano_bytes")]
+    pub tamano_bytes: Option<i64>,
+    pub sha256: Option<String>,
+    #[serde(alias = "registrado_por")]
+    pub registrado_por: String,
+    #[serde(alias = "created_at")]
+    pub created_at: Datetime,
+    #[serde(alias = "updated_at")]
+    pub updated_at: Datetime,
+}
+
+/// Regla que exige un tipo de documento para ingresar.
+///
+/// - Sin empresa ni área: aplica a todos los contratistas.
+/// - Con empresa: solo a los contratistas de esa empresa.
+/// - Con área: solo cuando el ingreso es a esa área.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequisitoDocumento {
+    pub id: RecordId,
+    pub tipo: TipoDocumentoContratista,
+    pub empresa: Option<RecordId>,
+    pub area: Option<String>,
+    pub descripcion: Option<String>,
+    #[serde(alias = "creado_por")]
+    pub creado_por: String,
+    #[serde(alias = "created_at")]
+    pub created_at: Datetime,
+}
+
+// ==========================================
+// DTOs de entrada (Commands/Input)
+// ==========================================
+
+/// Datos para registrar un documento.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateDocumentoContratistaInput {
+    pub contratista_id: String,
+    pub tipo: TipoDocumentoContratista,
+    pub numero: Option<String>,
+    pub fecha_emision: Option<String>,
+    pub fecha_vencimiento: Option<String>,
+    pub descripcion: Option<String>,
+    /// Ruta absoluta del escaneo (seleccionado en el diálogo nativo).
+    pub file_path: Option<String>,
+}
+
+/// Actualización parcial de un documento; `file_path` reemplaza el escaneo.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateDocumentoContratistaInput {
+    pub numero: Option<String>,
+    pub fecha_emision: Option<String>,
+    pub fecha_vencimiento: Option<String>,
+    pub descripcion: Option<String>,
+    pub file_path: Option<String>,
+}
+
+/// Datos para crear una regla de documento requerido.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRequisitoDocumentoInput {
+    pub tipo: TipoDocumentoContratista,
+    pub empresa_id: Option<String>,
+    pub area: Option<String>,
+    pub descripcion: Option<String>,
+}
+
+// ==========================================
+// DTOs PARA PERSISTENCIA (Service -> DB)
+// ==========================================
+
+#[derive(Debug, Serialize)]
+pub struct DocumentoContratistaCreateDTO {
+    pub contratista: RecordId,
+    pub tipo: TipoDocumentoContratista,
+    pub numero: Option<String>,
+    pub fecha_emision: Option<Datetime>,
+    pub fecha_vencimiento: Option<Datetime>,
+    pub descripcion: Option<String>,
+    pub archivo_uuid: Option<String>,
+    pub nombre_original: Option<String>,
+    pub tipo_mime: Option<String>,
+    pub tamano_bytes: Option<i64>,
+    pub sha256: Option<String>,
+    pub registrado_por: String,
+}
+
+/// DTO tipado para actualizaciones parciales (PATCH) - los campos None se omiten.
+#[derive(Debug, Serialize, Default)]
+pub struct DocumentoContratistaUpdateDTO {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub numero: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fecha_emision: Option<Datetime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fecha_vencimiento: Option<Datetime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub descripcion: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archivo_uuid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nombre_original: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tipo_mime: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tamano_bytes: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RequisitoDocumentoCreateDTO {
+    pub tipo: TipoDocumentoContratista,
+    pub empresa: Option<RecordId>,
+    pub area: Option<String>,
+    pub descripcion: Option<String>,
+    pub creado_por: String,
+}
+
+// ==========================================
+// DTOs de salida (Response/ViewModel)
+// ==========================================
+
+/// Metadatos de un documento (sin el contenido del escaneo).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentoContratistaResponse {
+    pub id: String,
+    pub contratista_id: String,
+    pub tipo: TipoDocumentoContratista,
+    pub tipo_display: String,
+    pub numero: Option<String>,
+    pub fecha_emision: Option<String>,
+    pub fecha_vencimiento: Option<String>,
+    pub descripcion: Option<String>,
+    pub tiene_escaneo: bool,
+    pub nombre_original: Option<String>,
+    pub tipo_mime: Option<String>,
+    pub tamano_bytes: Option<i64>,
+    pub vencido: bool,
+    /// Días hasta el vencimiento (negativo si ya venció; `None` si no vence).
+    pub dias_hasta_vencimiento: Option<i64>,
+    pub registrado_por: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<DocumentoContratista> for DocumentoContratistaResponse {
+    fn from(doc: DocumentoContratista) -> Self {
+        let fecha = |dt: &Datetime| datetime_to_iso(dt).chars().take(10).collect::<String>();
+        let fecha_vencimiento = doc.fecha_vencimiento.as_ref().map(fecha);
+        let dias_hasta_vencimiento = fecha_vencimiento
+            .as_deref()
+            .and_then(|f| crate::domain::documento_contratista::dias_hasta_vencimiento(f).ok());
+
+        Self {
+            id: doc.id.to_string(),
+            contratista_id: doc.contratista.to_string(),
+            tipo: doc.tipo,
+            tipo_display: doc.tipo.display().to_string(),
+            numero: doc.numero,
+            fecha_emision: doc.fecha_emision.as_ref().map(fecha),
+            fecha_vencimiento,
+            descripcion: doc.descripcion,
+            tiene_escaneo: doc.archivo_uuid.is_some(),
+            nombre_original: doc.nombre_original,
+            tipo_mime: doc.tipo_mime,
+            tamano_bytes: doc.tamano_bytes,
+            vencido: dias_hasta_vencimiento.is_some_and(|d| d < 0),
+            dias_hasta_vencimiento,
+            registrado_por: doc.registrado_por,
+            created_at: datetime_to_iso(&doc.created_at),
+            updated_at: datetime_to_iso(&doc.updated_at),
+        }
+    }
+}
+
+/// Escaneo descifrado y verificado, listo para mostrarse.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EscaneoDocumentoResponse {
+    #[serde(flatten)]
+    pub documento: DocumentoContratistaResponse,
+    /// Contenido original en Base64.
+    pub contenido_base64: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequisitoDocumentoResponse {
+    pub id: String,
+    pub tipo: TipoDocumentoContratista,
+    pub tipo_display: String,
+    pub empresa_id: Option<String>,
+    pub area: Option<String>,
+    pub descripcion: Option<String>,
+    pub creado_por: String,
+    pub created_at: String,
+}
+
+impl From<RequisitoDocumento> for RequisitoDocumentoResponse {
+    fn from(req: RequisitoDocumento) -> Self {
+        Self {
+            id: req.id.to_string(),
+            tipo: req.tipo,
+            tipo_display: req.tipo.display().to_string(),
+            empresa_id: req.empresa.map(|e| e.to_string()),
+            area: req.area,
+            descripcion: req.descripcion,
+            creado_por: req.creado_por,
+            created_at: datetime_to_iso(&req.created_at),
+        }
+    }
+}
+
+/// Documento exigido que el contratista no cumple.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentoPendiente {
+    pub tipo: TipoDocumentoContratista,
+    pub motivo: MotivoDocumentoPendiente,
+    /// Último vencimiento registrado (solo si está vencido).
+    pub fecha_vencimiento: Option<String>,
+}
+
+impl DocumentoPendiente {
+    /// Descripción corta para mensajes del motor y alertas.
+    pub fn describir(&self) -> String {
+        match (self.motivo, &self.fecha_vencimiento) {
+            (MotivoDocumentoPendiente::Vencido, Some(fecha)) => {
+                format!("{} (vencido el {fecha})", self.tipo.display())
+            }
+            (MotivoDocumentoPendiente::Vencido, None) => {
+                format!("{} (vencido)", self.tipo.display())
+            }
+            (MotivoDocumentoPendiente::Faltante, _) => {
+                format!("{} (no registrado)", self.tipo.display())
+            }
+        }
+    }
+}
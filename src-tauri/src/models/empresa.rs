@@ -11,6 +11,9 @@ use surrealdb::RecordId;
 
 /// Entidad empresarial (Proveedores, Contratistas, Clientes).
 /// Actúa como agrupación para personal externo.
+///
+/// Una subcontratista apunta a su contratista principal en `empresa_padre`; el periodo
+/// de contrato de la principal también limita el acceso del personal de la subcontratista.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Empresa {
@@ -19,31 +22,72 @@ pub struct Empresa {
     pub direccion: Option<String>,
     #[serde(alias = "is_active")]
     pub is_active: bool,
+    #[serde(alias = "cedula_juridica", default)]
+    pub cedula_juridica: Option<String>,
+    #[serde(default)]
+    pub contactos: Vec<ContactoEmpresa>,
+    #[serde(alias = "empresa_padre", default)]
+    pub empresa_padre: Option<RecordId>,
+    /// Inicio del contrato vigente (sin fecha: sin límite inicial).
+    #[serde(alias = "contrato_inicio", default)]
+    pub contrato_inicio: Option<surrealdb::Datetime>,
+    /// Fin del contrato vigente (sin fecha: sin límite final).
+    #[serde(alias = "contrato_fin", default)]
+    pub contrato_fin: Option<surrealdb::Datetime>,
     #[serde(alias = "created_at", default)]
     pub created_at: Option<surrealdb::Datetime>,
     #[serde(alias = "updated_at", default)]
     pub updated_at: Option<surrealdb::Datetime>,
 }
 
+/// Persona de contacto de la empresa (encargado del contrato, salud ocupacional...).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactoEmpresa {
+    pub nombre: String,
+    pub cargo: Option<String>,
+    pub telefono: Option<String>,
+    pub email: Option<String>,
+    /// Contacto al que se dirigen los avisos (solo uno por empresa).
+    #[serde(default)]
+    pub principal: bool,
+}
+
 // --------------------------------------------------------------------------
 // DTOs DE ENTRADA
 // --------------------------------------------------------------------------
 
 /// Datos requeridos para registrar una empresa.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateEmpresaInput {
     pub nombre: String,
     pub direccion: Option<String>,
+    pub cedula_juridica: Option<String>,
+    #[serde(default)]
+    pub contactos: Vec<ContactoEmpresa>,
+    /// Contratista principal, si la empresa es subcontratista.
+    pub empresa_padre_id: Option<String>,
+    /// Periodo de contrato (YYYY-MM-DD).
+    pub contrato_inicio: Option<String>,
+    pub contrato_fin: Option<String>,
 }
 
 /// Datos para actualización parcial de empresas.
-#[derive(Debug, Deserialize)]
+///
+/// En `empresa_padre_id`, `contrato_inicio` y `contrato_fin` una cadena vacía quita el valor.
+#[derive(Debug, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateEmpresaInput {
     pub nombre: Option<String>,
     pub direccion: Option<String>,
     pub is_active: Option<bool>,
+    pub cedula_juridica: Option<String>,
+    /// Reemplaza la lista completa de contactos.
+    pub contactos: Option<Vec<ContactoEmpresa>>,
+    pub empresa_padre_id: Option<String>,
+    pub contrato_inicio: Option<String>,
+    pub contrato_fin: Option<String>,
 }
 
 // --------------------------------------------------------------------------
@@ -55,6 +99,11 @@ pub struct EmpresaCreateDTO {
     pub nombre: String,
     pub direccion: Option<String>,
     pub is_active: bool,
+    pub cedula_juridica: Option<String>,
+    pub contactos: Vec<ContactoEmpresa>,
+    pub empresa_padre: Option<RecordId>,
+    pub contrato_inicio: Option<surrealdb::Datetime>,
+    pub contrato_fin: Option<surrealdb::Datetime>,
 }
 
 #[derive(Debug, Serialize, Default)]
@@ -66,6 +115,16 @@ pub struct EmpresaUpdateDTO {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_active: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub cedula_juridica: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contactos: Option<Vec<ContactoEmpresa>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub empresa_padre: Option<RecordId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contrato_inicio: Option<surrealdb::Datetime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contrato_fin: Option<surrealdb::Datetime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<surrealdb::Datetime>,
 }
 
@@ -81,6 +140,14 @@ pub struct EmpresaResponse {
     pub nombre: String,
     pub direccion: Option<String>,
     pub is_active: bool,
+    pub cedula_juridica: Option<String>,
+    pub contactos: Vec<ContactoEmpresa>,
+    pub empresa_padre_id: Option<String>,
+    /// Se llena en capa de servicio.
+    pub empresa_padre_nombre: Option<String>,
+    /// Periodo de contrato (YYYY-MM-DD).
+    pub contrato_inicio: Option<String>,
+    pub contrato_fin: Option<String>,
     pub total_contratistas: usize,
     pub created_at: String,
     pub updated_at: String,
@@ -93,6 +160,12 @@ impl From<Empresa> for EmpresaResponse {
             nombre: e.nombre,
             direccion: e.direccion,
             is_active: e.is_active,
+            cedula_juridica: e.cedula_juridica,
+            contactos: e.contactos,
+            empresa_padre_id: e.empresa_padre.map(|p| p.to_string()),
+            empresa_padre_nombre: None,
+            contrato_inicio: e.contrato_inicio.as_ref().map(fecha_simple),
+            contrato_fin: e.contrato_fin.as_ref().map(fecha_simple),
             total_contratistas: 0, // Se llena en capa de servicio si es necesario
             created_at: e
                 .created_at
@@ -104,6 +177,24 @@ impl From<Empresa> for EmpresaResponse {
     }
 }
 
+/// Fecha YYYY-MM-DD de un `Datetime` guardado a medianoche UTC.
+fn fecha_simple(dt: &surrealdb::Datetime) -> String {
+    let iso = crate::domain::common::datetime_to_iso(dt);
+    iso.get(..10).unwrap_or(&iso).to_string()
+}
+
+/// Resultado de dar de baja una empresa.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BajaEmpresaResponse {
+    /// `true` si se eliminó; `false` si se desactivó en cascada por tener personal.
+    pub eliminada: bool,
+    /// Empresas desactivadas (la empresa y sus subcontratistas).
+    pub empresas_desactivadas: usize,
+    pub contratistas_desactivados: usize,
+    pub proveedores_desactivados: usize,
+}
+
 /// Lista paginada de empresas.
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -361,3 +361,180 @@ impl ExportProfile {
         }
     }
 }
+
+// --------------------------------------------------------------------------
+// REPORTES GENERADOS EN EL SERVIDOR
+// --------------------------------------------------------------------------
+
+/// Reportes predefinidos que se construyen consultando `SurrealDB` directamente.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportKind {
+    /// Ingresos (contratistas, proveedores y visitas) en un rango de fechas.
+    IngresosPorFecha,
+    /// Personas que se encuentran actualmente dentro de las instalaciones.
+    PersonalEnPlanta,
+    /// Contratistas con PRAIND vencido o por vencer en los próximos días.
+    PraindPorVencer,
+    /// Gafetes marcados como extraviados y adeudos pendientes.
+    GafetesExtraviados,
+    /// Personas con bloqueo activo en lista negra.
+    ListaNegraActiva,
+    /// Citas vencidas sin presentarse, agrupadas por anfitrión.
+    InasistenciasCitas,
+}
+
+impl ReportKind {
+    pub const fn all() -> [Self; 6] {
+        [
+            Self::IngresosPorFecha,
+            Self::PersonalEnPlanta,
+            Self::PraindPorVencer,
+            Self::GafetesExtraviados,
+            Self::ListaNegraActiva,
+            Self::InasistenciasCitas,
+        ]
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::IngresosPorFecha => "ingresos_por_fecha",
+            Self::PersonalEnPlanta => "personal_en_planta",
+            Self::PraindPorVencer => "praind_por_vencer",
+            Self::GafetesExtraviados => "gafetes_extraviados",
+            Self::ListaNegraActiva => "lista_negra_activa",
+            Self::InasistenciasCitas => "inasistencias_citas",
+        }
+    }
+
+    pub const fn titulo(self) -> &'static str {
+        match self {
+            Self::IngresosPorFecha => "Ingresos por Fecha",
+            Self::PersonalEnPlanta => "Personal en Planta",
+            Self::PraindPorVencer => "PRAIND por Vencer",
+            Self::GafetesExtraviados => "Gafetes Extraviados",
+            Self::ListaNegraActiva => "Lista Negra Activa",
+            Self::InasistenciasCitas => "Inasistencias por Anfitrión",
+        }
+    }
+
+    /// Reportes que exigen un rango de fechas; al programarlos se usa el periodo elegido.
+    pub const fn usa_periodo(self) -> bool {
+        matches!(self, Self::IngresosPorFecha | Self::InasistenciasCitas)
+    }
+}
+
+impl std::str::FromStr for ReportKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| format!("Reporte desconocido: {s}"))
+    }
+}
+
+/// Solicitud de un reporte generado en el servidor.
+///
+/// Los filtros que no apliquen al reporte elegido se ignoran.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportRequest {
+    pub reporte: ReportKind,
+    pub format: String,
+    /// Rango de fechas (YYYY-MM-DD, inclusivo).
+    pub desde: Option<String>,
+    pub hasta: Option<String>,
+    /// Tipo de ingreso (`contratista`, `proveedor`, `visita`).
+    pub tipo_ingreso: Option<String>,
+    /// Empresa (`empresa:xxx`) para acotar ingresos, personal y PRAIND.
+    pub empresa_id: Option<String>,
+    /// Horizonte en días para `praind_por_vencer` (default 30).
+    pub dias: Option<i64>,
+    pub title: Option<String>,
+    pub orientation: Option<String>,
+    pub show_preview: Option<bool>,
+    pub template_id: Option<String>,
+    pub delimiter: Option<String>,
+    pub include_bom: Option<bool>,
+    pub target_path: Option<String>,
+}
+
+/// Descripción de un reporte disponible (para poblar la UI).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportDefinition {
+    pub id: ReportKind,
+    pub titulo: String,
+    pub descripcion: String,
+    pub headers: Vec<String>,
+    /// Parámetros que acepta el reporte (`desde`, `hasta`, `tipoIngreso`, ...).
+    pub parametros: Vec<String>,
+}
+
+// --------------------------------------------------------------------------
+// REPORTES PROGRAMADOS
+// --------------------------------------------------------------------------
+
+/// Periodo de datos relativo al momento de ejecución de un reporte programado.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportPeriod {
+    #[default]
+    Hoy,
+    Ayer,
+    /// Últimos 7 días, incluyendo hoy.
+    UltimaSemana,
+    /// Últimos 30 días, incluyendo hoy.
+    UltimoMes,
+}
+
+/// Reporte que se genera automáticamente según una expresión tipo cron.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportSchedule {
+    /// Vacío al crear; el servicio asigna uno nuevo.
+    #[serde(default)]
+    pub id: String,
+    pub nombre: String,
+    pub reporte: ReportKind,
+    /// Perfil de exportación que define formato y diseño (default: perfil predeterminado).
+    pub profile_id: Option<String>,
+    /// Expresión cron de 5 campos (`min hora día-mes mes día-semana`), hora local.
+    pub cron: String,
+    #[serde(default)]
+    pub periodo: ReportPeriod,
+    /// Directorio de destino de los archivos generados.
+    pub output_dir: String,
+    pub tipo_ingreso: Option<String>,
+    pub empresa_id: Option<String>,
+    pub dias: Option<i64>,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+    /// Minuto de la última ejecución automática (RFC 3339), evita duplicados.
+    #[serde(default)]
+    pub last_run_at: Option<String>,
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+/// Resultado de una ejecución de reporte programado.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportRun {
+    pub id: String,
+    pub schedule_id: String,
+    pub schedule_nombre: String,
+    pub reporte: ReportKind,
+    pub started_at: String,
+    pub finished_at: String,
+    pub success: bool,
+    pub file_path: Option<String>,
+    pub message: String,
+    /// `true` si se lanzó manualmente desde la UI.
+    #[serde(default)]
+    pub manual: bool,
+}
@@ -22,6 +22,14 @@ pub struct Gafete {
     /// Indica si el gafete está actualmente asignado a un ingreso activo.
     #[serde(default, alias = "en_uso")]
     pub en_uso: bool,
+    /// Última vez que se entregó (para repartir el desgaste).
+    #[serde(default, alias = "ultimo_uso")]
+    pub ultimo_uso: Option<Datetime>,
+    /// Reserva temporal al proponerlo en una terminal.
+    #[serde(default, alias = "reservado_hasta")]
+    pub reservado_hasta: Option<Datetime>,
+    #[serde(default, alias = "reservado_por")]
+    pub reservado_por: Option<RecordId>,
     #[serde(alias = "created_at")]
     pub created_at: Datetime,
     #[serde(alias = "updated_at")]
@@ -120,6 +128,167 @@ impl std::str::FromStr for GafeteEstado {
     }
 }
 
+// --------------------------------------------------------------------------
+// ASIGNACIÓN AUTOMÁTICA
+// --------------------------------------------------------------------------
+
+/// Criterio para proponer el siguiente gafete libre.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PoliticaAsignacionGafete {
+    /// El número libre más bajo.
+    #[default]
+    MenorNumero,
+    /// El que lleva más tiempo sin entregarse (reparte el desgaste).
+    MenosUsado,
+}
+
+/// Rango de números reservado para el personal de una empresa.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RangoGafeteEmpresa {
+    pub empresa_id: String,
+    pub tipo: TipoGafete,
+    pub desde: i32,
+    pub hasta: i32,
+}
+
+/// Deuda de gafete para la UI.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeudaGafeteResponse {
+    pub id: String,
+    pub gafete_id: String,
+    pub numero: i32,
+    pub tipo: TipoGafete,
+    pub cedula: String,
+    pub nombre: Option<String>,
+    pub ingreso_id: Option<String>,
+    pub desde: String,
+    pub saldada: bool,
+    pub saldada_en: Option<String>,
+    pub notas: Option<String>,
+}
+
+impl From<DeudaGafete> for DeudaGafeteResponse {
+    fn from(d: DeudaGafete) -> Self {
+        Self {
+            id: d.id.to_string(),
+            gafete_id: d.gafete.to_string(),
+            numero: d.numero,
+            tipo: d.tipo,
+            cedula: d.cedula,
+            nombre: d.nombre,
+            ingreso_id: d.ingreso.map(|i| i.to_string()),
+            desde: d.desde.to_string(),
+            saldada: d.saldada,
+            saldada_en: d.saldada_en.map(|f| f.to_string()),
+            notas: d.notas,
+        }
+    }
+}
+
+/// Gafete propuesto y reservado para la terminal que lo pidió.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropuestaGafeteResponse {
+    pub gafete: GafeteResponse,
+    pub politica: PoliticaAsignacionGafete,
+    /// El número pertenece a un rango reservado para la empresa indicada.
+    pub rango_empresa: bool,
+    pub reservado_hasta: String,
+}
+
+// --------------------------------------------------------------------------
+// HISTORIAL (CADENA DE CUSTODIA)
+// --------------------------------------------------------------------------
+
+/// Evento registrado en la historia de un gafete.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AccionGafete {
+    /// Entregado a una persona al registrar su ingreso.
+    Asignado,
+    /// Recuperado en la salida o al resolver la alerta de no devolución.
+    Devuelto,
+    /// La persona salió sin devolverlo.
+    NoDevuelto,
+    /// Cambio de estado físico (ej. dañado, extraviado).
+    CambioEstado,
+}
+
+impl AccionGafete {
+    pub const fn as_str(&self) -> &str {
+        match self {
+            Self::Asignado => "asignado",
+            Self::Devuelto => "devuelto",
+            Self::NoDevuelto => "no_devuelto",
+            Self::CambioEstado => "cambio_estado",
+        }
+    }
+}
+
+impl std::fmt::Display for AccionGafete {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Movimiento persistido en `gafete_movimiento`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GafeteMovimiento {
+    pub id: RecordId,
+    pub gafete: RecordId,
+    pub numero: i32,
+    pub tipo: TipoGafete,
+    pub accion: AccionGafete,
+    /// Persona que recibe o tenía el gafete.
+    pub cedula: Option<String>,
+    pub nombre: Option<String>,
+    /// Ingreso a través del cual se entregó o recuperó.
+    pub ingreso: Option<RecordId>,
+    /// Guarda o supervisor que registró el movimiento.
+    pub usuario: Option<RecordId>,
+    pub estado_anterior: Option<GafeteEstado>,
+    pub estado_nuevo: Option<GafeteEstado>,
+    pub notas: Option<String>,
+    pub fecha: Datetime,
+}
+
+/// Datos de quién, cómo y por qué ocurre un movimiento; los aportan los servicios de ingreso.
+#[derive(Debug, Clone, Default)]
+pub struct ContextoMovimientoGafete {
+    pub cedula: Option<String>,
+    pub nombre: Option<String>,
+    pub ingreso: Option<RecordId>,
+    pub usuario: Option<RecordId>,
+    pub notas: Option<String>,
+}
+
+/// Deuda de gafete: la persona salió sin devolverlo y sigue debiéndolo.
+///
+/// Vive en `deuda_gafete`, indexada por número+tipo y por cédula, y se salda
+/// cuando el gafete vuelve al inventario.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeudaGafete {
+    pub id: RecordId,
+    pub gafete: RecordId,
+    pub numero: i32,
+    pub tipo: TipoGafete,
+    pub cedula: String,
+    pub nombre: Option<String>,
+    /// Ingreso en el que se entregó y no se devolvió.
+    pub ingreso: Option<RecordId>,
+    /// Guarda que registró la salida sin devolución.
+    pub registrada_por: Option<RecordId>,
+    pub desde: Datetime,
+    #[serde(default)]
+    pub saldada: bool,
+    pub saldada_en: Option<Datetime>,
+    pub saldada_por: Option<RecordId>,
+    pub notas: Option<String>,
+}
+
 // --------------------------------------------------------------------------
 // DTOs DE ENTRADA
 // --------------------------------------------------------------------------
@@ -169,6 +338,38 @@ pub struct GafeteCreateDTO {
     pub en_uso: bool,
 }
 
+/// DTO para registrar un movimiento de gafete.
+#[derive(Debug, Serialize)]
+pub struct GafeteMovimientoCreateDTO {
+    pub gafete: RecordId,
+    pub numero: i32,
+    pub tipo: TipoGafete,
+    pub accion: AccionGafete,
+    pub cedula: Option<String>,
+    pub nombre: Option<String>,
+    pub ingreso: Option<RecordId>,
+    pub usuario: Option<RecordId>,
+    pub estado_anterior: Option<GafeteEstado>,
+    pub estado_nuevo: Option<GafeteEstado>,
+    pub notas: Option<String>,
+}
+
+/// DTO para abrir una deuda de gafete.
+#[derive(Debug, Serialize)]
+pub struct DeudaGafeteCreateDTO {
+    pub gafete: RecordId,
+    pub numero: i32,
+    pub tipo: TipoGafete,
+    pub cedula: String,
+    pub nombre: Option<String>,
+    pub ingreso: Option<RecordId>,
+    pub registrada_por: Option<RecordId>,
+    pub notas: Option<String>,
+    /// Inicio de la deuda; si falta se usa el momento del registro.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desde: Option<Datetime>,
+}
+
 // --------------------------------------------------------------------------
 // DTOs DE RESPUESTA
 // --------------------------------------------------------------------------
@@ -274,3 +475,34 @@ pub struct StatsPorTipo {
     pub visitas: usize,
     pub otros: usize,
 }
+
+// --------------------------------------------------------------------------
+// RESPUESTAS DE HISTORIAL
+// --------------------------------------------------------------------------
+
+/// Movimiento de un gafete listo para mostrar.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GafeteMovimientoResponse {
+    pub id: String,
+    pub accion: AccionGafete,
+    pub cedula: Option<String>,
+    pub nombre: Option<String>,
+    pub ingreso_id: Option<String>,
+    pub usuario_id: Option<String>,
+    pub usuario_nombre: Option<String>,
+    pub estado_anterior: Option<GafeteEstado>,
+    pub estado_nuevo: Option<GafeteEstado>,
+    pub notas: Option<String>,
+    pub fecha: String,
+}
+
+/// Historia completa de un gafete (movimientos del más reciente al más antiguo).
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GafeteHistorialResponse {
+    pub gafete: GafeteResponse,
+    /// Última persona a la que se entregó el gafete.
+    pub ultimo_portador: Option<GafeteMovimientoResponse>,
+    pub movimientos: Vec<GafeteMovimientoResponse>,
+}
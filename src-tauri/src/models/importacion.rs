@@ -0,0 +1,103 @@
+//! # Models: Importación Masiva de Personas
+//!
+//! Alta masiva de contratistas, proveedores y visitantes desde CSV o Excel (`.xlsx`),
+//! p. ej. al incorporar una empresa contratista con todo su personal.
+//!
+//! ## Flujo
+//! 1. `previsualizar_importacion`: lee el archivo, valida cada fila y reporta errores
+//!    sin escribir nada (dry-run).
+//! 2. `importar_personas`: vuelve a validar y registra las filas válidas en una sola
+//!    sentencia (todo o nada), luego actualiza el índice de búsqueda.
+//!
+//! Los vehículos no se importan; se registran después desde la ficha de cada persona.
+
+use serde::{Deserialize, Serialize};
+
+// --------------------------------------------------------------------------
+// ENUMS DE DOMINIO
+// --------------------------------------------------------------------------
+
+/// Tipo de persona que se importa.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TipoImportacion {
+    Contratista,
+    Proveedor,
+    Visitante,
+}
+
+impl TipoImportacion {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Contratista => "contratista",
+            Self::Proveedor => "proveedor",
+            Self::Visitante => "visitante",
+        }
+    }
+
+    /// Permiso requerido para importar este tipo.
+    pub const fn permiso(self) -> &'static str {
+        match self {
+            Self::Contratista => "contratistas:create",
+            Self::Proveedor => "proveedores:create",
+            Self::Visitante => "visitantes:create",
+        }
+    }
+}
+
+// ==========================================
+// DTOs de entrada (Commands/Input)
+// ==========================================
+
+/// Archivo a importar.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportacionInput {
+    pub tipo: TipoImportacion,
+    /// Ruta absoluta del `.csv` o `.xlsx` (seleccionado en el diálogo nativo).
+    pub file_path: String,
+    /// Empresa para las filas sin columna `empresa` (o con la celda vacía).
+    pub empresa_id: Option<String>,
+    /// Delimitador del CSV; si falta se detecta entre `,`, `;` y tabulador.
+    pub delimiter: Option<char>,
+}
+
+// ==========================================
+// DTOs de salida (Response/ViewModel)
+// ==========================================
+
+/// Resultado de validar una fila del archivo.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilaImportacionReporte {
+    /// Número de fila en el archivo (la fila 1 es el encabezado).
+    pub fila: usize,
+    pub cedula: Option<String>,
+    pub nombre_completo: Option<String>,
+    pub empresa: Option<String>,
+    pub valida: bool,
+    pub errores: Vec<String>,
+}
+
+/// Vista previa (dry-run) de una importación: no se escribió nada.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrevisualizacionImportacion {
+    pub tipo: TipoImportacion,
+    pub total: usize,
+    pub validas: usize,
+    pub con_errores: usize,
+    /// Encabezados del archivo que no corresponden a ningún campo.
+    pub columnas_ignoradas: Vec<String>,
+    pub filas: Vec<FilaImportacionReporte>,
+}
+
+/// Resultado de aplicar una importación.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportacionResponse {
+    pub tipo: TipoImportacion,
+    pub creados: usize,
+    /// Filas que no se importaron, con sus errores.
+    pub rechazadas: Vec<FilaImportacionReporte>,
+}
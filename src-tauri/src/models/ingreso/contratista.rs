@@ -81,6 +81,12 @@ pub struct CreateIngresoContratistaInput {
     /// Valores: número (ej: "1", "2") o vacío/null para sin gafete (0)
     pub gafete_numero: Option<String>,
     pub observaciones: Option<String>,
+    /// Área a la que se dirige; activa los documentos exigidos para esa área.
+    pub area: Option<String>,
+    /// Se verificó que no es la persona de una posible coincidencia en lista negra.
+    /// Requiere `lista_negra:update` y queda en `audit_log` con el bloqueo coincidente.
+    #[serde(default)]
+    pub coincidencia_lista_negra_descartada: bool,
 }
 
 // --------------------------------------------------------------------------
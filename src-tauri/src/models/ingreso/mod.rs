@@ -23,7 +23,7 @@ pub use response::*;
 pub use visita::*;
 
 use serde::{Deserialize, Serialize};
-use surrealdb::RecordId;
+use surrealdb::{Datetime, RecordId};
 
 // ==========================================
 // ENUMS DE DOMINIO
@@ -183,6 +183,38 @@ impl UniversalIngresoFetched {
     }
 }
 
+/// Proyección plana de un ingreso (de cualquier tipo) para el índice de historial.
+///
+/// Los campos que no existen en una tabla (p.ej. `anfitrion` en `ingreso_contratista`)
+/// llegan como `None`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct IngresoHistorialRow {
+    pub id: RecordId,
+    /// `contratista`, `proveedor` o `visita`.
+    pub tipo: String,
+    pub cedula: String,
+    pub nombre: String,
+    pub apellido: String,
+    pub segundo_nombre: Option<String>,
+    pub segundo_apellido: Option<String>,
+    pub empresa_nombre: Option<String>,
+    pub anfitrion: Option<String>,
+    pub area_visitada: Option<String>,
+    pub motivo: Option<String>,
+    pub observaciones: Option<String>,
+    pub placa_vehiculo: Option<String>,
+    pub fecha_hora_ingreso: Datetime,
+    pub fecha_hora_salida: Option<Datetime>,
+    pub updated_at: Datetime,
+}
+
+/// Lápida de un ingreso borrado, registrada por los eventos `DELETE` del schema.
+#[derive(Debug, Clone, Deserialize)]
+pub struct IngresoEliminadoRow {
+    pub ingreso: RecordId,
+    pub eliminado_en: Datetime,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(tag = "tipo", rename_all = "lowercase")]
 pub enum CreateIngresoInput {
@@ -234,6 +266,10 @@ pub struct ValidacionIngresoResponse {
     pub contratista: Option<serde_json::Value>,
     pub tiene_ingreso_abierto: bool,
     pub ingreso_abierto: Option<IngresoResponse>,
+    /// Bloqueos de otra cédula con un nombre parecido.
+    pub posibles_coincidencias: Vec<crate::models::lista_negra::CoincidenciaListaNegra>,
+    /// El guardia debe confirmar la identidad antes de registrar el ingreso.
+    pub requiere_confirmacion: bool,
 }
 
 // --------------------------------------------------------------------------
@@ -2,6 +2,7 @@
 // src/models/ingreso/proveedor.rs
 // ==========================================
 
+use crate::models::lista_negra::CoincidenciaListaNegra;
 use crate::models::proveedor::ProveedorFetched;
 use crate::models::user::User;
 use serde::{Deserialize, Serialize};
@@ -88,6 +89,10 @@ pub struct CreateIngresoProveedorInput {
     pub observaciones: Option<String>,
     pub segundo_nombre: Option<String>,
     pub segundo_apellido: Option<String>,
+    /// Se verificó que no es la persona de una posible coincidencia en lista negra.
+    /// Requiere `lista_negra:update` y queda en `audit_log` con el bloqueo coincidente.
+    #[serde(default)]
+    pub coincidencia_lista_negra_descartada: bool,
 }
 
 // --------------------------------------------------------------------------
@@ -135,4 +140,13 @@ pub struct ValidacionIngresoProveedorResponse {
     pub alertas_gafete: Vec<String>,
     pub tiene_gafetes_pendientes: bool,
     pub tiene_ingreso_abierto: bool,
+    /// Advertencia por veto de severidad baja sobre la empresa.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alerta_empresa: Option<String>,
+    /// Bloqueos de otra cédula con un nombre parecido.
+    #[serde(default)]
+    pub posibles_coincidencias: Vec<CoincidenciaListaNegra>,
+    /// El guardia debe confirmar la identidad antes de registrar el ingreso.
+    #[serde(default)]
+    pub requiere_confirmacion: bool,
 }
@@ -78,6 +78,19 @@ pub struct ListaNegra {
     pub bloqueado_por_nombre: Option<String>,
     #[serde(alias = "is_active")]
     pub is_active: bool,
+    /// Fecha a partir de la cual el bloqueo deja de aplicar (`None` = indefinido).
+    #[serde(default, alias = "vigente_hasta")]
+    pub vigente_hasta: Option<surrealdb::Datetime>,
+    /// Fecha límite para revisar si el bloqueo debe mantenerse.
+    #[serde(default, alias = "proxima_revision")]
+    pub proxima_revision: Option<surrealdb::Datetime>,
+    #[serde(default, alias = "ultima_revision")]
+    pub ultima_revision: Option<surrealdb::Datetime>,
+    #[serde(default, alias = "revisado_por")]
+    pub revisado_por: Option<String>,
+    /// Sitio (planta) del que se importó el bloqueo; `None` si se registró aquí.
+    #[serde(default, alias = "sitio_origen")]
+    pub sitio_origen: Option<String>,
     #[serde(alias = "created_at")]
     pub created_at: surrealdb::Datetime,
     #[serde(alias = "updated_at")]
@@ -89,7 +102,7 @@ pub struct ListaNegra {
 // --------------------------------------------------------------------------
 
 /// Datos requeridos para agregar a una persona a la lista negra.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AddToListaNegraInput {
     pub cedula: String,
@@ -102,16 +115,36 @@ pub struct AddToListaNegraInput {
     pub nivel_severidad: String,
     pub motivo_bloqueo: Option<String>,
     pub bloqueado_por: String,
+    /// Vencimiento explícito (RFC 3339 o YYYY-MM-DD); si falta se aplica la política del nivel.
+    #[serde(default)]
+    pub vigente_hasta: Option<String>,
+    /// Sitio de origen cuando el bloqueo proviene de una importación.
+    #[serde(default)]
+    pub sitio_origen: Option<String>,
 }
 
 /// Datos para actualizar un registro de bloqueo existente.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateListaNegraInput {
     pub nivel_severidad: Option<String>,
     pub motivo_bloqueo: Option<String>,
     pub empresa_id: Option<String>,
     pub empresa_nombre: Option<String>,
+    /// Nuevo vencimiento (RFC 3339 o YYYY-MM-DD).
+    #[serde(default)]
+    pub vigente_hasta: Option<String>,
+}
+
+/// Resultado de la revisión periódica de un bloqueo.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevisarListaNegraInput {
+    /// `false` levanta el bloqueo; `true` lo mantiene hasta la siguiente revisión.
+    pub mantener: bool,
+    /// Nuevo vencimiento opcional al mantenerlo.
+    #[serde(default)]
+    pub vigente_hasta: Option<String>,
 }
 
 // --------------------------------------------------------------------------
@@ -137,6 +170,13 @@ pub struct ListaNegraResponse {
     pub bloqueado_por_nombre: Option<String>,
     pub is_active: bool,
     pub dias_bloqueado: i64,
+    pub vigente_hasta: Option<String>,
+    pub proxima_revision: Option<String>,
+    pub ultima_revision: Option<String>,
+    pub revisado_por: Option<String>,
+    /// La revisión periódica ya venció (o nunca se programó).
+    pub revision_pendiente: bool,
+    pub sitio_origen: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -164,6 +204,10 @@ impl From<ListaNegra> for ListaNegraResponse {
         let updated = parse_surreal_date(ln.updated_at.to_string());
 
         let dias_bloqueado = (chrono::Utc::now() - created).num_days();
+        let opcional =
+            |d: Option<surrealdb::Datetime>| d.map(|f| parse_surreal_date(f.to_string()));
+        let proxima_revision = opcional(ln.proxima_revision);
+        let revision_pendiente = proxima_revision.is_none_or(|r| r <= chrono::Utc::now());
 
         Self {
             id: ln.id.to_string(),
@@ -181,12 +225,30 @@ impl From<ListaNegra> for ListaNegraResponse {
             bloqueado_por_nombre: ln.bloqueado_por_nombre,
             is_active: ln.is_active,
             dias_bloqueado,
+            vigente_hasta: opcional(ln.vigente_hasta).map(|f| f.to_rfc3339()),
+            proxima_revision: proxima_revision.map(|f| f.to_rfc3339()),
+            ultima_revision: opcional(ln.ultima_revision).map(|f| f.to_rfc3339()),
+            revisado_por: ln.revisado_por,
+            revision_pendiente,
+            sitio_origen: ln.sitio_origen,
             created_at: created.to_rfc3339(),
             updated_at: updated.to_rfc3339(),
         }
     }
 }
 
+/// Aviso periódico de la tarea de vigencia (evento `lista_negra:revision`).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvisoRevisionListaNegra {
+    /// Bloqueos con revisión vencida o próxima a vencer.
+    pub revisiones_pendientes: usize,
+    /// De ellos, los que ya superaron la fecha de revisión.
+    pub revision_vencida: usize,
+    /// Cédulas desbloqueadas en esta pasada por vencimiento.
+    pub desactivados_por_vencimiento: Vec<String>,
+}
+
 /// Respuesta simplificada de verificación de bloqueo.
 #[derive(Debug, Clone, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -199,6 +261,45 @@ pub struct BlockStatus {
 /// Respuesta simplificada de verificación de bloqueo (Legacy alias replacement).
 pub type BlockCheckResponse = BlockStatus;
 
+/// Posible coincidencia por nombre con un bloqueo vigente de otra cédula.
+///
+/// No bloquea por sí sola: el guardia debe confirmar si se trata de la misma persona.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoincidenciaListaNegra {
+    pub id: String,
+    pub cedula: String,
+    pub nombre_completo: String,
+    pub nivel_severidad: String,
+    pub motivo_bloqueo: Option<String>,
+    /// Similitud del nombre (0–1).
+    pub score: f64,
+}
+
+impl CoincidenciaListaNegra {
+    pub fn new(ln: ListaNegra, score: f64) -> Self {
+        let nombre_completo = [
+            Some(ln.nombre.as_str()),
+            ln.segundo_nombre.as_deref(),
+            Some(ln.apellido.as_str()),
+            ln.segundo_apellido.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+        Self {
+            id: ln.id.to_string(),
+            cedula: ln.cedula,
+            nombre_completo,
+            nivel_severidad: ln.nivel_severidad,
+            motivo_bloqueo: ln.motivo_bloqueo,
+            score,
+        }
+    }
+}
+
 /// Lista paginada de personas bloqueadas.
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -234,3 +335,468 @@ pub struct PersonaSearchResult {
     pub empresa_nombre: Option<String>,
     pub ya_bloqueado: bool,
 }
+
+// --------------------------------------------------------------------------
+// EMPRESAS VETADAS (Bloqueo a nivel de empresa)
+// --------------------------------------------------------------------------
+
+/// Veto sobre una empresa completa.
+///
+/// Aplica a todos los contratistas y proveedores vinculados a la empresa, además
+/// de los bloqueos individuales por cédula.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmpresaVetada {
+    pub id: RecordId,
+    pub empresa: RecordId,
+    #[serde(default, alias = "empresa_nombre")]
+    pub empresa_nombre: Option<String>,
+    #[serde(alias = "nivel_severidad")]
+    pub nivel_severidad: String,
+    pub motivo: String,
+    /// Impide registrar nuevos contratistas o proveedores bajo la empresa.
+    #[serde(alias = "bloquear_registros")]
+    pub bloquear_registros: bool,
+    #[serde(alias = "bloqueado_por")]
+    pub bloqueado_por: String,
+    #[serde(alias = "is_active")]
+    pub is_active: bool,
+    #[serde(alias = "created_at")]
+    pub created_at: surrealdb::Datetime,
+    #[serde(alias = "updated_at")]
+    pub updated_at: surrealdb::Datetime,
+}
+
+/// Datos para vetar una empresa.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VetarEmpresaInput {
+    pub empresa_id: String,
+    pub nivel_severidad: String,
+    pub motivo: String,
+    /// Por defecto el veto también impide nuevos registros bajo la empresa.
+    #[serde(default = "default_bloquear_registros")]
+    pub bloquear_registros: bool,
+}
+
+const fn default_bloquear_registros() -> bool {
+    true
+}
+
+/// Datos para modificar un veto de empresa existente.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateEmpresaVetadaInput {
+    pub nivel_severidad: Option<String>,
+    pub motivo: Option<String>,
+    pub bloquear_registros: Option<bool>,
+}
+
+/// Respuesta de un veto de empresa.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmpresaVetadaResponse {
+    pub id: String,
+    pub empresa_id: String,
+    pub empresa_nombre: Option<String>,
+    pub nivel_severidad: String,
+    pub motivo: String,
+    pub bloquear_registros: bool,
+    pub bloqueado_por: String,
+    pub is_active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<EmpresaVetada> for EmpresaVetadaResponse {
+    fn from(ev: EmpresaVetada) -> Self {
+        Self {
+            id: ev.id.to_string(),
+            empresa_id: ev.empresa.to_string(),
+            empresa_nombre: ev.empresa_nombre,
+            nivel_severidad: ev.nivel_severidad,
+            motivo: ev.motivo,
+            bloquear_registros: ev.bloquear_registros,
+            bloqueado_por: ev.bloqueado_por,
+            is_active: ev.is_active,
+            created_at: crate::domain::common::datetime_to_iso(&ev.created_at),
+            updated_at: crate::domain::common::datetime_to_iso(&ev.updated_at),
+        }
+    }
+}
+
+/// Estado de veto de una empresa (hot-path de registro e ingreso).
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EmpresaVetadaStatus {
+    pub is_vetada: bool,
+    pub empresa_nombre: Option<String>,
+    pub nivel_severidad: Option<String>,
+    pub motivo: Option<String>,
+    pub bloquear_registros: bool,
+}
+
+impl From<Option<EmpresaVetada>> for EmpresaVetadaStatus {
+    fn from(ev: Option<EmpresaVetada>) -> Self {
+        ev.map_or_else(Self::default, |ev| Self {
+            is_vetada: true,
+            empresa_nombre: ev.empresa_nombre,
+            nivel_severidad: Some(ev.nivel_severidad),
+            motivo: Some(ev.motivo),
+            bloquear_registros: ev.bloquear_registros,
+        })
+    }
+}
+
+// --------------------------------------------------------------------------
+// EVIDENCIAS ADJUNTAS
+// --------------------------------------------------------------------------
+
+/// Evidencia (foto o informe) que respalda un bloqueo.
+///
+/// El contenido se guarda cifrado en disco; aquí solo viven los metadatos y el
+/// SHA-256 del original para verificar la integridad al descifrar.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvidenciaListaNegra {
+    pub id: RecordId,
+    #[serde(alias = "lista_negra")]
+    pub lista_negra: RecordId,
+    /// Nombre ofuscado del archivo cifrado (`{uuid}.enc`).
+    #[serde(alias = "archivo_uuid")]
+    pub archivo_uuid: String,
+    #[serde(alias = "nombre_original")]
+    pub nombre_original: String,
+    #[serde(alias = "tipo_mime")]
+    pub tipo_mime: String,
+    #[serde(alias = "tamano_bytes")]
+    pub tamano_bytes: i64,
+    pub sha256: String,
+    pub descripcion: Option<String>,
+    #[serde(alias = "subido_por")]
+    pub subido_por: String,
+    #[serde(alias = "created_at")]
+    pub created_at: surrealdb::Datetime,
+}
+
+/// Datos para adjuntar una evidencia a un registro de lista negra.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdjuntarEvidenciaInput {
+    pub lista_negra_id: String,
+    /// Ruta absoluta del archivo original (seleccionado en el diálogo nativo).
+    pub file_path: String,
+    pub descripcion: Option<String>,
+}
+
+/// Metadatos de una evidencia (sin contenido).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvidenciaListaNegraResponse {
+    pub id: String,
+    pub lista_negra_id: String,
+    pub nombre_original: String,
+    pub tipo_mime: String,
+    pub tamano_bytes: i64,
+    pub sha256: String,
+    pub descripcion: Option<String>,
+    pub subido_por: String,
+    pub created_at: String,
+}
+
+impl From<EvidenciaListaNegra> for EvidenciaListaNegraResponse {
+    fn from(ev: EvidenciaListaNegra) -> Self {
+        Self {
+            id: ev.id.to_string(),
+            lista_negra_id: ev.lista_negra.to_string(),
+            nombre_original: ev.nombre_original,
+            tipo_mime: ev.tipo_mime,
+            tamano_bytes: ev.tamano_bytes,
+            sha256: ev.sha256,
+            descripcion: ev.descripcion,
+            subido_por: ev.subido_por,
+            created_at: crate::domain::common::datetime_to_iso(&ev.created_at),
+        }
+    }
+}
+
+/// Evidencia descifrada y verificada, lista para mostrarse.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvidenciaContenidoResponse {
+    #[serde(flatten)]
+    pub evidencia: EvidenciaListaNegraResponse,
+    /// Contenido original en Base64.
+    pub contenido_base64: String,
+}
+
+// --------------------------------------------------------------------------
+// INTERCAMBIO ENTRE SITIOS
+// --------------------------------------------------------------------------
+
+/// Bloqueo tal como viaja en un archivo de intercambio entre plantas.
+///
+/// No incluye IDs internos ni usuarios: solo la identidad de la persona y el bloqueo.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistroIntercambioListaNegra {
+    pub cedula: String,
+    pub nombre: String,
+    #[serde(default)]
+    pub segundo_nombre: Option<String>,
+    pub apellido: String,
+    #[serde(default)]
+    pub segundo_apellido: Option<String>,
+    #[serde(default)]
+    pub empresa_nombre: Option<String>,
+    pub nivel_severidad: String,
+    #[serde(default)]
+    pub motivo_bloqueo: Option<String>,
+    /// Vencimiento en RFC 3339 (`None` = indefinido).
+    #[serde(default)]
+    pub vigente_hasta: Option<String>,
+    /// Sitio donde se originó el bloqueo (se conserva al reexportar).
+    #[serde(default)]
+    pub sitio_origen: Option<String>,
+}
+
+/// Contenido de un archivo de intercambio en formato JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivoIntercambioListaNegra {
+    pub version: u32,
+    /// Sitio que generó el archivo.
+    pub sitio: String,
+    pub generado_en: String,
+    pub registros: Vec<RegistroIntercambioListaNegra>,
+}
+
+/// Planta hermana cuyos archivos de intercambio se aceptan al importar.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SitioConfiable {
+    /// Debe coincidir con el `sitio` del encabezado de sus archivos.
+    pub nombre: String,
+    /// Clave pública Ed25519 del sitio (HEX).
+    pub clave_publica: String,
+}
+
+/// Resultado de exportar la lista negra a un archivo firmado.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportacionListaNegraResponse {
+    pub total: usize,
+    pub file_path: String,
+    /// Firma separada (`{file_path}.sig`) que debe acompañar al archivo.
+    pub firma_path: String,
+}
+
+/// Tipo de cambio que produciría un registro importado.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TipoCambioImportacion {
+    /// La cédula no existe localmente (o su bloqueo ya no está activo).
+    Nuevo,
+    /// Ya bloqueada aquí con otra severidad.
+    SeveridadActualizada,
+    /// La cédula existe localmente con un nombre distinto: no se aplica.
+    Conflicto,
+    /// Ya bloqueada aquí con la misma severidad.
+    SinCambios,
+}
+
+/// Cambio previsto para un registro del archivo importado.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CambioImportacionListaNegra {
+    pub tipo: TipoCambioImportacion,
+    pub sitio_origen: String,
+    pub cedula: String,
+    pub nombre_completo: String,
+    pub nivel_severidad: String,
+    /// Severidad del bloqueo local, si existe.
+    pub nivel_local: Option<String>,
+    /// Nombre registrado localmente para la misma cédula, si existe.
+    pub nombre_local: Option<String>,
+}
+
+/// Error al leer o validar un registro del archivo importado.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorImportacionListaNegra {
+    /// Número de registro dentro del archivo (desde 1).
+    pub registro: usize,
+    pub cedula: Option<String>,
+    pub mensaje: String,
+}
+
+/// Vista previa de una importación: la firma ya fue verificada.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrevisualizacionImportacionListaNegra {
+    /// Sitio que generó el archivo (solo en formato JSON; en CSV va por registro).
+    pub sitio_origen: Option<String>,
+    pub total: usize,
+    pub cambios: Vec<CambioImportacionListaNegra>,
+    pub errores: Vec<ErrorImportacionListaNegra>,
+}
+
+/// Resultado de aplicar una importación.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportacionListaNegraResponse {
+    pub creados: usize,
+    pub actualizados: usize,
+    /// Conflictos y registros sin cambios que no se aplicaron.
+    pub omitidos: usize,
+    /// Altas o cambios que quedaron a la espera de aprobación (doble control).
+    pub pendientes: usize,
+    pub errores: Vec<ErrorImportacionListaNegra>,
+}
+
+// --------------------------------------------------------------------------
+// APROBACIÓN DE CAMBIOS (DOBLE CONTROL)
+// --------------------------------------------------------------------------
+
+/// Operación que se propone sobre la lista negra.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TipoPropuestaListaNegra {
+    Alta,
+    Modificacion,
+    Baja,
+}
+
+impl TipoPropuestaListaNegra {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Alta => "ALTA",
+            Self::Modificacion => "MODIFICACION",
+            Self::Baja => "BAJA",
+        }
+    }
+}
+
+/// Estado de una propuesta.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum EstadoPropuestaListaNegra {
+    Pendiente,
+    Aprobada,
+    Rechazada,
+}
+
+impl EstadoPropuestaListaNegra {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pendiente => "PENDIENTE",
+            Self::Aprobada => "APROBADA",
+            Self::Rechazada => "RECHAZADA",
+        }
+    }
+}
+
+/// Cambio propuesto por un usuario que espera la revisión de un supervisor.
+///
+/// `datos` conserva el input original (JSON) para aplicarlo tal cual al aprobarse.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropuestaListaNegra {
+    pub id: RecordId,
+    pub tipo: TipoPropuestaListaNegra,
+    /// Registro afectado (modificación o baja).
+    #[serde(alias = "lista_negra", default)]
+    pub lista_negra: Option<RecordId>,
+    pub cedula: String,
+    #[serde(alias = "nombre_completo")]
+    pub nombre_completo: String,
+    #[serde(alias = "nivel_severidad", default)]
+    pub nivel_severidad: Option<String>,
+    #[serde(default)]
+    pub motivo: Option<String>,
+    pub datos: String,
+    pub estado: EstadoPropuestaListaNegra,
+    #[serde(alias = "propuesto_por")]
+    pub propuesto_por: String,
+    #[serde(alias = "revisado_por", default)]
+    pub revisado_por: Option<String>,
+    #[serde(default)]
+    pub comentario: Option<String>,
+    #[serde(alias = "created_at")]
+    pub created_at: surrealdb::Datetime,
+    #[serde(alias = "resuelto_at", default)]
+    pub resuelto_at: Option<surrealdb::Datetime>,
+}
+
+/// Decisión del supervisor sobre una propuesta.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolverPropuestaInput {
+    pub aprobar: bool,
+    /// Justificación obligatoria (queda en la auditoría).
+    pub comentario: String,
+}
+
+/// Propuesta tal como se muestra en la bandeja del supervisor.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropuestaListaNegraResponse {
+    pub id: String,
+    pub tipo: TipoPropuestaListaNegra,
+    pub lista_negra_id: Option<String>,
+    pub cedula: String,
+    pub nombre_completo: String,
+    pub nivel_severidad: Option<String>,
+    pub motivo: Option<String>,
+    pub estado: EstadoPropuestaListaNegra,
+    pub propuesto_por: String,
+    pub revisado_por: Option<String>,
+    pub comentario: Option<String>,
+    pub created_at: String,
+    pub resuelto_at: Option<String>,
+}
+
+impl From<PropuestaListaNegra> for PropuestaListaNegraResponse {
+    fn from(p: PropuestaListaNegra) -> Self {
+        Self {
+            id: p.id.to_string(),
+            tipo: p.tipo,
+            lista_negra_id: p.lista_negra.map(|id| id.to_string()),
+            cedula: p.cedula,
+            nombre_completo: p.nombre_completo,
+            nivel_severidad: p.nivel_severidad,
+            motivo: p.motivo,
+            estado: p.estado,
+            propuesto_por: p.propuesto_por,
+            revisado_por: p.revisado_por,
+            comentario: p.comentario,
+            created_at: crate::domain::common::datetime_to_iso(&p.created_at),
+            resuelto_at: p.resuelto_at.as_ref().map(crate::domain::common::datetime_to_iso),
+        }
+    }
+}
+
+/// Resultado de un cambio a la lista negra.
+///
+/// Con doble control el cambio no se aplica: `aplicado = false` y `propuesta`
+/// contiene la propuesta que espera a un supervisor.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CambioListaNegraResponse {
+    pub aplicado: bool,
+    /// Registro resultante (ausente en bajas y en cambios pendientes).
+    pub registro: Option<ListaNegraResponse>,
+    pub propuesta: Option<PropuestaListaNegraResponse>,
+}
+
+impl CambioListaNegraResponse {
+    #[must_use]
+    pub const fn aplicado(registro: Option<ListaNegraResponse>) -> Self {
+        Self { aplicado: true, registro, propuesta: None }
+    }
+
+    #[must_use]
+    pub const fn pendiente(propuesta: PropuestaListaNegraResponse) -> Self {
+        Self { aplicado: false, registro: None, propuesta: Some(propuesta) }
+    }
+}
@@ -1,12 +1,16 @@
 pub mod cita;
 pub mod contratista;
+pub mod documento_contratista;
 pub mod empresa;
 pub mod export;
 pub mod gafete;
+pub mod importacion;
 pub mod ingreso;
 pub mod lista_negra;
+pub mod pase;
 pub mod proveedor;
 pub mod role;
+pub mod scanner;
 pub mod user;
 pub mod validation;
 pub mod vehiculo;
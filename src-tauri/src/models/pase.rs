@@ -0,0 +1,111 @@
+// ==========================================
+// src/models/pase.rs
+// ==========================================
+
+use serde::{Deserialize, Serialize};
+
+// --------------------------------------------------------------------------
+// ENUMS
+// --------------------------------------------------------------------------
+
+/// Plantillas disponibles para el pase imprimible que se entrega al ingresar.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PaseTemplate {
+    /// Media carta A6 con anfitrión y área destacados.
+    Visitante,
+    /// A6 con empresa y autorización destacadas.
+    ContratistaTemporal,
+    /// A6 con empresa y área de entrega.
+    Proveedor,
+    /// Tarjeta tamaño credencial (CR80) con lo esencial.
+    Compacto,
+}
+
+impl PaseTemplate {
+    pub const fn all() -> [Self; 4] {
+        [Self::Visitante, Self::ContratistaTemporal, Self::Proveedor, Self::Compacto]
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Visitante => "visitante",
+            Self::ContratistaTemporal => "contratista_temporal",
+            Self::Proveedor => "proveedor",
+            Self::Compacto => "compacto",
+        }
+    }
+
+    /// Versión del diseño de la plantilla; se incrementa al cambiar su markup.
+    pub const fn version(self) -> u32 {
+        match self {
+            Self::Visitante | Self::ContratistaTemporal | Self::Proveedor | Self::Compacto => 1,
+        }
+    }
+
+    pub const fn display(self) -> &'static str {
+        match self {
+            Self::Visitante => "Pase de Visitante",
+            Self::ContratistaTemporal => "Pase Temporal de Contratista",
+            Self::Proveedor => "Pase de Proveedor",
+            Self::Compacto => "Credencial Compacta",
+        }
+    }
+}
+
+// --------------------------------------------------------------------------
+// MODELO DE RENDERIZADO
+// --------------------------------------------------------------------------
+
+/// Datos ya formateados que se imprimen en el pase.
+#[derive(Debug, Clone, Default)]
+pub struct PaseData {
+    /// ID completo del ingreso (`ingreso_visita:xxx`), codificado en el QR.
+    pub ingreso_id: String,
+    /// Encabezado del pase (p.ej. `VISITANTE`).
+    pub titulo: String,
+    pub nombre_completo: String,
+    pub cedula: String,
+    pub empresa: Option<String>,
+    pub gafete: Option<String>,
+    pub anfitrion: Option<String>,
+    pub area: Option<String>,
+    pub placa: Option<String>,
+    /// Fecha/hora de ingreso formateada (`dd/mm/aaaa HH:MM`).
+    pub fecha_ingreso: String,
+    /// Vigencia del pase formateada (`dd/mm/aaaa HH:MM`).
+    pub valido_hasta: String,
+    /// Foto (PNG, JPEG, GIF o WebP), si está disponible.
+    pub foto: Option<Vec<u8>>,
+}
+
+/// Plantilla con la que se generó un pase, guardada junto al PDF.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PaseMeta {
+    pub plantilla: PaseTemplate,
+    pub version_plantilla: u32,
+}
+
+// --------------------------------------------------------------------------
+// DTOs DE SALIDA
+// --------------------------------------------------------------------------
+
+/// PDF del pase listo para imprimir.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaseResponse {
+    pub ingreso_id: String,
+    pub plantilla: PaseTemplate,
+    pub version_plantilla: u32,
+    pub file_path: Option<String>,
+    pub bytes: Vec<u8>,
+}
+
+/// Descripción de una plantilla (para poblar la UI).
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaseTemplateInfo {
+    pub id: PaseTemplate,
+    pub nombre: String,
+}
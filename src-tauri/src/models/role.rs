@@ -24,6 +24,10 @@ pub enum Module {
     Vehiculos,
     Gafetes,
     ListaNegra,
+    /// Evidencias adjuntas a la lista negra (separado: el guardia ve el bloqueo, no la evidencia).
+    ListaNegraEvidencia,
+    /// Aprobación de cambios propuestos a la lista negra (doble control).
+    ListaNegraAprobacion,
     Config,
     SettingsGeneral,
     SettingsVisual,
@@ -48,6 +52,8 @@ impl Module {
             Self::Vehiculos,
             Self::Gafetes,
             Self::ListaNegra,
+            Self::ListaNegraEvidencia,
+            Self::ListaNegraAprobacion,
             Self::Config,
             Self::SettingsGeneral,
             Self::SettingsVisual,
@@ -72,6 +78,8 @@ impl Module {
             Self::Vehiculos => "vehiculos",
             Self::Gafetes => "gafetes",
             Self::ListaNegra => "lista_negra",
+            Self::ListaNegraEvidencia => "lista_negra_evidencia",
+            Self::ListaNegraAprobacion => "lista_negra_aprobacion",
             Self::Config => "config",
             Self::SettingsGeneral => "settings_general",
             Self::SettingsVisual => "settings_visual",
@@ -96,6 +104,8 @@ impl Module {
             Self::Vehiculos => "Vehículos",
             Self::Gafetes => "Gafetes",
             Self::ListaNegra => "Lista Negra",
+            Self::ListaNegraEvidencia => "Evidencias de Lista Negra",
+            Self::ListaNegraAprobacion => "Aprobación de Lista Negra",
             Self::Config => "Configuración",
             Self::SettingsGeneral => "Ajustes Generales",
             Self::SettingsVisual => "Ajustes Visuales",
@@ -124,6 +134,8 @@ impl std::str::FromStr for Module {
             "vehiculos" => Ok(Self::Vehiculos),
             "gafetes" => Ok(Self::Gafetes),
             "lista_negra" => Ok(Self::ListaNegra),
+            "lista_negra_evidencia" => Ok(Self::ListaNegraEvidencia),
+            "lista_negra_aprobacion" => Ok(Self::ListaNegraAprobacion),
             "config" => Ok(Self::Config),
             "settings_general" => Ok(Self::SettingsGeneral),
             "settings_visual" => Ok(Self::SettingsVisual),
@@ -0,0 +1,84 @@
+// ==========================================
+// src/models/scanner.rs
+// ==========================================
+
+use crate::models::cita::CitaResponse;
+use crate::models::gafete::GafeteResponse;
+use crate::models::ingreso::IngresoResponse;
+use serde::{Deserialize, Serialize};
+
+// --------------------------------------------------------------------------
+// CÓDIGOS DECODIFICADOS
+// --------------------------------------------------------------------------
+
+/// Resultado de decodificar una lectura cruda del escáner.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "clase", rename_all = "snake_case")]
+pub enum CodigoEscaneado {
+    /// Cédula digitada o leída del código de barras de la cédula física.
+    Cedula {
+        cedula: String,
+        nombre: Option<String>,
+        apellido: Option<String>,
+        #[serde(rename = "segundoApellido")]
+        segundo_apellido: Option<String>,
+        /// Fecha de nacimiento `YYYY-MM-DD` (solo cédula física).
+        #[serde(rename = "fechaNacimiento")]
+        fecha_nacimiento: Option<String>,
+        /// Vencimiento del documento `YYYY-MM-DD` (solo cédula física).
+        #[serde(rename = "fechaVencimiento")]
+        fecha_vencimiento: Option<String>,
+    },
+    /// Gafete físico (número solo o QR propio con tipo).
+    Gafete { numero: i32, tipo: Option<String> },
+    /// QR propio de una cita.
+    Cita { id: String },
+    /// QR de invitación firmada (`BRISAS:INV:...`), se verifica en el servicio de citas.
+    Invitacion { token: String },
+    /// QR del pase impreso al ingresar.
+    Ingreso { id: String },
+}
+
+// --------------------------------------------------------------------------
+// DTOs DE SALIDA
+// --------------------------------------------------------------------------
+
+/// Siguiente paso sugerido a la UI tras resolver una lectura.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AccionEscaneo {
+    ValidarIngresoContratista,
+    ValidarIngresoProveedor,
+    ValidarIngresoVisita,
+    /// Cédula desconocida: pre-llenar el alta de visitante.
+    RegistrarVisitante,
+    /// La persona o gafete tiene un ingreso abierto.
+    RegistrarSalida,
+    ProcesarCita,
+    /// Sin ingreso abierto: solo se muestra el estado del gafete o del ingreso.
+    ConsultarGafete,
+}
+
+/// Persona registrada que corresponde a la cédula leída.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonaEscaneada {
+    /// `contratista`, `proveedor` o `visitante`.
+    pub tipo: String,
+    pub id: String,
+    pub cedula: String,
+    pub nombre_completo: String,
+    pub empresa: Option<String>,
+}
+
+/// Contexto listo para validar a partir de una lectura del escáner.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanResolucion {
+    pub codigo: CodigoEscaneado,
+    pub accion: AccionEscaneo,
+    pub persona: Option<PersonaEscaneada>,
+    pub gafete: Option<GafeteResponse>,
+    pub ingreso_abierto: Option<IngresoResponse>,
+    pub cita: Option<CitaResponse>,
+}
@@ -41,6 +41,10 @@ pub enum ValidationReason {
     AlreadyInside,
     ExpiredDocuments,
     GafeteAlert,
+    PossibleBlacklistMatch,
+    CompanyBlacklisted,
+    PendingBlacklistApproval,
+    CompanyContractExpired,
 }
 
 /// Resultado de la ejecución del motor.
@@ -94,8 +98,18 @@ pub struct MotorContexto {
     pub ident_nombre: String,
     pub tipo_acceso: TipoAcceso,
     pub lista_negra: Option<InfoListaNegra>,
+    /// Bloqueos de otra cédula con un nombre parecido (requieren confirmación del guardia).
+    pub posibles_coincidencias: Vec<InfoCoincidenciaListaNegra>,
+    /// Bloqueo propuesto que aún espera aprobación (doble control).
+    pub bloqueo_propuesto: Option<InfoBloqueoPropuesto>,
+    /// Veto sobre la empresa a la que pertenece la persona.
+    pub empresa_vetada: Option<InfoEmpresaVetada>,
     pub ingreso_activo: Option<InfoIngresoActivoInt>,
+    /// Motivo si hoy está fuera del contrato de la empresa o de sus principales.
+    pub contrato_empresa: Option<String>,
     pub estado_autorizacion: EstadoAutorizacion,
+    /// Documentos exigidos faltantes o vencidos (ya reflejados en `estado_autorizacion`).
+    pub documentos_pendientes: Vec<String>,
     pub alerta_gafete: Option<String>,
 }
 
@@ -106,6 +120,31 @@ pub struct InfoListaNegra {
     pub severidad: NivelSeveridad,
 }
 
+/// Posible coincidencia por nombre con la lista negra.
+#[derive(Debug, Clone)]
+pub struct InfoCoincidenciaListaNegra {
+    pub cedula: String,
+    pub nombre_completo: String,
+    pub severidad: NivelSeveridad,
+    /// Similitud del nombre (0–1).
+    pub score: f64,
+}
+
+/// Bloqueo propuesto por un guardia y pendiente de aprobación.
+#[derive(Debug, Clone)]
+pub struct InfoBloqueoPropuesto {
+    pub motivo: String,
+    pub severidad: NivelSeveridad,
+}
+
+/// Detalle de veto de la empresa.
+#[derive(Debug, Clone)]
+pub struct InfoEmpresaVetada {
+    pub empresa_nombre: String,
+    pub motivo: String,
+    pub severidad: NivelSeveridad,
+}
+
 /// Detalle de permanencia actual.
 #[derive(Debug, Clone)]
 pub struct InfoIngresoActivoInt {
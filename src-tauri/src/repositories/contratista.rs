@@ -2,19 +2,27 @@ use super::traits::{
     AuditRepository, ContratistaRepository, EmpresaRepository, SecurityRepository,
     VehiculoRepository,
 };
+use crate::domain::lista_negra::NombrePersona;
 use crate::models::contratista::{
     Contratista, ContratistaCreateDTO, ContratistaFetched, ContratistaUpdateDTO, EstadoContratista,
 };
+use crate::models::documento_contratista::{DocumentoContratista, RequisitoDocumento};
 use crate::models::empresa::Empresa;
-use crate::models::lista_negra::BlockStatus;
+use crate::models::lista_negra::{
+    BlockStatus, CoincidenciaListaNegra, EmpresaVetadaStatus, PropuestaListaNegra,
+};
 use crate::models::vehiculo::Vehiculo;
+use crate::services::search_service::SearchService;
 use crate::services::surrealdb_service::SurrealDbError;
+use crate::services::{lista_negra_propuesta_service, lista_negra_service};
 use async_trait::async_trait;
+use std::sync::Arc;
 use surrealdb::RecordId;
 
 // Importaciones de Queries existentes
 use crate::db::surrealdb_audit_queries as audit_db;
 use crate::db::surrealdb_contratista_queries as db;
+use crate::db::surrealdb_documento_contratista_queries as doc_db;
 use crate::db::surrealdb_empresa_queries as empresa_db;
 use crate::db::surrealdb_lista_negra_queries as ln_db;
 use crate::db::surrealdb_vehiculo_queries as veh_db;
@@ -91,13 +99,37 @@ impl ContratistaRepository for SurrealContratistaRepository {
     async fn find_archived(&self) -> Result<Vec<ContratistaFetched>, SurrealDbError> {
         db::find_archived().await
     }
+
+    async fn find_documentos(
+        &self,
+        contratista: &RecordId,
+    ) -> Result<Vec<DocumentoContratista>, SurrealDbError> {
+        doc_db::find_by_contratista(contratista).await
+    }
+
+    async fn find_requisitos_documento(&self) -> Result<Vec<RequisitoDocumento>, SurrealDbError> {
+        doc_db::find_requisitos().await
+    }
+
+    async fn find_empresa(&self, id: &RecordId) -> Result<Option<Empresa>, SurrealDbError> {
+        empresa_db::find_by_id(id).await
+    }
 }
 
 // ================================================================
 // Auxiliary Repositories Implementations
 // ================================================================
 
-pub struct SurrealSecurityRepository;
+pub struct SurrealSecurityRepository {
+    /// Índice de búsqueda para acotar la coincidencia por nombre (sin él se compara todo).
+    search: Option<Arc<SearchService>>,
+}
+
+impl SurrealSecurityRepository {
+    pub const fn new(search: Option<Arc<SearchService>>) -> Self {
+        Self { search }
+    }
+}
 
 #[async_trait]
 impl SecurityRepository for SurrealSecurityRepository {
@@ -107,6 +139,42 @@ impl SecurityRepository for SurrealSecurityRepository {
     ) -> Result<BlockStatus, SurrealDbError> {
         ln_db::check_if_blocked_by_cedula(cedula).await
     }
+
+    async fn find_posibles_coincidencias(
+        &self,
+        persona: &NombrePersona<'_>,
+        cedula: &str,
+    ) -> Result<Vec<CoincidenciaListaNegra>, SurrealDbError> {
+        lista_negra_service::buscar_coincidencias_nombre(self.search.as_deref(), persona, cedula)
+            .await
+            .map_err(|e| SurrealDbError::Query(e.to_string()))
+    }
+
+    async fn check_empresa_vetada(
+        &self,
+        empresa: &RecordId,
+    ) -> Result<EmpresaVetadaStatus, SurrealDbError> {
+        ln_db::check_empresa_vetada(empresa).await.map(EmpresaVetadaStatus::from)
+    }
+
+    async fn check_bloqueo_propuesto(
+        &self,
+        cedula: &str,
+    ) -> Result<Option<PropuestaListaNegra>, SurrealDbError> {
+        lista_negra_propuesta_service::bloqueo_propuesto(cedula)
+            .await
+            .map_err(|e| SurrealDbError::Query(e.to_string()))
+    }
+
+    async fn registrar_coincidencia_descartada(
+        &self,
+        bloqueo_id: &str,
+        persona: &RecordId,
+        cedula: &str,
+        usuario: &RecordId,
+    ) -> Result<(), SurrealDbError> {
+        audit_db::insert_coincidencia_descartada(bloqueo_id, persona, cedula, usuario).await
+    }
 }
 
 pub struct SurrealEmpresaRepository;
@@ -169,4 +237,11 @@ impl AuditRepository for SurrealAuditRepository {
         )
         .await
     }
+
+    async fn ultimo_motivo_estado(
+        &self,
+        contratista_id: &str,
+    ) -> Result<Option<String>, SurrealDbError> {
+        audit_db::ultimo_motivo_estado(contratista_id).await
+    }
 }
@@ -1,29 +1,63 @@
+use crate::models::gafete::{ContextoMovimientoGafete, DeudaGafete};
 use crate::repositories::traits::GafeteRepository;
 use crate::services::gafete_service;
 use crate::services::surrealdb_service::SurrealDbError;
 use async_trait::async_trait;
+use surrealdb::RecordId;
 
 pub struct SurrealGafeteRepository;
 
 #[async_trait]
 impl GafeteRepository for SurrealGafeteRepository {
-    async fn is_disponible(&self, numero: i32, tipo: &str) -> Result<bool, SurrealDbError> {
-        gafete_service::is_gafete_disponible(numero, tipo)
+    async fn is_disponible(
+        &self,
+        numero: i32,
+        tipo: &str,
+        usuario: &RecordId,
+    ) -> Result<bool, SurrealDbError> {
+        gafete_service::is_gafete_disponible(numero, tipo, Some(usuario))
             .await
             .map_err(|e| SurrealDbError::Query(e.to_string()))
     }
 
-    async fn marcar_en_uso(&self, numero: i32, tipo: &str) -> Result<(), SurrealDbError> {
-        gafete_service::marcar_en_uso(numero, tipo)
+    async fn marcar_en_uso(
+        &self,
+        numero: i32,
+        tipo: &str,
+        contexto: ContextoMovimientoGafete,
+    ) -> Result<(), SurrealDbError> {
+        gafete_service::marcar_en_uso(numero, tipo, contexto)
             .await
             .map_err(|e| SurrealDbError::Query(e.to_string()))?;
         Ok(())
     }
 
-    async fn liberar(&self, numero: i32, tipo: &str) -> Result<(), SurrealDbError> {
-        gafete_service::liberar_gafete(numero, tipo)
+    async fn liberar(
+        &self,
+        numero: i32,
+        tipo: &str,
+        contexto: ContextoMovimientoGafete,
+    ) -> Result<(), SurrealDbError> {
+        gafete_service::liberar_gafete(numero, tipo, contexto)
             .await
             .map_err(|e| SurrealDbError::Query(e.to_string()))?;
         Ok(())
     }
+
+    async fn registrar_no_devuelto(
+        &self,
+        numero: i32,
+        tipo: &str,
+        contexto: ContextoMovimientoGafete,
+    ) -> Result<(), SurrealDbError> {
+        gafete_service::registrar_no_devuelto(numero, tipo, contexto)
+            .await
+            .map_err(|e| SurrealDbError::Query(e.to_string()))
+    }
+
+    async fn deudas_pendientes(&self, cedula: &str) -> Result<Vec<DeudaGafete>, SurrealDbError> {
+        gafete_service::get_deudas_by_cedula(cedula)
+            .await
+            .map_err(|e| SurrealDbError::Query(e.to_string()))
+    }
 }
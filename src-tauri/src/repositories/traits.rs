@@ -1,7 +1,9 @@
 use crate::models::contratista::{
     Contratista, ContratistaCreateDTO, ContratistaFetched, ContratistaUpdateDTO, EstadoContratista,
 };
+use crate::models::documento_contratista::{DocumentoContratista, RequisitoDocumento};
 use crate::models::empresa::Empresa;
+use crate::models::gafete::{ContextoMovimientoGafete, DeudaGafete};
 use crate::models::ingreso::{
     IngresoContratista, IngresoContratistaCreateDTO, IngresoContratistaFetched,
 };
@@ -38,6 +40,15 @@ pub trait ContratistaRepository: Send + Sync {
     async fn delete(&self, id: &RecordId) -> Result<(), SurrealDbError>;
     async fn restore(&self, id: &RecordId) -> Result<(), SurrealDbError>;
     async fn find_archived(&self) -> Result<Vec<ContratistaFetched>, SurrealDbError>;
+    /// Documentos registrados del contratista (inducción, pólizas, certificados...).
+    async fn find_documentos(
+        &self,
+        contratista: &RecordId,
+    ) -> Result<Vec<DocumentoContratista>, SurrealDbError>;
+    /// Reglas de documentos requeridos para ingresar.
+    async fn find_requisitos_documento(&self) -> Result<Vec<RequisitoDocumento>, SurrealDbError>;
+    /// Empresa por ID (para recorrer la cadena de contratistas principales).
+    async fn find_empresa(&self, id: &RecordId) -> Result<Option<Empresa>, SurrealDbError>;
 }
 
 #[async_trait]
@@ -46,6 +57,30 @@ pub trait SecurityRepository: Send + Sync {
         &self,
         cedula: &str,
     ) -> Result<crate::models::lista_negra::BlockStatus, SurrealDbError>;
+    /// Bloqueos vigentes de otra cédula con un nombre parecido al de la persona.
+    async fn find_posibles_coincidencias(
+        &self,
+        persona: &crate::domain::lista_negra::NombrePersona<'_>,
+        cedula: &str,
+    ) -> Result<Vec<crate::models::lista_negra::CoincidenciaListaNegra>, SurrealDbError>;
+    /// Veto activo sobre la empresa de la persona.
+    async fn check_empresa_vetada(
+        &self,
+        empresa: &RecordId,
+    ) -> Result<crate::models::lista_negra::EmpresaVetadaStatus, SurrealDbError>;
+    /// Bloqueo propuesto para la cédula que aún espera aprobación.
+    async fn check_bloqueo_propuesto(
+        &self,
+        cedula: &str,
+    ) -> Result<Option<crate::models::lista_negra::PropuestaListaNegra>, SurrealDbError>;
+    /// Deja constancia de que el usuario descartó una posible coincidencia por nombre.
+    async fn registrar_coincidencia_descartada(
+        &self,
+        bloqueo_id: &str,
+        persona: &RecordId,
+        cedula: &str,
+        usuario: &RecordId,
+    ) -> Result<(), SurrealDbError>;
 }
 
 #[async_trait]
@@ -81,6 +116,12 @@ pub trait AuditRepository: Send + Sync {
         usuario_id: Option<&str>,
         motivo: &str,
     ) -> Result<(), SurrealDbError>;
+
+    /// Motivo del cambio de estado más reciente del contratista.
+    async fn ultimo_motivo_estado(
+        &self,
+        contratista_id: &str,
+    ) -> Result<Option<String>, SurrealDbError>;
 }
 
 #[async_trait]
@@ -117,7 +158,31 @@ pub trait IngresoContratistaRepository: Send + Sync {
 
 #[async_trait]
 pub trait GafeteRepository: Send + Sync {
-    async fn is_disponible(&self, numero: i32, tipo: &str) -> Result<bool, SurrealDbError>;
-    async fn marcar_en_uso(&self, numero: i32, tipo: &str) -> Result<(), SurrealDbError>;
-    async fn liberar(&self, numero: i32, tipo: &str) -> Result<(), SurrealDbError>;
+    /// Disponible para `usuario`: sus propias reservas no bloquean el gafete.
+    async fn is_disponible(
+        &self,
+        numero: i32,
+        tipo: &str,
+        usuario: &RecordId,
+    ) -> Result<bool, SurrealDbError>;
+    async fn marcar_en_uso(
+        &self,
+        numero: i32,
+        tipo: &str,
+        contexto: ContextoMovimientoGafete,
+    ) -> Result<(), SurrealDbError>;
+    async fn liberar(
+        &self,
+        numero: i32,
+        tipo: &str,
+        contexto: ContextoMovimientoGafete,
+    ) -> Result<(), SurrealDbError>;
+    async fn registrar_no_devuelto(
+        &self,
+        numero: i32,
+        tipo: &str,
+        contexto: ContextoMovimientoGafete,
+    ) -> Result<(), SurrealDbError>;
+    /// Gafetes que la persona adeuda (libro `deuda_gafete`).
+    async fn deudas_pendientes(&self, cedula: &str) -> Result<Vec<DeudaGafete>, SurrealDbError>;
 }
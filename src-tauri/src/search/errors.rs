@@ -28,6 +28,9 @@ pub enum SearchError {
 
     #[error("Error de base de datos: {0}")]
     DatabaseError(String),
+
+    #[error("Ya hay una reconstrucción del índice en curso")]
+    RebuildInProgress,
 }
 
 impl From<std::io::Error> for SearchError {
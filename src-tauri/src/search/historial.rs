@@ -0,0 +1,497 @@
+//! # Historial: Índice Full-Text de Movimientos (Ingresos)
+//!
+//! Índice Tantivy separado del índice global de personas. Indexa cada registro de
+//! `ingreso_contratista`, `ingreso_proveedor` e `ingreso_visita` con sus textos libres
+//! (observaciones, motivo, anfitrión, área visitada, placa) y la fecha de ingreso como campo
+//! `FAST`, para consultas de investigación del tipo "¿quién vino a ver a Ing. Rodríguez en
+//! marzo?".
+//!
+//! ## Tokenización
+//! Los campos de texto usan el tokenizer `es_folding` (minúsculas + eliminación de tildes),
+//! de modo que "rodriguez" encuentra "Rodríguez". Debe registrarse en cada `Index` abierto.
+//!
+//! ## Versionado
+//! Al ser un índice derivado, si la huella del schema en disco no coincide con la actual se
+//! descarta completo y se reconstruye desde `SurrealDB`.
+
+use crate::domain::common::datetime_to_iso;
+use crate::models::ingreso::IngresoHistorialRow;
+use crate::search::errors::SearchError;
+use crate::search::indexer::to_index_date;
+use crate::search::schema::schema_fingerprint;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::ops::Bound;
+use std::path::Path;
+use tantivy::collector::{Count, TopDocs};
+use tantivy::query::{AllQuery, BooleanQuery, Occur, Query, QueryParser, RangeQuery, TermSetQuery};
+use tantivy::schema::{
+    DateOptions, DateTimePrecision, Field, IndexRecordOption, Schema, TextFieldIndexing,
+    TextOptions, Value, FAST, STORED, STRING,
+};
+use tantivy::tokenizer::{
+    AsciiFoldingFilter, LowerCaser, RemoveLongFilter, SimpleTokenizer, TextAnalyzer,
+};
+use tantivy::{DateTime, DocAddress, Index, IndexWriter, Order, Searcher, TantivyDocument, Term};
+
+/// Tokenizer con plegado de acentos para textos en español.
+pub const FOLDING_TOKENIZER: &str = "es_folding";
+
+/// Nombres de campos del índice de historial
+pub mod fields {
+    pub const ID: &str = "id";
+    pub const TIPO: &str = "tipo";
+    pub const CEDULA: &str = "cedula";
+    pub const NOMBRE_COMPLETO: &str = "nombre_completo";
+    pub const EMPRESA_NOMBRE: &str = "empresa_nombre";
+    pub const ANFITRION: &str = "anfitrion";
+    pub const AREA_VISITADA: &str = "area_visitada";
+    pub const MOTIVO: &str = "motivo";
+    pub const OBSERVACIONES: &str = "observaciones";
+    pub const PLACA_VEHICULO: &str = "placa_vehiculo";
+    pub const FECHA_INGRESO: &str = "fecha_ingreso";
+    pub const FECHA_SALIDA: &str = "fecha_salida";
+    pub const SEARCH_TEXT: &str = "search_text";
+}
+
+/// Crea el schema del índice de historial de ingresos
+pub fn build_historial_schema() -> Schema {
+    let mut schema_builder = Schema::builder();
+
+    let texto = TextOptions::default().set_stored().set_indexing_options(
+        TextFieldIndexing::default()
+            .set_tokenizer(FOLDING_TOKENIZER)
+            .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+    );
+    let fecha = DateOptions::from(FAST)
+        .set_indexed()
+        .set_stored()
+        .set_precision(DateTimePrecision::Seconds);
+
+    schema_builder.add_text_field(fields::ID, STRING | STORED);
+    schema_builder.add_text_field(fields::TIPO, STRING | STORED);
+    schema_builder.add_text_field(fields::CEDULA, texto.clone());
+    schema_builder.add_text_field(fields::NOMBRE_COMPLETO, texto.clone());
+    schema_builder.add_text_field(fields::EMPRESA_NOMBRE, texto.clone());
+    schema_builder.add_text_field(fields::ANFITRION, texto.clone());
+    schema_builder.add_text_field(fields::AREA_VISITADA, texto.clone());
+    schema_builder.add_text_field(fields::MOTIVO, texto.clone());
+    schema_builder.add_text_field(fields::OBSERVACIONES, texto.clone());
+    schema_builder.add_text_field(fields::PLACA_VEHICULO, texto);
+    schema_builder.add_date_field(fields::FECHA_INGRESO, fecha);
+    schema_builder.add_date_field(fields::FECHA_SALIDA, STORED);
+
+    // Campo de búsqueda general (no almacenado)
+    let search_text = TextOptions::default().set_indexing_options(
+        TextFieldIndexing::default()
+            .set_tokenizer(FOLDING_TOKENIZER)
+            .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+    );
+    schema_builder.add_text_field(fields::SEARCH_TEXT, search_text);
+
+    schema_builder.build()
+}
+
+/// Registra el tokenizer `es_folding` en el índice.
+pub fn register_tokenizers(index: &Index) {
+    let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+        .filter(RemoveLongFilter::limit(40))
+        .filter(LowerCaser)
+        .filter(AsciiFoldingFilter)
+        .build();
+    index.tokenizers().register(FOLDING_TOKENIZER, analyzer);
+}
+
+/// Handles pre-cargados de los campos del índice de historial.
+#[derive(Clone, Copy)]
+pub struct HistorialFields {
+    pub id: Field,
+    pub tipo: Field,
+    pub cedula: Field,
+    pub nombre_completo: Field,
+    pub empresa_nombre: Field,
+    pub anfitrion: Field,
+    pub area_visitada: Field,
+    pub motivo: Field,
+    pub observaciones: Field,
+    pub placa_vehiculo: Field,
+    pub fecha_ingreso: Field,
+    pub fecha_salida: Field,
+    pub search_text: Field,
+}
+
+impl HistorialFields {
+    pub fn new(schema: &Schema) -> Result<Self, SearchError> {
+        let get = |name: &str| {
+            schema.get_field(name).map_err(|_| SearchError::FieldNotFound(name.to_string()))
+        };
+
+        Ok(Self {
+            id: get(fields::ID)?,
+            tipo: get(fields::TIPO)?,
+            cedula: get(fields::CEDULA)?,
+            nombre_completo: get(fields::NOMBRE_COMPLETO)?,
+            empresa_nombre: get(fields::EMPRESA_NOMBRE)?,
+            anfitrion: get(fields::ANFITRION)?,
+            area_visitada: get(fields::AREA_VISITADA)?,
+            motivo: get(fields::MOTIVO)?,
+            observaciones: get(fields::OBSERVACIONES)?,
+            placa_vehiculo: get(fields::PLACA_VEHICULO)?,
+            fecha_ingreso: get(fields::FECHA_INGRESO)?,
+            fecha_salida: get(fields::FECHA_SALIDA)?,
+            search_text: get(fields::SEARCH_TEXT)?,
+        })
+    }
+}
+
+/// Abre (o crea) el índice de historial.
+///
+/// Retorna `true` como segundo valor si el índice se creó vacío y debe poblarse completo.
+pub fn initialize_historial_index(index_path: &Path) -> Result<(Index, bool), SearchError> {
+    let schema = build_historial_schema();
+
+    if index_path.join("meta.json").exists() {
+        match Index::open_in_dir(index_path) {
+            Ok(index) if schema_fingerprint(&index.schema()) == schema_fingerprint(&schema) => {
+                register_tokenizers(&index);
+                return Ok((index, false));
+            }
+            Ok(_) => warn!("⚠️ Schema del índice de historial desactualizado, se reconstruirá"),
+            Err(e) => warn!("⚠️ No se pudo abrir el índice de historial ({e}), se reconstruirá"),
+        }
+
+        std::fs::remove_dir_all(index_path).map_err(|e| {
+            SearchError::IoError(format!("Error al descartar índice de historial: {e}"))
+        })?;
+    }
+
+    std::fs::create_dir_all(index_path).map_err(|e| {
+        SearchError::IoError(format!("Error al crear directorio de historial: {e}"))
+    })?;
+
+    info!("📂 Creando índice de historial en: {}", index_path.display());
+    let index = Index::create_in_dir(index_path, schema)
+        .map_err(|e| SearchError::TantivyError(format!("Error al crear índice: {e}")))?;
+    register_tokenizers(&index);
+
+    Ok((index, true))
+}
+
+/// Indexa (o reemplaza) un movimiento en el índice de historial.
+pub fn upsert_ingreso(
+    writer: &IndexWriter,
+    fields: &HistorialFields,
+    row: &IngresoHistorialRow,
+) -> Result<(), SearchError> {
+    let id = row.id.to_string();
+    writer.delete_term(Term::from_field_text(fields.id, &id));
+
+    let nombre_completo = [
+        Some(row.nombre.as_str()),
+        row.segundo_nombre.as_deref(),
+        Some(row.apellido.as_str()),
+        row.segundo_apellido.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ");
+
+    let opcionales = [
+        (fields.empresa_nombre, &row.empresa_nombre),
+        (fields.anfitrion, &row.anfitrion),
+        (fields.area_visitada, &row.area_visitada),
+        (fields.motivo, &row.motivo),
+        (fields.observaciones, &row.observaciones),
+        (fields.placa_vehiculo, &row.placa_vehiculo),
+    ];
+
+    let mut doc = TantivyDocument::default();
+    doc.add_text(fields.id, &id);
+    doc.add_text(fields.tipo, &row.tipo);
+    doc.add_text(fields.cedula, &row.cedula);
+    doc.add_text(fields.nombre_completo, &nombre_completo);
+
+    let mut search_text_parts = vec![row.cedula.clone(), nombre_completo];
+    for (field, valor) in opcionales {
+        if let Some(valor) = valor.as_deref().filter(|v| !v.trim().is_empty()) {
+            doc.add_text(field, valor);
+            search_text_parts.push(valor.to_string());
+        }
+    }
+    doc.add_text(fields.search_text, search_text_parts.join(" "));
+
+    if let Some(fecha) = to_index_date(&row.fecha_hora_ingreso) {
+        doc.add_date(fields.fecha_ingreso, fecha);
+    }
+    if let Some(fecha) = row.fecha_hora_salida.as_ref().and_then(to_index_date) {
+        doc.add_date(fields.fecha_salida, fecha);
+    }
+
+    writer
+        .add_document(doc)
+        .map_err(|e| SearchError::TantivyError(format!("Error al indexar ingreso: {e}")))?;
+
+    Ok(())
+}
+
+/// Quita un movimiento borrado del índice de historial.
+pub fn delete_ingreso(writer: &IndexWriter, fields: &HistorialFields, id: &str) {
+    writer.delete_term(Term::from_field_text(fields.id, id));
+}
+
+/// Filtros de una consulta sobre el historial.
+#[derive(Debug, Clone, Default)]
+pub struct HistorialFilters {
+    /// Tipos de ingreso (`contratista`, `proveedor`, `visita`). Vacío = todos.
+    pub tipos: Vec<String>,
+    /// Rango inclusivo sobre la fecha de ingreso.
+    pub desde: Option<DateTime>,
+    pub hasta: Option<DateTime>,
+}
+
+/// Movimiento encontrado en el historial.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IngresoHistorialHit {
+    pub id: String,
+    pub tipo: String,
+    pub score: f32,
+    pub cedula: Option<String>,
+    pub nombre_completo: Option<String>,
+    pub empresa_nombre: Option<String>,
+    pub anfitrion: Option<String>,
+    pub area_visitada: Option<String>,
+    pub motivo: Option<String>,
+    pub observaciones: Option<String>,
+    pub placa_vehiculo: Option<String>,
+    pub fecha_hora_ingreso: Option<String>,
+    pub fecha_hora_salida: Option<String>,
+}
+
+/// Página de resultados del historial.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistorialSearchResult {
+    pub resultados: Vec<IngresoHistorialHit>,
+    pub total: usize,
+}
+
+/// Busca en el historial de movimientos.
+///
+/// Con texto, los resultados se ordenan por relevancia; sin texto, por fecha de ingreso
+/// descendente (útil para listar todo un rango de fechas).
+pub fn search_historial(
+    index: &Index,
+    searcher: &Searcher,
+    fields: &HistorialFields,
+    query_str: Option<&str>,
+    filters: &HistorialFilters,
+    limit: usize,
+    offset: usize,
+) -> Result<HistorialSearchResult, SearchError> {
+    let texto = query_str.map(str::trim).filter(|q| !q.is_empty());
+    let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+
+    if let Some(texto) = texto {
+        let mut query_parser = QueryParser::for_index(
+            index,
+            vec![
+                fields.cedula,
+                fields.nombre_completo,
+                fields.empresa_nombre,
+                fields.anfitrion,
+                fields.area_visitada,
+                fields.motivo,
+                fields.observaciones,
+                fields.placa_vehiculo,
+                fields.search_text,
+            ],
+        );
+        query_parser.set_conjunction_by_default();
+        let query = query_parser
+            .parse_query(texto)
+            .map_err(|e| SearchError::QueryError(format!("Query inválido: {e}")))?;
+        clauses.push((Occur::Must, query));
+    } else {
+        clauses.push((Occur::Must, Box::new(AllQuery)));
+    }
+
+    if !filters.tipos.is_empty() {
+        let terms = filters.tipos.iter().map(|t| Term::from_field_text(fields.tipo, t));
+        clauses.push((Occur::Must, Box::new(TermSetQuery::new(terms))));
+    }
+
+    if filters.desde.is_some() || filters.hasta.is_some() {
+        clauses.push((
+            Occur::Must,
+            Box::new(RangeQuery::new_date_bounds(
+                fields::FECHA_INGRESO.to_string(),
+                filters.desde.map_or(Bound::Unbounded, Bound::Included),
+                filters.hasta.map_or(Bound::Unbounded, Bound::Included),
+            )),
+        ));
+    }
+
+    let query = BooleanQuery::new(clauses);
+    let top = TopDocs::with_limit(limit.max(1)).and_offset(offset);
+
+    let (docs, total): (Vec<(f32, DocAddress)>, usize) = if texto.is_some() {
+        searcher
+            .search(&query, &(top, Count))
+            .map_err(|e| SearchError::TantivyError(format!("Error búsqueda: {e}")))?
+    } else {
+        let (docs, total) = searcher
+            .search(
+                &query,
+                &(top.order_by_fast_field::<DateTime>(fields::FECHA_INGRESO, Order::Desc), Count),
+            )
+            .map_err(|e| SearchError::TantivyError(format!("Error búsqueda: {e}")))?;
+        (docs.into_iter().map(|(_, addr)| (0.0, addr)).collect(), total)
+    };
+
+    let mut resultados = Vec::with_capacity(docs.len());
+    for (score, doc_addr) in docs {
+        let doc: TantivyDocument =
+            searcher.doc(doc_addr).map_err(|e| SearchError::TantivyError(e.to_string()))?;
+
+        resultados.push(IngresoHistorialHit {
+            id: get_val(&doc, fields.id).unwrap_or_default(),
+            tipo: get_val(&doc, fields.tipo).unwrap_or_default(),
+            score,
+            cedula: get_val(&doc, fields.cedula),
+            nombre_completo: get_val(&doc, fields.nombre_completo),
+            empresa_nombre: get_val(&doc, fields.empresa_nombre),
+            anfitrion: get_val(&doc, fields.anfitrion),
+            area_visitada: get_val(&doc, fields.area_visitada),
+            motivo: get_val(&doc, fields.motivo),
+            observaciones: get_val(&doc, fields.observaciones),
+            placa_vehiculo: get_val(&doc, fields.placa_vehiculo),
+            fecha_hora_ingreso: get_date(&doc, fields.fecha_ingreso),
+            fecha_hora_salida: get_date(&doc, fields.fecha_salida),
+        });
+    }
+
+    Ok(HistorialSearchResult { resultados, total })
+}
+
+fn get_val(doc: &TantivyDocument, field: Field) -> Option<String> {
+    doc.get_first(field)?.as_str().map(std::string::ToString::to_string)
+}
+
+fn get_date(doc: &TantivyDocument, field: Field) -> Option<String> {
+    let fecha = doc.get_first(field)?.as_datetime()?;
+    let fecha = chrono::DateTime::from_timestamp(fecha.into_timestamp_secs(), 0)?;
+    Some(datetime_to_iso(&surrealdb::Datetime::from(fecha)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::indexer::{commit_index, get_index_writer};
+    use chrono::{TimeZone, Utc};
+    use surrealdb::RecordId;
+
+    fn row(
+        id: &str,
+        tipo: &str,
+        anfitrion: Option<&str>,
+        observaciones: Option<&str>,
+        fecha: chrono::DateTime<Utc>,
+    ) -> IngresoHistorialRow {
+        IngresoHistorialRow {
+            id: RecordId::from_table_key(format!("ingreso_{tipo}"), id),
+            tipo: tipo.to_string(),
+            cedula: "1-1111-1111".to_string(),
+            nombre: "María".to_string(),
+            apellido: "Pérez".to_string(),
+            segundo_nombre: None,
+            segundo_apellido: None,
+            empresa_nombre: None,
+            anfitrion: anfitrion.map(str::to_string),
+            area_visitada: None,
+            motivo: Some("Reunión".to_string()),
+            observaciones: observaciones.map(str::to_string),
+            placa_vehiculo: None,
+            fecha_hora_ingreso: surrealdb::Datetime::from(fecha),
+            fecha_hora_salida: None,
+            updated_at: surrealdb::Datetime::from(fecha),
+        }
+    }
+
+    fn setup() -> (Index, Searcher, HistorialFields) {
+        let index = Index::create_in_ram(build_historial_schema());
+        register_tokenizers(&index);
+        let fields = HistorialFields::new(&index.schema()).unwrap();
+        let mut writer = get_index_writer(&index).unwrap();
+
+        let marzo = Utc.with_ymd_and_hms(2025, 3, 12, 14, 0, 0).unwrap();
+        let abril = Utc.with_ymd_and_hms(2025, 4, 2, 9, 30, 0).unwrap();
+        let rows = [
+            row("v1", "visita", Some("Ing. Rodríguez"), None, marzo),
+            row("v2", "visita", Some("Ing. Rodríguez"), None, abril),
+            row(
+                "c1",
+                "contratista",
+                None,
+                Some("Ingresó con placa ABC-123, golpe en puerta"),
+                marzo,
+            ),
+        ];
+        for r in &rows {
+            upsert_ingreso(&writer, &fields, r).unwrap();
+        }
+        // Reindexar el mismo ingreso no debe duplicarlo
+        upsert_ingreso(&writer, &fields, &rows[0]).unwrap();
+        commit_index(&mut writer).unwrap();
+
+        let reader = index.reader().unwrap();
+        let searcher = reader.searcher();
+        (index, searcher, fields)
+    }
+
+    fn rango(desde: (i32, u32, u32), hasta: (i32, u32, u32)) -> HistorialFilters {
+        let ts = |(y, m, d): (i32, u32, u32), h, min, s| {
+            DateTime::from_timestamp_secs(
+                Utc.with_ymd_and_hms(y, m, d, h, min, s).unwrap().timestamp(),
+            )
+        };
+        HistorialFilters {
+            tipos: Vec::new(),
+            desde: Some(ts(desde, 0, 0, 0)),
+            hasta: Some(ts(hasta, 23, 59, 59)),
+        }
+    }
+
+    #[test]
+    fn test_anfitrion_sin_tildes_y_rango_de_fechas() {
+        let (index, searcher, fields) = setup();
+
+        let todos = HistorialFilters::default();
+        let res =
+            search_historial(&index, &searcher, &fields, Some("rodriguez"), &todos, 10, 0).unwrap();
+        assert_eq!(res.total, 2, "Debe ignorar tildes y no duplicar por upsert");
+
+        let marzo = rango((2025, 3, 1), (2025, 3, 31));
+        let res =
+            search_historial(&index, &searcher, &fields, Some("rodriguez"), &marzo, 10, 0).unwrap();
+        assert_eq!(res.total, 1);
+        assert_eq!(res.resultados[0].anfitrion.as_deref(), Some("Ing. Rodríguez"));
+    }
+
+    #[test]
+    fn test_observaciones_y_orden_por_fecha() {
+        let (index, searcher, fields) = setup();
+        let todos = HistorialFilters::default();
+
+        let res =
+            search_historial(&index, &searcher, &fields, Some("ABC-123"), &todos, 10, 0).unwrap();
+        assert_eq!(res.total, 1);
+        assert_eq!(res.resultados[0].tipo, "contratista");
+
+        // Sin texto: orden descendente por fecha de ingreso
+        let res = search_historial(&index, &searcher, &fields, None, &todos, 10, 0).unwrap();
+        assert_eq!(res.total, 3);
+        assert!(res.resultados[0].fecha_hora_ingreso.as_deref().unwrap().starts_with("2025-04-02"));
+    }
+}
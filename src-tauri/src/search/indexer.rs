@@ -5,59 +5,217 @@
 //! documentos Tantivy y escribirlos en el disco.
 //!
 //! ## Responsabilidades
-//! - Inicialización del índice (manejo de directorios y schema)
+//! - Inicialización del índice (manejo de directorios, generaciones y versión del schema)
 //! - Creación de `IndexWriter`
 //! - Conversión de structs Rust -> `TantivyDocument`
 //! - Operaciones atómicas de indexación (add, delete, update)
 
+use crate::domain::common::datetime_to_iso;
 use crate::models::contratista::{Contratista, ContratistaFetched};
 use crate::models::lista_negra::ListaNegra;
 use crate::models::proveedor::{Proveedor, ProveedorFetched};
 use crate::models::user::{User, UserFetched};
 use crate::search::errors::SearchError;
-use crate::search::schema::{build_search_schema, fields, FieldHandles};
-use log::{debug, info};
-use std::path::Path;
+use crate::search::historial::register_tokenizers;
+use crate::search::schema::{
+    build_search_schema, empresa_key, schema_fingerprint, FieldHandles, SCHEMA_VERSION,
+};
+use chrono::Utc;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use tantivy::schema::Schema;
 use tantivy::{Index, IndexWriter, TantivyDocument};
 
-/// Inicializa el índice de Tantivy
-pub fn initialize_index(index_path: &Path) -> Result<Index, SearchError> {
-    let schema = build_search_schema();
-    let meta_path = index_path.join("meta.json");
-
-    // Solo intentar abrir si existe meta.json (índice válido)
-    if meta_path.exists() {
-        let index = Index::open_in_dir(index_path)
-            .map_err(|e| SearchError::TantivyError(format!("Error al abrir índice: {e}")))?;
-
-        // Verificar si existe el campo "email" (indicador simple de migración)
-        if index.schema().get_field(fields::EMAIL).is_err() {
-            // Schema obsoleto, recrear índice
-            std::fs::remove_dir_all(index_path).map_err(|e| {
-                SearchError::IoError(format!("Error al eliminar índice obsoleto: {e}"))
-            })?;
-            std::fs::create_dir_all(index_path).map_err(|e| {
-                SearchError::IoError(format!("Error al crear directorio de índice: {e}"))
-            })?;
-
-            Index::create_in_dir(index_path, schema)
-                .map_err(|e| SearchError::TantivyError(format!("Error al crear índice: {e}")))
-        } else {
-            Ok(index)
+/// Archivo en la raíz del índice que apunta a la generación activa.
+const CURRENT_FILE: &str = "CURRENT";
+/// Archivo dentro de cada generación con la versión del schema con que fue construida.
+const SCHEMA_MARKER_FILE: &str = "schema_version.json";
+/// Prefijo de los subdirectorios de generación (`gen-<timestamp>`).
+const GENERATION_PREFIX: &str = "gen-";
+
+/// Versión y huella del schema persistidas junto a cada generación del índice.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaMarker {
+    pub version: u32,
+    pub fingerprint: String,
+}
+
+impl SchemaMarker {
+    /// Marca correspondiente al schema compilado en la aplicación.
+    pub fn current() -> Self {
+        Self { version: SCHEMA_VERSION, fingerprint: schema_fingerprint(&build_search_schema()) }
+    }
+}
+
+/// Estado del índice encontrado en disco al arrancar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexStatus {
+    /// El schema coincide con el de la aplicación.
+    Current,
+    /// Generación nueva y vacía (primer arranque o índice ilegible).
+    Created,
+    /// Schema distinto pero compatible: se sirve mientras se reconstruye en segundo plano.
+    Outdated,
+    /// Schema sin los campos que espera la aplicación: la generación anterior solo atiende
+    /// búsquedas de texto mientras se reconstruye en una generación nueva aún no activada.
+    Incompatible,
+}
+
+/// Índice abierto junto con su directorio de generación y su estado.
+pub struct OpenedIndex {
+    pub index: Index,
+    pub path: PathBuf,
+    pub status: IndexStatus,
+    /// Generación activa en disco con schema incompatible (solo con `Incompatible`).
+    pub previa: Option<(Index, PathBuf)>,
+}
+
+/// Inicializa el índice de Tantivy.
+///
+/// El directorio raíz contiene un archivo `CURRENT` con el nombre de la generación activa.
+/// Índices anteriores al versionado (archivos directamente en la raíz) se abren como
+/// generación heredada y se marcan `Outdated`. Si el schema en disco no es compatible con
+/// los campos que espera la aplicación, se crea una generación vacía sin activarla y la
+/// anterior se devuelve en `previa` para seguir sirviendo búsquedas hasta la reconstrucción.
+pub fn initialize_index(index_path: &Path) -> Result<OpenedIndex, SearchError> {
+    if !index_path.exists() {
+        std::fs::create_dir_all(index_path).map_err(|e| {
+            SearchError::IoError(format!("Error al crear directorio de índice: {e}"))
+        })?;
+    }
+
+    if let Some(generation) = resolve_active_generation(index_path) {
+        match Index::open_in_dir(&generation) {
+            Ok(index) => {
+                register_tokenizers(&index);
+                let status = if read_schema_marker(&generation) == Some(SchemaMarker::current()) {
+                    IndexStatus::Current
+                } else {
+                    IndexStatus::Outdated
+                };
+
+                if status == IndexStatus::Current || FieldHandles::new(&index.schema()).is_ok() {
+                    if status == IndexStatus::Outdated {
+                        warn!("⚠️ Schema del índice desactualizado en: {}", generation.display());
+                    }
+                    return Ok(OpenedIndex { index, path: generation, status, previa: None });
+                }
+
+                warn!("⚠️ Schema del índice incompatible, se reconstruirá en una generación nueva");
+                let (nuevo, path) = create_generation(index_path)?;
+                return Ok(OpenedIndex {
+                    index: nuevo,
+                    path,
+                    status: IndexStatus::Incompatible,
+                    previa: Some((index, generation)),
+                });
+            }
+            Err(e) => {
+                warn!("⚠️ No se pudo abrir el índice ({e}), se creará una generación vacía");
+            }
         }
-    } else {
-        // Crear directorio si no existe
-        if !index_path.exists() {
-            std::fs::create_dir_all(index_path).map_err(|e| {
-                SearchError::IoError(format!("Error al crear directorio de índice: {e}"))
-            })?;
+    }
+
+    let (index, path) = create_generation(index_path)?;
+    activate_generation(index_path, &path)?;
+    Ok(OpenedIndex { index, path, status: IndexStatus::Created, previa: None })
+}
+
+/// Resuelve el directorio de la generación activa, si existe alguna.
+fn resolve_active_generation(root: &Path) -> Option<PathBuf> {
+    if let Ok(name) = std::fs::read_to_string(root.join(CURRENT_FILE)) {
+        let generation = root.join(name.trim());
+        if generation.join("meta.json").exists() {
+            return Some(generation);
         }
+    }
+
+    // Índice heredado: los archivos de Tantivy viven directamente en la raíz.
+    if root.join("meta.json").exists() {
+        return Some(root.to_path_buf());
+    }
+
+    None
+}
+
+/// Lee la marca de schema de una generación (`None` si no existe o es ilegible).
+pub fn read_schema_marker(generation: &Path) -> Option<SchemaMarker> {
+    let content = std::fs::read_to_string(generation.join(SCHEMA_MARKER_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Persiste la marca del schema actual dentro de una generación.
+pub fn write_schema_marker(generation: &Path) -> Result<(), SearchError> {
+    let content = serde_json::to_string_pretty(&SchemaMarker::current())
+        .map_err(|e| SearchError::IoError(format!("Error al serializar versión: {e}")))?;
+    std::fs::write(generation.join(SCHEMA_MARKER_FILE), content)
+        .map_err(|e| SearchError::IoError(format!("Error al escribir versión del índice: {e}")))
+}
+
+/// Crea una generación nueva y vacía con el schema actual.
+pub fn create_generation(root: &Path) -> Result<(Index, PathBuf), SearchError> {
+    let name = format!("{GENERATION_PREFIX}{}", Utc::now().format("%Y%m%d%H%M%S%3f"));
+    let path = root.join(name);
+
+    std::fs::create_dir_all(&path).map_err(|e| {
+        SearchError::IoError(format!("Error al crear directorio de generación: {e}"))
+    })?;
+
+    info!("📂 Creando nuevo índice en: {}", path.display());
+    let index = Index::create_in_dir(&path, build_search_schema())
+        .map_err(|e| SearchError::TantivyError(format!("Error al crear índice: {e}")))?;
+    register_tokenizers(&index);
+    write_schema_marker(&path)?;
+
+    Ok((index, path))
+}
+
+/// Marca una generación como activa reemplazando `CURRENT` de forma atómica.
+pub fn activate_generation(root: &Path, generation: &Path) -> Result<(), SearchError> {
+    let name = generation
+        .file_name()
+        .ok_or(SearchError::IoError("Ruta de generación inválida".to_string()))?
+        .to_string_lossy()
+        .to_string();
+
+    let tmp_path = root.join(format!("{CURRENT_FILE}.tmp"));
+    std::fs::write(&tmp_path, name)
+        .map_err(|e| SearchError::IoError(format!("Error al escribir puntero de índice: {e}")))?;
+    std::fs::rename(&tmp_path, root.join(CURRENT_FILE))
+        .map_err(|e| SearchError::IoError(format!("Error al activar generación: {e}")))
+}
+
+/// Elimina generaciones que ya no están activas (incluidos archivos de un índice heredado).
+///
+/// Es best-effort: en Windows los archivos aún mapeados por lectores en curso no se pueden
+/// borrar, así que los fallos solo se registran y se reintentan en el próximo arranque.
+pub fn cleanup_stale_generations(root: &Path, active: &Path) {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        let result = if path.is_dir() {
+            if !name.starts_with(GENERATION_PREFIX) || path == active {
+                continue;
+            }
+            std::fs::remove_dir_all(&path)
+        } else {
+            // Los archivos sueltos en la raíz solo pertenecen a un índice heredado.
+            if active == root || name == CURRENT_FILE {
+                continue;
+            }
+            std::fs::remove_file(&path)
+        };
 
-        // Crear nuevo índice
-        info!("📂 Creando nuevo índice en: {}", index_path.display());
-        Index::create_in_dir(index_path, schema)
-            .map_err(|e| SearchError::TantivyError(format!("Error al crear índice: {e}")))
+        match result {
+            Ok(()) => debug!("🧹 Generación obsoleta eliminada: {}", path.display()),
+            Err(e) => warn!("⚠️ No se pudo eliminar {} (se reintentará): {e}", path.display()),
+        }
     }
 }
 
@@ -66,6 +224,13 @@ pub fn create_field_handles(schema: &Schema) -> Result<FieldHandles, SearchError
     FieldHandles::new(schema)
 }
 
+/// Convierte una fecha de `SurrealDB` al tipo de fecha del índice (precisión de segundos).
+pub(crate) fn to_index_date(fecha: &surrealdb::Datetime) -> Option<tantivy::DateTime> {
+    chrono::DateTime::parse_from_rfc3339(&datetime_to_iso(fecha))
+        .ok()
+        .map(|fecha| tantivy::DateTime::from_timestamp_secs(fecha.timestamp()))
+}
+
 /// Obtiene un writer para el índice
 pub fn get_index_writer(index: &Index) -> Result<IndexWriter, SearchError> {
     debug!("📝 Obteniendo IndexWriter (15MB budget)");
@@ -118,6 +283,11 @@ pub fn index_contratista(
     }
 
     doc.add_text(handles.empresa_nombre, empresa_nombre);
+    doc.add_text(handles.empresa_id, empresa_key(&contratista.empresa.to_string()));
+    doc.add_text(handles.estado, contratista.estado.as_str());
+    if let Some(vencimiento) = to_index_date(&contratista.fecha_vencimiento_praind) {
+        doc.add_date(handles.praind_vencimiento, vencimiento);
+    }
     doc.add_text(handles.search_text, &search_text);
 
     debug!("📥 Indexando contratista: {} ({})", contratista.nombre, contratista.id);
@@ -172,6 +342,11 @@ pub fn index_contratista_fetched(
     }
 
     doc.add_text(handles.empresa_nombre, empresa_nombre);
+    doc.add_text(handles.empresa_id, empresa_key(&contratista.empresa.id.to_string()));
+    doc.add_text(handles.estado, contratista.estado.as_str());
+    if let Some(vencimiento) = to_index_date(&contratista.fecha_vencimiento_praind) {
+        doc.add_date(handles.praind_vencimiento, vencimiento);
+    }
     doc.add_text(handles.search_text, &search_text);
 
     debug!("📥 Indexando contratista (fetched): {} ({})", contratista.nombre, contratista.id);
@@ -221,6 +396,7 @@ pub fn index_user(
         doc.add_text(handles.segundo_apellido, segundo_apellido);
     }
 
+    doc.add_text(handles.estado, if user.is_active { "activo" } else { "inactivo" });
     doc.add_text(handles.search_text, &search_text);
 
     debug!("📥 Indexando usuario: {} ({})", user.nombre, user.id);
@@ -270,6 +446,7 @@ pub fn index_user_fetched(
         doc.add_text(handles.segundo_apellido, segundo_apellido);
     }
 
+    doc.add_text(handles.estado, if user.is_active { "activo" } else { "inactivo" });
     doc.add_text(handles.search_text, &search_text);
 
     debug!("📥 Indexando usuario (fetched): {} ({})", user.nombre, user.id);
@@ -387,8 +564,23 @@ pub fn index_lista_negra(
         doc.add_text(handles.segundo_apellido, segundo_apellido);
     }
 
+    doc.add_text(handles.estado, "bloqueado");
+    if let Some(ref empresa_id) = lista_negra.empresa_id {
+        doc.add_text(handles.empresa_id, empresa_key(empresa_id));
+    }
     doc.add_text(handles.search_text, &search_text);
 
+    let nombre_plegado = [
+        Some(lista_negra.nombre.as_str()),
+        lista_negra.segundo_nombre.as_deref(),
+        Some(lista_negra.apellido.as_str()),
+        lista_negra.segundo_apellido.as_deref(),
+    ];
+    doc.add_text(
+        handles.nombre_plegado,
+        nombre_plegado.iter().flatten().copied().collect::<Vec<_>>().join(" "),
+    );
+
     writer
         .add_document(doc)
         .map_err(|e| SearchError::TantivyError(format!("Error al agregar lista negra: {e}")))?;
@@ -450,6 +642,8 @@ pub fn index_proveedor(
     }
 
     doc.add_text(handles.empresa_nombre, empresa_nombre);
+    doc.add_text(handles.empresa_id, empresa_key(&proveedor.empresa.to_string()));
+    doc.add_text(handles.estado, proveedor.estado.as_str().to_lowercase());
     doc.add_text(handles.search_text, &search_text);
 
     // Agregar al índice
@@ -503,6 +697,8 @@ pub fn index_proveedor_fetched(
     }
 
     doc.add_text(handles.empresa_nombre, empresa_nombre);
+    doc.add_text(handles.empresa_id, empresa_key(&proveedor.empresa.id.to_string()));
+    doc.add_text(handles.estado, proveedor.estado.as_str().to_lowercase());
     doc.add_text(handles.search_text, &search_text);
 
     // Agregar al índice
@@ -545,9 +741,14 @@ mod tests {
     use surrealdb::RecordId;
     use tantivy::Index;
 
+    fn temp_index_root() -> PathBuf {
+        std::env::temp_dir().join(format!("brisas_index_test_{}", uuid::Uuid::new_v4()))
+    }
+
     fn setup_test_index() -> (Index, FieldHandles) {
         let schema = build_search_schema();
         let index = Index::create_in_ram(schema.clone());
+        register_tokenizers(&index);
         let handles = FieldHandles::new(&schema).unwrap();
         (index, handles)
     }
@@ -624,4 +825,90 @@ mod tests {
         reader.reload().unwrap();
         assert_eq!(reader.searcher().num_docs(), 0);
     }
+
+    #[test]
+    fn test_initialize_index_versioned_generation() {
+        let root = temp_index_root();
+
+        let opened = initialize_index(&root).unwrap();
+        assert_eq!(opened.status, IndexStatus::Created);
+        assert_eq!(read_schema_marker(&opened.path), Some(SchemaMarker::current()));
+        let first_path = opened.path.clone();
+        drop(opened);
+
+        let reopened = initialize_index(&root).unwrap();
+        assert_eq!(reopened.status, IndexStatus::Current);
+        assert_eq!(reopened.path, first_path);
+        drop(reopened);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_initialize_index_detects_version_mismatch() {
+        let root = temp_index_root();
+        let opened = initialize_index(&root).unwrap();
+        let path = opened.path.clone();
+        drop(opened);
+
+        let stale = SchemaMarker { version: 0, fingerprint: "obsoleto".to_string() };
+        std::fs::write(path.join(SCHEMA_MARKER_FILE), serde_json::to_string(&stale).unwrap())
+            .unwrap();
+
+        let reopened = initialize_index(&root).unwrap();
+        assert_eq!(reopened.status, IndexStatus::Outdated);
+        assert_eq!(reopened.path, path);
+        drop(reopened);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_incompatible_index_keeps_previous_generation_active() {
+        use tantivy::schema::{Schema, STORED, STRING, TEXT};
+
+        let root = temp_index_root();
+        let viejo = root.join(format!("{GENERATION_PREFIX}viejo"));
+        std::fs::create_dir_all(&viejo).unwrap();
+        let mut builder = Schema::builder();
+        builder.add_text_field("id", STRING | STORED);
+        builder.add_text_field("search_text", TEXT);
+        drop(Index::create_in_dir(&viejo, builder.build()).unwrap());
+        activate_generation(&root, &viejo).unwrap();
+
+        let opened = initialize_index(&root).unwrap();
+        assert_eq!(opened.status, IndexStatus::Incompatible);
+        assert_ne!(opened.path, viejo);
+        assert_eq!(opened.previa.as_ref().map(|(_, p)| p.clone()), Some(viejo.clone()));
+        // La generación anterior sigue siendo la activa en disco hasta la reconstrucción
+        assert_eq!(resolve_active_generation(&root), Some(viejo));
+        drop(opened);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_legacy_index_is_replaced_by_generation() {
+        let root = temp_index_root();
+        std::fs::create_dir_all(&root).unwrap();
+        drop(Index::create_in_dir(&root, build_search_schema()).unwrap());
+
+        let legacy = initialize_index(&root).unwrap();
+        assert_eq!(legacy.status, IndexStatus::Outdated);
+        assert_eq!(legacy.path, root);
+        drop(legacy);
+
+        let (index, generation) = create_generation(&root).unwrap();
+        drop(index);
+        activate_generation(&root, &generation).unwrap();
+        cleanup_stale_generations(&root, &generation);
+
+        assert!(!root.join("meta.json").exists());
+        let reopened = initialize_index(&root).unwrap();
+        assert_eq!(reopened.status, IndexStatus::Current);
+        assert_eq!(reopened.path, generation);
+        drop(reopened);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
 }
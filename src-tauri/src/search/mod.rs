@@ -9,9 +9,11 @@
 //! - `schema`: Definición de la estructura de datos del índice.
 //! - `errors`: Manejo de errores específicos.
 //! - `connection`: Gestión del estado global y persistencia del índice.
+//! - `historial`: Índice separado del historial de ingresos (textos libres y fechas).
 
 pub mod connection;
 pub mod errors;
+pub mod historial;
 pub mod indexer;
 pub mod schema;
 pub mod searcher;
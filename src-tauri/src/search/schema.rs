@@ -8,9 +8,26 @@
 //! - `tipo`: Tipo de entidad (contratista, usuario, etc.)
 //! - `search_text`: Campo unificado para búsquedas globales
 //! - Otros metadatos específicos (cedula, nombre, empresa, etc.)
+//! - Campos de filtrado (`tipo`, `empresa_id`, `estado`, `praind_vencimiento`): indexados
+//!   como términos exactos y `FAST` para filtros estructurados y facetas
+//! - `nombre_plegado`: nombres y apellidos de la lista negra con el tokenizer `es_folding`,
+//!   para la coincidencia aproximada por nombre
+//!
+//! ## Versionado
+//! Cualquier cambio en `build_search_schema` debe incrementar `SCHEMA_VERSION`. Al arrancar,
+//! el indexer compara la versión y la huella del schema persistidas junto al índice y, si no
+//! coinciden, programa una reconstrucción completa.
 
 use crate::search::errors::SearchError;
-use tantivy::schema::{Field, Schema, STORED, STRING, TEXT};
+use crate::search::historial::FOLDING_TOKENIZER;
+use sha2::{Digest, Sha256};
+use tantivy::schema::{
+    Field, IndexRecordOption, Schema, TextFieldIndexing, TextOptions, FAST, INDEXED, STORED,
+    STRING, TEXT,
+};
+
+/// Versión lógica del schema. Incrementar al agregar, quitar o cambiar opciones de campos.
+pub const SCHEMA_VERSION: u32 = 4;
 
 /// Crea el schema para el índice de búsqueda
 pub fn build_search_schema() -> Schema {
@@ -19,8 +36,8 @@ pub fn build_search_schema() -> Schema {
     // ID único del documento
     schema_builder.add_text_field("id", STRING | STORED);
 
-    // Tipo de entidad
-    schema_builder.add_text_field("tipo", STRING | STORED);
+    // Tipo de entidad (FAST para facetas)
+    schema_builder.add_text_field("tipo", STRING | STORED | FAST);
 
     // Campos de búsqueda para contratistas
     schema_builder.add_text_field("cedula", TEXT | STORED);
@@ -34,9 +51,43 @@ pub fn build_search_schema() -> Schema {
     // Campo de búsqueda general (concatenación de todos los campos)
     schema_builder.add_text_field("search_text", TEXT);
 
+    // Campos de filtrado estructurado
+    schema_builder.add_text_field("empresa_id", STRING | STORED | FAST);
+    schema_builder.add_text_field("estado", STRING | STORED | FAST);
+    schema_builder.add_date_field("praind_vencimiento", INDEXED | STORED | FAST);
+
+    // Nombres plegados (sin tildes) para la coincidencia aproximada con la lista negra
+    let plegado = TextOptions::default().set_indexing_options(
+        TextFieldIndexing::default()
+            .set_tokenizer(FOLDING_TOKENIZER)
+            .set_index_option(IndexRecordOption::Basic),
+    );
+    schema_builder.add_text_field("nombre_plegado", plegado);
+
     schema_builder.build()
 }
 
+/// Huella SHA-256 del schema serializado.
+///
+/// Complementa a `SCHEMA_VERSION`: detecta cambios de schema aunque se haya olvidado
+/// incrementar la versión.
+pub fn schema_fingerprint(schema: &Schema) -> String {
+    let serialized = serde_json::to_vec(schema).unwrap_or_default();
+    hex::encode(Sha256::digest(&serialized))
+}
+
+/// Normaliza una referencia de empresa (`empresa:⟨abc⟩`, `empresa:abc` o `abc`) a su clave.
+///
+/// Los contratistas y proveedores guardan un `RecordId`, mientras que la lista negra guarda
+/// el id como texto; el campo `empresa_id` del índice siempre contiene solo la clave.
+pub fn empresa_key(raw: &str) -> String {
+    raw.trim()
+        .trim_start_matches("empresa:")
+        .trim_start_matches('⟨')
+        .trim_end_matches('⟩')
+        .to_string()
+}
+
 /// Nombres de campos como constantes para evitar typos
 pub mod fields {
     pub const ID: &str = "id";
@@ -49,6 +100,10 @@ pub mod fields {
     pub const EMPRESA_NOMBRE: &str = "empresa_nombre";
     pub const EMAIL: &str = "email";
     pub const SEARCH_TEXT: &str = "search_text";
+    pub const EMPRESA_ID: &str = "empresa_id";
+    pub const ESTADO: &str = "estado";
+    pub const PRAIND_VENCIMIENTO: &str = "praind_vencimiento";
+    pub const NOMBRE_PLEGADO: &str = "nombre_plegado";
 }
 
 /// Handles pre-cargados de todos los campos del schema.
@@ -65,6 +120,10 @@ pub struct FieldHandles {
     pub empresa_nombre: Field,
     pub email: Field,
     pub search_text: Field,
+    pub empresa_id: Field,
+    pub estado: Field,
+    pub praind_vencimiento: Field,
+    pub nombre_plegado: Field,
 }
 
 impl FieldHandles {
@@ -102,6 +161,18 @@ impl FieldHandles {
             search_text: schema
                 .get_field(fields::SEARCH_TEXT)
                 .map_err(|_| SearchError::FieldNotFound(fields::SEARCH_TEXT.to_string()))?,
+            empresa_id: schema
+                .get_field(fields::EMPRESA_ID)
+                .map_err(|_| SearchError::FieldNotFound(fields::EMPRESA_ID.to_string()))?,
+            estado: schema
+                .get_field(fields::ESTADO)
+                .map_err(|_| SearchError::FieldNotFound(fields::ESTADO.to_string()))?,
+            praind_vencimiento: schema
+                .get_field(fields::PRAIND_VENCIMIENTO)
+                .map_err(|_| SearchError::FieldNotFound(fields::PRAIND_VENCIMIENTO.to_string()))?,
+            nombre_plegado: schema
+                .get_field(fields::NOMBRE_PLEGADO)
+                .map_err(|_| SearchError::FieldNotFound(fields::NOMBRE_PLEGADO.to_string()))?,
         })
     }
 }
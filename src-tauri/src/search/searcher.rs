@@ -7,15 +7,25 @@
 //! - Mapeo de campos del schema para búsquedas optimizadas
 //! - Parsing y ejecución de queries full-text
 //! - Construcción de resultados (`SearchResultDto`)
+//! - Búsqueda estructurada con filtros, paginación y facetas (`search_structured`)
 //! - Búsquedas especializadas por tipo de entidad
 
 use crate::search::errors::SearchError;
-use crate::search::schema::fields;
+use crate::search::schema::{empresa_key, fields};
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use tantivy::collector::TopDocs;
-use tantivy::query::QueryParser;
-use tantivy::schema::{Field, Schema, Value};
-use tantivy::{Index, IndexReader, ReloadPolicy, TantivyDocument};
+use std::ops::Bound;
+use tantivy::aggregation::agg_req::Aggregations;
+use tantivy::aggregation::{AggregationCollector, AggregationLimits};
+use tantivy::collector::{Count, TopDocs};
+use tantivy::query::{
+    AllQuery, BooleanQuery, FuzzyTermQuery, Occur, Query, QueryParser, RangeQuery, TermQuery,
+    TermSetQuery,
+};
+use tantivy::schema::{Field, IndexRecordOption, Schema, Value};
+use tantivy::{
+    DateTime, DocAddress, Index, IndexReader, ReloadPolicy, Searcher, TantivyDocument, Term,
+};
 
 /// Cache de campos para evitar lookups por string en cada búsqueda
 pub struct SearchFields {
@@ -29,6 +39,10 @@ pub struct SearchFields {
     pub empresa_nombre: Field,
     pub email: Field,
     pub search_text: Field,
+    pub empresa_id: Field,
+    pub estado: Field,
+    pub praind_vencimiento: Field,
+    pub nombre_plegado: Field,
 }
 
 impl SearchFields {
@@ -64,6 +78,18 @@ impl SearchFields {
             search_text: schema
                 .get_field(fields::SEARCH_TEXT)
                 .expect("Falta campo search_text en schema (debe validarse al inicio)"),
+            empresa_id: schema
+                .get_field(fields::EMPRESA_ID)
+                .expect("Falta campo empresa_id en schema (debe validarse al inicio)"),
+            estado: schema
+                .get_field(fields::ESTADO)
+                .expect("Falta campo estado en schema (debe validarse al inicio)"),
+            praind_vencimiento: schema
+                .get_field(fields::PRAIND_VENCIMIENTO)
+                .expect("Falta campo praind_vencimiento en schema (debe validarse al inicio)"),
+            nombre_plegado: schema
+                .get_field(fields::NOMBRE_PLEGADO)
+                .expect("Falta campo nombre_plegado en schema (debe validarse al inicio)"),
         }
     }
 }
@@ -99,7 +125,310 @@ pub fn search_index(
     limit: usize,
 ) -> Result<Vec<SearchResultDto>, SearchError> {
     let searcher = reader.searcher();
+    let query = build_text_query(index, fields, query_str)?;
 
+    // Ejecutar búsqueda
+    let top_docs = searcher
+        .search(&query, &TopDocs::with_limit(limit))
+        .map_err(|e| SearchError::TantivyError(format!("Error búsqueda: {e}")))?;
+
+    collect_results(&searcher, fields, top_docs)
+}
+
+/// Búsqueda de texto sobre una generación con schema incompatible (anterior a la actual).
+///
+/// Solo se usa mientras se reconstruye el índice: los campos se resuelven por nombre y se
+/// omiten los que ese schema no tenga, así que no admite filtros ni facetas.
+pub fn search_legacy_index(
+    index: &Index,
+    reader: &IndexReader,
+    query_str: &str,
+    limit: usize,
+) -> Result<Vec<SearchResultDto>, SearchError> {
+    let schema = index.schema();
+    let campo = |nombre: &str| schema.get_field(nombre).ok();
+
+    let objetivo: Vec<Field> = [
+        fields::CEDULA,
+        fields::NOMBRE,
+        fields::SEGUNDO_NOMBRE,
+        fields::APELLIDO,
+        fields::SEGUNDO_APELLIDO,
+        fields::EMPRESA_NOMBRE,
+        fields::EMAIL,
+        fields::SEARCH_TEXT,
+        fields::TIPO,
+    ]
+    .into_iter()
+    .filter_map(campo)
+    .filter(|f| schema.get_field_entry(*f).is_indexed())
+    .collect();
+    if objetivo.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut query_parser = QueryParser::for_index(index, objetivo);
+    if let Some(search_text) = campo(fields::SEARCH_TEXT) {
+        query_parser.set_field_fuzzy(search_text, true, 1, true);
+    }
+    let query = query_parser
+        .parse_query(&query_str.to_lowercase())
+        .map_err(|e| SearchError::QueryError(format!("Query inválido: {e}")))?;
+
+    let searcher = reader.searcher();
+    let top_docs = searcher
+        .search(&query, &TopDocs::with_limit(limit))
+        .map_err(|e| SearchError::TantivyError(format!("Error búsqueda: {e}")))?;
+
+    let mut results = Vec::with_capacity(top_docs.len());
+    for (score, doc_addr) in top_docs {
+        let doc: TantivyDocument =
+            searcher.doc(doc_addr).map_err(|e| SearchError::TantivyError(e.to_string()))?;
+        let texto = |nombre: &str| campo(nombre).and_then(|f| get_val(&doc, f));
+
+        results.push(SearchResultDto {
+            id: texto(fields::ID).unwrap_or_default(),
+            tipo: texto(fields::TIPO).unwrap_or_default(),
+            score,
+            cedula: texto(fields::CEDULA),
+            nombre_completo: texto(fields::NOMBRE)
+                .zip(texto(fields::APELLIDO))
+                .map(|(nombre, apellido)| format!("{nombre} {apellido}")),
+            empresa_nombre: texto(fields::EMPRESA_NOMBRE),
+            email: texto(fields::EMAIL),
+        });
+    }
+
+    Ok(results)
+}
+
+/// Filtro de estado operativo para búsquedas estructuradas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EstadoFiltro {
+    /// Estado `activo` y sin PRAIND vencido.
+    Activo,
+    /// Estado `inactivo`.
+    Inactivo,
+    /// Contratistas bloqueados, proveedores suspendidos y entradas de lista negra.
+    Bloqueado,
+    /// PRAIND con fecha de vencimiento anterior al momento de la consulta.
+    Vencido,
+}
+
+/// Filtros estructurados aplicados sobre los campos `FAST`/indexados del schema.
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilters {
+    /// Tipos de entidad permitidos (`contratista`, `usuario`, ...). Vacío = todos.
+    pub tipos: Vec<String>,
+    /// Clave de la empresa (ver `empresa_key`).
+    pub empresa_id: Option<String>,
+    pub estado: Option<EstadoFiltro>,
+    /// Rango inclusivo sobre la fecha de vencimiento del PRAIND.
+    pub praind_desde: Option<DateTime>,
+    pub praind_hasta: Option<DateTime>,
+    /// `Some(true)`: solo los IDs de `ids_dentro`; `Some(false)`: excluirlos.
+    pub dentro: Option<bool>,
+    /// IDs de las personas actualmente dentro de las instalaciones (resuelto en `SurrealDB`).
+    pub ids_dentro: Vec<String>,
+}
+
+/// Conteo de documentos para un valor de faceta.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetCount {
+    pub valor: String,
+    /// Texto legible del valor (p.ej. nombre de la empresa), si se conoce.
+    pub etiqueta: Option<String>,
+    pub total: u64,
+}
+
+/// Resultado paginado de una búsqueda estructurada.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructuredSearchResult {
+    pub resultados: Vec<SearchResultDto>,
+    /// Total de documentos que cumplen la consulta (sin paginar).
+    pub total: usize,
+    pub por_tipo: Vec<FacetCount>,
+    pub por_empresa: Vec<FacetCount>,
+}
+
+/// Máximo de valores devueltos por faceta.
+const FACET_SIZE: u32 = 100;
+
+/// Búsqueda estructurada: texto libre opcional + filtros, con paginación y facetas.
+///
+/// Sin texto se listan todos los documentos que cumplan los filtros. Las facetas por tipo
+/// y por empresa se calculan sobre el conjunto completo filtrado, no solo sobre la página.
+pub fn search_structured(
+    index: &Index,
+    reader: &IndexReader,
+    fields: &SearchFields,
+    query_str: Option<&str>,
+    filters: &SearchFilters,
+    limit: usize,
+    offset: usize,
+) -> Result<StructuredSearchResult, SearchError> {
+    let searcher = reader.searcher();
+    let query = build_structured_query(index, fields, query_str, filters)?;
+
+    let aggregations: Aggregations = serde_json::from_value(serde_json::json!({
+        fields::TIPO: { "terms": { "field": fields::TIPO, "size": FACET_SIZE } },
+        fields::EMPRESA_ID: { "terms": { "field": fields::EMPRESA_ID, "size": FACET_SIZE } },
+    }))
+    .map_err(|e| SearchError::QueryError(format!("Agregación inválida: {e}")))?;
+    let facet_collector =
+        AggregationCollector::from_aggs(aggregations, AggregationLimits::default());
+
+    let (top_docs, total, facets) = searcher
+        .search(
+            &query,
+            &(TopDocs::with_limit(limit.max(1)).and_offset(offset), Count, facet_collector),
+        )
+        .map_err(|e| SearchError::TantivyError(format!("Error búsqueda: {e}")))?;
+
+    let facets = serde_json::to_value(facets)
+        .map_err(|e| SearchError::TantivyError(format!("Error al leer facetas: {e}")))?;
+
+    Ok(StructuredSearchResult {
+        resultados: collect_results(&searcher, fields, top_docs)?,
+        total,
+        por_tipo: parse_facet_buckets(&facets, fields::TIPO),
+        por_empresa: parse_facet_buckets(&facets, fields::EMPRESA_ID),
+    })
+}
+
+/// Construye la query booleana combinando texto libre y filtros estructurados.
+fn build_structured_query(
+    index: &Index,
+    fields: &SearchFields,
+    query_str: Option<&str>,
+    filters: &SearchFilters,
+) -> Result<BooleanQuery, SearchError> {
+    let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+
+    // Siempre debe existir al menos una cláusula Must: Tantivy no devuelve nada con solo MustNot.
+    match query_str.map(str::trim).filter(|q| !q.is_empty()) {
+        Some(texto) => clauses.push((Occur::Must, build_text_query(index, fields, texto)?)),
+        None => clauses.push((Occur::Must, Box::new(AllQuery))),
+    }
+
+    if !filters.tipos.is_empty() {
+        clauses.push((Occur::Must, term_set(fields.tipo, filters.tipos.iter())));
+    }
+
+    if let Some(ref empresa_id) = filters.empresa_id {
+        clauses.push((Occur::Must, term(fields.empresa_id, &empresa_key(empresa_id))));
+    }
+
+    let ahora = DateTime::from_timestamp_secs(Utc::now().timestamp());
+    match filters.estado {
+        Some(EstadoFiltro::Activo) => {
+            clauses.push((Occur::Must, term(fields.estado, "activo")));
+            clauses.push((Occur::MustNot, praind_range(Bound::Unbounded, Bound::Excluded(ahora))));
+        }
+        Some(EstadoFiltro::Inactivo) => {
+            clauses.push((Occur::Must, term(fields.estado, "inactivo")));
+        }
+        Some(EstadoFiltro::Bloqueado) => {
+            clauses
+                .push((Occur::Must, term_set(fields.estado, ["bloqueado", "suspendido"].iter())));
+        }
+        Some(EstadoFiltro::Vencido) => {
+            clauses.push((Occur::Must, praind_range(Bound::Unbounded, Bound::Excluded(ahora))));
+        }
+        None => {}
+    }
+
+    if filters.praind_desde.is_some() || filters.praind_hasta.is_some() {
+        let desde = filters.praind_desde.map_or(Bound::Unbounded, Bound::Included);
+        let hasta = filters.praind_hasta.map_or(Bound::Unbounded, Bound::Included);
+        clauses.push((Occur::Must, praind_range(desde, hasta)));
+    }
+
+    match filters.dentro {
+        Some(true) => clauses.push((Occur::Must, term_set(fields.id, filters.ids_dentro.iter()))),
+        Some(false) if !filters.ids_dentro.is_empty() => {
+            clauses.push((Occur::MustNot, term_set(fields.id, filters.ids_dentro.iter())));
+        }
+        _ => {}
+    }
+
+    Ok(BooleanQuery::new(clauses))
+}
+
+/// Busca solo en contratistas (Optimizado)
+pub fn search_contratistas(
+    index: &Index,
+    reader: &IndexReader,
+    fields: &SearchFields, // Cache de campos inyectada
+    query_str: &str,
+    limit: usize,
+) -> Result<Vec<SearchResultDto>, SearchError> {
+    // Agregar filtro de tipo
+    let filtered_query = format!("+tipo:contratista +({query_str})");
+    search_index(index, reader, fields, &filtered_query, limit)
+}
+
+/// Candidatos de la lista negra cuyo nombre se parece a los términos dados.
+///
+/// Cada término es un token ya plegado (sin tildes, en minúsculas) con los errores de
+/// tipeo que tolera. Los documentos que coinciden con más términos aparecen primero; el
+/// puntaje definitivo de la coincidencia lo calcula la capa de dominio.
+pub fn search_lista_negra_por_nombre(
+    reader: &IndexReader,
+    fields: &SearchFields,
+    terminos: &[(String, u8)],
+    limit: usize,
+) -> Result<Vec<SearchResultDto>, SearchError> {
+    if terminos.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let nombres: Vec<(Occur, Box<dyn Query>)> = terminos
+        .iter()
+        .map(|(token, distancia)| {
+            let term = Term::from_field_text(fields.nombre_plegado, token);
+            (Occur::Should, Box::new(FuzzyTermQuery::new(term, *distancia, true)) as Box<dyn Query>)
+        })
+        .collect();
+
+    let query = BooleanQuery::new(vec![
+        (Occur::Must, term(fields.tipo, "lista_negra")),
+        (Occur::Must, Box::new(BooleanQuery::new(nombres))),
+    ]);
+
+    let searcher = reader.searcher();
+    let top_docs = searcher
+        .search(&query, &TopDocs::with_limit(limit))
+        .map_err(|e| SearchError::TantivyError(format!("Error búsqueda lista negra: {e}")))?;
+
+    collect_results(&searcher, fields, top_docs)
+}
+
+/// Cuenta los documentos indexados de un tipo de entidad (`contratista`, `usuario`, etc.).
+pub fn count_by_tipo(
+    reader: &IndexReader,
+    fields: &SearchFields,
+    tipo: &str,
+) -> Result<u64, SearchError> {
+    let searcher = reader.searcher();
+    let query = TermQuery::new(Term::from_field_text(fields.tipo, tipo), IndexRecordOption::Basic);
+
+    let count = searcher
+        .search(&query, &Count)
+        .map_err(|e| SearchError::TantivyError(format!("Error al contar documentos: {e}")))?;
+
+    Ok(count as u64)
+}
+
+/// Query full-text sobre los campos de texto (fuzzy en `search_text`).
+fn build_text_query(
+    index: &Index,
+    fields: &SearchFields,
+    query_str: &str,
+) -> Result<Box<dyn Query>, SearchError> {
     // 1. Usar campos pre-calculated (Cero costo de lookup)
     let search_target_fields = vec![
         fields.cedula,
@@ -120,15 +449,17 @@ pub fn search_index(
 
     // Parsear query (normalizado a minúsculas para asegurar coincidencia con tokenizer default)
     let query_normalized = query_str.to_lowercase();
-    let query = query_parser
+    query_parser
         .parse_query(&query_normalized)
-        .map_err(|e| SearchError::QueryError(format!("Query inválido: {e}")))?;
-
-    // Ejecutar búsqueda
-    let top_docs = searcher
-        .search(&query, &TopDocs::with_limit(limit))
-        .map_err(|e| SearchError::TantivyError(format!("Error búsqueda: {e}")))?;
+        .map_err(|e| SearchError::QueryError(format!("Query inválido: {e}")))
+}
 
+/// Carga los documentos de una página de resultados.
+fn collect_results(
+    searcher: &Searcher,
+    fields: &SearchFields,
+    top_docs: Vec<(f32, DocAddress)>,
+) -> Result<Vec<SearchResultDto>, SearchError> {
     let mut results = Vec::with_capacity(top_docs.len());
 
     for (score, doc_addr) in top_docs {
@@ -150,17 +481,38 @@ pub fn search_index(
     Ok(results)
 }
 
-/// Busca solo en contratistas (Optimizado)
-pub fn search_contratistas(
-    index: &Index,
-    reader: &IndexReader,
-    fields: &SearchFields, // Cache de campos inyectada
-    query_str: &str,
-    limit: usize,
-) -> Result<Vec<SearchResultDto>, SearchError> {
-    // Agregar filtro de tipo
-    let filtered_query = format!("+tipo:contratista +({query_str})");
-    search_index(index, reader, fields, &filtered_query, limit)
+fn term(field: Field, value: &str) -> Box<dyn Query> {
+    Box::new(TermQuery::new(Term::from_field_text(field, value), IndexRecordOption::Basic))
+}
+
+fn term_set<'a, S: AsRef<str> + 'a>(
+    field: Field,
+    values: impl Iterator<Item = &'a S>,
+) -> Box<dyn Query> {
+    Box::new(TermSetQuery::new(values.map(|v| Term::from_field_text(field, v.as_ref()))))
+}
+
+fn praind_range(desde: Bound<DateTime>, hasta: Bound<DateTime>) -> Box<dyn Query> {
+    Box::new(RangeQuery::new_date_bounds(fields::PRAIND_VENCIMIENTO.to_string(), desde, hasta))
+}
+
+/// Extrae los buckets de una agregación `terms` del JSON de resultados.
+fn parse_facet_buckets(facets: &serde_json::Value, name: &str) -> Vec<FacetCount> {
+    facets[name]["buckets"]
+        .as_array()
+        .map(|buckets| {
+            buckets
+                .iter()
+                .filter_map(|bucket| {
+                    Some(FacetCount {
+                        valor: bucket["key"].as_str()?.to_string(),
+                        etiqueta: None,
+                        total: bucket["doc_count"].as_u64()?,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 // Helpers optimizados (reciben Field u32, no string name)
@@ -190,15 +542,19 @@ fn build_full_name(doc: &TantivyDocument, fields: &SearchFields) -> Option<Strin
 mod tests {
     use super::*;
     use crate::models::contratista::{Contratista, EstadoContratista};
+    use crate::models::lista_negra::ListaNegra;
     use crate::models::user::User;
+    use crate::search::historial::register_tokenizers;
     use crate::search::indexer::{
-        commit_index, create_field_handles, get_index_writer, index_contratista, index_user,
+        commit_index, create_field_handles, get_index_writer, index_contratista, index_lista_negra,
+        index_user,
     };
     use crate::search::schema::build_search_schema;
 
     fn setup_test_index_with_data() -> (Index, IndexReader, SearchFields) {
         let schema = build_search_schema();
         let index = Index::create_in_ram(schema.clone());
+        register_tokenizers(&index);
         let handles = create_field_handles(&schema).unwrap();
         let mut writer = get_index_writer(&index).unwrap();
 
@@ -271,6 +627,38 @@ mod tests {
         assert!(!res.is_empty());
     }
 
+    #[test]
+    fn test_search_legacy_index() {
+        use tantivy::schema::{STORED, STRING, TEXT};
+
+        // Schema de una versión anterior: sin campos de filtrado ni nombres plegados
+        let mut builder = Schema::builder();
+        let id = builder.add_text_field(fields::ID, STRING | STORED);
+        let tipo = builder.add_text_field(fields::TIPO, STRING | STORED);
+        let nombre = builder.add_text_field(fields::NOMBRE, TEXT | STORED);
+        let apellido = builder.add_text_field(fields::APELLIDO, TEXT | STORED);
+        let search_text = builder.add_text_field(fields::SEARCH_TEXT, TEXT);
+        let index = Index::create_in_ram(builder.build());
+
+        let mut writer = get_index_writer(&index).unwrap();
+        let mut doc = TantivyDocument::default();
+        doc.add_text(id, "contratista:c1");
+        doc.add_text(tipo, "contratista");
+        doc.add_text(nombre, "Alexander");
+        doc.add_text(apellido, "Gomez");
+        doc.add_text(search_text, "123 Alexander Gomez Intel");
+        writer.add_document(doc).unwrap();
+        commit_index(&mut writer).unwrap();
+
+        let reader = get_index_reader(&index).unwrap();
+        reader.reload().unwrap();
+        let res = search_legacy_index(&index, &reader, "alexande", 10).unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].id, "contratista:c1");
+        assert_eq!(res[0].nombre_completo.as_deref(), Some("Alexander Gomez"));
+        assert_eq!(res[0].cedula, None);
+    }
+
     #[test]
     fn test_search_contratistas_filter() {
         let (index, reader, fields) = setup_test_index_with_data();
@@ -288,4 +676,119 @@ mod tests {
         let res = search_contratistas(&index, &reader, &fields, "Alexander", 10).unwrap();
         assert_eq!(res.len(), 1, "Should find Contractor 'Alexander'");
     }
+
+    #[test]
+    fn test_count_by_tipo() {
+        let (_index, reader, fields) = setup_test_index_with_data();
+
+        assert_eq!(count_by_tipo(&reader, &fields, "contratista").unwrap(), 1);
+        assert_eq!(count_by_tipo(&reader, &fields, "usuario").unwrap(), 1);
+        assert_eq!(count_by_tipo(&reader, &fields, "proveedor").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_search_structured_filters_and_facets() {
+        let (index, reader, fields) = setup_test_index_with_data();
+
+        // Sin texto ni filtros: todos los documentos, facetas sobre el total
+        let res =
+            search_structured(&index, &reader, &fields, None, &SearchFilters::default(), 1, 0)
+                .unwrap();
+        assert_eq!(res.total, 2);
+        assert_eq!(res.resultados.len(), 1, "La página respeta el límite");
+        assert_eq!(res.por_tipo.len(), 2);
+        assert_eq!(res.por_empresa.len(), 1);
+        assert_eq!(res.por_empresa[0].valor, "emp-1");
+
+        // Offset: la segunda página contiene el documento restante
+        let res =
+            search_structured(&index, &reader, &fields, None, &SearchFilters::default(), 1, 1)
+                .unwrap();
+        assert_eq!(res.resultados.len(), 1);
+
+        // Filtro por empresa (acepta el RecordId completo)
+        let filters =
+            SearchFilters { empresa_id: Some("empresa:⟨emp-1⟩".into()), ..Default::default() };
+        let res = search_structured(&index, &reader, &fields, None, &filters, 10, 0).unwrap();
+        assert_eq!(res.total, 1);
+        assert_eq!(res.resultados[0].tipo, "contratista");
+
+        // Filtro por tipo combinado con texto
+        let filters = SearchFilters { tipos: vec!["usuario".into()], ..Default::default() };
+        let res = search_structured(&index, &reader, &fields, Some("Alexander"), &filters, 10, 0)
+            .unwrap();
+        assert_eq!(res.total, 0);
+
+        // Bloqueados: ninguno en los datos de prueba
+        let filters = SearchFilters { estado: Some(EstadoFiltro::Bloqueado), ..Default::default() };
+        let res = search_structured(&index, &reader, &fields, None, &filters, 10, 0).unwrap();
+        assert_eq!(res.total, 0);
+
+        // Actualmente dentro: solo los IDs resueltos en base de datos
+        let filters = SearchFilters {
+            dentro: Some(true),
+            ids_dentro: vec![res_id(&index, &reader, &fields, "Doe")],
+            ..Default::default()
+        };
+        let res = search_structured(&index, &reader, &fields, None, &filters, 10, 0).unwrap();
+        assert_eq!(res.total, 1);
+        assert_eq!(res.resultados[0].tipo, "usuario");
+    }
+
+    #[test]
+    fn test_search_lista_negra_por_nombre() {
+        use chrono::Utc;
+        use surrealdb::RecordId;
+
+        let schema = build_search_schema();
+        let index = Index::create_in_ram(schema.clone());
+        register_tokenizers(&index);
+        let handles = create_field_handles(&schema).unwrap();
+        let mut writer = get_index_writer(&index).unwrap();
+
+        let ln = ListaNegra {
+            id: RecordId::from_table_key("lista_negra", "ln1"),
+            cedula: "789".into(),
+            nombre: "José".into(),
+            segundo_nombre: Some("Luis".into()),
+            apellido: "Rodríguez".into(),
+            segundo_apellido: Some("Peña".into()),
+            empresa_id: None,
+            empresa_nombre: None,
+            nivel_severidad: "ALTO".into(),
+            motivo_bloqueo: Some("Hurto".into()),
+            bloqueado_por: "user:u1".into(),
+            bloqueado_por_nombre: None,
+            is_active: true,
+            vigente_hasta: None,
+            proxima_revision: None,
+            ultima_revision: None,
+            revisado_por: None,
+            sitio_origen: None,
+            created_at: surrealdb::Datetime::from(Utc::now()),
+            updated_at: surrealdb::Datetime::from(Utc::now()),
+        };
+        index_lista_negra(&mut writer, &handles, &ln).unwrap();
+        commit_index(&mut writer).unwrap();
+
+        let reader = get_index_reader(&index).unwrap();
+        let fields = SearchFields::new(&schema);
+        let buscar = |terminos: &[(&str, u8)]| {
+            let terminos: Vec<(String, u8)> =
+                terminos.iter().map(|(t, d)| ((*t).to_string(), *d)).collect();
+            search_lista_negra_por_nombre(&reader, &fields, &terminos, 10).unwrap()
+        };
+
+        // Sin tildes y con un error de tipeo
+        let res = buscar(&[("jose", 1), ("rodrigez", 2)]);
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].cedula.as_deref(), Some("789"));
+
+        assert!(buscar(&[("pedro", 1), ("mora", 1)]).is_empty());
+        assert!(buscar(&[]).is_empty());
+    }
+
+    fn res_id(index: &Index, reader: &IndexReader, fields: &SearchFields, query: &str) -> String {
+        search_index(index, reader, fields, query, 1).unwrap().remove(0).id
+    }
 }
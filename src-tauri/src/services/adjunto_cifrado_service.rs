@@ -0,0 +1,165 @@
+/// Servicio: Almacenamiento Cifrado de Adjuntos.
+///
+/// Infraestructura común de las evidencias de lista negra y los escaneos de documentos de
+/// contratista. El archivo se cifra con `ChaCha20Poly1305` y se guarda con un nombre
+/// ofuscado; la base de datos solo conserva los metadatos y el SHA-256 del original.
+///
+/// Responsabilidades:
+/// - Ingesta (Validación de tipo/tamaño -> SHA-256 -> `ChaCha20Poly1305` -> Disk).
+/// - Recuperación verificada (Disk -> Decrypt -> SHA-256).
+/// - Eliminación del archivo cifrado y rollback si falla el registro en la base de datos.
+use crate::commands::security_commands::{decrypt_data, encrypt_data};
+use crate::domain::adjunto;
+use crate::domain::errors::AdjuntoError;
+use log::error;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use surrealdb::RecordId;
+
+/// Largo máximo del nombre original conservado
+const NOMBRE_ORIGINAL_MAX_LEN: usize = 255;
+
+/// Directorio cifrado de un tipo de adjunto.
+pub struct AlmacenAdjuntos {
+    /// Subdirectorio dentro de los datos de la aplicación.
+    pub dir: &'static str,
+    /// Nombre del adjunto en los mensajes (`evidencia`, `escaneo`).
+    pub etiqueta: &'static str,
+    pub max_bytes: usize,
+}
+
+/// Adjunto ya cifrado en disco, pendiente de registrarse en la base de datos.
+pub struct AdjuntoGuardado {
+    pub archivo_uuid: String,
+    pub nombre_original: String,
+    pub tipo_mime: &'static str,
+    pub tamano_bytes: i64,
+    pub sha256: String,
+    archivo_path: PathBuf,
+}
+
+impl AdjuntoGuardado {
+    /// Rollback manual: borra el archivo cifrado si falla el registro en la base de datos.
+    pub fn descartar(&self) {
+        let _ = fs::remove_file(&self.archivo_path);
+    }
+}
+
+pub fn sha256_hex(contenido: &[u8]) -> String {
+    hex::encode(Sha256::digest(contenido))
+}
+
+/// Acepta `tabla:clave` o solo la clave.
+pub fn parse_id(id: &str, tabla: &str) -> Result<RecordId, AdjuntoError> {
+    if id.contains(':') {
+        id.parse().map_err(|_| AdjuntoError::Validation(format!("ID inválido: {id}")))
+    } else {
+        Ok(RecordId::from_table_key(tabla, id))
+    }
+}
+
+impl AlmacenAdjuntos {
+    /// Obtiene (y crea si hace falta) el directorio del almacén
+    fn base_path(&self) -> Result<PathBuf, AdjuntoError> {
+        let data_dir = dirs::data_local_dir().ok_or_else(|| {
+            AdjuntoError::IO("No se pudo obtener directorio de datos".to_string())
+        })?;
+
+        let path = data_dir.join("com.brisas.app").join(self.dir);
+
+        if !path.exists() {
+            fs::create_dir_all(&path)
+                .map_err(|e| AdjuntoError::IO(format!("Error creando directorio: {e}")))?;
+        }
+
+        Ok(path)
+    }
+
+    fn archivo_path(&self, archivo_uuid: &str) -> Result<PathBuf, AdjuntoError> {
+        Ok(self.base_path()?.join(format!("{archivo_uuid}.enc")))
+    }
+
+    /// Valida, cifra y guarda un archivo. El tamaño se comprueba antes de leerlo y el tipo
+    /// por su contenido, no por la extensión.
+    pub fn guardar(&self, file_path: &str) -> Result<AdjuntoGuardado, AdjuntoError> {
+        // 1. Validar tamaño (sin leer el archivo completo) y contenido
+        let ruta = Path::new(file_path);
+        let tamano = fs::metadata(ruta)
+            .map_err(|e| AdjuntoError::IO(format!("Error leyendo archivo: {e}")))?
+            .len();
+        adjunto::validar_tamano(tamano, self.max_bytes, self.etiqueta)?;
+        let contenido =
+            fs::read(ruta).map_err(|e| AdjuntoError::IO(format!("Error leyendo archivo: {e}")))?;
+        let tipo_mime = adjunto::validar_adjunto(&contenido, self.max_bytes, self.etiqueta)?;
+
+        // 2. Huella de integridad del original
+        let sha256 = sha256_hex(&contenido);
+
+        // 3. Cifrar y guardar con nombre ofuscado
+        let cifrado = encrypt_data(&contenido)
+            .map_err(|e| AdjuntoError::Internal(format!("Error de encriptación: {e}")))?;
+        let archivo_uuid = uuid::Uuid::now_v7().to_string();
+        let archivo_path = self.archivo_path(&archivo_uuid)?;
+        fs::write(&archivo_path, &cifrado)
+            .map_err(|e| AdjuntoError::IO(format!("Error escribiendo archivo cifrado: {e}")))?;
+
+        let nombre_original: String = ruta
+            .file_name()
+            .map_or_else(|| self.etiqueta.to_string(), |n| n.to_string_lossy().into_owned())
+            .chars()
+            .take(NOMBRE_ORIGINAL_MAX_LEN)
+            .collect();
+
+        Ok(AdjuntoGuardado {
+            archivo_uuid,
+            nombre_original,
+            tipo_mime,
+            tamano_bytes: i64::try_from(contenido.len()).unwrap_or(i64::MAX),
+            sha256,
+            archivo_path,
+        })
+    }
+
+    /// Descifra un adjunto y verifica que coincida con el SHA-256 registrado.
+    ///
+    /// # Argumentos
+    /// * `registro` - Id del registro dueño del adjunto, para mensajes y logs.
+    pub fn leer_verificado(
+        &self,
+        archivo_uuid: &str,
+        sha256: &str,
+        registro: &str,
+    ) -> Result<Vec<u8>, AdjuntoError> {
+        let cifrado = fs::read(self.archivo_path(archivo_uuid)?).map_err(|_| {
+            AdjuntoError::IO(format!(
+                "Archivo de {} {registro} no encontrado en disco",
+                self.etiqueta
+            ))
+        })?;
+
+        let contenido = decrypt_data(&cifrado).map_err(|e| {
+            AdjuntoError::Internal(format!("Error desencriptando {}: {e}", self.etiqueta))
+        })?;
+
+        if sha256_hex(&contenido) != sha256 {
+            error!(
+                "🚨 Archivo de {} {registro} no coincide con su SHA-256 registrado",
+                self.etiqueta
+            );
+            return Err(AdjuntoError::Integridad(registro.to_string()));
+        }
+
+        Ok(contenido)
+    }
+
+    /// Borra el archivo cifrado, si existe.
+    pub fn eliminar(&self, archivo_uuid: &str) -> Result<(), AdjuntoError> {
+        let archivo_path = self.archivo_path(archivo_uuid)?;
+        if archivo_path.exists() {
+            fs::remove_file(&archivo_path)
+                .map_err(|e| AdjuntoError::IO(format!("Error eliminando archivo: {e}")))?;
+        }
+        Ok(())
+    }
+}
@@ -8,9 +8,11 @@
 /// - Gestionar la resolución de alertas por parte de seguridad.
 use crate::db::surrealdb_alerta_queries as db;
 use crate::domain::errors::AlertaError;
+use crate::models::gafete::ContextoMovimientoGafete;
 use crate::models::ingreso::AlertaGafete;
 use crate::services::gafete_service;
 use log::{error, info, warn};
+use surrealdb::RecordId;
 
 /// Localiza una alerta específica para su auditoría o resolución.
 ///
@@ -115,6 +117,22 @@ pub async fn resolver(
 
     info!("Resolviendo alerta {id} (gafete: {gafete_numero}, tipo: {tipo_gafete})");
 
+    // Contexto para el historial del gafete (quién lo tenía y quién lo recupera)
+    let contexto = ContextoMovimientoGafete {
+        cedula: Some(alerta.cedula.clone()),
+        nombre: Some(alerta.nombre_completo.clone()),
+        ingreso: alerta
+            .ingreso_contratista
+            .clone()
+            .or_else(|| alerta.ingreso_proveedor.clone())
+            .or_else(|| alerta.ingreso_visita.clone()),
+        usuario: input.usuario_id.as_deref().and_then(|u| u.parse::<RecordId>().ok()),
+        notas: Some(input.notas.as_deref().map_or_else(
+            || "Devuelto al resolver la alerta".to_string(),
+            |n| format!("Devuelto al resolver la alerta: {n}"),
+        )),
+    };
+
     // 3. Resolver la alerta en BD
     db::resolver(input).await.map_err(|e| {
         error!("Error al resolver alerta {id}: {e}");
@@ -123,7 +141,7 @@ pub async fn resolver(
 
     // 4. Liberar el gafete para que vuelva al inventario
     if gafete_numero != 0 {
-        match gafete_service::liberar_gafete(gafete_numero, tipo_gafete).await {
+        match gafete_service::liberar_gafete(gafete_numero, tipo_gafete, contexto).await {
             Ok(()) => {
                 info!("Gafete {gafete_numero} liberado exitosamente tras resolver alerta {id}");
             }
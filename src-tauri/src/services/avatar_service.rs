@@ -14,6 +14,7 @@ use base64::{engine::general_purpose::STANDARD, Engine};
 use image::{DynamicImage, ImageReader};
 use std::fs;
 use std::path::PathBuf;
+use surrealdb::RecordId;
 
 /// Directorio donde se guardan los avatares encriptados
 const AVATAR_DIR: &str = "secure_avatars";
@@ -138,23 +139,40 @@ pub async fn get_avatar(user_id: &str) -> Result<String, UserError> {
         return Err(UserError::NotFound); // O manejar como Ok("") si UI lo prefiere vacio
     }
 
-    // 2. Construir la ruta del archivo
+    // 2. Leer y desencriptar el archivo
+    let decrypted = leer_avatar(&avatar_uuid)?;
+
+    // 3. Convertir a Base64
+    let b64 = STANDARD.encode(&decrypted);
+
+    Ok(b64)
+}
+
+/// Foto de una persona (contratista, proveedor o visitante) para el pase impreso.
+///
+/// Retorna `None` si el registro no tiene avatar asignado.
+pub async fn get_avatar_bytes(persona: &RecordId) -> Result<Option<Vec<u8>>, UserError> {
+    let avatar_uuid =
+        db::get_avatar_path_of(persona).await.map_err(|e| UserError::Database(e.to_string()))?;
+
+    if avatar_uuid.is_empty() {
+        return Ok(None);
+    }
+
+    leer_avatar(&avatar_uuid).map(Some)
+}
+
+/// Lee y desencripta el archivo ofuscado de un avatar.
+fn leer_avatar(avatar_uuid: &str) -> Result<Vec<u8>, UserError> {
     let base_path = get_avatar_base_path()?;
     let avatar_file_path = base_path.join(format!("{avatar_uuid}.enc"));
 
-    // 3. Leer archivo encriptado
     let encrypted = fs::read(&avatar_file_path).map_err(|_| {
         UserError::IO(format!("Archivo de avatar {avatar_uuid} no encontrado en disco"))
     })?;
 
-    // 4. Desencriptar
-    let decrypted = decrypt_data(&encrypted)
-        .map_err(|e| UserError::Internal(format!("Error desencriptando avatar: {e}")))?;
-
-    // 5. Convertir a Base64
-    let b64 = STANDARD.encode(&decrypted);
-
-    Ok(b64)
+    decrypt_data(&encrypted)
+        .map_err(|e| UserError::Internal(format!("Error desencriptando avatar: {e}")))
 }
 
 /// Borrado Seguro: Elimina el archivo cifrado y limpia la referencia en DB.
@@ -0,0 +1,121 @@
+//! # Servicio: Ciclo de Vida de Citas
+//!
+//! Mantiene el estado de las citas sincronizado con lo que ocurre en portería:
+//!
+//! - `pendiente` → `en_curso`: al procesar el ingreso (`cita_service::procesar_ingreso_cita`).
+//! - `en_curso` → `completada`: al registrar la salida del ingreso de visita vinculado.
+//! - `pendiente` → `vencida`: tarea periódica para citas cuyo inicio pasó hace más de
+//!   `citas.gracia_vencimiento_minutos` sin que el visitante se presentara (no-show).
+//!
+//! La misma tarea extiende la ventana móvil de las series recurrentes.
+//!
+//! También construye el resumen de inasistencias por anfitrión.
+
+use crate::db::surrealdb_cita_queries as db;
+use crate::domain::cita as domain;
+use crate::domain::common::{limites_dias_locales, parsear_fecha_simple};
+use crate::domain::errors::CitaError;
+use crate::models::cita::InasistenciasAnfitrion;
+use crate::services::cita_service;
+use chrono::{Duration, Utc};
+use log::{error, info};
+use surrealdb::RecordId;
+
+/// Intervalo de la tarea de vencimiento.
+const TICK_SECS: u64 = 300;
+
+// --------------------------------------------------------------------------
+// TRANSICIONES AUTOMÁTICAS
+// --------------------------------------------------------------------------
+
+/// Completa la cita vinculada a un ingreso de visita cuando se registra su salida.
+///
+/// No es fatal: un fallo aquí no debe impedir la salida del visitante.
+pub async fn al_registrar_salida_visita(ingreso_id: &RecordId) {
+    match db::completar_por_ingreso(ingreso_id).await {
+        Ok(citas) => {
+            for cita in citas {
+                info!("✅ Cita {} completada por salida del ingreso {ingreso_id}", cita.id);
+            }
+        }
+        Err(e) => {
+            error!("Error no fatal: no se pudo completar la cita del ingreso {ingreso_id}: {e}");
+        }
+    }
+}
+
+/// Marca como vencidas las citas pendientes que superaron el período de gracia.
+///
+/// # Retorno
+/// Cantidad de citas vencidas en esta pasada.
+pub async fn vencer_citas_pendientes() -> Result<usize, CitaError> {
+    let gracia = cita_service::config_citas().gracia_vencimiento_minutos;
+    let limite = Utc::now() - Duration::minutes(i64::from(gracia));
+
+    let vencidas = db::vencer_pendientes(surrealdb::Datetime::from(limite))
+        .await
+        .map_err(|e| CitaError::Database(e.to_string()))?;
+
+    if !vencidas.is_empty() {
+        info!("⌛ {} cita(s) marcadas como vencidas (gracia: {gracia} min)", vencidas.len());
+    }
+    Ok(vencidas.len())
+}
+
+/// Lanza la tarea de vencimiento en segundo plano durante toda la vida de la aplicación.
+///
+/// La primera pasada se ejecuta al arrancar para recuperar lo ocurrido con la app cerrada.
+pub fn start_lifecycle_job() {
+    tauri::async_runtime::spawn(async {
+        info!("⏰ Tarea de vencimiento de citas iniciada");
+        loop {
+            if let Err(e) = vencer_citas_pendientes().await {
+                error!("❌ Falló el vencimiento de citas: {e}");
+            }
+            if let Err(e) = cita_service::materializar_series().await {
+                error!("❌ Falló la generación de citas recurrentes: {e}");
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(TICK_SECS)).await;
+        }
+    });
+}
+
+// --------------------------------------------------------------------------
+// INASISTENCIAS
+// --------------------------------------------------------------------------
+
+/// Resumen de inasistencias por anfitrión para un rango YYYY-MM-DD (inclusivo).
+///
+/// `hasta` por defecto es igual a `desde`.
+pub async fn reporte_inasistencias(
+    desde: &str,
+    hasta: Option<&str>,
+) -> Result<Vec<InasistenciasAnfitrion>, CitaError> {
+    let inicio = parsear_fecha_simple(desde).map_err(|e| CitaError::Validation(e.to_string()))?;
+    let fin = hasta
+        .map(parsear_fecha_simple)
+        .transpose()
+        .map_err(|e| CitaError::Validation(e.to_string()))?
+        .unwrap_or(inicio);
+
+    if fin < inicio {
+        return Err(CitaError::Validation(
+            "La fecha final no puede ser anterior a la inicial".to_string(),
+        ));
+    }
+
+    let (inicio, fin) =
+        limites_dias_locales(inicio, fin).map_err(|e| CitaError::Validation(e.to_string()))?;
+    inasistencias_en_rango(&inicio, &fin).await
+}
+
+/// Resumen de inasistencias de las citas con inicio entre dos instantes RFC 3339.
+pub async fn inasistencias_en_rango(
+    inicio: &str,
+    fin: &str,
+) -> Result<Vec<InasistenciasAnfitrion>, CitaError> {
+    let citas =
+        db::find_en_rango(inicio, fin).await.map_err(|e| CitaError::Database(e.to_string()))?;
+
+    Ok(domain::resumir_inasistencias(citas.iter().map(|c| (c.anfitrion.as_deref(), &c.estado))))
+}
@@ -5,12 +5,32 @@
 /// - Agendar citas vinculando visitantes (existentes o nuevos).
 /// - Consultar la agenda diaria y estados de citas.
 /// - Procesar el ingreso físico de visitantes con cita previa.
+/// - Emitir y verificar invitaciones firmadas (QR) para presentar en garita.
+/// - Gestionar series recurrentes (alta, edición y cancelación a nivel de serie).
+/// - Controlar la capacidad por anfitrión y área, y ofrecer horarios libres.
+/// - Exportar e importar la agenda en formato iCalendar (`.ics`).
+use crate::config::manager::load_config;
+use crate::config::settings::CitaConfig;
 use crate::db::surrealdb_cita_queries as db;
+use crate::db::surrealdb_cita_serie_queries as serie_db;
+use crate::domain::cita as domain;
+use crate::domain::cita_serie as serie_domain;
+use crate::domain::common::{datetime_to_iso, limites_dias_locales, parsear_fecha_simple};
 use crate::domain::errors::CitaError;
-use crate::models::cita::{CitaCreateDTO, CitaResponse, CreateCitaInput};
+use crate::export::ical;
+use crate::models::cita::{
+    Cita, CitaCreateDTO, CitaFetched, CitaResponse, CitaSerie, CitaSerieCreateDTO,
+    CitaSerieResponse, CitaSerieUpdateDTO, CitaUpdateDTO, CreateCitaInput, CreateCitaSerieInput,
+    ErrorImportacionIcal, EstadoCita, EventoIcal, HorarioLibre, IcalExportResponse,
+    IcalImportResponse, InvitacionCita, InvitacionPdfData, InvitacionResponse,
+    UpdateCitaSerieInput,
+};
 use crate::models::visitante::CreateVisitanteInput;
-use crate::services::visitante_service;
+use crate::services::surrealdb_service::SurrealDbError;
+use crate::services::{keyring_service, visitante_service};
 use chrono::{Local, Utc};
+use log::{debug, info, warn};
+use std::collections::HashSet;
 use surrealdb::RecordId;
 
 // --------------------------------------------------------------------------
@@ -22,6 +42,8 @@ pub struct AgendarCitaParams {
     pub cita: CreateCitaInput,
     pub visitante_extra: Option<CreateVisitanteInput>,
     pub usuario_id: String,
+    /// `UID` del evento iCalendar de origen (solo importaciones).
+    pub uid_ical: Option<String>,
 }
 
 /// Agenda una nueva cita en el calendario institucional.
@@ -34,6 +56,7 @@ pub struct AgendarCitaParams {
 ///
 /// # Errores
 /// - `CitaError::Validation`: Si los datos de entrada no cumplen las reglas de dominio.
+/// - `CitaError::Conflicto`: Si el anfitrión o el área ya están a plena capacidad en ese horario.
 /// - `CitaError::Database`: Si ocurre un error de persistencia o red con `SurrealDB`.
 pub async fn agendar_cita(params: AgendarCitaParams) -> Result<CitaResponse, CitaError> {
     // 1. Validación de Dominio (Capa Pura)
@@ -41,52 +64,45 @@ pub async fn agendar_cita(params: AgendarCitaParams) -> Result<CitaResponse, Cit
 
     let usuario_id = parse_record_id(&params.usuario_id, "user")?;
 
-    // 2. Orquestación de Identidad del Visitante
-    let visitante_id: Option<RecordId> = if let Some(ref id_str) = params.cita.visitante_id {
-        if id_str.is_empty() {
-            None
-        } else {
-            Some(parse_record_id(id_str, "visitante")?)
-        }
-    } else {
-        None
-    };
+    // 2. Disponibilidad del anfitrión y del área (consulta previa para no dar de alta al
+    //    visitante en vano; el cupo definitivo se verifica al insertar)
+    let config = config_citas();
+    let inicio = parse_instante(&params.cita.fecha_cita)?;
+    let fin = inicio
+        + chrono::Duration::minutes(i64::from(
+            params.cita.duracion_minutos.unwrap_or(config.duracion_default_minutos),
+        ));
+    verificar_disponibilidad(
+        &config,
+        Some(&params.cita.anfitrion),
+        Some(&params.cita.area_visitada),
+        inicio,
+        fin,
+    )
+    .await?;
 
-    let final_visitante_id = if let Some(vid) = visitante_id {
-        Some(vid)
-    } else if let Some(v_input) = params.visitante_extra {
-        let existente = visitante_service::get_visitante_by_cedula(&v_input.cedula)
-            .await
-            .map_err(|e| CitaError::Database(e.to_string()))?;
+    // 3. Orquestación de Identidad del Visitante
+    let final_visitante_id =
+        resolver_visitante(params.cita.visitante_id.as_deref(), params.visitante_extra).await?;
 
-        if let Some(v) = existente {
-            Some(parse_record_id(&v.id, "visitante")?)
-        } else {
-            let nuevo = visitante_service::create_visitante(v_input)
-                .await
-                .map_err(|e| CitaError::Database(e.to_string()))?;
-            Some(parse_record_id(&nuevo.id, "visitante")?)
-        }
-    } else {
-        None
-    };
-
-    let fecha_inicio = parse_datetime(&params.cita.fecha_cita)?;
-
-    // 3. Persistencia
+    // 4. Persistencia
     let dto = CitaCreateDTO {
         visitante_id: final_visitante_id,
         usuario_id,
         motivo: params.cita.motivo,
-        fecha_inicio: fecha_inicio.clone(),
-        fecha_fin: fecha_inicio,
+        fecha_inicio: surrealdb::Datetime::from(inicio),
+        fecha_fin: surrealdb::Datetime::from(fin),
         anfitrion: Some(params.cita.anfitrion),
         area_visitada: Some(params.cita.area_visitada),
         visitante_nombre: params.cita.visitante_nombre,
         visitante_cedula: params.cita.visitante_cedula,
+        serie: None,
+        uid_ical: params.uid_ical,
     };
 
-    let nueva_cita = db::insert(dto).await.map_err(|e| CitaError::Database(e.to_string()))?;
+    let limites =
+        limites_capacidad(&config, dto.anfitrion.as_deref(), dto.area_visitada.as_deref());
+    let nueva_cita = db::insert(dto, limites).await.map_err(map_insert_error)?;
 
     let fetched = db::find_by_id_fetched(&nueva_cita.id)
         .await
@@ -174,7 +190,7 @@ pub async fn cancelar_cita(id_str: String) -> Result<(), CitaError> {
 /// * `usuario_id_str` - ID del oficial de seguridad que procesa la entrada.
 ///
 /// # Retorno
-/// La cita actualizada o error de procesamiento.
+/// La cita en estado `en_curso`; pasa a `completada` al registrar la salida del ingreso.
 ///
 /// # Errores
 /// - `CitaError::Unauthorized`: Si el usuario no tiene permisos de actualización.
@@ -231,24 +247,829 @@ pub async fn procesar_ingreso_cita(
         )),
     };
 
-    crate::services::ingreso_visita_service::registrar_ingreso(ingreso_input, usuario_id_str)
-        .await
-        .map_err(|e| CitaError::Database(e.to_string()))?;
+    let ingreso =
+        crate::services::ingreso_visita_service::registrar_ingreso(ingreso_input, usuario_id_str)
+            .await
+            .map_err(|e| CitaError::Database(e.to_string()))?;
 
-    let completed = db::completar(&cita_id)
+    // La cita queda en curso hasta que se registre la salida del ingreso vinculado
+    let ingreso_id = parse_record_id(&ingreso.id, "ingreso_visita")?;
+    let en_curso = db::iniciar(&cita_id, &ingreso_id)
         .await
         .map_err(|e| CitaError::Database(e.to_string()))?
         .ok_or(CitaError::Database(
-            "Fallo al actualizar el estado de la cita a 'completada'".to_string(),
+            "Fallo al actualizar el estado de la cita a 'en_curso'".to_string(),
         ))?;
 
-    Ok(CitaResponse::from_fetched(completed))
+    Ok(CitaResponse::from_fetched(en_curso))
+}
+
+// --------------------------------------------------------------------------
+// INVITACIONES FIRMADAS
+// --------------------------------------------------------------------------
+
+/// Genera la invitación firmada (token para QR + PDF) de una cita programada.
+///
+/// El token codifica el ID de la cita, la cédula del visitante y la ventana de validez,
+/// firmados con HMAC-SHA256 usando una clave guardada en el llavero del sistema.
+pub async fn generar_invitacion(cita_id_str: String) -> Result<InvitacionResponse, CitaError> {
+    let cita_id = parse_record_id(&cita_id_str, "cita")?;
+    let cita = db::find_by_id_fetched(&cita_id)
+        .await
+        .map_err(|e| CitaError::Database(e.to_string()))?
+        .ok_or(CitaError::NotFound)?;
+
+    if cita.estado != EstadoCita::Programada {
+        return Err(CitaError::Validation(format!(
+            "Solo se pueden emitir invitaciones para citas programadas (estado: {})",
+            cita.estado
+        )));
+    }
+
+    let visitante = cita.visitante_id.as_ref();
+    let cedula = visitante
+        .map(|v| v.cedula.clone())
+        .or_else(|| cita.visitante_cedula.clone())
+        .filter(|c| !c.trim().is_empty())
+        .ok_or_else(|| CitaError::Validation("La cita no tiene cédula de visitante".to_string()))?;
+    let visitante_nombre = visitante
+        .map(|v| format!("{} {}", v.nombre, v.apellido))
+        .or_else(|| cita.visitante_nombre.clone())
+        .unwrap_or_default();
+
+    let inicio = a_local(&cita.fecha_inicio)?;
+    let fin = a_local(&cita.fecha_fin)?.max(inicio);
+    let (valido_desde, valido_hasta) =
+        domain::ventana_invitacion(inicio.timestamp(), fin.timestamp());
+
+    let clave = keyring_service::get_or_create_invitation_key()
+        .map_err(|e| CitaError::Invitacion(e.to_string()))?;
+    let token = domain::firmar_invitacion(
+        &InvitacionCita {
+            cita_id: cita.id.to_string(),
+            cedula: cedula.clone(),
+            valido_desde,
+            valido_hasta,
+        },
+        &clave,
+    )?;
+
+    let formatear = |unix: i64| {
+        chrono::DateTime::from_timestamp(unix, 0)
+            .map(|f| f.with_timezone(&Local).format("%d/%m/%Y %H:%M").to_string())
+            .unwrap_or_default()
+    };
+
+    let pdf = renderizar_invitacion(InvitacionPdfData {
+        token: token.clone(),
+        visitante_nombre,
+        cedula,
+        fecha_cita: inicio.format("%d/%m/%Y %H:%M").to_string(),
+        anfitrion: cita.anfitrion.clone(),
+        area: cita.area_visitada.clone(),
+        motivo: cita.motivo.clone(),
+        valido_desde: formatear(valido_desde),
+        valido_hasta: formatear(valido_hasta),
+    })
+    .await?;
+
+    info!("✉️ Invitación firmada emitida para la cita {}", cita.id);
+
+    Ok(InvitacionResponse {
+        cita_id: cita.id.to_string(),
+        token,
+        valido_desde: formatear(valido_desde),
+        valido_hasta: formatear(valido_hasta),
+        pdf,
+    })
+}
+
+/// Verifica un token de invitación escaneado y devuelve la cita para pre-llenar la visita.
+///
+/// # Errores
+/// - `CitaError::Validation`: Firma inválida, fuera de ventana o cédula distinta a la de la cita.
+/// - `CitaError::Cancelled` / `CitaError::AlreadyProcessed`: La cita ya no está programada.
+pub async fn verificar_invitacion(token: &str) -> Result<CitaResponse, CitaError> {
+    let clave = keyring_service::get_or_create_invitation_key()
+        .map_err(|e| CitaError::Invitacion(e.to_string()))?;
+    let inv = domain::verificar_invitacion(token, &clave, Utc::now().timestamp())?;
+
+    let cita_id = parse_record_id(&inv.cita_id, "cita")?;
+    let cita = db::find_by_id_fetched(&cita_id)
+        .await
+        .map_err(|e| CitaError::Database(e.to_string()))?
+        .ok_or(CitaError::NotFound)?;
+
+    match cita.estado {
+        EstadoCita::Programada => {}
+        EstadoCita::Cancelada => return Err(CitaError::Cancelled),
+        _ => return Err(CitaError::AlreadyProcessed),
+    }
+
+    let cedula_cita = cita
+        .visitante_id
+        .as_ref()
+        .map(|v| v.cedula.clone())
+        .or_else(|| cita.visitante_cedula.clone())
+        .unwrap_or_default();
+    if solo_digitos(&cedula_cita) != solo_digitos(&inv.cedula) {
+        return Err(CitaError::Validation(
+            "La invitación no corresponde al visitante de la cita".to_string(),
+        ));
+    }
+
+    Ok(CitaResponse::from_fetched(cita))
+}
+
+/// Procesa el ingreso de una cita a partir del token escaneado de la invitación.
+pub async fn procesar_ingreso_cita_token(
+    token: &str,
+    gafete_numero: Option<String>,
+    usuario_id_str: String,
+) -> Result<CitaResponse, CitaError> {
+    let cita = verificar_invitacion(token).await?;
+    procesar_ingreso_cita(cita.id, gafete_numero, usuario_id_str).await
+}
+
+#[cfg(feature = "export-pdf")]
+async fn renderizar_invitacion(data: InvitacionPdfData) -> Result<Option<Vec<u8>>, CitaError> {
+    tokio::task::spawn_blocking(move || crate::export::pdf::generate_invitacion_pdf(&data))
+        .await
+        .map_err(|e| CitaError::Invitacion(format!("Error en tarea de generación: {e}")))?
+        .map(Some)
+        .map_err(|e| CitaError::Invitacion(e.to_string()))
+}
+
+#[cfg(not(feature = "export-pdf"))]
+async fn renderizar_invitacion(_data: InvitacionPdfData) -> Result<Option<Vec<u8>>, CitaError> {
+    Ok(None)
+}
+
+// --------------------------------------------------------------------------
+// SERIES RECURRENTES
+// --------------------------------------------------------------------------
+
+/// Datos consolidados para agendar una serie de citas.
+pub struct AgendarSerieParams {
+    pub serie: CreateCitaSerieInput,
+    pub visitante_extra: Option<CreateVisitanteInput>,
+    pub usuario_id: String,
+}
+
+/// Agenda una serie recurrente y materializa sus citas dentro de la ventana móvil.
+///
+/// La primera cita (`fecha_cita`) fija la hora del día y el patrón de la serie.
+///
+/// # Errores
+/// - `CitaError::Validation`: Datos de la cita o regla de recurrencia inválidos.
+/// - `CitaError::Database`: Fallo de persistencia.
+pub async fn agendar_serie(params: AgendarSerieParams) -> Result<CitaSerieResponse, CitaError> {
+    let CreateCitaSerieInput { cita, regla } = params.serie;
+    domain::validar_create_input(&cita)?;
+
+    let fecha_inicio = parse_datetime(&cita.fecha_cita)?;
+    serie_domain::validar_regla(&regla, a_local(&fecha_inicio)?.date_naive())?;
+
+    let usuario_id = parse_record_id(&params.usuario_id, "user")?;
+    let visitante_id =
+        resolver_visitante(cita.visitante_id.as_deref(), params.visitante_extra).await?;
+
+    let serie = serie_db::insert(CitaSerieCreateDTO {
+        visitante_id,
+        usuario_id,
+        motivo: cita.motivo,
+        anfitrion: Some(cita.anfitrion),
+        area_visitada: Some(cita.area_visitada),
+        visitante_nombre: cita.visitante_nombre,
+        visitante_cedula: cita.visitante_cedula,
+        fecha_inicio,
+        duracion_minutos: cita.duracion_minutos,
+        regla,
+    })
+    .await
+    .map_err(|e| CitaError::Database(e.to_string()))?;
+
+    let (creadas, omitidas) = materializar_serie(&serie).await?;
+    info!("🔁 Serie {} agendada: {creadas} cita(s) generadas", serie.id);
+
+    serie_response(serie, omitidas).await
+}
+
+/// Obtiene una serie con sus próximas citas pendientes.
+pub async fn get_serie(id_str: String) -> Result<CitaSerieResponse, CitaError> {
+    let id = parse_record_id(&id_str, "cita_serie")?;
+    let serie = serie_db::find_by_id(&id)
+        .await
+        .map_err(|e| CitaError::Database(e.to_string()))?
+        .ok_or(CitaError::NotFound)?;
+
+    serie_response(serie, Vec::new()).await
+}
+
+/// Edita una serie completa: los cambios se aplican a las citas pendientes futuras.
+///
+/// Si cambia la regla, las citas pendientes futuras se eliminan y se regeneran.
+/// Las citas ya atendidas, vencidas o canceladas individualmente no se tocan.
+pub async fn actualizar_serie(
+    id_str: String,
+    input: UpdateCitaSerieInput,
+) -> Result<CitaSerieResponse, CitaError> {
+    let id = parse_record_id(&id_str, "cita_serie")?;
+    let serie = serie_db::find_by_id(&id)
+        .await
+        .map_err(|e| CitaError::Database(e.to_string()))?
+        .ok_or(CitaError::NotFound)?;
+
+    if !serie.activa {
+        return Err(CitaError::Validation("La serie fue cancelada".to_string()));
+    }
+    serie_domain::validar_update_input(&input, a_local(&serie.fecha_inicio)?.date_naive())?;
+
+    let UpdateCitaSerieInput { anfitrion, area_visitada, motivo, regla } = input;
+
+    if anfitrion.is_some() || area_visitada.is_some() || motivo.is_some() {
+        serie_db::update(
+            &id,
+            CitaSerieUpdateDTO {
+                motivo: motivo.clone(),
+                anfitrion: anfitrion.clone(),
+                area_visitada: area_visitada.clone(),
+            },
+        )
+        .await
+        .map_err(|e| CitaError::Database(e.to_string()))?;
+
+        db::update_pendientes_serie(
+            &id,
+            CitaUpdateDTO { motivo, anfitrion, area_visitada, ..CitaUpdateDTO::default() },
+        )
+        .await
+        .map_err(|e| CitaError::Database(e.to_string()))?;
+    }
+
+    if let Some(regla) = regla {
+        serie_db::update_regla(&id, regla).await.map_err(|e| CitaError::Database(e.to_string()))?;
+        db::delete_pendientes_serie(&id).await.map_err(|e| CitaError::Database(e.to_string()))?;
+    }
+
+    let actualizada = serie_db::find_by_id(&id)
+        .await
+        .map_err(|e| CitaError::Database(e.to_string()))?
+        .ok_or(CitaError::NotFound)?;
+    let (_, omitidas) = materializar_serie(&actualizada).await?;
+
+    info!("🔁 Serie {id} actualizada");
+    serie_response(actualizada, omitidas).await
+}
+
+/// Cancela una serie: deja de generar citas y cancela las pendientes futuras.
+///
+/// # Retorno
+/// Cantidad de citas canceladas.
+pub async fn cancelar_serie(id_str: String) -> Result<usize, CitaError> {
+    let id = parse_record_id(&id_str, "cita_serie")?;
+    serie_db::desactivar(&id)
+        .await
+        .map_err(|e| CitaError::Database(e.to_string()))?
+        .ok_or(CitaError::NotFound)?;
+
+    let canceladas =
+        db::cancel_pendientes_serie(&id).await.map_err(|e| CitaError::Database(e.to_string()))?;
+
+    info!("🚫 Serie {id} cancelada ({} cita(s) pendientes)", canceladas.len());
+    Ok(canceladas.len())
+}
+
+/// Extiende la ventana móvil de todas las series activas (tarea periódica).
+///
+/// # Retorno
+/// Cantidad total de citas generadas.
+pub async fn materializar_series() -> Result<usize, CitaError> {
+    let series = serie_db::find_activas().await.map_err(|e| CitaError::Database(e.to_string()))?;
+
+    let mut total = 0;
+    for serie in &series {
+        match materializar_serie(serie).await {
+            Ok((n, omitidas)) => {
+                total += n;
+                if !omitidas.is_empty() {
+                    // Se reintentan en cada pasada; solo se informa en modo depuración
+                    debug!("Serie {}: {} ocurrencia(s) sin capacidad", serie.id, omitidas.len());
+                }
+            }
+            Err(e) => warn!("⚠️ No se pudo materializar la serie {}: {e}", serie.id),
+        }
+    }
+
+    if total > 0 {
+        info!("🔁 {total} cita(s) generadas a partir de series recurrentes");
+    }
+    Ok(total)
+}
+
+/// Crea las citas futuras de la serie que aún no existen dentro de la ventana móvil.
+///
+/// Las ocurrencias que ya tienen cita (incluso cancelada) no se vuelven a crear, de modo
+/// que cancelar una sola cita de la serie actúa como excepción. Las que chocan con la
+/// capacidad del anfitrión o del área se omiten y se devuelven (RFC 3339).
+async fn materializar_serie(serie: &CitaSerie) -> Result<(usize, Vec<String>), CitaError> {
+    let config = config_citas();
+    let duracion = chrono::Duration::minutes(i64::from(
+        serie.duracion_minutos.unwrap_or(config.duracion_default_minutos),
+    ));
+    let ahora = Local::now();
+    let limite = ahora
+        .date_naive()
+        .checked_add_days(chrono::Days::new(serie_domain::VENTANA_MATERIALIZACION_DIAS))
+        .unwrap_or(chrono::NaiveDate::MAX);
+    let ocurrencias = serie_domain::ocurrencias(
+        a_local(&serie.fecha_inicio)?.naive_local(),
+        &serie.regla(),
+        limite,
+    )?;
+
+    let existentes: HashSet<i64> = db::find_by_serie(&serie.id)
+        .await
+        .map_err(|e| CitaError::Database(e.to_string()))?
+        .iter()
+        .filter_map(|c| a_local(&c.fecha_inicio).ok())
+        .map(|f| f.timestamp())
+        .collect();
+
+    let mut creadas = 0;
+    let mut omitidas = Vec::new();
+    for ocurrencia in ocurrencias {
+        // Hora inexistente por cambio de horario: se omite esa ocurrencia
+        let Some(instante) = ocurrencia.and_local_timezone(Local).earliest() else {
+            warn!("⚠️ Ocurrencia {ocurrencia} de la serie {} no existe en hora local", serie.id);
+            continue;
+        };
+        if instante <= ahora || existentes.contains(&instante.timestamp()) {
+            continue;
+        }
+
+        let inicio = instante.with_timezone(&Utc);
+        let fin = inicio + duracion;
+        match verificar_disponibilidad(
+            &config,
+            serie.anfitrion.as_deref(),
+            serie.area_visitada.as_deref(),
+            inicio,
+            fin,
+        )
+        .await
+        {
+            Ok(()) => {}
+            Err(CitaError::Conflicto(_)) => {
+                omitidas.push(instante.to_rfc3339());
+                continue;
+            }
+            Err(e) => return Err(e),
+        }
+
+        let insercion = db::insert(
+            CitaCreateDTO {
+                visitante_id: serie.visitante_id.clone(),
+                usuario_id: serie.usuario_id.clone(),
+                motivo: serie.motivo.clone(),
+                fecha_inicio: surrealdb::Datetime::from(inicio),
+                fecha_fin: surrealdb::Datetime::from(fin),
+                anfitrion: serie.anfitrion.clone(),
+                area_visitada: serie.area_visitada.clone(),
+                visitante_nombre: serie.visitante_nombre.clone(),
+                visitante_cedula: serie.visitante_cedula.clone(),
+                serie: Some(serie.id.clone()),
+                uid_ical: None,
+            },
+            limites_capacidad(&config, serie.anfitrion.as_deref(), serie.area_visitada.as_deref()),
+        )
+        .await
+        .map_err(map_insert_error);
+        match insercion {
+            Ok(_) => creadas += 1,
+            // Otra cita ocupó el último cupo entre la consulta y el alta
+            Err(CitaError::Conflicto(_)) => omitidas.push(instante.to_rfc3339()),
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok((creadas, omitidas))
+}
+
+async fn serie_response(
+    serie: CitaSerie,
+    omitidas: Vec<String>,
+) -> Result<CitaSerieResponse, CitaError> {
+    let ahora = Utc::now().timestamp();
+    let proximas = db::find_by_serie(&serie.id)
+        .await
+        .map_err(|e| CitaError::Database(e.to_string()))?
+        .into_iter()
+        .filter(|c| c.estado == EstadoCita::Programada)
+        .filter(|c| a_local(&c.fecha_inicio).is_ok_and(|f| f.timestamp() >= ahora))
+        .map(CitaResponse::from)
+        .collect();
+
+    Ok(CitaSerieResponse::new(serie, proximas, omitidas))
+}
+
+// --------------------------------------------------------------------------
+// CALENDARIO (iCalendar)
+// --------------------------------------------------------------------------
+
+/// Sufijo de los UID que genera la exportación; permite reconocer citas propias al importar.
+const SUFIJO_UID_ICAL: &str = "@brisas";
+
+/// Exporta a `.ics` las citas de un rango de días (YYYY-MM-DD, hora local, inclusivo).
+///
+/// # Argumentos
+/// * `hasta` - Por defecto igual a `desde`.
+/// * `anfitrion` - Si se indica, solo sus citas (sin distinguir mayúsculas ni espacios extremos).
+/// * `target_path` - Si se indica, además de devolver el contenido se escribe en disco.
+pub async fn exportar_ical(
+    desde: &str,
+    hasta: Option<&str>,
+    anfitrion: Option<&str>,
+    target_path: Option<String>,
+) -> Result<IcalExportResponse, CitaError> {
+    let inicio = parsear_fecha_simple(desde).map_err(|e| CitaError::Validation(e.to_string()))?;
+    let fin = hasta
+        .map(parsear_fecha_simple)
+        .transpose()
+        .map_err(|e| CitaError::Validation(e.to_string()))?
+        .unwrap_or(inicio);
+    if fin < inicio {
+        return Err(CitaError::Validation(
+            "La fecha final no puede ser anterior a la inicial".to_string(),
+        ));
+    }
+
+    let (limite_inicio, limite_fin) =
+        limites_dias_locales(inicio, fin).map_err(|e| CitaError::Validation(e.to_string()))?;
+    let citas = db::find_en_rango_fetched(&limite_inicio, &limite_fin)
+        .await
+        .map_err(|e| CitaError::Database(e.to_string()))?;
+
+    let eventos: Vec<EventoIcal> = citas
+        .into_iter()
+        .filter(|c| {
+            anfitrion
+                .is_none_or(|a| c.anfitrion.as_deref().is_some_and(|v| domain::mismo_recurso(v, a)))
+        })
+        .map(evento_desde_cita)
+        .collect();
+
+    let nombre = anfitrion.map_or_else(|| "Citas".to_string(), |a| format!("Citas - {a}"));
+    let contenido = ical::generate_ics(&eventos, &nombre, Utc::now());
+
+    let file_path = if let Some(path) = target_path {
+        std::fs::write(&path, &contenido).map_err(|e| {
+            CitaError::Calendario(format!("No se pudo escribir el archivo {path}: {e}"))
+        })?;
+        Some(path)
+    } else {
+        None
+    };
+
+    info!("📅 {} cita(s) exportadas a iCalendar", eventos.len());
+    Ok(IcalExportResponse { total: eventos.len(), file_path, contenido })
+}
+
+/// Importa los eventos de un archivo `.ics` como citas nuevas.
+///
+/// Cada evento pasa por `agendar_cita` (validación de dominio y capacidad); los que fallan
+/// se reportan sin detener el resto. Se omiten los eventos cancelados, los que ya
+/// existen en el sistema (exportados desde aquí o importados antes con el mismo `UID`)
+/// y los `UID` repetidos dentro del mismo archivo. Si la cédula corresponde a un visitante
+/// registrado se vincula; si no, la cita guarda los datos del visitante como snapshot.
+pub async fn importar_ical(
+    contenido: &str,
+    usuario_id: String,
+) -> Result<IcalImportResponse, CitaError> {
+    let eventos = ical::parse_ics(contenido).map_err(|e| CitaError::Calendario(e.to_string()))?;
+    let total = eventos.len();
+    let mut creadas = Vec::new();
+    let mut errores = Vec::new();
+    let mut vistos = std::collections::HashSet::new();
+
+    for (i, (evento, error_lectura)) in eventos.into_iter().enumerate() {
+        let repetido = !evento.uid.is_empty() && !vistos.insert(evento.uid.clone());
+        let resultado = match error_lectura {
+            Some(mensaje) => Err(mensaje),
+            None if repetido => Err(format!("UID {} repetido en el archivo", evento.uid)),
+            None => importar_evento(&evento, &usuario_id).await,
+        };
+
+        match resultado {
+            Ok(cita) => creadas.push(cita),
+            Err(mensaje) => errores.push(ErrorImportacionIcal {
+                evento: i + 1,
+                uid: Some(evento.uid).filter(|u| !u.is_empty()),
+                resumen: evento.resumen,
+                mensaje,
+            }),
+        }
+    }
+
+    info!(
+        "📅 Importación iCalendar: {} cita(s) creadas, {} evento(s) con error",
+        creadas.len(),
+        errores.len()
+    );
+    Ok(IcalImportResponse { total, creadas, errores })
+}
+
+async fn importar_evento(evento: &EventoIcal, usuario_id: &str) -> Result<CitaResponse, String> {
+    if evento.cancelado {
+        return Err("El evento está cancelado".to_string());
+    }
+
+    if let Some(id) = evento.uid.strip_suffix(SUFIJO_UID_ICAL) {
+        if let Ok(id) = id.parse::<RecordId>() {
+            if db::find_by_id(&id).await.map_err(|e| e.to_string())?.is_some() {
+                return Err(format!("La cita {id} ya existe en el sistema"));
+            }
+        }
+    }
+
+    let uid_ical = Some(evento.uid.clone()).filter(|u| !u.is_empty());
+    if let Some(ref uid) = uid_ical {
+        if let Some(existente) = db::find_by_uid_ical(uid).await.map_err(|e| e.to_string())? {
+            return Err(format!("El evento ya se importó como la cita {}", existente.id));
+        }
+    }
+
+    let mut cita = input_desde_evento(evento);
+    if let Some(cedula) = cita.visitante_cedula.as_deref() {
+        if let Some(v) =
+            visitante_service::get_visitante_by_cedula(cedula).await.map_err(|e| e.to_string())?
+        {
+            cita.visitante_id = Some(v.id);
+        }
+    }
+
+    agendar_cita(AgendarCitaParams {
+        cita,
+        visitante_extra: None,
+        usuario_id: usuario_id.to_string(),
+        uid_ical,
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+fn evento_desde_cita(c: CitaFetched) -> EventoIcal {
+    let uid = format!("{}{SUFIJO_UID_ICAL}", c.id);
+    let cancelado = c.estado == EstadoCita::Cancelada;
+    let inicio = a_local(&c.fecha_inicio).ok().map(|f| f.with_timezone(&Utc));
+    let fin = a_local(&c.fecha_fin).ok().map(|f| f.with_timezone(&Utc));
+    let r = CitaResponse::from_fetched(c);
+
+    let mut descripcion = Vec::new();
+    if let Some(ref nombre) = r.visitante_nombre_completo {
+        let cedula = r.visitante_cedula.as_deref().map(|c| format!(" ({c})")).unwrap_or_default();
+        descripcion.push(format!("Visitante: {nombre}{cedula}"));
+    }
+    if let Some(ref anfitrion) = r.anfitrion {
+        descripcion.push(format!("Anfitrión: {anfitrion}"));
+    }
+    descripcion.push(format!("Estado: {}", r.estado));
+
+    EventoIcal {
+        uid,
+        inicio,
+        fin,
+        resumen: Some(r.motivo),
+        descripcion: Some(descripcion.join("\n")),
+        ubicacion: r.area_visitada,
+        anfitrion: r.anfitrion,
+        visitante_nombre: r.visitante_nombre,
+        visitante_apellido: r.visitante_apellido,
+        visitante_cedula: r.visitante_cedula,
+        cancelado,
+    }
+}
+
+/// Arma la entrada de agendado a partir de un evento; la validación queda en `agendar_cita`.
+fn input_desde_evento(evento: &EventoIcal) -> CreateCitaInput {
+    let duracion_minutos = evento
+        .inicio
+        .zip(evento.fin)
+        .map(|(inicio, fin)| (fin - inicio).num_minutes())
+        .filter(|m| *m > 0)
+        .and_then(|m| u32::try_from(m).ok());
+
+    CreateCitaInput {
+        visitante_id: None,
+        fecha_cita: evento.inicio.map(|f| f.with_timezone(&Local).to_rfc3339()).unwrap_or_default(),
+        anfitrion: evento.anfitrion.clone().unwrap_or_default(),
+        area_visitada: evento.ubicacion.clone().unwrap_or_default(),
+        motivo: evento.resumen.clone().unwrap_or_default(),
+        visitante_cedula: evento.visitante_cedula.clone(),
+        visitante_nombre: evento.visitante_nombre.clone(),
+        visitante_apellido: evento.visitante_apellido.clone(),
+        duracion_minutos,
+    }
+}
+
+// --------------------------------------------------------------------------
+// DISPONIBILIDAD (CAPACIDAD Y CONFLICTOS)
+// --------------------------------------------------------------------------
+
+/// Horarios libres de un día para un anfitrión y/o área, según la capacidad configurada.
+///
+/// # Argumentos
+/// * `fecha` - Día a consultar (YYYY-MM-DD, hora local).
+/// * `anfitrion` / `area` - Recursos a respetar; sin límite configurado no restringen.
+/// * `duracion_minutos` - Duración de la cita; por defecto la configurada.
+pub async fn horarios_libres(
+    fecha: &str,
+    anfitrion: Option<&str>,
+    area: Option<&str>,
+    duracion_minutos: Option<u32>,
+) -> Result<Vec<HorarioLibre>, CitaError> {
+    let config = config_citas();
+    let dia = parsear_fecha_simple(fecha).map_err(|e| CitaError::Validation(e.to_string()))?;
+    let duracion = duracion_minutos.unwrap_or(config.duracion_default_minutos);
+    domain::validar_duracion(duracion)?;
+
+    let en_local = |hora: &str| -> Result<chrono::DateTime<Local>, CitaError> {
+        dia.and_time(domain::parsear_hora(hora)?).and_local_timezone(Local).earliest().ok_or_else(
+            || {
+                CitaError::Validation(format!(
+                    "La hora {hora} no existe el {dia} (horario de verano)"
+                ))
+            },
+        )
+    };
+    let jornada_inicio = en_local(&config.jornada_inicio)?;
+    let jornada_fin = en_local(&config.jornada_fin)?;
+
+    let candidatas = domain::franjas_candidatas(
+        jornada_inicio.timestamp(),
+        jornada_fin.timestamp(),
+        i64::from(duracion) * 60,
+        i64::from(config.intervalo_horarios_minutos.max(1)) * 60,
+    );
+
+    let citas = db::find_ocupadas(
+        surrealdb::Datetime::from(jornada_inicio.with_timezone(&Utc)),
+        surrealdb::Datetime::from(jornada_fin.with_timezone(&Utc)),
+    )
+    .await
+    .map_err(|e| CitaError::Database(e.to_string()))?;
+
+    let mut restricciones = Vec::new();
+    if let (Some(cap), Some(nombre)) = (config.capacidad_por_anfitrion, anfitrion) {
+        restricciones.push((franjas_de(&citas, |c| c.anfitrion.as_deref(), nombre), cap));
+    }
+    if let Some(nombre) = area {
+        if let Some(cap) = domain::capacidad_area(
+            &config.capacidad_por_area,
+            config.capacidad_area_default,
+            nombre,
+        ) {
+            restricciones.push((franjas_de(&citas, |c| c.area_visitada.as_deref(), nombre), cap));
+        }
+    }
+
+    let ahora = Utc::now().timestamp();
+    Ok(domain::horarios_libres(candidatas, &restricciones)
+        .into_iter()
+        .filter(|(inicio, _)| *inicio >= ahora)
+        .filter_map(|(inicio, fin)| {
+            let inicio = chrono::DateTime::from_timestamp(inicio, 0)?.with_timezone(&Local);
+            let fin = chrono::DateTime::from_timestamp(fin, 0)?.with_timezone(&Local);
+            Some(HorarioLibre { inicio: inicio.to_rfc3339(), fin: fin.to_rfc3339() })
+        })
+        .collect())
+}
+
+/// Límites de capacidad configurados para el anfitrión y el área de una cita.
+fn limites_capacidad(
+    config: &CitaConfig,
+    anfitrion: Option<&str>,
+    area: Option<&str>,
+) -> db::LimitesCapacidad {
+    db::LimitesCapacidad {
+        anfitrion: config.capacidad_por_anfitrion.filter(|_| anfitrion.is_some()),
+        area: area.and_then(|a| {
+            domain::capacidad_area(&config.capacidad_por_area, config.capacidad_area_default, a)
+        }),
+    }
+}
+
+/// El rechazo por capacidad dentro de la transacción de alta es un conflicto de agenda.
+fn map_insert_error(e: SurrealDbError) -> CitaError {
+    let msg = e.to_string();
+    match msg.find(db::SIN_CAPACIDAD) {
+        Some(pos) => CitaError::Conflicto(msg[pos + db::SIN_CAPACIDAD.len()..].to_string()),
+        None => CitaError::Database(msg),
+    }
+}
+
+/// Verifica que el anfitrión y el área tengan capacidad en `[inicio, fin)`.
+///
+/// Sin límites configurados no consulta la base de datos.
+async fn verificar_disponibilidad(
+    config: &CitaConfig,
+    anfitrion: Option<&str>,
+    area: Option<&str>,
+    inicio: chrono::DateTime<Utc>,
+    fin: chrono::DateTime<Utc>,
+) -> Result<(), CitaError> {
+    let limite_anfitrion = config.capacidad_por_anfitrion.zip(anfitrion);
+    let limite_area = area.and_then(|a| {
+        domain::capacidad_area(&config.capacidad_por_area, config.capacidad_area_default, a)
+            .map(|cap| (cap, a))
+    });
+    if limite_anfitrion.is_none() && limite_area.is_none() {
+        return Ok(());
+    }
+
+    let citas =
+        db::find_ocupadas(surrealdb::Datetime::from(inicio), surrealdb::Datetime::from(fin))
+            .await
+            .map_err(|e| CitaError::Database(e.to_string()))?;
+    let franja = (inicio.timestamp(), fin.timestamp());
+
+    if let Some((cap, nombre)) = limite_anfitrion {
+        let ocupadas = franjas_de(&citas, |c| c.anfitrion.as_deref(), nombre);
+        domain::verificar_capacidad(&ocupadas, franja, cap, &format!("El anfitrión {nombre}"))?;
+    }
+    if let Some((cap, nombre)) = limite_area {
+        let ocupadas = franjas_de(&citas, |c| c.area_visitada.as_deref(), nombre);
+        domain::verificar_capacidad(&ocupadas, franja, cap, &format!("El área {nombre}"))?;
+    }
+    Ok(())
+}
+
+/// Franjas `[inicio, fin)` (segundos Unix) de las citas cuyo campo coincide con `valor`.
+fn franjas_de<'a>(
+    citas: &'a [Cita],
+    campo: impl Fn(&'a Cita) -> Option<&'a str>,
+    valor: &str,
+) -> Vec<(i64, i64)> {
+    citas
+        .iter()
+        .filter(|&c| campo(c).is_some_and(|v| domain::mismo_recurso(v, valor)))
+        .filter_map(|c| {
+            let inicio = a_local(&c.fecha_inicio).ok()?.timestamp();
+            let fin = a_local(&c.fecha_fin).ok()?.timestamp();
+            Some((inicio, fin))
+        })
+        .collect()
+}
+
+/// Configuración de citas vigente (valores por defecto si no se puede leer).
+pub(crate) fn config_citas() -> CitaConfig {
+    load_config().map(|c| c.citas).unwrap_or_else(|e| {
+        warn!("⚠️ No se pudo leer la configuración de citas, usando valores por defecto: {e}");
+        CitaConfig::default()
+    })
 }
 
 // --------------------------------------------------------------------------
 // HELPERS INTERNOS
 // --------------------------------------------------------------------------
 
+/// Resuelve el visitante de una cita: ID existente, búsqueda por cédula o alta nueva.
+async fn resolver_visitante(
+    visitante_id: Option<&str>,
+    visitante_extra: Option<CreateVisitanteInput>,
+) -> Result<Option<RecordId>, CitaError> {
+    if let Some(id_str) = visitante_id.filter(|id| !id.is_empty()) {
+        return Ok(Some(parse_record_id(id_str, "visitante")?));
+    }
+
+    let Some(v_input) = visitante_extra else {
+        return Ok(None);
+    };
+
+    let existente = visitante_service::get_visitante_by_cedula(&v_input.cedula)
+        .await
+        .map_err(|e| CitaError::Database(e.to_string()))?;
+
+    if let Some(v) = existente {
+        Ok(Some(parse_record_id(&v.id, "visitante")?))
+    } else {
+        let nuevo = visitante_service::create_visitante(v_input)
+            .await
+            .map_err(|e| CitaError::Database(e.to_string()))?;
+        Ok(Some(parse_record_id(&nuevo.id, "visitante")?))
+    }
+}
+
+fn a_local(dt: &surrealdb::Datetime) -> Result<chrono::DateTime<Local>, CitaError> {
+    chrono::DateTime::parse_from_rfc3339(&datetime_to_iso(dt))
+        .map(|f| f.with_timezone(&Local))
+        .map_err(|e| CitaError::Validation(format!("Fecha de cita inválida: {e}")))
+}
+
+fn solo_digitos(cedula: &str) -> String {
+    cedula.chars().filter(char::is_ascii_digit).collect()
+}
+
 /// Convierte un ID de texto en un `RecordId` de `SurrealDB`.
 fn parse_record_id(id_str: &str, table: &str) -> Result<RecordId, CitaError> {
     if id_str.contains(':') {
@@ -265,9 +1086,14 @@ fn parse_record_id(id_str: &str, table: &str) -> Result<RecordId, CitaError> {
 /// Soporta RFC 3339 (fecha con hora) y YYYY-MM-DD (fecha simple).
 /// Para la fecha simple, se asume el mediodía como hora por defecto.
 fn parse_datetime(date_str: &str) -> Result<surrealdb::Datetime, CitaError> {
+    parse_instante(date_str).map(surrealdb::Datetime::from)
+}
+
+/// Igual que `parse_datetime`, pero devuelve el instante en UTC para operar con él.
+fn parse_instante(date_str: &str) -> Result<chrono::DateTime<Utc>, CitaError> {
     // Intenta parsear como timestamp completo (Estándar para almacenamiento detallado)
     if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(date_str) {
-        return Ok(surrealdb::Datetime::from(dt.with_timezone(&Utc)));
+        return Ok(dt.with_timezone(&Utc));
     }
 
     // Fallback: Intenta parsear como fecha simple YYYY-MM-DD
@@ -275,9 +1101,7 @@ fn parse_datetime(date_str: &str) -> Result<surrealdb::Datetime, CitaError> {
         let dt = date
             .and_hms_opt(12, 0, 0) // Por defecto al mediodía si no hay hora especificada.
             .ok_or(CitaError::Validation("Fecha con formato incompleto".to_string()))?;
-        return Ok(surrealdb::Datetime::from(chrono::DateTime::<Utc>::from_naive_utc_and_offset(
-            dt, Utc,
-        )));
+        return Ok(chrono::DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc));
     }
 
     Err(CitaError::Validation(format!(
@@ -320,4 +1144,25 @@ mod tests {
         let id_raw = parse_record_id("xyz", "visitante").unwrap();
         assert_eq!(id_raw.to_string(), "visitante:xyz");
     }
+
+    #[test]
+    fn test_input_desde_evento() {
+        let inicio = chrono::DateTime::parse_from_rfc3339("2026-01-15T14:00:00Z").unwrap();
+        let evento = EventoIcal {
+            uid: "externo-1".to_string(),
+            inicio: Some(inicio.with_timezone(&Utc)),
+            fin: Some((inicio + chrono::Duration::minutes(45)).with_timezone(&Utc)),
+            resumen: Some("Entrega".to_string()),
+            ubicacion: Some("Bodega".to_string()),
+            visitante_cedula: Some("1-2345-6789".to_string()),
+            ..EventoIcal::default()
+        };
+
+        let input = input_desde_evento(&evento);
+        assert_eq!(input.duracion_minutos, Some(45));
+        assert_eq!(parse_instante(&input.fecha_cita).unwrap(), inicio.with_timezone(&Utc));
+        assert_eq!(input.area_visitada, "Bodega");
+        // Sin anfitrión: lo rechaza la validación de dominio al agendar
+        assert!(domain::validar_create_input(&input).is_err());
+    }
 }
@@ -56,6 +56,16 @@ where
         Self { repo, sec_repo, emp_repo, veh_repo, audit_repo, search_service }
     }
 
+    /// Refleja los cambios del contratista en el índice de búsqueda (si está disponible).
+    async fn sync_search(&self, contratista: &crate::models::contratista::ContratistaFetched) {
+        if let Some(search) = &self.search_service {
+            let empresa_nombre = contratista.empresa.nombre.clone();
+            if let Err(e) = search.update_contratista_fetched(contratista, &empresa_nombre).await {
+                log::warn!("Aviso: Falló la sincronización del buscador: {e}");
+            }
+        }
+    }
+
     async fn build_response_fetched(
         &self,
         contratista: crate::models::contratista::ContratistaFetched,
@@ -123,6 +133,16 @@ where
             return Err(ContratistaError::EmpresaNotFound);
         }
 
+        let veto = self.sec_repo.check_empresa_vetada(&empresa_id).await.map_err(map_db_error)?;
+        if veto.is_vetada && veto.bloquear_registros {
+            let nivel = veto.nivel_severidad.unwrap_or_else(|| "BAJO".to_string());
+            warn!("Registro rechazado: empresa {} vetada (Nivel: {nivel})", input.empresa_id);
+            return Err(ContratistaError::Validation(format!(
+                "EMPRESA VETADA: No se pueden registrar contratistas de {} (Nivel: {nivel}).",
+                veto.empresa_nombre.unwrap_or(input.empresa_id)
+            )));
+        }
+
         // Note: domain::validar_fecha now returns DateTime<Utc> directly per domain refactor
         let fecha_vencimiento: DateTime<Utc> =
             domain::validar_fecha(&input.fecha_vencimiento_praind)?;
@@ -320,12 +340,7 @@ where
             }
         }
 
-        let empresa_nombre = updated.empresa.nombre.clone();
-        if let Some(search) = &self.search_service {
-            if let Err(e) = search.update_contratista_fetched(&updated, &empresa_nombre).await {
-                log::warn!("Aviso: Falló la sincronización del buscador: {e}");
-            }
-        }
+        self.sync_search(&updated).await;
 
         self.build_response_fetched(updated).await
     }
@@ -377,7 +392,7 @@ where
             ..Default::default()
         };
 
-        let updated = self.repo.update(&id, dto).await.map_err(map_db_error)?;
+        let mut updated = self.repo.update(&id, dto).await.map_err(map_db_error)?;
         self.audit_repo
             .insert_praind_historial(
                 &input.contratista_id,
@@ -388,13 +403,52 @@ where
             )
             .await
             .map_err(map_db_error)?;
+
+        // La suspensión automática por PRAIND vencido se levanta al renovarlo; una
+        // suspensión manual se mantiene
+        let ultimo_motivo = if contratista.estado == EstadoContratista::Suspendido {
+            self.audit_repo
+                .ultimo_motivo_estado(&input.contratista_id)
+                .await
+                .map_err(map_db_error)?
+        } else {
+            None
+        };
+        if domain::reactivar_al_renovar_praind(
+            &contratista.estado,
+            ultimo_motivo.as_deref(),
+            nueva_fecha > Utc::now(),
+        ) {
+            updated = self
+                .repo
+                .update_status(&id, EstadoContratista::Activo)
+                .await
+                .map_err(map_db_error)?;
+            self.audit_repo
+                .insert_historial_estado(
+                    &input.contratista_id,
+                    EstadoContratista::Suspendido.as_str(),
+                    EstadoContratista::Activo.as_str(),
+                    Some(&usuario_id),
+                    "Reactivación automática: PRAIND renovado",
+                )
+                .await
+                .map_err(map_db_error)?;
+            info!("✅ Contratista {} reactivado al renovar PRAIND", input.contratista_id);
+        }
+
+        self.sync_search(&updated).await;
         self.build_response_fetched(updated).await
     }
 
+    /// Cambia el estado registrando el motivo en el historial.
+    ///
+    /// `usuario_id` es `None` cuando el cambio lo hace el sistema (p. ej. la
+    /// suspensión automática por PRAIND vencido).
     pub async fn cambiar_estado_con_historial(
         &self,
         input: CambiarEstadoConHistorialInput,
-        usuario_id: String,
+        usuario_id: Option<String>,
     ) -> Result<ContratistaResponse, ContratistaError> {
         let id = parse_contratista_id(&input.contratista_id);
         let contratista = self
@@ -413,11 +467,13 @@ where
                 &input.contratista_id,
                 &estado_anterior,
                 nuevo_estado.as_str(),
-                Some(&usuario_id),
+                usuario_id.as_deref(),
                 &input.motivo,
             )
             .await
             .map_err(map_db_error)?;
+
+        self.sync_search(&updated).await;
         self.build_response_fetched(updated).await
     }
 
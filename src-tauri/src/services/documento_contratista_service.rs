@@ -0,0 +1,356 @@
+/// Servicio: Documentos de Contratista y Requisitos de Ingreso.
+///
+/// Gestiona las credenciales que el contratista mantiene además del PRAIND (inducción
+/// de seguridad, póliza de riesgos, certificado médico, trabajo en alturas) y las
+/// reglas que las exigen por empresa o por área. El escaneo opcional se guarda con
+/// `adjunto_cifrado_service`, igual que las evidencias de lista negra: cifrado con
+/// `ChaCha20Poly1305`, nombre ofuscado en disco y SHA-256 del original en la base de datos.
+///
+/// Responsabilidades:
+/// - Registro y actualización de documentos (Validación -> Escaneo cifrado -> DB).
+/// - Recuperación verificada del escaneo (Disk -> Decrypt -> SHA-256 -> Base64).
+/// - Administración de reglas de documentos requeridos.
+/// - Consulta de documentos pendientes (los mismos que evalúa el motor en garita).
+use crate::db::surrealdb_contratista_queries as contratista_db;
+use crate::db::surrealdb_documento_contratista_queries as db;
+use crate::db::surrealdb_empresa_queries as empresa_db;
+use crate::domain::documento_contratista as domain;
+use crate::domain::errors::DocumentoContratistaError;
+use crate::models::documento_contratista::{
+    CreateDocumentoContratistaInput, CreateRequisitoDocumentoInput, DocumentoContratistaCreateDTO,
+    DocumentoContratistaResponse, DocumentoContratistaUpdateDTO, DocumentoPendiente,
+    EscaneoDocumentoResponse, RequisitoDocumentoCreateDTO, RequisitoDocumentoResponse,
+    UpdateDocumentoContratistaInput,
+};
+use crate::services::adjunto_cifrado_service::{parse_id, AlmacenAdjuntos};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{NaiveDate, Utc};
+use log::{info, warn};
+use surrealdb::Datetime;
+
+/// Almacén cifrado de los escaneos
+const ESCANEOS: AlmacenAdjuntos = AlmacenAdjuntos {
+    dir: "secure_documentos",
+    etiqueta: "escaneo",
+    max_bytes: domain::ESCANEO_MAX_BYTES,
+};
+
+fn fecha_a_datetime(fecha: NaiveDate) -> Datetime {
+    Datetime::from(fecha.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+}
+
+fn datetime_a_fecha(dt: &Datetime) -> Option<NaiveDate> {
+    let iso = crate::domain::common::datetime_to_iso(dt);
+    NaiveDate::parse_from_str(iso.get(..10).unwrap_or(&iso), "%Y-%m-%d").ok()
+}
+
+fn texto_opcional(valor: Option<&str>) -> Option<String> {
+    valor.map(str::trim).filter(|v| !v.is_empty()).map(str::to_string)
+}
+
+// --------------------------------------------------------------------------
+// DOCUMENTOS
+// --------------------------------------------------------------------------
+
+/// Registra un documento para un contratista, con escaneo cifrado opcional.
+///
+/// # Errors
+///
+/// * `DocumentoContratistaError::ContratistaNotFound`: El contratista no existe.
+/// * `DocumentoContratistaError::Validation`: Número, descripción, fechas o escaneo inválidos.
+/// * `DocumentoContratistaError::IO` / `Internal`: Fallo de disco o de cifrado.
+pub async fn registrar(
+    input: CreateDocumentoContratistaInput,
+    registrado_por: &str,
+) -> Result<DocumentoContratistaResponse, DocumentoContratistaError> {
+    let contratista_id = parse_id(&input.contratista_id, "contratista")?;
+
+    domain::validar_numero(input.numero.as_deref())?;
+    domain::validar_descripcion(input.tipo, input.descripcion.as_deref())?;
+    let emision = domain::parsear_fecha_opcional(input.fecha_emision.as_deref(), "Emisión")?;
+    let vencimiento =
+        domain::parsear_fecha_opcional(input.fecha_vencimiento.as_deref(), "Vencimiento")?;
+    domain::validar_fechas(emision, vencimiento, Utc::now().date_naive())?;
+
+    contratista_db::find_by_id(&contratista_id)
+        .await
+        .map_err(|e| DocumentoContratistaError::Database(e.to_string()))?
+        .ok_or(DocumentoContratistaError::ContratistaNotFound)?;
+
+    let escaneo = input.file_path.as_deref().map(|path| ESCANEOS.guardar(path)).transpose()?;
+
+    let dto = DocumentoContratistaCreateDTO {
+        contratista: contratista_id.clone(),
+        tipo: input.tipo,
+        numero: texto_opcional(input.numero.as_deref()),
+        fecha_emision: emision.map(fecha_a_datetime),
+        fecha_vencimiento: vencimiento.map(fecha_a_datetime),
+        descripcion: texto_opcional(input.descripcion.as_deref()),
+        archivo_uuid: escaneo.as_ref().map(|e| e.archivo_uuid.clone()),
+        nombre_original: escaneo.as_ref().map(|e| e.nombre_original.clone()),
+        tipo_mime: escaneo.as_ref().map(|e| e.tipo_mime.to_string()),
+        tamano_bytes: escaneo.as_ref().map(|e| e.tamano_bytes),
+        sha256: escaneo.as_ref().map(|e| e.sha256.clone()),
+        registrado_por: registrado_por.to_string(),
+    };
+
+    let creado = match db::create(dto).await {
+        Ok(creado) => creado,
+        Err(e) => {
+            if let Some(ref escaneo) = escaneo {
+                escaneo.descartar();
+            }
+            return Err(DocumentoContratistaError::Database(e.to_string()));
+        }
+    };
+
+    info!(
+        "📄 Documento {} registrado para {contratista_id} (escaneo: {}), por={registrado_por}",
+        creado.tipo.as_str(),
+        if escaneo.is_some() { "sí" } else { "no" }
+    );
+    Ok(creado.into())
+}
+
+/// Documentos de un contratista (sin descifrar escaneos).
+pub async fn listar(
+    contratista_id: &str,
+) -> Result<Vec<DocumentoContratistaResponse>, DocumentoContratistaError> {
+    let contratista_id = parse_id(contratista_id, "contratista")?;
+
+    let documentos = db::find_by_contratista(&contratista_id)
+        .await
+        .map_err(|e| DocumentoContratistaError::Database(e.to_string()))?;
+
+    Ok(documentos.into_iter().map(std::convert::Into::into).collect())
+}
+
+/// Actualiza un documento. Un `file_path` nuevo reemplaza el escaneo anterior, que se
+/// borra solo después de registrar el nuevo.
+pub async fn actualizar(
+    id: &str,
+    input: UpdateDocumentoContratistaInput,
+    actualizado_por: &str,
+) -> Result<DocumentoContratistaResponse, DocumentoContratistaError> {
+    let record_id = parse_id(id, "documento_contratista")?;
+
+    let actual = db::find_by_id(&record_id)
+        .await
+        .map_err(|e| DocumentoContratistaError::Database(e.to_string()))?
+        .ok_or(DocumentoContratistaError::NotFound)?;
+
+    domain::validar_numero(input.numero.as_deref())?;
+    if input.descripcion.is_some() {
+        domain::validar_descripcion(actual.tipo, input.descripcion.as_deref())?;
+    }
+    let emision = domain::parsear_fecha_opcional(input.fecha_emision.as_deref(), "Emisión")?;
+    let vencimiento =
+        domain::parsear_fecha_opcional(input.fecha_vencimiento.as_deref(), "Vencimiento")?;
+
+    // Las fechas se validan combinadas con las que ya tiene el documento
+    domain::validar_fechas(
+        emision.or_else(|| actual.fecha_emision.as_ref().and_then(datetime_a_fecha)),
+        vencimiento.or_else(|| actual.fecha_vencimiento.as_ref().and_then(datetime_a_fecha)),
+        Utc::now().date_naive(),
+    )?;
+
+    let escaneo = input.file_path.as_deref().map(|path| ESCANEOS.guardar(path)).transpose()?;
+
+    let dto = DocumentoContratistaUpdateDTO {
+        numero: texto_opcional(input.numero.as_deref()),
+        fecha_emision: emision.map(fecha_a_datetime),
+        fecha_vencimiento: vencimiento.map(fecha_a_datetime),
+        descripcion: texto_opcional(input.descripcion.as_deref()),
+        archivo_uuid: escaneo.as_ref().map(|e| e.archivo_uuid.clone()),
+        nombre_original: escaneo.as_ref().map(|e| e.nombre_original.clone()),
+        tipo_mime: escaneo.as_ref().map(|e| e.tipo_mime.to_string()),
+        tamano_bytes: escaneo.as_ref().map(|e| e.tamano_bytes),
+        sha256: escaneo.as_ref().map(|e| e.sha256.clone()),
+    };
+
+    let actualizado = match db::update(&record_id, dto).await {
+        Ok(actualizado) => actualizado,
+        Err(e) => {
+            if let Some(ref escaneo) = escaneo {
+                escaneo.descartar();
+            }
+            return Err(DocumentoContratistaError::Database(e.to_string()));
+        }
+    };
+
+    if escaneo.is_some() {
+        if let Some(ref anterior) = actual.archivo_uuid {
+            if let Err(e) = ESCANEOS.eliminar(anterior) {
+                warn!("No se pudo borrar el escaneo reemplazado de {record_id}: {e}");
+            }
+        }
+    }
+
+    info!("✏️ Documento actualizado: id={record_id}, por={actualizado_por}");
+    Ok(actualizado.into())
+}
+
+/// Descifra el escaneo de un documento y verifica que coincida con el SHA-256 registrado.
+///
+/// # Errors
+///
+/// * `DocumentoContratistaError::NotFound`: El documento no existe o no tiene escaneo.
+/// * `DocumentoContratistaError::Integridad`: El contenido descifrado no coincide con la huella.
+pub async fn obtener_escaneo(
+    id: &str,
+    solicitado_por: &str,
+) -> Result<EscaneoDocumentoResponse, DocumentoContratistaError> {
+    let record_id = parse_id(id, "documento_contratista")?;
+
+    let documento = db::find_by_id(&record_id)
+        .await
+        .map_err(|e| DocumentoContratistaError::Database(e.to_string()))?
+        .ok_or(DocumentoContratistaError::NotFound)?;
+
+    let (Some(archivo_uuid), Some(sha256)) = (&documento.archivo_uuid, &documento.sha256) else {
+        return Err(DocumentoContratistaError::NotFound);
+    };
+
+    let contenido = ESCANEOS.leer_verificado(archivo_uuid, sha256, &documento.id.to_string())?;
+
+    info!(target: "audit", "[DOCUMENTO] id={} consultado por={solicitado_por}", documento.id);
+
+    Ok(EscaneoDocumentoResponse {
+        contenido_base64: STANDARD.encode(&contenido),
+        documento: documento.into(),
+    })
+}
+
+/// Elimina un documento: primero el escaneo cifrado, luego la referencia.
+pub async fn eliminar(id: &str, eliminado_por: &str) -> Result<(), DocumentoContratistaError> {
+    let record_id = parse_id(id, "documento_contratista")?;
+
+    let documento = db::find_by_id(&record_id)
+        .await
+        .map_err(|e| DocumentoContratistaError::Database(e.to_string()))?
+        .ok_or(DocumentoContratistaError::NotFound)?;
+
+    if let Some(ref archivo_uuid) = documento.archivo_uuid {
+        ESCANEOS.eliminar(archivo_uuid)?;
+    }
+
+    db::delete(&record_id).await.map_err(|e| DocumentoContratistaError::Database(e.to_string()))?;
+
+    warn!(
+        "🗑️ Documento eliminado: id={}, contratista={}, tipo={}, por={eliminado_por}",
+        documento.id,
+        documento.contratista,
+        documento.tipo.as_str()
+    );
+    Ok(())
+}
+
+/// Documentos exigidos que el contratista no cumple (la misma evaluación del motor).
+///
+/// # Argumentos
+/// * `contratista_id` - Contratista a evaluar.
+/// * `area` - Área de ingreso; sin ella solo se evalúan las reglas globales y por empresa.
+pub async fn get_pendientes(
+    contratista_id: &str,
+    area: Option<&str>,
+) -> Result<Vec<DocumentoPendiente>, DocumentoContratistaError> {
+    let contratista_id = parse_id(contratista_id, "contratista")?;
+
+    let contratista = contratista_db::find_by_id(&contratista_id)
+        .await
+        .map_err(|e| DocumentoContratistaError::Database(e.to_string()))?
+        .ok_or(DocumentoContratistaError::ContratistaNotFound)?;
+
+    let requisitos = db::find_requisitos()
+        .await
+        .map_err(|e| DocumentoContratistaError::Database(e.to_string()))?;
+    let documentos = db::find_by_contratista(&contratista_id)
+        .await
+        .map_err(|e| DocumentoContratistaError::Database(e.to_string()))?;
+
+    Ok(domain::documentos_pendientes(
+        &requisitos,
+        &documentos,
+        &contratista.empresa.to_string(),
+        domain::normalizar_area(area).as_deref(),
+        Utc::now().date_naive(),
+    ))
+}
+
+// --------------------------------------------------------------------------
+// REQUISITOS
+// --------------------------------------------------------------------------
+
+/// Crea una regla de documento requerido (global, por empresa o por área).
+pub async fn crear_requisito(
+    input: CreateRequisitoDocumentoInput,
+    creado_por: &str,
+) -> Result<RequisitoDocumentoResponse, DocumentoContratistaError> {
+    domain::validar_requisito(input.tipo, input.area.as_deref(), input.descripcion.as_deref())?;
+
+    let empresa = match texto_opcional(input.empresa_id.as_deref()) {
+        Some(empresa_id) => {
+            let empresa_id = parse_id(&empresa_id, "empresa")?;
+            empresa_db::find_by_id(&empresa_id)
+                .await
+                .map_err(|e| DocumentoContratistaError::Database(e.to_string()))?
+                .ok_or(DocumentoContratistaError::EmpresaNotFound)?;
+            Some(empresa_id)
+        }
+        None => None,
+    };
+
+    let dto = RequisitoDocumentoCreateDTO {
+        tipo: input.tipo,
+        empresa,
+        area: domain::normalizar_area(input.area.as_deref()),
+        descripcion: texto_opcional(input.descripcion.as_deref()),
+        creado_por: creado_por.to_string(),
+    };
+
+    let creado = db::create_requisito(dto)
+        .await
+        .map_err(|e| DocumentoContratistaError::Database(e.to_string()))?;
+
+    info!(
+        "📋 Requisito de documento creado: tipo={}, empresa={:?}, area={:?}, por={creado_por}",
+        creado.tipo.as_str(),
+        creado.empresa.as_ref().map(ToString::to_string),
+        creado.area
+    );
+    Ok(creado.into())
+}
+
+/// Todas las reglas de documentos requeridos.
+pub async fn listar_requisitos(
+) -> Result<Vec<RequisitoDocumentoResponse>, DocumentoContratistaError> {
+    let requisitos = db::find_requisitos()
+        .await
+        .map_err(|e| DocumentoContratistaError::Database(e.to_string()))?;
+
+    Ok(requisitos.into_iter().map(std::convert::Into::into).collect())
+}
+
+/// Elimina una regla de documento requerido.
+pub async fn eliminar_requisito(
+    id: &str,
+    eliminado_por: &str,
+) -> Result<(), DocumentoContratistaError> {
+    let record_id = parse_id(id, "requisito_documento")?;
+
+    let requisito = db::find_requisito_by_id(&record_id)
+        .await
+        .map_err(|e| DocumentoContratistaError::Database(e.to_string()))?
+        .ok_or(DocumentoContratistaError::RequisitoNotFound)?;
+
+    db::delete_requisito(&record_id)
+        .await
+        .map_err(|e| DocumentoContratistaError::Database(e.to_string()))?;
+
+    warn!(
+        "🗑️ Requisito de documento eliminado: id={}, tipo={}, por={eliminado_por}",
+        requisito.id,
+        requisito.tipo.as_str()
+    );
+    Ok(())
+}
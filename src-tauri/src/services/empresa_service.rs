@@ -6,17 +6,24 @@
 /// Responsabilidades:
 /// - Registro y actualización de perfiles corporativos.
 /// - Gestión de la vigencia operativa (activación/desactivación).
+/// - Jerarquía contratista principal / subcontratistas.
 /// - Control de integridad referencial para eliminaciones.
+use crate::db::surrealdb_contratista_queries as contratista_queries;
 use crate::db::surrealdb_empresa_queries as db;
+use crate::db::surrealdb_proveedor_queries as proveedor_queries;
 use crate::domain::empresa as domain;
 use crate::domain::errors::EmpresaError;
 use crate::models::empresa::{
-    CreateEmpresaInput, EmpresaListResponse, EmpresaResponse, UpdateEmpresaInput,
+    BajaEmpresaResponse, CreateEmpresaInput, EmpresaListResponse, EmpresaResponse,
+    UpdateEmpresaInput,
 };
+use crate::services::search_service::SearchService;
 use crate::services::surrealdb_service::SurrealDbError;
-use chrono::Utc;
-use log::info;
-use surrealdb::RecordId;
+use chrono::{NaiveDate, Utc};
+use log::{info, warn};
+use std::collections::HashMap;
+use std::sync::Arc;
+use surrealdb::{Datetime, RecordId};
 
 // --------------------------------------------------------------------------
 // CONSULTAS DE EMPRESA
@@ -34,7 +41,17 @@ pub async fn get_all_empresas() -> Result<EmpresaListResponse, EmpresaError> {
     let total = empresas.len();
     let activas = empresas.iter().filter(|e| e.is_active).count();
 
-    let responses: Vec<EmpresaResponse> = empresas.into_iter().map(EmpresaResponse::from).collect();
+    let nombres: HashMap<String, String> =
+        empresas.iter().map(|e| (e.id.to_string(), e.nombre.clone())).collect();
+    let responses: Vec<EmpresaResponse> = empresas
+        .into_iter()
+        .map(|e| {
+            let mut res = EmpresaResponse::from(e);
+            res.empresa_padre_nombre =
+                res.empresa_padre_id.as_ref().and_then(|p| nombres.get(p)).cloned();
+            res
+        })
+        .collect();
 
     Ok(EmpresaListResponse { empresas: responses, total, activas })
 }
@@ -66,17 +83,43 @@ pub async fn get_empresa_by_id(id_str: &str) -> Result<EmpresaResponse, EmpresaE
     let id = parse_empresa_id(id_str);
     let empresa = db::find_by_id(&id).await.map_err(map_db_error)?.ok_or(EmpresaError::NotFound)?;
 
-    Ok(EmpresaResponse::from(empresa))
+    let padre = empresa.empresa_padre.clone();
+    let mut res = EmpresaResponse::from(empresa);
+    if let Some(padre) = padre {
+        res.empresa_padre_nombre =
+            db::find_by_id(&padre).await.map_err(map_db_error)?.map(|p| p.nombre);
+    }
+    Ok(res)
+}
+
+/// Lista las subcontratistas directas de una empresa.
+///
+/// # Errores
+/// - `EmpresaError::NotFound`: La empresa no existe.
+/// - `EmpresaError::Database`: Error técnico de base de datos.
+pub async fn get_subempresas(id_str: &str) -> Result<Vec<EmpresaResponse>, EmpresaError> {
+    let id = parse_empresa_id(id_str);
+    let empresa = db::find_by_id(&id).await.map_err(map_db_error)?.ok_or(EmpresaError::NotFound)?;
+
+    let subempresas = db::find_subempresas(&id).await.map_err(map_db_error)?;
+    Ok(subempresas
+        .into_iter()
+        .map(|e| {
+            let mut res = EmpresaResponse::from(e);
+            res.empresa_padre_nombre = Some(empresa.nombre.clone());
+            res
+        })
+        .collect())
 }
 
 // --------------------------------------------------------------------------
 // OPERACIONES DE GESTIÓN (MUTACIONES)
 // --------------------------------------------------------------------------
 
-/// Registra una nueva empresa garantizando la unicidad del nombre.
+/// Registra una nueva empresa garantizando la unicidad del nombre y la cédula jurídica.
 ///
 /// # Argumentos
-/// * `input` - Datos de creación (nombre, dirección).
+/// * `input` - Datos de creación (nombre, dirección, contactos, principal, contrato).
 ///
 /// # Retorno
 /// La empresa recién creada.
@@ -84,21 +127,44 @@ pub async fn get_empresa_by_id(id_str: &str) -> Result<EmpresaResponse, EmpresaE
 /// # Errores
 /// - `EmpresaError::Validation`: Datos inválidos según reglas de dominio.
 /// - `EmpresaError::NameExists`: Ya existe una empresa con ese nombre.
+/// - `EmpresaError::RifExists`: Ya existe una empresa con esa cédula jurídica.
+/// - `EmpresaError::NotFound`: La contratista principal indicada no existe.
 /// - `EmpresaError::Database`: Error de persistencia.
 pub async fn create_empresa(input: CreateEmpresaInput) -> Result<EmpresaResponse, EmpresaError> {
     // 1. Validación de Dominio (Capa Pura)
     domain::validar_create_input(&input)?;
+    let (contrato_inicio, contrato_fin) = domain::validar_periodo_contrato(
+        input.contrato_inicio.as_deref(),
+        input.contrato_fin.as_deref(),
+    )?;
 
     // 2. Control de duplicidad
     let exists = db::exists_by_name(&input.nombre).await.map_err(map_db_error)?;
     if exists {
         return Err(EmpresaError::NameExists);
     }
+    let cedula_juridica = input.cedula_juridica.map(|c| c.trim().to_string());
+    if let Some(ref cedula) = cedula_juridica {
+        if db::exists_by_cedula_juridica(cedula, None).await.map_err(map_db_error)? {
+            return Err(EmpresaError::RifExists);
+        }
+    }
+
+    // 3. Contratista principal
+    let empresa_padre = match input.empresa_padre_id.as_deref().map(str::trim) {
+        Some(padre) if !padre.is_empty() => Some(verificar_empresa_padre(None, padre).await?),
+        _ => None,
+    };
 
     let dto = crate::models::empresa::EmpresaCreateDTO {
         nombre: domain::normalizar_nombre(&input.nombre),
         direccion: input.direccion.map(|s| s.trim().to_string()),
         is_active: true,
+        cedula_juridica,
+        contactos: domain::normalizar_contactos(input.contactos),
+        empresa_padre,
+        contrato_inicio: contrato_inicio.map(fecha_a_datetime),
+        contrato_fin: contrato_fin.map(fecha_a_datetime),
     };
 
     let saved = db::create(dto).await.map_err(map_db_error)?;
@@ -117,8 +183,9 @@ pub async fn create_empresa(input: CreateEmpresaInput) -> Result<EmpresaResponse
 /// La empresa actualizada.
 ///
 /// # Errores
-/// - `EmpresaError::NotFound`: La empresa no existe.
+/// - `EmpresaError::NotFound`: La empresa (o la principal indicada) no existe.
 /// - `EmpresaError::Validation`: Reglas de dominio fallidas.
+/// - `EmpresaError::RifExists`: La cédula jurídica ya pertenece a otra empresa.
 /// - `EmpresaError::Database`: Error de `SurrealDB`.
 pub async fn update_empresa(
     id_str: &str,
@@ -127,12 +194,13 @@ pub async fn update_empresa(
     let id = parse_empresa_id(id_str);
 
     // Verificar existencia previa
-    db::find_by_id(&id).await.map_err(map_db_error)?.ok_or(EmpresaError::NotFound)?;
+    let actual = db::find_by_id(&id).await.map_err(map_db_error)?.ok_or(EmpresaError::NotFound)?;
 
     // Validación de dominio
     domain::validar_update_input(&input)?;
 
     let mut dto = crate::models::empresa::EmpresaUpdateDTO::default();
+    let mut quitar: Vec<&str> = Vec::new();
     if let Some(ref nombre) = input.nombre {
         dto.nombre = Some(domain::normalizar_nombre(nombre));
     }
@@ -143,49 +211,225 @@ pub async fn update_empresa(
     if let Some(is_active) = input.is_active {
         dto.is_active = Some(is_active);
     }
+    if let Some(ref cedula) = input.cedula_juridica {
+        let cedula = cedula.trim().to_string();
+        if db::exists_by_cedula_juridica(&cedula, Some(&id)).await.map_err(map_db_error)? {
+            return Err(EmpresaError::RifExists);
+        }
+        dto.cedula_juridica = Some(cedula);
+    }
+    if let Some(contactos) = input.contactos {
+        dto.contactos = Some(domain::normalizar_contactos(contactos));
+    }
+
+    // Contratista principal (cadena vacía: deja de ser subcontratista)
+    match input.empresa_padre_id.as_deref().map(str::trim) {
+        Some("") => quitar.push("empresa_padre"),
+        Some(padre) => {
+            dto.empresa_padre = Some(verificar_empresa_padre(Some(&id), padre).await?);
+        }
+        None => {}
+    }
+
+    // Periodo de contrato: el resultado combinado con lo guardado debe ser coherente
+    let guardado = domain::PeriodoContrato::desde_empresa(&actual);
+    let (inicio, _) = domain::validar_periodo_contrato(input.contrato_inicio.as_deref(), None)?;
+    let (_, fin) = domain::validar_periodo_contrato(None, input.contrato_fin.as_deref())?;
+    let inicio_final = if input.contrato_inicio.is_some() { inicio } else { guardado.inicio };
+    let fin_final = if input.contrato_fin.is_some() { fin } else { guardado.fin };
+    if let (Some(i), Some(f)) = (inicio_final, fin_final) {
+        if f < i {
+            return Err(EmpresaError::Validation(
+                "El fin del contrato no puede ser anterior a su inicio".to_string(),
+            ));
+        }
+    }
+    if input.contrato_inicio.is_some() {
+        match inicio {
+            Some(fecha) => dto.contrato_inicio = Some(fecha_a_datetime(fecha)),
+            None => quitar.push("contrato_inicio"),
+        }
+    }
+    if input.contrato_fin.is_some() {
+        match fin {
+            Some(fecha) => dto.contrato_fin = Some(fecha_a_datetime(fecha)),
+            None => quitar.push("contrato_fin"),
+        }
+    }
     dto.updated_at = Some(surrealdb::Datetime::from(Utc::now()));
 
     info!("Actualizando empresa: {id_str}");
-    let updated = db::update(&id, dto).await.map_err(map_db_error)?;
+    let updated = db::update(&id, dto, &quitar).await.map_err(map_db_error)?;
 
     Ok(EmpresaResponse::from(updated))
 }
 
-/// Elimina una empresa del sistema verificando vínculos previos.
+/// Da de baja una empresa verificando vínculos previos.
 ///
-/// Regla de Integridad: No se puede eliminar si tiene contratistas asociados.
+/// Regla de Integridad: Solo se elimina físicamente si ningún contratista, proveedor,
+/// visitante ni subcontratista la referencia. En caso contrario se rechaza, salvo que se
+/// pida `desactivar_en_cascada`: entonces la empresa y sus subcontratistas quedan
+/// inactivas junto con su personal, conservando el historial.
 ///
 /// # Argumentos
-/// * `id_str` - ID de la empresa a borrar.
+/// * `id_str` - ID de la empresa a dar de baja.
+/// * `desactivar_en_cascada` - Desactivar en lugar de rechazar si tiene vínculos.
+/// * `usuario_id` - Usuario que pide la baja (queda en el historial de estados).
+/// * `search_service` - Buscador donde se refleja el nuevo estado del personal.
 ///
 /// # Retorno
-/// Ok(()) si la eliminación física fue exitosa.
+/// Resumen de la baja (eliminación física o desactivación en cascada).
 ///
 /// # Errores
-/// - `EmpresaError::HasContratistas`: Violación de integridad referencial.
+/// - `EmpresaError::NotFound`: La empresa no existe.
+/// - `EmpresaError::HasContratistas` / `HasProveedores` / `HasVisitantes` /
+///   `HasSubempresas`: Violación de integridad referencial.
+/// - `EmpresaError::PersonalDentro`: Hay personal del grupo dentro de las instalaciones.
 /// - `EmpresaError::Database`: Error en la operación de borrado.
-pub async fn delete_empresa(id_str: &str) -> Result<(), EmpresaError> {
+pub async fn delete_empresa(
+    id_str: &str,
+    desactivar_en_cascada: bool,
+    usuario_id: &str,
+    search_service: &Arc<SearchService>,
+) -> Result<BajaEmpresaResponse, EmpresaError> {
     let id = parse_empresa_id(id_str);
+    db::find_by_id(&id).await.map_err(map_db_error)?.ok_or(EmpresaError::NotFound)?;
 
     // Verificación de integridad referencial
-    let count = db::count_contratistas_by_empresa(&id).await.map_err(map_db_error)?;
-    if count > 0 {
-        return Err(EmpresaError::HasContratistas(count.try_into().unwrap_or(i64::MAX)));
+    let contratistas = db::count_contratistas_by_empresa(&id).await.map_err(map_db_error)?;
+    let proveedores = db::count_proveedores_by_empresa(&id).await.map_err(map_db_error)?;
+    let visitantes = db::count_visitantes_by_empresa(&id).await.map_err(map_db_error)?;
+    let subempresas = db::count_subempresas(&id).await.map_err(map_db_error)?;
+
+    if contratistas + proveedores + visitantes + subempresas == 0 {
+        db::delete(&id).await.map_err(map_db_error)?;
+        info!("Empresa eliminada físicamente: {id_str}");
+        return Ok(BajaEmpresaResponse {
+            eliminada: true,
+            empresas_desactivadas: 0,
+            contratistas_desactivados: 0,
+            proveedores_desactivados: 0,
+        });
     }
 
-    db::delete(&id).await.map_err(map_db_error)?;
+    if !desactivar_en_cascada {
+        let conteo = |n: usize| n.try_into().unwrap_or(i64::MAX);
+        return Err(if contratistas > 0 {
+            EmpresaError::HasContratistas(conteo(contratistas))
+        } else if proveedores > 0 {
+            EmpresaError::HasProveedores(conteo(proveedores))
+        } else if visitantes > 0 {
+            EmpresaError::HasVisitantes(conteo(visitantes))
+        } else {
+            EmpresaError::HasSubempresas(conteo(subempresas))
+        });
+    }
 
-    info!("Empresa eliminada físicamente: {id_str}");
-    Ok(())
+    // Desactivación en cascada: la empresa, sus subcontratistas y el personal de todas
+    let empresas = db::find_all().await.map_err(map_db_error)?;
+    let grupo = domain::empresa_y_subempresas(&id.to_string(), &domain::mapa_padres(&empresas));
+    let ids: Vec<RecordId> =
+        empresas.into_iter().filter(|e| grupo.contains(&e.id.to_string())).map(|e| e.id).collect();
+    let empresas_desactivadas = ids.len();
+
+    // No se deja a nadie dentro con su empresa dada de baja: primero deben salir
+    let dentro = db::count_personal_dentro(ids.clone()).await.map_err(map_db_error)?;
+    if dentro > 0 {
+        return Err(EmpresaError::PersonalDentro(dentro.try_into().unwrap_or(i64::MAX)));
+    }
+
+    let motivo = format!("Baja en cascada de la empresa {id_str}");
+    let (contratistas, proveedores) =
+        db::desactivar_en_cascada(ids, usuario_id, &motivo).await.map_err(map_db_error)?;
+    let contratistas_desactivados = contratistas.len();
+    let proveedores_desactivados = proveedores.len();
+
+    // El buscador debe reflejar el nuevo estado para no ofrecer personal inactivo
+    sincronizar_personal_en_buscador(search_service, &contratistas, &proveedores).await;
+
+    info!(
+        "🏢 Empresa {id_str} desactivada en cascada: {empresas_desactivadas} empresa(s), \
+         {contratistas_desactivados} contratista(s), {proveedores_desactivados} proveedor(es)"
+    );
+    Ok(BajaEmpresaResponse {
+        eliminada: false,
+        empresas_desactivadas,
+        contratistas_desactivados,
+        proveedores_desactivados,
+    })
+}
+
+/// Reindexa el personal desactivado en cascada; un fallo solo se registra.
+async fn sincronizar_personal_en_buscador(
+    search_service: &SearchService,
+    contratistas: &[RecordId],
+    proveedores: &[RecordId],
+) {
+    for id in contratistas {
+        match contratista_queries::find_by_id_fetched(id).await {
+            Ok(Some(c)) => {
+                let empresa_nombre = c.empresa.nombre.clone();
+                if let Err(e) = search_service.update_contratista_fetched(&c, &empresa_nombre).await
+                {
+                    warn!("⚠️ No se pudo reindexar el contratista {id}: {e}");
+                }
+            }
+            Ok(None) => {}
+            Err(e) => warn!("⚠️ No se pudo leer el contratista {id} para reindexarlo: {e}"),
+        }
+    }
+    for id in proveedores {
+        match proveedor_queries::find_by_id_fetched(id).await {
+            Ok(Some(p)) => {
+                let empresa_nombre = p.empresa.nombre.clone();
+                if let Err(e) = search_service.update_proveedor_fetched(&p, &empresa_nombre).await {
+                    warn!("⚠️ No se pudo reindexar el proveedor {id}: {e}");
+                }
+            }
+            Ok(None) => {}
+            Err(e) => warn!("⚠️ No se pudo leer el proveedor {id} para reindexarlo: {e}"),
+        }
+    }
 }
 
 // --------------------------------------------------------------------------
 // HELPERS INTERNOS
 // --------------------------------------------------------------------------
 
+/// Comprueba que `padre_str` exista y pueda ser la contratista principal de la empresa.
+async fn verificar_empresa_padre(
+    empresa_id: Option<&RecordId>,
+    padre_str: &str,
+) -> Result<RecordId, EmpresaError> {
+    let padre = parse_empresa_id(padre_str);
+    db::find_by_id(&padre).await.map_err(map_db_error)?.ok_or(EmpresaError::NotFound)?;
+
+    let empresas = db::find_all().await.map_err(map_db_error)?;
+    let empresa_id = empresa_id.map(ToString::to_string);
+    domain::validar_empresa_padre(
+        empresa_id.as_deref(),
+        &padre.to_string(),
+        &domain::mapa_padres(&empresas),
+    )?;
+    Ok(padre)
+}
+
+/// Fecha de contrato guardada a medianoche UTC.
+fn fecha_a_datetime(fecha: NaiveDate) -> Datetime {
+    Datetime::from(fecha.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+}
+
 /// Mapeo de errores de infraestructura a dominio.
 fn map_db_error(e: SurrealDbError) -> EmpresaError {
-    EmpresaError::Database(e.to_string())
+    // Carreras entre la verificación previa y la escritura las atrapan los índices únicos
+    let msg = e.to_string();
+    if msg.contains("idx_empresa_cedula_juridica") {
+        EmpresaError::RifExists
+    } else if msg.contains("idx_empresa_nombre") {
+        EmpresaError::NameExists
+    } else {
+        EmpresaError::Database(msg)
+    }
 }
 
 /// Normalización de IDs de empresa para `SurrealDB`.
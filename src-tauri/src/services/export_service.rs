@@ -16,9 +16,10 @@ use crate::domain::export as domain;
 use crate::export::errors::{ExportError, ExportResult};
 use crate::models::export::{
     CsvConfig, CsvDelimiter, ExcelConfig, ExportData, ExportFormat, ExportRequest, ExportResponse,
-    PageOrientation, PdfConfig,
+    ExportValue, PageOrientation, PdfConfig,
 };
 use log::{error, info, warn};
+use std::collections::HashMap;
 
 // ==========================================
 // FUNCIÓN PRINCIPAL DE EXPORTACIÓN
@@ -64,6 +65,72 @@ pub async fn export_data(request: ExportRequest) -> ExportResult<ExportResponse>
     let export_data = normalizar_export_data(&request)?;
 
     // 4. Exportar según formato
+    exportar_por_formato(export_data).await
+}
+
+/// Exporta filas ya tipadas, construidas en el backend (p.ej. reportes desde `SurrealDB`).
+///
+/// `options` aporta el formato y las opciones de salida (título, plantilla, delimitador,
+/// destino); sus `headers` y `rows` se ignoran.
+///
+/// # Errores
+/// * `ExportError::EmptyData` - Si no hay filas que exportar.
+/// * `ExportError::InvalidData` - Si se superan los límites de filas o los headers son inválidos.
+pub async fn export_rows(
+    options: &ExportRequest,
+    headers: Vec<String>,
+    rows: Vec<HashMap<String, ExportValue>>,
+) -> ExportResult<ExportResponse> {
+    info!(
+        "Iniciando exportación de reporte: formato={:?}, filas={}, título={:?}",
+        options.format,
+        rows.len(),
+        options.title
+    );
+
+    domain::validar_headers(&headers).map_err(ExportError::InvalidData)?;
+    if rows.is_empty() {
+        return Err(ExportError::EmptyData);
+    }
+    if rows.len() > domain::MAX_ROWS {
+        warn!("Reporte rechazado por tamaño: {} filas", rows.len());
+        return Err(ExportError::InvalidData(format!(
+            "Demasiadas filas. Máximo: {}, recibido: {}",
+            domain::MAX_ROWS,
+            rows.len()
+        )));
+    }
+
+    let format: ExportFormat = options.format.parse().map_err(ExportError::InvalidFormat)?;
+    let config_request = ExportRequest { headers: headers.clone(), ..options.clone() };
+
+    let export_data = ExportData {
+        pdf_config: if format == ExportFormat::Pdf {
+            Some(construir_pdf_config(&config_request)?)
+        } else {
+            None
+        },
+        excel_config: if format == ExportFormat::Excel {
+            Some(construir_excel_config(&config_request))
+        } else {
+            None
+        },
+        csv_config: if format == ExportFormat::Csv {
+            Some(construir_csv_config(&config_request)?)
+        } else {
+            None
+        },
+        format,
+        headers,
+        rows,
+        target_path: options.target_path.clone(),
+    };
+
+    exportar_por_formato(export_data).await
+}
+
+/// Delegación al generador correspondiente según el formato normalizado.
+async fn exportar_por_formato(export_data: ExportData) -> ExportResult<ExportResponse> {
     let result = match export_data.format {
         ExportFormat::Pdf => export_to_pdf_internal(export_data).await,
         ExportFormat::Excel => export_to_excel_internal(export_data).await,
@@ -92,7 +159,7 @@ fn normalizar_export_data(request: &ExportRequest) -> ExportResult<ExportData> {
     let headers = request.headers.clone();
 
     // 3. Normalizar todas las rows (JSON → ExportValue)
-    let rows: Vec<std::collections::HashMap<String, crate::models::export::ExportValue>> =
+    let rows: Vec<HashMap<String, ExportValue>> =
         request.rows.iter().map(|row| domain::normalizar_row(row, &headers)).collect();
 
     // 4. Construir config según formato
@@ -338,7 +405,7 @@ fn export_to_csv_internal(data: ExportData) -> ExportResult<ExportResponse> {
 // ==========================================
 
 /// Sanitiza un filename (remueve caracteres especiales)
-fn sanitizar_filename(filename: &str) -> String {
+pub(crate) fn sanitizar_filename(filename: &str) -> String {
     filename
         .chars()
         .map(|c| match c {
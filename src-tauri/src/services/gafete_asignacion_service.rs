@@ -0,0 +1,129 @@
+//! # Servicio de Asignación Automática de Gafetes
+//!
+//! Propone el siguiente gafete libre al registrar un ingreso y lo aparta unos minutos
+//! para la terminal que lo pidió, de modo que dos guardias no entreguen el mismo número.
+//!
+//! ## Responsabilidades
+//! - Aplicar la política configurada (`menor_numero` o `menos_usado`).
+//! - Respetar los rangos de números reservados por empresa.
+//! - Excluir gafetes con deudas pendientes (no devueltos).
+//! - Reservar de forma atómica el gafete propuesto.
+//!
+//! ## Dependencias
+//! - `crate::db::surrealdb_gafete_queries`: Candidatos y reservas.
+//! - `crate::domain::gafete`: Orden de candidatos.
+//! - `crate::config`: `GafeteConfig` (política, rangos y duración de la reserva).
+
+use crate::config::manager::load_config;
+use crate::config::settings::GafeteConfig;
+use crate::db::surrealdb_gafete_queries as db;
+use crate::domain::common::{datetime_to_iso, parse_record_id};
+use crate::domain::errors::GafeteError;
+use crate::domain::gafete::{self as domain, CandidatoGafete};
+use crate::models::gafete::{Gafete, GafeteResponse, PropuestaGafeteResponse};
+use chrono::Utc;
+use log::{info, warn};
+
+// --------------------------------------------------------------------------
+// FUNCIONES PÚBLICAS
+// --------------------------------------------------------------------------
+
+/// Propone y reserva el siguiente gafete libre del tipo indicado.
+///
+/// # Argumentos
+/// * `tipo` - Tipo de gafete (ej. "contratista", "visita").
+/// * `empresa_id` - Empresa del ingresante, para usar sus rangos reservados.
+/// * `usuario_id` - Guardia que hace la solicitud; la reserva queda a su nombre.
+///
+/// # Errores
+/// * `GafeteError::InvalidType` - Tipo desconocido.
+/// * `GafeteError::SinDisponibles` - No queda ningún gafete libre que proponer.
+/// * `GafeteError::Database` - Error de persistencia.
+pub async fn proponer_gafete(
+    tipo: &str,
+    empresa_id: Option<&str>,
+    usuario_id: &str,
+) -> Result<PropuestaGafeteResponse, GafeteError> {
+    let tipo_enum = domain::validar_tipo(tipo)?;
+    let usuario = parse_record_id(usuario_id, "user");
+    let config = config_gafetes();
+
+    let libres = db::find_candidatos_asignacion(tipo_enum.as_str(), &usuario)
+        .await
+        .map_err(|e| GafeteError::Database(e.to_string()))?;
+
+    // Un gafete adeudado sigue físicamente fuera aunque figure libre
+    let bloqueados = db::find_numeros_adeudados(tipo_enum.as_str())
+        .await
+        .map_err(|e| GafeteError::Database(e.to_string()))?;
+
+    let candidatos: Vec<CandidatoGafete> = libres.iter().map(candidato).collect();
+    let orden = domain::ordenar_candidatos(
+        &candidatos,
+        &tipo_enum,
+        config.politica,
+        &config.rangos_empresa,
+        empresa_id.filter(|e| !e.trim().is_empty()),
+        &bloqueados,
+    );
+
+    let hasta = Utc::now() + chrono::Duration::minutes(i64::from(config.reserva_minutos.max(1)));
+
+    for (numero, rango_empresa) in orden {
+        let Some(gafete) = libres.iter().find(|g| g.numero == numero) else {
+            continue;
+        };
+        // Otra terminal pudo adelantarse entre la consulta y la reserva: se prueba el siguiente
+        let reservado = db::reservar(&gafete.id, &usuario, surrealdb::Datetime::from(hasta))
+            .await
+            .map_err(|e| GafeteError::Database(e.to_string()))?;
+
+        if let Some(g) = reservado {
+            info!("🎫 Gafete {} ({}) propuesto y reservado para {usuario_id}", g.numero, g.tipo);
+            return Ok(PropuestaGafeteResponse {
+                gafete: GafeteResponse::from(g),
+                politica: config.politica,
+                rango_empresa,
+                reservado_hasta: hasta.to_rfc3339(),
+            });
+        }
+    }
+
+    Err(GafeteError::SinDisponibles(format!("no quedan gafetes libres de tipo {tipo_enum}")))
+}
+
+/// Libera la reserva de un gafete propuesto que finalmente no se entregó.
+///
+/// Solo afecta reservas del propio usuario; si ya expiró o se entregó no hace nada.
+pub async fn cancelar_reserva(
+    numero: i32,
+    tipo: &str,
+    usuario_id: &str,
+) -> Result<(), GafeteError> {
+    let tipo_enum = domain::validar_tipo(tipo)?;
+    let usuario = parse_record_id(usuario_id, "user");
+    db::liberar_reserva(numero, tipo_enum.as_str(), &usuario)
+        .await
+        .map_err(|e| GafeteError::Database(e.to_string()))
+}
+
+// --------------------------------------------------------------------------
+// HELPERS INTERNOS
+// --------------------------------------------------------------------------
+
+/// Configuración de gafetes vigente (valores por defecto si no se puede leer).
+fn config_gafetes() -> GafeteConfig {
+    load_config().map(|c| c.gafetes).unwrap_or_else(|e| {
+        warn!("⚠️ No se pudo leer la configuración de gafetes, usando valores por defecto: {e}");
+        GafeteConfig::default()
+    })
+}
+
+fn candidato(g: &Gafete) -> CandidatoGafete {
+    let ultimo_uso = g.ultimo_uso.as_ref().and_then(|dt| {
+        chrono::DateTime::parse_from_rfc3339(&datetime_to_iso(dt))
+            .ok()
+            .map(|f| f.timestamp_millis())
+    });
+    CandidatoGafete { numero: g.numero, ultimo_uso }
+}
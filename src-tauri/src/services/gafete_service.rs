@@ -6,6 +6,8 @@
 //! - Gestionar el inventario de gafetes (Altas, Bajas, Modificaciones).
 //! - Controlar la disponibilidad y estado (Activo, En Uso, Perdido).
 //! - Garantizar la integridad de las asignaciones (evitar duplicados físicos).
+//! - Mantener la cadena de custodia (`gafete_movimiento`): quién lo tuvo, cuándo y quién lo entregó.
+//! - Llevar el libro de deudas (`deuda_gafete`): quién debe qué gafete y desde cuándo.
 //!
 //! ## Dependencias
 //! - `crate::db::surrealdb_gafete_queries`: Persistencia.
@@ -14,7 +16,9 @@
 use crate::db::surrealdb_gafete_queries as db;
 use crate::domain::errors::GafeteError;
 use crate::models::gafete::{
-    CreateGafeteInput, CreateGafeteRangeInput, GafeteCreateDTO, GafeteEstado, GafeteResponse,
+    AccionGafete, ContextoMovimientoGafete, CreateGafeteInput, CreateGafeteRangeInput, DeudaGafete,
+    DeudaGafeteCreateDTO, DeudaGafeteResponse, Gafete, GafeteCreateDTO, GafeteEstado,
+    GafeteHistorialResponse, GafeteMovimientoCreateDTO, GafeteMovimientoResponse, GafeteResponse,
     TipoGafete,
 };
 use log::{error, info, warn};
@@ -37,6 +41,20 @@ fn parse_gafete_id(id_str: &str) -> Result<RecordId, GafeteError> {
     }
 }
 
+/// Reserva vigente de otra terminal sobre el gafete.
+fn reservado_por_otro(gafete: &Gafete, usuario: Option<&RecordId>) -> bool {
+    use crate::domain::common::datetime_to_iso;
+    use crate::domain::gafete as domain;
+    use chrono::{DateTime, Utc};
+
+    let hasta = gafete.reservado_hasta.as_ref().and_then(|dt| {
+        DateTime::parse_from_rfc3339(&datetime_to_iso(dt)).ok().map(|d| d.with_timezone(&Utc))
+    });
+    let por = gafete.reservado_por.as_ref().map(ToString::to_string);
+    let usuario = usuario.map(ToString::to_string);
+    domain::reservado_por_otro(hasta, por.as_deref(), usuario.as_deref(), Utc::now())
+}
+
 // --------------------------------------------------------------------------
 // FUNCIONES PÚBLICAS
 // --------------------------------------------------------------------------
@@ -47,17 +65,21 @@ fn parse_gafete_id(id_str: &str) -> Result<RecordId, GafeteError> {
 /// 1. El gafete debe existir en base de datos.
 /// 2. Su estado debe ser `Activo`.
 /// 3. No debe estar marcado como `en_uso`.
-/// 4. No debe tener alertas pendientes (gafete perdido/no devuelto).
+/// 4. No debe estar reservado (vigente) por otro usuario.
+/// 5. No debe tener una deuda pendiente (gafete no devuelto).
 ///
 /// # Argumentos
 /// * `numero` - Número identificador del gafete.
 /// * `tipo` - Tipo de gafete (ej. "contratista", "visita").
+/// * `usuario` - Guardia que lo entregaría; sus propias reservas no lo bloquean.
 ///
 /// # Retorno
 /// `true` si puede asignarse, `false` en caso contrario.
-pub async fn is_gafete_disponible(numero: i32, tipo: &str) -> Result<bool, GafeteError> {
-    use crate::db::surrealdb_alerta_queries as alerta_db;
-
+pub async fn is_gafete_disponible(
+    numero: i32,
+    tipo: &str,
+    usuario: Option<&RecordId>,
+) -> Result<bool, GafeteError> {
     match db::get_gafete(numero, tipo).await {
         Ok(Some(g)) => {
             // Criterio básico: estado activo y no en uso
@@ -65,13 +87,18 @@ pub async fn is_gafete_disponible(numero: i32, tipo: &str) -> Result<bool, Gafet
                 return Ok(false);
             }
 
-            // Criterio adicional: verificar si hay alerta pendiente para este gafete
-            let alertas_pendientes = alerta_db::find_all(Some(false)).await.unwrap_or_default();
+            if reservado_por_otro(&g, usuario) {
+                info!("Gafete {numero} ({tipo}) reservado por otra terminal - no disponible");
+                return Ok(false);
+            }
 
-            let tiene_alerta = alertas_pendientes.iter().any(|a| a.gafete_numero == numero);
+            // Criterio adicional: consulta indexada al libro de deudas (número + tipo)
+            let deuda = db::find_deuda_pendiente(numero, tipo)
+                .await
+                .map_err(|e| GafeteError::Database(e.to_string()))?;
 
-            if tiene_alerta {
-                warn!("Gafete {numero} tiene alerta pendiente - no disponible");
+            if let Some(d) = deuda {
+                warn!("Gafete {numero} ({tipo}) adeudado por {} - no disponible", d.cedula);
                 return Ok(false);
             }
 
@@ -85,28 +112,47 @@ pub async fn is_gafete_disponible(numero: i32, tipo: &str) -> Result<bool, Gafet
     }
 }
 
-/// Marca un gafete como entregado y en uso.
+/// Marca un gafete como entregado y en uso, y registra la entrega en su historial.
+///
+/// La entrega es atómica: solo procede si el gafete sigue libre y sin reserva vigente
+/// de otro usuario distinto de `contexto.usuario`.
 ///
 /// # Errores
 /// * `GafeteError::NotFound` - Si el gafete no existe.
+/// * `GafeteError::NotAvailable` - Ya se entregó o lo reservó otra terminal.
 /// * `GafeteError::Database` - Error de persistencia.
-pub async fn marcar_en_uso(numero: i32, tipo: &str) -> Result<(), GafeteError> {
+pub async fn marcar_en_uso(
+    numero: i32,
+    tipo: &str,
+    contexto: ContextoMovimientoGafete,
+) -> Result<(), GafeteError> {
     let gafete = db::get_gafete(numero, tipo)
         .await
         .map_err(|e| GafeteError::Database(e.to_string()))?
         .ok_or(GafeteError::NotFound)?;
 
-    db::set_gafete_uso(&gafete.id, true).await.map_err(|e| GafeteError::Database(e.to_string()))?;
+    let entregado = db::marcar_en_uso(&gafete.id, contexto.usuario.as_ref())
+        .await
+        .map_err(|e| GafeteError::Database(e.to_string()))?;
+    if !entregado {
+        warn!("Gafete {numero} ({tipo}) ya entregado o reservado por otra terminal");
+        return Err(GafeteError::NotAvailable);
+    }
 
+    registrar_movimiento(&gafete, AccionGafete::Asignado, contexto, None).await;
     Ok(())
 }
 
-/// Libera un gafete, marcándolo como disponible.
+/// Libera un gafete, marcándolo como disponible, y registra la devolución en su historial.
 ///
 /// # Errores
 /// * `GafeteError::NotFound` - Si el gafete no existe.
 /// * `GafeteError::Database` - Error de persistencia.
-pub async fn liberar_gafete(numero: i32, tipo: &str) -> Result<(), GafeteError> {
+pub async fn liberar_gafete(
+    numero: i32,
+    tipo: &str,
+    contexto: ContextoMovimientoGafete,
+) -> Result<(), GafeteError> {
     let gafete = db::get_gafete(numero, tipo)
         .await
         .map_err(|e| GafeteError::Database(e.to_string()))?
@@ -116,9 +162,139 @@ pub async fn liberar_gafete(numero: i32, tipo: &str) -> Result<(), GafeteError>
         .await
         .map_err(|e| GafeteError::Database(e.to_string()))?;
 
+    // El gafete volvió al inventario: quien lo debía ya no lo debe
+    match db::saldar_deudas(numero, tipo, contexto.usuario.clone()).await {
+        Ok(0) => {}
+        Ok(n) => info!("🧾 {n} deuda(s) del gafete {numero} ({tipo}) saldada(s)"),
+        Err(e) => error!("Error saldando deudas del gafete {numero} ({tipo}): {e}"),
+    }
+
+    registrar_movimiento(&gafete, AccionGafete::Devuelto, contexto, None).await;
+    Ok(())
+}
+
+/// Deja constancia de que la persona salió sin devolver el gafete y abre su deuda.
+///
+/// El gafete sigue en uso hasta que se devuelva o se resuelva la alerta. Si ya
+/// existe una deuda pendiente para el gafete no se abre otra.
+///
+/// # Errores
+/// * `GafeteError::NotFound` - Si el gafete no existe.
+/// * `GafeteError::Validation` - Si no se conoce la cédula del portador.
+/// * `GafeteError::Database` - Error de persistencia.
+pub async fn registrar_no_devuelto(
+    numero: i32,
+    tipo: &str,
+    contexto: ContextoMovimientoGafete,
+) -> Result<(), GafeteError> {
+    let gafete = db::get_gafete(numero, tipo)
+        .await
+        .map_err(|e| GafeteError::Database(e.to_string()))?
+        .ok_or(GafeteError::NotFound)?;
+
+    let cedula = contexto.cedula.clone().filter(|c| !c.trim().is_empty()).ok_or_else(|| {
+        GafeteError::Validation("Se requiere la cédula de quien adeuda el gafete".to_string())
+    })?;
+
+    let existente = db::find_deuda_pendiente(numero, tipo)
+        .await
+        .map_err(|e| GafeteError::Database(e.to_string()))?;
+
+    if existente.is_none() {
+        db::insert_deuda(DeudaGafeteCreateDTO {
+            gafete: gafete.id.clone(),
+            numero,
+            tipo: gafete.tipo.clone(),
+            cedula: cedula.clone(),
+            nombre: contexto.nombre.clone(),
+            ingreso: contexto.ingreso.clone(),
+            registrada_por: contexto.usuario.clone(),
+            notas: contexto.notas.clone(),
+            desde: None,
+        })
+        .await
+        .map_err(|e| GafeteError::Database(e.to_string()))?;
+        info!("🧾 Deuda abierta: gafete {numero} ({tipo}) a cargo de {cedula}");
+    }
+
+    registrar_movimiento(&gafete, AccionGafete::NoDevuelto, contexto, None).await;
     Ok(())
 }
 
+/// Migración de arranque: abre la deuda de cada alerta de gafete pendiente que aún no
+/// la tenga (alertas registradas antes de existir `deuda_gafete`).
+///
+/// Es idempotente: un gafete con deuda pendiente no recibe otra, así que puede
+/// ejecutarse en cada arranque. Retorna cuántas deudas se abrieron.
+pub async fn migrar_alertas_a_deudas() -> Result<usize, GafeteError> {
+    use crate::db::surrealdb_alerta_queries as alerta_db;
+    use crate::domain::gafete as domain;
+
+    let alertas =
+        alerta_db::find_all(Some(false)).await.map_err(|e| GafeteError::Database(e.to_string()))?;
+    if alertas.is_empty() {
+        return Ok(0);
+    }
+
+    let mut adeudados = Vec::new();
+    for tipo in [TipoGafete::Contratista, TipoGafete::Proveedor, TipoGafete::Visita] {
+        let numeros = db::find_numeros_adeudados(tipo.as_str())
+            .await
+            .map_err(|e| GafeteError::Database(e.to_string()))?;
+        adeudados.extend(numeros.into_iter().map(|n| (n, tipo.clone())));
+    }
+
+    let mut abiertas = 0;
+    for (alerta, tipo) in domain::alertas_sin_deuda(&alertas, &adeudados) {
+        let numero = alerta.gafete_numero;
+        let Some(gafete) = db::get_gafete(numero, tipo.as_str())
+            .await
+            .map_err(|e| GafeteError::Database(e.to_string()))?
+        else {
+            warn!("Alerta {} sin gafete {numero} ({tipo}) en inventario - no migrada", alerta.id);
+            continue;
+        };
+
+        db::insert_deuda(DeudaGafeteCreateDTO {
+            gafete: gafete.id,
+            numero,
+            tipo,
+            cedula: alerta.cedula.clone(),
+            nombre: Some(alerta.nombre_completo.clone()),
+            ingreso: alerta
+                .ingreso_contratista
+                .clone()
+                .or_else(|| alerta.ingreso_proveedor.clone())
+                .or_else(|| alerta.ingreso_visita.clone()),
+            registrada_por: Some(alerta.reportado_por.clone()),
+            notas: Some(format!("Migrada desde la alerta {}", alerta.id)),
+            desde: Some(alerta.fecha_reporte.clone()),
+        })
+        .await
+        .map_err(|e| GafeteError::Database(e.to_string()))?;
+        abiertas += 1;
+    }
+
+    if abiertas > 0 {
+        info!("🧾 {abiertas} deuda(s) de gafete abierta(s) a partir de alertas pendientes");
+    }
+    Ok(abiertas)
+}
+
+/// Gafetes que una persona adeuda, del más antiguo al más reciente.
+pub async fn get_deudas_by_cedula(cedula: &str) -> Result<Vec<DeudaGafete>, GafeteError> {
+    db::find_deudas_pendientes_by_cedula(cedula)
+        .await
+        .map_err(|e| GafeteError::Database(e.to_string()))
+}
+
+/// Todas las deudas de gafetes pendientes.
+pub async fn get_deudas_pendientes() -> Result<Vec<DeudaGafeteResponse>, GafeteError> {
+    let deudas =
+        db::find_deudas_pendientes().await.map_err(|e| GafeteError::Database(e.to_string()))?;
+    Ok(deudas.into_iter().map(DeudaGafeteResponse::from).collect())
+}
+
 /// Crea un único gafete manualmente.
 ///
 /// # Argumentos
@@ -368,13 +544,19 @@ pub async fn get_gafetes_disponibles(tipo_str: &str) -> Result<Vec<GafeteRespons
     Ok(gafetes.into_iter().map(GafeteResponse::from).collect())
 }
 
-/// Cambia el estado operativo de un gafete (ej. Perdido, Dañado).
+/// Cambia el estado operativo de un gafete (ej. Perdido, Dañado) y lo registra en su historial.
 pub async fn update_gafete_status(
     id_str: &str,
     estado: GafeteEstado,
+    contexto: ContextoMovimientoGafete,
 ) -> Result<GafeteResponse, GafeteError> {
     let id = parse_gafete_id(id_str)?;
 
+    let anterior = db::find_by_id(&id)
+        .await
+        .map_err(|e| GafeteError::Database(e.to_string()))?
+        .ok_or(GafeteError::NotFound)?;
+
     // Logs de auditoría importantes para cambios de estado
     if estado == GafeteEstado::Extraviado || estado == GafeteEstado::Danado {
         warn!("Marcando gafete {id_str} como {estado}");
@@ -384,9 +566,105 @@ pub async fn update_gafete_status(
         .await
         .map_err(|e| GafeteError::Database(e.to_string()))?;
 
+    if anterior.estado != gafete.estado {
+        let estados = Some((anterior.estado, gafete.estado.clone()));
+        registrar_movimiento(&gafete, AccionGafete::CambioEstado, contexto, estados).await;
+    }
+
     Ok(GafeteResponse::from(gafete))
 }
 
+/// Historia completa de un gafete: entregas, devoluciones y cambios de estado.
+///
+/// Incluye la última persona a la que se entregó, para ubicar gafetes extraviados.
+pub async fn get_historial(id_str: &str) -> Result<GafeteHistorialResponse, GafeteError> {
+    use crate::db::surrealdb_user_queries as user_db;
+    use crate::domain::gafete as domain;
+    use std::collections::HashMap;
+
+    let id = parse_gafete_id(id_str)?;
+    let gafete = db::find_by_id(&id)
+        .await
+        .map_err(|e| GafeteError::Database(e.to_string()))?
+        .ok_or(GafeteError::NotFound)?;
+
+    let movimientos =
+        db::find_movimientos(&id).await.map_err(|e| GafeteError::Database(e.to_string()))?;
+
+    let mut user_names: HashMap<String, String> = HashMap::new();
+    for usuario in movimientos.iter().filter_map(|m| m.usuario.as_ref()) {
+        let key = usuario.to_string();
+        if user_names.contains_key(&key) {
+            continue;
+        }
+        if let Ok(Some(user)) = user_db::find_by_id(usuario).await {
+            user_names.insert(key, format!("{} {}", user.nombre, user.apellido));
+        }
+    }
+
+    let ultimo = domain::ultimo_portador(movimientos.iter().map(|m| &m.accion));
+    let movimientos: Vec<GafeteMovimientoResponse> = movimientos
+        .into_iter()
+        .map(|m| {
+            let usuario_id = m.usuario.as_ref().map(ToString::to_string);
+            GafeteMovimientoResponse {
+                id: m.id.to_string(),
+                accion: m.accion,
+                cedula: m.cedula,
+                nombre: m.nombre,
+                ingreso_id: m.ingreso.as_ref().map(ToString::to_string),
+                usuario_nombre: usuario_id.as_ref().and_then(|u| user_names.get(u).cloned()),
+                usuario_id,
+                estado_anterior: m.estado_anterior,
+                estado_nuevo: m.estado_nuevo,
+                notas: m.notas,
+                fecha: format_datetime_iso(&m.fecha),
+            }
+        })
+        .collect();
+
+    let mut resp = GafeteResponse::from(gafete);
+    let ultimo_portador = ultimo.and_then(|(i, _)| movimientos.get(i).cloned());
+    // Si no lo ha devuelto, el último portador es quien lo tiene ahora
+    if let Some((_, true)) = ultimo {
+        resp.asignado_a = ultimo_portador.as_ref().and_then(|m| m.nombre.clone());
+    }
+
+    Ok(GafeteHistorialResponse { gafete: resp, ultimo_portador, movimientos })
+}
+
+/// Registra un movimiento en el historial del gafete.
+///
+/// No es fatal: un fallo aquí no debe bloquear el ingreso o la salida en portería.
+async fn registrar_movimiento(
+    gafete: &Gafete,
+    accion: AccionGafete,
+    contexto: ContextoMovimientoGafete,
+    estados: Option<(GafeteEstado, GafeteEstado)>,
+) {
+    let (estado_anterior, estado_nuevo) = estados.unzip();
+    let dto = GafeteMovimientoCreateDTO {
+        gafete: gafete.id.clone(),
+        numero: gafete.numero,
+        tipo: gafete.tipo.clone(),
+        accion: accion.clone(),
+        cedula: contexto.cedula,
+        nombre: contexto.nombre,
+        ingreso: contexto.ingreso,
+        usuario: contexto.usuario,
+        estado_anterior,
+        estado_nuevo,
+        notas: contexto.notas,
+    };
+
+    if let Err(e) = db::insert_movimiento(dto).await {
+        error!(
+            "Error no fatal: no se pudo registrar el movimiento '{accion}' del gafete {} ({}): {e}",
+            gafete.numero, gafete.tipo
+        );
+    }
+}
+
 /// Elimina un gafete del sistema.
 pub async fn delete_gafete(id_str: &str) -> Result<(), GafeteError> {
     let id = parse_gafete_id(id_str)?;
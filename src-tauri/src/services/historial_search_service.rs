@@ -0,0 +1,433 @@
+//! # Servicio: Búsqueda en el Historial de Ingresos (Tantivy)
+//!
+//! Mantiene un índice full-text separado con los movimientos de contratistas, proveedores
+//! y visitas, para búsquedas de investigación sobre textos libres (observaciones, motivo,
+//! anfitrión, placa) filtradas por rango de fechas.
+//!
+//! ## Sincronización
+//! El índice se actualiza de forma incremental a partir de `updated_at`: antes de buscar se
+//! indexan los ingresos creados o cerrados desde la última sincronización (como mucho una vez
+//! cada [`SYNC_MIN_INTERVAL`]). La marca de agua se persiste junto al índice, por lo que no
+//! hace falta instrumentar cada comando de ingreso (contratista, proveedor, visita o cita).
+//!
+//! La consulta usa `updated_at >= marca` para no perder filas escritas en el mismo instante
+//! que la marca; la marca guarda además los ids ya vistos en ese instante para no
+//! reindexarlos. Los borrados llegan como lápidas (`ingreso_eliminado`, creadas por eventos
+//! `DELETE` del schema) y se quitan del índice.
+
+use crate::db::surrealdb_ingreso_general_queries as ingreso_general_queries;
+use crate::domain::common::datetime_to_iso;
+use crate::models::ingreso::IngresoHistorialRow;
+use crate::search::errors::SearchError;
+use crate::search::historial::{
+    build_historial_schema, delete_ingreso, initialize_historial_index, register_tokenizers,
+    search_historial, upsert_ingreso, HistorialFields, HistorialFilters, HistorialSearchResult,
+};
+use crate::search::{commit_index, get_index_reader, get_index_writer};
+use crate::services::search_service::parse_fecha_limite;
+use chrono::{DateTime, Utc};
+use log::{debug, info, warn};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::PoisonError;
+use std::time::{Duration, Instant};
+use tantivy::{Index, IndexReader};
+use tokio::sync::Mutex;
+
+/// Archivo con la marca de agua de la última sincronización.
+const SYNC_STATE_FILE: &str = "sync_state.txt";
+
+/// Intervalo mínimo entre sincronizaciones disparadas por búsquedas.
+pub const SYNC_MIN_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Marca de agua: mayor fecha de cambio ya aplicada y los ids vistos en ese instante.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MarcaSync {
+    fecha: DateTime<Utc>,
+    ids: HashSet<String>,
+}
+
+impl MarcaSync {
+    /// Formato del archivo: la fecha RFC 3339 en la primera línea y un id por línea.
+    /// Los archivos antiguos (solo la fecha) se leen con el conjunto de ids vacío.
+    fn parse(raw: &str) -> Option<Self> {
+        let mut lineas = raw.lines().map(str::trim).filter(|l| !l.is_empty());
+        let fecha = DateTime::parse_from_rfc3339(lineas.next()?).ok()?.with_timezone(&Utc);
+        Some(Self { fecha, ids: lineas.map(str::to_string).collect() })
+    }
+
+    fn serializar(&self) -> String {
+        let mut ids: Vec<&str> = self.ids.iter().map(String::as_str).collect();
+        ids.sort_unstable();
+        std::iter::once(self.fecha.to_rfc3339().as_str()).chain(ids).collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// Cambio pendiente de aplicar en el índice.
+#[derive(Debug)]
+enum Cambio {
+    Upsert(Box<IngresoHistorialRow>),
+    Eliminado,
+}
+
+/// Deduplica los cambios por id (gana el más reciente; a igual fecha, el último de la
+/// lista), descarta los ya aplicados en el instante de la marca y calcula la marca nueva.
+fn cambios_pendientes<T>(
+    cambios: Vec<(String, DateTime<Utc>, T)>,
+    marca: Option<&MarcaSync>,
+) -> (Vec<(String, T)>, Option<MarcaSync>) {
+    let mut ultimo: HashMap<String, (DateTime<Utc>, T)> = HashMap::new();
+    for (id, fecha, cambio) in cambios {
+        if ultimo.get(&id).is_none_or(|(previa, _)| fecha >= *previa) {
+            ultimo.insert(id, (fecha, cambio));
+        }
+    }
+
+    let mut nueva = marca.cloned();
+    let mut pendientes = Vec::new();
+    for (id, (fecha, cambio)) in ultimo {
+        match &mut nueva {
+            Some(m) if fecha < m.fecha => {}
+            Some(m) if fecha == m.fecha => {
+                m.ids.insert(id.clone());
+            }
+            _ => nueva = Some(MarcaSync { fecha, ids: HashSet::from([id.clone()]) }),
+        }
+
+        let ya_aplicado = marca.is_some_and(|m| fecha == m.fecha && m.ids.contains(&id));
+        if !ya_aplicado {
+            pendientes.push((fecha, id, cambio));
+        }
+    }
+
+    pendientes.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+    (pendientes.into_iter().map(|(_, id, cambio)| (id, cambio)).collect(), nueva)
+}
+
+fn a_utc(fecha: &surrealdb::Datetime) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&datetime_to_iso(fecha)).ok().map(|f| f.with_timezone(&Utc))
+}
+
+/// Consulta sobre el historial de ingresos.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistorialQuery {
+    pub texto: Option<String>,
+    /// Tipos de ingreso (`contratista`, `proveedor`, `visita`).
+    #[serde(default)]
+    pub tipos: Vec<String>,
+    /// Rango de fecha de ingreso (YYYY-MM-DD, inclusivo).
+    pub desde: Option<String>,
+    pub hasta: Option<String>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+/// Orquestador del índice de historial de ingresos.
+pub struct HistorialSearchService {
+    index: Index,
+    reader: IndexReader,
+    fields: HistorialFields,
+    /// Serializa las sincronizaciones (un único writer a la vez).
+    writer_mutex: Mutex<()>,
+    /// Último cambio ya indexado. `None` = falta la carga completa.
+    watermark: std::sync::Mutex<Option<MarcaSync>>,
+    /// Momento de la última sincronización exitosa (para espaciar las de `search`).
+    ultima_sync: std::sync::Mutex<Option<Instant>>,
+    /// Ruta del archivo de marca de agua (`None` en índices en memoria).
+    state_path: Option<PathBuf>,
+}
+
+impl HistorialSearchService {
+    /// Abre o crea el índice de historial en el directorio especificado.
+    pub fn new(index_dir_path: &str) -> Result<Self, SearchError> {
+        let index_path = PathBuf::from(index_dir_path);
+        let (index, creado) = initialize_historial_index(&index_path)?;
+        let state_path = index_path.join(SYNC_STATE_FILE);
+
+        let watermark = if creado {
+            None
+        } else {
+            std::fs::read_to_string(&state_path).ok().and_then(|raw| MarcaSync::parse(&raw))
+        };
+
+        Self::with_index(index, watermark, Some(state_path))
+    }
+
+    /// Instancia en memoria para pruebas.
+    pub fn test_instance() -> Self {
+        let index = Index::create_in_ram(build_historial_schema());
+        register_tokenizers(&index);
+        Self::with_index(index, None, None).expect("Fallo al crear índice de historial en memoria")
+    }
+
+    fn with_index(
+        index: Index,
+        watermark: Option<MarcaSync>,
+        state_path: Option<PathBuf>,
+    ) -> Result<Self, SearchError> {
+        let fields = HistorialFields::new(&index.schema())?;
+        let reader = get_index_reader(&index)?;
+
+        Ok(Self {
+            index,
+            reader,
+            fields,
+            writer_mutex: Mutex::new(()),
+            watermark: std::sync::Mutex::new(watermark),
+            ultima_sync: std::sync::Mutex::new(None),
+            state_path,
+        })
+    }
+
+    fn watermark(&self) -> Option<MarcaSync> {
+        self.watermark.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    fn set_watermark(&self, marca: Option<MarcaSync>) {
+        if let Some(path) = &self.state_path {
+            let result = match &marca {
+                Some(marca) => std::fs::write(path, marca.serializar()),
+                None => std::fs::remove_file(path).or(Ok(())),
+            };
+            if let Err(e) = result {
+                warn!("⚠️ No se pudo persistir la marca de sincronización del historial: {e}");
+            }
+        }
+
+        *self.watermark.lock().unwrap_or_else(PoisonError::into_inner) = marca;
+    }
+
+    fn marcar_sync(&self) {
+        *self.ultima_sync.lock().unwrap_or_else(PoisonError::into_inner) = Some(Instant::now());
+    }
+
+    /// `true` si pasó [`SYNC_MIN_INTERVAL`] desde la última sincronización exitosa.
+    fn sync_vencida(&self) -> bool {
+        self.ultima_sync
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_none_or(|ultima| ultima.elapsed() >= SYNC_MIN_INTERVAL)
+    }
+
+    /// Indexa los ingresos creados, modificados o borrados desde la última sincronización.
+    ///
+    /// Retorna la cantidad de movimientos aplicados.
+    pub async fn sync(&self) -> Result<usize, SearchError> {
+        let _lock = self.writer_mutex.lock().await;
+        self.sync_locked().await
+    }
+
+    /// Descarta el índice y lo vuelve a poblar con todo el historial.
+    pub async fn reindex_all(&self) -> Result<usize, SearchError> {
+        let _lock = self.writer_mutex.lock().await;
+        info!("🔄 Reindexando historial de ingresos completo");
+
+        {
+            let mut writer = get_index_writer(&self.index)?;
+            writer
+                .delete_all_documents()
+                .map_err(|e| SearchError::TantivyError(format!("Error al limpiar índice: {e}")))?;
+            commit_index(&mut writer)?;
+        }
+        self.set_watermark(None);
+
+        self.sync_locked().await
+    }
+
+    async fn sync_locked(&self) -> Result<usize, SearchError> {
+        let marca = self.watermark();
+        let desde = marca.as_ref().map(|m| surrealdb::Datetime::from(m.fecha));
+
+        let rows = ingreso_general_queries::find_historial_rows(desde.clone())
+            .await
+            .map_err(|e| SearchError::DatabaseError(e.to_string()))?;
+        // En la carga completa no hay nada indexado que borrar.
+        let eliminados = match desde {
+            Some(desde) => ingreso_general_queries::find_historial_eliminados(desde)
+                .await
+                .map_err(|e| SearchError::DatabaseError(e.to_string()))?,
+            None => Vec::new(),
+        };
+
+        let cambios = rows
+            .into_iter()
+            .filter_map(|row| {
+                let fecha = a_utc(&row.updated_at)?;
+                Some((row.id.to_string(), fecha, Cambio::Upsert(Box::new(row))))
+            })
+            .chain(eliminados.into_iter().filter_map(|lapida| {
+                let fecha = a_utc(&lapida.eliminado_en)?;
+                Some((lapida.ingreso.to_string(), fecha, Cambio::Eliminado))
+            }))
+            .collect();
+
+        let (pendientes, nueva_marca) = cambios_pendientes(cambios, marca.as_ref());
+        if pendientes.is_empty() {
+            self.marcar_sync();
+            return Ok(0);
+        }
+
+        {
+            let mut writer = get_index_writer(&self.index)?;
+            for (id, cambio) in &pendientes {
+                match cambio {
+                    Cambio::Upsert(row) => upsert_ingreso(&writer, &self.fields, row)?,
+                    Cambio::Eliminado => delete_ingreso(&writer, &self.fields, id),
+                }
+            }
+            commit_index(&mut writer)?;
+        }
+
+        self.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
+        self.set_watermark(nueva_marca);
+        self.marcar_sync();
+
+        debug!("📥 Historial sincronizado: {} movimientos", pendientes.len());
+        Ok(pendientes.len())
+    }
+
+    /// Busca en el historial, sincronizando antes los movimientos recientes si la última
+    /// sincronización tiene más de [`SYNC_MIN_INTERVAL`].
+    ///
+    /// Si la sincronización falla (p.ej. base de datos no disponible) se busca sobre el
+    /// índice tal como está.
+    pub async fn search(
+        &self,
+        query: &HistorialQuery,
+    ) -> Result<HistorialSearchResult, SearchError> {
+        if self.sync_vencida() {
+            if let Err(e) = self.sync().await {
+                warn!("⚠️ No se pudo sincronizar el historial antes de buscar: {e}");
+            }
+        }
+
+        let filters = HistorialFilters {
+            tipos: query.tipos.clone(),
+            desde: query.desde.as_deref().map(|f| parse_fecha_limite(f, false)).transpose()?,
+            hasta: query.hasta.as_deref().map(|f| parse_fecha_limite(f, true)).transpose()?,
+        };
+
+        search_historial(
+            &self.index,
+            &self.reader.searcher(),
+            &self.fields,
+            query.texto.as_deref(),
+            &filters,
+            query.limit.unwrap_or(50),
+            query.offset.unwrap_or(0),
+        )
+    }
+
+    pub fn doc_count(&self) -> u64 {
+        self.reader.searcher().num_docs()
+    }
+}
+
+// --------------------------------------------------------------------------
+// PRUEBAS UNITARIAS
+// --------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_initialization() {
+        let service = HistorialSearchService::test_instance();
+        assert_eq!(service.doc_count(), 0);
+        assert!(service.watermark().is_none());
+    }
+
+    #[test]
+    fn test_new_crea_indice_y_recupera_marca() {
+        let path = std::env::temp_dir().join(format!("historial-{}", uuid::Uuid::new_v4()));
+        let path_str = path.to_string_lossy().to_string();
+
+        let service = HistorialSearchService::new(&path_str).unwrap();
+        assert!(service.watermark().is_none(), "Un índice nuevo requiere carga completa");
+
+        let marca = MarcaSync {
+            fecha: DateTime::parse_from_rfc3339("2025-03-01T10:00:00Z").unwrap().into(),
+            ids: HashSet::from(["ingreso_visita:a".to_string(), "ingreso_visita:b".to_string()]),
+        };
+        service.set_watermark(Some(marca.clone()));
+        drop(service);
+
+        let service = HistorialSearchService::new(&path_str).unwrap();
+        assert_eq!(service.watermark(), Some(marca));
+
+        drop(service);
+        let _ = std::fs::remove_dir_all(path);
+    }
+
+    fn fecha(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().into()
+    }
+
+    #[test]
+    fn test_marca_formato_antiguo_sin_ids() {
+        let marca = MarcaSync::parse("2025-03-01T10:00:00+00:00\n").unwrap();
+        assert_eq!(marca.fecha, fecha("2025-03-01T10:00:00Z"));
+        assert!(marca.ids.is_empty());
+    }
+
+    #[test]
+    fn test_cambios_en_el_instante_de_la_marca() {
+        let t0 = fecha("2025-03-01T10:00:00Z");
+        let marca = MarcaSync { fecha: t0, ids: HashSet::from(["a".to_string()]) };
+
+        // `a` ya estaba indexado; `b` se escribió en el mismo instante pero no se había visto.
+        let (pendientes, nueva) = cambios_pendientes(
+            vec![("a".to_string(), t0, 1), ("b".to_string(), t0, 2)],
+            Some(&marca),
+        );
+
+        assert_eq!(pendientes, vec![("b".to_string(), 2)]);
+        let nueva = nueva.unwrap();
+        assert_eq!(nueva.fecha, t0);
+        assert_eq!(nueva.ids, HashSet::from(["a".to_string(), "b".to_string()]));
+    }
+
+    #[test]
+    fn test_cambios_deduplica_por_id_y_avanza_marca() {
+        let t0 = fecha("2025-03-01T10:00:00Z");
+        let t1 = fecha("2025-03-01T10:05:00Z");
+
+        // El mismo ingreso se actualizó en t0 y se borró en t1: solo cuenta el borrado.
+        let (pendientes, nueva) = cambios_pendientes(
+            vec![
+                ("a".to_string(), t0, "upsert"),
+                ("c".to_string(), t0, "upsert"),
+                ("a".to_string(), t1, "eliminado"),
+            ],
+            None,
+        );
+
+        assert_eq!(pendientes, vec![("c".to_string(), "upsert"), ("a".to_string(), "eliminado")]);
+        let nueva = nueva.unwrap();
+        assert_eq!(nueva.fecha, t1);
+        assert_eq!(nueva.ids, HashSet::from(["a".to_string()]));
+    }
+
+    #[test]
+    fn test_cambios_sin_novedades_conserva_marca() {
+        let t0 = fecha("2025-03-01T10:00:00Z");
+        let marca = MarcaSync { fecha: t0, ids: HashSet::from(["a".to_string()]) };
+
+        let (pendientes, nueva) = cambios_pendientes(vec![("a".to_string(), t0, ())], Some(&marca));
+
+        assert!(pendientes.is_empty());
+        assert_eq!(nueva, Some(marca));
+    }
+
+    #[test]
+    fn test_sync_vencida_se_espacia() {
+        let service = HistorialSearchService::test_instance();
+        assert!(service.sync_vencida());
+
+        service.marcar_sync();
+        assert!(!service.sync_vencida());
+    }
+}
@@ -0,0 +1,445 @@
+//! # Servicio: Importación Masiva de Personas
+//!
+//! Alta de contratistas, proveedores y visitantes desde un `.csv` o `.xlsx`.
+//!
+//! ## Validación por fila
+//! Cada fila pasa por las mismas reglas del alta manual:
+//! - `validar_create_input` del módulo correspondiente (formato de cédula, nombres, PRAIND).
+//! - Empresa existente y activa (por ID o por nombre).
+//! - Cédula en lista negra y, para contratistas y proveedores, empresa vetada con
+//!   bloqueo de registros.
+//! - Cédula ya registrada o repetida dentro del mismo archivo.
+//!
+//! ## Aplicación
+//! `importar` vuelve a analizar el archivo y registra las filas válidas en una sola
+//! sentencia `INSERT` (si falla, no se crea ninguna); después actualiza el índice de
+//! búsqueda. Las filas con errores se devuelven como rechazadas.
+
+use crate::db::{
+    surrealdb_contratista_queries as contratista_db, surrealdb_empresa_queries as empresa_db,
+    surrealdb_lista_negra_queries as lista_negra_db, surrealdb_proveedor_queries as proveedor_db,
+    surrealdb_visitante_queries as visitante_db,
+};
+use crate::domain::errors::ImportacionError;
+use crate::domain::importacion::{self as domain, FilaImportacion, MAX_FILAS_IMPORTACION};
+use crate::domain::{contratista as contratista_domain, visitante as visitante_domain};
+use crate::export::csv::parse_csv;
+use crate::models::contratista::{ContratistaCreateDTO, EstadoContratista};
+use crate::models::importacion::{
+    FilaImportacionReporte, ImportacionInput, ImportacionResponse, PrevisualizacionImportacion,
+    TipoImportacion,
+};
+use crate::models::proveedor::{EstadoProveedor, ProveedorCreateDTO};
+use crate::models::visitante::VisitanteCreateDTO;
+use crate::services::search_service::SearchService;
+use log::{info, warn};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use surrealdb::RecordId;
+
+/// Fila leída junto con la empresa resuelta y sus errores.
+struct FilaAnalizada {
+    fila: FilaImportacion,
+    /// `(id, nombre)` de la empresa resuelta.
+    empresa: Option<(String, String)>,
+    errores: Vec<String>,
+}
+
+impl FilaAnalizada {
+    fn reporte(&self) -> FilaImportacionReporte {
+        let nombre_completo = self.fila.nombre_completo();
+        FilaImportacionReporte {
+            fila: self.fila.numero,
+            cedula: Some(self.fila.cedula.clone()).filter(|c| !c.is_empty()),
+            nombre_completo: Some(nombre_completo).filter(|n| !n.is_empty()),
+            empresa: self
+                .empresa
+                .as_ref()
+                .map(|(_, nombre)| nombre.clone())
+                .or_else(|| self.fila.empresa.clone()),
+            valida: self.errores.is_empty(),
+            errores: self.errores.clone(),
+        }
+    }
+}
+
+struct Analisis {
+    columnas_ignoradas: Vec<String>,
+    filas: Vec<FilaAnalizada>,
+}
+
+// --------------------------------------------------------------------------
+// OPERACIONES PÚBLICAS
+// --------------------------------------------------------------------------
+
+/// Valida el archivo fila por fila sin escribir nada (dry-run).
+///
+/// # Errors
+///
+/// * `ImportacionError::FormatoNoSoportado`: La extensión no es `.csv`, `.txt` ni `.xlsx`.
+/// * `ImportacionError::ArchivoInvalido`: Archivo vacío, demasiadas filas o faltan
+///   columnas obligatorias.
+/// * `ImportacionError::EmpresaNotFound`: La empresa por defecto no existe o está inactiva.
+pub async fn previsualizar(
+    input: &ImportacionInput,
+) -> Result<PrevisualizacionImportacion, ImportacionError> {
+    let analisis = analizar(input).await?;
+    let filas: Vec<FilaImportacionReporte> =
+        analisis.filas.iter().map(FilaAnalizada::reporte).collect();
+    let validas = filas.iter().filter(|f| f.valida).count();
+
+    Ok(PrevisualizacionImportacion {
+        tipo: input.tipo,
+        total: filas.len(),
+        validas,
+        con_errores: filas.len() - validas,
+        columnas_ignoradas: analisis.columnas_ignoradas,
+        filas,
+    })
+}
+
+/// Registra las filas válidas del archivo y devuelve las rechazadas.
+///
+/// El archivo se vuelve a validar al aplicar, por si cambió después de la vista previa.
+///
+/// # Errors
+///
+/// * `ImportacionError::SinFilasValidas`: Ninguna fila pasó la validación.
+/// * `ImportacionError::Database`: Falló la inserción (no se creó ningún registro).
+pub async fn importar(
+    input: &ImportacionInput,
+    search_service: &Arc<SearchService>,
+    importado_por: &str,
+) -> Result<ImportacionResponse, ImportacionError> {
+    let analisis = analizar(input).await?;
+    let (validas, rechazadas): (Vec<FilaAnalizada>, Vec<FilaAnalizada>) =
+        analisis.filas.into_iter().partition(|f| f.errores.is_empty());
+
+    if validas.is_empty() {
+        return Err(ImportacionError::SinFilasValidas);
+    }
+
+    let creados = match input.tipo {
+        TipoImportacion::Contratista => crear_contratistas(&validas, search_service).await?,
+        TipoImportacion::Proveedor => crear_proveedores(&validas, search_service).await?,
+        TipoImportacion::Visitante => crear_visitantes(&validas).await?,
+    };
+
+    info!(
+        "📥 Importación de {}s: {creados} creado(s), {} rechazado(s), archivo={}, por={importado_por}",
+        input.tipo.as_str(),
+        rechazadas.len(),
+        input.file_path
+    );
+
+    Ok(ImportacionResponse {
+        tipo: input.tipo,
+        creados,
+        rechazadas: rechazadas.iter().map(FilaAnalizada::reporte).collect(),
+    })
+}
+
+// --------------------------------------------------------------------------
+// ALTA POR TIPO
+// --------------------------------------------------------------------------
+
+async fn crear_contratistas(
+    validas: &[FilaAnalizada],
+    search_service: &Arc<SearchService>,
+) -> Result<usize, ImportacionError> {
+    let mut dtos = Vec::with_capacity(validas.len());
+    for f in validas {
+        let praind = f.fila.fecha_vencimiento_praind.as_deref().unwrap_or_default();
+        let fecha = contratista_domain::validar_fecha(praind)
+            .map_err(|e| ImportacionError::ArchivoInvalido(e.to_string()))?;
+        dtos.push(ContratistaCreateDTO {
+            cedula: f.fila.cedula.clone(),
+            nombre: f.fila.nombre.trim().to_string(),
+            segundo_nombre: f.fila.segundo_nombre.as_ref().map(|s| s.trim().to_string()),
+            apellido: f.fila.apellido.trim().to_string(),
+            segundo_apellido: f.fila.segundo_apellido.as_ref().map(|s| s.trim().to_string()),
+            empresa: empresa_record_id(f)?,
+            fecha_vencimiento_praind: surrealdb::Datetime::from(fecha),
+            estado: EstadoContratista::Activo,
+        });
+    }
+
+    let creados = contratista_db::create_lote(dtos)
+        .await
+        .map_err(|e| ImportacionError::Database(e.to_string()))?;
+
+    for contratista in &creados {
+        let empresa_nombre = contratista.empresa.nombre.clone();
+        if let Err(e) = search_service.add_contratista_fetched(contratista, &empresa_nombre).await {
+            warn!("⚠️ Falló la indexación del contratista importado {}: {e}", contratista.cedula);
+        }
+    }
+
+    Ok(creados.len())
+}
+
+async fn crear_proveedores(
+    validas: &[FilaAnalizada],
+    search_service: &Arc<SearchService>,
+) -> Result<usize, ImportacionError> {
+    let dtos = validas
+        .iter()
+        .map(|f| {
+            Ok(ProveedorCreateDTO {
+                cedula: f.fila.cedula.clone(),
+                nombre: f.fila.nombre.trim().to_string(),
+                segundo_nombre: f.fila.segundo_nombre.as_ref().map(|s| s.trim().to_string()),
+                apellido: f.fila.apellido.trim().to_string(),
+                segundo_apellido: f.fila.segundo_apellido.as_ref().map(|s| s.trim().to_string()),
+                empresa: empresa_record_id(f)?,
+                estado: EstadoProveedor::Activo,
+            })
+        })
+        .collect::<Result<Vec<_>, ImportacionError>>()?;
+
+    let creados = proveedor_db::create_lote(dtos)
+        .await
+        .map_err(|e| ImportacionError::Database(e.to_string()))?;
+
+    for proveedor in &creados {
+        let empresa_nombre = proveedor.empresa.nombre.clone();
+        if let Err(e) = search_service.add_proveedor_fetched(proveedor, &empresa_nombre).await {
+            warn!("⚠️ Falló la indexación del proveedor importado {}: {e}", proveedor.cedula);
+        }
+    }
+
+    Ok(creados.len())
+}
+
+async fn crear_visitantes(validas: &[FilaAnalizada]) -> Result<usize, ImportacionError> {
+    let dtos = validas
+        .iter()
+        .map(|f| {
+            Ok(VisitanteCreateDTO {
+                cedula: f.fila.cedula.clone(),
+                nombre: visitante_domain::normalizar_nombre(&f.fila.nombre),
+                apellido: visitante_domain::normalizar_nombre(&f.fila.apellido),
+                segundo_nombre: f
+                    .fila
+                    .segundo_nombre
+                    .as_deref()
+                    .map(visitante_domain::normalizar_nombre),
+                segundo_apellido: f
+                    .fila
+                    .segundo_apellido
+                    .as_deref()
+                    .map(visitante_domain::normalizar_nombre),
+                empresa: empresa_record_id(f)?,
+                has_vehicle: false,
+            })
+        })
+        .collect::<Result<Vec<_>, ImportacionError>>()?;
+
+    let creados = visitante_db::create_lote(dtos)
+        .await
+        .map_err(|e| ImportacionError::Database(e.to_string()))?;
+
+    Ok(creados.len())
+}
+
+// --------------------------------------------------------------------------
+// ANÁLISIS
+// --------------------------------------------------------------------------
+
+/// Lee el archivo, ubica las columnas y valida cada fila contra el dominio y la base.
+async fn analizar(input: &ImportacionInput) -> Result<Analisis, ImportacionError> {
+    let mut filas = leer_archivo(input)?;
+    if filas.is_empty() {
+        return Err(ImportacionError::ArchivoInvalido("El archivo está vacío".to_string()));
+    }
+    let encabezados = filas.remove(0);
+    if filas.len() > MAX_FILAS_IMPORTACION {
+        return Err(ImportacionError::ArchivoInvalido(format!(
+            "El archivo tiene {} filas; el máximo por importación es {MAX_FILAS_IMPORTACION}",
+            filas.len()
+        )));
+    }
+
+    let empresas: Vec<(String, String)> = empresa_db::find_all()
+        .await
+        .map_err(|e| ImportacionError::Database(e.to_string()))?
+        .into_iter()
+        .filter(|e| e.is_active)
+        .map(|e| (e.id.to_string(), e.nombre))
+        .collect();
+
+    let empresa_defecto = match input.empresa_id.as_deref().filter(|id| !id.trim().is_empty()) {
+        Some(id) => {
+            Some(domain::resolver_empresa(id, &empresas).ok_or(ImportacionError::EmpresaNotFound)?)
+        }
+        None => None,
+    };
+
+    let mapa = domain::mapear_columnas(input.tipo, &encabezados, empresa_defecto.is_some())?;
+    let leidas: Vec<FilaImportacion> = filas
+        .iter()
+        .enumerate()
+        .map(|(i, fila)| domain::leer_fila(input.tipo, &mapa, fila, i + 2))
+        .collect();
+    let repetidas = domain::cedulas_repetidas(&leidas);
+
+    // Veto por empresa: se consulta una sola vez por archivo.
+    let mut vetos: HashMap<String, Option<String>> = HashMap::new();
+    let mut analizadas = Vec::with_capacity(leidas.len());
+
+    for (fila, repetida) in leidas.into_iter().zip(repetidas) {
+        let mut errores = Vec::new();
+
+        let empresa = match fila.empresa.as_deref() {
+            Some(valor) => {
+                let resuelta = domain::resolver_empresa(valor, &empresas);
+                if resuelta.is_none() {
+                    errores.push(format!("Empresa '{valor}' no encontrada o inactiva"));
+                }
+                resuelta
+            }
+            None => {
+                if empresa_defecto.is_none() {
+                    errores.push("Falta la empresa".to_string());
+                }
+                empresa_defecto
+            }
+        }
+        .cloned();
+
+        if let Err(e) =
+            domain::validar_fila(input.tipo, &fila, empresa.as_ref().map(|(id, _)| id.as_str()))
+        {
+            errores.push(e);
+        }
+        if let Some(primera) = repetida {
+            errores.push(format!("Cédula repetida en el archivo (fila {primera})"));
+        }
+
+        // Las consultas a la base solo se hacen para filas con formato válido.
+        if errores.is_empty() {
+            if let Some((empresa_id, empresa_nombre)) = &empresa {
+                errores.extend(verificar_en_base(input.tipo, &fila.cedula).await?);
+
+                if input.tipo != TipoImportacion::Visitante {
+                    let veto = match vetos.get(empresa_id) {
+                        Some(veto) => veto.clone(),
+                        None => {
+                            let veto = verificar_veto(empresa_id, empresa_nombre).await?;
+                            vetos.insert(empresa_id.clone(), veto.clone());
+                            veto
+                        }
+                    };
+                    errores.extend(veto);
+                }
+            }
+        }
+
+        analizadas.push(FilaAnalizada { fila, empresa, errores });
+    }
+
+    Ok(Analisis { columnas_ignoradas: mapa.ignoradas, filas: analizadas })
+}
+
+/// Lista negra y cédula ya registrada.
+async fn verificar_en_base(
+    tipo: TipoImportacion,
+    cedula: &str,
+) -> Result<Vec<String>, ImportacionError> {
+    let mut errores = Vec::new();
+
+    let bloqueo = lista_negra_db::check_if_blocked_by_cedula(cedula)
+        .await
+        .map_err(|e| ImportacionError::Database(e.to_string()))?;
+    if bloqueo.is_blocked {
+        let nivel = bloqueo.nivel_severidad.unwrap_or_else(|| "BAJO".to_string());
+        warn!("🚨 Importación: cédula {cedula} en lista negra (Nivel: {nivel})");
+        errores.push(format!(
+            "BLOQUEO DE SEGURIDAD: La cédula figura en la lista negra (Nivel: {nivel})"
+        ));
+    }
+
+    let existe = match tipo {
+        TipoImportacion::Contratista => {
+            contratista_db::find_by_cedula(cedula).await.map(|c| c.is_some())
+        }
+        TipoImportacion::Proveedor => {
+            proveedor_db::find_by_cedula(cedula).await.map(|p| p.is_some())
+        }
+        TipoImportacion::Visitante => {
+            visitante_db::get_visitante_by_cedula(cedula).await.map(|v| v.is_some())
+        }
+    }
+    .map_err(|e| ImportacionError::Database(e.to_string()))?;
+    if existe {
+        errores.push(format!("Ya existe un {} con esta cédula", tipo.as_str()));
+    }
+
+    Ok(errores)
+}
+
+/// Mensaje de rechazo si la empresa está vetada con bloqueo de registros.
+async fn verificar_veto(
+    empresa_id: &str,
+    empresa_nombre: &str,
+) -> Result<Option<String>, ImportacionError> {
+    let veto = lista_negra_db::check_empresa_vetada(&parse_empresa_id(empresa_id))
+        .await
+        .map_err(|e| ImportacionError::Database(e.to_string()))?;
+
+    Ok(veto
+        .filter(|v| v.bloquear_registros)
+        .map(|v| format!("EMPRESA VETADA: {empresa_nombre} (Nivel: {})", v.nivel_severidad)))
+}
+
+// --------------------------------------------------------------------------
+// LECTURA DEL ARCHIVO
+// --------------------------------------------------------------------------
+
+/// Lee todas las filas del archivo (encabezado incluido) según su extensión.
+fn leer_archivo(input: &ImportacionInput) -> Result<Vec<Vec<String>>, ImportacionError> {
+    let extension = Path::new(&input.file_path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let contenido = std::fs::read(&input.file_path)
+        .map_err(|e| ImportacionError::IO(format!("No se pudo leer {}: {e}", input.file_path)))?;
+
+    match extension.as_str() {
+        "csv" | "txt" => {
+            let texto = std::str::from_utf8(&contenido).map_err(|_| {
+                ImportacionError::ArchivoInvalido("El CSV no está en UTF-8".to_string())
+            })?;
+            // Excel agrega BOM al guardar como "CSV UTF-8"
+            let texto = texto.trim_start_matches('\u{feff}');
+            let delimitador =
+                input.delimiter.unwrap_or_else(|| domain::detectar_delimitador(texto));
+            parse_csv(texto, delimitador)
+                .map_err(|e| ImportacionError::ArchivoInvalido(e.to_string()))
+        }
+        #[cfg(feature = "export-excel")]
+        "xlsx" => crate::export::parse_xlsx(&contenido)
+            .map_err(|e| ImportacionError::ArchivoInvalido(e.to_string())),
+        otra => Err(ImportacionError::FormatoNoSoportado(format!(".{otra}"))),
+    }
+}
+
+// --------------------------------------------------------------------------
+// HELPERS
+// --------------------------------------------------------------------------
+
+fn parse_empresa_id(id_str: &str) -> RecordId {
+    match id_str.split_once(':') {
+        Some((tabla, clave)) => RecordId::from_table_key(tabla, clave),
+        None => RecordId::from_table_key("empresa", id_str),
+    }
+}
+
+fn empresa_record_id(fila: &FilaAnalizada) -> Result<RecordId, ImportacionError> {
+    fila.empresa
+        .as_ref()
+        .map(|(id, _)| parse_empresa_id(id))
+        .ok_or(ImportacionError::EmpresaNotFound)
+}
@@ -9,20 +9,26 @@
 /// - Registro físico de entrada con asignación de recursos.
 /// - Control de salida y liberación de gafetes.
 /// - Monitoreo de tiempos de permanencia.
+use crate::domain::documento_contratista as documentos;
+use crate::domain::empresa::{self as empresa_domain, PeriodoContrato};
 use crate::domain::errors::IngresoContratistaError;
 use crate::repositories::traits::{
     ContratistaRepository, GafeteRepository, IngresoContratistaRepository, SecurityRepository,
 };
 
+use crate::domain::lista_negra::NombrePersona;
 use crate::domain::motor_validacion as motor;
-use crate::models::contratista::ContratistaResponse;
+use crate::models::contratista::{ContratistaFetched, ContratistaResponse};
+use crate::models::documento_contratista::DocumentoPendiente;
+use crate::models::gafete::{ContextoMovimientoGafete, DeudaGafete};
 use crate::models::ingreso::{
     AlertaTiempoExcedido, CreateIngresoContratistaInput, IngresoConEstadoResponse, IngresoResponse,
     RegistrarSalidaInput, ResultadoValidacionSalida, ValidacionIngresoResponse,
 };
-use crate::models::lista_negra::BlockStatus;
+use crate::models::lista_negra::{BlockStatus, CoincidenciaListaNegra};
 use crate::models::validation::{
-    EstadoAutorizacion, InfoListaNegra, MotorContexto, NivelSeveridad, TipoAcceso, ValidationStatus,
+    EstadoAutorizacion, InfoBloqueoPropuesto, InfoCoincidenciaListaNegra, InfoEmpresaVetada,
+    InfoListaNegra, MotorContexto, NivelSeveridad, TipoAcceso, ValidationReason, ValidationStatus,
 };
 use log::{error, info, warn};
 use surrealdb::RecordId;
@@ -55,9 +61,14 @@ where
         Self { ingreso_repo, gafete_repo, contratista_repo, security_repo }
     }
 
+    /// Pre-chequeo de ingreso.
+    ///
+    /// `area` es el área a la que se dirige el contratista; sin ella no se evalúan
+    /// los documentos exigidos por área.
     pub async fn validar_ingreso_contratista(
         &self,
         contratista_id_str: String,
+        area: Option<String>,
     ) -> Result<ValidacionIngresoResponse, IngresoContratistaError> {
         let contratista_id = parse_contratista_id(&contratista_id_str)
             .unwrap_or_else(|_| RecordId::from_table_key("contratista", &contratista_id_str));
@@ -82,12 +93,20 @@ where
         let estado_praind = crate::domain::contratista::calcular_estado_praind(clean_date);
         let praind_vencido = estado_praind.vencido;
 
-        let estado_autorizacion_calculado = if praind_vencido {
+        let estado_autorizacion_base = if praind_vencido {
             EstadoAutorizacion::Vencido
         } else {
             EstadoAutorizacion::from_str_lossy(contratista.estado.as_str())
         };
 
+        // Documentos exigidos (póliza, inducción, certificados...) faltantes o vencidos
+        let documentos_pendientes =
+            self.documentos_pendientes(&contratista, area.as_deref()).await?;
+        let estado_autorizacion_calculado = documentos::estado_autorizacion_con_documentos(
+            estado_autorizacion_base,
+            &documentos_pendientes,
+        );
+
         let b = self.security_repo.check_if_blocked_by_cedula(&contratista.cedula).await.unwrap_or(
             BlockStatus { is_blocked: false, nivel_severidad: None, bloqueado_desde: None },
         );
@@ -109,18 +128,45 @@ where
                 contratista: None,
                 tiene_ingreso_abierto: true,
                 ingreso_abierto: Some(resp),
+                posibles_coincidencias: vec![],
+                requiere_confirmacion: false,
             });
         }
 
-        // Consultar alertas de gafete pendientes (deudas de gafetes no devueltos)
-        let alertas_pendientes =
-            crate::services::alerta_service::find_pendientes_by_cedula(&contratista.cedula)
-                .await
-                .unwrap_or_default();
+        // Otra cédula con un nombre parecido a un bloqueo vigente (pasaporte, cédula nueva)
+        let coincidencias =
+            if b.is_blocked { Vec::new() } else { self.posibles_coincidencias(&contratista).await };
+
+        // Bloqueo propuesto por un guardia que aún espera la aprobación de un supervisor
+        let bloqueo_propuesto = if b.is_blocked {
+            None
+        } else {
+            self.security_repo.check_bloqueo_propuesto(&contratista.cedula).await.unwrap_or_else(
+                |e| {
+                    warn!(
+                        "No se pudo consultar bloqueos propuestos de {}: {e}",
+                        contratista.cedula
+                    );
+                    None
+                },
+            )
+        };
+
+        // Veto sobre la empresa del contratista
+        let veto_empresa = self.veto_empresa(&contratista).await?;
+
+        // Consultar el libro de deudas (gafetes no devueltos)
+        let deudas_pendientes =
+            self.gafete_repo.deudas_pendientes(&contratista.cedula).await.unwrap_or_else(|e| {
+                warn!("No se pudieron consultar deudas de gafete de {}: {e}", contratista.cedula);
+                Vec::new()
+            });
 
-        let _tiene_deuda_gafete = !alertas_pendientes.is_empty();
         let alerta_gafete_msg =
-            alertas_pendientes.first().map(|a| format!("Debe gafete #{}", a.gafete_numero));
+            deudas_pendientes.first().map(|d| format!("Debe gafete #{} ({})", d.numero, d.tipo));
+
+        // Periodo de contrato de la empresa y de sus contratistas principales
+        let contrato_empresa = self.contrato_empresa(&contratista).await;
 
         let motor_ctx = MotorContexto {
             ident_cedula: contratista.cedula.clone(),
@@ -137,14 +183,36 @@ where
             } else {
                 None
             },
+            posibles_coincidencias: coincidencias
+                .iter()
+                .map(|c| InfoCoincidenciaListaNegra {
+                    cedula: c.cedula.clone(),
+                    nombre_completo: c.nombre_completo.clone(),
+                    severidad: NivelSeveridad::from_str_lossy(&c.nivel_severidad),
+                    score: c.score,
+                })
+                .collect(),
+            bloqueo_propuesto: bloqueo_propuesto.map(|p| InfoBloqueoPropuesto {
+                motivo: p.motivo.unwrap_or_default(),
+                severidad: p
+                    .nivel_severidad
+                    .as_ref()
+                    .map_or(NivelSeveridad::Alto, |s| NivelSeveridad::from_str_lossy(s)),
+            }),
+            empresa_vetada: veto_empresa,
             ingreso_activo: None,
+            contrato_empresa,
             estado_autorizacion: estado_autorizacion_calculado,
+            documentos_pendientes: documentos_pendientes
+                .iter()
+                .map(DocumentoPendiente::describir)
+                .collect(),
             alerta_gafete: alerta_gafete_msg,
         };
 
         let motor_res = motor::ejecutar_validacion_motor(&motor_ctx);
 
-        let alertas_str = Self::format_alertas_pendientes(&alertas_pendientes);
+        let mut alertas_str = Self::format_deudas_pendientes(&deudas_pendientes);
         let num_alertas = alertas_str.len();
 
         // Regla de negocio: máximo 2 gafetes adeudados permitidos
@@ -155,6 +223,13 @@ where
         let motor_permite = motor_res.status == ValidationStatus::Allowed
             || motor_res.status == ValidationStatus::Warning;
         let motor_denegado = motor_res.status == ValidationStatus::Denied;
+        let requiere_confirmacion = motor_res.reason == ValidationReason::PossibleBlacklistMatch;
+        // Las advertencias del motor distintas de las deudas de gafete (ya listadas) van primero
+        if motor_res.status == ValidationStatus::Warning
+            && motor_res.reason != ValidationReason::GafeteAlert
+        {
+            alertas_str.insert(0, motor_res.message.clone());
+        }
 
         Ok(ValidacionIngresoResponse {
             // Permitir si: motor permite Y no excede límite de gafetes
@@ -176,6 +251,8 @@ where
             ),
             tiene_ingreso_abierto: false,
             ingreso_abierto: None,
+            posibles_coincidencias: coincidencias,
+            requiere_confirmacion,
         })
     }
 
@@ -220,7 +297,7 @@ where
             if g != 0 {
                 let disp = self
                     .gafete_repo
-                    .is_disponible(g, "contratista")
+                    .is_disponible(g, "contratista", &usuario_id)
                     .await
                     .map_err(|e| IngresoContratistaError::Gafete(e.to_string()))?;
 
@@ -238,6 +315,67 @@ where
             .map_err(|e| IngresoContratistaError::Database(e.to_string()))?
             .ok_or(IngresoContratistaError::ContratistaNotFound)?;
 
+        // ⚠️ REGLA DE SEGURIDAD: el veto de la empresa también se aplica al registrar,
+        // no solo en la pre-validación
+        if let Some(denegado) = self
+            .veto_empresa(&contratista)
+            .await?
+            .as_ref()
+            .and_then(motor::denegar_por_empresa_vetada)
+        {
+            warn!("Intento de ingreso bloqueado por empresa vetada: {}", contratista.cedula);
+            return Err(IngresoContratistaError::Blacklisted(denegado.message));
+        }
+
+        // ⚠️ REGLA DE NEGOCIO: fuera del periodo de contrato de la empresa (o de sus
+        // contratistas principales) tampoco se registra el ingreso
+        if let Some(motivo) = self.contrato_empresa(&contratista).await {
+            warn!("Ingreso de {} detenido: contrato fuera de vigencia", contratista.cedula);
+            return Err(IngresoContratistaError::ContratoEmpresaVencido(motivo));
+        }
+
+        // ⚠️ REGLA DE NEGOCIO: los documentos exigidos (póliza, inducción, certificados)
+        // también se verifican al registrar
+        let pendientes = self.documentos_pendientes(&contratista, input.area.as_deref()).await?;
+        if !pendientes.is_empty() {
+            warn!("Ingreso de {} detenido por documentos pendientes", contratista.cedula);
+            return Err(IngresoContratistaError::DocumentosPendientes(
+                pendientes.iter().map(DocumentoPendiente::describir).collect::<Vec<_>>().join(", "),
+            ));
+        }
+
+        // ⚠️ REGLA DE SEGURIDAD: una posible coincidencia por nombre en lista negra exige
+        // que un usuario autorizado la haya descartado explícitamente; cada descarte queda
+        // auditado con el bloqueo coincidente antes de registrar el ingreso
+        let coincidencias = self.posibles_coincidencias(&contratista).await;
+        if let Some(c) = coincidencias.first() {
+            if !input.coincidencia_lista_negra_descartada {
+                warn!(
+                    "Ingreso de {} detenido: posible coincidencia con bloqueo {}",
+                    contratista.cedula, c.id
+                );
+                return Err(IngresoContratistaError::PosibleListaNegra(format!(
+                    "{} (cédula {})",
+                    c.nombre_completo, c.cedula
+                )));
+            }
+            for c in &coincidencias {
+                self.security_repo
+                    .registrar_coincidencia_descartada(
+                        &c.id,
+                        &contratista.id,
+                        &contratista.cedula,
+                        &usuario_id,
+                    )
+                    .await
+                    .map_err(|e| IngresoContratistaError::Database(e.to_string()))?;
+            }
+            info!(
+                "Posible coincidencia en lista negra descartada por {usuario_id_str} para cédula {}",
+                contratista.cedula
+            );
+        }
+
         let dto = crate::models::ingreso::IngresoContratistaCreateDTO {
             contratista: contratista.id.clone(),
             nombre: contratista.nombre.clone(),
@@ -260,7 +398,14 @@ where
             .map_err(|e| IngresoContratistaError::Database(e.to_string()))?;
 
         if let Some(ref g) = nuevo_ingreso.gafete_numero {
-            if let Err(e) = self.gafete_repo.marcar_en_uso(*g, "contratista").await {
+            let contexto = ContextoMovimientoGafete {
+                cedula: Some(nuevo_ingreso.cedula.clone()),
+                nombre: Some(format!("{} {}", nuevo_ingreso.nombre, nuevo_ingreso.apellido)),
+                ingreso: Some(nuevo_ingreso.id.clone()),
+                usuario: Some(nuevo_ingreso.usuario_ingreso.id.clone()),
+                notas: None,
+            };
+            if let Err(e) = self.gafete_repo.marcar_en_uso(*g, "contratista", contexto).await {
                 error!("ERROR CRÍTICO: No se pudo marcar gafete {g} como 'en uso': {e}");
                 // No fallamos la transacción general pero dejamos evidencia del problema
             }
@@ -286,10 +431,21 @@ where
             .await
             .map_err(|e| IngresoContratistaError::Database(e.to_string()))?;
 
+        let contexto_gafete = ContextoMovimientoGafete {
+            cedula: Some(ingreso_actualizado.cedula.clone()),
+            nombre: Some(format!(
+                "{} {}",
+                ingreso_actualizado.nombre, ingreso_actualizado.apellido
+            )),
+            ingreso: Some(ingreso_actualizado.id.clone()),
+            usuario: Some(usuario_id.clone()),
+            notas: input.observaciones_salida.clone(),
+        };
+
         if input.devolvio_gafete {
             // Gafete devuelto: liberarlo para nuevo uso
             if let Some(ref g) = ingreso_actualizado.gafete_numero {
-                let _ = self.gafete_repo.liberar(*g, "contratista").await;
+                let _ = self.gafete_repo.liberar(*g, "contratista", contexto_gafete).await;
             }
         } else {
             // Gafete NO devuelto: crear alerta de gafete perdido
@@ -297,6 +453,14 @@ where
                 if *g != 0 {
                     warn!("Gafete {g} no devuelto por contratista, generando alerta");
 
+                    if let Err(e) = self
+                        .gafete_repo
+                        .registrar_no_devuelto(*g, "contratista", contexto_gafete)
+                        .await
+                    {
+                        error!("Error no fatal: no se pudo registrar el gafete {g} como no devuelto: {e}");
+                    }
+
                     // Crear alerta de gafete no devuelto
                     let alerta_input = crate::models::ingreso::CreateAlertaInput {
                         id: uuid::Uuid::new_v4().to_string(),
@@ -459,11 +623,120 @@ where
         Ok(vec![])
     }
 
-    fn format_alertas_pendientes(alertas: &[crate::models::ingreso::AlertaGafete]) -> Vec<String> {
-        alertas
+    /// Veto vigente sobre la empresa del contratista.
+    ///
+    /// A diferencia de las alertas, una consulta fallida no deja pasar: se devuelve el error.
+    async fn veto_empresa(
+        &self,
+        contratista: &ContratistaFetched,
+    ) -> Result<Option<InfoEmpresaVetada>, IngresoContratistaError> {
+        let veto = self.security_repo.check_empresa_vetada(&contratista.empresa.id).await.map_err(
+            |e| {
+                error!("No se pudo consultar el veto de la empresa de {}: {e}", contratista.cedula);
+                IngresoContratistaError::Database(format!(
+                    "No se pudo verificar el veto de la empresa: {e}"
+                ))
+            },
+        )?;
+
+        Ok(veto.is_vetada.then(|| InfoEmpresaVetada {
+            empresa_nombre: contratista.empresa.nombre.clone(),
+            motivo: veto.motivo.clone().unwrap_or_default(),
+            severidad: veto
+                .nivel_severidad
+                .as_ref()
+                .map_or(NivelSeveridad::Alto, |s| NivelSeveridad::from_str_lossy(s)),
+        }))
+    }
+
+    /// Posibles coincidencias por nombre en lista negra; si la consulta falla no se bloquea.
+    async fn posibles_coincidencias(
+        &self,
+        contratista: &ContratistaFetched,
+    ) -> Vec<CoincidenciaListaNegra> {
+        let persona = NombrePersona {
+            nombre: &contratista.nombre,
+            segundo_nombre: contratista.segundo_nombre.as_deref(),
+            apellido: &contratista.apellido,
+            segundo_apellido: contratista.segundo_apellido.as_deref(),
+        };
+        self.security_repo
+            .find_posibles_coincidencias(&persona, &contratista.cedula)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(
+                    "No se pudo verificar coincidencias por nombre de {}: {e}",
+                    contratista.cedula
+                );
+                Vec::new()
+            })
+    }
+
+    /// Documentos exigidos que el contratista no cumple.
+    ///
+    /// Como con el veto de la empresa, una consulta fallida no deja pasar: se devuelve el error.
+    async fn documentos_pendientes(
+        &self,
+        contratista: &ContratistaFetched,
+        area: Option<&str>,
+    ) -> Result<Vec<DocumentoPendiente>, IngresoContratistaError> {
+        let requisitos = self.contratista_repo.find_requisitos_documento().await.map_err(|e| {
+            error!("No se pudieron consultar los documentos requeridos: {e}");
+            IngresoContratistaError::Database(format!(
+                "No se pudieron verificar los documentos requeridos: {e}"
+            ))
+        })?;
+        if requisitos.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let registrados =
+            self.contratista_repo.find_documentos(&contratista.id).await.map_err(|e| {
+                error!("No se pudieron consultar los documentos de {}: {e}", contratista.cedula);
+                IngresoContratistaError::Database(format!(
+                    "No se pudieron verificar los documentos del contratista: {e}"
+                ))
+            })?;
+
+        Ok(documentos::documentos_pendientes(
+            &requisitos,
+            &registrados,
+            &contratista.empresa.id.to_string(),
+            documentos::normalizar_area(area).as_deref(),
+            chrono::Utc::now().date_naive(),
+        ))
+    }
+
+    /// Motivo si hoy está fuera del contrato de la empresa del contratista o de alguna de
+    /// sus principales; si la consulta de una principal falla, se evalúa lo ya obtenido.
+    async fn contrato_empresa(&self, contratista: &ContratistaFetched) -> Option<String> {
+        let mut cadena = vec![PeriodoContrato::desde_empresa(&contratista.empresa)];
+        let mut padre = contratista.empresa.empresa_padre.clone();
+
+        while let Some(id) = padre.take() {
+            if cadena.len() >= empresa_domain::MAX_NIVELES_JERARQUIA {
+                break;
+            }
+            match self.contratista_repo.find_empresa(&id).await {
+                Ok(Some(empresa)) => {
+                    cadena.push(PeriodoContrato::desde_empresa(&empresa));
+                    padre = empresa.empresa_padre;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    warn!("No se pudo consultar la contratista principal {id}: {e}");
+                }
+            }
+        }
+
+        empresa_domain::evaluar_contrato(&cadena, chrono::Utc::now().date_naive())
+    }
+
+    fn format_deudas_pendientes(deudas: &[DeudaGafete]) -> Vec<String> {
+        deudas
             .iter()
-            .map(|a| {
-                let fecha_raw = a.fecha_reporte.to_string();
+            .map(|d| {
+                let fecha_raw = d.desde.to_string();
                 let fecha_iso = fecha_raw
                     .trim_start_matches("d'")
                     .trim_end_matches('\'')
@@ -481,7 +754,7 @@ where
                     fecha_iso.to_string()
                 };
 
-                format!("Gafete #{} no devuelto ({})", a.gafete_numero, fecha_formateada)
+                format!("Gafete #{} no devuelto ({})", d.numero, fecha_formateada)
             })
             .collect()
     }
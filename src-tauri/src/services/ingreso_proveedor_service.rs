@@ -2,18 +2,28 @@
 ///
 /// Orquestador para el control de acceso de proveedores y personal externo.
 /// Responsabilidades:
-/// - Registro de entradas con validación de seguridad (Lista Negra).
+/// - Registro de entradas con validación de seguridad (Lista Negra, empresa vetada).
 /// - Asignación y control de gafetes provisionales.
 /// - Registro de salidas y liberación de recursos.
 /// - Trazabilidad de áreas visitadas y motivos.
+use crate::db::surrealdb_audit_queries as audit_db;
 use crate::db::surrealdb_ingreso_proveedor_queries as db;
 use crate::db::surrealdb_proveedor_queries as proveedor_queries;
 use crate::domain::errors::IngresoProveedorError;
+use crate::domain::lista_negra::NombrePersona;
+use crate::domain::motor_validacion as motor;
+use crate::models::gafete::ContextoMovimientoGafete;
 use crate::models::ingreso::{
     CreateIngresoProveedorInput, IngresoProveedorCreateDTO, IngresoResponse,
     ValidacionIngresoProveedorResponse,
 };
-use crate::services::{gafete_service, lista_negra_service, proveedor_service};
+use crate::models::lista_negra::CoincidenciaListaNegra;
+use crate::models::validation::{
+    EstadoAutorizacion, InfoEmpresaVetada, MotorContexto, NivelSeveridad, TipoAcceso,
+    ValidationResult, ValidationStatus,
+};
+use crate::services::search_service::SearchService;
+use crate::services::{gafete_service, lista_negra_service, pase_service, proveedor_service};
 use log::{error, info, warn};
 use surrealdb::RecordId;
 
@@ -32,10 +42,13 @@ fn parse_id(id: &str, table: &str) -> Result<RecordId, IngresoProveedorError> {
 }
 
 /// Valida disponibilidad de gafete.
-async fn validar_gafete_disponible(gafete: Option<i32>) -> Result<(), IngresoProveedorError> {
+async fn validar_gafete_disponible(
+    gafete: Option<i32>,
+    usuario: &RecordId,
+) -> Result<(), IngresoProveedorError> {
     if let Some(g) = gafete {
         if g != 0 {
-            let disp = gafete_service::is_gafete_disponible(g, "proveedor")
+            let disp = gafete_service::is_gafete_disponible(g, "proveedor", Some(usuario))
                 .await
                 .map_err(|e| IngresoProveedorError::Gafete(e.to_string()))?;
             if !disp {
@@ -65,6 +78,60 @@ async fn validar_lista_negra(cedula: String) -> Result<(), IngresoProveedorError
     Ok(())
 }
 
+/// Evalúa con el motor el veto sobre la empresa del proveedor.
+///
+/// La lista negra personal y el ingreso duplicado se verifican aparte. Si el veto no se
+/// puede consultar se falla cerrado: no se deja pasar a nadie sin saber si la empresa
+/// está vetada.
+async fn evaluar_empresa_vetada(
+    empresa: &RecordId,
+    cedula: &str,
+    nombre: &str,
+) -> Result<ValidationResult, IngresoProveedorError> {
+    let veto = lista_negra_service::check_empresa_vetada(empresa).await.map_err(|e| {
+        error!("No se pudo consultar el veto de la empresa {empresa}: {e}");
+        IngresoProveedorError::Database(format!("No se pudo verificar el veto de la empresa: {e}"))
+    })?;
+
+    let ctx = MotorContexto {
+        ident_cedula: cedula.to_string(),
+        ident_nombre: nombre.to_string(),
+        tipo_acceso: TipoAcceso::Proveedor,
+        lista_negra: None,
+        posibles_coincidencias: Vec::new(),
+        bloqueo_propuesto: None,
+        empresa_vetada: veto.is_vetada.then(|| InfoEmpresaVetada {
+            empresa_nombre: veto.empresa_nombre.clone().unwrap_or_else(|| empresa.to_string()),
+            motivo: veto.motivo.clone().unwrap_or_default(),
+            severidad: veto
+                .nivel_severidad
+                .as_ref()
+                .map_or(NivelSeveridad::Alto, |s| NivelSeveridad::from_str_lossy(s)),
+        }),
+        ingreso_activo: None,
+        contrato_empresa: None,
+        estado_autorizacion: EstadoAutorizacion::PorDefinir,
+        documentos_pendientes: Vec::new(),
+        alerta_gafete: None,
+    };
+
+    Ok(motor::ejecutar_validacion_motor(&ctx))
+}
+
+/// Posibles coincidencias por nombre en lista negra; si la consulta falla no se bloquea.
+async fn posibles_coincidencias(
+    search: Option<&SearchService>,
+    persona: &NombrePersona<'_>,
+    cedula: &str,
+) -> Vec<CoincidenciaListaNegra> {
+    lista_negra_service::buscar_coincidencias_nombre(search, persona, cedula).await.unwrap_or_else(
+        |e| {
+            warn!("No se pudo verificar coincidencias por nombre de {cedula}: {e}");
+            Vec::new()
+        },
+    )
+}
+
 /// Verifica si ya tiene un ingreso activo.
 async fn validar_ingreso_unico(proveedor_id: &RecordId) -> Result<(), IngresoProveedorError> {
     let abierto = db::find_ingreso_abierto_by_proveedor(proveedor_id)
@@ -93,23 +160,26 @@ async fn validar_ingreso_unico(proveedor_id: &RecordId) -> Result<(), IngresoPro
 /// # Argumentos
 /// * `input` - Datos del intento de ingreso.
 /// * `usuario_id_str` - ID del usuario que registra la acción.
+/// * `search` - Índice para acotar la coincidencia por nombre en lista negra (opcional).
 ///
 /// # Retorno
 /// DTO con la información del ingreso registrado.
 ///
 /// # Errores
 /// - `IngresoProveedorError::Validation`: Si falla alguna regla de negocio.
+/// - `IngresoProveedorError::PosibleListaNegra`: Coincidencia por nombre sin descartar.
 /// - `IngresoProveedorError::Gafete`: Problemas con el sistema de gafetes.
 /// - `IngresoProveedorError::Database`: Fallos de persistencia.
 pub async fn registrar_ingreso(
     input: CreateIngresoProveedorInput,
     usuario_id_str: String,
+    search: Option<&SearchService>,
 ) -> Result<IngresoResponse, IngresoProveedorError> {
     let proveedor_id = parse_id(&input.proveedor_id, "proveedor")?;
     let usuario_id = parse_id(&usuario_id_str, "user")?;
 
     // Validaciones de Negocio
-    validar_gafete_disponible(input.gafete_numero).await?;
+    validar_gafete_disponible(input.gafete_numero, &usuario_id).await?;
     validar_lista_negra(input.cedula.clone()).await?;
     validar_ingreso_unico(&proveedor_id).await?;
 
@@ -119,6 +189,52 @@ pub async fn registrar_ingreso(
         .map_err(|e| IngresoProveedorError::Database(e.to_string()))?
         .ok_or(IngresoProveedorError::Validation("Proveedor no encontrado".to_string()))?;
 
+    let veto =
+        evaluar_empresa_vetada(&proveedor.empresa, &proveedor.cedula, &proveedor.nombre).await?;
+    if veto.status == ValidationStatus::Denied {
+        warn!("Intento de ingreso bloqueado por empresa vetada: {}", proveedor.cedula);
+        return Err(IngresoProveedorError::Validation(format!(
+            "ACCESO DENEGADO: {}",
+            veto.message
+        )));
+    }
+
+    // ⚠️ REGLA DE SEGURIDAD: una posible coincidencia por nombre en lista negra exige
+    // que un usuario autorizado la haya descartado; cada descarte queda auditado
+    let persona = NombrePersona {
+        nombre: &proveedor.nombre,
+        segundo_nombre: proveedor.segundo_nombre.as_deref(),
+        apellido: &proveedor.apellido,
+        segundo_apellido: proveedor.segundo_apellido.as_deref(),
+    };
+    let coincidencias = posibles_coincidencias(search, &persona, &proveedor.cedula).await;
+    if let Some(c) = coincidencias.first() {
+        if !input.coincidencia_lista_negra_descartada {
+            warn!(
+                "Ingreso de proveedor {} detenido: posible coincidencia con bloqueo {}",
+                proveedor.cedula, c.id
+            );
+            return Err(IngresoProveedorError::PosibleListaNegra(format!(
+                "{} (cédula {})",
+                c.nombre_completo, c.cedula
+            )));
+        }
+        for c in &coincidencias {
+            audit_db::insert_coincidencia_descartada(
+                &c.id,
+                &proveedor_id,
+                &proveedor.cedula,
+                &usuario_id,
+            )
+            .await
+            .map_err(|e| IngresoProveedorError::Database(e.to_string()))?;
+        }
+        info!(
+            "Posible coincidencia en lista negra descartada por {usuario_id_str} para proveedor {}",
+            proveedor.cedula
+        );
+    }
+
     let dto = IngresoProveedorCreateDTO {
         proveedor: proveedor_id,
         nombre: proveedor.nombre.clone(),
@@ -143,7 +259,14 @@ pub async fn registrar_ingreso(
     // Actualizar estado de Gafete (Efecto secundario)
     if let Some(g) = input.gafete_numero {
         if g != 0 {
-            if let Err(e) = gafete_service::marcar_en_uso(g, "proveedor").await {
+            let contexto = ContextoMovimientoGafete {
+                cedula: Some(nuevo_ingreso.cedula.clone()),
+                nombre: Some(format!("{} {}", nuevo_ingreso.nombre, nuevo_ingreso.apellido)),
+                ingreso: Some(nuevo_ingreso.id.clone()),
+                usuario: Some(nuevo_ingreso.usuario_ingreso.id.clone()),
+                notas: None,
+            };
+            if let Err(e) = gafete_service::marcar_en_uso(g, "proveedor", contexto).await {
                 error!("Error crítico: Gafete {g} no se pudo marcar en uso: {e:?}");
                 // No fallamos el ingreso, pero logueamos el error de consistencia.
             }
@@ -155,6 +278,8 @@ pub async fn registrar_ingreso(
         proveedor.nombre, proveedor.apellido, input.gafete_numero
     );
 
+    pase_service::generar_pase_en_segundo_plano(nuevo_ingreso.id.to_string());
+
     Ok(IngresoResponse::from_proveedor_fetched(nuevo_ingreso))
 }
 
@@ -185,16 +310,23 @@ pub async fn registrar_salida(
     let usuario_id = parse_id(&usuario_id_str, "user")?;
 
     let ingreso_actualizado =
-        db::update_salida(&ingreso_id, &usuario_id, observaciones).await.map_err(|e| {
+        db::update_salida(&ingreso_id, &usuario_id, observaciones.clone()).await.map_err(|e| {
             error!("Error DB al registrar salida de proveedor: {e}");
             IngresoProveedorError::Database(e.to_string())
         })?;
 
     // Gestión de Gafetes en Salida
+    let contexto = ContextoMovimientoGafete {
+        cedula: Some(ingreso_actualizado.cedula.clone()),
+        nombre: Some(format!("{} {}", ingreso_actualizado.nombre, ingreso_actualizado.apellido)),
+        ingreso: Some(ingreso_actualizado.id.clone()),
+        usuario: Some(usuario_id),
+        notas: observaciones,
+    };
     if devolvio_gafete {
         if let Some(ref g) = ingreso_actualizado.gafete_numero {
             if *g != 0 {
-                if let Err(e) = gafete_service::liberar_gafete(*g, "proveedor").await {
+                if let Err(e) = gafete_service::liberar_gafete(*g, "proveedor", contexto).await {
                     error!("Error no fatal: No se pudo liberar gafete {g} en salida: {e:?}");
                 } else {
                     info!("Gafete {g} liberado correctamente.");
@@ -204,6 +336,11 @@ pub async fn registrar_salida(
     } else if let Some(g) = ingreso_actualizado.gafete_numero {
         if g != 0 {
             warn!("ALERTA: Proveedor salió SIN devolver gafete {g}");
+            if let Err(e) = gafete_service::registrar_no_devuelto(g, "proveedor", contexto).await {
+                error!(
+                    "Error no fatal: no se pudo registrar el gafete {g} como no devuelto: {e:?}"
+                );
+            }
         }
     }
 
@@ -233,11 +370,13 @@ pub async fn get_activos() -> Result<Vec<IngresoResponse>, IngresoProveedorError
 ///
 /// # Argumentos
 /// * `proveedor_id_str`: ID del proveedor a consultar.
+/// * `search`: Índice para acotar la coincidencia por nombre en lista negra (opcional).
 ///
 /// # Retorno
 /// DTO con flag `puedeIngresar`, datos del proveedor y alertas.
 pub async fn validar_ingreso(
     proveedor_id_str: String,
+    search: Option<&SearchService>,
 ) -> Result<ValidacionIngresoProveedorResponse, IngresoProveedorError> {
     let p_id = parse_id(&proveedor_id_str, "proveedor")?;
 
@@ -262,7 +401,29 @@ pub async fn validar_ingreso(
         .map(|a| a.notas.unwrap_or_else(|| "Gafete no devuelto".to_string()))
         .collect();
 
-    let puede_ingresar = abierto.is_none();
+    // 4. Veto sobre la empresa (motor de validación)
+    let empresa_id = parse_id(&p.empresa_id, "empresa")?;
+    let veto = evaluar_empresa_vetada(&empresa_id, &p.cedula, &p.nombre_completo).await?;
+    let empresa_denegada = veto.status == ValidationStatus::Denied;
+
+    // 5. Posibles coincidencias por nombre en lista negra (no bloquean, se confirman)
+    let persona = NombrePersona {
+        nombre: &p.nombre,
+        segundo_nombre: p.segundo_nombre.as_deref(),
+        apellido: &p.apellido,
+        segundo_apellido: p.segundo_apellido.as_deref(),
+    };
+    let coincidencias = posibles_coincidencias(search, &persona, &p.cedula).await;
+
+    let puede_ingresar = abierto.is_none() && !empresa_denegada;
+    let requiere_confirmacion = puede_ingresar && !coincidencias.is_empty();
+    let motivo_rechazo = if abierto.is_some() {
+        Some("Ya tiene un ingreso activo".to_string())
+    } else if empresa_denegada {
+        Some(veto.message.clone())
+    } else {
+        None
+    };
 
     Ok(ValidacionIngresoProveedorResponse {
         puede_ingresar,
@@ -272,14 +433,13 @@ pub async fn validar_ingreso(
         segundo_nombre: p.segundo_nombre.clone(),
         segundo_apellido: p.segundo_apellido.clone(),
         empresa_nombre: p.empresa_nombre.clone(),
-        motivo_rechazo: if puede_ingresar {
-            None
-        } else {
-            Some("Ya tiene un ingreso activo".to_string())
-        },
+        motivo_rechazo,
         alertas_gafete,
         tiene_gafetes_pendientes,
-        tiene_ingreso_abierto: !puede_ingresar,
+        tiene_ingreso_abierto: abierto.is_some(),
+        alerta_empresa: (veto.status == ValidationStatus::Warning).then_some(veto.message),
+        posibles_coincidencias: coincidencias,
+        requiere_confirmacion,
     })
 }
 
@@ -8,8 +8,9 @@
 /// - Trazabilidad de motivos y anfitriones.
 use crate::db::surrealdb_ingreso_visita_queries as db;
 use crate::domain::errors::IngresoVisitaError;
+use crate::models::gafete::ContextoMovimientoGafete;
 use crate::models::ingreso::{CreateIngresoVisitaInput, IngresoResponse, IngresoVisitaCreateDTO};
-use crate::services::{gafete_service, lista_negra_service};
+use crate::services::{cita_lifecycle_service, gafete_service, lista_negra_service, pase_service};
 use log::{error, info, warn};
 use surrealdb::RecordId;
 
@@ -28,10 +29,13 @@ fn parse_id(id: &str, table: &str) -> Result<RecordId, IngresoVisitaError> {
 }
 
 /// Valida disponibilidad de gafete.
-async fn validar_gafete_disponible(gafete: Option<i32>) -> Result<(), IngresoVisitaError> {
+async fn validar_gafete_disponible(
+    gafete: Option<i32>,
+    usuario: &RecordId,
+) -> Result<(), IngresoVisitaError> {
     if let Some(g) = gafete {
         if g != 0 {
-            let disp = gafete_service::is_gafete_disponible(g, "visita")
+            let disp = gafete_service::is_gafete_disponible(g, "visita", Some(usuario))
                 .await
                 .map_err(|e| IngresoVisitaError::Gafete(e.to_string()))?;
             if !disp {
@@ -101,7 +105,7 @@ pub async fn registrar_ingreso(
     let usuario_id = parse_id(&usuario_id_str, "user")?;
 
     // Validaciones de Negocio
-    validar_gafete_disponible(input.gafete_numero).await?;
+    validar_gafete_disponible(input.gafete_numero, &usuario_id).await?;
     validar_lista_negra(input.cedula.clone()).await?;
     validar_ingreso_unico_cedula(&input.cedula).await?;
 
@@ -129,7 +133,14 @@ pub async fn registrar_ingreso(
     // Marcado de activo físico.
     if let Some(ref g) = nuevo_ingreso.gafete_numero {
         if *g != 0 {
-            if let Err(e) = gafete_service::marcar_en_uso(*g, "visita").await {
+            let contexto = ContextoMovimientoGafete {
+                cedula: Some(nuevo_ingreso.cedula.clone()),
+                nombre: Some(format!("{} {}", nuevo_ingreso.nombre, nuevo_ingreso.apellido)),
+                ingreso: Some(nuevo_ingreso.id.clone()),
+                usuario: Some(nuevo_ingreso.usuario_ingreso.id.clone()),
+                notas: None,
+            };
+            if let Err(e) = gafete_service::marcar_en_uso(*g, "visita", contexto).await {
                 error!("Error crítico: Gafete {g} no se pudo marcar en uso: {e:?}");
             }
         }
@@ -140,6 +151,8 @@ pub async fn registrar_ingreso(
         input.nombre, input.apellido, input.gafete_numero
     );
 
+    pase_service::generar_pase_en_segundo_plano(nuevo_ingreso.id.to_string());
+
     Ok(IngresoResponse::from_visita_fetched(nuevo_ingreso))
 }
 
@@ -170,15 +183,22 @@ pub async fn registrar_salida(
     let usuario_id = parse_id(&usuario_id_str, "user")?;
 
     let actualizado =
-        db::update_salida(&ingreso_id, &usuario_id, observaciones).await.map_err(|e| {
+        db::update_salida(&ingreso_id, &usuario_id, observaciones.clone()).await.map_err(|e| {
             error!("Error DB al registrar salida de visita: {e}");
             IngresoVisitaError::Database(e.to_string())
         })?;
 
+    let contexto = ContextoMovimientoGafete {
+        cedula: Some(actualizado.cedula.clone()),
+        nombre: Some(format!("{} {}", actualizado.nombre, actualizado.apellido)),
+        ingreso: Some(actualizado.id.clone()),
+        usuario: Some(usuario_id),
+        notas: observaciones,
+    };
     if devolvio_gafete {
         if let Some(ref g) = actualizado.gafete_numero {
             if *g != 0 {
-                if let Err(e) = gafete_service::liberar_gafete(*g, "visita").await {
+                if let Err(e) = gafete_service::liberar_gafete(*g, "visita", contexto).await {
                     error!("Error no fatal: No se pudo liberar gafete {g} en salida visita: {e:?}");
                 } else {
                     info!("Gafete {g} (visita) liberado correctamente.");
@@ -188,9 +208,16 @@ pub async fn registrar_salida(
     } else if let Some(g) = actualizado.gafete_numero {
         if g != 0 {
             warn!("ALERTA: Visitante salió SIN devolver gafete {g}");
+            if let Err(e) = gafete_service::registrar_no_devuelto(g, "visita", contexto).await {
+                error!(
+                    "Error no fatal: no se pudo registrar el gafete {g} como no devuelto: {e:?}"
+                );
+            }
         }
     }
 
+    cita_lifecycle_service::al_registrar_salida_visita(&ingreso_id).await;
+
     info!("Salida de visita registrada: {}", actualizado.nombre);
 
     Ok(IngresoResponse::from_visita_fetched(actualizado))
@@ -6,10 +6,14 @@
 
 use crate::domain::errors::KeyringError;
 use keyring::Entry;
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair};
 use serde::{Deserialize, Serialize};
 
 const SERVICE_NAME: &str = "brisas-app";
 const KEY_PASSWORD_SECRET: &str = "brisas_argon2_secret_v1";
+const KEY_CITA_INVITATION_SECRET: &str = "brisas_cita_invitation_hmac_v1";
+const KEY_LISTA_NEGRA_EXCHANGE_SIGNING: &str = "brisas_lista_negra_exchange_ed25519_v1";
 
 // ==========================================
 // DTOs
@@ -145,6 +149,62 @@ pub fn is_fully_configured() -> bool {
     has_argon2_secret()
 }
 
+/// Obtiene la clave HMAC para firmar invitaciones de citas, creándola la primera vez.
+pub fn get_or_create_invitation_key() -> KeyringResult<Vec<u8>> {
+    get_or_create_hmac_key(KEY_CITA_INVITATION_SECRET)
+        .map_err(|e| KeyringError::Message(format!("Clave de invitación corrupta: {e}")))
+}
+
+/// Obtiene el par de claves Ed25519 (PKCS#8) con que este sitio firma sus archivos de
+/// intercambio de lista negra, creándolo la primera vez. La clave privada nunca sale del
+/// llavero: las plantas hermanas solo reciben la pública.
+pub fn get_or_create_lista_negra_signing_key() -> KeyringResult<Vec<u8>> {
+    if let Some(pkcs8) = retrieve_value(KEY_LISTA_NEGRA_EXCHANGE_SIGNING).filter(|v| !v.is_empty())
+    {
+        return hex::decode(pkcs8)
+            .map_err(|e| KeyringError::Message(format!("Clave de intercambio corrupta: {e}")));
+    }
+
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+        .map_err(|_| KeyringError::Message("No se pudo generar la clave de intercambio".into()))?;
+    store_value(KEY_LISTA_NEGRA_EXCHANGE_SIGNING, &hex::encode(pkcs8.as_ref()))?;
+    Ok(pkcs8.as_ref().to_vec())
+}
+
+/// Clave pública (HEX) de este sitio, para registrarla como confiable en las plantas hermanas.
+pub fn get_lista_negra_public_key() -> KeyringResult<String> {
+    let pkcs8 = get_or_create_lista_negra_signing_key()?;
+    let par = Ed25519KeyPair::from_pkcs8(&pkcs8)
+        .map_err(|e| KeyringError::Message(format!("Clave de intercambio corrupta: {e}")))?;
+    Ok(hex::encode(par.public_key().as_ref()))
+}
+
+/// Reemplaza el par de claves de intercambio del sitio (PKCS#8 en HEX), por ejemplo al
+/// trasladar el sitio a un equipo nuevo. Es de solo escritura: no existe la lectura inversa.
+pub fn set_lista_negra_signing_key(pkcs8_hex: &str) -> KeyringResult<()> {
+    let pkcs8 = pkcs8_hex.trim().to_ascii_lowercase();
+    let bytes = hex::decode(&pkcs8)
+        .map_err(|_| KeyringError::Message("La clave debe estar en hexadecimal".to_string()))?;
+    Ed25519KeyPair::from_pkcs8(&bytes).map_err(|_| {
+        KeyringError::Message("La clave no es un par Ed25519 en formato PKCS#8".to_string())
+    })?;
+
+    store_value(KEY_LISTA_NEGRA_EXCHANGE_SIGNING, &pkcs8)
+}
+
+fn get_or_create_hmac_key(key: &str) -> KeyringResult<Vec<u8>> {
+    let secret = match retrieve_value(key) {
+        Some(secret) if !secret.is_empty() => secret,
+        _ => {
+            let secret = generate_random_secret();
+            store_value(key, &secret)?;
+            secret
+        }
+    };
+
+    hex::decode(&secret).map_err(|e| KeyringError::Message(e.to_string()))
+}
+
 // Helper methods for generic secret storage (used by security_commands directly)
 pub fn save_secret(key: &str, value: &str) -> KeyringResult<()> {
     store_value(key, value)
@@ -0,0 +1,157 @@
+/// Servicio: Evidencias Cifradas de Lista Negra.
+///
+/// Gestiona los adjuntos (fotos, informes en PDF) que respaldan una decisión de bloqueo.
+/// El cifrado en disco y la verificación de integridad los resuelve
+/// `adjunto_cifrado_service`; la base de datos solo conserva los metadatos.
+///
+/// Responsabilidades:
+/// - Ingesta con tope de evidencias por registro.
+/// - Recuperación verificada (Disk -> Decrypt -> SHA-256 -> Base64).
+/// - Eliminación del archivo cifrado junto con su referencia.
+///
+/// El acceso se controla con el permiso `lista_negra_evidencia:*`, distinto de
+/// `lista_negra:*`: el guardia ve que la persona está bloqueada, no la evidencia.
+use crate::db::surrealdb_lista_negra_evidencia_queries::{self as db, NuevaEvidencia};
+use crate::db::surrealdb_lista_negra_queries as lista_negra_db;
+use crate::domain::errors::ListaNegraError;
+use crate::domain::lista_negra as domain;
+use crate::models::lista_negra::{
+    AdjuntarEvidenciaInput, EvidenciaContenidoResponse, EvidenciaListaNegraResponse,
+};
+use crate::services::adjunto_cifrado_service::{parse_id, AlmacenAdjuntos};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use log::{info, warn};
+
+/// Almacén cifrado de las evidencias
+const EVIDENCIAS: AlmacenAdjuntos = AlmacenAdjuntos {
+    dir: "secure_evidencias",
+    etiqueta: "evidencia",
+    max_bytes: domain::EVIDENCIA_MAX_BYTES,
+};
+
+/// Adjunta una evidencia a un registro de lista negra.
+///
+/// El tamaño se comprueba antes de leer el archivo y el tipo por su contenido, no por
+/// la extensión. El SHA-256 se calcula sobre el original para detectar alteraciones.
+///
+/// # Errors
+///
+/// * `ListaNegraError::NotFound`: El registro no existe o está inactivo.
+/// * `ListaNegraError::Validation`: Archivo vacío, muy grande, de tipo no admitido o
+///   límite de evidencias alcanzado.
+/// * `ListaNegraError::IO` / `Internal`: Fallo de disco o de cifrado.
+pub async fn adjuntar(
+    input: AdjuntarEvidenciaInput,
+    subido_por: &str,
+) -> Result<EvidenciaListaNegraResponse, ListaNegraError> {
+    let lista_negra_id = parse_id(&input.lista_negra_id, "lista_negra")?;
+    if let Some(ref descripcion) = input.descripcion {
+        domain::validar_motivo(descripcion)?;
+    }
+
+    lista_negra_db::find_by_id(&lista_negra_id)
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?
+        .ok_or(ListaNegraError::NotFound)?;
+
+    let existentes = db::count_by_lista_negra(&lista_negra_id)
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+    if existentes >= domain::EVIDENCIAS_MAX_POR_REGISTRO {
+        return Err(ListaNegraError::Validation(format!(
+            "El registro ya tiene el máximo de {} evidencias",
+            domain::EVIDENCIAS_MAX_POR_REGISTRO
+        )));
+    }
+
+    let guardada = EVIDENCIAS.guardar(&input.file_path)?;
+
+    let creada = db::create(NuevaEvidencia {
+        lista_negra: &lista_negra_id,
+        archivo_uuid: &guardada.archivo_uuid,
+        nombre_original: &guardada.nombre_original,
+        tipo_mime: guardada.tipo_mime,
+        tamano_bytes: guardada.tamano_bytes,
+        sha256: &guardada.sha256,
+        descripcion: input.descripcion.as_deref().map(str::trim),
+        subido_por,
+    })
+    .await;
+
+    let creada = match creada {
+        Ok(creada) => creada,
+        Err(e) => {
+            guardada.descartar();
+            return Err(ListaNegraError::Database(e.to_string()));
+        }
+    };
+
+    info!(
+        "📎 Evidencia asegurada: registro={lista_negra_id}, tipo={}, {} bytes, por={subido_por}",
+        guardada.tipo_mime, guardada.tamano_bytes
+    );
+    Ok(creada.into())
+}
+
+/// Metadatos de las evidencias de un registro (sin descifrar contenido).
+pub async fn listar(
+    lista_negra_id: &str,
+) -> Result<Vec<EvidenciaListaNegraResponse>, ListaNegraError> {
+    let lista_negra_id = parse_id(lista_negra_id, "lista_negra")?;
+
+    let evidencias = db::find_by_lista_negra(&lista_negra_id)
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+
+    Ok(evidencias.into_iter().map(std::convert::Into::into).collect())
+}
+
+/// Descifra una evidencia y verifica que coincida con el SHA-256 registrado.
+///
+/// # Errors
+///
+/// * `ListaNegraError::Integridad`: El contenido descifrado no coincide con la huella.
+pub async fn obtener(
+    id: &str,
+    solicitado_por: &str,
+) -> Result<EvidenciaContenidoResponse, ListaNegraError> {
+    let record_id = parse_id(id, "lista_negra_evidencia")?;
+
+    let evidencia = db::find_by_id(&record_id)
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?
+        .ok_or(ListaNegraError::NotFound)?;
+
+    let contenido = EVIDENCIAS.leer_verificado(
+        &evidencia.archivo_uuid,
+        &evidencia.sha256,
+        &evidencia.id.to_string(),
+    )?;
+
+    info!(target: "audit", "[EVIDENCIA] id={} consultada por={solicitado_por}", evidencia.id);
+
+    Ok(EvidenciaContenidoResponse {
+        contenido_base64: STANDARD.encode(&contenido),
+        evidencia: evidencia.into(),
+    })
+}
+
+/// Elimina una evidencia: primero el archivo cifrado, luego la referencia.
+pub async fn eliminar(id: &str, eliminado_por: &str) -> Result<(), ListaNegraError> {
+    let record_id = parse_id(id, "lista_negra_evidencia")?;
+
+    let evidencia = db::find_by_id(&record_id)
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?
+        .ok_or(ListaNegraError::NotFound)?;
+
+    EVIDENCIAS.eliminar(&evidencia.archivo_uuid)?;
+
+    db::delete(&record_id).await.map_err(|e| ListaNegraError::Database(e.to_string()))?;
+
+    warn!(
+        "🗑️ Evidencia eliminada: id={}, registro={}, por={eliminado_por}",
+        evidencia.id, evidencia.lista_negra
+    );
+    Ok(())
+}
@@ -0,0 +1,433 @@
+//! # Servicio: Intercambio de Lista Negra entre Sitios
+//!
+//! Las plantas hermanas comparten sus bloqueos mediante archivos firmados en lugar de
+//! hojas de cálculo por correo.
+//!
+//! ## Formato
+//! - `*.json`: encabezado (`version`, `sitio`, `generadoEn`) + `registros`.
+//! - `*.csv`: una fila por bloqueo con las columnas de `COLUMNAS_INTERCAMBIO_CSV`.
+//! - `{archivo}.sig`: firma separada `ed25519:<hex>` sobre los bytes exactos del archivo,
+//!   con la clave privada del sitio guardada en el llavero del sistema. Cada planta tiene
+//!   su propio par de claves y solo comparte la pública.
+//!
+//! ## Importación
+//! Solo se aceptan archivos firmados por un sitio de `lista_negra.sitios_confiables`, y el
+//! `sitio` del encabezado debe ser el de la clave que verificó la firma. Después se
+//! previsualizan los cambios (nuevos, severidad
+//! actualizada, cédulas en conflicto); al aplicar, los nuevos entran por
+//! `lista_negra_service::add_to_lista_negra` con el sitio de origen registrado.
+
+use crate::config::manager::load_config;
+use crate::db::surrealdb_lista_negra_queries as db;
+use crate::domain::common::datetime_to_iso;
+use crate::domain::errors::ListaNegraError;
+use crate::domain::lista_negra::{self as domain, BloqueoLocal, NombrePersona};
+use crate::export::csv::{encode_csv_line, parse_csv};
+use crate::models::lista_negra::{
+    AddToListaNegraInput, ArchivoIntercambioListaNegra, CambioImportacionListaNegra,
+    ErrorImportacionListaNegra, ExportacionListaNegraResponse, ImportacionListaNegraResponse,
+    ListaNegra, PrevisualizacionImportacionListaNegra, RegistroIntercambioListaNegra,
+    TipoCambioImportacion, UpdateListaNegraInput,
+};
+use crate::services::{keyring_service, lista_negra_service};
+use chrono::Utc;
+use log::{info, warn};
+use std::path::Path;
+
+/// Registro del archivo junto con el cambio que produciría en este sitio.
+struct RegistroAnalizado {
+    registro: RegistroIntercambioListaNegra,
+    cambio: CambioImportacionListaNegra,
+    /// ID del bloqueo local con la misma cédula, si existe.
+    local_id: Option<String>,
+}
+
+struct Analisis {
+    sitio_archivo: Option<String>,
+    total: usize,
+    registros: Vec<RegistroAnalizado>,
+    errores: Vec<ErrorImportacionListaNegra>,
+}
+
+// --------------------------------------------------------------------------
+// EXPORTACIÓN
+// --------------------------------------------------------------------------
+
+/// Exporta los bloqueos vigentes a un archivo firmado (`.csv` o `.json` según la extensión).
+///
+/// Los bloqueos importados conservan su sitio de origen; los propios llevan el de este sitio.
+pub async fn exportar(target_path: &str) -> Result<ExportacionListaNegraResponse, ListaNegraError> {
+    let sitio = sitio_local();
+    let registros: Vec<RegistroIntercambioListaNegra> = db::find_vigentes()
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?
+        .into_iter()
+        .map(|ln| registro_desde_bloqueo(ln, &sitio))
+        .collect();
+    let total = registros.len();
+
+    let contenido = if es_csv(target_path) {
+        let mut csv = String::from('\u{feff}');
+        let encabezados: Vec<String> =
+            domain::COLUMNAS_INTERCAMBIO_CSV.iter().map(ToString::to_string).collect();
+        csv.push_str(&encode_csv_line(&encabezados, ','));
+        csv.push('\n');
+        for registro in &registros {
+            csv.push_str(&encode_csv_line(&domain::registro_a_fila_csv(registro), ','));
+            csv.push('\n');
+        }
+        csv.into_bytes()
+    } else {
+        serde_json::to_vec_pretty(&ArchivoIntercambioListaNegra {
+            version: domain::INTERCAMBIO_VERSION,
+            sitio: sitio.clone(),
+            generado_en: Utc::now().to_rfc3339(),
+            registros,
+        })
+        .map_err(|e| ListaNegraError::Internal(format!("Error serializando: {e}")))?
+    };
+
+    let firma = domain::firmar_intercambio(&contenido, &clave_firma()?)?;
+    let firma_path = ruta_firma(target_path);
+
+    std::fs::write(target_path, &contenido)
+        .map_err(|e| ListaNegraError::IO(format!("No se pudo escribir {target_path}: {e}")))?;
+    std::fs::write(&firma_path, format!("{firma}\n"))
+        .map_err(|e| ListaNegraError::IO(format!("No se pudo escribir {firma_path}: {e}")))?;
+
+    info!("📤 Lista negra exportada: {total} bloqueo(s) desde '{sitio}' a {target_path}");
+    Ok(ExportacionListaNegraResponse { total, file_path: target_path.to_string(), firma_path })
+}
+
+// --------------------------------------------------------------------------
+// IMPORTACIÓN
+// --------------------------------------------------------------------------
+
+/// Verifica la firma del archivo y muestra los cambios que produciría, sin aplicarlos.
+///
+/// # Errors
+///
+/// * `ListaNegraError::FirmaInvalida`: Falta el `.sig`, el archivo fue alterado, no lo
+///   firmó un sitio confiable o su encabezado dice venir de otro sitio.
+pub async fn previsualizar(
+    file_path: &str,
+) -> Result<PrevisualizacionImportacionListaNegra, ListaNegraError> {
+    let analisis = analizar(file_path).await?;
+
+    Ok(PrevisualizacionImportacionListaNegra {
+        sitio_origen: analisis.sitio_archivo,
+        total: analisis.total,
+        cambios: analisis.registros.into_iter().map(|r| r.cambio).collect(),
+        errores: analisis.errores,
+    })
+}
+
+/// Aplica un archivo de intercambio: crea los bloqueos nuevos y actualiza severidades.
+///
+/// Las cédulas en conflicto y los registros sin cambios se omiten. El archivo se vuelve a
+/// verificar al aplicar, por si cambió después de la vista previa. Con el doble control
+/// activo, cada alta o cambio queda como propuesta pendiente de `importado_por`.
+pub async fn importar(
+    file_path: &str,
+    importado_por: &str,
+) -> Result<ImportacionListaNegraResponse, ListaNegraError> {
+    let analisis = analizar(file_path).await?;
+    let mut errores = analisis.errores;
+    let (mut creados, mut actualizados, mut omitidos, mut pendientes) = (0, 0, 0, 0);
+
+    for (i, analizado) in analisis.registros.into_iter().enumerate() {
+        let RegistroAnalizado { registro, cambio, local_id } = analizado;
+        let contador =
+            match (cambio.tipo, local_id) {
+                (TipoCambioImportacion::Nuevo, _) => {
+                    aplicar_nuevo(registro, &cambio.sitio_origen, importado_por)
+                        .await
+                        .map(|aplicado| if aplicado { &mut creados } else { &mut pendientes })
+                }
+                (TipoCambioImportacion::SeveridadActualizada, Some(id)) => {
+                    aplicar_severidad(id, registro, &cambio.sitio_origen, importado_por)
+                        .await
+                        .map(|aplicado| if aplicado { &mut actualizados } else { &mut pendientes })
+                }
+                _ => Ok(&mut omitidos),
+            };
+
+        match contador {
+            Ok(contador) => *contador += 1,
+            Err(e) => errores.push(ErrorImportacionListaNegra {
+                registro: i + 1,
+                cedula: Some(cambio.cedula),
+                mensaje: e.to_string(),
+            }),
+        }
+    }
+
+    info!(
+        "📥 Importación de lista negra: {creados} nuevo(s), {actualizados} actualizado(s), \
+         {omitidos} omitido(s), {pendientes} pendiente(s) de aprobación, {} error(es), \
+         por={importado_por}",
+        errores.len()
+    );
+    Ok(ImportacionListaNegraResponse { creados, actualizados, omitidos, pendientes, errores })
+}
+
+/// Retorna `false` si el alta quedó pendiente de aprobación.
+async fn aplicar_nuevo(
+    r: RegistroIntercambioListaNegra,
+    sitio_origen: &str,
+    importado_por: &str,
+) -> Result<bool, ListaNegraError> {
+    lista_negra_service::add_to_lista_negra(AddToListaNegraInput {
+        cedula: r.cedula,
+        nombre: r.nombre,
+        segundo_nombre: r.segundo_nombre,
+        apellido: r.apellido,
+        segundo_apellido: r.segundo_apellido,
+        empresa_id: None,
+        empresa_nombre: r.empresa_nombre,
+        nivel_severidad: r.nivel_severidad,
+        motivo_bloqueo: r.motivo_bloqueo,
+        bloqueado_por: importado_por.to_string(),
+        vigente_hasta: r.vigente_hasta,
+        sitio_origen: Some(sitio_origen.to_string()),
+    })
+    .await
+    .map(|cambio| cambio.aplicado)
+}
+
+/// Retorna `false` si el cambio quedó pendiente de aprobación.
+async fn aplicar_severidad(
+    id: String,
+    r: RegistroIntercambioListaNegra,
+    sitio_origen: &str,
+    importado_por: &str,
+) -> Result<bool, ListaNegraError> {
+    let cambio = lista_negra_service::update(
+        id,
+        UpdateListaNegraInput {
+            nivel_severidad: Some(r.nivel_severidad),
+            motivo_bloqueo: r.motivo_bloqueo,
+            empresa_id: None,
+            empresa_nombre: None,
+            vigente_hasta: r.vigente_hasta,
+        },
+        importado_por,
+    )
+    .await?;
+    // Pendiente de aprobación: todavía no hay registro que etiquetar
+    let Some(actualizado) = cambio.registro else {
+        return Ok(false);
+    };
+
+    let record_id = actualizado
+        .id
+        .parse()
+        .map_err(|_| ListaNegraError::Validation(format!("ID inválido: {}", actualizado.id)))?;
+    db::set_sitio_origen(&record_id, sitio_origen)
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+    Ok(true)
+}
+
+/// Verifica la firma, lee los registros y los compara contra los bloqueos locales.
+async fn analizar(file_path: &str) -> Result<Analisis, ListaNegraError> {
+    let contenido = std::fs::read(file_path)
+        .map_err(|e| ListaNegraError::IO(format!("No se pudo leer {file_path}: {e}")))?;
+    let firma_path = ruta_firma(file_path);
+    let firma = std::fs::read_to_string(&firma_path).map_err(|_| {
+        ListaNegraError::FirmaInvalida(format!("no se encontró la firma {firma_path}"))
+    })?;
+
+    let confiables = lista_negra_service::config_lista_negra().sitios_confiables;
+    let firmante = match domain::verificar_firma_intercambio(&contenido, &firma, &confiables) {
+        Ok(sitio) => sitio.nombre.trim().to_string(),
+        Err(e) => {
+            warn!("🚨 Archivo de intercambio rechazado ({file_path}): {e}");
+            return Err(e);
+        }
+    };
+
+    let (sitio_archivo, leidos) = leer_registros(file_path, &contenido)?;
+    if let Err(e) = domain::validar_sitio_firmante(sitio_archivo.as_deref(), &firmante) {
+        warn!("🚨 Archivo de intercambio rechazado ({file_path}): {e}");
+        return Err(e);
+    }
+    // El CSV no trae encabezado: su sitio es el que lo firmó
+    let sitio_archivo = Some(sitio_archivo.unwrap_or(firmante));
+    let sitio = sitio_local();
+    let ahora = Utc::now();
+    let total = leidos.len();
+    let mut registros = Vec::new();
+    let mut errores = Vec::new();
+
+    for (i, leido) in leidos.into_iter().enumerate() {
+        let error = |cedula: Option<String>, mensaje: String| ErrorImportacionListaNegra {
+            registro: i + 1,
+            cedula,
+            mensaje,
+        };
+
+        let mut registro = match leido {
+            Ok(registro) => registro,
+            Err(mensaje) => {
+                errores.push(error(None, mensaje));
+                continue;
+            }
+        };
+        if registro.sitio_origen.is_none() {
+            registro.sitio_origen.clone_from(&sitio_archivo);
+        }
+
+        let validacion = domain::validar_registro_intercambio(&registro).and_then(|_| {
+            registro
+                .vigente_hasta
+                .as_deref()
+                .map(|v| domain::parsear_vigente_hasta(v, ahora))
+                .transpose()
+        });
+        if let Err(e) = validacion {
+            errores.push(error(Some(registro.cedula), e.to_string()));
+            continue;
+        }
+
+        let sitio_origen = registro.sitio_origen.clone().unwrap_or_default();
+        if sitio_origen.trim().eq_ignore_ascii_case(sitio.trim()) {
+            errores.push(error(
+                Some(registro.cedula),
+                "El bloqueo se originó en este sitio; se omite".to_string(),
+            ));
+            continue;
+        }
+
+        let local = db::find_by_cedula_any_status(registro.cedula.trim())
+            .await
+            .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+        registros.push(analizar_registro(registro, sitio_origen, local.as_ref(), ahora));
+    }
+
+    Ok(Analisis { sitio_archivo, total, registros, errores })
+}
+
+fn analizar_registro(
+    registro: RegistroIntercambioListaNegra,
+    sitio_origen: String,
+    local: Option<&ListaNegra>,
+    ahora: chrono::DateTime<Utc>,
+) -> RegistroAnalizado {
+    let bloqueo_local = local.map(|ln| BloqueoLocal {
+        nombre: NombrePersona {
+            nombre: &ln.nombre,
+            segundo_nombre: ln.segundo_nombre.as_deref(),
+            apellido: &ln.apellido,
+            segundo_apellido: ln.segundo_apellido.as_deref(),
+        },
+        nivel_severidad: &ln.nivel_severidad,
+        vigente: ln.is_active
+            && domain::esta_vigente(
+                ln.vigente_hasta.as_ref().and_then(lista_negra_service::a_utc),
+                ahora,
+            ),
+    });
+    let tipo = domain::clasificar_importacion(&registro, bloqueo_local.as_ref());
+
+    let nombre_completo =
+        |partes: [Option<&str>; 4]| partes.into_iter().flatten().collect::<Vec<_>>().join(" ");
+
+    RegistroAnalizado {
+        cambio: CambioImportacionListaNegra {
+            tipo,
+            sitio_origen,
+            cedula: registro.cedula.clone(),
+            nombre_completo: nombre_completo([
+                Some(registro.nombre.as_str()),
+                registro.segundo_nombre.as_deref(),
+                Some(registro.apellido.as_str()),
+                registro.segundo_apellido.as_deref(),
+            ]),
+            nivel_severidad: registro.nivel_severidad.to_uppercase(),
+            nivel_local: local.map(|ln| ln.nivel_severidad.clone()),
+            nombre_local: local.map(|ln| {
+                nombre_completo([
+                    Some(ln.nombre.as_str()),
+                    ln.segundo_nombre.as_deref(),
+                    Some(ln.apellido.as_str()),
+                    ln.segundo_apellido.as_deref(),
+                ])
+            }),
+        },
+        local_id: local.map(|ln| ln.id.to_string()),
+        registro,
+    }
+}
+
+/// Lee los registros de un archivo ya verificado; retorna el sitio del encabezado (JSON).
+fn leer_registros(
+    file_path: &str,
+    contenido: &[u8],
+) -> Result<(Option<String>, Vec<Result<RegistroIntercambioListaNegra, String>>), ListaNegraError> {
+    if es_csv(file_path) {
+        let texto = std::str::from_utf8(contenido)
+            .map_err(|_| ListaNegraError::Validation("El CSV no está en UTF-8".to_string()))?;
+        let mut filas =
+            parse_csv(texto, ',').map_err(|e| ListaNegraError::Validation(e.to_string()))?;
+        if filas.is_empty() {
+            return Err(ListaNegraError::Validation("El archivo está vacío".to_string()));
+        }
+        let encabezados = filas.remove(0);
+        let registros =
+            filas.iter().map(|fila| domain::registro_desde_fila_csv(&encabezados, fila)).collect();
+        return Ok((None, registros));
+    }
+
+    let archivo: ArchivoIntercambioListaNegra = serde_json::from_slice(contenido).map_err(|e| {
+        ListaNegraError::Validation(format!("Archivo de intercambio inválido: {e}"))
+    })?;
+    if archivo.version != domain::INTERCAMBIO_VERSION {
+        return Err(ListaNegraError::Validation(format!(
+            "Versión de archivo no soportada: {}",
+            archivo.version
+        )));
+    }
+
+    Ok((Some(archivo.sitio), archivo.registros.into_iter().map(Ok).collect()))
+}
+
+// --------------------------------------------------------------------------
+// HELPERS
+// --------------------------------------------------------------------------
+
+fn registro_desde_bloqueo(ln: ListaNegra, sitio: &str) -> RegistroIntercambioListaNegra {
+    RegistroIntercambioListaNegra {
+        cedula: ln.cedula,
+        nombre: ln.nombre,
+        segundo_nombre: ln.segundo_nombre,
+        apellido: ln.apellido,
+        segundo_apellido: ln.segundo_apellido,
+        empresa_nombre: ln.empresa_nombre,
+        nivel_severidad: ln.nivel_severidad,
+        motivo_bloqueo: ln.motivo_bloqueo,
+        vigente_hasta: ln.vigente_hasta.as_ref().map(datetime_to_iso),
+        sitio_origen: Some(ln.sitio_origen.unwrap_or_else(|| sitio.to_string())),
+    }
+}
+
+/// Nombre de este sitio: `lista_negra.sitio` o, si falta, la ubicación de la terminal.
+fn sitio_local() -> String {
+    load_config().map_or_else(
+        |_| "Sin asignar".to_string(),
+        |c| c.lista_negra.sitio.filter(|s| !s.trim().is_empty()).unwrap_or(c.terminal.ubicacion),
+    )
+}
+
+fn clave_firma() -> Result<Vec<u8>, ListaNegraError> {
+    keyring_service::get_or_create_lista_negra_signing_key()
+        .map_err(|e| ListaNegraError::Internal(e.to_string()))
+}
+
+fn ruta_firma(file_path: &str) -> String {
+    format!("{file_path}.sig")
+}
+
+fn es_csv(file_path: &str) -> bool {
+    Path::new(file_path).extension().is_some_and(|e| e.eq_ignore_ascii_case("csv"))
+}
@@ -0,0 +1,295 @@
+/// Servicio: Aprobación de Cambios a la Lista Negra (Doble Control).
+///
+/// Con `lista_negra.requiere_aprobacion` activo, quien tiene `lista_negra:create`,
+/// `update` o `delete` ya no modifica la lista directamente: su cambio queda como
+/// propuesta pendiente y un supervisor (`lista_negra_aprobacion:update`) lo aprueba o
+/// lo rechaza con un comentario.
+///
+/// Responsabilidades:
+/// - Registrar propuestas de alta, modificación y baja (una pendiente por cédula).
+/// - Resolver propuestas: quien propone no puede resolver la suya.
+/// - Aplicar la propuesta aprobada mediante `lista_negra_service`.
+/// - Exponer bloqueos propuestos para la advertencia temporal en el ingreso.
+///
+/// Cada propuesta y su resolución quedan en `audit_log` (eventos de la tabla).
+use crate::db::surrealdb_lista_negra_propuesta_queries::{self as db, NuevaPropuesta};
+use crate::db::surrealdb_lista_negra_queries as lista_negra_db;
+use crate::domain::errors::ListaNegraError;
+use crate::domain::lista_negra as domain;
+use crate::models::lista_negra::{
+    AddToListaNegraInput, EstadoPropuestaListaNegra, PropuestaListaNegra,
+    PropuestaListaNegraResponse, ResolverPropuestaInput, TipoPropuestaListaNegra,
+    UpdateListaNegraInput,
+};
+use crate::services::lista_negra_service;
+use log::{error, info, warn};
+use surrealdb::RecordId;
+
+fn parse_id(id: &str, tabla: &str) -> Result<RecordId, ListaNegraError> {
+    if id.contains(':') {
+        id.parse().map_err(|_| ListaNegraError::Validation(format!("ID inválido: {id}")))
+    } else {
+        Ok(RecordId::from_table_key(tabla, id))
+    }
+}
+
+fn nombre_completo(partes: [Option<&str>; 4]) -> String {
+    partes.into_iter().flatten().flat_map(str::split_whitespace).collect::<Vec<_>>().join(" ")
+}
+
+/// Evita dos propuestas abiertas sobre la misma persona.
+async fn verificar_sin_pendiente(cedula: &str) -> Result<(), ListaNegraError> {
+    let pendiente = db::find_pendiente_by_cedula(cedula)
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+
+    match pendiente {
+        Some(p) => Err(ListaNegraError::Validation(format!(
+            "Ya existe una propuesta pendiente para la cédula {cedula} ({})",
+            p.id
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn serializar<T: serde::Serialize>(datos: &T) -> Result<String, ListaNegraError> {
+    serde_json::to_string(datos)
+        .map_err(|e| ListaNegraError::Internal(format!("Error serializando propuesta: {e}")))
+}
+
+// --------------------------------------------------------------------------
+// PROPUESTAS
+// --------------------------------------------------------------------------
+
+/// Propone el bloqueo de una persona; el proponente es `input.bloqueado_por`.
+///
+/// # Errors
+///
+/// * `ListaNegraError::AlreadyExists`: La persona ya tiene un bloqueo vigente.
+/// * `ListaNegraError::Validation`: Datos inválidos o propuesta pendiente para la cédula.
+pub(crate) async fn proponer_alta(
+    input: &AddToListaNegraInput,
+) -> Result<PropuestaListaNegraResponse, ListaNegraError> {
+    domain::validar_add_input(input)?;
+    let cedula = input.cedula.trim();
+
+    let bloqueo = lista_negra_db::check_if_blocked_by_cedula(cedula)
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+    if bloqueo.is_blocked {
+        return Err(ListaNegraError::AlreadyExists);
+    }
+    verificar_sin_pendiente(cedula).await?;
+
+    let nivel = input.nivel_severidad.trim().to_uppercase();
+    let propuesta = db::create(NuevaPropuesta {
+        tipo: TipoPropuestaListaNegra::Alta,
+        lista_negra: None,
+        cedula,
+        nombre_completo: &nombre_completo([
+            Some(input.nombre.as_str()),
+            input.segundo_nombre.as_deref(),
+            Some(input.apellido.as_str()),
+            input.segundo_apellido.as_deref(),
+        ]),
+        nivel_severidad: Some(nivel.as_str()),
+        motivo: input.motivo_bloqueo.as_deref().map(str::trim),
+        datos: &serializar(input)?,
+        propuesto_por: input.bloqueado_por.trim(),
+    })
+    .await
+    .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+
+    warn!(
+        "📝 BLOQUEO PROPUESTO (pendiente de aprobación): cédula={cedula}, nivel={nivel}, por={}",
+        propuesta.propuesto_por
+    );
+    Ok(propuesta.into())
+}
+
+/// Propone modificar un bloqueo existente.
+pub(crate) async fn proponer_modificacion(
+    id: &str,
+    input: &UpdateListaNegraInput,
+    propuesto_por: &str,
+) -> Result<PropuestaListaNegraResponse, ListaNegraError> {
+    domain::validar_update_input(input)?;
+
+    let record_id = parse_id(id, "lista_negra")?;
+    let registro = lista_negra_db::find_by_id(&record_id)
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?
+        .ok_or(ListaNegraError::NotFound)?;
+    verificar_sin_pendiente(&registro.cedula).await?;
+
+    let nivel = input.nivel_severidad.as_deref().map(|n| n.trim().to_uppercase());
+    let propuesta = db::create(NuevaPropuesta {
+        tipo: TipoPropuestaListaNegra::Modificacion,
+        lista_negra: Some(&record_id),
+        cedula: &registro.cedula,
+        nombre_completo: &nombre_completo([
+            Some(registro.nombre.as_str()),
+            registro.segundo_nombre.as_deref(),
+            Some(registro.apellido.as_str()),
+            registro.segundo_apellido.as_deref(),
+        ]),
+        nivel_severidad: nivel.as_deref(),
+        motivo: input.motivo_bloqueo.as_deref().map(str::trim),
+        datos: &serializar(input)?,
+        propuesto_por,
+    })
+    .await
+    .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+
+    info!("📝 Modificación propuesta: registro={record_id}, por={propuesto_por}");
+    Ok(propuesta.into())
+}
+
+/// Propone levantar un bloqueo.
+pub(crate) async fn proponer_baja(
+    id: &str,
+    propuesto_por: &str,
+) -> Result<PropuestaListaNegraResponse, ListaNegraError> {
+    let record_id = parse_id(id, "lista_negra")?;
+    let registro = lista_negra_db::find_by_id(&record_id)
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?
+        .ok_or(ListaNegraError::NotFound)?;
+    verificar_sin_pendiente(&registro.cedula).await?;
+
+    let propuesta = db::create(NuevaPropuesta {
+        tipo: TipoPropuestaListaNegra::Baja,
+        lista_negra: Some(&record_id),
+        cedula: &registro.cedula,
+        nombre_completo: &nombre_completo([
+            Some(registro.nombre.as_str()),
+            registro.segundo_nombre.as_deref(),
+            Some(registro.apellido.as_str()),
+            registro.segundo_apellido.as_deref(),
+        ]),
+        nivel_severidad: Some(registro.nivel_severidad.as_str()),
+        motivo: None,
+        datos: "{}",
+        propuesto_por,
+    })
+    .await
+    .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+
+    info!("📝 Baja propuesta: registro={record_id}, por={propuesto_por}");
+    Ok(propuesta.into())
+}
+
+// --------------------------------------------------------------------------
+// CONSULTA Y RESOLUCIÓN
+// --------------------------------------------------------------------------
+
+/// Propuestas pendientes de revisión, las más antiguas primero.
+pub async fn get_pendientes() -> Result<Vec<PropuestaListaNegraResponse>, ListaNegraError> {
+    let propuestas =
+        db::find_pendientes().await.map_err(|e| ListaNegraError::Database(e.to_string()))?;
+
+    Ok(propuestas.into_iter().map(std::convert::Into::into).collect())
+}
+
+/// Bloqueo propuesto y aún sin aprobar para una cédula.
+///
+/// Alimenta la advertencia temporal del motor; no deniega el ingreso. Retorna `None`
+/// si `lista_negra.advertir_propuestas` está desactivado.
+pub async fn bloqueo_propuesto(
+    cedula: &str,
+) -> Result<Option<PropuestaListaNegra>, ListaNegraError> {
+    if !lista_negra_service::config_lista_negra().advertir_propuestas {
+        return Ok(None);
+    }
+
+    db::find_pendiente_by_cedula(cedula)
+        .await
+        .map(|p| p.filter(|p| p.tipo == TipoPropuestaListaNegra::Alta))
+        .map_err(|e| ListaNegraError::Database(e.to_string()))
+}
+
+/// Aprueba o rechaza una propuesta.
+///
+/// La propuesta se marca resuelta antes de aplicarse, de modo que dos supervisores no
+/// la apliquen dos veces. Si la aplicación falla, vuelve a quedar pendiente.
+///
+/// # Errors
+///
+/// * `ListaNegraError::Unauthorized`: El revisor es quien la propuso.
+/// * `ListaNegraError::Validation`: Sin comentario o ya resuelta.
+/// * Cualquier error de `lista_negra_service` al aplicar el cambio aprobado.
+pub async fn resolver(
+    id: &str,
+    input: ResolverPropuestaInput,
+    revisor: &str,
+) -> Result<PropuestaListaNegraResponse, ListaNegraError> {
+    let record_id = parse_id(id, "lista_negra_propuesta")?;
+    let propuesta = db::find_by_id(&record_id)
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?
+        .ok_or(ListaNegraError::NotFound)?;
+
+    domain::validar_resolucion(
+        propuesta.estado,
+        &propuesta.propuesto_por,
+        revisor,
+        &input.comentario,
+    )?;
+
+    let estado = if input.aprobar {
+        EstadoPropuestaListaNegra::Aprobada
+    } else {
+        EstadoPropuestaListaNegra::Rechazada
+    };
+    let comentario = domain::normalizar_texto(&input.comentario);
+
+    let resuelta = db::resolver(&record_id, estado, revisor, &comentario)
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?
+        .ok_or_else(|| ListaNegraError::Validation("La propuesta ya fue resuelta".to_string()))?;
+
+    if input.aprobar {
+        if let Err(e) = aplicar(&resuelta).await {
+            error!("❌ No se pudo aplicar la propuesta {record_id}: {e}");
+            if let Err(e) = db::reabrir(&record_id).await {
+                error!("❌ No se pudo reabrir la propuesta {record_id}: {e}");
+            }
+            return Err(e);
+        }
+    }
+
+    info!(
+        target: "audit",
+        "[LISTA_NEGRA] propuesta={} tipo={} cédula={} {} por={revisor}: {comentario}",
+        resuelta.id,
+        resuelta.tipo.as_str(),
+        resuelta.cedula,
+        estado.as_str()
+    );
+    Ok(resuelta.into())
+}
+
+/// Aplica el cambio guardado en una propuesta aprobada.
+async fn aplicar(propuesta: &PropuestaListaNegra) -> Result<(), ListaNegraError> {
+    let registro = || {
+        propuesta.lista_negra.as_ref().map(ToString::to_string).ok_or_else(|| {
+            ListaNegraError::Internal(format!("Propuesta {} sin registro asociado", propuesta.id))
+        })
+    };
+    let datos_invalidos =
+        |e: serde_json::Error| ListaNegraError::Internal(format!("Propuesta ilegible: {e}"));
+
+    match propuesta.tipo {
+        TipoPropuestaListaNegra::Alta => {
+            let input: AddToListaNegraInput =
+                serde_json::from_str(&propuesta.datos).map_err(datos_invalidos)?;
+            lista_negra_service::aplicar_alta(input).await.map(|_| ())
+        }
+        TipoPropuestaListaNegra::Modificacion => {
+            let input: UpdateListaNegraInput =
+                serde_json::from_str(&propuesta.datos).map_err(datos_invalidos)?;
+            lista_negra_service::aplicar_actualizacion(registro()?, input).await.map(|_| ())
+        }
+        TipoPropuestaListaNegra::Baja => lista_negra_service::aplicar_baja(registro()?).await,
+    }
+}
@@ -7,8 +7,14 @@
 //!
 //! ## Responsabilidades
 //! - Verificación de bloqueo por cédula (hot-path de seguridad)
+//! - Coincidencia aproximada por nombre cuando la cédula no coincide
 //! - Gestión CRUD de registros de lista negra
 //! - Validación de datos mediante capa de dominio
+//! - Vigencia y revisión periódica de los bloqueos según su severidad
+//! - Vetos a nivel de empresa (contratistas y proveedores vinculados)
+//! - Doble control: con `lista_negra.requiere_aprobacion` las altas, modificaciones,
+//!   bajas, restauraciones y levantamientos en revisión se convierten en propuestas
+//!   (ver `lista_negra_propuesta_service`)
 //! - Logging de operaciones críticas de seguridad
 //!
 //! ## Arquitectura
@@ -18,15 +24,24 @@
 //!            Domain (validaciones)
 //! ```
 
+use crate::config::manager::load_config;
+use crate::config::settings::ListaNegraConfig;
+use crate::db::surrealdb_empresa_queries as empresa_db;
 use crate::db::surrealdb_lista_negra_queries as db;
+use crate::domain::common::datetime_to_iso;
 use crate::domain::errors::ListaNegraError;
-use crate::domain::lista_negra as domain;
+use crate::domain::lista_negra::{self as domain, NombrePersona, VigenciaBloqueo};
 use crate::models::lista_negra::{
-    AddToListaNegraInput, BlockCheckResponse, ListaNegraListResponse, ListaNegraResponse,
-    NivelStats, UpdateListaNegraInput,
+    AddToListaNegraInput, BlockCheckResponse, CambioListaNegraResponse, CoincidenciaListaNegra,
+    EmpresaVetadaResponse, EmpresaVetadaStatus, ListaNegraListResponse, ListaNegraResponse,
+    NivelStats, RevisarListaNegraInput, UpdateEmpresaVetadaInput, UpdateListaNegraInput,
+    VetarEmpresaInput,
 };
+use crate::services::lista_negra_propuesta_service as propuesta_service;
+use crate::services::search_service::SearchService;
+use chrono::{DateTime, Utc};
 use log::{debug, info, warn};
-use surrealdb::RecordId;
+use surrealdb::{Datetime, RecordId};
 
 // --------------------------------------------------------------------------
 // VERIFICACIÓN DE BLOQUEO (HOT PATH)
@@ -57,6 +72,90 @@ pub async fn check_is_blocked(cedula: String) -> Result<BlockCheckResponse, List
         .map_err(|e| ListaNegraError::Database(e.to_string()))
 }
 
+/// Candidatos que se piden al índice antes de puntuar.
+const CANDIDATOS_INDICE: usize = 25;
+
+/// Máximo de posibles coincidencias reportadas al guardia.
+const MAX_COINCIDENCIAS: usize = 5;
+
+/// Busca bloqueos vigentes de **otra cédula** cuyo nombre se parece al de la persona.
+///
+/// Complementa a `check_is_blocked`: quien está bloqueado y se presenta con otro
+/// documento (pasaporte, cédula nueva) no coincide por cédula, pero sí por nombre.
+/// El índice de búsqueda acota los candidatos (sin tildes y con tolerancia a errores de
+/// tipeo) y la base de datos confirma que sigan vigentes. Sin índice se comparan todos
+/// los bloqueos vigentes.
+///
+/// ## Retorno
+/// Coincidencias con puntaje igual o superior a `lista_negra.umbral_coincidencia_nombre`,
+/// de mayor a menor.
+pub async fn buscar_coincidencias_nombre(
+    search: Option<&SearchService>,
+    persona: &NombrePersona<'_>,
+    cedula: &str,
+) -> Result<Vec<CoincidenciaListaNegra>, ListaNegraError> {
+    let tokens: Vec<String> =
+        persona.tokens_nombres().into_iter().chain(persona.tokens_apellidos()).collect();
+    if tokens.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let candidatos = match search {
+        Some(search) => {
+            let terminos: Vec<(String, u8)> = tokens
+                .into_iter()
+                .map(|t| {
+                    let distancia = domain::distancia_maxima(t.chars().count());
+                    (t, distancia)
+                })
+                .collect();
+            match search.search_lista_negra_por_nombre(&terminos, CANDIDATOS_INDICE) {
+                Ok(resultados) => {
+                    let ids = resultados.iter().filter_map(|r| r.id.parse::<RecordId>().ok());
+                    db::find_vigentes_by_ids(ids.collect()).await
+                }
+                Err(e) => {
+                    warn!("⚠️ Índice no disponible para coincidencia por nombre: {e}");
+                    db::find_vigentes().await
+                }
+            }
+        }
+        None => db::find_vigentes().await,
+    }
+    .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+
+    let umbral = config_lista_negra().umbral_coincidencia_nombre;
+    let cedula = cedula.trim();
+
+    let mut coincidencias: Vec<CoincidenciaListaNegra> = candidatos
+        .into_iter()
+        .filter(|ln| ln.cedula != cedula)
+        .filter_map(|ln| {
+            let score = domain::similitud_nombres(
+                persona,
+                &NombrePersona {
+                    nombre: &ln.nombre,
+                    segundo_nombre: ln.segundo_nombre.as_deref(),
+                    apellido: &ln.apellido,
+                    segundo_apellido: ln.segundo_apellido.as_deref(),
+                },
+            );
+            (score >= umbral).then(|| CoincidenciaListaNegra::new(ln, score))
+        })
+        .collect();
+
+    coincidencias.sort_by(|a, b| b.score.total_cmp(&a.score));
+    coincidencias.truncate(MAX_COINCIDENCIAS);
+
+    if !coincidencias.is_empty() {
+        warn!(
+            "⚠️ POSIBLE COINCIDENCIA EN LISTA NEGRA: cédula {cedula} se parece a {} bloqueo(s)",
+            coincidencias.len()
+        );
+    }
+    Ok(coincidencias)
+}
+
 // --------------------------------------------------------------------------
 // OPERACIONES DE LECTURA
 // --------------------------------------------------------------------------
@@ -145,6 +244,9 @@ pub async fn search(query: &str) -> Result<Vec<ListaNegraResponse>, ListaNegraEr
 
 /// Agrega una persona a la lista negra.
 ///
+/// Con la política de doble control activa el bloqueo no se aplica: queda como
+/// propuesta de `input.bloqueado_por` hasta que un supervisor la apruebe.
+///
 /// ## Flujo de Operación
 /// 1. Validar input (dominio)
 /// 2. Verificar que no esté ya bloqueado
@@ -160,11 +262,26 @@ pub async fn search(query: &str) -> Result<Vec<ListaNegraResponse>, ListaNegraEr
 /// * `ListaNegraError::AlreadyBlocked` - Ya existe bloqueo activo
 /// * `ListaNegraError::Database` - Error de persistencia
 ///
+/// ## Retorno
+/// * `aplicado = false` con la propuesta cuando queda en aprobación (doble control)
+///
 /// ## Logging
 /// - `INFO`: Persona agregada a lista negra (operación crítica de seguridad)
 /// - `WARN`: Intento de bloquear persona ya bloqueada
 pub async fn add_to_lista_negra(
     input: AddToListaNegraInput,
+) -> Result<CambioListaNegraResponse, ListaNegraError> {
+    if config_lista_negra().requiere_aprobacion {
+        let propuesta = propuesta_service::proponer_alta(&input).await?;
+        return Ok(CambioListaNegraResponse::pendiente(propuesta));
+    }
+
+    aplicar_alta(input).await.map(|r| CambioListaNegraResponse::aplicado(Some(r)))
+}
+
+/// Aplica un bloqueo sin pasar por el doble control (alta directa o propuesta aprobada).
+pub(crate) async fn aplicar_alta(
+    input: AddToListaNegraInput,
 ) -> Result<ListaNegraResponse, ListaNegraError> {
     info!("🚫 Iniciando bloqueo: cédula={}", input.cedula);
 
@@ -176,8 +293,19 @@ pub async fn add_to_lista_negra(
         .await
         .map_err(|e| ListaNegraError::Database(e.to_string()))?;
 
+    let ahora = Utc::now();
+    let nivel = domain::validar_nivel_severidad(&input.nivel_severidad)?;
+    let vigente_hasta = input
+        .vigente_hasta
+        .as_deref()
+        .map(|v| domain::parsear_vigente_hasta(v, ahora))
+        .transpose()?;
+    let vigencia = vigencia_para(&nivel, ahora, vigente_hasta);
+
     if let Some(registro) = existente {
-        if registro.is_active {
+        // Un bloqueo vencido que la tarea aún no desactivó se trata como inactivo
+        let vigente = domain::esta_vigente(registro.vigente_hasta.as_ref().and_then(a_utc), ahora);
+        if registro.is_active && vigente {
             warn!("⚠️ Persona ya bloqueada (activa): cédula={}", input.cedula);
             return Err(ListaNegraError::AlreadyExists);
         }
@@ -189,7 +317,11 @@ pub async fn add_to_lista_negra(
         );
 
         // 1. Restaurar (activar)
-        db::restore(&registro.id).await.map_err(|e| ListaNegraError::Database(e.to_string()))?;
+        if !registro.is_active {
+            db::restore(&registro.id)
+                .await
+                .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+        }
 
         // 2. Actualizar con los nuevos datos
         let update_input = UpdateListaNegraInput {
@@ -203,6 +335,7 @@ pub async fn add_to_lista_negra(
                 }
             }),
             empresa_nombre: input.empresa_nombre,
+            vigente_hasta: None,
         };
 
         // Nota: db::update solo actualiza campos específicos.
@@ -210,10 +343,19 @@ pub async fn add_to_lista_negra(
         // o asumir que la identidad (cédula/nombre) no cambia.
         // Por ahora, actualizamos lo crítico (motivo, nivel, empresa).
 
-        let updated = db::update(&registro.id, &update_input)
+        db::update(&registro.id, &update_input)
             .await
             .map_err(|e| ListaNegraError::Database(e.to_string()))?;
 
+        if let Some(sitio) = input.sitio_origen.as_deref() {
+            db::set_sitio_origen(&registro.id, sitio.trim())
+                .await
+                .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+        }
+
+        // 3. Reiniciar vigencia y revisión desde hoy
+        let updated = guardar_vigencia(&registro.id, vigencia).await?;
+
         info!("🚫 Persona re-bloqueada exitosamente: id={}", updated.id);
         return Ok(updated.into());
     }
@@ -236,12 +378,18 @@ pub async fn add_to_lista_negra(
         nivel_severidad: input.nivel_severidad.to_uppercase(),
         motivo_bloqueo: input.motivo_bloqueo.map(|m| domain::normalizar_texto(&m)),
         bloqueado_por: input.bloqueado_por.trim().to_string(),
+        vigente_hasta: None,
+        sitio_origen: input.sitio_origen.map(|s| s.trim().to_string()),
     };
 
     // 4. Crear en DB
-    let created = db::create(&input_normalizado)
-        .await
-        .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+    let created = db::create(
+        &input_normalizado,
+        vigencia.vigente_hasta.map(Datetime::from),
+        Datetime::from(vigencia.proxima_revision),
+    )
+    .await
+    .map_err(|e| ListaNegraError::Database(e.to_string()))?;
 
     // 5. Log de operación crítica
     info!(
@@ -263,16 +411,40 @@ pub async fn add_to_lista_negra(
 /// - `nivel_severidad`: Puede cambiar la gravedad
 /// - `motivo_bloqueo`: Agregar más información (opcional)
 ///
+/// Con la política de doble control activa el cambio queda como propuesta de
+/// `solicitado_por` y se retorna sin aplicar.
+///
 /// ## Logging
 /// - `INFO`: Registro actualizado
 pub async fn update(
     id: String,
     input: UpdateListaNegraInput,
+    solicitado_por: &str,
+) -> Result<CambioListaNegraResponse, ListaNegraError> {
+    if config_lista_negra().requiere_aprobacion {
+        let propuesta =
+            propuesta_service::proponer_modificacion(&id, &input, solicitado_por).await?;
+        return Ok(CambioListaNegraResponse::pendiente(propuesta));
+    }
+
+    aplicar_actualizacion(id, input).await.map(|r| CambioListaNegraResponse::aplicado(Some(r)))
+}
+
+/// Aplica una modificación sin pasar por el doble control.
+pub(crate) async fn aplicar_actualizacion(
+    id: String,
+    input: UpdateListaNegraInput,
 ) -> Result<ListaNegraResponse, ListaNegraError> {
     info!("✏️ Actualizando lista negra: id={id}");
 
     // Validar input
     domain::validar_update_input(&input)?;
+    let ahora = Utc::now();
+    let vigente_hasta = input
+        .vigente_hasta
+        .as_deref()
+        .map(|v| domain::parsear_vigente_hasta(v, ahora))
+        .transpose()?;
 
     // Normalizar ID empresa si existe
     let mut input_normalizado = input.clone();
@@ -287,7 +459,7 @@ pub async fn update(
         id.parse().map_err(|_| ListaNegraError::Validation(format!("ID inválido: {id}")))?;
 
     // Actualizar
-    let updated = db::update(&record_id, &input_normalizado).await.map_err(|e| {
+    let mut updated = db::update(&record_id, &input_normalizado).await.map_err(|e| {
         let msg = e.to_string();
         if msg.contains("no encontrado") || msg.contains("not found") {
             ListaNegraError::NotFound
@@ -296,6 +468,12 @@ pub async fn update(
         }
     })?;
 
+    // Un cambio de severidad o de vencimiento reprograma la vigencia desde hoy
+    if input.nivel_severidad.is_some() || vigente_hasta.is_some() {
+        let nivel = domain::validar_nivel_severidad(&updated.nivel_severidad)?;
+        updated = guardar_vigencia(&record_id, vigencia_para(&nivel, ahora, vigente_hasta)).await?;
+    }
+
     info!("✏️ Lista negra actualizada: id={}, cédula={}", updated.id, updated.cedula);
 
     Ok(updated.into())
@@ -309,9 +487,25 @@ pub async fn update(
 /// - Auditoría histórica
 /// - Restauración si fue un error
 ///
+/// Con la política de doble control activa la baja queda como propuesta de
+/// `solicitado_por` y se retorna sin aplicar.
+///
 /// ## Logging
 /// - `WARN`: Persona removida de lista negra (operación notable)
-pub async fn delete(id: String) -> Result<(), ListaNegraError> {
+pub async fn delete(
+    id: String,
+    solicitado_por: &str,
+) -> Result<CambioListaNegraResponse, ListaNegraError> {
+    if config_lista_negra().requiere_aprobacion {
+        let propuesta = propuesta_service::proponer_baja(&id, solicitado_por).await?;
+        return Ok(CambioListaNegraResponse::pendiente(propuesta));
+    }
+
+    aplicar_baja(id).await.map(|()| CambioListaNegraResponse::aplicado(None))
+}
+
+/// Aplica una baja sin pasar por el doble control.
+pub(crate) async fn aplicar_baja(id: String) -> Result<(), ListaNegraError> {
     warn!("🗑️ Removiendo de lista negra: id={id}");
 
     // Parsear ID
@@ -337,15 +531,44 @@ pub async fn delete(id: String) -> Result<(), ListaNegraError> {
 
 /// Restaura un registro previamente eliminado.
 ///
+/// Con la política de doble control activa la restauración es un alta más: queda
+/// como propuesta de `solicitado_por` con los datos del registro eliminado.
+///
 /// ## Logging
 /// - `WARN`: Persona restaurada a lista negra
-pub async fn restore(id: String) -> Result<ListaNegraResponse, ListaNegraError> {
+pub async fn restore(
+    id: String,
+    solicitado_por: &str,
+) -> Result<CambioListaNegraResponse, ListaNegraError> {
     warn!("♻️ Restaurando a lista negra: id={id}");
 
     // Parsear ID
     let record_id: RecordId =
         id.parse().map_err(|_| ListaNegraError::Validation(format!("ID inválido: {id}")))?;
 
+    if config_lista_negra().requiere_aprobacion {
+        let registro = db::find_by_id(&record_id)
+            .await
+            .map_err(|e| ListaNegraError::Database(e.to_string()))?
+            .ok_or(ListaNegraError::NotFound)?;
+        let input = AddToListaNegraInput {
+            cedula: registro.cedula,
+            nombre: registro.nombre,
+            segundo_nombre: registro.segundo_nombre,
+            apellido: registro.apellido,
+            segundo_apellido: registro.segundo_apellido,
+            empresa_id: registro.empresa_id,
+            empresa_nombre: registro.empresa_nombre,
+            nivel_severidad: registro.nivel_severidad,
+            motivo_bloqueo: registro.motivo_bloqueo,
+            bloqueado_por: solicitado_por.to_string(),
+            vigente_hasta: None,
+            sitio_origen: registro.sitio_origen,
+        };
+        let propuesta = propuesta_service::proponer_alta(&input).await?;
+        return Ok(CambioListaNegraResponse::pendiente(propuesta));
+    }
+
     // Restaurar
     let restored = db::restore(&record_id).await.map_err(|e| {
         let msg = e.to_string();
@@ -356,12 +579,288 @@ pub async fn restore(id: String) -> Result<ListaNegraResponse, ListaNegraError>
         }
     })?;
 
+    // La vigencia anterior pudo haber vencido: se reprograma desde hoy
+    let nivel = domain::validar_nivel_severidad(&restored.nivel_severidad)?;
+    let restored = guardar_vigencia(&record_id, vigencia_para(&nivel, Utc::now(), None)).await?;
+
     warn!(
         "♻️ PERSONA RESTAURADA A LISTA NEGRA: id={}, cédula={}, nombre={} {}",
         restored.id, restored.cedula, restored.nombre, restored.apellido
     );
 
-    Ok(restored.into())
+    Ok(CambioListaNegraResponse::aplicado(Some(restored.into())))
+}
+
+// --------------------------------------------------------------------------
+// EMPRESAS VETADAS
+// --------------------------------------------------------------------------
+
+/// Verifica si una empresa tiene un veto activo.
+///
+/// Hot-path de registro e ingreso de contratistas y proveedores: complementa a
+/// `check_is_blocked`, que solo mira la cédula de la persona.
+pub async fn check_empresa_vetada(
+    empresa: &RecordId,
+) -> Result<EmpresaVetadaStatus, ListaNegraError> {
+    db::check_empresa_vetada(empresa)
+        .await
+        .map(EmpresaVetadaStatus::from)
+        .map_err(|e| ListaNegraError::Database(e.to_string()))
+}
+
+/// Vetos de empresa activos.
+pub async fn get_empresas_vetadas() -> Result<Vec<EmpresaVetadaResponse>, ListaNegraError> {
+    let vetos =
+        db::find_empresas_vetadas().await.map_err(|e| ListaNegraError::Database(e.to_string()))?;
+
+    Ok(vetos.into_iter().map(std::convert::Into::into).collect())
+}
+
+/// Veta una empresa completa.
+///
+/// Si la empresa tuvo un veto que luego se levantó, se reactiva con los datos nuevos.
+///
+/// ## Errores
+/// * `ListaNegraError::AlreadyExists` - La empresa ya tiene un veto activo
+/// * `ListaNegraError::NotFound` - La empresa no existe
+pub async fn vetar_empresa(
+    input: VetarEmpresaInput,
+    bloqueado_por: &str,
+) -> Result<EmpresaVetadaResponse, ListaNegraError> {
+    domain::validar_vetar_empresa_input(&input)?;
+
+    let empresa_id = parse_empresa_id(&input.empresa_id)?;
+    let empresa = empresa_db::find_by_id(&empresa_id)
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?
+        .ok_or(ListaNegraError::NotFound)?;
+
+    let nivel = input.nivel_severidad.to_uppercase();
+    let motivo = domain::normalizar_texto(&input.motivo);
+
+    let existente = db::find_empresa_vetada_any_status(&empresa_id)
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+
+    let veto = match existente {
+        Some(veto) if veto.is_active => {
+            warn!("⚠️ Empresa ya vetada: {}", empresa.nombre);
+            return Err(ListaNegraError::AlreadyExists);
+        }
+        Some(veto) => {
+            let cambios = UpdateEmpresaVetadaInput {
+                nivel_severidad: Some(nivel),
+                motivo: Some(motivo),
+                bloquear_registros: Some(input.bloquear_registros),
+            };
+            db::update_empresa_vetada(&veto.id, &cambios, Some(bloqueado_por)).await
+        }
+        None => {
+            db::create_empresa_vetada(
+                &empresa_id,
+                &nivel,
+                &motivo,
+                input.bloquear_registros,
+                bloqueado_por,
+            )
+            .await
+        }
+    }
+    .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+
+    warn!(
+        "🚫 EMPRESA VETADA: {} (nivel={}, bloquea registros={}, por={bloqueado_por})",
+        empresa.nombre, veto.nivel_severidad, veto.bloquear_registros
+    );
+
+    Ok(veto.into())
+}
+
+/// Modifica severidad, motivo o el bloqueo de registros de un veto activo.
+pub async fn update_empresa_vetada(
+    id: String,
+    input: UpdateEmpresaVetadaInput,
+) -> Result<EmpresaVetadaResponse, ListaNegraError> {
+    domain::validar_update_empresa_vetada_input(&input)?;
+
+    let record_id: RecordId =
+        id.parse().map_err(|_| ListaNegraError::Validation(format!("ID inválido: {id}")))?;
+
+    let cambios = UpdateEmpresaVetadaInput {
+        nivel_severidad: input.nivel_severidad.map(|n| n.to_uppercase()),
+        motivo: input.motivo.map(|m| domain::normalizar_texto(&m)),
+        bloquear_registros: input.bloquear_registros,
+    };
+
+    let veto = db::update_empresa_vetada(&record_id, &cambios, None).await.map_err(|e| {
+        let msg = e.to_string();
+        if msg.contains("no encontrado") {
+            ListaNegraError::NotFound
+        } else {
+            ListaNegraError::Database(msg)
+        }
+    })?;
+
+    info!("✏️ Veto de empresa actualizado: id={}", veto.id);
+    Ok(veto.into())
+}
+
+/// Levanta el veto de una empresa (soft delete).
+pub async fn levantar_veto_empresa(id: String) -> Result<(), ListaNegraError> {
+    let record_id: RecordId =
+        id.parse().map_err(|_| ListaNegraError::Validation(format!("ID inválido: {id}")))?;
+
+    let veto = db::find_empresa_vetada_by_id(&record_id)
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?
+        .filter(|v| v.is_active)
+        .ok_or(ListaNegraError::NotFound)?;
+
+    db::levantar_empresa_vetada(&record_id)
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+
+    warn!(
+        "🗑️ VETO DE EMPRESA LEVANTADO: id={}, empresa={}",
+        veto.id,
+        veto.empresa_nombre.as_deref().unwrap_or("?")
+    );
+    Ok(())
+}
+
+/// Acepta el ID de empresa con o sin prefijo de tabla.
+fn parse_empresa_id(id: &str) -> Result<RecordId, ListaNegraError> {
+    if id.contains(':') {
+        id.parse().map_err(|_| ListaNegraError::Validation(format!("ID de empresa inválido: {id}")))
+    } else {
+        Ok(RecordId::from_table_key("empresa", id.trim()))
+    }
+}
+
+// --------------------------------------------------------------------------
+// VIGENCIA Y REVISIÓN PERIÓDICA
+// --------------------------------------------------------------------------
+
+/// Bloqueos cuya revisión vence dentro de `aviso_revision_dias` (o ya venció).
+pub async fn get_revisiones_pendientes() -> Result<Vec<ListaNegraResponse>, ListaNegraError> {
+    let aviso = config_lista_negra().aviso_revision_dias;
+    let hasta = Utc::now() + chrono::Duration::days(i64::from(aviso));
+
+    let registros = db::find_revision_pendiente(Datetime::from(hasta))
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+
+    Ok(registros.into_iter().map(std::convert::Into::into).collect())
+}
+
+/// Registra la revisión periódica de un bloqueo.
+///
+/// ## Flujo
+/// - `mantener = true`: se programa la siguiente revisión según el nivel
+///   (y opcionalmente un nuevo vencimiento).
+/// - `mantener = false`: el bloqueo se levanta como una baja; con doble control
+///   queda como propuesta de `revisado_por`.
+///
+/// ## Logging
+/// - `INFO`: Bloqueo revisado y mantenido
+/// - `WARN`: Bloqueo levantado en revisión
+pub async fn revisar(
+    id: String,
+    input: RevisarListaNegraInput,
+    revisado_por: &str,
+) -> Result<CambioListaNegraResponse, ListaNegraError> {
+    let record_id: RecordId =
+        id.parse().map_err(|_| ListaNegraError::Validation(format!("ID inválido: {id}")))?;
+
+    let registro = db::find_by_id(&record_id)
+        .await
+        .map_err(|e| ListaNegraError::Database(e.to_string()))?
+        .ok_or(ListaNegraError::NotFound)?;
+
+    if !input.mantener {
+        let cambio = delete(id, revisado_por).await?;
+        if cambio.aplicado {
+            warn!(
+                "🗑️ BLOQUEO LEVANTADO EN REVISIÓN: id={}, cédula={}, por={revisado_por}",
+                registro.id, registro.cedula
+            );
+        } else {
+            info!(
+                "📝 Levantamiento en revisión enviado a aprobación: id={}, por={revisado_por}",
+                registro.id
+            );
+        }
+        return Ok(cambio);
+    }
+
+    let ahora = Utc::now();
+    let nivel = domain::validar_nivel_severidad(&registro.nivel_severidad)?;
+    let vigente_hasta = match input.vigente_hasta.as_deref() {
+        Some(v) => Some(domain::parsear_vigente_hasta(v, ahora)?),
+        // Sin fecha nueva se conserva el vencimiento vigente
+        None => registro.vigente_hasta.as_ref().and_then(a_utc),
+    };
+    let politica = config_lista_negra().politica(&nivel);
+    let vigencia = domain::calcular_vigencia(ahora, None, politica.revision_meses, vigente_hasta);
+
+    let revisado = db::marcar_revisado(
+        &record_id,
+        revisado_por,
+        vigencia.vigente_hasta.map(Datetime::from),
+        Datetime::from(vigencia.proxima_revision),
+    )
+    .await
+    .map_err(|e| ListaNegraError::Database(e.to_string()))?;
+
+    info!(
+        "🔎 Bloqueo revisado y mantenido: id={}, cédula={}, próxima revisión={}",
+        revisado.id,
+        revisado.cedula,
+        vigencia.proxima_revision.to_rfc3339()
+    );
+
+    Ok(CambioListaNegraResponse::aplicado(Some(revisado.into())))
+}
+
+/// Configuración de vigencia vigente (valores por defecto si no se puede leer).
+pub(crate) fn config_lista_negra() -> ListaNegraConfig {
+    load_config().map(|c| c.lista_negra).unwrap_or_else(|e| {
+        warn!(
+            "⚠️ No se pudo leer la configuración de lista negra, usando valores por defecto: {e}"
+        );
+        ListaNegraConfig::default()
+    })
+}
+
+fn vigencia_para(
+    nivel: &crate::models::lista_negra::NivelSeveridad,
+    desde: DateTime<Utc>,
+    vigente_hasta: Option<DateTime<Utc>>,
+) -> VigenciaBloqueo {
+    let politica = config_lista_negra().politica(nivel);
+    domain::calcular_vigencia(
+        desde,
+        politica.vigencia_meses,
+        politica.revision_meses,
+        vigente_hasta,
+    )
+}
+
+async fn guardar_vigencia(
+    id: &RecordId,
+    vigencia: VigenciaBloqueo,
+) -> Result<crate::models::lista_negra::ListaNegra, ListaNegraError> {
+    db::set_vigencia(
+        id,
+        vigencia.vigente_hasta.map(Datetime::from),
+        Datetime::from(vigencia.proxima_revision),
+    )
+    .await
+    .map_err(|e| ListaNegraError::Database(e.to_string()))
+}
+
+pub(crate) fn a_utc(dt: &Datetime) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&datetime_to_iso(dt)).ok().map(|f| f.with_timezone(&Utc))
 }
 
 // --------------------------------------------------------------------------
@@ -0,0 +1,90 @@
+//! # Servicio: Vigencia de la Lista Negra
+//!
+//! Tarea periódica que mantiene los bloqueos al día con su política de severidad:
+//!
+//! - Desactiva (soft delete) los bloqueos cuyo `vigente_hasta` ya pasó.
+//! - Avisa al frontend (`lista_negra:revision`) cuando hay bloqueos con la revisión
+//!   vencida o próxima a vencer (`lista_negra.aviso_revision_dias`).
+//!
+//! La verificación de bloqueo no depende de esta tarea: `check_if_blocked_by_cedula`
+//! ya ignora los registros vencidos.
+
+use crate::db::surrealdb_lista_negra_queries as db;
+use crate::domain::errors::ListaNegraError;
+use crate::models::lista_negra::AvisoRevisionListaNegra;
+use crate::services::lista_negra_service;
+use log::{error, info, warn};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// Intervalo de la tarea de vigencia.
+const TICK_SECS: u64 = 3600;
+
+/// Cada cuánto se repite el aviso si las revisiones pendientes no cambian.
+const REPETIR_AVISO_SECS: u64 = 24 * 3600;
+
+/// Evento emitido al frontend con el resumen de revisiones pendientes.
+pub const EVENTO_REVISION: &str = "lista_negra:revision";
+
+/// Desactiva los bloqueos vencidos.
+///
+/// # Retorno
+/// Cédulas desbloqueadas en esta pasada.
+pub async fn desactivar_vencidos() -> Result<Vec<String>, ListaNegraError> {
+    let vencidos =
+        db::desactivar_vencidos().await.map_err(|e| ListaNegraError::Database(e.to_string()))?;
+
+    for registro in &vencidos {
+        warn!(
+            "⌛ BLOQUEO VENCIDO DESACTIVADO: id={}, cédula={}, nivel={}",
+            registro.id, registro.cedula, registro.nivel_severidad
+        );
+    }
+    Ok(vencidos.into_iter().map(|r| r.cedula).collect())
+}
+
+/// Lanza la tarea de vigencia en segundo plano durante toda la vida de la aplicación.
+///
+/// La primera pasada se ejecuta al arrancar para recuperar lo ocurrido con la app cerrada.
+pub fn start_vigencia_job(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        info!("⏰ Tarea de vigencia de lista negra iniciada");
+        let mut ultimo_aviso: Option<(Instant, usize)> = None;
+
+        loop {
+            let desactivados = desactivar_vencidos().await.unwrap_or_else(|e| {
+                error!("❌ Falló la desactivación de bloqueos vencidos: {e}");
+                Vec::new()
+            });
+
+            match lista_negra_service::get_revisiones_pendientes().await {
+                Ok(pendientes) => {
+                    let total = pendientes.len();
+                    let repetir = ultimo_aviso.is_none_or(|(cuando, previo)| {
+                        previo != total
+                            || cuando.elapsed() >= Duration::from_secs(REPETIR_AVISO_SECS)
+                    });
+
+                    if !desactivados.is_empty() || (total > 0 && repetir) {
+                        let aviso = AvisoRevisionListaNegra {
+                            revisiones_pendientes: total,
+                            revision_vencida: pendientes
+                                .iter()
+                                .filter(|p| p.revision_pendiente)
+                                .count(),
+                            desactivados_por_vencimiento: desactivados,
+                        };
+                        if let Err(e) = app.emit(EVENTO_REVISION, &aviso) {
+                            error!("❌ No se pudo emitir el aviso de revisión: {e}");
+                        }
+                        ultimo_aviso = Some((Instant::now(), total));
+                        info!("🔔 {total} bloqueo(s) de lista negra pendientes de revisión");
+                    }
+                }
+                Err(e) => error!("❌ Falló la consulta de revisiones pendientes: {e}"),
+            }
+
+            tokio::time::sleep(Duration::from_secs(TICK_SECS)).await;
+        }
+    });
+}
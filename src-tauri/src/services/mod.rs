@@ -6,24 +6,39 @@ pub mod backup;
 pub mod session;
 
 pub mod contratista_service;
+pub mod documento_contratista_service;
 pub mod empresa_service;
 pub mod module_service;
 
 pub mod export_profile_service;
 pub mod export_service;
+pub mod gafete_asignacion_service;
 pub mod gafete_service;
 pub mod keyring_service;
 pub mod role_service;
 
 // Módulos plataforma-específicos eliminados tras unificación de keyring.
 
+pub mod adjunto_cifrado_service;
+pub mod cita_lifecycle_service;
 pub mod cita_service;
+pub mod historial_search_service;
+pub mod importacion_service;
 pub mod ingreso_contratista_service;
 pub mod ingreso_general_service;
 pub mod ingreso_proveedor_service;
 pub mod ingreso_visita_service;
+pub mod lista_negra_evidencia_service;
+pub mod lista_negra_intercambio_service;
+pub mod lista_negra_propuesta_service;
 pub mod lista_negra_service;
+pub mod lista_negra_vigencia_service;
+pub mod pase_service;
+pub mod praind_vigencia_service;
 pub mod proveedor_service;
+pub mod report_schedule_service;
+pub mod report_service;
+pub mod scanner_service;
 pub mod search_service;
 
 pub mod user_service;
@@ -0,0 +1,411 @@
+//! # Servicio: Pases Imprimibles
+//!
+//! Genera el pase en PDF (nombre, empresa, gafete, anfitrión, vigencia, foto y QR con el
+//! ID del ingreso) que se entrega a visitas, contratistas y proveedores al entrar.
+//!
+//! ## Generación
+//! Al registrar un ingreso se genera el pase en segundo plano (si está habilitado en
+//! `[pases]` de la configuración) y se guarda en `pases/` dentro del directorio de datos.
+//! Un fallo al generar el pase nunca bloquea el ingreso. La UI puede pedir el PDF luego
+//! con `get_pase`, que lo regenera si no existe.
+//!
+//! ## Plantillas
+//! Cada tipo de ingreso usa la plantilla configurada (`plantilla_visita`,
+//! `plantilla_contratista`, `plantilla_proveedor`); también se puede forzar una al
+//! regenerar el pase manualmente. La plantilla y su versión se guardan junto al PDF
+//! (`<ingreso>.json`), de modo que `get_pase` informa y vuelve a renderizar con la
+//! plantilla original aunque la configuración haya cambiado después.
+
+use crate::config::manager::load_config;
+use crate::config::settings::PaseConfig;
+use crate::db::surrealdb_ingreso_general_queries as ingreso_general_queries;
+use crate::db::surrealdb_visitante_queries as visitante_queries;
+use crate::domain::common::{datetime_to_iso, parse_record_id};
+use crate::domain::errors::ExportError;
+use crate::models::ingreso::{TipoIngreso, UniversalIngresoFetched};
+use crate::models::pase::{PaseData, PaseMeta, PaseResponse, PaseTemplate};
+use crate::services::avatar_service;
+use crate::services::export_service::sanitizar_filename;
+use chrono::{DateTime, Duration, Local, NaiveTime};
+use log::{info, warn};
+use std::path::PathBuf;
+use surrealdb::RecordId;
+
+const PASES_DIR: &str = "pases";
+
+// --------------------------------------------------------------------------
+// CONSTRUCCIÓN DE DATOS
+// --------------------------------------------------------------------------
+
+/// Plantilla configurada para un tipo de ingreso.
+pub const fn plantilla_para(tipo: &TipoIngreso, config: &PaseConfig) -> PaseTemplate {
+    match tipo {
+        TipoIngreso::Visita => config.plantilla_visita,
+        TipoIngreso::Contratista => config.plantilla_contratista,
+        TipoIngreso::Proveedor => config.plantilla_proveedor,
+    }
+}
+
+/// Vigencia del pase: `horas_validez` desde el ingreso o, sin valor, el final del día.
+fn calcular_valido_hasta(ingreso: DateTime<Local>, horas_validez: Option<u32>) -> DateTime<Local> {
+    match horas_validez {
+        Some(horas) => ingreso + Duration::hours(i64::from(horas)),
+        None => ingreso
+            .date_naive()
+            .and_time(NaiveTime::from_hms_opt(23, 59, 0).unwrap_or_default())
+            .and_local_timezone(Local)
+            .earliest()
+            .unwrap_or(ingreso),
+    }
+}
+
+fn nombre_completo(
+    nombre: &str,
+    segundo_nombre: Option<&String>,
+    apellido: &str,
+    segundo_apellido: Option<&String>,
+) -> String {
+    [
+        Some(nombre),
+        segundo_nombre.map(String::as_str),
+        Some(apellido),
+        segundo_apellido.map(String::as_str),
+    ]
+    .into_iter()
+    .flatten()
+    .map(str::trim)
+    .filter(|parte| !parte.is_empty())
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+fn formatear_gafete(gafete: Option<i32>) -> Option<String> {
+    gafete.filter(|g| *g != 0).map(|g| g.to_string())
+}
+
+/// Extrae los datos imprimibles de un ingreso ya poblado.
+pub fn datos_pase(ingreso: &UniversalIngresoFetched, horas_validez: Option<u32>) -> PaseData {
+    let (id, tipo, fecha_ingreso) = match ingreso {
+        UniversalIngresoFetched::Visita(i) => (&i.id, TipoIngreso::Visita, &i.fecha_hora_ingreso),
+        UniversalIngresoFetched::Contratista(i) => {
+            (&i.id, TipoIngreso::Contratista, &i.fecha_hora_ingreso)
+        }
+        UniversalIngresoFetched::Proveedor(i) => {
+            (&i.id, TipoIngreso::Proveedor, &i.fecha_hora_ingreso)
+        }
+    };
+
+    let ingreso_local = DateTime::parse_from_rfc3339(&datetime_to_iso(fecha_ingreso))
+        .map_or_else(|_| Local::now(), |fecha| fecha.with_timezone(&Local));
+    let valido_hasta = calcular_valido_hasta(ingreso_local, horas_validez);
+
+    let mut data = PaseData {
+        ingreso_id: id.to_string(),
+        titulo: tipo.as_str().to_string(),
+        fecha_ingreso: ingreso_local.format("%d/%m/%Y %H:%M").to_string(),
+        valido_hasta: valido_hasta.format("%d/%m/%Y %H:%M").to_string(),
+        ..PaseData::default()
+    };
+
+    match ingreso {
+        UniversalIngresoFetched::Visita(i) => {
+            data.nombre_completo = nombre_completo(
+                &i.nombre,
+                i.segundo_nombre.as_ref(),
+                &i.apellido,
+                i.segundo_apellido.as_ref(),
+            );
+            data.cedula.clone_from(&i.cedula);
+            data.anfitrion = Some(i.anfitrion.clone());
+            data.area = Some(i.area_visitada.clone());
+            data.gafete = formatear_gafete(i.gafete_numero);
+            data.placa.clone_from(&i.placa_vehiculo);
+        }
+        UniversalIngresoFetched::Contratista(i) => {
+            data.nombre_completo = nombre_completo(
+                &i.nombre,
+                i.segundo_nombre.as_ref(),
+                &i.apellido,
+                i.segundo_apellido.as_ref(),
+            );
+            data.cedula.clone_from(&i.cedula);
+            data.empresa = Some(i.contratista.empresa.nombre.clone());
+            data.gafete = formatear_gafete(i.gafete_numero);
+            data.placa.clone_from(&i.placa_vehiculo);
+        }
+        UniversalIngresoFetched::Proveedor(i) => {
+            data.nombre_completo = nombre_completo(
+                &i.nombre,
+                i.segundo_nombre.as_ref(),
+                &i.apellido,
+                i.segundo_apellido.as_ref(),
+            );
+            data.cedula.clone_from(&i.cedula);
+            data.empresa = Some(i.proveedor.empresa.nombre.clone());
+            data.area = Some(i.area_visitada.clone());
+            data.gafete = formatear_gafete(i.gafete_numero);
+            data.placa.clone_from(&i.placa_vehiculo);
+        }
+    }
+
+    data
+}
+
+const fn tipo_de(ingreso: &UniversalIngresoFetched) -> TipoIngreso {
+    match ingreso {
+        UniversalIngresoFetched::Visita(_) => TipoIngreso::Visita,
+        UniversalIngresoFetched::Contratista(_) => TipoIngreso::Contratista,
+        UniversalIngresoFetched::Proveedor(_) => TipoIngreso::Proveedor,
+    }
+}
+
+// --------------------------------------------------------------------------
+// ALMACENAMIENTO
+// --------------------------------------------------------------------------
+
+fn get_pases_dir() -> Result<PathBuf, ExportError> {
+    let data_dir = dirs::data_local_dir()
+        .ok_or_else(|| ExportError::Io("No se pudo obtener directorio de datos".to_string()))?;
+
+    let dir = data_dir.join("com.brisas.app").join(PASES_DIR);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| ExportError::Io(format!("Error creando directorio de pases: {e}")))?;
+    Ok(dir)
+}
+
+fn ruta_pase(ingreso_id: &str) -> Result<PathBuf, ExportError> {
+    Ok(get_pases_dir()?.join(format!("{}.pdf", sanitizar_filename(ingreso_id))))
+}
+
+fn ruta_meta(ingreso_id: &str) -> Result<PathBuf, ExportError> {
+    Ok(get_pases_dir()?.join(format!("{}.json", sanitizar_filename(ingreso_id))))
+}
+
+/// Plantilla con la que se generó el pase (`None` si es anterior a guardarla).
+fn leer_meta(ingreso_id: &str) -> Result<Option<PaseMeta>, ExportError> {
+    let path = ruta_meta(ingreso_id)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contenido = std::fs::read_to_string(&path)
+        .map_err(|e| ExportError::Io(format!("Error leyendo metadatos del pase: {e}")))?;
+    match serde_json::from_str(&contenido) {
+        Ok(meta) => Ok(Some(meta)),
+        Err(e) => {
+            warn!("⚠️ Metadatos ilegibles del pase {ingreso_id}: {e}");
+            Ok(None)
+        }
+    }
+}
+
+fn guardar_meta(ingreso_id: &str, meta: PaseMeta) -> Result<(), ExportError> {
+    let json = serde_json::to_string(&meta)
+        .map_err(|e| ExportError::Io(format!("Error serializando metadatos del pase: {e}")))?;
+    std::fs::write(ruta_meta(ingreso_id)?, json)
+        .map_err(|e| ExportError::Io(format!("Error guardando metadatos del pase: {e}")))
+}
+
+async fn buscar_ingreso(ingreso_id: &str) -> Result<UniversalIngresoFetched, ExportError> {
+    let id = parse_record_id(ingreso_id, "ingreso_visita");
+    ingreso_general_queries::find_by_id_fetched(&id)
+        .await
+        .map_err(|e| ExportError::Database(e.to_string()))?
+        .ok_or_else(|| ExportError::Validation(format!("Ingreso no encontrado: {ingreso_id}")))
+}
+
+/// Registro de la persona que ingresa (la visita se resuelve por cédula).
+async fn persona_de(ingreso: &UniversalIngresoFetched) -> Result<Option<RecordId>, ExportError> {
+    match ingreso {
+        UniversalIngresoFetched::Visita(i) => {
+            Ok(visitante_queries::get_visitante_by_cedula(&i.cedula)
+                .await
+                .map_err(|e| ExportError::Database(e.to_string()))?
+                .map(|v| v.id))
+        }
+        UniversalIngresoFetched::Contratista(i) => Ok(Some(i.contratista.id.clone())),
+        UniversalIngresoFetched::Proveedor(i) => Ok(Some(i.proveedor.id.clone())),
+    }
+}
+
+/// Avatar de la persona, si tiene uno. La falta de foto nunca impide generar el pase.
+async fn foto_de(ingreso: &UniversalIngresoFetched) -> Option<Vec<u8>> {
+    let persona = match persona_de(ingreso).await {
+        Ok(persona) => persona?,
+        Err(e) => {
+            warn!("⚠️ No se pudo resolver la persona del pase: {e}");
+            return None;
+        }
+    };
+
+    avatar_service::get_avatar_bytes(&persona).await.unwrap_or_else(|e| {
+        warn!("⚠️ No se pudo cargar el avatar de {persona}: {e}");
+        None
+    })
+}
+
+// --------------------------------------------------------------------------
+// GENERACIÓN
+// --------------------------------------------------------------------------
+
+#[cfg(feature = "export-pdf")]
+async fn renderizar(data: PaseData, plantilla: PaseTemplate) -> Result<Vec<u8>, ExportError> {
+    tokio::task::spawn_blocking(move || crate::export::pdf::generate_pase_pdf(&data, plantilla))
+        .await
+        .map_err(|e| ExportError::Generation(format!("Error en tarea de generación: {e}")))?
+        .map_err(ExportError::from)
+}
+
+#[cfg(not(feature = "export-pdf"))]
+async fn renderizar(_data: PaseData, _plantilla: PaseTemplate) -> Result<Vec<u8>, ExportError> {
+    Err(ExportError::UnsupportedFormat(
+        "Generación de pases PDF no disponible en esta build".to_string(),
+    ))
+}
+
+/// Genera (o regenera) el pase de un ingreso y lo guarda en disco.
+///
+/// `plantilla` y `foto` son opcionales: sin plantilla se usa la configurada para el tipo
+/// de ingreso y sin foto se usa el avatar de la persona, si lo tiene.
+pub async fn generar_pase(
+    ingreso_id: &str,
+    plantilla: Option<PaseTemplate>,
+    foto: Option<Vec<u8>>,
+) -> Result<PaseResponse, ExportError> {
+    let config = load_config().map_err(|e| ExportError::Io(e.to_string()))?.pases;
+    let ingreso = buscar_ingreso(ingreso_id).await?;
+
+    let plantilla = plantilla.unwrap_or_else(|| plantilla_para(&tipo_de(&ingreso), &config));
+    let mut data = datos_pase(&ingreso, config.horas_validez);
+    data.foto = match foto {
+        Some(foto) => Some(foto),
+        None => foto_de(&ingreso).await,
+    };
+
+    let id = data.ingreso_id.clone();
+    let bytes = renderizar(data, plantilla).await?;
+
+    let path = ruta_pase(&id)?;
+    std::fs::write(&path, &bytes)
+        .map_err(|e| ExportError::Io(format!("Error guardando pase: {e}")))?;
+    let meta = PaseMeta { plantilla, version_plantilla: plantilla.version() };
+    guardar_meta(&id, meta)?;
+
+    info!("🪪 Pase generado para {id} ({} v{})", plantilla.as_str(), meta.version_plantilla);
+
+    Ok(PaseResponse {
+        ingreso_id: id,
+        plantilla,
+        version_plantilla: meta.version_plantilla,
+        file_path: Some(path.to_string_lossy().to_string()),
+        bytes,
+    })
+}
+
+/// Obtiene el pase guardado de un ingreso, generándolo si todavía no existe.
+///
+/// Se informa (y, si falta el PDF, se vuelve a renderizar) con la plantilla guardada
+/// con el pase; solo los pases sin metadatos usan la configurada para el tipo de ingreso.
+pub async fn get_pase(ingreso_id: &str) -> Result<PaseResponse, ExportError> {
+    let ingreso = buscar_ingreso(ingreso_id).await?;
+    let id = match &ingreso {
+        UniversalIngresoFetched::Visita(i) => i.id.to_string(),
+        UniversalIngresoFetched::Contratista(i) => i.id.to_string(),
+        UniversalIngresoFetched::Proveedor(i) => i.id.to_string(),
+    };
+    let meta = leer_meta(&id)?;
+
+    let path = ruta_pase(&id)?;
+    if !path.exists() {
+        return generar_pase(&id, meta.map(|m| m.plantilla), None).await;
+    }
+
+    let meta = match meta {
+        Some(meta) => meta,
+        None => {
+            let config = load_config().map_err(|e| ExportError::Io(e.to_string()))?.pases;
+            let plantilla = plantilla_para(&tipo_de(&ingreso), &config);
+            PaseMeta { plantilla, version_plantilla: plantilla.version() }
+        }
+    };
+    if meta.version_plantilla != meta.plantilla.version() {
+        info!(
+            "🪪 Pase {id} generado con {} v{} (vigente v{})",
+            meta.plantilla.as_str(),
+            meta.version_plantilla,
+            meta.plantilla.version()
+        );
+    }
+    let bytes =
+        std::fs::read(&path).map_err(|e| ExportError::Io(format!("Error leyendo pase: {e}")))?;
+
+    Ok(PaseResponse {
+        ingreso_id: id,
+        plantilla: meta.plantilla,
+        version_plantilla: meta.version_plantilla,
+        file_path: Some(path.to_string_lossy().to_string()),
+        bytes,
+    })
+}
+
+/// Dispara la generación del pase tras un ingreso sin bloquear al llamador.
+///
+/// Respeta `generar_al_ingresar`; los errores solo se registran en el log.
+pub fn generar_pase_en_segundo_plano(ingreso_id: String) {
+    tauri::async_runtime::spawn(async move {
+        let habilitado = load_config().map(|c| c.pases.generar_al_ingresar).unwrap_or(true);
+        if !habilitado {
+            return;
+        }
+
+        if let Err(e) = generar_pase(&ingreso_id, None, None).await {
+            warn!("⚠️ No se pudo generar el pase del ingreso {ingreso_id}: {e}");
+        }
+    });
+}
+
+// --------------------------------------------------------------------------
+// PRUEBAS UNITARIAS
+// --------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_valido_hasta() {
+        let ingreso = Local.with_ymd_and_hms(2025, 3, 5, 8, 30, 0).unwrap();
+
+        let fin_del_dia = calcular_valido_hasta(ingreso, None);
+        assert_eq!(fin_del_dia.format("%Y-%m-%d %H:%M").to_string(), "2025-03-05 23:59");
+
+        let ocho_horas = calcular_valido_hasta(ingreso, Some(8));
+        assert_eq!(ocho_horas.format("%Y-%m-%d %H:%M").to_string(), "2025-03-05 16:30");
+    }
+
+    #[test]
+    fn test_plantilla_por_tipo() {
+        let config =
+            PaseConfig { plantilla_proveedor: PaseTemplate::Compacto, ..PaseConfig::default() };
+        assert_eq!(plantilla_para(&TipoIngreso::Visita, &config), PaseTemplate::Visitante);
+        assert_eq!(
+            plantilla_para(&TipoIngreso::Contratista, &config),
+            PaseTemplate::ContratistaTemporal
+        );
+        assert_eq!(plantilla_para(&TipoIngreso::Proveedor, &config), PaseTemplate::Compacto);
+    }
+
+    #[test]
+    fn test_meta_conserva_plantilla_y_version() {
+        let meta = PaseMeta { plantilla: PaseTemplate::Compacto, version_plantilla: 1 };
+        let json = serde_json::to_string(&meta).unwrap();
+        assert_eq!(json, r#"{"plantilla":"compacto","versionPlantilla":1}"#);
+        assert_eq!(serde_json::from_str::<PaseMeta>(&json).unwrap(), meta);
+    }
+
+    #[test]
+    fn test_nombre_completo_omite_vacios() {
+        let segundo = " ".to_string();
+        assert_eq!(nombre_completo("Ana", Some(&segundo), "Pérez", None), "Ana Pérez");
+    }
+}
@@ -0,0 +1,343 @@
+//! # Servicio: Vigencia del PRAIND
+//!
+//! Revisión diaria de la certificación PRAIND de los contratistas:
+//!
+//! - Suspende (vía `cambiar_estado_con_historial`) a los contratistas activos con el
+//!   PRAIND vencido, dejando el motivo automático en el historial de estados.
+//! - Genera un digest por empresa con los vencidos y los que vencen dentro de
+//!   `DIAS_ALERTA_PRAIND`. Los digests se guardan en `praind_digests.json` (junto a la
+//!   base de datos) y se pueden exportar con los motores PDF/Excel/CSV.
+//! - Avisa al frontend (`praind:digest`) cuando el digest tiene contenido.
+//!
+//! El motor de validación no depende de esta tarea: el PRAIND vencido ya bloquea el
+//! ingreso al calcularse en cada validación.
+
+use crate::config::manager::{get_database_path, load_config};
+use crate::db::surrealdb_contratista_queries as contratista_queries;
+use crate::domain::common::datetime_to_iso;
+use crate::domain::contratista::{construir_nombre_completo, MOTIVO_SUSPENSION_PRAIND};
+use crate::domain::errors::ExportError;
+use crate::domain::export as export_domain;
+use crate::domain::ingreso_contratista::{
+    agrupar_digest_por_empresa, clasificar_para_digest, dias_hasta_vencimiento_praind,
+    DIAS_ALERTA_PRAIND,
+};
+use crate::models::contratista::{
+    AccionPraind, CambiarEstadoConHistorialInput, DigestPraind, EntradaDigestPraind,
+    EstadoContratista, ExportarDigestPraindInput,
+};
+use crate::models::export::{ExportRequest, ExportResponse, ExportValue};
+use crate::repositories::contratista::{
+    SurrealAuditRepository, SurrealContratistaRepository, SurrealEmpresaRepository,
+    SurrealSecurityRepository, SurrealVehiculoRepository,
+};
+use crate::services::contratista_service::ContratistaService;
+use crate::services::export_service;
+use crate::services::search_service::SearchService;
+use chrono::{Duration as ChronoDuration, Local, Utc};
+use log::{error, info, warn};
+use serde_json::json;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+const DIGESTS_FILE_NAME: &str = "praind_digests.json";
+
+/// Digests conservados (aprox. dos meses de revisiones diarias).
+const MAX_DIGESTS: usize = 60;
+
+/// Intervalo de la tarea; la revisión en sí corre una vez por día.
+const TICK_SECS: u64 = 3600;
+
+/// Evento emitido al frontend con el digest del día.
+pub const EVENTO_DIGEST: &str = "praind:digest";
+
+/// Serializa las lecturas/escrituras de `praind_digests.json`.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Evita dos revisiones simultáneas (tarea diaria vs. ejecución manual).
+static REVISION_EN_CURSO: AtomicBool = AtomicBool::new(false);
+
+// --------------------------------------------------------------------------
+// PERSISTENCIA
+// --------------------------------------------------------------------------
+
+fn get_store_path() -> Result<PathBuf, ExportError> {
+    let config = load_config().map_err(|e| ExportError::Io(e.to_string()))?;
+    let db_path = get_database_path(&config);
+
+    let parent = db_path.parent().ok_or_else(|| {
+        ExportError::Io("No se pudo localizar el directorio de configuración del sistema.".into())
+    })?;
+    Ok(parent.join(DIGESTS_FILE_NAME))
+}
+
+fn read_digests() -> Result<Vec<DigestPraind>, ExportError> {
+    let path = get_store_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path).map_err(|e| ExportError::Io(e.to_string()))?;
+    serde_json::from_str(&content).map_err(|e| {
+        error!("JSON corrupto en {DIGESTS_FILE_NAME}: {e}");
+        ExportError::Io(format!("Error de lectura en {DIGESTS_FILE_NAME}: {e}"))
+    })
+}
+
+fn write_digests(digests: &[DigestPraind]) -> Result<(), ExportError> {
+    let path = get_store_path()?;
+    let json = serde_json::to_string_pretty(digests).map_err(|e| ExportError::Io(e.to_string()))?;
+    fs::write(path, json).map_err(|e| {
+        error!("Error al escribir {DIGESTS_FILE_NAME}: {e}");
+        ExportError::Io(e.to_string())
+    })
+}
+
+/// Guarda un digest al inicio de la lista, descartando los más antiguos.
+fn guardar_digest(digest: &DigestPraind) -> Result<(), ExportError> {
+    let _lock = STORE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut digests = read_digests()?;
+    digests.insert(0, digest.clone());
+    digests.truncate(MAX_DIGESTS);
+    write_digests(&digests)
+}
+
+/// Digests guardados, del más reciente al más antiguo.
+pub fn get_digests() -> Result<Vec<DigestPraind>, ExportError> {
+    let _lock = STORE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    read_digests()
+}
+
+/// Busca un digest guardado por su id.
+pub fn get_digest(id: &str) -> Result<DigestPraind, ExportError> {
+    get_digests()?
+        .into_iter()
+        .find(|d| d.id == id)
+        .ok_or_else(|| ExportError::Validation(format!("Digest PRAIND no encontrado: {id}")))
+}
+
+/// Indica si ya hay un digest registrado para el día local indicado.
+fn existe_digest_del_dia(fecha: &str) -> bool {
+    get_digests().is_ok_and(|digests| digests.iter().any(|d| d.fecha == fecha))
+}
+
+// --------------------------------------------------------------------------
+// REVISIÓN
+// --------------------------------------------------------------------------
+
+/// Libera la marca de revisión en curso al terminar (incluso con error).
+struct RevisionGuard;
+
+impl Drop for RevisionGuard {
+    fn drop(&mut self) {
+        REVISION_EN_CURSO.store(false, Ordering::Release);
+    }
+}
+
+/// Ejecuta la revisión de PRAIND: suspende los vencidos y genera el digest por empresa.
+///
+/// # Argumentos
+/// * `search_service` - Índice de búsqueda a mantener sincronizado con las suspensiones.
+///
+/// # Errores
+/// * `ExportError::Validation` - Ya hay una revisión en curso.
+/// * `ExportError::Database` - No se pudieron consultar los contratistas.
+/// * `ExportError::Io` - No se pudo guardar el digest.
+pub async fn ejecutar_revision(
+    search_service: Option<Arc<SearchService>>,
+) -> Result<DigestPraind, ExportError> {
+    if REVISION_EN_CURSO.swap(true, Ordering::AcqRel) {
+        return Err(ExportError::Validation("Ya hay una revisión de PRAIND en curso".into()));
+    }
+    let _guard = RevisionGuard;
+
+    // Vencen dentro de la ventana de alerta: antes de mañana + DIAS_ALERTA_PRAIND
+    let limite = Utc::now() + ChronoDuration::days(DIAS_ALERTA_PRAIND + 1);
+    let contratistas = contratista_queries::find_activos_praind_antes_de(limite.into())
+        .await
+        .map_err(|e| ExportError::Database(e.to_string()))?;
+
+    let service = ContratistaService::new(
+        SurrealContratistaRepository,
+        SurrealSecurityRepository::new(None),
+        SurrealEmpresaRepository,
+        SurrealVehiculoRepository,
+        SurrealAuditRepository,
+        search_service,
+    );
+
+    let mut entradas = Vec::new();
+    for c in contratistas {
+        // Solo la fecha (YYYY-MM-DD), que es lo que interpreta el dominio
+        let fecha_vencimiento: String =
+            datetime_to_iso(&c.fecha_vencimiento_praind).chars().take(10).collect();
+        let dias = match dias_hasta_vencimiento_praind(&fecha_vencimiento) {
+            Ok(dias) => dias,
+            Err(e) => {
+                warn!("⚠️ PRAIND ilegible para contratista {}: {e}", c.id);
+                continue;
+            }
+        };
+        let Some(mut accion) = clasificar_para_digest(&c.estado, dias) else {
+            continue;
+        };
+
+        let contratista_id = c.id.to_string();
+
+        if accion == AccionPraind::Suspendido {
+            let input = CambiarEstadoConHistorialInput {
+                contratista_id: contratista_id.clone(),
+                nuevo_estado: EstadoContratista::Suspendido,
+                motivo: format!("{MOTIVO_SUSPENSION_PRAIND} el {fecha_vencimiento}"),
+            };
+            // Un vencido que no se pudo suspender sigue en el digest para los supervisores
+            if let Err(e) = service.cambiar_estado_con_historial(input, None).await {
+                error!("❌ No se pudo suspender al contratista {contratista_id}: {e}");
+                accion = AccionPraind::SuspensionFallida;
+            } else {
+                warn!(
+                    "⛔ CONTRATISTA SUSPENDIDO POR PRAIND VENCIDO: id={contratista_id}, cédula={}",
+                    c.cedula
+                );
+            }
+        }
+
+        entradas.push((
+            c.empresa.id.to_string(),
+            c.empresa.nombre.clone(),
+            EntradaDigestPraind {
+                contratista_id,
+                cedula: c.cedula.clone(),
+                nombre_completo: construir_nombre_completo(
+                    &c.nombre,
+                    c.segundo_nombre.as_deref(),
+                    &c.apellido,
+                    c.segundo_apellido.as_deref(),
+                ),
+                fecha_vencimiento,
+                dias_restantes: dias,
+                accion,
+            },
+        ));
+    }
+
+    let contar =
+        |accion: AccionPraind| entradas.iter().filter(|(_, _, e)| e.accion == accion).count();
+    let total_suspendidos = contar(AccionPraind::Suspendido);
+    let total_suspensiones_fallidas = contar(AccionPraind::SuspensionFallida);
+    let digest = DigestPraind {
+        id: uuid::Uuid::now_v7().to_string(),
+        generado_en: Utc::now().to_rfc3339(),
+        fecha: Local::now().date_naive().to_string(),
+        dias_alerta: DIAS_ALERTA_PRAIND,
+        total_por_vencer: contar(AccionPraind::PorVencer),
+        total_suspendidos,
+        total_suspensiones_fallidas,
+        empresas: agrupar_digest_por_empresa(entradas),
+    };
+
+    guardar_digest(&digest)?;
+    info!(
+        "📋 Digest PRAIND generado: {} por vencer, {} suspendidos, {} sin suspender, {} empresas",
+        digest.total_por_vencer,
+        digest.total_suspendidos,
+        digest.total_suspensiones_fallidas,
+        digest.empresas.len()
+    );
+    Ok(digest)
+}
+
+/// Lanza la revisión diaria de PRAIND en segundo plano durante toda la vida de la aplicación.
+///
+/// Revisa cada hora si ya se generó el digest del día; así la primera pasada ocurre al
+/// arrancar y no se pierde el día aunque la app se abra tarde.
+pub fn start_praind_job(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        info!("⏰ Tarea de vigencia de PRAIND iniciada");
+
+        loop {
+            let hoy = Local::now().date_naive().to_string();
+            if !existe_digest_del_dia(&hoy) {
+                let search_service =
+                    app.try_state::<Arc<SearchService>>().map(|s| s.inner().clone());
+
+                match ejecutar_revision(search_service).await {
+                    Ok(digest) => {
+                        if !digest.empresas.is_empty() {
+                            if let Err(e) = app.emit(EVENTO_DIGEST, &digest) {
+                                error!("❌ No se pudo emitir el digest de PRAIND: {e}");
+                            }
+                        }
+                    }
+                    Err(e) => error!("❌ Falló la revisión diaria de PRAIND: {e}"),
+                }
+            }
+
+            tokio::time::sleep(Duration::from_secs(TICK_SECS)).await;
+        }
+    });
+}
+
+// --------------------------------------------------------------------------
+// EXPORTACIÓN
+// --------------------------------------------------------------------------
+
+fn headers_digest() -> Vec<String> {
+    ["Empresa", "Cédula", "Nombre", "Vencimiento PRAIND", "Días restantes", "Acción"]
+        .iter()
+        .map(|&h| h.to_string())
+        .collect()
+}
+
+/// Exporta un digest guardado con los motores PDF/Excel/CSV.
+pub async fn exportar_digest(
+    request: ExportarDigestPraindInput,
+    generated_by: Option<String>,
+) -> Result<ExportResponse, ExportError> {
+    let digest = get_digest(&request.digest_id)?;
+    let headers = headers_digest();
+
+    let rows: Vec<HashMap<String, ExportValue>> = digest
+        .empresas
+        .iter()
+        .flat_map(|empresa| {
+            empresa.entradas.iter().map(|e| {
+                let fila: HashMap<String, serde_json::Value> = [
+                    ("Empresa", json!(empresa.empresa_nombre)),
+                    ("Cédula", json!(e.cedula)),
+                    ("Nombre", json!(e.nombre_completo)),
+                    ("Vencimiento PRAIND", json!(e.fecha_vencimiento)),
+                    ("Días restantes", json!(e.dias_restantes)),
+                    ("Acción", json!(e.accion.display())),
+                ]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect();
+                export_domain::normalizar_row(&fila, &headers)
+            })
+        })
+        .collect();
+
+    let options = ExportRequest {
+        format: request.format,
+        title: Some(request.title.unwrap_or_else(|| format!("Digest PRAIND {}", digest.fecha))),
+        orientation: request.orientation,
+        show_preview: request.show_preview,
+        template_id: request.template_id,
+        delimiter: request.delimiter,
+        include_bom: request.include_bom,
+        target_path: request.target_path,
+        generated_by,
+        ..ExportRequest::default()
+    };
+
+    export_service::export_rows(&options, headers, rows).await.map_err(|e| {
+        error!("Error exportando digest PRAIND {}: {e}", digest.id);
+        ExportError::Engine(e)
+    })
+}
@@ -60,7 +60,7 @@ fn parse_empresa_id(id_str: &str) -> RecordId {
 ///
 /// El registro de un proveedor es un proceso de confianza que implica:
 /// 1. Verificación de Vínculo: Debe pertenecer a una empresa registrada.
-/// 2. Filtro de Integridad: Chequeo contra la lista negra institucional.
+/// 2. Filtro de Integridad: Chequeo contra la lista negra institucional y el veto de la empresa.
 /// 3. Prevención de Duplicidad: Control estricto de identidad por cédula.
 /// 4. Declaración Vehicular: Registro del medio de transporte para control de accesos.
 /// 5. Visibilidad Logística: Indexación inmediata para el personal de garita.
@@ -91,6 +91,19 @@ pub async fn create_proveedor(
         )));
     }
 
+    // Seguridad: una empresa vetada puede tener prohibido registrar personal nuevo.
+    let veto = lista_negra_db::check_empresa_vetada(&empresa_id)
+        .await
+        .map_err(|e| ProveedorError::Validation(e.to_string()))?;
+
+    if let Some(veto) = veto.filter(|v| v.bloquear_registros) {
+        warn!("Registro de proveedor rechazado: empresa {empresa_nombre} vetada");
+        return Err(ProveedorError::Validation(format!(
+            "EMPRESA VETADA: No se pueden registrar proveedores de {} (Nivel: {}).",
+            empresa_nombre, veto.nivel_severidad
+        )));
+    }
+
     if db::find_by_cedula(&input.cedula)
         .await
         .map_err(|e| ProveedorError::Database(e.to_string()))?
@@ -0,0 +1,408 @@
+//! # Servicio: Reportes Programados
+//!
+//! Ejecuta reportes predefinidos (`report_service`) en horarios tipo cron y deja el
+//! archivo resultante en un directorio configurado, con nombre fechado. Cada ejecución
+//! (automática o manual) queda registrada en un historial con su resultado.
+//!
+//! ## Persistencia
+//! Las programaciones (`report_schedules.json`) y el historial (`report_runs.json`) se
+//! guardan junto a la base de datos, igual que los perfiles de exportación.
+//!
+//! ## Ejecución
+//! El planificador revisa las programaciones cada 30 segundos y dispara las que coinciden
+//! con el minuto actual (hora local). Las ejecuciones perdidas con la aplicación cerrada
+//! no se recuperan.
+
+use crate::config::manager::{get_database_path, load_config};
+use crate::domain::errors::ExportError;
+use crate::domain::report_schedule::{self as domain, MAX_HISTORIAL_EJECUCIONES};
+use crate::export::errors::ExportError as EngineError;
+use crate::models::export::{ExportProfile, ReportRequest, ReportRun, ReportSchedule};
+use crate::services::export_service::sanitizar_filename;
+use crate::services::{export_profile_service, report_service};
+use chrono::{DateTime, Local};
+use log::{error, info, warn};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+const SCHEDULES_FILE_NAME: &str = "report_schedules.json";
+const RUNS_FILE_NAME: &str = "report_runs.json";
+
+/// Intervalo de revisión del planificador.
+const TICK_SECS: u64 = 30;
+
+/// Firma que aparece en los reportes generados automáticamente.
+const GENERADO_POR: &str = "Reportes programados";
+
+/// Serializa las lecturas/escrituras de los archivos JSON (planificador vs. UI).
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+// --------------------------------------------------------------------------
+// PERSISTENCIA
+// --------------------------------------------------------------------------
+
+/// Ubicación de un archivo de datos junto a la base de datos.
+fn get_store_path(file_name: &str) -> Result<PathBuf, ExportError> {
+    let config = load_config().map_err(|e| ExportError::Io(e.to_string()))?;
+    let db_path = get_database_path(&config);
+
+    let parent = db_path.parent().ok_or_else(|| {
+        ExportError::Io("No se pudo localizar el directorio de configuración del sistema.".into())
+    })?;
+    Ok(parent.join(file_name))
+}
+
+fn read_store<T: DeserializeOwned>(file_name: &str) -> Result<Vec<T>, ExportError> {
+    let path = get_store_path(file_name)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path).map_err(|e| ExportError::Io(e.to_string()))?;
+    serde_json::from_str(&content).map_err(|e| {
+        error!("JSON corrupto en {file_name}: {e}");
+        ExportError::Io(format!("Error de lectura en {file_name}: {e}"))
+    })
+}
+
+fn write_store<T: Serialize>(file_name: &str, items: &[T]) -> Result<(), ExportError> {
+    let path = get_store_path(file_name)?;
+    let json = serde_json::to_string_pretty(items).map_err(|e| ExportError::Io(e.to_string()))?;
+    fs::write(path, json).map_err(|e| {
+        error!("Error al escribir {file_name}: {e}");
+        ExportError::Io(e.to_string())
+    })
+}
+
+// --------------------------------------------------------------------------
+// GESTIÓN DE PROGRAMACIONES
+// --------------------------------------------------------------------------
+
+/// Lista todas las programaciones guardadas.
+pub fn get_all_schedules() -> Result<Vec<ReportSchedule>, ExportError> {
+    let _lock = STORE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    read_store(SCHEDULES_FILE_NAME)
+}
+
+/// Crea o actualiza una programación (upsert por `id`).
+///
+/// # Errores
+/// * `ExportError::Validation` - Cron inválido, nombre/directorio vacío o perfil inexistente.
+/// * `ExportError::Io` - No se pudo crear el directorio de destino o guardar el archivo.
+pub fn save_schedule(mut schedule: ReportSchedule) -> Result<ReportSchedule, ExportError> {
+    domain::validar_schedule(&schedule).map_err(ExportError::Validation)?;
+
+    if let Some(ref profile_id) = schedule.profile_id {
+        if export_profile_service::get_profile_by_id(profile_id).is_none() {
+            return Err(ExportError::Validation(format!(
+                "Perfil de exportación no encontrado: {profile_id}"
+            )));
+        }
+    }
+
+    fs::create_dir_all(&schedule.output_dir).map_err(|e| {
+        ExportError::Io(format!("No se pudo preparar el directorio de destino: {e}"))
+    })?;
+
+    let _lock = STORE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut schedules: Vec<ReportSchedule> = read_store(SCHEDULES_FILE_NAME)?;
+
+    if schedule.id.trim().is_empty() {
+        schedule.id = uuid::Uuid::now_v7().to_string();
+    }
+
+    if let Some(existente) = schedules.iter_mut().find(|s| s.id == schedule.id) {
+        // La marca de ejecución la administra el planificador
+        schedule.last_run_at = existente.last_run_at.clone();
+        *existente = schedule.clone();
+        info!("Actualizando reporte programado: {} ({})", schedule.nombre, schedule.id);
+    } else {
+        schedule.last_run_at = None;
+        schedules.push(schedule.clone());
+        info!("Creando reporte programado: {} ({})", schedule.nombre, schedule.id);
+    }
+
+    write_store(SCHEDULES_FILE_NAME, &schedules)?;
+    Ok(schedule)
+}
+
+/// Elimina una programación. Su historial de ejecuciones se conserva.
+pub fn delete_schedule(id: &str) -> Result<(), ExportError> {
+    let _lock = STORE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut schedules: Vec<ReportSchedule> = read_store(SCHEDULES_FILE_NAME)?;
+
+    let antes = schedules.len();
+    schedules.retain(|s| s.id != id);
+    if schedules.len() == antes {
+        return Err(ExportError::Validation(format!("Programación no encontrada: {id}")));
+    }
+
+    write_store(SCHEDULES_FILE_NAME, &schedules)?;
+    info!("Reporte programado eliminado: {id}");
+    Ok(())
+}
+
+/// Historial de ejecuciones, de la más reciente a la más antigua.
+pub fn get_run_history(
+    schedule_id: Option<&str>,
+    limit: usize,
+) -> Result<Vec<ReportRun>, ExportError> {
+    let _lock = STORE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let runs: Vec<ReportRun> = read_store(RUNS_FILE_NAME)?;
+
+    Ok(runs
+        .into_iter()
+        .rev()
+        .filter(|r| schedule_id.is_none_or(|id| r.schedule_id == id))
+        .take(limit)
+        .collect())
+}
+
+fn record_run(run: &ReportRun) -> Result<(), ExportError> {
+    let _lock = STORE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut runs: Vec<ReportRun> = read_store(RUNS_FILE_NAME)?;
+
+    runs.push(run.clone());
+    if runs.len() > MAX_HISTORIAL_EJECUCIONES {
+        runs.drain(..runs.len() - MAX_HISTORIAL_EJECUCIONES);
+    }
+    write_store(RUNS_FILE_NAME, &runs)
+}
+
+/// Marca la programación como ejecutada en `ahora` antes de generar el reporte,
+/// para que un segundo tick dentro del mismo minuto no la repita.
+fn mark_started(id: &str, ahora: &DateTime<Local>) -> Result<(), ExportError> {
+    let _lock = STORE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut schedules: Vec<ReportSchedule> = read_store(SCHEDULES_FILE_NAME)?;
+
+    if let Some(schedule) = schedules.iter_mut().find(|s| s.id == id) {
+        schedule.last_run_at = Some(ahora.to_rfc3339());
+        write_store(SCHEDULES_FILE_NAME, &schedules)?;
+    }
+    Ok(())
+}
+
+// --------------------------------------------------------------------------
+// EJECUCIÓN
+// --------------------------------------------------------------------------
+
+/// Ejecuta una programación de inmediato, sin esperar su horario.
+pub async fn run_schedule_now(id: &str) -> Result<ReportRun, ExportError> {
+    let schedule = get_all_schedules()?
+        .into_iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| ExportError::Validation(format!("Programación no encontrada: {id}")))?;
+
+    Ok(ejecutar(&schedule, Local::now(), true).await)
+}
+
+/// Revisa las programaciones activas y ejecuta las que coinciden con el minuto actual.
+pub async fn tick() {
+    let ahora = Local::now();
+    let momento = ahora.naive_local();
+
+    let schedules = match get_all_schedules() {
+        Ok(s) => s,
+        Err(e) => {
+            warn!("⚠️ No se pudieron leer los reportes programados: {e}");
+            return;
+        }
+    };
+
+    for schedule in schedules.iter().filter(|s| s.is_active) {
+        let cron = match domain::validar_schedule(schedule) {
+            Ok(cron) => cron,
+            Err(e) => {
+                warn!("⚠️ Reporte programado '{}' inválido: {e}", schedule.nombre);
+                continue;
+            }
+        };
+
+        if !cron.matches(&momento)
+            || domain::ya_ejecutado_en(schedule.last_run_at.as_deref(), &momento)
+        {
+            continue;
+        }
+
+        if let Err(e) = mark_started(&schedule.id, &ahora) {
+            error!("❌ No se pudo registrar el inicio de '{}': {e}", schedule.nombre);
+            continue;
+        }
+        ejecutar(schedule, ahora, false).await;
+    }
+}
+
+/// Lanza el planificador en segundo plano durante toda la vida de la aplicación.
+pub fn start_scheduler() {
+    tauri::async_runtime::spawn(async {
+        info!("⏰ Planificador de reportes iniciado");
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(TICK_SECS)).await;
+            tick().await;
+        }
+    });
+}
+
+/// Genera el reporte de una programación y registra el resultado en el historial.
+async fn ejecutar(schedule: &ReportSchedule, ahora: DateTime<Local>, manual: bool) -> ReportRun {
+    info!("📊 Ejecutando reporte programado '{}' ({})", schedule.nombre, schedule.id);
+
+    let profile = schedule
+        .profile_id
+        .as_deref()
+        .and_then(export_profile_service::get_profile_by_id)
+        .or_else(export_profile_service::get_default_profile);
+    let request = construir_request(schedule, profile.as_ref(), &ahora);
+    let destino = request.target_path.clone();
+
+    let resultado = report_service::generate_report(request, Some(GENERADO_POR.to_string())).await;
+
+    let (success, file_path, message) = match resultado {
+        Ok(response) => (true, response.file_path.or(destino), response.message),
+        Err(ExportError::Engine(EngineError::EmptyData)) => {
+            (true, None, "Sin datos para el periodo; no se generó archivo".to_string())
+        }
+        Err(e) => {
+            error!("❌ Falló el reporte programado '{}': {e}", schedule.nombre);
+            (false, None, e.to_string())
+        }
+    };
+
+    let run = ReportRun {
+        id: uuid::Uuid::now_v7().to_string(),
+        schedule_id: schedule.id.clone(),
+        schedule_nombre: schedule.nombre.clone(),
+        reporte: schedule.reporte,
+        started_at: ahora.to_rfc3339(),
+        finished_at: Local::now().to_rfc3339(),
+        success,
+        file_path,
+        message,
+        manual,
+    };
+
+    if let Err(e) = record_run(&run) {
+        error!("❌ No se pudo guardar el historial de reportes programados: {e}");
+    }
+    run
+}
+
+/// Traduce programación + perfil de exportación a una solicitud de reporte.
+fn construir_request(
+    schedule: &ReportSchedule,
+    profile: Option<&ExportProfile>,
+    ahora: &DateTime<Local>,
+) -> ReportRequest {
+    let format = profile.map_or_else(|| "pdf".to_string(), |p| p.format.clone());
+    let (desde, hasta) = domain::rango_periodo(schedule.periodo, ahora.date_naive());
+
+    // Solo ingresos e inasistencias dependen del periodo; el resto son fotografías del momento
+    let (desde, hasta) = if schedule.reporte.usa_periodo() {
+        (Some(desde.to_string()), Some(hasta.to_string()))
+    } else {
+        (None, None)
+    };
+
+    ReportRequest {
+        reporte: schedule.reporte,
+        desde,
+        hasta,
+        tipo_ingreso: schedule.tipo_ingreso.clone(),
+        empresa_id: schedule.empresa_id.clone(),
+        dias: schedule.dias,
+        title: Some(schedule.nombre.clone()),
+        orientation: profile.and_then(|p| p.pdf_design.as_ref()).map(|d| d.orientation.clone()),
+        show_preview: Some(false),
+        template_id: profile.map(|p| p.id.clone()),
+        delimiter: profile.and_then(|p| p.csv_options.as_ref()).map(|o| o.delimiter.clone()),
+        include_bom: profile.and_then(|p| p.csv_options.as_ref()).map(|o| o.include_bom),
+        target_path: Some(
+            ruta_salida(Path::new(&schedule.output_dir), &schedule.nombre, &format, ahora)
+                .to_string_lossy()
+                .to_string(),
+        ),
+        format,
+    }
+}
+
+/// `<directorio>/<Nombre>_<YYYY-MM-DD_HHMM>.<ext>`
+fn ruta_salida(dir: &Path, nombre: &str, format: &str, ahora: &DateTime<Local>) -> PathBuf {
+    let extension = match format.to_lowercase().as_str() {
+        "excel" | "xlsx" => "xlsx",
+        "csv" => "csv",
+        _ => "pdf",
+    };
+    let base = sanitizar_filename(nombre);
+    let base = if base.is_empty() { "reporte".to_string() } else { base };
+
+    dir.join(format!("{base}_{}.{extension}", ahora.format("%Y-%m-%d_%H%M")))
+}
+
+// --------------------------------------------------------------------------
+// PRUEBAS UNITARIAS
+// --------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::export::{CsvOptions, ReportKind, ReportPeriod};
+    use chrono::TimeZone;
+
+    fn schedule(reporte: ReportKind) -> ReportSchedule {
+        ReportSchedule {
+            id: "s1".to_string(),
+            nombre: "Movimientos del día".to_string(),
+            reporte,
+            profile_id: None,
+            cron: "0 18 * * *".to_string(),
+            periodo: ReportPeriod::Hoy,
+            output_dir: "/tmp/reportes".to_string(),
+            tipo_ingreso: None,
+            empresa_id: None,
+            dias: None,
+            is_active: true,
+            last_run_at: None,
+        }
+    }
+
+    #[test]
+    fn test_construir_request_ingresos_del_dia() {
+        let ahora = Local.with_ymd_and_hms(2025, 3, 10, 18, 0, 0).unwrap();
+        let request = construir_request(&schedule(ReportKind::IngresosPorFecha), None, &ahora);
+
+        assert_eq!(request.format, "pdf");
+        assert_eq!(request.desde.as_deref(), Some("2025-03-10"));
+        assert_eq!(request.hasta.as_deref(), Some("2025-03-10"));
+        assert!(request.target_path.unwrap().ends_with("Movimientos_del_d-a_2025-03-10_1800.pdf"));
+    }
+
+    #[test]
+    fn test_construir_request_inasistencias_usa_periodo() {
+        let ahora = Local.with_ymd_and_hms(2025, 3, 10, 18, 0, 0).unwrap();
+        let request = construir_request(&schedule(ReportKind::InasistenciasCitas), None, &ahora);
+
+        assert_eq!(request.desde.as_deref(), Some("2025-03-10"));
+        assert_eq!(request.hasta.as_deref(), Some("2025-03-10"));
+    }
+
+    #[test]
+    fn test_construir_request_con_perfil_csv() {
+        let ahora = Local.with_ymd_and_hms(2025, 3, 10, 7, 30, 0).unwrap();
+        let mut profile =
+            ExportProfile::new("csv-1".to_string(), "CSV".to_string(), "csv".to_string());
+        profile.csv_options =
+            Some(CsvOptions { delimiter: "semicolon".to_string(), include_bom: false });
+
+        let request =
+            construir_request(&schedule(ReportKind::PraindPorVencer), Some(&profile), &ahora);
+
+        assert!(request.desde.is_none(), "PRAIND no depende del periodo");
+        assert_eq!(request.template_id.as_deref(), Some("csv-1"));
+        assert_eq!(request.delimiter.as_deref(), Some("semicolon"));
+        assert_eq!(request.include_bom, Some(false));
+        assert!(request.target_path.unwrap().ends_with(".csv"));
+    }
+}
@@ -0,0 +1,617 @@
+//! # Servicio: Reportes Generados en el Servidor
+//!
+//! Define los reportes operativos predefinidos (ingresos por fecha, personal en planta,
+//! PRAIND por vencer, gafetes extraviados, lista negra activa, inasistencias a citas). Cada reporte consulta
+//! `SurrealDB` directamente y entrega sus filas a los generadores PDF/Excel/CSV de
+//! `export_service`, sin pasar los datos por el frontend.
+//!
+//! El filtro `empresaId` incluye a las subcontratistas de la empresa, y los reportes de
+//! personal agregan la columna "Empresa principal" para consolidar por contratista principal.
+//!
+//! ## Dependencias
+//! - `crate::db::*_queries`: Fuentes de datos de cada reporte.
+//! - `crate::services::export_service`: Generación del archivo final.
+
+use crate::db::{
+    surrealdb_alerta_queries as alerta_queries,
+    surrealdb_contratista_queries as contratista_queries,
+    surrealdb_empresa_queries as empresa_queries, surrealdb_gafete_queries as gafete_queries,
+    surrealdb_ingreso_general_queries as ingreso_general_queries,
+    surrealdb_lista_negra_queries as lista_negra_queries, surrealdb_utils::leer_paginado,
+};
+use crate::domain::common::{
+    datetime_to_iso, limites_dias_locales, parse_record_id, parsear_fecha_simple,
+};
+use crate::domain::contratista::calcular_estado_praind;
+use crate::domain::empresa as empresa_domain;
+use crate::domain::errors::ExportError;
+use crate::domain::export as export_domain;
+use crate::domain::gafete::tipo_de_alerta;
+use crate::models::cita::InasistenciasAnfitrion;
+use crate::models::contratista::ContratistaFetched;
+use crate::models::empresa::Empresa;
+use crate::models::export::{
+    ExportRequest, ExportResponse, ExportValue, ReportDefinition, ReportKind, ReportRequest,
+};
+use crate::models::gafete::{Gafete, GafeteEstado};
+use crate::models::ingreso::{AlertaGafete, IngresoResponse};
+use crate::models::lista_negra::ListaNegra;
+use crate::services::{cita_lifecycle_service, export_service};
+use log::{error, info};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+
+/// Horizonte por defecto (días) del reporte de PRAIND por vencer.
+const DIAS_PRAIND_DEFAULT: i64 = 30;
+
+type Fila = HashMap<String, Value>;
+
+// --------------------------------------------------------------------------
+// DEFINICIONES
+// --------------------------------------------------------------------------
+
+/// Columnas de cada reporte, en orden de presentación.
+pub fn headers_de(kind: ReportKind) -> Vec<String> {
+    let headers: &[&str] = match kind {
+        ReportKind::IngresosPorFecha => &[
+            "Ingreso",
+            "Salida",
+            "Tipo",
+            "Cédula",
+            "Nombre",
+            "Empresa",
+            "Empresa principal",
+            "Gafete",
+            "Placa",
+            "Permanencia",
+            "Registrado por",
+            "Observaciones",
+        ],
+        ReportKind::PersonalEnPlanta => &[
+            "Ingreso",
+            "Tipo",
+            "Cédula",
+            "Nombre",
+            "Empresa",
+            "Empresa principal",
+            "Gafete",
+            "Placa",
+            "Modo",
+            "Registrado por",
+        ],
+        ReportKind::PraindPorVencer => &[
+            "Cédula",
+            "Nombre",
+            "Empresa",
+            "Empresa principal",
+            "Vencimiento PRAIND",
+            "Días restantes",
+            "Estado",
+        ],
+        ReportKind::GafetesExtraviados => {
+            &["Gafete", "Tipo", "En uso", "Adeudado por", "Cédula", "Reportado", "Notas"]
+        }
+        ReportKind::ListaNegraActiva => &[
+            "Cédula",
+            "Nombre",
+            "Empresa",
+            "Severidad",
+            "Motivo",
+            "Bloqueado por",
+            "Bloqueado desde",
+        ],
+        ReportKind::InasistenciasCitas => &[
+            "Anfitrión",
+            "Citas",
+            "Asistidas",
+            "Vencidas",
+            "Canceladas",
+            "Pendientes",
+            "% Inasistencia",
+        ],
+    };
+    headers.iter().map(|&h| h.to_string()).collect()
+}
+
+/// Descripción del reporte y parámetros que acepta.
+pub fn definicion(kind: ReportKind) -> ReportDefinition {
+    let (descripcion, parametros): (&str, &[&str]) = match kind {
+        ReportKind::IngresosPorFecha => (
+            "Movimientos de contratistas, proveedores y visitas en el rango indicado",
+            &["desde", "hasta", "tipoIngreso", "empresaId"],
+        ),
+        ReportKind::PersonalEnPlanta => {
+            ("Personas que se encuentran actualmente dentro", &["tipoIngreso", "empresaId"])
+        }
+        ReportKind::PraindPorVencer => {
+            ("Contratistas con PRAIND vencido o próximo a vencer", &["dias", "empresaId"])
+        }
+        ReportKind::GafetesExtraviados => {
+            ("Gafetes extraviados y quién los adeuda, si hay alerta pendiente", &[])
+        }
+        ReportKind::ListaNegraActiva => {
+            ("Personas con bloqueo de acceso vigente", &["desde", "hasta"])
+        }
+        ReportKind::InasistenciasCitas => (
+            "Citas por anfitrión en el rango y porcentaje de visitantes que no se presentaron",
+            &["desde", "hasta"],
+        ),
+    };
+
+    ReportDefinition {
+        id: kind,
+        titulo: kind.titulo().to_string(),
+        descripcion: descripcion.to_string(),
+        headers: headers_de(kind),
+        parametros: parametros.iter().map(|&p| p.to_string()).collect(),
+    }
+}
+
+/// Lista todos los reportes disponibles.
+pub fn list_report_definitions() -> Vec<ReportDefinition> {
+    ReportKind::all().into_iter().map(definicion).collect()
+}
+
+// --------------------------------------------------------------------------
+// GENERACIÓN
+// --------------------------------------------------------------------------
+
+/// Construye el reporte solicitado desde la base de datos y lo exporta al formato pedido.
+///
+/// # Errores
+/// * `ExportError::Validation` - Parámetros inválidos (fechas, rango, tipo de ingreso).
+/// * `ExportError::Database` - Fallo al consultar `SurrealDB`.
+/// * `ExportError::Engine` - Fallo del generador (incluye `EmptyData` si no hay filas).
+pub async fn generate_report(
+    request: ReportRequest,
+    generated_by: Option<String>,
+) -> Result<ExportResponse, ExportError> {
+    let kind = request.reporte;
+    info!("📊 Generando reporte {} ({})", kind.as_str(), request.format);
+
+    let filas = match kind {
+        ReportKind::IngresosPorFecha => filas_ingresos_por_fecha(&request).await?,
+        ReportKind::PersonalEnPlanta => filas_personal_en_planta(&request).await?,
+        ReportKind::PraindPorVencer => filas_praind_por_vencer(&request).await?,
+        ReportKind::GafetesExtraviados => filas_gafetes_extraviados().await?,
+        ReportKind::ListaNegraActiva => filas_lista_negra_activa(&request).await?,
+        ReportKind::InasistenciasCitas => filas_inasistencias_citas(&request).await?,
+    };
+
+    let headers = headers_de(kind);
+    let rows: Vec<HashMap<String, ExportValue>> =
+        filas.iter().map(|fila| export_domain::normalizar_row(fila, &headers)).collect();
+
+    let options = ExportRequest {
+        format: request.format,
+        title: Some(request.title.unwrap_or_else(|| kind.titulo().to_string())),
+        orientation: request.orientation,
+        show_preview: request.show_preview,
+        template_id: request.template_id,
+        delimiter: request.delimiter,
+        include_bom: request.include_bom,
+        target_path: request.target_path,
+        generated_by,
+        ..ExportRequest::default()
+    };
+
+    export_service::export_rows(&options, headers, rows).await.map_err(|e| {
+        error!("Error generando reporte {}: {e}", kind.as_str());
+        ExportError::Engine(e)
+    })
+}
+
+async fn filas_ingresos_por_fecha(request: &ReportRequest) -> Result<Vec<Fila>, ExportError> {
+    let (inicio, fin) = rango_fechas(request.desde.as_deref(), request.hasta.as_deref())?;
+    let tipo = validar_tipo_ingreso(request.tipo_ingreso.as_deref())?;
+    let jerarquia = JerarquiaEmpresas::cargar().await?;
+    let empresas = jerarquia.nombres_filtrados(request.empresa_id.as_deref())?;
+
+    let ingresos = ingreso_general_queries::find_salidas_in_range_fetched(&inicio, &fin)
+        .await
+        .map_err(|e| ExportError::Database(e.to_string()))?;
+
+    Ok(ingresos
+        .into_iter()
+        .filter_map(|ingreso| ingreso.to_response().ok())
+        .filter(|i| coincide_ingreso(i, tipo.as_deref(), empresas.as_ref()))
+        .map(|i| fila_ingreso(&i, jerarquia.principal_por_nombre(&i.empresa_nombre)))
+        .collect())
+}
+
+async fn filas_personal_en_planta(request: &ReportRequest) -> Result<Vec<Fila>, ExportError> {
+    let tipo = validar_tipo_ingreso(request.tipo_ingreso.as_deref())?;
+    let jerarquia = JerarquiaEmpresas::cargar().await?;
+    let empresas = jerarquia.nombres_filtrados(request.empresa_id.as_deref())?;
+
+    let abiertos = ingreso_general_queries::find_ingresos_abiertos_fetched()
+        .await
+        .map_err(|e| ExportError::Database(e.to_string()))?;
+
+    Ok(abiertos
+        .into_iter()
+        .filter_map(|ingreso| ingreso.to_response().ok())
+        .filter(|i| coincide_ingreso(i, tipo.as_deref(), empresas.as_ref()))
+        .map(|i| fila_en_planta(&i, jerarquia.principal_por_nombre(&i.empresa_nombre)))
+        .collect())
+}
+
+async fn filas_praind_por_vencer(request: &ReportRequest) -> Result<Vec<Fila>, ExportError> {
+    let dias = request.dias.unwrap_or(DIAS_PRAIND_DEFAULT);
+    if dias < 0 {
+        return Err(ExportError::Validation("El horizonte de días no puede ser negativo".into()));
+    }
+    let jerarquia = JerarquiaEmpresas::cargar().await?;
+    let empresas = jerarquia.ids_filtrados(request.empresa_id.as_deref())?;
+
+    let contratistas = leer_paginado(contratista_queries::find_page_fetched)
+        .await
+        .map_err(|e| ExportError::Database(e.to_string()))?;
+
+    let mut filas: Vec<(i64, Fila)> = contratistas
+        .iter()
+        .filter(|c| empresas.as_ref().is_none_or(|ids| ids.contains(&c.empresa.id.to_string())))
+        .filter_map(|c| {
+            let estado = calcular_estado_praind(&datetime_to_iso(&c.fecha_vencimiento_praind));
+            (estado.dias_hasta_vencimiento <= dias).then(|| {
+                let principal = jerarquia.principal_por_id(&c.empresa.id.to_string());
+                (
+                    estado.dias_hasta_vencimiento,
+                    fila_praind(c, principal, estado.dias_hasta_vencimiento),
+                )
+            })
+        })
+        .collect();
+
+    // Los más urgentes (o ya vencidos) primero
+    filas.sort_by_key(|(dias, _)| *dias);
+    Ok(filas.into_iter().map(|(_, fila)| fila).collect())
+}
+
+async fn filas_gafetes_extraviados() -> Result<Vec<Fila>, ExportError> {
+    let gafetes = gafete_queries::get_all_gafetes()
+        .await
+        .map_err(|e| ExportError::Database(e.to_string()))?;
+    let alertas = alerta_queries::find_all(Some(false))
+        .await
+        .map_err(|e| ExportError::Database(e.to_string()))?;
+
+    let mut extraviados: Vec<&Gafete> =
+        gafetes.iter().filter(|g| g.estado == GafeteEstado::Extraviado).collect();
+    extraviados.sort_by_key(|g| (g.tipo.as_str().to_string(), g.numero));
+
+    Ok(extraviados
+        .into_iter()
+        .map(|g| {
+            // El número se repite entre tipos: la alerta se asocia por número y tipo
+            let alerta = alertas.iter().find(|a| {
+                a.gafete_numero == g.numero && tipo_de_alerta(a).as_ref() == Some(&g.tipo)
+            });
+            fila_gafete(g, alerta)
+        })
+        .collect())
+}
+
+async fn filas_lista_negra_activa(request: &ReportRequest) -> Result<Vec<Fila>, ExportError> {
+    let rango = match (request.desde.as_deref(), request.hasta.as_deref()) {
+        (None, None) => None,
+        (desde, hasta) => Some(rango_fechas(desde.or(hasta), hasta)?),
+    };
+
+    let bloqueados = leer_paginado(lista_negra_queries::find_page)
+        .await
+        .map_err(|e| ExportError::Database(e.to_string()))?;
+
+    Ok(bloqueados
+        .iter()
+        .filter(|b| b.is_active)
+        .filter(|b| {
+            rango.as_ref().is_none_or(|(inicio, fin)| {
+                let creado = datetime_to_iso(&b.created_at);
+                creado.as_str() >= inicio.as_str() && creado.as_str() <= fin.as_str()
+            })
+        })
+        .map(fila_lista_negra)
+        .collect())
+}
+
+async fn filas_inasistencias_citas(request: &ReportRequest) -> Result<Vec<Fila>, ExportError> {
+    let (inicio, fin) = rango_fechas(request.desde.as_deref(), request.hasta.as_deref())?;
+
+    let resumen = cita_lifecycle_service::inasistencias_en_rango(&inicio, &fin)
+        .await
+        .map_err(|e| ExportError::Database(e.to_string()))?;
+
+    Ok(resumen.iter().map(fila_inasistencias).collect())
+}
+
+// --------------------------------------------------------------------------
+// HELPERS DE FILTRO
+// --------------------------------------------------------------------------
+
+/// Convierte un rango YYYY-MM-DD (inclusivo) a límites RFC 3339 de los días locales.
+///
+/// `hasta` por defecto es igual a `desde`.
+fn rango_fechas(desde: Option<&str>, hasta: Option<&str>) -> Result<(String, String), ExportError> {
+    let desde = desde.ok_or_else(|| {
+        ExportError::Validation("El reporte requiere una fecha inicial (desde)".to_string())
+    })?;
+    let inicio = parsear_fecha_simple(desde).map_err(|e| ExportError::Validation(e.to_string()))?;
+    let fin = hasta
+        .map(parsear_fecha_simple)
+        .transpose()
+        .map_err(|e| ExportError::Validation(e.to_string()))?
+        .unwrap_or(inicio);
+
+    if fin < inicio {
+        return Err(ExportError::Validation(
+            "La fecha final no puede ser anterior a la inicial".to_string(),
+        ));
+    }
+
+    limites_dias_locales(inicio, fin).map_err(|e| ExportError::Validation(e.to_string()))
+}
+
+fn validar_tipo_ingreso(tipo: Option<&str>) -> Result<Option<String>, ExportError> {
+    match tipo.map(|t| t.trim().to_lowercase()) {
+        None => Ok(None),
+        Some(t) if t.is_empty() => Ok(None),
+        Some(t) if matches!(t.as_str(), "contratista" | "proveedor" | "visita") => Ok(Some(t)),
+        Some(t) => Err(ExportError::Validation(format!("Tipo de ingreso desconocido: {t}"))),
+    }
+}
+
+/// Jerarquía de empresas para filtrar y consolidar por contratista principal.
+///
+/// Los ingresos solo guardan el nombre de la empresa, por eso se indexa también por nombre.
+struct JerarquiaEmpresas {
+    padres: HashMap<String, Option<String>>,
+    nombres: HashMap<String, String>,
+    ids_por_nombre: HashMap<String, String>,
+}
+
+impl JerarquiaEmpresas {
+    async fn cargar() -> Result<Self, ExportError> {
+        let empresas =
+            empresa_queries::find_all().await.map_err(|e| ExportError::Database(e.to_string()))?;
+        Ok(Self::new(&empresas))
+    }
+
+    fn new(empresas: &[Empresa]) -> Self {
+        Self {
+            padres: empresa_domain::mapa_padres(empresas),
+            nombres: empresas.iter().map(|e| (e.id.to_string(), e.nombre.clone())).collect(),
+            ids_por_nombre: empresas
+                .iter()
+                .map(|e| (e.nombre.to_lowercase(), e.id.to_string()))
+                .collect(),
+        }
+    }
+
+    /// IDs de la empresa filtrada y de sus subcontratistas (`None` si no se filtra).
+    fn ids_filtrados(
+        &self,
+        empresa_id: Option<&str>,
+    ) -> Result<Option<HashSet<String>>, ExportError> {
+        let Some(id) = empresa_id else {
+            return Ok(None);
+        };
+        let id = parse_record_id(id, "empresa").to_string();
+        if !self.nombres.contains_key(&id) {
+            return Err(ExportError::Validation(format!("Empresa no encontrada: {id}")));
+        }
+        Ok(Some(empresa_domain::empresa_y_subempresas(&id, &self.padres)))
+    }
+
+    /// Nombres (en minúsculas) de la empresa filtrada y de sus subcontratistas.
+    fn nombres_filtrados(
+        &self,
+        empresa_id: Option<&str>,
+    ) -> Result<Option<HashSet<String>>, ExportError> {
+        Ok(self.ids_filtrados(empresa_id)?.map(|ids| {
+            ids.iter().filter_map(|id| self.nombres.get(id)).map(|n| n.to_lowercase()).collect()
+        }))
+    }
+
+    fn principal_por_id(&self, id: &str) -> Option<&str> {
+        let principal = empresa_domain::empresa_principal(id, &self.padres);
+        self.nombres.get(principal).map(String::as_str)
+    }
+
+    fn principal_por_nombre(&self, nombre: &str) -> Option<&str> {
+        self.ids_por_nombre.get(&nombre.to_lowercase()).and_then(|id| self.principal_por_id(id))
+    }
+}
+
+fn coincide_ingreso(
+    ingreso: &IngresoResponse,
+    tipo: Option<&str>,
+    empresas: Option<&HashSet<String>>,
+) -> bool {
+    tipo.is_none_or(|t| ingreso.tipo_ingreso.as_str() == t)
+        && empresas.is_none_or(|e| e.contains(&ingreso.empresa_nombre.to_lowercase()))
+}
+
+// --------------------------------------------------------------------------
+// MAPEO A FILAS
+// --------------------------------------------------------------------------
+
+fn fila(pares: Vec<(&str, Value)>) -> Fila {
+    pares.into_iter().map(|(k, v)| (k.to_string(), v)).collect()
+}
+
+fn fila_ingreso(i: &IngresoResponse, principal: Option<&str>) -> Fila {
+    fila(vec![
+        ("Ingreso", json!(i.fecha_hora_ingreso)),
+        ("Salida", json!(i.fecha_hora_salida)),
+        ("Tipo", json!(i.tipo_ingreso_display)),
+        ("Cédula", json!(i.cedula)),
+        ("Nombre", json!(i.nombre_completo)),
+        ("Empresa", json!(i.empresa_nombre)),
+        ("Empresa principal", json!(principal)),
+        ("Gafete", json!(i.gafete_numero)),
+        ("Placa", json!(i.vehiculo_placa.as_ref().or(i.placa_temporal.as_ref()))),
+        ("Permanencia", json!(i.tiempo_permanencia_texto)),
+        ("Registrado por", json!(i.usuario_ingreso_nombre)),
+        ("Observaciones", json!(i.observaciones)),
+    ])
+}
+
+fn fila_en_planta(i: &IngresoResponse, principal: Option<&str>) -> Fila {
+    fila(vec![
+        ("Ingreso", json!(i.fecha_hora_ingreso)),
+        ("Tipo", json!(i.tipo_ingreso_display)),
+        ("Cédula", json!(i.cedula)),
+        ("Nombre", json!(i.nombre_completo)),
+        ("Empresa", json!(i.empresa_nombre)),
+        ("Empresa principal", json!(principal)),
+        ("Gafete", json!(i.gafete_numero)),
+        ("Placa", json!(i.vehiculo_placa.as_ref().or(i.placa_temporal.as_ref()))),
+        ("Modo", json!(i.modo_ingreso_display)),
+        ("Registrado por", json!(i.usuario_ingreso_nombre)),
+    ])
+}
+
+fn fila_praind(c: &ContratistaFetched, principal: Option<&str>, dias: i64) -> Fila {
+    let nombre = [
+        Some(c.nombre.as_str()),
+        c.segundo_nombre.as_deref(),
+        Some(c.apellido.as_str()),
+        c.segundo_apellido.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|parte| !parte.trim().is_empty())
+    .collect::<Vec<_>>()
+    .join(" ");
+
+    fila(vec![
+        ("Cédula", json!(c.cedula)),
+        ("Nombre", json!(nombre)),
+        ("Empresa", json!(c.empresa.nombre)),
+        ("Empresa principal", json!(principal)),
+        ("Vencimiento PRAIND", json!(datetime_to_iso(&c.fecha_vencimiento_praind))),
+        ("Días restantes", json!(dias)),
+        ("Estado", json!(if dias < 0 { "Vencido" } else { "Por vencer" })),
+    ])
+}
+
+fn fila_gafete(g: &Gafete, alerta: Option<&AlertaGafete>) -> Fila {
+    fila(vec![
+        ("Gafete", json!(g.numero)),
+        ("Tipo", json!(g.tipo.display())),
+        ("En uso", json!(g.en_uso)),
+        ("Adeudado por", json!(alerta.map(|a| a.nombre_completo.as_str()))),
+        ("Cédula", json!(alerta.map(|a| a.cedula.as_str()))),
+        ("Reportado", json!(alerta.map(|a| datetime_to_iso(&a.fecha_reporte)))),
+        ("Notas", json!(alerta.and_then(|a| a.notas.as_deref()))),
+    ])
+}
+
+fn fila_lista_negra(b: &ListaNegra) -> Fila {
+    fila(vec![
+        ("Cédula", json!(b.cedula)),
+        ("Nombre", json!(format!("{} {}", b.nombre, b.apellido))),
+        ("Empresa", json!(b.empresa_nombre)),
+        ("Severidad", json!(b.nivel_severidad)),
+        ("Motivo", json!(b.motivo_bloqueo)),
+        ("Bloqueado por", json!(b.bloqueado_por_nombre)),
+        ("Bloqueado desde", json!(datetime_to_iso(&b.created_at))),
+    ])
+}
+
+fn fila_inasistencias(r: &InasistenciasAnfitrion) -> Fila {
+    fila(vec![
+        ("Anfitrión", json!(r.anfitrion)),
+        ("Citas", json!(r.total)),
+        ("Asistidas", json!(r.asistidas)),
+        ("Vencidas", json!(r.vencidas)),
+        ("Canceladas", json!(r.canceladas)),
+        ("Pendientes", json!(r.pendientes)),
+        ("% Inasistencia", json!(r.porcentaje_inasistencia)),
+    ])
+}
+
+// --------------------------------------------------------------------------
+// PRUEBAS UNITARIAS
+// --------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definiciones_tienen_headers_validos() {
+        for def in list_report_definitions() {
+            assert!(
+                export_domain::validar_headers(&def.headers).is_ok(),
+                "Headers inválidos en {}",
+                def.id.as_str()
+            );
+            assert_eq!(def.id.as_str().parse::<ReportKind>().unwrap(), def.id);
+        }
+    }
+
+    #[test]
+    fn test_rango_fechas() {
+        let local = |s: &str| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .unwrap()
+                .with_timezone(&chrono::Local)
+                .naive_local()
+                .to_string()
+        };
+        let (inicio, fin) = rango_fechas(Some("2025-03-01"), Some("2025-03-31")).unwrap();
+        assert_eq!(local(&inicio), "2025-03-01 00:00:00");
+        assert_eq!(local(&fin), "2025-03-31 23:59:59");
+
+        let (_, fin) = rango_fechas(Some("2025-03-01"), None).unwrap();
+        assert_eq!(local(&fin), "2025-03-01 23:59:59");
+
+        assert!(rango_fechas(None, Some("2025-03-01")).is_err());
+        assert!(rango_fechas(Some("2025-03-10"), Some("2025-03-01")).is_err());
+        assert!(rango_fechas(Some("01/03/2025"), None).is_err());
+    }
+
+    fn empresa(id: &str, nombre: &str, padre: Option<&str>) -> Empresa {
+        Empresa {
+            id: surrealdb::RecordId::from(("empresa", id)),
+            nombre: nombre.to_string(),
+            direccion: None,
+            is_active: true,
+            cedula_juridica: None,
+            contactos: Vec::new(),
+            empresa_padre: padre.map(|p| surrealdb::RecordId::from(("empresa", p))),
+            contrato_inicio: None,
+            contrato_fin: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_jerarquia_empresas() {
+        let jerarquia = JerarquiaEmpresas::new(&[
+            empresa("acme", "Acme", None),
+            empresa("sub", "Montajes Sub", Some("acme")),
+            empresa("subsub", "Pinturas", Some("sub")),
+            empresa("otra", "Otra", None),
+        ]);
+
+        assert_eq!(jerarquia.principal_por_nombre("PINTURAS"), Some("Acme"));
+        assert_eq!(jerarquia.principal_por_nombre("Otra"), Some("Otra"));
+        assert_eq!(jerarquia.principal_por_nombre("Desconocida"), None);
+
+        let nombres = jerarquia.nombres_filtrados(Some("empresa:sub")).unwrap().unwrap();
+        assert_eq!(nombres, HashSet::from(["montajes sub".to_string(), "pinturas".to_string()]));
+        assert!(jerarquia.nombres_filtrados(None).unwrap().is_none());
+        assert!(jerarquia.ids_filtrados(Some("empresa:nada")).is_err());
+    }
+
+    #[test]
+    fn test_validar_tipo_ingreso() {
+        assert_eq!(validar_tipo_ingreso(Some(" Visita ")).unwrap().as_deref(), Some("visita"));
+        assert!(validar_tipo_ingreso(Some("")).unwrap().is_none());
+        assert!(validar_tipo_ingreso(Some("empleado")).is_err());
+    }
+}
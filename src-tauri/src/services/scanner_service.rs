@@ -0,0 +1,223 @@
+//! # Servicio: Lecturas de Escáner
+//!
+//! Recibe la cadena cruda de un escáner (modo teclado o serial), la decodifica con
+//! `domain::scanner` y la resuelve contra la base de datos: persona registrada por cédula,
+//! gafete y su ingreso abierto, cita o ingreso del pase impreso. El resultado indica a la UI
+//! el siguiente paso (validar ingreso, registrar salida, procesar cita...).
+
+use crate::config::manager::load_config;
+use crate::db::surrealdb_contratista_queries as contratista_queries;
+use crate::db::surrealdb_gafete_queries as gafete_queries;
+use crate::db::surrealdb_ingreso_general_queries as ingreso_general_queries;
+use crate::db::surrealdb_proveedor_queries as proveedor_queries;
+use crate::domain::common::parse_record_id;
+use crate::domain::errors::ScanError;
+use crate::domain::scanner::{decodificar_lectura, variantes_cedula};
+use crate::models::gafete::{GafeteResponse, TipoGafete};
+use crate::models::ingreso::{IngresoResponse, UniversalIngresoFetched};
+use crate::models::scanner::{AccionEscaneo, CodigoEscaneado, PersonaEscaneada, ScanResolucion};
+use crate::services::{cita_service, visitante_service};
+use log::debug;
+
+/// Decodifica una lectura cruda usando el prefijo/sufijo configurado.
+pub fn decodificar(raw: &str) -> Result<CodigoEscaneado, ScanError> {
+    let config = load_config().map_err(|e| ScanError::Config(e.to_string()))?.scanner;
+    decodificar_lectura(raw, &config.prefijo, &config.sufijo).map_err(ScanError::Lectura)
+}
+
+/// Decodifica y resuelve una lectura del escáner.
+pub async fn resolver_lectura(raw: &str) -> Result<ScanResolucion, ScanError> {
+    let codigo = decodificar(raw)?;
+    debug!("🔎 Lectura de escáner decodificada: {codigo:?}");
+
+    match codigo {
+        CodigoEscaneado::Cedula { ref cedula, .. } => {
+            let persona = buscar_persona(cedula).await?;
+            let accion = match persona.as_ref().map(|p| p.tipo.as_str()) {
+                Some("contratista") => AccionEscaneo::ValidarIngresoContratista,
+                Some("proveedor") => AccionEscaneo::ValidarIngresoProveedor,
+                Some(_) => AccionEscaneo::ValidarIngresoVisita,
+                None => AccionEscaneo::RegistrarVisitante,
+            };
+            Ok(resolucion(codigo, accion, persona, None))
+        }
+        CodigoEscaneado::Gafete { numero, ref tipo } => {
+            let ingreso = buscar_ingreso_abierto(numero, tipo.as_deref()).await?;
+
+            let tipo_gafete = tipo
+                .clone()
+                .or_else(|| ingreso.as_ref().map(|i| i.tipo_ingreso.as_str().to_string()));
+            let gafete = buscar_gafete(numero, tipo_gafete.as_deref()).await?;
+
+            if gafete.is_none() && ingreso.is_none() {
+                return Err(ScanError::NotFound(format!("Gafete {numero}")));
+            }
+
+            let accion = if ingreso.is_some() {
+                AccionEscaneo::RegistrarSalida
+            } else {
+                AccionEscaneo::ConsultarGafete
+            };
+            let mut res = resolucion(codigo, accion, None, ingreso);
+            res.gafete = gafete;
+            Ok(res)
+        }
+        CodigoEscaneado::Cita { ref id } => {
+            let cita = cita_service::get_cita_by_id(id.clone())
+                .await
+                .map_err(|e| ScanError::NotFound(format!("Cita {id}: {e}")))?;
+            let mut res = resolucion(codigo, AccionEscaneo::ProcesarCita, None, None);
+            res.cita = Some(cita);
+            Ok(res)
+        }
+        CodigoEscaneado::Invitacion { ref token } => {
+            let cita = cita_service::verificar_invitacion(token)
+                .await
+                .map_err(|e| ScanError::Lectura(e.to_string()))?;
+            let mut res = resolucion(codigo, AccionEscaneo::ProcesarCita, None, None);
+            res.cita = Some(cita);
+            Ok(res)
+        }
+        CodigoEscaneado::Ingreso { ref id } => {
+            let ingreso =
+                ingreso_general_queries::find_by_id_fetched(&parse_record_id(id, "ingreso_visita"))
+                    .await
+                    .map_err(|e| ScanError::Database(e.to_string()))?
+                    .ok_or_else(|| ScanError::NotFound(format!("Ingreso {id}")))?
+                    .to_response()
+                    .map_err(ScanError::Database)?;
+
+            let accion = if ingreso.fecha_hora_salida.is_none() {
+                AccionEscaneo::RegistrarSalida
+            } else {
+                AccionEscaneo::ConsultarGafete
+            };
+            Ok(resolucion(codigo, accion, None, Some(ingreso)))
+        }
+    }
+}
+
+const fn resolucion(
+    codigo: CodigoEscaneado,
+    accion: AccionEscaneo,
+    persona: Option<PersonaEscaneada>,
+    ingreso_abierto: Option<IngresoResponse>,
+) -> ScanResolucion {
+    ScanResolucion { codigo, accion, persona, gafete: None, ingreso_abierto, cita: None }
+}
+
+/// Busca la cédula en contratistas, proveedores y visitantes (en ese orden).
+async fn buscar_persona(cedula: &str) -> Result<Option<PersonaEscaneada>, ScanError> {
+    for variante in variantes_cedula(cedula) {
+        if let Some(c) = contratista_queries::find_by_cedula(&variante)
+            .await
+            .map_err(|e| ScanError::Database(e.to_string()))?
+        {
+            return Ok(Some(PersonaEscaneada {
+                tipo: "contratista".to_string(),
+                id: c.id.to_string(),
+                nombre_completo: format!("{} {}", c.nombre, c.apellido),
+                cedula: c.cedula,
+                empresa: Some(c.empresa.nombre),
+            }));
+        }
+
+        if let Some(p) = proveedor_queries::find_by_cedula(&variante)
+            .await
+            .map_err(|e| ScanError::Database(e.to_string()))?
+        {
+            return Ok(Some(PersonaEscaneada {
+                tipo: "proveedor".to_string(),
+                id: p.id.to_string(),
+                nombre_completo: format!("{} {}", p.nombre, p.apellido),
+                cedula: p.cedula,
+                empresa: Some(p.empresa.nombre),
+            }));
+        }
+
+        if let Some(v) = visitante_service::get_visitante_by_cedula(&variante)
+            .await
+            .map_err(|e| ScanError::Database(e.to_string()))?
+        {
+            return Ok(Some(PersonaEscaneada {
+                tipo: "visitante".to_string(),
+                id: v.id,
+                nombre_completo: format!("{} {}", v.nombre, v.apellido),
+                cedula: v.cedula,
+                empresa: Some(v.empresa).filter(|e| !e.is_empty()),
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Tabla de ingresos que usa cada tipo de gafete (`otro` no se asigna en ingresos).
+fn tabla_ingreso(tipo: &str) -> Option<&'static str> {
+    match tipo {
+        "contratista" => Some("ingreso_contratista"),
+        "proveedor" => Some("ingreso_proveedor"),
+        "visita" => Some("ingreso_visita"),
+        _ => None,
+    }
+}
+
+/// Ingreso abierto que porta el gafete, buscado por número y tipo.
+///
+/// Sin tipo se buscan todos los ingresos; si el número está en uso por más de un tipo la
+/// lectura es ambigua y se rechaza en lugar de elegir uno al azar.
+async fn buscar_ingreso_abierto(
+    numero: i32,
+    tipo: Option<&str>,
+) -> Result<Option<IngresoResponse>, ScanError> {
+    let tablas: Vec<&str> = match tipo {
+        Some(t) => tabla_ingreso(t).into_iter().collect(),
+        None => vec!["ingreso_contratista", "ingreso_proveedor", "ingreso_visita"],
+    };
+    if tablas.is_empty() {
+        return Ok(None);
+    }
+
+    let mut abiertos = ingreso_general_queries::find_abiertos_by_gafete_fetched(numero, &tablas)
+        .await
+        .map_err(|e| ScanError::Database(e.to_string()))?;
+    if abiertos.len() > 1 {
+        return Err(ScanError::Lectura(format!(
+            "El gafete {numero} está en uso por {} ingresos de distinto tipo; escanee el código con tipo",
+            abiertos.len()
+        )));
+    }
+    abiertos
+        .pop()
+        .map(UniversalIngresoFetched::to_response)
+        .transpose()
+        .map_err(ScanError::Database)
+}
+
+/// Busca el gafete por número; sin tipo, solo si el número no es ambiguo.
+async fn buscar_gafete(
+    numero: i32,
+    tipo: Option<&str>,
+) -> Result<Option<GafeteResponse>, ScanError> {
+    let tipos: Vec<String> = match tipo {
+        Some(t) => vec![t.to_string()],
+        None => {
+            [TipoGafete::Contratista, TipoGafete::Proveedor, TipoGafete::Visita, TipoGafete::Otro]
+                .iter()
+                .map(|t| t.as_str().to_string())
+                .collect()
+        }
+    };
+
+    let mut encontrados = Vec::new();
+    for t in &tipos {
+        if let Some(g) = gafete_queries::get_gafete(numero, t)
+            .await
+            .map_err(|e| ScanError::Database(e.to_string()))?
+        {
+            encontrados.push(g);
+        }
+    }
+
+    Ok(if encontrados.len() == 1 { encontrados.pop().map(GafeteResponse::from) } else { None })
+}
@@ -8,58 +8,202 @@
 //! - Inicialización y gestión del índice de búsqueda
 //! - Indexación de entidades (Contratistas, Usuarios, Proveedores, `ListaNegra`)
 //! - Reindexación completa desde `SurrealDB`
+//! - Reconstrucción en segundo plano cuando cambia el schema (intercambio atómico); si el
+//!   schema anterior es incompatible, esa generación sigue atendiendo búsquedas de texto
+//! - Verificación de consistencia índice vs base de datos
 //! - Búsqueda multi-entidad de alto rendimiento
 //!
 //! ## Arquitectura
+//! - **`ActiveIndex`**: Generación activa (Index + Reader + campos), intercambiable en caliente
 //! - **Index**: Índice persistido en disco
 //! - **Reader**: Lector para consultas (recargable tras commits)
 //! - **Writer**: Escritor protegido por Mutex
 //! - **`FieldHandles`**: Cache de campos para acceso O(1)
 
+use crate::db::surrealdb_utils::leer_paginado;
 use crate::db::{
     surrealdb_contratista_queries as contratista_queries,
+    surrealdb_empresa_queries as empresa_queries,
+    surrealdb_ingreso_general_queries as ingreso_general_queries,
     surrealdb_lista_negra_queries as lista_negra_queries,
     surrealdb_proveedor_queries as proveedor_queries, surrealdb_user_queries as user_queries,
 };
+use crate::domain::common::parsear_fecha_simple;
 use crate::models::contratista::ContratistaFetched;
 use crate::models::lista_negra::ListaNegra;
 use crate::models::proveedor::ProveedorFetched;
+use crate::models::role::Module;
 use crate::models::user::{User, UserFetched};
 use crate::search::errors::SearchError;
-use crate::search::schema::FieldHandles;
-use crate::search::searcher::SearchResultDto;
+use crate::search::schema::{empresa_key, FieldHandles, SCHEMA_VERSION};
+use crate::search::searcher::{
+    search_legacy_index, search_lista_negra_por_nombre, search_structured, EstadoFiltro,
+    SearchFilters, SearchResultDto, StructuredSearchResult,
+};
 use crate::search::{
-    commit_index, create_field_handles, delete_from_index, index_contratista_fetched,
+    activate_generation, cleanup_stale_generations, commit_index, count_by_tipo,
+    create_field_handles, create_generation, delete_from_index, index_contratista_fetched,
     index_lista_negra, index_proveedor_fetched, index_user, index_user_fetched,
     update_contratista_fetched_in_index, update_lista_negra_in_index,
     update_proveedor_fetched_in_index, update_user_fetched_in_index, update_user_in_index,
+    write_schema_marker, IndexStatus,
 };
 use crate::search::{
     get_index_reader, get_index_writer, initialize_index, search_index, SearchFields,
 };
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::Arc;
-use tantivy::{Index, IndexReader};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError};
+use tantivy::{Index, IndexReader, IndexWriter};
 use tauri::async_runtime::spawn_blocking;
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
 
 pub struct SearchState(pub RwLock<Arc<SearchService>>);
 
-/// Orquestador del motor de búsqueda.
-pub struct SearchService {
-    /// Instancia principal del índice en disco.
-    pub index: Arc<Index>,
+/// Componentes de una generación abierta del índice.
+///
+/// Se reemplazan en bloque cuando una reconstrucción en segundo plano termina, de modo que
+/// las búsquedas en curso siguen usando la generación anterior hasta soltar su `Arc`.
+pub struct ActiveIndex {
+    /// Instancia del índice en disco.
+    pub index: Index,
     /// Lector para realizar consultas. Se recarga tras cada 'commit'.
-    pub reader: Arc<IndexReader>,
+    pub reader: IndexReader,
     /// Cache de identificadores de campos (schema) para optimizar el acceso en caliente.
-    pub fields: Arc<SearchFields>,
+    pub fields: SearchFields,
     /// Handles pre-calculados para evitar errores de mapeo durante la escritura.
     pub handles: FieldHandles,
+    /// Directorio de la generación.
+    pub path: PathBuf,
+}
+
+impl ActiveIndex {
+    fn open(index: Index, path: PathBuf) -> Result<Self, SearchError> {
+        // Pre-calculamos los campos una sola vez para maximizar la velocidad de búsqueda.
+        let handles = create_field_handles(&index.schema())?;
+        let fields = SearchFields::new(&index.schema());
+        let reader = get_index_reader(&index)?;
+
+        Ok(Self { index, reader, fields, handles, path })
+    }
+}
+
+/// Generación anterior con schema incompatible, activa en disco hasta que termine la
+/// reconstrucción. Solo atiende búsquedas de texto (ver `search_legacy_index`).
+struct GeneracionPrevia {
+    index: Index,
+    reader: IndexReader,
+}
+
+/// Fotografía de las entidades indexables leída desde `SurrealDB`.
+struct EntitySnapshot {
+    contratistas: Vec<ContratistaFetched>,
+    users: Vec<UserFetched>,
+    lista_negra: Vec<ListaNegra>,
+    proveedores: Vec<ProveedorFetched>,
+}
+
+impl EntitySnapshot {
+    const fn len(&self) -> usize {
+        self.contratistas.len() + self.users.len() + self.lista_negra.len() + self.proveedores.len()
+    }
+}
+
+/// Comparación de conteos entre el índice y la base de datos para un tipo de entidad.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexConsistencyEntry {
+    pub tipo: String,
+    pub indexados: u64,
+    pub en_base_datos: u64,
+    pub consistente: bool,
+}
+
+/// Resultado de la verificación de consistencia del índice de búsqueda.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexConsistencyReport {
+    pub schema_version: u32,
+    pub requiere_reconstruccion: bool,
+    pub reconstruccion_en_curso: bool,
+    pub entradas: Vec<IndexConsistencyEntry>,
+    pub consistente: bool,
+}
+
+/// Módulo cuyo permiso de lectura da acceso a cada tipo de entidad indexado.
+pub const MODULO_POR_TIPO: [(&str, Module); 4] = [
+    ("contratista", Module::Contratistas),
+    ("usuario", Module::Users),
+    ("proveedor", Module::Proveedores),
+    ("lista_negra", Module::ListaNegra),
+];
+
+/// Tipos a consultar: los solicitados (todos si no se pide ninguno) que el usuario puede leer.
+pub fn tipos_visibles(solicitados: &[String], permitidos: &[&str]) -> Vec<String> {
+    permitidos
+        .iter()
+        .filter(|p| solicitados.is_empty() || solicitados.iter().any(|s| s == *p))
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Consulta estructurada: texto libre opcional más filtros, paginación y facetas.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchQuery {
+    pub texto: Option<String>,
+    /// Tipos de entidad (`contratista`, `usuario`, `proveedor`, `lista_negra`).
+    #[serde(default)]
+    pub tipos: Vec<String>,
+    pub empresa_id: Option<String>,
+    pub estado: Option<EstadoFiltro>,
+    /// Rango de vencimiento del PRAIND (YYYY-MM-DD, inclusivo).
+    pub praind_desde: Option<String>,
+    pub praind_hasta: Option<String>,
+    /// `true`: solo personas actualmente dentro; `false`: solo las que están fuera.
+    pub dentro: Option<bool>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+/// Página de resultados de una búsqueda estructurada.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchPage {
+    #[serde(flatten)]
+    pub resultado: StructuredSearchResult,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+/// Convierte una fecha YYYY-MM-DD al inicio (o final) del día en el tipo de fecha del índice.
+pub(crate) fn parse_fecha_limite(
+    fecha: &str,
+    fin_de_dia: bool,
+) -> Result<tantivy::DateTime, SearchError> {
+    let fecha = parsear_fecha_simple(fecha).map_err(|e| SearchError::QueryError(e.to_string()))?;
+    let hora = if fin_de_dia { fecha.and_hms_opt(23, 59, 59) } else { fecha.and_hms_opt(0, 0, 0) }
+        .ok_or_else(|| SearchError::QueryError("Fecha fuera de rango".to_string()))?;
+    Ok(tantivy::DateTime::from_timestamp_secs(hora.and_utc().timestamp()))
+}
+
+/// Orquestador del motor de búsqueda.
+pub struct SearchService {
+    /// Generación activa del índice, intercambiable tras una reconstrucción.
+    active: std::sync::RwLock<Arc<ActiveIndex>>,
     /// Mutex de escritura: Solo un hilo puede escribir o modificar el índice a la vez.
     pub writer_mutex: Mutex<()>,
-    #[allow(dead_code)]
+    /// El schema en disco no coincide con el de la aplicación.
+    needs_rebuild: AtomicBool,
+    /// Evita lanzar dos reconstrucciones simultáneas.
+    rebuilding: AtomicBool,
+    /// Generación incompatible que sigue atendiendo búsquedas mientras se reconstruye.
+    previa: std::sync::RwLock<Option<Arc<GeneracionPrevia>>>,
+    /// Directorio raíz que contiene las generaciones del índice.
     index_path: PathBuf,
 }
 
@@ -77,19 +221,35 @@ impl SearchService {
             })?;
         }
 
-        let index = initialize_index(&index_path)?;
-        let reader = get_index_reader(&index)?;
+        let opened = initialize_index(&index_path)?;
+        let needs_rebuild =
+            matches!(opened.status, IndexStatus::Outdated | IndexStatus::Incompatible);
 
-        // Pre-calculamos los campos una sola vez al inicio para maximizar la velocidad de búsqueda.
-        let fields = Arc::new(SearchFields::new(&index.schema()));
-        let handles = create_field_handles(&index.schema())?;
+        let previa = opened.previa.and_then(|(index, path)| match get_index_reader(&index) {
+            Ok(reader) => {
+                info!("📂 Se sigue sirviendo {} hasta reconstruir el índice", path.display());
+                Some(Arc::new(GeneracionPrevia { index, reader }))
+            }
+            Err(e) => {
+                warn!("⚠️ No se pudo abrir la generación anterior del índice: {e}");
+                None
+            }
+        });
+
+        // Restos de generaciones anteriores (p. ej. no borrables en Windows en la sesión previa).
+        // Con una generación previa en uso la limpieza espera al final de la reconstrucción.
+        if previa.is_none() {
+            cleanup_stale_generations(&index_path, &opened.path);
+        }
+
+        let active = ActiveIndex::open(opened.index, opened.path)?;
 
         Ok(Self {
-            index: Arc::new(index),
-            reader: Arc::new(reader),
-            fields,
-            handles,
+            active: std::sync::RwLock::new(Arc::new(active)),
             writer_mutex: Mutex::new(()),
+            needs_rebuild: AtomicBool::new(needs_rebuild),
+            rebuilding: AtomicBool::new(false),
+            previa: std::sync::RwLock::new(previa),
             index_path,
         })
     }
@@ -98,40 +258,49 @@ impl SearchService {
     pub fn test_instance() -> Self {
         use crate::search::schema::build_search_schema;
         let schema = build_search_schema();
-        let index = Index::create_in_ram(schema.clone());
-        let reader = get_index_reader(&index).unwrap();
-        let fields = Arc::new(SearchFields::new(&schema));
-        let handles = create_field_handles(&schema).unwrap();
+        let index = Index::create_in_ram(schema);
+        crate::search::historial::register_tokenizers(&index);
+        let active = ActiveIndex::open(index, PathBuf::from("memory")).unwrap();
 
         Self {
-            index: Arc::new(index),
-            reader: Arc::new(reader),
-            fields,
-            handles,
+            active: std::sync::RwLock::new(Arc::new(active)),
             writer_mutex: Mutex::new(()),
+            needs_rebuild: AtomicBool::new(false),
+            rebuilding: AtomicBool::new(false),
+            previa: std::sync::RwLock::new(None),
             index_path: PathBuf::from("memory"),
         }
     }
 
-    /// Reconstruye el índice completo sincronizándolo con los datos actuales de `SurrealDB`.
-    ///
-    /// Este proceso es intensivo en recursos. Se utiliza principalmente en el arranque inicial
-    /// o cuando se detecta una inconsistencia grave entre la base de datos y el motor de búsqueda.
-    pub async fn reindex_all(&self) -> Result<(), SearchError> {
-        info!("🔄 Iniciando reindexación completa del motor de búsqueda");
+    /// Generación activa del índice.
+    fn active(&self) -> Arc<ActiveIndex> {
+        self.active.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
 
-        // Obtenemos una fotografía actual de todas las entidades relevantes de la DB.
-        let contratistas = contratista_queries::find_all_fetched().await.map_err(|e| {
-            error!("❌ Error al cargar contratistas para reindexación: {e}");
-            SearchError::DatabaseError(e.to_string())
-        })?;
+    /// Generación incompatible aún en servicio, si la reconstrucción no ha terminado.
+    fn previa(&self) -> Option<Arc<GeneracionPrevia>> {
+        self.previa.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Indica si el schema en disco está desactualizado y debe reconstruirse.
+    pub fn needs_rebuild(&self) -> bool {
+        self.needs_rebuild.load(Ordering::Acquire)
+    }
+
+    /// Obtiene una fotografía actual de todas las entidades indexables.
+    async fn load_snapshot() -> Result<EntitySnapshot, SearchError> {
+        let contratistas =
+            leer_paginado(contratista_queries::find_page_fetched).await.map_err(|e| {
+                error!("❌ Error al cargar contratistas para reindexación: {e}");
+                SearchError::DatabaseError(e.to_string())
+            })?;
 
         let users = user_queries::find_all_fetched(None).await.map_err(|e| {
             error!("❌ Error al cargar usuarios para reindexación: {e}");
             SearchError::DatabaseError(e.to_string())
         })?;
 
-        let lista_negra = lista_negra_queries::find_all().await.map_err(|e| {
+        let lista_negra = leer_paginado(lista_negra_queries::find_page).await.map_err(|e| {
             error!("❌ Error al cargar lista negra para reindexación: {e}");
             SearchError::DatabaseError(e.to_string())
         })?;
@@ -149,13 +318,57 @@ impl SearchService {
             proveedores.len()
         );
 
+        Ok(EntitySnapshot { contratistas, users, lista_negra, proveedores })
+    }
+
+    /// Escribe todas las entidades de la fotografía con un writer ya abierto.
+    fn write_snapshot(
+        writer: &mut IndexWriter,
+        handles: &FieldHandles,
+        snapshot: &EntitySnapshot,
+    ) -> Result<(), SearchError> {
+        for c in &snapshot.contratistas {
+            index_contratista_fetched(writer, handles, c, &c.empresa.nombre)?;
+        }
+        for user in &snapshot.users {
+            index_user_fetched(writer, handles, user)?;
+        }
+        for ln in &snapshot.lista_negra {
+            index_lista_negra(writer, handles, ln)?;
+        }
+        for p in &snapshot.proveedores {
+            index_proveedor_fetched(writer, handles, p, &p.empresa.nombre)?;
+        }
+        Ok(())
+    }
+
+    /// Reconstruye el índice completo sincronizándolo con los datos actuales de `SurrealDB`.
+    ///
+    /// Este proceso es intensivo en recursos. Se utiliza principalmente en el arranque inicial
+    /// o cuando se detecta una inconsistencia grave entre la base de datos y el motor de búsqueda.
+    pub async fn reindex_all(&self) -> Result<(), SearchError> {
+        // La generación activa en memoria aún no está activada en disco: hay que reconstruir
+        // en una generación nueva e intercambiarla.
+        if self.previa().is_some() {
+            return self.rebuild_in_new_generation().await.map(|_| ());
+        }
+
+        info!("🔄 Iniciando reindexación completa del motor de búsqueda");
+
+        // Obtenemos una fotografía actual de todas las entidades relevantes de la DB.
+        let snapshot = Self::load_snapshot().await?;
+
         // Adquirimos el lock de escritura para evitar que otras actualizaciones parciales
         // interfieran con el vaciado y reconstrucción total del índice.
         let _lock = self.writer_mutex.lock().await;
+        let active = self.active();
 
         // 🚀 Movemos toda la indexación a un hilo de bloqueo para no congelar la UI
-        let index = self.index.clone();
-        let handles = self.handles;
+        let index = active.index.clone();
+        let handles = active.handles;
+        // Una generación `Outdated` se reconstruye aquí mismo: al terminar debe quedar marcada
+        // con el schema actual para no volver a reconstruirse en cada arranque.
+        let marcar_schema = self.needs_rebuild().then(|| active.path.clone());
 
         let indexed_count = spawn_blocking(move || -> Result<usize, SearchError> {
             let mut writer = get_index_writer(&index)?;
@@ -167,38 +380,126 @@ impl SearchService {
             })?;
 
             // Procesamos e indexamos cada tipo de entidad secuencialmente.
-            for c in &contratistas {
-                index_contratista_fetched(&mut writer, &handles, c, &c.empresa.nombre)?;
-            }
-            for user in &users {
-                index_user_fetched(&mut writer, &handles, user)?;
-            }
-            for ln in &lista_negra {
-                index_lista_negra(&mut writer, &handles, ln)?;
-            }
-            for p in &proveedores {
-                index_proveedor_fetched(&mut writer, &handles, p, &p.empresa.nombre)?;
-            }
+            Self::write_snapshot(&mut writer, &handles, &snapshot)?;
 
             // El commit persiste los cambios en disco.
             commit_index(&mut writer)?;
 
-            Ok(contratistas.len() + users.len() + lista_negra.len() + proveedores.len())
+            if let Some(path) = marcar_schema {
+                write_schema_marker(&path)?;
+            }
+
+            Ok(snapshot.len())
         })
         .await
         .map_err(|e| SearchError::TantivyError(format!("Error de hilo: {e}")))??;
 
         // Obligamos al lector a recargarse para que las búsquedas reflejen los nuevos datos de inmediato.
-        self.reader.reload().map_err(|e| {
+        active.reader.reload().map_err(|e| {
             error!("❌ Error al recargar lector de búsqueda: {e}");
             SearchError::TantivyError(format!("Error al recargar el lector de búsqueda: {e}"))
         })?;
+        self.needs_rebuild.store(false, Ordering::Release);
 
         info!("✅ Reindexación completa: {indexed_count} documentos indexados");
 
         Ok(())
     }
 
+    /// Reconstruye el índice en una generación nueva y la activa al terminar.
+    ///
+    /// A diferencia de `reindex_all`, la generación actual sigue atendiendo búsquedas durante
+    /// todo el proceso; solo las escrituras esperan en `writer_mutex` para que ningún cambio
+    /// se pierda entre la lectura de la DB y el intercambio. Retorna los documentos indexados.
+    pub async fn rebuild_in_new_generation(&self) -> Result<usize, SearchError> {
+        if self.rebuilding.swap(true, Ordering::AcqRel) {
+            return Err(SearchError::RebuildInProgress);
+        }
+
+        let result = self.rebuild_and_swap().await;
+        self.rebuilding.store(false, Ordering::Release);
+        result
+    }
+
+    async fn rebuild_and_swap(&self) -> Result<usize, SearchError> {
+        let _lock = self.writer_mutex.lock().await;
+        let snapshot = Self::load_snapshot().await?;
+        let root = self.index_path.clone();
+
+        let (new_active, indexed_count) =
+            spawn_blocking(move || -> Result<(ActiveIndex, usize), SearchError> {
+                let (index, path) = create_generation(&root)?;
+                let handles = create_field_handles(&index.schema())?;
+
+                let mut writer = get_index_writer(&index)?;
+                Self::write_snapshot(&mut writer, &handles, &snapshot)?;
+                commit_index(&mut writer)?;
+                drop(writer);
+
+                Ok((ActiveIndex::open(index, path)?, snapshot.len()))
+            })
+            .await
+            .map_err(|e| SearchError::TantivyError(format!("Error de hilo: {e}")))??;
+
+        new_active.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
+
+        // Primero se persiste el puntero y luego se intercambia en memoria: si la app se cierra
+        // entre ambos pasos, el próximo arranque abre directamente la generación nueva.
+        activate_generation(&self.index_path, &new_active.path)?;
+        let new_path = new_active.path.clone();
+        let previous = {
+            let mut guard = self.active.write().unwrap_or_else(PoisonError::into_inner);
+            std::mem::replace(&mut *guard, Arc::new(new_active))
+        };
+        self.needs_rebuild.store(false, Ordering::Release);
+        let previa = self.previa.write().unwrap_or_else(PoisonError::into_inner).take();
+
+        drop(previous);
+        drop(previa);
+        cleanup_stale_generations(&self.index_path, &new_path);
+
+        info!("✅ Índice reconstruido en {}: {indexed_count} documentos", new_path.display());
+        Ok(indexed_count)
+    }
+
+    /// Compara los documentos indexados por tipo con los registros en `SurrealDB`.
+    pub async fn check_consistency(&self) -> Result<IndexConsistencyReport, SearchError> {
+        let db_counts = [
+            ("contratista", contratista_queries::count_active().await),
+            ("usuario", user_queries::count_all().await),
+            ("lista_negra", lista_negra_queries::count_active().await),
+            ("proveedor", proveedor_queries::count_active().await),
+        ];
+
+        let active = self.active();
+        let mut entradas = Vec::with_capacity(db_counts.len());
+
+        for (tipo, db_count) in db_counts {
+            let en_base_datos = db_count.map_err(|e| SearchError::DatabaseError(e.to_string()))?;
+            let indexados = count_by_tipo(&active.reader, &active.fields, tipo)?;
+
+            entradas.push(IndexConsistencyEntry {
+                tipo: tipo.to_string(),
+                indexados,
+                en_base_datos,
+                consistente: indexados == en_base_datos,
+            });
+        }
+
+        let consistente = entradas.iter().all(|e| e.consistente);
+        if !consistente {
+            debug!("⚠️ Índice inconsistente con la base de datos: {entradas:?}");
+        }
+
+        Ok(IndexConsistencyReport {
+            schema_version: SCHEMA_VERSION,
+            requiere_reconstruccion: self.needs_rebuild(),
+            reconstruccion_en_curso: self.rebuilding.load(Ordering::Acquire),
+            entradas,
+            consistente,
+        })
+    }
+
     pub async fn reindex_all_contratistas(&self) -> Result<(), SearchError> {
         self.reindex_all().await
     }
@@ -212,15 +513,16 @@ impl SearchService {
     ) -> Result<(), SearchError> {
         debug!("➕ Indexando contratista: {}", contratista.id);
         let _lock = self.writer_mutex.lock().await;
+        let active = self.active();
 
         {
-            let mut writer = get_index_writer(&self.index)?;
+            let mut writer = get_index_writer(&active.index)?;
 
-            index_contratista_fetched(&mut writer, &self.handles, contratista, empresa_nombre)?;
+            index_contratista_fetched(&mut writer, &active.handles, contratista, empresa_nombre)?;
             commit_index(&mut writer)?;
         }
 
-        self.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
+        active.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
         Ok(())
     }
 
@@ -233,20 +535,21 @@ impl SearchService {
     ) -> Result<(), SearchError> {
         debug!("✏️ Actualizando índice contratista: {}", contratista.id);
         let _lock = self.writer_mutex.lock().await;
+        let active = self.active();
 
         {
-            let mut writer = get_index_writer(&self.index)?;
+            let mut writer = get_index_writer(&active.index)?;
 
             update_contratista_fetched_in_index(
                 &mut writer,
-                &self.handles,
+                &active.handles,
                 contratista,
                 empresa_nombre,
             )?;
             commit_index(&mut writer)?;
         }
 
-        self.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
+        active.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
         Ok(())
     }
 
@@ -255,15 +558,16 @@ impl SearchService {
     pub async fn delete_contratista(&self, id: &str) -> Result<(), SearchError> {
         debug!("🗑️ Eliminando contratista del índice: {id}");
         let _lock = self.writer_mutex.lock().await;
+        let active = self.active();
 
         {
-            let mut writer = get_index_writer(&self.index)?;
+            let mut writer = get_index_writer(&active.index)?;
 
-            delete_from_index(&mut writer, &self.handles, id)?;
+            delete_from_index(&mut writer, &active.handles, id)?;
             commit_index(&mut writer)?;
         }
 
-        self.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
+        active.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
         Ok(())
     }
 
@@ -272,15 +576,16 @@ impl SearchService {
     pub async fn add_user(&self, user: &User) -> Result<(), SearchError> {
         debug!("➕ Indexando usuario: {}", user.id);
         let _lock = self.writer_mutex.lock().await;
+        let active = self.active();
 
         {
-            let mut writer = get_index_writer(&self.index)?;
+            let mut writer = get_index_writer(&active.index)?;
 
-            index_user(&mut writer, &self.handles, user)?;
+            index_user(&mut writer, &active.handles, user)?;
             commit_index(&mut writer)?;
         }
 
-        self.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
+        active.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
         Ok(())
     }
 
@@ -289,15 +594,16 @@ impl SearchService {
     pub async fn update_user(&self, user: &User) -> Result<(), SearchError> {
         debug!("✏️ Actualizando índice usuario: {}", user.id);
         let _lock = self.writer_mutex.lock().await;
+        let active = self.active();
 
         {
-            let mut writer = get_index_writer(&self.index)?;
+            let mut writer = get_index_writer(&active.index)?;
 
-            update_user_in_index(&mut writer, &self.handles, user)?;
+            update_user_in_index(&mut writer, &active.handles, user)?;
             commit_index(&mut writer)?;
         }
 
-        self.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
+        active.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
         Ok(())
     }
 
@@ -306,45 +612,48 @@ impl SearchService {
     pub async fn delete_user(&self, id: &str) -> Result<(), SearchError> {
         debug!("🗑️ Eliminando usuario del índice: {id}");
         let _lock = self.writer_mutex.lock().await;
+        let active = self.active();
 
         {
-            let mut writer = get_index_writer(&self.index)?;
+            let mut writer = get_index_writer(&active.index)?;
 
-            delete_from_index(&mut writer, &self.handles, id)?;
+            delete_from_index(&mut writer, &active.handles, id)?;
             commit_index(&mut writer)?;
         }
 
-        self.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
+        active.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
         Ok(())
     }
 
     pub async fn add_user_fetched(&self, user: &UserFetched) -> Result<(), SearchError> {
         debug!("➕ Indexando usuario (fetched): {}", user.id);
         let _lock = self.writer_mutex.lock().await;
+        let active = self.active();
 
         {
-            let mut writer = get_index_writer(&self.index)?;
+            let mut writer = get_index_writer(&active.index)?;
 
-            index_user_fetched(&mut writer, &self.handles, user)?;
+            index_user_fetched(&mut writer, &active.handles, user)?;
             commit_index(&mut writer)?;
         }
 
-        self.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
+        active.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
         Ok(())
     }
 
     pub async fn update_user_fetched(&self, user: &UserFetched) -> Result<(), SearchError> {
         debug!("✏️ Actualizando índice usuario (fetched): {}", user.id);
         let _lock = self.writer_mutex.lock().await;
+        let active = self.active();
 
         {
-            let mut writer = get_index_writer(&self.index)?;
+            let mut writer = get_index_writer(&active.index)?;
 
-            update_user_fetched_in_index(&mut writer, &self.handles, user)?;
+            update_user_fetched_in_index(&mut writer, &active.handles, user)?;
             commit_index(&mut writer)?;
         }
 
-        self.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
+        active.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
         Ok(())
     }
 
@@ -353,45 +662,48 @@ impl SearchService {
     pub async fn add_lista_negra(&self, lista_negra: &ListaNegra) -> Result<(), SearchError> {
         debug!("➕ Indexando lista negra: {}", lista_negra.id);
         let _lock = self.writer_mutex.lock().await;
+        let active = self.active();
 
         {
-            let mut writer = get_index_writer(&self.index)?;
+            let mut writer = get_index_writer(&active.index)?;
 
-            index_lista_negra(&mut writer, &self.handles, lista_negra)?;
+            index_lista_negra(&mut writer, &active.handles, lista_negra)?;
             commit_index(&mut writer)?;
         }
 
-        self.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
+        active.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
         Ok(())
     }
 
     pub async fn update_lista_negra(&self, lista_negra: &ListaNegra) -> Result<(), SearchError> {
         debug!("✏️ Actualizando índice lista negra: {}", lista_negra.id);
         let _lock = self.writer_mutex.lock().await;
+        let active = self.active();
 
         {
-            let mut writer = get_index_writer(&self.index)?;
+            let mut writer = get_index_writer(&active.index)?;
 
-            update_lista_negra_in_index(&mut writer, &self.handles, lista_negra)?;
+            update_lista_negra_in_index(&mut writer, &active.handles, lista_negra)?;
             commit_index(&mut writer)?;
         }
 
-        self.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
+        active.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
         Ok(())
     }
 
     pub async fn delete_lista_negra(&self, id: &str) -> Result<(), SearchError> {
         debug!("🗑️ Eliminando lista negra del índice: {id}");
         let _lock = self.writer_mutex.lock().await;
+        let active = self.active();
 
         {
-            let mut writer = get_index_writer(&self.index)?;
+            let mut writer = get_index_writer(&active.index)?;
 
-            delete_from_index(&mut writer, &self.handles, id)?;
+            delete_from_index(&mut writer, &active.handles, id)?;
             commit_index(&mut writer)?;
         }
 
-        self.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
+        active.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
         Ok(())
     }
 
@@ -399,32 +711,120 @@ impl SearchService {
     ///
     /// Se optimiza pasando los campos pre-calculados (Cache), permitiendo devolver
     /// resultados relevantes en milisegundos incluso con miles de registros.
+    ///
+    /// Mientras se reconstruye un índice incompatible se consulta la generación anterior.
     pub fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResultDto>, SearchError> {
-        search_index(&self.index, &self.reader, &self.fields, query, limit)
+        if let Some(previa) = self.previa() {
+            return search_legacy_index(&previa.index, &previa.reader, query, limit);
+        }
+        let active = self.active();
+        search_index(&active.index, &active.reader, &active.fields, query, limit)
+    }
+
+    /// Candidatos de la lista negra con un nombre parecido (tokens plegados + tolerancia).
+    pub fn search_lista_negra_por_nombre(
+        &self,
+        terminos: &[(String, u8)],
+        limit: usize,
+    ) -> Result<Vec<SearchResultDto>, SearchError> {
+        if self.previa().is_some() {
+            return Err(SearchError::RebuildInProgress);
+        }
+        let active = self.active();
+        search_lista_negra_por_nombre(&active.reader, &active.fields, terminos, limit)
+    }
+
+    /// Búsqueda estructurada con filtros, paginación y facetas por tipo y empresa.
+    ///
+    /// El filtro "actualmente dentro" no vive en el índice: se resuelve en `SurrealDB` a partir
+    /// de los ingresos abiertos en el momento de la consulta.
+    pub async fn search_structured(&self, query: &SearchQuery) -> Result<SearchPage, SearchError> {
+        // Los filtros y facetas dependen de campos que la generación anterior no tiene
+        if self.previa().is_some() {
+            return Err(SearchError::RebuildInProgress);
+        }
+        let limit = query.limit.unwrap_or(20);
+        let offset = query.offset.unwrap_or(0);
+
+        let mut filters = SearchFilters {
+            tipos: query.tipos.clone(),
+            empresa_id: query.empresa_id.clone(),
+            estado: query.estado,
+            praind_desde: query
+                .praind_desde
+                .as_deref()
+                .map(|fecha| parse_fecha_limite(fecha, false))
+                .transpose()?,
+            praind_hasta: query
+                .praind_hasta
+                .as_deref()
+                .map(|fecha| parse_fecha_limite(fecha, true))
+                .transpose()?,
+            dentro: query.dentro,
+            ids_dentro: Vec::new(),
+        };
+
+        if query.dentro.is_some() {
+            filters.ids_dentro = ingreso_general_queries::find_personas_dentro_ids()
+                .await
+                .map_err(|e| SearchError::DatabaseError(e.to_string()))?
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+        }
+
+        let mut resultado = {
+            let active = self.active();
+            search_structured(
+                &active.index,
+                &active.reader,
+                &active.fields,
+                query.texto.as_deref(),
+                &filters,
+                limit,
+                offset,
+            )?
+        };
+
+        if !resultado.por_empresa.is_empty() {
+            let nombres: HashMap<String, String> = empresa_queries::find_all()
+                .await
+                .map_err(|e| SearchError::DatabaseError(e.to_string()))?
+                .into_iter()
+                .map(|empresa| (empresa_key(&empresa.id.to_string()), empresa.nombre))
+                .collect();
+
+            for faceta in &mut resultado.por_empresa {
+                faceta.etiqueta = nombres.get(&faceta.valor).cloned();
+            }
+        }
+
+        Ok(SearchPage { resultado, limit, offset })
     }
 
     pub fn is_empty(&self) -> bool {
-        let searcher = self.reader.searcher();
+        let searcher = self.active().reader.searcher();
         searcher.num_docs() == 0
     }
 
     pub fn doc_count(&self) -> u64 {
-        let searcher = self.reader.searcher();
+        let searcher = self.active().reader.searcher();
         searcher.num_docs()
     }
 
     pub async fn delete_proveedor(&self, id: &str) -> Result<(), SearchError> {
         debug!("🗑️ Eliminando proveedor del índice: {id}");
         let _lock = self.writer_mutex.lock().await;
+        let active = self.active();
 
         {
-            let mut writer = get_index_writer(&self.index)?;
+            let mut writer = get_index_writer(&active.index)?;
 
-            delete_from_index(&mut writer, &self.handles, id)?;
+            delete_from_index(&mut writer, &active.handles, id)?;
             commit_index(&mut writer)?;
         }
 
-        self.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
+        active.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
         Ok(())
     }
 
@@ -435,15 +835,16 @@ impl SearchService {
     ) -> Result<(), SearchError> {
         debug!("➕ Indexando proveedor: {}", proveedor.id);
         let _lock = self.writer_mutex.lock().await;
+        let active = self.active();
 
         {
-            let mut writer = get_index_writer(&self.index)?;
+            let mut writer = get_index_writer(&active.index)?;
 
-            index_proveedor_fetched(&mut writer, &self.handles, proveedor, empresa_nombre)?;
+            index_proveedor_fetched(&mut writer, &active.handles, proveedor, empresa_nombre)?;
             commit_index(&mut writer)?;
         }
 
-        self.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
+        active.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
         Ok(())
     }
 
@@ -454,20 +855,21 @@ impl SearchService {
     ) -> Result<(), SearchError> {
         debug!("✏️ Actualizando índice proveedor: {}", proveedor.id);
         let _lock = self.writer_mutex.lock().await;
+        let active = self.active();
 
         {
-            let mut writer = get_index_writer(&self.index)?;
+            let mut writer = get_index_writer(&active.index)?;
 
             update_proveedor_fetched_in_index(
                 &mut writer,
-                &self.handles,
+                &active.handles,
                 proveedor,
                 empresa_nombre,
             )?;
             commit_index(&mut writer)?;
         }
 
-        self.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
+        active.reader.reload().map_err(|e| SearchError::TantivyError(e.to_string()))?;
         Ok(())
     }
 }
@@ -482,6 +884,17 @@ mod tests {
     use chrono::Utc;
     use surrealdb::RecordId;
 
+    #[test]
+    fn test_tipos_visibles_respeta_permisos() {
+        let permitidos = ["contratista", "proveedor"];
+        assert_eq!(tipos_visibles(&[], &permitidos), vec!["contratista", "proveedor"]);
+        assert_eq!(
+            tipos_visibles(&["usuario".into(), "proveedor".into()], &permitidos),
+            vec!["proveedor"]
+        );
+        assert!(tipos_visibles(&["lista_negra".into()], &permitidos).is_empty());
+    }
+
     /// Verifica que se pueda crear una instancia en memoria sin errores.
     #[test]
     fn test_initialization() {
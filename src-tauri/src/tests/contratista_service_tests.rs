@@ -1,9 +1,13 @@
+use crate::domain::lista_negra::NombrePersona;
 use crate::models::contratista::{
     Contratista, ContratistaCreateDTO, ContratistaFetched, ContratistaUpdateDTO,
     CreateContratistaInput, EstadoContratista,
 };
+use crate::models::documento_contratista::{DocumentoContratista, RequisitoDocumento};
 use crate::models::empresa::Empresa;
-use crate::models::lista_negra::BlockStatus;
+use crate::models::lista_negra::{
+    BlockStatus, CoincidenciaListaNegra, EmpresaVetadaStatus, PropuestaListaNegra,
+};
 use crate::models::vehiculo::Vehiculo;
 use crate::repositories::traits::{
     AuditRepository, ContratistaRepository, EmpresaRepository, SecurityRepository,
@@ -57,6 +61,11 @@ impl ContratistaRepository for MockContratistaRepository {
                 nombre: "Empresa Mock".to_string(),
                 direccion: None,
                 is_active: true,
+                cedula_juridica: None,
+                contactos: Vec::new(),
+                empresa_padre: None,
+                contrato_inicio: None,
+                contrato_fin: None,
                 created_at: None,
                 updated_at: None,
             },
@@ -109,6 +118,11 @@ impl ContratistaRepository for MockContratistaRepository {
                 nombre: "Empresa Update".to_string(),
                 direccion: None,
                 is_active: true,
+                cedula_juridica: None,
+                contactos: Vec::new(),
+                empresa_padre: None,
+                contrato_inicio: None,
+                contrato_fin: None,
                 created_at: None,
                 updated_at: None,
             },
@@ -135,10 +149,26 @@ impl ContratistaRepository for MockContratistaRepository {
     async fn find_archived(&self) -> Result<Vec<ContratistaFetched>, SurrealDbError> {
         Ok(vec![])
     }
+
+    async fn find_documentos(
+        &self,
+        _: &RecordId,
+    ) -> Result<Vec<DocumentoContratista>, SurrealDbError> {
+        Ok(vec![])
+    }
+
+    async fn find_requisitos_documento(&self) -> Result<Vec<RequisitoDocumento>, SurrealDbError> {
+        Ok(vec![])
+    }
+
+    async fn find_empresa(&self, _: &RecordId) -> Result<Option<Empresa>, SurrealDbError> {
+        Ok(None)
+    }
 }
 
 struct MockSecurityRepository {
     pub should_block: bool,
+    pub empresa_vetada: bool,
 }
 
 #[async_trait]
@@ -150,6 +180,47 @@ impl SecurityRepository for MockSecurityRepository {
             bloqueado_desde: None,
         })
     }
+
+    async fn find_posibles_coincidencias(
+        &self,
+        _: &NombrePersona<'_>,
+        _: &str,
+    ) -> Result<Vec<CoincidenciaListaNegra>, SurrealDbError> {
+        Ok(vec![])
+    }
+
+    async fn check_empresa_vetada(
+        &self,
+        _: &RecordId,
+    ) -> Result<EmpresaVetadaStatus, SurrealDbError> {
+        if !self.empresa_vetada {
+            return Ok(EmpresaVetadaStatus::default());
+        }
+        Ok(EmpresaVetadaStatus {
+            is_vetada: true,
+            empresa_nombre: Some("Empresa Valid".to_string()),
+            nivel_severidad: Some("ALTO".to_string()),
+            motivo: Some("Incumplimiento".to_string()),
+            bloquear_registros: true,
+        })
+    }
+
+    async fn check_bloqueo_propuesto(
+        &self,
+        _: &str,
+    ) -> Result<Option<PropuestaListaNegra>, SurrealDbError> {
+        Ok(None)
+    }
+
+    async fn registrar_coincidencia_descartada(
+        &self,
+        _: &str,
+        _: &RecordId,
+        _: &str,
+        _: &RecordId,
+    ) -> Result<(), SurrealDbError> {
+        Ok(())
+    }
 }
 
 struct MockEmpresaRepository {
@@ -164,6 +235,11 @@ impl EmpresaRepository for MockEmpresaRepository {
                 nombre: "Empresa Valid".to_string(),
                 direccion: None,
                 is_active: true,
+                cedula_juridica: None,
+                contactos: Vec::new(),
+                empresa_padre: None,
+                contrato_inicio: None,
+                contrato_fin: None,
                 created_at: None,
                 updated_at: None,
             }))
@@ -204,6 +280,9 @@ impl AuditRepository for MockAuditRepository {
     ) -> Result<(), SurrealDbError> {
         Ok(())
     }
+    async fn ultimo_motivo_estado(&self, _: &str) -> Result<Option<String>, SurrealDbError> {
+        Ok(None)
+    }
 }
 
 // ================================================================
@@ -213,7 +292,7 @@ impl AuditRepository for MockAuditRepository {
 #[tokio::test]
 async fn test_create_contratista_success() {
     let repo = MockContratistaRepository { contratistas: Arc::new(Mutex::new(vec![])) };
-    let sec_repo = MockSecurityRepository { should_block: false };
+    let sec_repo = MockSecurityRepository { should_block: false, empresa_vetada: false };
     let emp_repo = MockEmpresaRepository { exists: true };
     let veh_repo = MockVehiculoRepository;
     let audit_repo = MockAuditRepository;
@@ -247,7 +326,7 @@ async fn test_create_contratista_success() {
 #[tokio::test]
 async fn test_create_contratista_blocked_security() {
     let repo = MockContratistaRepository { contratistas: Arc::new(Mutex::new(vec![])) };
-    let sec_repo = MockSecurityRepository { should_block: true }; // BLOCKED
+    let sec_repo = MockSecurityRepository { should_block: true, empresa_vetada: false }; // BLOCKED
     let emp_repo = MockEmpresaRepository { exists: true };
     let veh_repo = MockVehiculoRepository;
     let audit_repo = MockAuditRepository;
@@ -281,3 +360,37 @@ async fn test_create_contratista_blocked_security() {
         _ => panic!("Expected Validation error"),
     }
 }
+
+#[tokio::test]
+async fn test_create_contratista_empresa_vetada() {
+    let repo = MockContratistaRepository { contratistas: Arc::new(Mutex::new(vec![])) };
+    let sec_repo = MockSecurityRepository { should_block: false, empresa_vetada: true };
+    let emp_repo = MockEmpresaRepository { exists: true };
+    let veh_repo = MockVehiculoRepository;
+    let audit_repo = MockAuditRepository;
+
+    let service = ContratistaService::new(repo, sec_repo, emp_repo, veh_repo, audit_repo, None);
+
+    let input = CreateContratistaInput {
+        cedula: "0999999999".to_string(),
+        nombre: "Test".to_string(),
+        apellido: "Vetado".to_string(),
+        segundo_nombre: None,
+        segundo_apellido: None,
+        empresa_id: "empresa:123".to_string(),
+        fecha_vencimiento_praind: "2030-01-01".to_string(),
+        tiene_vehiculo: None,
+        tipo_vehiculo: None,
+        placa: None,
+        marca: None,
+        modelo: None,
+        color: None,
+    };
+
+    match service.create_contratista(input).await {
+        Err(crate::domain::errors::ContratistaError::Validation(msg)) => {
+            assert!(msg.contains("EMPRESA VETADA"));
+        }
+        other => panic!("Expected Validation error, got {:?}", other.map(|r| r.cedula)),
+    }
+}
@@ -1,10 +1,18 @@
+use crate::domain::errors::IngresoContratistaError;
+use crate::domain::lista_negra::NombrePersona;
 use crate::models::contratista::{Contratista, ContratistaFetched, EstadoContratista};
+use crate::models::documento_contratista::{
+    DocumentoContratista, RequisitoDocumento, TipoDocumentoContratista,
+};
 use crate::models::empresa::Empresa;
+use crate::models::gafete::{ContextoMovimientoGafete, DeudaGafete, TipoGafete};
 use crate::models::ingreso::{
     CreateIngresoContratistaInput, IngresoContratista, IngresoContratistaCreateDTO,
     IngresoContratistaFetched, RegistrarSalidaInput,
 };
-use crate::models::lista_negra::BlockStatus;
+use crate::models::lista_negra::{
+    BlockStatus, CoincidenciaListaNegra, EmpresaVetadaStatus, PropuestaListaNegra,
+};
 use crate::models::user::User;
 use crate::repositories::traits::{
     ContratistaRepository, GafeteRepository, IngresoContratistaRepository, SecurityRepository,
@@ -19,6 +27,7 @@ use surrealdb::RecordId;
 // MOCKS
 // ================================================================
 
+#[derive(Default)]
 struct MockIngresoRepo {
     pub ingresos: Arc<Mutex<Vec<IngresoContratistaFetched>>>,
 }
@@ -64,6 +73,11 @@ fn create_mock_contratista_fetched(id: RecordId) -> ContratistaFetched {
             nombre: "TestCorp".to_string(),
             direccion: None,
             is_active: true,
+            cedula_juridica: None,
+            contactos: Vec::new(),
+            empresa_padre: None,
+            contrato_inicio: None,
+            contrato_fin: None,
             created_at: None,
             updated_at: None,
         },
@@ -167,29 +181,91 @@ impl IngresoContratistaRepository for MockIngresoRepo {
 
 struct MockGafeteRepo {
     pub disponible: bool,
+    pub deudas: Vec<DeudaGafete>,
     pub en_uso_called: Arc<Mutex<bool>>,
     pub liberado_called: Arc<Mutex<bool>>,
 }
 
+impl Default for MockGafeteRepo {
+    fn default() -> Self {
+        Self {
+            disponible: true,
+            deudas: vec![],
+            en_uso_called: Arc::new(Mutex::new(false)),
+            liberado_called: Arc::new(Mutex::new(false)),
+        }
+    }
+}
+
 #[async_trait]
 impl GafeteRepository for MockGafeteRepo {
-    async fn is_disponible(&self, _: i32, _: &str) -> Result<bool, SurrealDbError> {
+    async fn is_disponible(&self, _: i32, _: &str, _: &RecordId) -> Result<bool, SurrealDbError> {
         Ok(self.disponible)
     }
-    async fn marcar_en_uso(&self, _: i32, _: &str) -> Result<(), SurrealDbError> {
+    async fn marcar_en_uso(
+        &self,
+        _: i32,
+        _: &str,
+        _: ContextoMovimientoGafete,
+    ) -> Result<(), SurrealDbError> {
         let mut called = self.en_uso_called.lock().unwrap();
         *called = true;
         Ok(())
     }
-    async fn liberar(&self, _: i32, _: &str) -> Result<(), SurrealDbError> {
+    async fn liberar(
+        &self,
+        _: i32,
+        _: &str,
+        _: ContextoMovimientoGafete,
+    ) -> Result<(), SurrealDbError> {
         let mut called = self.liberado_called.lock().unwrap();
         *called = true;
         Ok(())
     }
+    async fn registrar_no_devuelto(
+        &self,
+        _: i32,
+        _: &str,
+        _: ContextoMovimientoGafete,
+    ) -> Result<(), SurrealDbError> {
+        Ok(())
+    }
+    async fn deudas_pendientes(&self, _: &str) -> Result<Vec<DeudaGafete>, SurrealDbError> {
+        Ok(self.deudas.clone())
+    }
 }
 
+fn create_mock_deuda(numero: i32) -> DeudaGafete {
+    DeudaGafete {
+        id: RecordId::from(("deuda_gafete", format!("d{numero}"))),
+        gafete: RecordId::from(("gafete", format!("g{numero}"))),
+        numero,
+        tipo: TipoGafete::Contratista,
+        cedula: "123".to_string(),
+        nombre: Some("Test User".to_string()),
+        ingreso: None,
+        registrada_por: None,
+        desde: chrono::Utc::now().into(),
+        saldada: false,
+        saldada_en: None,
+        saldada_por: None,
+        notas: None,
+    }
+}
+
+#[derive(Default)]
 struct MockContratistaRepo {
     pub contratista: Option<ContratistaFetched>,
+    pub documentos: Vec<DocumentoContratista>,
+    pub requisitos: Vec<RequisitoDocumento>,
+    /// La consulta de documentos del contratista falla.
+    pub documentos_falla: bool,
+}
+
+impl MockContratistaRepo {
+    fn con(contratista: ContratistaFetched) -> Self {
+        Self { contratista: Some(contratista), ..Self::default() }
+    }
 }
 
 #[async_trait]
@@ -244,10 +320,33 @@ impl ContratistaRepository for MockContratistaRepo {
     async fn find_archived(&self) -> Result<Vec<ContratistaFetched>, SurrealDbError> {
         Ok(vec![])
     }
+    async fn find_documentos(
+        &self,
+        _: &RecordId,
+    ) -> Result<Vec<DocumentoContratista>, SurrealDbError> {
+        if self.documentos_falla {
+            return Err(SurrealDbError::Query("sin conexión".into()));
+        }
+        Ok(self.documentos.clone())
+    }
+    async fn find_requisitos_documento(&self) -> Result<Vec<RequisitoDocumento>, SurrealDbError> {
+        Ok(self.requisitos.clone())
+    }
+    async fn find_empresa(&self, _: &RecordId) -> Result<Option<Empresa>, SurrealDbError> {
+        Ok(None)
+    }
 }
 
+#[derive(Default)]
 struct MockSecurityRepo {
     pub blocked: bool,
+    pub coincidencias: Vec<CoincidenciaListaNegra>,
+    /// Severidad del veto sobre la empresa (`None` = sin veto).
+    pub veto_empresa: Option<&'static str>,
+    /// Simula una falla al consultar el veto.
+    pub veto_falla: bool,
+    /// Descartes auditados: (bloqueo, usuario).
+    pub descartes: Arc<Mutex<Vec<(String, String)>>>,
 }
 
 #[async_trait]
@@ -259,12 +358,109 @@ impl SecurityRepository for MockSecurityRepo {
             bloqueado_desde: None,
         })
     }
+
+    async fn find_posibles_coincidencias(
+        &self,
+        _: &NombrePersona<'_>,
+        _: &str,
+    ) -> Result<Vec<CoincidenciaListaNegra>, SurrealDbError> {
+        Ok(self.coincidencias.clone())
+    }
+
+    async fn check_empresa_vetada(
+        &self,
+        _: &RecordId,
+    ) -> Result<EmpresaVetadaStatus, SurrealDbError> {
+        if self.veto_falla {
+            return Err(SurrealDbError::Query("sin conexión".into()));
+        }
+        Ok(self.veto_empresa.map_or_else(EmpresaVetadaStatus::default, |nivel| {
+            EmpresaVetadaStatus {
+                is_vetada: true,
+                empresa_nombre: Some("Empresa Test".into()),
+                nivel_severidad: Some(nivel.into()),
+                motivo: Some("Incumplimiento de normas".into()),
+                bloquear_registros: true,
+            }
+        }))
+    }
+
+    async fn check_bloqueo_propuesto(
+        &self,
+        _: &str,
+    ) -> Result<Option<PropuestaListaNegra>, SurrealDbError> {
+        Ok(None)
+    }
+
+    async fn registrar_coincidencia_descartada(
+        &self,
+        bloqueo_id: &str,
+        _: &RecordId,
+        _: &str,
+        usuario: &RecordId,
+    ) -> Result<(), SurrealDbError> {
+        self.descartes.lock().unwrap().push((bloqueo_id.to_string(), usuario.to_string()));
+        Ok(())
+    }
+}
+
+fn create_mock_coincidencia() -> CoincidenciaListaNegra {
+    CoincidenciaListaNegra {
+        id: "lista_negra:ln1".into(),
+        cedula: "999".into(),
+        nombre_completo: "Test User".into(),
+        nivel_severidad: "ALTO".into(),
+        motivo_bloqueo: Some("Hurto".into()),
+        score: 0.93,
+    }
+}
+
+/// Mocks del servicio; cada prueba sobreescribe solo lo que le interesa.
+#[derive(Default)]
+struct Mocks {
+    ingresos: MockIngresoRepo,
+    gafetes: MockGafeteRepo,
+    contratistas: MockContratistaRepo,
+    security: MockSecurityRepo,
+}
+
+type TestService = IngresoContratistaService<
+    MockIngresoRepo,
+    MockGafeteRepo,
+    MockContratistaRepo,
+    MockSecurityRepo,
+>;
+
+fn service_with(mocks: Mocks) -> TestService {
+    IngresoContratistaService::new(
+        mocks.ingresos,
+        mocks.gafetes,
+        mocks.contratistas,
+        mocks.security,
+    )
+}
+
+fn create_mock_input() -> CreateIngresoContratistaInput {
+    CreateIngresoContratistaInput {
+        contratista_id: "c1".to_string(),
+        tipo_autorizacion: "NORMAL".to_string(),
+        modo_ingreso: "PEATONAL".to_string(),
+        placa_vehiculo: None,
+        gafete_numero: None,
+        observaciones: None,
+        area: None,
+        coincidencia_lista_negra_descartada: false,
+    }
 }
 
 // ================================================================
 // TESTS
 // ================================================================
 
+fn contratista_c1() -> ContratistaFetched {
+    create_mock_contratista_fetched(RecordId::from(("contratista", "c1")))
+}
+
 #[tokio::test]
 async fn test_validar_ingreso_success() {
     let contratista = create_mock_contratista_fetched(RecordId::from(("contratista", "c1")));
@@ -275,16 +471,222 @@ async fn test_validar_ingreso_success() {
             disponible: true,
             en_uso_called: Arc::new(Mutex::new(false)),
             liberado_called: Arc::new(Mutex::new(false)),
+            ..Default::default()
         },
-        MockContratistaRepo { contratista: Some(contratista) },
-        MockSecurityRepo { blocked: false },
+        MockContratistaRepo { contratista: Some(contratista), ..Default::default() },
+        MockSecurityRepo { blocked: false, ..Default::default() },
     );
 
-    let result = service.validar_ingreso_contratista("c1".to_string()).await;
+    let result = service.validar_ingreso_contratista("c1".to_string(), None).await;
     assert!(result.is_ok());
     assert!(result.unwrap().puede_ingresar);
 }
 
+#[tokio::test]
+async fn test_validar_ingreso_con_deudas_de_gafete() {
+    let service = service_with(Mocks {
+        gafetes: MockGafeteRepo {
+            deudas: vec![create_mock_deuda(7), create_mock_deuda(8), create_mock_deuda(9)],
+            ..Default::default()
+        },
+        contratistas: MockContratistaRepo::con(contratista_c1()),
+        ..Default::default()
+    });
+
+    let result = service.validar_ingreso_contratista("c1".to_string(), None).await.unwrap();
+    assert!(!result.puede_ingresar, "Más de 2 gafetes adeudados debe impedir el ingreso");
+    assert_eq!(result.alertas.len(), 3);
+    assert!(result.alertas[0].starts_with("Gafete #7 no devuelto"));
+    assert!(result.motivo_rechazo.unwrap().contains("3 gafetes sin devolver"));
+}
+
+#[tokio::test]
+async fn test_validar_ingreso_empresa_vetada() {
+    let service_con_veto = |nivel: &'static str| {
+        service_with(Mocks {
+            contratistas: MockContratistaRepo::con(contratista_c1()),
+            security: MockSecurityRepo { veto_empresa: Some(nivel), ..Default::default() },
+            ..Default::default()
+        })
+    };
+
+    let result =
+        service_con_veto("ALTO").validar_ingreso_contratista("c1".to_string(), None).await.unwrap();
+    assert!(!result.puede_ingresar);
+    assert!(result.motivo_rechazo.unwrap().contains("VETADA"));
+
+    // Severidad baja: se permite el ingreso con advertencia
+    let result =
+        service_con_veto("BAJO").validar_ingreso_contratista("c1".to_string(), None).await.unwrap();
+    assert!(result.puede_ingresar);
+    assert!(result.motivo_rechazo.is_none());
+    assert!(result.alertas[0].contains("Empresa"));
+
+    // El registro también aplica el veto, aunque se salte la pre-validación
+    let result = service_con_veto("ALTO")
+        .crear_ingreso_contratista(create_mock_input(), "u1".to_string())
+        .await;
+    assert!(matches!(result, Err(IngresoContratistaError::Blacklisted(_))));
+    let result = service_con_veto("BAJO")
+        .crear_ingreso_contratista(create_mock_input(), "u1".to_string())
+        .await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_veto_empresa_no_consultable_falla_cerrado() {
+    let service = service_with(Mocks {
+        contratistas: MockContratistaRepo::con(contratista_c1()),
+        security: MockSecurityRepo { veto_falla: true, ..Default::default() },
+        ..Default::default()
+    });
+
+    let result = service.validar_ingreso_contratista("c1".to_string(), None).await;
+    assert!(matches!(result, Err(IngresoContratistaError::Database(_))));
+
+    let result = service.crear_ingreso_contratista(create_mock_input(), "u1".to_string()).await;
+    assert!(matches!(result, Err(IngresoContratistaError::Database(_))));
+}
+
+#[tokio::test]
+async fn test_validar_ingreso_contrato_empresa_vencido() {
+    let mut contratista = contratista_c1();
+    let ayer = chrono::Utc::now() - chrono::Duration::days(1);
+    contratista.empresa.contrato_fin = Some(ayer.into());
+
+    let service = service_with(Mocks {
+        contratistas: MockContratistaRepo::con(contratista),
+        ..Default::default()
+    });
+
+    let result = service.validar_ingreso_contratista("c1".to_string(), None).await.unwrap();
+    assert!(!result.puede_ingresar, "Fuera del contrato de la empresa no hay acceso");
+    let motivo = result.motivo_rechazo.unwrap();
+    assert!(motivo.contains("el contrato de TestCorp venció el"), "{motivo}");
+
+    let result = service.crear_ingreso_contratista(create_mock_input(), "u1".to_string()).await;
+    assert!(matches!(result, Err(IngresoContratistaError::ContratoEmpresaVencido(_))));
+}
+
+fn create_mock_requisito(tipo: TipoDocumentoContratista, area: Option<&str>) -> RequisitoDocumento {
+    RequisitoDocumento {
+        id: RecordId::from(("requisito_documento", tipo.as_str())),
+        tipo,
+        empresa: None,
+        area: area.map(str::to_string),
+        descripcion: None,
+        creado_por: "u1".to_string(),
+        created_at: chrono::Utc::now().into(),
+    }
+}
+
+fn create_mock_documento(
+    tipo: TipoDocumentoContratista,
+    dias_vigencia: i64,
+) -> DocumentoContratista {
+    DocumentoContratista {
+        id: RecordId::from(("documento_contratista", tipo.as_str())),
+        contratista: RecordId::from(("contratista", "c1")),
+        tipo,
+        numero: None,
+        fecha_emision: None,
+        fecha_vencimiento: Some(
+            (chrono::Utc::now() + chrono::Duration::days(dias_vigencia)).into(),
+        ),
+        descripcion: None,
+        archivo_uuid: None,
+        nombre_original: None,
+        tipo_mime: None,
+        tamano_bytes: None,
+        sha256: None,
+        registrado_por: "u1".to_string(),
+        created_at: chrono::Utc::now().into(),
+        updated_at: chrono::Utc::now().into(),
+    }
+}
+
+#[tokio::test]
+async fn test_validar_ingreso_documentos_requeridos() {
+    let service_con_documentos = |documentos: Vec<DocumentoContratista>| {
+        service_with(Mocks {
+            contratistas: MockContratistaRepo {
+                documentos,
+                requisitos: vec![
+                    create_mock_requisito(TipoDocumentoContratista::PolizaRiesgos, None),
+                    create_mock_requisito(
+                        TipoDocumentoContratista::TrabajoAlturas,
+                        Some("Calderas"),
+                    ),
+                ],
+                ..MockContratistaRepo::con(contratista_c1())
+            },
+            ..Default::default()
+        })
+    };
+
+    // Póliza vigente y sin área: la regla de alturas no aplica
+    let service = service_con_documentos(vec![create_mock_documento(
+        TipoDocumentoContratista::PolizaRiesgos,
+        30,
+    )]);
+    let result = service.validar_ingreso_contratista("c1".to_string(), None).await.unwrap();
+    assert!(result.puede_ingresar);
+
+    // Ingreso a Calderas sin certificación de alturas
+    let result = service
+        .validar_ingreso_contratista("c1".to_string(), Some("calderas".to_string()))
+        .await
+        .unwrap();
+    assert!(!result.puede_ingresar);
+    assert!(result.motivo_rechazo.unwrap().contains("trabajo en alturas (no registrado)"));
+
+    // Póliza vencida
+    let service = service_con_documentos(vec![create_mock_documento(
+        TipoDocumentoContratista::PolizaRiesgos,
+        -5,
+    )]);
+    let result = service.validar_ingreso_contratista("c1".to_string(), None).await.unwrap();
+    assert!(!result.puede_ingresar);
+    assert!(result.motivo_rechazo.unwrap().contains("Póliza de riesgos (vencido el"));
+
+    // El registro aplica las mismas reglas, incluidas las del área de destino
+    let result = service.crear_ingreso_contratista(create_mock_input(), "u1".to_string()).await;
+    assert!(matches!(result, Err(IngresoContratistaError::DocumentosPendientes(_))));
+
+    let service = service_con_documentos(vec![create_mock_documento(
+        TipoDocumentoContratista::PolizaRiesgos,
+        30,
+    )]);
+    let a_calderas =
+        CreateIngresoContratistaInput { area: Some("Calderas".to_string()), ..create_mock_input() };
+    let result = service.crear_ingreso_contratista(a_calderas, "u1".to_string()).await;
+    match result {
+        Err(IngresoContratistaError::DocumentosPendientes(msg)) => {
+            assert!(msg.contains("trabajo en alturas (no registrado)"), "{msg}");
+        }
+        otro => panic!("Se esperaba DocumentosPendientes, se obtuvo {otro:?}"),
+    }
+    assert!(service.crear_ingreso_contratista(create_mock_input(), "u1".to_string()).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_documentos_no_consultables_falla_cerrado() {
+    let service = service_with(Mocks {
+        contratistas: MockContratistaRepo {
+            requisitos: vec![create_mock_requisito(TipoDocumentoContratista::PolizaRiesgos, None)],
+            documentos_falla: true,
+            ..MockContratistaRepo::con(contratista_c1())
+        },
+        ..Default::default()
+    });
+
+    let result = service.validar_ingreso_contratista("c1".to_string(), None).await;
+    assert!(matches!(result, Err(IngresoContratistaError::Database(_))));
+
+    let result = service.crear_ingreso_contratista(create_mock_input(), "u1".to_string()).await;
+    assert!(matches!(result, Err(IngresoContratistaError::Database(_))));
+}
+
 #[tokio::test]
 async fn test_crear_ingreso_success() {
     let contratista = create_mock_contratista_fetched(RecordId::from(("contratista", "c1")));
@@ -296,9 +698,10 @@ async fn test_crear_ingreso_success() {
             disponible: true,
             en_uso_called: en_uso_called.clone(),
             liberado_called: Arc::new(Mutex::new(false)),
+            ..Default::default()
         },
-        MockContratistaRepo { contratista: Some(contratista) },
-        MockSecurityRepo { blocked: false },
+        MockContratistaRepo { contratista: Some(contratista), ..Default::default() },
+        MockSecurityRepo { blocked: false, ..Default::default() },
     );
 
     let input = CreateIngresoContratistaInput {
@@ -308,6 +711,8 @@ async fn test_crear_ingreso_success() {
         placa_vehiculo: None,
         gafete_numero: Some("101".to_string()),
         observaciones: None,
+        area: None,
+        coincidencia_lista_negra_descartada: false,
     };
 
     let result = service.crear_ingreso_contratista(input, "u1".to_string()).await;
@@ -315,6 +720,57 @@ async fn test_crear_ingreso_success() {
     assert!(*en_uso_called.lock().unwrap(), "Gafete should be marked in use");
 }
 
+fn service_con_coincidencia() -> TestService {
+    service_with(Mocks {
+        contratistas: MockContratistaRepo::con(contratista_c1()),
+        security: MockSecurityRepo {
+            coincidencias: vec![create_mock_coincidencia()],
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+#[tokio::test]
+async fn test_validar_ingreso_con_posible_coincidencia() {
+    let service = service_con_coincidencia();
+
+    let result = service.validar_ingreso_contratista("c1".to_string(), None).await.unwrap();
+    assert!(result.puede_ingresar, "La coincidencia por nombre es advertencia, no bloqueo");
+    assert!(result.requiere_confirmacion);
+    assert_eq!(result.posibles_coincidencias.len(), 1);
+    assert!(result.alertas[0].contains("LISTA NEGRA"));
+}
+
+#[tokio::test]
+async fn test_crear_ingreso_con_posible_coincidencia_requiere_descartarla() {
+    let descartes = Arc::new(Mutex::new(Vec::new()));
+    let service = service_with(Mocks {
+        contratistas: MockContratistaRepo::con(contratista_c1()),
+        security: MockSecurityRepo {
+            coincidencias: vec![create_mock_coincidencia()],
+            descartes: descartes.clone(),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let input = |descartada: bool| CreateIngresoContratistaInput {
+        coincidencia_lista_negra_descartada: descartada,
+        ..create_mock_input()
+    };
+
+    let result = service.crear_ingreso_contratista(input(false), "u1".to_string()).await;
+    assert!(matches!(result, Err(IngresoContratistaError::PosibleListaNegra(_))));
+    assert!(descartes.lock().unwrap().is_empty());
+
+    let result = service.crear_ingreso_contratista(input(true), "u1".to_string()).await;
+    assert!(result.is_ok());
+    assert_eq!(
+        *descartes.lock().unwrap(),
+        vec![("lista_negra:ln1".to_string(), "user:u1".to_string())]
+    );
+}
+
 #[tokio::test]
 async fn test_registrar_salida_success() {
     let c_id = RecordId::from(("contratista", "c1"));
@@ -348,9 +804,10 @@ async fn test_registrar_salida_success() {
             disponible: true,
             en_uso_called: Arc::new(Mutex::new(false)),
             liberado_called: liberado_called.clone(),
+            ..Default::default()
         },
-        MockContratistaRepo { contratista: None },
-        MockSecurityRepo { blocked: false },
+        MockContratistaRepo { contratista: None, ..Default::default() },
+        MockSecurityRepo { blocked: false, ..Default::default() },
     );
 
     let input = RegistrarSalidaInput {
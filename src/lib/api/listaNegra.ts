@@ -3,6 +3,7 @@ import { invoke } from '@tauri-apps/api/core';
 import type {
 	ListaNegraResponse,
 	ListaNegraListResponse,
+	CambioListaNegraResponse,
 	BlockCheckResponse,
 	AddToListaNegraInput,
 	UpdateListaNegraInput,
@@ -13,14 +14,17 @@ export const listaNegra = {
 	/**
 	 * Agregar persona a lista negra
 	 */
-	add: async (input: AddToListaNegraInput): Promise<ListaNegraResponse> => {
+	add: async (input: AddToListaNegraInput): Promise<CambioListaNegraResponse> => {
 		return await invoke('add_to_lista_negra', { input });
 	},
 
 	/**
 	 * Actualizar información de bloqueo
 	 */
-	update: async (id: string, input: UpdateListaNegraInput): Promise<ListaNegraResponse> => {
+	update: async (
+		id: string,
+		input: UpdateListaNegraInput
+	): Promise<CambioListaNegraResponse> => {
 		return await invoke('update_lista_negra', { id, input });
 	},
 
@@ -28,7 +32,7 @@ export const listaNegra = {
 	 * Desbloquear (desactivar) - soft delete, isActive: true → false
 	 * Usa delete_from_lista_negra que hace soft delete
 	 */
-	remove: async (id: string): Promise<void> => {
+	remove: async (id: string): Promise<CambioListaNegraResponse> => {
 		return await invoke('delete_from_lista_negra', { id });
 	},
 
@@ -36,7 +40,7 @@ export const listaNegra = {
 	 * Re-bloquear (reactivar) - isActive: false → true
 	 * Usa restore_lista_negra para restaurar un registro desactivado
 	 */
-	reactivate: async (id: string): Promise<ListaNegraResponse> => {
+	reactivate: async (id: string): Promise<CambioListaNegraResponse> => {
 		return await invoke('restore_lista_negra', { id });
 	},
 
@@ -245,7 +245,8 @@ export async function crearIngreso(
 					placaVehiculo: placaVehiculo,
 					tipoAutorizacion: formData.tipoAutorizacion || 'praind',
 					modoIngreso: formData.modoIngreso || 'caminando',
-					observaciones: formData.observaciones || null
+					observaciones: formData.observaciones || null,
+					coincidenciaListaNegraDescartada: formData.coincidenciaListaNegraDescartada === true
 				},
 				usuarioId: usuarioId
 			};
@@ -263,7 +264,8 @@ export async function crearIngreso(
 					modoIngreso: formData.modoIngreso || 'caminando',
 					placaVehiculo: formData.vehiculoId, // For proveedor, usually passed directly
 					gafeteNumero: formData.gafete,
-					observaciones: formData.observaciones || null
+					observaciones: formData.observaciones || null,
+					coincidenciaListaNegraDescartada: formData.coincidenciaListaNegraDescartada === true
 				},
 				usuarioId: usuarioId
 			});
@@ -409,6 +411,8 @@ function mapContratistaResponse(res: ValidacionIngresoResponse): ValidacionIngre
 		alertas: res.alertas || [],
 		tieneIngresoAbierto: res.tieneIngresoAbierto,
 		ingresoAbierto: res.ingresoAbierto,
+		posiblesCoincidencias: res.posiblesCoincidencias || [],
+		requiereConfirmacion: res.requiereConfirmacion === true,
 		contratista: res.contratista,
 		persona: res.contratista
 			? {
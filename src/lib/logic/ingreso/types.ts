@@ -10,6 +10,7 @@ import type { ContratistaResponse } from '$lib/types/contratista';
 import type { ProveedorResponse } from '$lib/types/proveedor';
 import type { VisitanteResponse } from '$lib/types/visitante';
 import type { IngresoResponse, ValidacionIngresoResponse } from '$lib/types/ingreso';
+import type { CoincidenciaListaNegra } from '$lib/types/listaNegra';
 
 export type MotivoBloqueo =
 	| { tipo: 'lista_negra'; motivo: string }
@@ -55,6 +56,10 @@ export interface ValidacionIngresoResult {
 
 	tieneIngresoAbierto: boolean;
 	ingresoAbierto?: IngresoResponse;
+
+	// Posibles coincidencias por nombre en lista negra (requieren confirmación del guardia)
+	posiblesCoincidencias?: CoincidenciaListaNegra[];
+	requiereConfirmacion?: boolean;
 }
 
 export type IngresoStep = 'SEARCH' | 'VALIDATION' | 'DETAILS' | 'CONFIRM';
@@ -82,4 +87,5 @@ export interface FinalizarIngresoForm {
 	motivoExcepcional?: string; // Para excepcional
 	areaVisitada?: string;
 	motivo?: string;
+	coincidenciaListaNegraDescartada?: boolean; // El guardia confirmó que es otra persona
 }
@@ -7,6 +7,7 @@ import { listaNegra } from '$lib/api/listaNegra';
 import type {
 	ListaNegraResponse,
 	ListaNegraListResponse,
+	CambioListaNegraResponse,
 	AddToListaNegraInput,
 	UpdateListaNegraInput,
 	PersonaSearchResult,
@@ -185,7 +186,9 @@ export async function fetchById(id: string): Promise<ServiceResult<ListaNegraRes
 /**
  * Agregar a lista negra (con validación)
  */
-export async function add(input: AddToListaNegraInput): Promise<ServiceResult<ListaNegraResponse>> {
+export async function add(
+	input: AddToListaNegraInput
+): Promise<ServiceResult<CambioListaNegraResponse>> {
 	const validation = validateAddInput(input);
 	if (!validation.ok) {
 		return { ok: false, error: validation.message };
@@ -206,7 +209,7 @@ export async function add(input: AddToListaNegraInput): Promise<ServiceResult<Li
 export async function update(
 	id: string,
 	input: UpdateListaNegraInput
-): Promise<ServiceResult<ListaNegraResponse>> {
+): Promise<ServiceResult<CambioListaNegraResponse>> {
 	const validation = validateUpdateInput(input);
 	if (!validation.ok) {
 		return { ok: false, error: validation.message };
@@ -223,16 +226,17 @@ export async function update(
 
 /**
  * Desbloquear (desactivar isActive)
- * Nota: La operación no retorna el registro actualizado, solo confirma el éxito
+ * Nota: La operación no retorna el registro actualizado; `aplicado = false` indica
+ * que la baja quedó pendiente de aprobación
  */
-export async function unblock(id: string): Promise<ServiceResult<void>> {
+export async function unblock(id: string): Promise<ServiceResult<CambioListaNegraResponse>> {
 	if (!id) {
 		return { ok: false, error: 'El ID del registro es inválido.' };
 	}
 
 	try {
-		await listaNegra.remove(id);
-		return { ok: true, data: undefined };
+		const data = await listaNegra.remove(id);
+		return { ok: true, data };
 	} catch (err: unknown) {
 		console.error('Error al desbloquear:', err);
 		return { ok: false, error: parseError(err) };
@@ -244,7 +248,7 @@ export async function unblock(id: string): Promise<ServiceResult<void>> {
  * Nota: Los parámetros adicionales ya no son necesarios,
  * el registro se restaura con sus valores originales
  */
-export async function reblock(id: string): Promise<ServiceResult<ListaNegraResponse>> {
+export async function reblock(id: string): Promise<ServiceResult<CambioListaNegraResponse>> {
 	if (!id) {
 		return { ok: false, error: 'El ID del registro es inválido.' };
 	}
@@ -1,3 +1,5 @@
+import type { CoincidenciaListaNegra } from './listaNegra';
+
 export interface IngresoVisita {
 	id: string;
 	visitanteId: string;
@@ -59,6 +61,8 @@ export interface CreateIngresoProveedorInput {
 	gafeteNumero?: number;
 	// Audit
 	observaciones?: string;
+	// El guardia confirmó que no es la persona de una posible coincidencia en lista negra
+	coincidenciaListaNegraDescartada?: boolean;
 }
 
 export interface ProveedorCatalogItem {
@@ -88,4 +92,7 @@ export interface ValidacionIngresoProveedorResponse {
 	alertasGafete: string[];
 	tieneGafetesPendientes: boolean;
 	tieneIngresoAbierto: boolean;
+	alertaEmpresa?: string;
+	posiblesCoincidencias?: CoincidenciaListaNegra[];
+	requiereConfirmacion?: boolean;
 }
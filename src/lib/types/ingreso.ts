@@ -5,6 +5,7 @@ import { z } from 'zod';
 import type { ContratistaResponse } from './contratista';
 import type { ProveedorResponse } from './proveedor';
 import type { VisitanteResponse } from './visitante';
+import type { CoincidenciaListaNegra } from './listaNegra';
 
 // ==========================================
 // ESQUEMAS (VALIDACIÓN)
@@ -150,6 +151,8 @@ export interface ValidacionIngresoResponse {
 	visitante?: VisitanteResponse;
 	tieneIngresoAbierto: boolean;
 	ingresoAbierto?: IngresoResponse;
+	posiblesCoincidencias?: CoincidenciaListaNegra[];
+	requiereConfirmacion?: boolean;
 }
 
 export interface AlertaGafeteResponse {
@@ -75,6 +75,23 @@ export interface ListaNegraResponse {
 	updatedAt: string;
 }
 
+/** Bloqueo de otra cédula con un nombre parecido; el guardia confirma si es la misma persona */
+export interface CoincidenciaListaNegra {
+	id: string;
+	cedula: string;
+	nombreCompleto: string;
+	nivelSeveridad: NivelSeveridad;
+	motivoBloqueo?: string;
+	score: number;
+}
+
+/** Resultado de un cambio: aplicado, o propuesta pendiente de aprobación (doble control) */
+export interface CambioListaNegraResponse {
+	aplicado: boolean;
+	registro?: ListaNegraResponse;
+	propuesta?: { id: string; tipo: string; cedula: string; estado: string };
+}
+
 export interface ListaNegraListResponse {
 	bloqueados: ListaNegraResponse[];
 	total: number;